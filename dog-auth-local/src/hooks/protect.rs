@@ -168,6 +168,17 @@ where
             HookResult::Many(vs) => {
                 HookResult::Many(vs.into_iter().map(|v| self.strip_one(v)).collect())
             }
+            HookResult::Page {
+                data,
+                total,
+                limit,
+                skip,
+            } => HookResult::Page {
+                data: data.into_iter().map(|v| self.strip_one(v)).collect(),
+                total,
+                limit,
+                skip,
+            },
         });
 
         Ok(())
@@ -4,11 +4,12 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use async_trait::async_trait;
-use bcrypt::{hash, verify};
 use dog_auth::core::{
     AuthenticationBase, AuthenticationParams, AuthenticationRequest, AuthenticationResult,
     AuthenticationStrategy,
 };
+use dog_auth::hooks::event::{LoginFailure, LoginFailureReason};
+use dog_auth::password;
 use dog_core::errors::DogError;
 use dog_core::HookContext;
 use serde_json::{json, Map, Value};
@@ -41,7 +42,6 @@ pub struct LocalStrategyOptions {
     pub entity_password_field: String,
 
     pub error_message: String,
-    pub hash_size: u32,
 }
 
 impl Default for LocalStrategyOptions {
@@ -52,7 +52,6 @@ impl Default for LocalStrategyOptions {
             entity_username_field: "email".to_string(),
             entity_password_field: "password".to_string(),
             error_message: "Invalid login".to_string(),
-            hash_size: 10,
         }
     }
 }
@@ -128,8 +127,11 @@ where
         Ok(())
     }
 
+    /// Hash `password` on the blocking thread pool, as argon2id is too slow
+    /// to run on an async worker thread.
     pub async fn hash_password(&self, password: &str) -> Result<String> {
-        hash(password, self.options.hash_size).map_err(|e| anyhow::anyhow!(e.to_string()))
+        let password = password.to_string();
+        Ok(tokio::task::spawn_blocking(move || password::hash_password(&password)).await?)
     }
 
     fn get_required_str(
@@ -174,7 +176,9 @@ where
             return Ok(None);
         }
 
-        let svc = ctx.services.service(service_name)?;
+        // The service itself, not its hooks: entity lookups must see fields
+        // that after hooks hide from clients.
+        let svc = ctx.services.service(service_name)?.inner().clone();
 
         // If a query builder is provided, allow the app/adaptor to inject an efficient query/limit
         // into the params type (e.g. for Mongo/Postgres adapters).
@@ -209,9 +213,11 @@ where
             return Err(DogError::not_authenticated(&self.options.error_message).into_anyhow());
         };
 
-        let ok = verify(password, hash_val)
-            .map_err(|e| DogError::not_authenticated(e.to_string()).into_anyhow())?;
-        if !ok {
+        let (password, hash_val) = (password.to_string(), hash_val.to_string());
+        let matches =
+            tokio::task::spawn_blocking(move || password::verify_password(&password, &hash_val))
+                .await?;
+        if !matches {
             return Err(DogError::not_authenticated(&self.options.error_message).into_anyhow());
         }
         Ok(())
@@ -247,6 +253,10 @@ where
             &self.options.error_message,
         )?;
 
+        // Throttle per strategy and username, whether or not the user exists.
+        let identity = format!("{}:{}", self.name, username);
+        auth.check_login_allowed(&identity).await?;
+
         let entity = if let Some(resolver) = self.entity_resolver.as_ref() {
             resolver.resolve_entity(&username, ctx).await?
         } else {
@@ -255,9 +265,27 @@ where
                     .into_anyhow()
             })?;
             self.find_entity(ctx, &service_name, &username).await?
-        }
-        .ok_or_else(|| DogError::not_authenticated(&self.options.error_message).into_anyhow())?;
-        self.compare_password(&entity, &password).await?;
+        };
+        // Clients get the same error either way; the reason is for auth events.
+        let failed =
+            |reason| LoginFailure::new(reason, &identity).into_error(&self.options.error_message);
+        let checked = match entity {
+            Some(entity) => match self.compare_password(&entity, &password).await {
+                Ok(()) => Ok(entity),
+                Err(_) => Err(failed(LoginFailureReason::BadPassword)),
+            },
+            None => Err(failed(LoginFailureReason::UnknownUser)),
+        };
+        let entity = match checked {
+            Ok(entity) => {
+                auth.record_login_success(&identity).await?;
+                entity
+            }
+            Err(err) => {
+                auth.record_login_failure(&identity).await?;
+                return Err(err);
+            }
+        };
 
         let entity = Self::strip_password(entity, &self.options.entity_password_field);
 
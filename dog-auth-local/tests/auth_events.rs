@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use dog_auth::hooks::event::{AuthEvent, AuthEventKind, LoginFailureReason};
+use dog_auth::hooks::AuthParams;
+use dog_auth::password::hash_password;
+use dog_auth::{AuthOptions, AuthenticationService, JwtOptions, JwtStrategy, LoginThrottleOptions};
+use dog_auth_local::{LocalEntityResolver, LocalStrategy};
+use dog_core::errors::DogError;
+use dog_core::events::{EventListener, ServiceEventData};
+use dog_core::tenant::TenantContext;
+use dog_core::{DogApp, DogAppBuilder, DogService, HookContext};
+use serde_json::{json, Value};
+
+type Params = AuthParams<()>;
+
+struct Users;
+
+#[async_trait]
+impl DogService<Value, Params> for Users {
+    async fn get(&self, _ctx: &TenantContext, id: &str, _params: Params) -> anyhow::Result<Value> {
+        Ok(json!({ "id": id, "email": "alice@example.com" }))
+    }
+}
+
+struct OneUser {
+    password_hash: String,
+}
+
+#[async_trait]
+impl LocalEntityResolver<Params> for OneUser {
+    async fn resolve_entity(
+        &self,
+        username: &str,
+        _ctx: &mut HookContext<Value, Params>,
+    ) -> anyhow::Result<Option<Value>> {
+        Ok((username == "alice@example.com")
+            .then(|| json!({ "id": "user-1", "email": username, "password": self.password_hash })))
+    }
+}
+
+fn app(
+    throttle: Option<LoginThrottleOptions>,
+) -> (DogApp<Value, Params>, Arc<Mutex<Vec<AuthEvent>>>) {
+    let mut builder = DogAppBuilder::new();
+    let options = AuthOptions {
+        entity: Some("user".to_string()),
+        service: Some("users".to_string()),
+        jwt: JwtOptions {
+            secret: Some("test-secret".to_string()),
+            ..JwtOptions::default()
+        },
+        login_throttle: throttle,
+        ..AuthOptions::default()
+    };
+    let mut auth = AuthenticationService::builder(&mut builder, Some(options)).unwrap();
+    auth.register("jwt", Arc::new(JwtStrategy::new()));
+    auth.register(
+        "local",
+        Arc::new(LocalStrategy::new().with_entity_resolver(Arc::new(OneUser {
+            password_hash: hash_password("correct horse"),
+        }))),
+    );
+    let auth = Arc::new(AuthenticationService::new(Arc::new(auth.build())));
+    let adapter = AuthenticationService::install(&mut builder, auth);
+    builder.register_service("users", Arc::new(Users));
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&events);
+    let listener: EventListener<Value, Params> = Arc::new(move |data, _ctx| {
+        if let ServiceEventData::Custom(event) = data {
+            if let Some(event) = event.downcast_ref::<AuthEvent>() {
+                sink.lock().unwrap().push(event.clone());
+            }
+        }
+        Box::pin(async { Ok(()) })
+    });
+    builder.on_str("authentication *", listener).unwrap();
+
+    let app = builder.build();
+    adapter.setup(app.clone());
+    (app, events)
+}
+
+fn external() -> Params {
+    Params {
+        provider: Some("rest".to_string()),
+        ..Params::default()
+    }
+}
+
+async fn login(app: &DogApp<Value, Params>, email: &str, password: &str) -> anyhow::Result<Value> {
+    app.service("authentication")?
+        .create(
+            TenantContext::new("acme"),
+            json!({ "strategy": "local", "email": email, "password": password }),
+            external(),
+        )
+        .await
+}
+
+fn failure(reason: LoginFailureReason) -> AuthEvent {
+    AuthEvent {
+        kind: AuthEventKind::LoginFailed,
+        tenant: "acme".to_string(),
+        identity: Some("local:alice@example.com".to_string()),
+        strategy: Some("local".to_string()),
+        reason: Some(reason),
+    }
+}
+
+#[tokio::test]
+async fn failed_login_emits_one_login_failed_event() {
+    let (app, events) = app(None);
+
+    let err = login(&app, "alice@example.com", "wrong").await.unwrap_err();
+    assert_eq!(
+        DogError::from_anyhow(&err).unwrap().message,
+        "Invalid login"
+    );
+
+    assert_eq!(
+        *events.lock().unwrap(),
+        [failure(LoginFailureReason::BadPassword)]
+    );
+}
+
+#[tokio::test]
+async fn failure_reasons_are_told_apart_in_events_only() {
+    let (app, events) = app(Some(LoginThrottleOptions {
+        max_attempts: 2,
+        ..LoginThrottleOptions::default()
+    }));
+
+    let unknown = login(&app, "mallory@example.com", "wrong")
+        .await
+        .unwrap_err();
+    let bad = login(&app, "alice@example.com", "wrong").await.unwrap_err();
+    assert_eq!(unknown.to_string(), bad.to_string());
+
+    login(&app, "alice@example.com", "wrong").await.unwrap_err();
+    login(&app, "alice@example.com", "correct horse")
+        .await
+        .unwrap_err();
+
+    let reasons: Vec<_> = events.lock().unwrap().iter().map(|e| e.reason).collect();
+    assert_eq!(
+        reasons,
+        [
+            Some(LoginFailureReason::UnknownUser),
+            Some(LoginFailureReason::BadPassword),
+            Some(LoginFailureReason::BadPassword),
+            Some(LoginFailureReason::Locked),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn login_refresh_and_logout_emit_events_with_the_identity() {
+    let (app, events) = app(None);
+    let auth = app.service("authentication").unwrap();
+
+    let result = login(&app, "alice@example.com", "correct horse")
+        .await
+        .unwrap();
+    auth.create(
+        TenantContext::new("acme"),
+        json!({ "strategy": "refresh", "refreshToken": result["refreshToken"] }),
+        external(),
+    )
+    .await
+    .unwrap();
+    let token = result["accessToken"].as_str().unwrap();
+    auth.remove(
+        TenantContext::new("acme"),
+        None,
+        Params {
+            headers: HashMap::from([("authorization".to_string(), format!("Bearer {token}"))]),
+            ..external()
+        },
+    )
+    .await
+    .unwrap();
+
+    let events = events.lock().unwrap();
+    let kinds: Vec<_> = events.iter().map(|e| e.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            AuthEventKind::Login,
+            AuthEventKind::TokenRefreshed,
+            AuthEventKind::Logout
+        ]
+    );
+    for event in events.iter() {
+        assert_eq!(event.tenant, "acme");
+        assert_eq!(event.identity.as_deref(), Some("user-1"));
+        assert_eq!(event.reason, None);
+    }
+}
+
+#[tokio::test]
+async fn reauthenticating_with_a_jwt_is_not_a_login() {
+    let (app, events) = app(None);
+
+    let result = login(&app, "alice@example.com", "correct horse")
+        .await
+        .unwrap();
+    let reauth = app
+        .service("authentication")
+        .unwrap()
+        .create(
+            TenantContext::new("acme"),
+            json!({ "strategy": "jwt", "accessToken": result["accessToken"] }),
+            external(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(reauth["accessToken"], result["accessToken"]);
+
+    let kinds: Vec<_> = events.lock().unwrap().iter().map(|e| e.kind).collect();
+    assert_eq!(kinds, [AuthEventKind::Login]);
+}
@@ -0,0 +1,194 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use dog_auth::password::hash_password;
+use dog_auth::{
+    AttemptStore, AuthOptions, AuthenticationParams, AuthenticationRequest, AuthenticationService,
+    LoginAttempts, LoginThrottleOptions, MemoryAttemptStore,
+};
+use dog_auth_local::{LocalEntityResolver, LocalStrategy};
+use dog_core::errors::{DogError, ErrorKind};
+use dog_core::tenant::TenantContext;
+use dog_core::{DogApp, DogAppBuilder, HookContext, ServiceCaller, ServiceMethodKind};
+use serde_json::{json, Value};
+
+struct OneUser {
+    password_hash: String,
+}
+
+#[async_trait]
+impl LocalEntityResolver<()> for OneUser {
+    async fn resolve_entity(
+        &self,
+        username: &str,
+        _ctx: &mut HookContext<Value, ()>,
+    ) -> anyhow::Result<Option<Value>> {
+        Ok((username == "alice@example.com")
+            .then(|| json!({ "id": "1", "email": username, "password": self.password_hash })))
+    }
+}
+
+/// Memory store that yields before every call, so concurrent logins
+/// interleave between reading and writing their attempts.
+#[derive(Default)]
+struct Yielding(MemoryAttemptStore);
+
+#[async_trait]
+impl AttemptStore for Yielding {
+    async fn get(&self, key: &str) -> anyhow::Result<Option<LoginAttempts>> {
+        tokio::task::yield_now().await;
+        self.0.get(key).await
+    }
+
+    async fn increment(&self, key: &str, ttl: Duration) -> anyhow::Result<LoginAttempts> {
+        tokio::task::yield_now().await;
+        self.0.increment(key, ttl).await
+    }
+
+    async fn put(&self, key: &str, attempts: LoginAttempts, ttl: Duration) -> anyhow::Result<()> {
+        tokio::task::yield_now().await;
+        self.0.put(key, attempts, ttl).await
+    }
+
+    async fn clear(&self, key: &str) -> anyhow::Result<()> {
+        tokio::task::yield_now().await;
+        self.0.clear(key).await
+    }
+}
+
+fn auth_service() -> (DogApp<Value, ()>, AuthenticationService<()>) {
+    auth_service_with(None)
+}
+
+fn auth_service_with(
+    attempts: Option<Arc<dyn AttemptStore>>,
+) -> (DogApp<Value, ()>, AuthenticationService<()>) {
+    let mut builder = DogAppBuilder::new();
+    let options = AuthOptions {
+        login_throttle: Some(LoginThrottleOptions::default()),
+        ..AuthOptions::default()
+    };
+    let mut auth = AuthenticationService::builder(&mut builder, Some(options)).unwrap();
+    let strategy = LocalStrategy::new().with_entity_resolver(Arc::new(OneUser {
+        password_hash: hash_password("correct horse"),
+    }));
+    auth.register("local", Arc::new(strategy));
+    if let Some(attempts) = attempts {
+        auth.attempt_store(attempts);
+    }
+    let auth = AuthenticationService::new(Arc::new(auth.build()));
+    (builder.build(), auth)
+}
+
+async fn login(
+    app: &DogApp<Value, ()>,
+    auth: &AuthenticationService<()>,
+    password: &str,
+) -> Result<Value, Option<ErrorKind>> {
+    let request: AuthenticationRequest = serde_json::from_value(json!({
+        "strategy": "local",
+        "email": "alice@example.com",
+        "password": password,
+    }))
+    .unwrap();
+    let mut ctx = HookContext::new(
+        TenantContext::new("default"),
+        ServiceMethodKind::Create,
+        (),
+        ServiceCaller::new(app.clone()),
+        app.config_snapshot(),
+    );
+    auth.authenticate(
+        &request,
+        &AuthenticationParams::default(),
+        &mut ctx,
+        &["local".to_string()],
+    )
+    .await
+    .map_err(|err| DogError::from_anyhow(&err).map(|e| e.kind))
+}
+
+#[tokio::test]
+async fn sixth_failed_attempt_is_locked_out() {
+    let (app, auth) = auth_service();
+
+    for _ in 0..5 {
+        let err = login(&app, &auth, "wrong").await.unwrap_err();
+        assert_eq!(err, Some(ErrorKind::NotAuthenticated));
+    }
+
+    let err = login(&app, &auth, "wrong").await.unwrap_err();
+    assert_eq!(err, Some(ErrorKind::TooManyRequests));
+
+    // The right password does not get around the lockout.
+    let err = login(&app, &auth, "correct horse").await.unwrap_err();
+    assert_eq!(err, Some(ErrorKind::TooManyRequests));
+}
+
+#[tokio::test]
+async fn concurrent_failures_are_all_counted() {
+    let (app, auth) = auth_service_with(Some(Arc::new(Yielding::default())));
+
+    let burst = tokio::join!(
+        login(&app, &auth, "wrong"),
+        login(&app, &auth, "wrong"),
+        login(&app, &auth, "wrong"),
+        login(&app, &auth, "wrong"),
+        login(&app, &auth, "wrong"),
+    );
+    for result in [burst.0, burst.1, burst.2, burst.3, burst.4] {
+        assert_eq!(result.unwrap_err(), Some(ErrorKind::NotAuthenticated));
+    }
+
+    let err = login(&app, &auth, "correct horse").await.unwrap_err();
+    assert_eq!(err, Some(ErrorKind::TooManyRequests));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn increment_is_atomic_across_threads() {
+    let store = Arc::new(MemoryAttemptStore::new());
+    let tasks: Vec<_> = (0..50)
+        .map(|_| {
+            let store = store.clone();
+            tokio::spawn(async move {
+                store
+                    .increment("local:alice@example.com", Duration::from_secs(60))
+                    .await
+                    .unwrap()
+                    .failures
+            })
+        })
+        .collect();
+    let mut counts = Vec::new();
+    for task in tasks {
+        counts.push(task.await.unwrap());
+    }
+    counts.sort();
+    assert_eq!(counts, (1..=50).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn successful_login_resets_the_count() {
+    let (app, auth) = auth_service();
+
+    for _ in 0..4 {
+        login(&app, &auth, "wrong").await.unwrap_err();
+    }
+    let result = login(&app, &auth, "correct horse").await.unwrap();
+    assert_eq!(result["user"]["email"], "alice@example.com");
+    assert!(result["user"].get("password").is_none());
+
+    for _ in 0..4 {
+        let err = login(&app, &auth, "wrong").await.unwrap_err();
+        assert_eq!(err, Some(ErrorKind::NotAuthenticated));
+    }
+    login(&app, &auth, "correct horse").await.unwrap();
+}
+
+#[test]
+fn lockouts_double_up_to_the_cap() {
+    let throttle = LoginThrottleOptions::default();
+    let minutes = |n| throttle.lockout_for(n).as_secs() / 60;
+    assert_eq!([1, 2, 3, 4, 7, 8].map(minutes), [1, 2, 4, 8, 60, 60]);
+}
@@ -0,0 +1,258 @@
+// GitHub OAuth provider.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use dog_auth::core::AuthenticationBuilder;
+use dog_core::HookContext;
+use serde_json::Value;
+
+use crate::oauth2_client::{
+    OAuth2Authorization, OAuth2AuthorizationCodeProvider, OAuth2ClientConfig,
+};
+use crate::pkce::PkceVerifierStore;
+use crate::strategy::{OAuthEntityResolver, OAuthProvider, OAuthStrategy, OAuthStrategyOptions};
+use crate::tokens::ProviderTokens;
+
+pub const GITHUB_AUTH_URL: &str = "https://github.com/login/oauth/authorize";
+pub const GITHUB_TOKEN_URL: &str = "https://github.com/login/oauth/access_token";
+pub const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// GitHub OAuth app settings. The URLs default to github.com; point them at
+/// a GitHub Enterprise host if needed.
+#[derive(Clone, Debug)]
+pub struct GitHubOAuthConfig {
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_uri: String,
+    pub scopes: Vec<String>,
+    pub auth_url: String,
+    pub token_url: String,
+    pub api_url: String,
+}
+
+impl GitHubOAuthConfig {
+    pub fn new(
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        redirect_uri: impl Into<String>,
+    ) -> Self {
+        Self {
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            redirect_uri: redirect_uri.into(),
+            scopes: vec!["read:user".to_string(), "user:email".to_string()],
+            auth_url: GITHUB_AUTH_URL.to_string(),
+            token_url: GITHUB_TOKEN_URL.to_string(),
+            api_url: GITHUB_API_URL.to_string(),
+        }
+    }
+
+    /// Read `oauth.github.client_id`, `oauth.github.client_secret` and
+    /// `oauth.github.redirect_uri`.
+    pub fn from_config(config: &dog_core::DogConfigSnapshot) -> Result<Self> {
+        let get = |key: &str| {
+            config
+                .get_string(&format!("oauth.github.{key}"))
+                .ok_or_else(|| anyhow::anyhow!("Missing oauth.github.{key}"))
+        };
+        Ok(Self::new(
+            get("client_id")?,
+            get("client_secret")?,
+            get("redirect_uri")?,
+        ))
+    }
+}
+
+/// Authorization-code provider for GitHub.
+///
+/// The profile is GitHub's `/user` response, plus `sub` (the numeric user id
+/// as a string, like an OpenID Connect subject) and `email`: users who keep
+/// their address private have none in `/user`, so it is then taken from
+/// the primary verified address in `/user/emails`.
+pub struct GitHubOAuthProvider<P>
+where
+    P: Clone + Send + Sync + 'static,
+{
+    oauth: OAuth2AuthorizationCodeProvider<P>,
+    api_url: String,
+    http: reqwest::Client,
+}
+
+impl<P> GitHubOAuthProvider<P>
+where
+    P: Clone + Send + Sync + 'static,
+{
+    pub fn new(config: GitHubOAuthConfig) -> Result<Self> {
+        Self::with_name("github", config)
+    }
+
+    pub fn with_name(name: impl Into<String>, config: GitHubOAuthConfig) -> Result<Self> {
+        let oauth = OAuth2AuthorizationCodeProvider::new(OAuth2ClientConfig {
+            name: name.into(),
+            client_id: config.client_id,
+            client_secret: config.client_secret,
+            auth_url: config.auth_url,
+            token_url: config.token_url,
+            redirect_uri: config.redirect_uri,
+            scopes: config.scopes,
+            user_info_url: None,
+        })?;
+        // GitHub's API rejects requests without a User-Agent.
+        let http = reqwest::Client::builder()
+            .user_agent(concat!("dog-auth-oauth/", env!("CARGO_PKG_VERSION")))
+            .build()?;
+
+        Ok(Self {
+            oauth,
+            api_url: config.api_url.trim_end_matches('/').to_string(),
+            http,
+        })
+    }
+
+    /// See [`OAuth2AuthorizationCodeProvider::with_pkce`].
+    pub fn with_pkce(mut self, store: Arc<dyn PkceVerifierStore>) -> Self {
+        self.oauth = self.oauth.with_pkce(store);
+        self
+    }
+
+    pub fn authorize_url(&self) -> String {
+        self.oauth.authorize_url()
+    }
+
+    pub async fn authorization(&self) -> Result<OAuth2Authorization> {
+        self.oauth.authorization().await
+    }
+
+    async fn get(&self, path: &str, access_token: &str) -> Result<Value> {
+        Ok(self
+            .http
+            .get(format!("{}{path}", self.api_url))
+            .bearer_auth(access_token)
+            .header("Accept", "application/vnd.github+json")
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Value>()
+            .await?)
+    }
+
+    async fn primary_email(&self, access_token: &str) -> Result<Option<String>> {
+        let emails = self.get("/user/emails", access_token).await?;
+        let emails = emails.as_array().map(Vec::as_slice).unwrap_or_default();
+        let verified = |e: &&Value| e.get("verified").and_then(Value::as_bool) == Some(true);
+        let primary = |e: &&Value| e.get("primary").and_then(Value::as_bool) == Some(true);
+
+        Ok(emails
+            .iter()
+            .filter(verified)
+            .find(primary)
+            .or_else(|| emails.iter().find(verified))
+            .and_then(|e| e.get("email"))
+            .and_then(Value::as_str)
+            .map(str::to_string))
+    }
+}
+
+#[async_trait]
+impl<P> OAuthProvider<P> for GitHubOAuthProvider<P>
+where
+    P: Clone + Send + Sync + 'static,
+{
+    fn name(&self) -> &str {
+        self.oauth.name()
+    }
+
+    async fn exchange_code(&self, code: &str, ctx: &mut HookContext<Value, P>) -> Result<String> {
+        self.oauth.exchange_code(code, ctx).await
+    }
+
+    async fn exchange_code_with_state(
+        &self,
+        code: &str,
+        state: Option<&str>,
+        ctx: &mut HookContext<Value, P>,
+    ) -> Result<String> {
+        self.oauth.exchange_code_with_state(code, state, ctx).await
+    }
+
+    async fn exchange_code_for_tokens(
+        &self,
+        code: &str,
+        state: Option<&str>,
+        ctx: &mut HookContext<Value, P>,
+    ) -> Result<ProviderTokens> {
+        self.oauth.exchange_code_for_tokens(code, state, ctx).await
+    }
+
+    async fn refresh_token(
+        &self,
+        refresh_token: &str,
+        ctx: &mut HookContext<Value, P>,
+    ) -> Result<ProviderTokens> {
+        self.oauth.refresh_token(refresh_token, ctx).await
+    }
+
+    async fn fetch_profile(
+        &self,
+        access_token: &str,
+        _ctx: &mut HookContext<Value, P>,
+    ) -> Result<Option<Value>> {
+        let Value::Object(mut profile) = self.get("/user", access_token).await? else {
+            return Ok(None);
+        };
+
+        let id = match profile.get("id") {
+            Some(Value::Number(n)) => n.to_string(),
+            Some(Value::String(s)) => s.clone(),
+            _ => return Ok(None),
+        };
+        profile.insert("sub".to_string(), Value::String(id));
+
+        let has_email = profile
+            .get("email")
+            .and_then(Value::as_str)
+            .is_some_and(|e| !e.is_empty());
+        if !has_email {
+            let email = self.primary_email(access_token).await?;
+            profile.insert(
+                "email".to_string(),
+                email.map_or(Value::Null, Value::String),
+            );
+        }
+
+        Ok(Some(Value::Object(profile)))
+    }
+}
+
+/// Register an `OAuthStrategy` named `github` with a [`GitHubOAuthProvider`]
+/// configured from `oauth.github.*`, and return the authorize URL to send
+/// users to.
+///
+/// Complete the login with `OAuthService::authenticate_callback("github",
+/// { "code": ... }, ..)`. Without an `entity_resolver`, users are linked via
+/// the `githubId` field of the configured authentication service.
+pub fn register_github_oauth<P>(
+    builder: &mut dog_core::DogAppBuilder<Value, P>,
+    auth: &mut AuthenticationBuilder<P>,
+    entity_resolver: Option<Arc<dyn OAuthEntityResolver<P>>>,
+) -> Result<String>
+where
+    P: Clone + Send + Sync + 'static,
+{
+    let config = GitHubOAuthConfig::from_config(&builder.config_snapshot())?;
+    let provider = Arc::new(GitHubOAuthProvider::new(config)?);
+    let authorize_url = provider.authorize_url();
+
+    let mut opts = OAuthStrategyOptions {
+        default_provider: Some("github".to_string()),
+        entity_resolver,
+        ..Default::default()
+    };
+    opts.providers.insert("github".to_string(), provider);
+
+    let strategy = OAuthStrategy::new().with_name("github").with_options(opts);
+    auth.register("github", Arc::new(strategy));
+    Ok(authorize_url)
+}
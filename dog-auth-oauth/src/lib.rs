@@ -1,11 +1,23 @@
+pub mod pkce;
 pub mod service;
 pub mod strategy;
+pub mod tokens;
 
+#[cfg(feature = "oauth2-client")]
+pub mod github;
 #[cfg(feature = "oauth2-client")]
 pub mod oauth2_client;
+#[cfg(feature = "oauth2-client")]
+pub mod oidc;
 
+pub use pkce::*;
 pub use service::*;
 pub use strategy::*;
+pub use tokens::*;
 
+#[cfg(feature = "oauth2-client")]
+pub use github::*;
 #[cfg(feature = "oauth2-client")]
 pub use oauth2_client::*;
+#[cfg(feature = "oauth2-client")]
+pub use oidc::*;
@@ -1,24 +1,44 @@
 use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use async_trait::async_trait;
+use dog_core::errors::DogError;
 use dog_core::HookContext;
-use oauth2::basic::BasicClient;
+use oauth2::basic::{
+    BasicErrorResponse, BasicRevocationErrorResponse, BasicTokenIntrospectionResponse,
+    BasicTokenType,
+};
 use oauth2::{
-    AuthUrl, AuthorizationCode, ClientId, ClientSecret, CsrfToken, EndpointNotSet, EndpointSet,
-    RedirectUrl, Scope, TokenResponse, TokenUrl,
+    AuthUrl, AuthorizationCode, Client, ClientId, ClientSecret, CsrfToken, EndpointNotSet,
+    EndpointSet, ExtraTokenFields, PkceCodeChallenge, PkceCodeVerifier, RedirectUrl, RefreshToken,
+    Scope, StandardRevocableToken, StandardTokenResponse, TokenResponse, TokenUrl,
 };
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::pkce::PkceVerifierStore;
 use crate::strategy::OAuthProvider;
+use crate::tokens::ProviderTokens;
+
+/// How long a login may take between the authorize redirect and the
+/// callback when PKCE is enabled.
+pub const DEFAULT_PKCE_TTL: Duration = Duration::from_secs(10 * 60);
 
 // ---------------------------------------------------------------------------
 // Type alias for the configured client with both auth_uri AND token_uri set.
 // oauth2 5.x uses type-state generics: each endpoint tracks Set/NotSet.
-// Parameters (in order): HasAuthUrl, HasDeviceAuthUrl, HasIntrospectionUrl,
-//                         HasRevocationUrl, HasTokenUrl
+// This is `BasicClient` except that token responses keep the OpenID Connect
+// `id_token`. Endpoint parameters (in order): HasAuthUrl, HasDeviceAuthUrl,
+// HasIntrospectionUrl, HasRevocationUrl, HasTokenUrl
 // ---------------------------------------------------------------------------
-type ConfiguredBasicClient = BasicClient<
+type ConfiguredClient = Client<
+    BasicErrorResponse,
+    StandardTokenResponse<IdTokenFields, BasicTokenType>,
+    BasicTokenIntrospectionResponse,
+    StandardRevocableToken,
+    BasicRevocationErrorResponse,
     EndpointSet,    // HasAuthUrl    — set via .set_auth_uri()
     EndpointNotSet, // HasDeviceAuthUrl
     EndpointNotSet, // HasIntrospectionUrl
@@ -26,15 +46,24 @@ type ConfiguredBasicClient = BasicClient<
     EndpointSet,    // HasTokenUrl   — set via .set_token_uri()
 >;
 
-pub struct OAuth2ClientConfig {
-    pub name: String,
-    pub client_id: String,
-    pub client_secret: String,
-    pub auth_url: String,
-    pub token_url: String,
-    pub redirect_uri: String,
-    pub scopes: Vec<String>,
-    pub userinfo_url: Option<String>,
+/// Extra token response field of OpenID Connect providers.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct IdTokenFields {
+    id_token: Option<String>,
+}
+
+impl ExtraTokenFields for IdTokenFields {}
+
+/// Client settings for [`OAuth2AuthorizationCodeProvider`], the same struct
+/// as the entries of `AuthOptions::oauth_providers`.
+pub type OAuth2ClientConfig = dog_auth::OAuthProvider;
+
+/// Where to send the user to log in, and the `state` the provider will
+/// echo back on the callback.
+#[derive(Clone, Debug)]
+pub struct OAuth2Authorization {
+    pub url: String,
+    pub state: String,
 }
 
 pub struct OAuth2AuthorizationCodeProvider<P>
@@ -42,9 +71,11 @@ where
     P: Clone + Send + Sync + 'static,
 {
     name: String,
-    client: ConfiguredBasicClient,
+    client: ConfiguredClient,
     scopes: Vec<String>,
     userinfo_url: Option<String>,
+    pkce: Option<Arc<dyn PkceVerifierStore>>,
+    pkce_ttl: Duration,
     _marker: PhantomData<fn() -> P>,
 }
 
@@ -53,9 +84,9 @@ where
     P: Clone + Send + Sync + 'static,
 {
     pub fn new(config: OAuth2ClientConfig) -> Result<Self> {
-        // oauth2 5.x: BasicClient::new() takes only ClientId; other fields via builders.
-        // Each set_* call changes the type-state, giving us ConfiguredBasicClient.
-        let client = BasicClient::new(ClientId::new(config.client_id))
+        // oauth2 5.x: Client::new() takes only ClientId; other fields via builders.
+        // Each set_* call changes the type-state, giving us ConfiguredClient.
+        let client = Client::new(ClientId::new(config.client_id))
             .set_client_secret(ClientSecret::new(config.client_secret))
             .set_auth_uri(AuthUrl::new(config.auth_url)?)
             .set_token_uri(TokenUrl::new(config.token_url)?)
@@ -65,20 +96,64 @@ where
             name: config.name,
             client,
             scopes: config.scopes,
-            userinfo_url: config.userinfo_url,
+            userinfo_url: config.user_info_url,
+            pkce: None,
+            pkce_ttl: DEFAULT_PKCE_TTL,
             _marker: PhantomData,
         })
     }
 
+    /// Use PKCE (S256): [`authorization`](Self::authorization) stores a
+    /// code verifier in `store` against the `state`, and the code exchange
+    /// sends it. Callbacks must then carry the `state`.
+    pub fn with_pkce(mut self, store: Arc<dyn PkceVerifierStore>) -> Self {
+        self.pkce = Some(store);
+        self
+    }
+
+    /// How long a PKCE verifier is kept. Defaults to [`DEFAULT_PKCE_TTL`].
+    pub fn with_pkce_ttl(mut self, ttl: Duration) -> Self {
+        self.pkce_ttl = ttl;
+        self
+    }
+
+    /// Authorize URL without PKCE; the `state` in it is not kept.
     pub fn authorize_url(&self) -> String {
+        let (url, _csrf) = self.authorization_request().url();
+        url.to_string()
+    }
+
+    /// Authorize URL with a fresh `state`, plus a PKCE challenge if enabled.
+    pub async fn authorization(&self) -> Result<OAuth2Authorization> {
+        let mut req = self.authorization_request();
+        let mut verifier = None;
+        if self.pkce.is_some() {
+            let (challenge, v) = PkceCodeChallenge::new_random_sha256();
+            req = req.set_pkce_challenge(challenge);
+            verifier = Some(v);
+        }
+        let (url, csrf) = req.url();
+
+        if let (Some(store), Some(verifier)) = (self.pkce.as_ref(), verifier) {
+            store
+                .put(csrf.secret(), verifier.secret(), self.pkce_ttl)
+                .await?;
+        }
+
+        Ok(OAuth2Authorization {
+            url: url.to_string(),
+            state: csrf.secret().to_string(),
+        })
+    }
+
+    fn authorization_request(&self) -> oauth2::AuthorizationRequest<'_> {
         // oauth2 5.x with EndpointSet: authorize_url() returns AuthorizationRequest directly
         // (infallible — no Result). Use .url() to extract the (Url, CsrfToken) pair.
         let mut req = self.client.authorize_url(CsrfToken::new_random);
         for s in &self.scopes {
             req = req.add_scope(Scope::new(s.clone()));
         }
-        let (url, _csrf) = req.url();
-        url.to_string()
+        req
     }
 }
 
@@ -91,17 +166,64 @@ where
         &self.name
     }
 
-    async fn exchange_code(&self, code: &str, _ctx: &mut HookContext<Value, P>) -> Result<String> {
+    async fn exchange_code(&self, code: &str, ctx: &mut HookContext<Value, P>) -> Result<String> {
+        self.exchange_code_with_state(code, None, ctx).await
+    }
+
+    async fn exchange_code_with_state(
+        &self,
+        code: &str,
+        state: Option<&str>,
+        ctx: &mut HookContext<Value, P>,
+    ) -> Result<String> {
+        Ok(self
+            .exchange_code_for_tokens(code, state, ctx)
+            .await?
+            .access_token)
+    }
+
+    async fn exchange_code_for_tokens(
+        &self,
+        code: &str,
+        state: Option<&str>,
+        _ctx: &mut HookContext<Value, P>,
+    ) -> Result<ProviderTokens> {
         // oauth2 5.x with EndpointSet: exchange_code() returns CodeTokenRequest (not Result).
         // request_async takes a &reqwest::Client (implements AsyncHttpClient).
+        let mut req = self
+            .client
+            .exchange_code(AuthorizationCode::new(code.to_string()));
+
+        if let Some(store) = self.pkce.as_ref() {
+            let state = state
+                .filter(|s| !s.is_empty())
+                .ok_or_else(|| DogError::bad_request("Missing OAuth state").into_anyhow())?;
+            let verifier = store.take(state).await?.ok_or_else(|| {
+                DogError::bad_request("OAuth state is unknown or expired").into_anyhow()
+            })?;
+            req = req.set_pkce_verifier(PkceCodeVerifier::new(verifier));
+        }
+
+        let http_client = reqwest::Client::new();
+        let token = req.request_async(&http_client).await?;
+
+        Ok(provider_tokens(&token))
+    }
+
+    async fn refresh_token(
+        &self,
+        refresh_token: &str,
+        _ctx: &mut HookContext<Value, P>,
+    ) -> Result<ProviderTokens> {
+        let refresh_token = RefreshToken::new(refresh_token.to_string());
         let http_client = reqwest::Client::new();
         let token = self
             .client
-            .exchange_code(AuthorizationCode::new(code.to_string()))
+            .exchange_refresh_token(&refresh_token)
             .request_async(&http_client)
             .await?;
 
-        Ok(token.access_token().secret().to_string())
+        Ok(provider_tokens(&token))
     }
 
     async fn fetch_profile(
@@ -126,3 +248,13 @@ where
         Ok(Some(profile))
     }
 }
+
+fn provider_tokens(token: &StandardTokenResponse<IdTokenFields, BasicTokenType>) -> ProviderTokens {
+    let mut tokens = ProviderTokens::new(token.access_token().secret());
+    tokens.refresh_token = token.refresh_token().map(|t| t.secret().to_string());
+    tokens.id_token = token.extra_fields().id_token.clone();
+    match token.expires_in() {
+        Some(expires_in) => tokens.expires_in(expires_in),
+        None => tokens,
+    }
+}
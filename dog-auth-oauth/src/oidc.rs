@@ -0,0 +1,444 @@
+// OpenID Connect provider.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use dog_auth::core::AuthenticationBuilder;
+use dog_core::errors::DogError;
+use dog_core::HookContext;
+use jsonwebtoken::jwk::{Jwk, JwkSet};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::oauth2_client::{
+    OAuth2Authorization, OAuth2AuthorizationCodeProvider, OAuth2ClientConfig,
+};
+use crate::pkce::PkceVerifierStore;
+use crate::strategy::{OAuthEntityResolver, OAuthProvider, OAuthStrategy, OAuthStrategyOptions};
+use crate::tokens::ProviderTokens;
+
+/// ID token signature algorithms accepted. Symmetric ones (`HS*`) are not:
+/// the JWKS holds public keys only.
+const ID_TOKEN_ALGORITHMS: &[Algorithm] = &[
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::EdDSA,
+];
+
+/// Least time between JWKS refetches for an unknown `kid`, so tokens with
+/// made-up key ids can't make the provider hammer the issuer.
+const JWKS_MIN_REFETCH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long validated ID token claims wait for `fetch_profile`.
+const PENDING_CLAIMS_TTL: Duration = Duration::from_secs(300);
+
+/// Most code exchanges whose claims can wait for `fetch_profile` at once.
+const MAX_PENDING_CLAIMS: usize = 10_000;
+
+/// An OpenID Connect client: the issuer and the client registered with it.
+#[derive(Clone, Debug)]
+pub struct OidcConfig {
+    /// e.g. `https://example.okta.com`, `https://tenant.auth0.com/` or
+    /// `https://keycloak.example.com/realms/main`.
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_uri: String,
+    pub scopes: Vec<String>,
+}
+
+impl OidcConfig {
+    /// Requests the `openid`, `email` and `profile` scopes.
+    pub fn new(
+        issuer: impl Into<String>,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        redirect_uri: impl Into<String>,
+    ) -> Self {
+        Self {
+            issuer: issuer.into(),
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            redirect_uri: redirect_uri.into(),
+            scopes: vec![
+                "openid".to_string(),
+                "email".to_string(),
+                "profile".to_string(),
+            ],
+        }
+    }
+
+    pub fn with_scopes<I, S>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// The parts of an issuer's `/.well-known/openid-configuration` in use.
+#[derive(Clone, Debug, Deserialize)]
+pub struct OidcDiscovery {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    #[serde(default)]
+    pub userinfo_endpoint: Option<String>,
+    pub jwks_uri: String,
+}
+
+impl OidcDiscovery {
+    /// Fetch the discovery document of `issuer`, checking that it is the
+    /// issuer's own.
+    pub async fn fetch(http: &reqwest::Client, issuer: &str) -> Result<Self> {
+        let issuer = issuer.trim_end_matches('/');
+        let discovery: Self = http
+            .get(format!("{issuer}/.well-known/openid-configuration"))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if discovery.issuer.trim_end_matches('/') != issuer {
+            anyhow::bail!(
+                "OpenID configuration of {issuer} is for another issuer ({})",
+                discovery.issuer
+            );
+        }
+        Ok(discovery)
+    }
+}
+
+/// Authorization-code provider for any OpenID Connect issuer, configured
+/// from its discovery document.
+///
+/// The `id_token` of the code exchange is required and validated: signature
+/// against the issuer's JWKS (refetched for an unknown `kid`, at most once a
+/// minute, so key rotation works), `iss`, `aud` and `exp`. The profile is
+/// its claims, completed by the userinfo endpoint when the issuer has one.
+pub struct OidcProvider<P>
+where
+    P: Clone + Send + Sync + 'static,
+{
+    oauth: OAuth2AuthorizationCodeProvider<P>,
+    discovery: OidcDiscovery,
+    client_id: String,
+    http: reqwest::Client,
+    jwks: RwLock<JwkSet>,
+    /// When the JWKS was last refetched for an unknown `kid`
+    jwks_refetched: Mutex<Option<Instant>>,
+    /// Validated ID token claims by access token, from the code exchange
+    /// until `fetch_profile`.
+    claims: Mutex<PendingClaims>,
+}
+
+impl<P> OidcProvider<P>
+where
+    P: Clone + Send + Sync + 'static,
+{
+    /// Discover `config.issuer` and fetch its JWKS. The provider is named
+    /// `oidc`.
+    pub async fn discover(config: OidcConfig) -> Result<Self> {
+        Self::discover_with_name("oidc", config).await
+    }
+
+    pub async fn discover_with_name(name: impl Into<String>, config: OidcConfig) -> Result<Self> {
+        let http = reqwest::Client::new();
+        let discovery = OidcDiscovery::fetch(&http, &config.issuer).await?;
+        let jwks = fetch_jwks(&http, &discovery.jwks_uri).await?;
+
+        let oauth = OAuth2AuthorizationCodeProvider::new(OAuth2ClientConfig {
+            name: name.into(),
+            client_id: config.client_id.clone(),
+            client_secret: config.client_secret,
+            auth_url: discovery.authorization_endpoint.clone(),
+            token_url: discovery.token_endpoint.clone(),
+            redirect_uri: config.redirect_uri,
+            scopes: config.scopes,
+            user_info_url: None,
+        })?;
+
+        Ok(Self {
+            oauth,
+            discovery,
+            client_id: config.client_id,
+            http,
+            jwks: RwLock::new(jwks),
+            jwks_refetched: Mutex::new(None),
+            claims: Mutex::new(PendingClaims::default()),
+        })
+    }
+
+    /// See [`OAuth2AuthorizationCodeProvider::with_pkce`].
+    pub fn with_pkce(mut self, store: Arc<dyn PkceVerifierStore>) -> Self {
+        self.oauth = self.oauth.with_pkce(store);
+        self
+    }
+
+    pub fn discovery(&self) -> &OidcDiscovery {
+        &self.discovery
+    }
+
+    pub fn authorize_url(&self) -> String {
+        self.oauth.authorize_url()
+    }
+
+    pub async fn authorization(&self) -> Result<OAuth2Authorization> {
+        self.oauth.authorization().await
+    }
+
+    /// Validate `id_token` and return its claims.
+    pub async fn validate_id_token(&self, id_token: &str) -> Result<Value> {
+        let invalid = |e: anyhow::Error| {
+            DogError::not_authenticated("Invalid ID token")
+                .with_source(e)
+                .into_anyhow()
+        };
+
+        let header = jsonwebtoken::decode_header(id_token).map_err(|e| invalid(e.into()))?;
+        if !ID_TOKEN_ALGORITHMS.contains(&header.alg) {
+            return Err(invalid(anyhow::anyhow!(
+                "Unsupported ID token algorithm {:?}",
+                header.alg
+            )));
+        }
+
+        let unknown_kid = || invalid(anyhow::anyhow!("No JWKS key for kid {:?}", header.kid));
+        let jwk = match self.find_key(header.kid.as_deref()) {
+            Some(jwk) => jwk,
+            None => {
+                if !self.claim_jwks_refetch() {
+                    return Err(unknown_kid());
+                }
+                let jwks = fetch_jwks(&self.http, &self.discovery.jwks_uri).await?;
+                *self.jwks.write().unwrap() = jwks;
+                self.find_key(header.kid.as_deref())
+                    .ok_or_else(unknown_kid)?
+            }
+        };
+        let key = DecodingKey::from_jwk(&jwk).map_err(|e| invalid(e.into()))?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&self.discovery.issuer]);
+        validation.set_audience(&[&self.client_id]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+        let data = jsonwebtoken::decode::<Value>(id_token, &key, &validation)
+            .map_err(|e| invalid(e.into()))?;
+        Ok(data.claims)
+    }
+
+    /// Whether a JWKS refetch may go ahead now; if so, the next one has to
+    /// wait [`JWKS_MIN_REFETCH_INTERVAL`].
+    fn claim_jwks_refetch(&self) -> bool {
+        let mut refetched = self.jwks_refetched.lock().unwrap();
+        let now = Instant::now();
+        if refetched.is_some_and(|at| now.duration_since(at) < JWKS_MIN_REFETCH_INTERVAL) {
+            return false;
+        }
+        *refetched = Some(now);
+        true
+    }
+
+    fn find_key(&self, kid: Option<&str>) -> Option<Jwk> {
+        let jwks = self.jwks.read().unwrap();
+        match kid {
+            Some(kid) => jwks.find(kid).cloned(),
+            // Without a kid, only an issuer with a single key is unambiguous.
+            None if jwks.keys.len() == 1 => jwks.keys.first().cloned(),
+            None => None,
+        }
+    }
+}
+
+/// Claims waiting for `fetch_profile`, dropped oldest first after
+/// [`PENDING_CLAIMS_TTL`] or beyond [`MAX_PENDING_CLAIMS`], so exchanges
+/// whose profile is never fetched don't pile up.
+#[derive(Default)]
+struct PendingClaims {
+    by_token: HashMap<String, (Instant, Value)>,
+    /// Insertion order, for eviction without scanning `by_token`
+    order: VecDeque<(Instant, String)>,
+}
+
+impl PendingClaims {
+    fn insert(&mut self, access_token: String, claims: Value) {
+        let now = Instant::now();
+        self.evict(now);
+        self.order.push_back((now, access_token.clone()));
+        self.by_token.insert(access_token, (now, claims));
+    }
+
+    fn take(&mut self, access_token: &str) -> Option<Value> {
+        self.evict(Instant::now());
+        self.by_token.remove(access_token).map(|(_, claims)| claims)
+    }
+
+    fn evict(&mut self, now: Instant) {
+        while let Some((at, _)) = self.order.front() {
+            let expired = now.duration_since(*at) >= PENDING_CLAIMS_TTL;
+            if !expired && self.order.len() < MAX_PENDING_CLAIMS {
+                break;
+            }
+            let Some((at, token)) = self.order.pop_front() else {
+                break;
+            };
+            // Only if the token wasn't taken and stored again since.
+            if self
+                .by_token
+                .get(&token)
+                .is_some_and(|(stored, _)| *stored == at)
+            {
+                self.by_token.remove(&token);
+            }
+        }
+    }
+}
+
+async fn fetch_jwks(http: &reqwest::Client, jwks_uri: &str) -> Result<JwkSet> {
+    Ok(http
+        .get(jwks_uri)
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<JwkSet>()
+        .await?)
+}
+
+#[async_trait]
+impl<P> OAuthProvider<P> for OidcProvider<P>
+where
+    P: Clone + Send + Sync + 'static,
+{
+    fn name(&self) -> &str {
+        self.oauth.name()
+    }
+
+    async fn exchange_code(&self, code: &str, ctx: &mut HookContext<Value, P>) -> Result<String> {
+        self.exchange_code_with_state(code, None, ctx).await
+    }
+
+    async fn exchange_code_with_state(
+        &self,
+        code: &str,
+        state: Option<&str>,
+        ctx: &mut HookContext<Value, P>,
+    ) -> Result<String> {
+        Ok(self
+            .exchange_code_for_tokens(code, state, ctx)
+            .await?
+            .access_token)
+    }
+
+    async fn exchange_code_for_tokens(
+        &self,
+        code: &str,
+        state: Option<&str>,
+        ctx: &mut HookContext<Value, P>,
+    ) -> Result<ProviderTokens> {
+        let tokens = self
+            .oauth
+            .exchange_code_for_tokens(code, state, ctx)
+            .await?;
+        let id_token = tokens.id_token.as_deref().ok_or_else(|| {
+            DogError::not_authenticated("OpenID Connect login returned no ID token").into_anyhow()
+        })?;
+        let claims = self.validate_id_token(id_token).await?;
+
+        self.claims
+            .lock()
+            .unwrap()
+            .insert(tokens.access_token.clone(), claims);
+        Ok(tokens)
+    }
+
+    async fn refresh_token(
+        &self,
+        refresh_token: &str,
+        ctx: &mut HookContext<Value, P>,
+    ) -> Result<ProviderTokens> {
+        self.oauth.refresh_token(refresh_token, ctx).await
+    }
+
+    async fn fetch_profile(
+        &self,
+        access_token: &str,
+        _ctx: &mut HookContext<Value, P>,
+    ) -> Result<Option<Value>> {
+        let claims = self.claims.lock().unwrap().take(access_token);
+
+        let userinfo = match self.discovery.userinfo_endpoint.as_deref() {
+            Some(url) => Some(
+                self.http
+                    .get(url)
+                    .bearer_auth(access_token)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json::<Value>()
+                    .await?,
+            ),
+            None => None,
+        };
+
+        match (claims, userinfo) {
+            (Some(Value::Object(mut profile)), Some(Value::Object(userinfo))) => {
+                // The userinfo response must be about the ID token's subject.
+                if userinfo.get("sub") != profile.get("sub") {
+                    return Err(DogError::not_authenticated(
+                        "OpenID Connect userinfo does not match the ID token",
+                    )
+                    .into_anyhow());
+                }
+                profile.extend(userinfo);
+                Ok(Some(Value::Object(profile)))
+            }
+            (Some(claims), _) => Ok(Some(claims)),
+            // An access token passed in by the client, not from a code
+            // exchange here: only userinfo can vouch for it.
+            (None, userinfo) => Ok(userinfo),
+        }
+    }
+}
+
+/// Discover `config.issuer`, then register an `OAuthStrategy` named `name`
+/// with an [`OidcProvider`] of the same name.
+///
+/// Returns the provider, to build authorize URLs with. Without an
+/// `entity_resolver`, users are linked via the `<name>Id` field of the
+/// configured authentication service, from the ID token's `sub`.
+pub async fn register_oidc<P>(
+    auth: &mut AuthenticationBuilder<P>,
+    name: &str,
+    config: OidcConfig,
+    entity_resolver: Option<Arc<dyn OAuthEntityResolver<P>>>,
+) -> Result<Arc<OidcProvider<P>>>
+where
+    P: Clone + Send + Sync + 'static,
+{
+    let provider = Arc::new(OidcProvider::discover_with_name(name, config).await?);
+
+    let mut opts = OAuthStrategyOptions {
+        default_provider: Some(name.to_string()),
+        entity_resolver,
+        ..Default::default()
+    };
+    opts.providers.insert(name.to_string(), provider.clone());
+
+    let strategy = OAuthStrategy::new().with_name(name).with_options(opts);
+    auth.register(name, Arc::new(strategy));
+    Ok(provider)
+}
@@ -0,0 +1,52 @@
+// PKCE verifier storage.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// PKCE code verifiers keyed by the OAuth `state` of the authorization that
+/// created them.
+///
+/// `take` must be atomic so that one verifier cannot be used twice. Share
+/// one store between instances for a login started on one to be completed
+/// on another (e.g. Redis with `SET ... EX` and `GETDEL`).
+#[async_trait]
+pub trait PkceVerifierStore: Send + Sync {
+    async fn put(&self, state: &str, verifier: &str, ttl: Duration) -> Result<()>;
+
+    async fn take(&self, state: &str) -> Result<Option<String>>;
+}
+
+/// In-process `PkceVerifierStore`.
+#[derive(Default)]
+pub struct MemoryPkceVerifierStore {
+    verifiers: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+impl MemoryPkceVerifierStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl PkceVerifierStore for MemoryPkceVerifierStore {
+    async fn put(&self, state: &str, verifier: &str, ttl: Duration) -> Result<()> {
+        let now = Instant::now();
+        let mut verifiers = self.verifiers.lock().unwrap();
+        verifiers.retain(|_, (_, until)| *until > now);
+        verifiers.insert(state.to_string(), (verifier.to_string(), now + ttl));
+        Ok(())
+    }
+
+    async fn take(&self, state: &str) -> Result<Option<String>> {
+        let mut verifiers = self.verifiers.lock().unwrap();
+        Ok(verifiers
+            .remove(state)
+            .filter(|(_, until)| *until > Instant::now())
+            .map(|(verifier, _)| verifier))
+    }
+}
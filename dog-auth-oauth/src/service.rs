@@ -1,6 +1,8 @@
 // OAuth service.
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use async_trait::async_trait;
@@ -12,6 +14,12 @@ use dog_core::errors::DogError;
 use dog_core::HookContext;
 use serde_json::{Map, Value};
 
+use crate::strategy::{map_oauth_provider_error, OAuthProvider};
+use crate::tokens::{ProviderTokenStore, ProviderTokens};
+
+/// Access tokens this close to expiring are refreshed too.
+const REFRESH_LEEWAY: Duration = Duration::from_secs(30);
+
 pub struct OAuthCallbackResponse {
     pub auth_result: AuthenticationResult,
     pub location: Option<String>,
@@ -50,6 +58,8 @@ where
 {
     pub auth_service: Arc<AuthenticationService<P>>,
     pub redirect: Option<Arc<dyn OAuthRedirect<P>>>,
+    pub token_store: Option<Arc<dyn ProviderTokenStore>>,
+    pub providers: HashMap<String, Arc<dyn OAuthProvider<P>>>,
 }
 
 impl<P> OAuthService<P>
@@ -60,6 +70,8 @@ where
         Self {
             auth_service,
             redirect: None,
+            token_store: None,
+            providers: HashMap::new(),
         }
     }
 
@@ -68,6 +80,59 @@ where
         self
     }
 
+    /// The store the OAuth strategy keeps provider tokens in (see
+    /// `OAuthStrategy::with_token_store`).
+    pub fn with_token_store(mut self, store: Arc<dyn ProviderTokenStore>) -> Self {
+        self.token_store = Some(store);
+        self
+    }
+
+    /// A provider whose tokens `refresh_provider_token` may refresh.
+    pub fn register_provider(mut self, provider: Arc<dyn OAuthProvider<P>>) -> Self {
+        self.providers.insert(provider.name().to_string(), provider);
+        self
+    }
+
+    /// The stored tokens of `identity` for `provider`, refreshed first with
+    /// the stored refresh token if the access token has expired.
+    pub async fn refresh_provider_token(
+        &self,
+        identity: &str,
+        provider: &str,
+        ctx: &mut HookContext<Value, P>,
+    ) -> Result<ProviderTokens> {
+        let store = self.token_store.as_ref().ok_or_else(|| {
+            DogError::general_error("No OAuth provider token store configured").into_anyhow()
+        })?;
+        let tokens = store.get(identity, provider).await?.ok_or_else(|| {
+            DogError::not_found(format!("No '{provider}' tokens for this identity")).into_anyhow()
+        })?;
+        if !tokens.is_expired(REFRESH_LEEWAY) {
+            return Ok(tokens);
+        }
+
+        let Some(refresh_token) = tokens.refresh_token.clone() else {
+            return Err(DogError::not_authenticated(format!(
+                "The '{provider}' access token has expired and cannot be refreshed"
+            ))
+            .into_anyhow());
+        };
+        let oauth_provider = self.providers.get(provider).ok_or_else(|| {
+            DogError::bad_request(format!("Unknown OAuth provider '{provider}'")).into_anyhow()
+        })?;
+
+        let mut refreshed = oauth_provider
+            .refresh_token(&refresh_token, ctx)
+            .await
+            .map_err(map_oauth_provider_error)?;
+        // Providers may not rotate the refresh token; keep the old one then.
+        if refreshed.refresh_token.is_none() {
+            refreshed.refresh_token = Some(refresh_token);
+        }
+        store.put(identity, provider, refreshed.clone()).await?;
+        Ok(refreshed)
+    }
+
     pub async fn authenticate_callback(
         &self,
         provider: &str,
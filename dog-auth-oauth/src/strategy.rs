@@ -14,6 +14,8 @@ use dog_core::HookContext;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Map, Value};
 
+use crate::tokens::{ProviderTokenStore, ProviderTokens};
+
 #[async_trait]
 pub trait OAuthEntityResolver<P>: Send + Sync
 where
@@ -36,6 +38,44 @@ where
 
     async fn exchange_code(&self, code: &str, ctx: &mut HookContext<Value, P>) -> Result<String>;
 
+    /// Exchange `code` for an access token, given the `state` returned on
+    /// the callback. Providers that bind data to the state (e.g. a PKCE
+    /// verifier) override this; the default ignores it.
+    async fn exchange_code_with_state(
+        &self,
+        code: &str,
+        _state: Option<&str>,
+        ctx: &mut HookContext<Value, P>,
+    ) -> Result<String> {
+        self.exchange_code(code, ctx).await
+    }
+
+    /// Like `exchange_code_with_state`, keeping the refresh token and expiry
+    /// when the provider returns them.
+    async fn exchange_code_for_tokens(
+        &self,
+        code: &str,
+        state: Option<&str>,
+        ctx: &mut HookContext<Value, P>,
+    ) -> Result<ProviderTokens> {
+        Ok(ProviderTokens::new(
+            self.exchange_code_with_state(code, state, ctx).await?,
+        ))
+    }
+
+    /// Get new tokens with `refresh_token`.
+    async fn refresh_token(
+        &self,
+        _refresh_token: &str,
+        _ctx: &mut HookContext<Value, P>,
+    ) -> Result<ProviderTokens> {
+        Err(DogError::bad_request(format!(
+            "OAuth provider '{}' does not support token refresh",
+            self.name()
+        ))
+        .into_anyhow())
+    }
+
     async fn fetch_profile(
         &self,
         _access_token: &str,
@@ -50,6 +90,7 @@ pub struct OAuthAuthenticateData {
     pub provider: String,
     pub access_token: Option<String>,
     pub code: Option<String>,
+    pub state: Option<String>,
     pub profile: Option<Value>,
 }
 
@@ -61,6 +102,8 @@ where
     pub default_provider: Option<String>,
     pub providers: HashMap<String, Arc<dyn OAuthProvider<P>>>,
     pub entity_resolver: Option<Arc<dyn OAuthEntityResolver<P>>>,
+    /// Where to keep the provider tokens of code logins.
+    pub token_store: Option<Arc<dyn ProviderTokenStore>>,
 }
 
 impl<P> Default for OAuthStrategyOptions<P>
@@ -72,6 +115,7 @@ where
             default_provider: None,
             providers: HashMap::new(),
             entity_resolver: None,
+            token_store: None,
         }
     }
 }
@@ -126,6 +170,11 @@ where
         self
     }
 
+    pub fn with_token_store(mut self, store: Arc<dyn ProviderTokenStore>) -> Self {
+        self.options.token_store = Some(store);
+        self
+    }
+
     fn read_string(data: &Map<String, Value>, key: &str) -> Option<String> {
         data.get(key)
             .and_then(|v| v.as_str())
@@ -144,6 +193,7 @@ where
             .or_else(|| Self::read_string(&authentication.data, "access_token"));
 
         let code = Self::read_string(&authentication.data, "code");
+        let state = Self::read_string(&authentication.data, "state");
 
         let profile = authentication.data.get("profile").cloned();
 
@@ -151,6 +201,7 @@ where
             provider,
             access_token,
             code,
+            state,
             profile,
         })
     }
@@ -177,7 +228,9 @@ where
         };
 
         let key = format!("{provider}Id");
-        let svc = ctx.services.service(service_name)?;
+        // The service itself, not its hooks: entity lookups must see fields
+        // that after hooks hide from clients.
+        let svc = ctx.services.service(service_name)?.inner().clone();
 
         // Minimal lookup: find all and filter.
         let all = svc.find(&ctx.tenant, ctx.params.clone()).await?;
@@ -202,7 +255,7 @@ where
             return Err(DogError::not_authenticated("Missing profile id").into_anyhow());
         };
         data.insert(format!("{provider}Id"), Value::String(pid));
-        let svc = ctx.services.service(service_name)?;
+        let svc = ctx.services.service(service_name)?.inner().clone();
         svc.create(&ctx.tenant, Value::Object(data), ctx.params.clone())
             .await
     }
@@ -228,7 +281,7 @@ where
         if let Some(pid) = Self::profile_id(provider, profile) {
             data.insert(format!("{provider}Id"), Value::String(pid));
         }
-        let svc = ctx.services.service(service_name)?;
+        let svc = ctx.services.service(service_name)?.inner().clone();
         svc.patch(
             &ctx.tenant,
             Some(&id),
@@ -267,13 +320,19 @@ where
         // Resolve access token and/or profile via external provider implementation.
         let mut access_token = req.access_token.clone();
         let mut profile = req.profile.clone();
+        let mut provider_tokens = None;
 
         if access_token.is_none() {
             if let (Some(code), Some(provider)) = (req.code.as_deref(), external.as_ref()) {
-                access_token = Some(match provider.exchange_code(code, ctx).await {
+                let tokens = match provider
+                    .exchange_code_for_tokens(code, req.state.as_deref(), ctx)
+                    .await
+                {
                     Ok(t) => t,
                     Err(e) => return Err(map_oauth_provider_error(e)),
-                });
+                };
+                access_token = Some(tokens.access_token.clone());
+                provider_tokens = Some(tokens);
             }
         }
 
@@ -312,6 +371,23 @@ where
             }
         }
 
+        if let (Some(store), Some(tokens)) = (self.options.token_store.as_ref(), provider_tokens) {
+            let entity_id = cfg
+                .entity
+                .as_deref()
+                .and_then(|key| entity_out.as_ref()?.get(key)?.get("id"));
+            let identity = match entity_id {
+                Some(Value::String(s)) => Some(s.clone()),
+                Some(Value::Number(n)) => Some(n.to_string()),
+                _ => profile
+                    .as_ref()
+                    .and_then(|p| Self::profile_id(&req.provider, p)),
+            };
+            if let Some(identity) = identity {
+                store.put(&identity, &req.provider, tokens).await?;
+            }
+        }
+
         let mut auth_obj = Map::new();
         auth_obj.insert("strategy".to_string(), Value::String(self.name.clone()));
         auth_obj.insert("provider".to_string(), Value::String(req.provider.clone()));
@@ -344,7 +420,7 @@ where
     }
 }
 
-fn map_oauth_provider_error(e: anyhow::Error) -> anyhow::Error {
+pub(crate) fn map_oauth_provider_error(e: anyhow::Error) -> anyhow::Error {
     // We keep this provider-agnostic by inspecting error chain text.
     // If we can identify a common OAuth failure, return a DogError::bad_request so HTTP adapters
     // produce a clear 400 rather than a generic 500.
@@ -0,0 +1,105 @@
+// Provider token storage.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Tokens issued by an OAuth provider, kept to call its API after login.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderTokens {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    /// Unix seconds after which `access_token` is expired, if the provider
+    /// said.
+    pub expires_at: Option<i64>,
+    /// OpenID Connect ID token of the login, if any.
+    #[serde(default)]
+    pub id_token: Option<String>,
+}
+
+impl ProviderTokens {
+    pub fn new(access_token: impl Into<String>) -> Self {
+        Self {
+            access_token: access_token.into(),
+            refresh_token: None,
+            expires_at: None,
+            id_token: None,
+        }
+    }
+
+    /// Set `expires_at` from the `expires_in` of a token response.
+    pub fn expires_in(mut self, expires_in: Duration) -> Self {
+        self.expires_at = Some(unix_now().saturating_add(expires_in.as_secs() as i64));
+        self
+    }
+
+    /// Whether the access token is expired or will be within `leeway`.
+    pub fn is_expired(&self, leeway: Duration) -> bool {
+        self.expires_at
+            .is_some_and(|at| at <= unix_now().saturating_add(leeway.as_secs() as i64))
+    }
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// Provider tokens per identity and provider.
+///
+/// `OAuthStrategy` writes them after a login when given a store, keyed by
+/// the linked entity's `id` (or the profile id without an entity), and
+/// `OAuthService::refresh_provider_token` reads them back. The tokens grant
+/// access to the user's provider account: store them encrypted at rest.
+#[async_trait]
+pub trait ProviderTokenStore: Send + Sync {
+    async fn get(&self, identity: &str, provider: &str) -> Result<Option<ProviderTokens>>;
+
+    async fn put(&self, identity: &str, provider: &str, tokens: ProviderTokens) -> Result<()>;
+
+    async fn remove(&self, identity: &str, provider: &str) -> Result<()>;
+}
+
+/// In-process `ProviderTokenStore`.
+#[derive(Default)]
+pub struct MemoryProviderTokenStore {
+    tokens: Mutex<HashMap<(String, String), ProviderTokens>>,
+}
+
+impl MemoryProviderTokenStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ProviderTokenStore for MemoryProviderTokenStore {
+    async fn get(&self, identity: &str, provider: &str) -> Result<Option<ProviderTokens>> {
+        let tokens = self.tokens.lock().unwrap();
+        Ok(tokens
+            .get(&(identity.to_string(), provider.to_string()))
+            .cloned())
+    }
+
+    async fn put(&self, identity: &str, provider: &str, tokens: ProviderTokens) -> Result<()> {
+        self.tokens
+            .lock()
+            .unwrap()
+            .insert((identity.to_string(), provider.to_string()), tokens);
+        Ok(())
+    }
+
+    async fn remove(&self, identity: &str, provider: &str) -> Result<()> {
+        self.tokens
+            .lock()
+            .unwrap()
+            .remove(&(identity.to_string(), provider.to_string()));
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+#![cfg(feature = "oauth2-client")]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use axum::extract::Form;
+use axum::http::HeaderMap;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use dog_auth::{AuthOptions, AuthenticationParams, AuthenticationRequest, AuthenticationService};
+use dog_auth_oauth::{
+    register_github_oauth, GitHubOAuthConfig, GitHubOAuthProvider, OAuthEntityResolver,
+    OAuthStrategy,
+};
+use dog_core::tenant::TenantContext;
+use dog_core::{DogAppBuilder, HookContext, ServiceCaller, ServiceMethodKind};
+use serde_json::{json, Value};
+
+/// Stands in for github.com and api.github.com.
+async fn mock_github() -> String {
+    async fn access_token(Form(form): Form<HashMap<String, String>>) -> Json<Value> {
+        assert_eq!(form["code"], "the-code");
+        assert_eq!(form["grant_type"], "authorization_code");
+        Json(json!({
+            "access_token": "gho_test",
+            "token_type": "bearer",
+            "scope": "read:user,user:email",
+        }))
+    }
+
+    fn check_request(headers: &HeaderMap) {
+        assert_eq!(headers["authorization"], "Bearer gho_test");
+        assert!(headers.contains_key("user-agent"));
+    }
+
+    async fn user(headers: HeaderMap) -> Json<Value> {
+        check_request(&headers);
+        // The user keeps their email private.
+        Json(json!({ "id": 583231, "login": "octocat", "name": "The Octocat", "email": null }))
+    }
+
+    async fn user_emails(headers: HeaderMap) -> Json<Value> {
+        check_request(&headers);
+        Json(json!([
+            { "email": "old@example.com", "primary": false, "verified": true },
+            { "email": "unverified@example.com", "primary": true, "verified": false },
+            { "email": "octocat@github.com", "primary": true, "verified": true },
+        ]))
+    }
+
+    let app = Router::new()
+        .route("/login/oauth/access_token", post(access_token))
+        .route("/user", get(user))
+        .route("/user/emails", get(user_emails));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{addr}")
+}
+
+#[derive(Default)]
+struct RecordingResolver {
+    profiles: Mutex<Vec<Value>>,
+}
+
+#[async_trait]
+impl OAuthEntityResolver<()> for RecordingResolver {
+    async fn resolve_entity(
+        &self,
+        provider: &str,
+        profile: &Value,
+        _ctx: &mut HookContext<Value, ()>,
+    ) -> anyhow::Result<Option<Value>> {
+        assert_eq!(provider, "github");
+        self.profiles.lock().unwrap().push(profile.clone());
+        Ok(Some(json!({ "id": "u1", "githubId": profile["sub"] })))
+    }
+}
+
+#[tokio::test]
+async fn github_login_resolves_identity_and_private_email() {
+    let base = mock_github().await;
+
+    let mut builder = DogAppBuilder::<Value, ()>::new();
+    builder.set("oauth.github.client_id", "client-id");
+    builder.set("oauth.github.client_secret", "client-secret");
+    builder.set("oauth.github.redirect_uri", "http://localhost/callback");
+    let options = AuthOptions {
+        entity: Some("user".to_string()),
+        service: Some("users".to_string()),
+        ..AuthOptions::default()
+    };
+    let mut auth = AuthenticationService::builder(&mut builder, Some(options)).unwrap();
+    let resolver = Arc::new(RecordingResolver::default());
+    let authorize_url =
+        register_github_oauth(&mut builder, &mut auth, Some(resolver.clone())).unwrap();
+    assert!(authorize_url.starts_with("https://github.com/login/oauth/authorize?"));
+    assert!(authorize_url.contains("client_id=client-id"));
+
+    // Re-register against the mock server.
+    let mut config = GitHubOAuthConfig::from_config(&builder.config_snapshot()).unwrap();
+    config.auth_url = format!("{base}/login/oauth/authorize");
+    config.token_url = format!("{base}/login/oauth/access_token");
+    config.api_url = base.clone();
+    let strategy = OAuthStrategy::new()
+        .with_name("github")
+        .register_provider(Arc::new(GitHubOAuthProvider::new(config).unwrap()))
+        .with_entity_resolver(resolver.clone());
+    auth.register("github", Arc::new(strategy));
+
+    let auth = AuthenticationService::new(Arc::new(auth.build()));
+    let app = builder.build();
+
+    let request: AuthenticationRequest = serde_json::from_value(json!({
+        "strategy": "github",
+        "provider": "github",
+        "code": "the-code",
+    }))
+    .unwrap();
+    let mut ctx = HookContext::new(
+        TenantContext::new("default"),
+        ServiceMethodKind::Create,
+        (),
+        ServiceCaller::new(app.clone()),
+        app.config_snapshot(),
+    );
+    let result = auth
+        .authenticate(
+            &request,
+            &AuthenticationParams::default(),
+            &mut ctx,
+            &["github".to_string()],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(result["authentication"]["accessToken"], "gho_test");
+    assert_eq!(result["profile"]["sub"], "583231");
+    assert_eq!(result["profile"]["login"], "octocat");
+    assert_eq!(result["profile"]["email"], "octocat@github.com");
+    assert_eq!(result["user"], json!({ "id": "u1", "githubId": "583231" }));
+
+    let profiles = resolver.profiles.lock().unwrap();
+    assert_eq!(profiles.len(), 1);
+    assert_eq!(profiles[0]["sub"], "583231");
+}
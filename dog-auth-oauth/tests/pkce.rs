@@ -0,0 +1,163 @@
+#![cfg(feature = "oauth2-client")]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::extract::{Form, State};
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::{Json, Router};
+use dog_auth_oauth::oauth2_client::OAuth2ClientConfig;
+use dog_auth_oauth::{MemoryPkceVerifierStore, OAuth2AuthorizationCodeProvider, OAuthProvider};
+use dog_core::errors::{DogError, ErrorKind};
+use dog_core::tenant::TenantContext;
+use dog_core::{DogApp, DogAppBuilder, HookContext, ServiceCaller, ServiceMethodKind};
+use oauth2::url::Url;
+use oauth2::{PkceCodeChallenge, PkceCodeVerifier};
+use serde_json::{json, Value};
+
+/// Authorization codes issued by the simulated provider, with the PKCE
+/// challenge each was bound to.
+type Codes = Arc<Mutex<HashMap<String, String>>>;
+
+async fn token(
+    State(codes): State<Codes>,
+    Form(form): Form<HashMap<String, String>>,
+) -> (StatusCode, Json<Value>) {
+    let challenge = codes.lock().unwrap().remove(&form["code"]);
+    let verified = match (challenge, form.get("code_verifier")) {
+        (Some(challenge), Some(verifier)) => {
+            let verifier = PkceCodeVerifier::new(verifier.clone());
+            PkceCodeChallenge::from_code_verifier_sha256(&verifier).as_str() == challenge
+        }
+        _ => false,
+    };
+    if !verified {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "invalid_grant" })),
+        );
+    }
+    (
+        StatusCode::OK,
+        Json(json!({ "access_token": "at_test", "token_type": "bearer" })),
+    )
+}
+
+async fn mock_provider(codes: Codes) -> String {
+    let app = Router::new().route("/token", post(token)).with_state(codes);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{addr}")
+}
+
+fn client(base: &str) -> OAuth2AuthorizationCodeProvider<()> {
+    OAuth2AuthorizationCodeProvider::new(OAuth2ClientConfig {
+        name: "mock".to_string(),
+        client_id: "client-id".to_string(),
+        client_secret: "client-secret".to_string(),
+        auth_url: format!("{base}/authorize"),
+        token_url: format!("{base}/token"),
+        redirect_uri: "http://localhost/callback".to_string(),
+        scopes: vec!["profile".to_string()],
+        user_info_url: None,
+    })
+    .unwrap()
+}
+
+/// The user approves the login at `authorize_url`, and the provider issues
+/// `code` bound to its PKCE challenge.
+fn approve(codes: &Codes, authorize_url: &str, code: &str) {
+    let url = Url::parse(authorize_url).unwrap();
+    let query: HashMap<_, _> = url.query_pairs().into_owned().collect();
+    assert_eq!(query["code_challenge_method"], "S256");
+    codes
+        .lock()
+        .unwrap()
+        .insert(code.to_string(), query["code_challenge"].clone());
+}
+
+fn ctx(app: &DogApp<Value, ()>) -> HookContext<Value, ()> {
+    HookContext::new(
+        TenantContext::new("default"),
+        ServiceMethodKind::Create,
+        (),
+        ServiceCaller::new(app.clone()),
+        app.config_snapshot(),
+    )
+}
+
+fn kind(err: &anyhow::Error) -> Option<ErrorKind> {
+    DogError::from_anyhow(err).map(|e| e.kind)
+}
+
+#[tokio::test]
+async fn exchange_sends_the_verifier_stored_for_the_state() {
+    let codes = Codes::default();
+    let base = mock_provider(codes.clone()).await;
+    let app = DogAppBuilder::<Value, ()>::new().build();
+    let provider = client(&base).with_pkce(Arc::new(MemoryPkceVerifierStore::new()));
+
+    let authorization = provider.authorization().await.unwrap();
+    approve(&codes, &authorization.url, "code-a");
+
+    let token = provider
+        .exchange_code_with_state("code-a", Some(&authorization.state), &mut ctx(&app))
+        .await
+        .unwrap();
+    assert_eq!(token, "at_test");
+
+    // The verifier is single use.
+    let err = provider
+        .exchange_code_with_state("code-a", Some(&authorization.state), &mut ctx(&app))
+        .await
+        .unwrap_err();
+    assert_eq!(kind(&err), Some(ErrorKind::BadRequest));
+}
+
+#[tokio::test]
+async fn exchange_without_the_matching_verifier_is_rejected() {
+    let codes = Codes::default();
+    let base = mock_provider(codes.clone()).await;
+    let app = DogAppBuilder::<Value, ()>::new().build();
+    let provider = client(&base).with_pkce(Arc::new(MemoryPkceVerifierStore::new()));
+
+    let victim = provider.authorization().await.unwrap();
+    let attacker = provider.authorization().await.unwrap();
+    approve(&codes, &victim.url, "code-a");
+
+    // An intercepted code redeemed under another login's state.
+    let err = provider
+        .exchange_code_with_state("code-a", Some(&attacker.state), &mut ctx(&app))
+        .await
+        .unwrap_err();
+    assert!(format!("{err:#}").contains("invalid_grant"), "{err:#}");
+
+    // An intercepted code redeemed without any verifier.
+    approve(&codes, &victim.url, "code-b");
+    let err = client(&base)
+        .exchange_code("code-b", &mut ctx(&app))
+        .await
+        .unwrap_err();
+    assert!(format!("{err:#}").contains("invalid_grant"), "{err:#}");
+}
+
+#[tokio::test]
+async fn exchange_requires_a_known_state() {
+    let codes = Codes::default();
+    let base = mock_provider(codes.clone()).await;
+    let app = DogAppBuilder::<Value, ()>::new().build();
+    let provider = client(&base).with_pkce(Arc::new(MemoryPkceVerifierStore::new()));
+
+    let authorization = provider.authorization().await.unwrap();
+    approve(&codes, &authorization.url, "code-a");
+
+    for state in [None, Some("forged")] {
+        let err = provider
+            .exchange_code_with_state("code-a", state, &mut ctx(&app))
+            .await
+            .unwrap_err();
+        assert_eq!(kind(&err), Some(ErrorKind::BadRequest));
+    }
+}
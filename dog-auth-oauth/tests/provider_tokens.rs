@@ -0,0 +1,160 @@
+#![cfg(feature = "oauth2-client")]
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::extract::{Form, State};
+use axum::routing::post;
+use axum::{Json, Router};
+use dog_auth::{AuthOptions, AuthenticationParams, AuthenticationRequest, AuthenticationService};
+use dog_auth_oauth::oauth2_client::OAuth2ClientConfig;
+use dog_auth_oauth::{
+    MemoryProviderTokenStore, OAuth2AuthorizationCodeProvider, OAuthEntityResolver, OAuthService,
+    OAuthStrategy, ProviderTokenStore,
+};
+use dog_core::tenant::TenantContext;
+use dog_core::{DogApp, DogAppBuilder, HookContext, ServiceCaller, ServiceMethodKind};
+use serde_json::{json, Value};
+
+/// Token endpoint: the code grant returns an access token that is already
+/// expired, the refresh grant a fresh one. Counts refreshes.
+async fn token(
+    State(refreshes): State<Arc<AtomicUsize>>,
+    Form(form): Form<HashMap<String, String>>,
+) -> Json<Value> {
+    match form["grant_type"].as_str() {
+        "authorization_code" => {
+            assert_eq!(form["code"], "the-code");
+            Json(json!({
+                "access_token": "at-1",
+                "refresh_token": "rt-1",
+                "token_type": "bearer",
+                "expires_in": 0,
+            }))
+        }
+        "refresh_token" => {
+            assert_eq!(form["refresh_token"], "rt-1");
+            refreshes.fetch_add(1, Ordering::SeqCst);
+            Json(json!({
+                "access_token": "at-2",
+                "token_type": "bearer",
+                "expires_in": 3600,
+            }))
+        }
+        other => panic!("unexpected grant_type {other}"),
+    }
+}
+
+async fn mock_provider(refreshes: Arc<AtomicUsize>) -> String {
+    let app = Router::new()
+        .route("/token", post(token))
+        .with_state(refreshes);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{addr}")
+}
+
+struct FixedUser;
+
+#[async_trait]
+impl OAuthEntityResolver<()> for FixedUser {
+    async fn resolve_entity(
+        &self,
+        _provider: &str,
+        _profile: &Value,
+        _ctx: &mut HookContext<Value, ()>,
+    ) -> anyhow::Result<Option<Value>> {
+        Ok(Some(json!({ "id": "u1" })))
+    }
+}
+
+fn ctx(app: &DogApp<Value, ()>) -> HookContext<Value, ()> {
+    HookContext::new(
+        TenantContext::new("default"),
+        ServiceMethodKind::Create,
+        (),
+        ServiceCaller::new(app.clone()),
+        app.config_snapshot(),
+    )
+}
+
+#[tokio::test]
+async fn expired_access_token_is_refreshed_with_the_stored_refresh_token() {
+    let refreshes = Arc::new(AtomicUsize::new(0));
+    let base = mock_provider(refreshes.clone()).await;
+
+    let provider = Arc::new(
+        OAuth2AuthorizationCodeProvider::<()>::new(OAuth2ClientConfig {
+            name: "mock".to_string(),
+            client_id: "client-id".to_string(),
+            client_secret: "client-secret".to_string(),
+            auth_url: format!("{base}/authorize"),
+            token_url: format!("{base}/token"),
+            redirect_uri: "http://localhost/callback".to_string(),
+            scopes: vec!["profile".to_string()],
+            user_info_url: None,
+        })
+        .unwrap(),
+    );
+    let store = Arc::new(MemoryProviderTokenStore::new());
+
+    let mut builder = DogAppBuilder::<Value, ()>::new();
+    let options = AuthOptions {
+        entity: Some("user".to_string()),
+        service: Some("users".to_string()),
+        ..AuthOptions::default()
+    };
+    let mut auth = AuthenticationService::builder(&mut builder, Some(options)).unwrap();
+    let strategy = OAuthStrategy::new()
+        .register_provider(provider.clone())
+        .with_entity_resolver(Arc::new(FixedUser))
+        .with_token_store(store.clone());
+    auth.register("oauth", Arc::new(strategy));
+    let auth = Arc::new(AuthenticationService::new(Arc::new(auth.build())));
+    let app = builder.build();
+
+    // Log in; the strategy keeps the provider tokens for the user.
+    let request: AuthenticationRequest = serde_json::from_value(json!({
+        "strategy": "oauth",
+        "provider": "mock",
+        "code": "the-code",
+        "profile": { "sub": "provider-user" },
+    }))
+    .unwrap();
+    auth.authenticate(
+        &request,
+        &AuthenticationParams::default(),
+        &mut ctx(&app),
+        &["oauth".to_string()],
+    )
+    .await
+    .unwrap();
+
+    let stored = store.get("u1", "mock").await.unwrap().unwrap();
+    assert_eq!(stored.access_token, "at-1");
+    assert_eq!(stored.refresh_token.as_deref(), Some("rt-1"));
+
+    let oauth = OAuthService::new(auth)
+        .with_token_store(store.clone())
+        .register_provider(provider);
+
+    let tokens = oauth
+        .refresh_provider_token("u1", "mock", &mut ctx(&app))
+        .await
+        .unwrap();
+    assert_eq!(tokens.access_token, "at-2");
+    assert_eq!(tokens.refresh_token.as_deref(), Some("rt-1"));
+    assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+    assert_eq!(store.get("u1", "mock").await.unwrap(), Some(tokens));
+
+    // Still valid: returned without another refresh.
+    let tokens = oauth
+        .refresh_provider_token("u1", "mock", &mut ctx(&app))
+        .await
+        .unwrap();
+    assert_eq!(tokens.access_token, "at-2");
+    assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+}
@@ -1,7 +1,8 @@
 // Authentication core.
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock, RwLockReadGuard};
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use async_trait::async_trait;
@@ -13,7 +14,14 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Map, Value};
 use uuid::Uuid;
 
-use crate::options::{AuthOptions, TokenType};
+use crate::hooks::event::{LoginFailure, LoginFailureReason};
+use crate::mfa::{
+    verify_totp_at, MemoryMfaChallengeStore, MfaChallenge, MfaChallengeStore, MfaSecretResolver,
+};
+use crate::options::{AuthOptions, JwtKey, JwtOptions, LoginThrottleOptions, TokenType};
+use crate::refresh::{MemoryRefreshTokenStore, RefreshTokenStore};
+use crate::revocation::{MemoryTokenRevocationStore, TokenRevocationStore};
+use crate::throttle::{AttemptStore, LoginAttempts, MemoryAttemptStore};
 
 #[cfg(any(feature = "jwt-aws-lc-rs", feature = "jwt-rust-crypto"))]
 use crate::options::JwtAlgorithm;
@@ -77,6 +85,24 @@ pub trait JwtProvider: Send + Sync {
         token: &str,
         overrides: Option<&JwtOverrides>,
     ) -> Result<Value>;
+
+    /// JWK Set with the public key tokens are verified with. Empty for HMAC
+    /// algorithms, whose secret must not be published.
+    fn jwks(&self, _jwt: &crate::options::JwtOptions) -> Result<Value> {
+        Ok(json!({ "keys": [] }))
+    }
+
+    /// The `kid` header of `token`, read without verifying it.
+    fn key_id(&self, _token: &str) -> Option<String> {
+        None
+    }
+
+    /// Parse the key material of `jwt` ahead of use, so tokens aren't
+    /// signed or verified with a key re-read per call. Called when a key is
+    /// configured or rotated in; fails on an unreadable or invalid key.
+    fn load_key(&self, _jwt: &crate::options::JwtOptions) -> Result<()> {
+        Ok(())
+    }
 }
 
 #[cfg(not(any(feature = "jwt-aws-lc-rs", feature = "jwt-rust-crypto")))]
@@ -105,10 +131,62 @@ impl JwtProvider for NoJwtProvider {
             "JWT support is disabled (enable one of: jwt-aws-lc-rs, jwt-rust-crypto)"
         ))
     }
+
+    fn jwks(&self, _jwt: &crate::options::JwtOptions) -> Result<Value> {
+        Err(anyhow::anyhow!(
+            "JWT support is disabled (enable one of: jwt-aws-lc-rs, jwt-rust-crypto)"
+        ))
+    }
 }
 
+/// Most parsed keys [`JsonwebtokenProvider`] keeps; the oldest go first.
 #[cfg(any(feature = "jwt-aws-lc-rs", feature = "jwt-rust-crypto"))]
-struct JsonwebtokenProvider;
+const MAX_LOADED_KEYS: usize = 16;
+
+#[cfg(any(feature = "jwt-aws-lc-rs", feature = "jwt-rust-crypto"))]
+#[derive(Default)]
+struct JsonwebtokenProvider {
+    /// Keys parsed from the `JwtOptions` they were configured with
+    keys: RwLock<Vec<Arc<LoadedKey>>>,
+}
+
+/// The key material fields of `JwtOptions`, identifying a parsed key.
+#[cfg(any(feature = "jwt-aws-lc-rs", feature = "jwt-rust-crypto"))]
+#[derive(PartialEq)]
+struct KeySource {
+    algorithm: JwtAlgorithm,
+    key_id: Option<String>,
+    secret: Option<String>,
+    private_key: Option<String>,
+    private_key_path: Option<String>,
+    public_key: Option<String>,
+    public_key_path: Option<String>,
+}
+
+#[cfg(any(feature = "jwt-aws-lc-rs", feature = "jwt-rust-crypto"))]
+impl KeySource {
+    fn of(jwt: &crate::options::JwtOptions) -> Self {
+        Self {
+            algorithm: jwt.algorithm.clone(),
+            key_id: jwt.key_id.clone(),
+            secret: jwt.secret.clone(),
+            private_key: jwt.private_key.clone(),
+            private_key_path: jwt.private_key_path.clone(),
+            public_key: jwt.public_key.clone(),
+            public_key_path: jwt.public_key_path.clone(),
+        }
+    }
+}
+
+/// A key parsed once; `None` where the options leave the key out.
+#[cfg(any(feature = "jwt-aws-lc-rs", feature = "jwt-rust-crypto"))]
+struct LoadedKey {
+    source: KeySource,
+    encoding: Option<jsonwebtoken::EncodingKey>,
+    decoding: Option<jsonwebtoken::DecodingKey>,
+    /// Public JWK, for RSA/ECDSA keys with a private key
+    jwk: Option<jsonwebtoken::jwk::Jwk>,
+}
 
 #[cfg(any(feature = "jwt-aws-lc-rs", feature = "jwt-rust-crypto"))]
 impl JsonwebtokenProvider {
@@ -124,6 +202,98 @@ impl JsonwebtokenProvider {
             JwtAlgorithm::ES384 => jsonwebtoken::Algorithm::ES384,
         }
     }
+
+    /// The parsed key for `jwt`, parsing it if it wasn't loaded before.
+    fn key(&self, jwt: &crate::options::JwtOptions) -> Result<Arc<LoadedKey>> {
+        let source = KeySource::of(jwt);
+        if let Some(key) = self
+            .keys
+            .read()
+            .unwrap()
+            .iter()
+            .find(|key| key.source == source)
+        {
+            return Ok(key.clone());
+        }
+
+        let key = Arc::new(Self::parse(jwt, source)?);
+        let mut keys = self.keys.write().unwrap();
+        if keys.len() >= MAX_LOADED_KEYS {
+            keys.remove(0);
+        }
+        keys.push(key.clone());
+        Ok(key)
+    }
+
+    fn parse(jwt: &crate::options::JwtOptions, source: KeySource) -> Result<LoadedKey> {
+        use jsonwebtoken::jwk::{Jwk, PublicKeyUse};
+        use jsonwebtoken::{DecodingKey, EncodingKey};
+
+        if jwt.is_hmac() {
+            let secret = jwt.secret.as_deref().map(str::as_bytes);
+            return Ok(LoadedKey {
+                source,
+                encoding: secret.map(EncodingKey::from_secret),
+                decoding: secret.map(DecodingKey::from_secret),
+                jwk: None,
+            });
+        }
+
+        let ec = matches!(jwt.algorithm, JwtAlgorithm::ES256 | JwtAlgorithm::ES384);
+        let encoding = match jwt.private_key_pem().map_err(|e| anyhow::anyhow!(e))? {
+            Some(pem) if ec => Some(EncodingKey::from_ec_pem(pem.as_bytes())),
+            Some(pem) => Some(EncodingKey::from_rsa_pem(pem.as_bytes())),
+            None => None,
+        }
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("Invalid JWT private key: {e}"))?;
+
+        let jwk = encoding
+            .as_ref()
+            .map(|encoding| -> Result<Jwk> {
+                let mut jwk =
+                    Jwk::from_encoding_key(encoding, Self::algorithm(jwt.algorithm.clone()))
+                        .map_err(|e| anyhow::anyhow!("Invalid JWT private key: {e}"))?;
+                jwk.common.public_key_use = Some(PublicKeyUse::Signature);
+                jwk.common.key_id = jwt.key_id.clone();
+                Ok(jwk)
+            })
+            .transpose()?;
+
+        // The public key from `public_key(_path)`, or else derived from the
+        // private key, so a service that signs can verify its own tokens.
+        let decoding = match jwt.public_key_pem().map_err(|e| anyhow::anyhow!(e))? {
+            Some(pem) => Some(
+                if ec {
+                    DecodingKey::from_ec_pem(pem.as_bytes())
+                } else {
+                    DecodingKey::from_rsa_pem(pem.as_bytes())
+                }
+                .map_err(|e| anyhow::anyhow!("Invalid JWT public key: {e}"))?,
+            ),
+            None => jwk
+                .as_ref()
+                .map(DecodingKey::from_jwk)
+                .transpose()
+                .map_err(|e| anyhow::anyhow!("Invalid JWT private key: {e}"))?,
+        };
+
+        Ok(LoadedKey {
+            source,
+            encoding,
+            decoding,
+            jwk,
+        })
+    }
+
+    fn missing(jwt: &crate::options::JwtOptions, key: &str) -> anyhow::Error {
+        let message = if jwt.is_hmac() {
+            "JWT secret is not configured".to_string()
+        } else {
+            format!("JWT {key} key is not configured")
+        };
+        DogError::not_authenticated(message).into_anyhow()
+    }
 }
 
 #[cfg(any(feature = "jwt-aws-lc-rs", feature = "jwt-rust-crypto"))]
@@ -134,13 +304,13 @@ impl JwtProvider for JsonwebtokenProvider {
         claims: Map<String, Value>,
         token_type: TokenType,
     ) -> Result<String> {
-        use jsonwebtoken::{encode, EncodingKey, Header};
+        use jsonwebtoken::{encode, Header};
 
-        // Minimal implementation: sign using HMAC secret.
-        // Key-based algorithms can be added later without changing the public API.
-        let secret = jwt.secret.as_ref().ok_or_else(|| {
-            DogError::not_authenticated("JWT secret is not configured").into_anyhow()
-        })?;
+        let key = self.key(jwt)?;
+        let encoding = key
+            .encoding
+            .as_ref()
+            .ok_or_else(|| Self::missing(jwt, "private"))?;
 
         let mut header = Header::new(Self::algorithm(jwt.algorithm.clone()));
         header.typ = Some(
@@ -151,13 +321,10 @@ impl JwtProvider for JsonwebtokenProvider {
             }
             .to_string(),
         );
+        header.kid = jwt.key_id.clone();
 
-        encode(
-            &header,
-            &claims,
-            &EncodingKey::from_secret(secret.as_bytes()),
-        )
-        .map_err(|e| DogError::not_authenticated(e.to_string()).into_anyhow())
+        encode(&header, &claims, encoding)
+            .map_err(|e| DogError::not_authenticated(e.to_string()).into_anyhow())
     }
 
     fn verify(
@@ -166,11 +333,14 @@ impl JwtProvider for JsonwebtokenProvider {
         token: &str,
         overrides: Option<&JwtOverrides>,
     ) -> Result<Value> {
-        use jsonwebtoken::{decode, DecodingKey, Validation};
+        use jsonwebtoken::{decode, Validation};
 
-        let secret = jwt.secret.as_ref().ok_or_else(|| {
-            DogError::not_authenticated("JWT secret is not configured").into_anyhow()
-        })?;
+        let key = self.key(jwt)?;
+        // A public key can only be derived from a private one.
+        let decoding = key
+            .decoding
+            .as_ref()
+            .ok_or_else(|| Self::missing(jwt, "private"))?;
 
         let issuer = overrides
             .and_then(|o| o.issuer.clone())
@@ -185,15 +355,32 @@ impl JwtProvider for JsonwebtokenProvider {
         validation.set_issuer(&[issuer.as_str()]);
         validation.set_audience(&audience.iter().map(|s| s.as_str()).collect::<Vec<_>>());
 
-        let decoded = decode::<Value>(
-            token,
-            &DecodingKey::from_secret(secret.as_bytes()),
-            &validation,
-        )
-        .map_err(|e| DogError::not_authenticated(e.to_string()).into_anyhow())?;
+        let decoded = decode::<Value>(token, decoding, &validation)
+            .map_err(|e| DogError::not_authenticated(e.to_string()).into_anyhow())?;
 
         Ok(decoded.claims)
     }
+
+    fn jwks(&self, jwt: &crate::options::JwtOptions) -> Result<Value> {
+        if jwt.is_hmac() {
+            return Ok(json!({ "keys": [] }));
+        }
+        let key = self.key(jwt)?;
+        let jwk = key
+            .jwk
+            .clone()
+            .ok_or_else(|| Self::missing(jwt, "private"))?;
+        let jwks = jsonwebtoken::jwk::JwkSet { keys: vec![jwk] };
+        Ok(serde_json::to_value(jwks)?)
+    }
+
+    fn key_id(&self, token: &str) -> Option<String> {
+        jsonwebtoken::decode_header(token).ok()?.kid
+    }
+
+    fn load_key(&self, jwt: &crate::options::JwtOptions) -> Result<()> {
+        self.key(jwt).map(drop)
+    }
 }
 
 #[async_trait]
@@ -217,6 +404,11 @@ where
     options: Arc<AuthOptions>,
     strategies: HashMap<String, Arc<dyn AuthenticationStrategy<P>>>,
     jwt: Arc<dyn JwtProvider>,
+    refresh_tokens: Arc<dyn RefreshTokenStore>,
+    revoked_tokens: Arc<dyn TokenRevocationStore>,
+    login_attempts: Arc<dyn AttemptStore>,
+    mfa: Option<Arc<dyn MfaSecretResolver>>,
+    mfa_challenges: Arc<dyn MfaChallengeStore>,
 }
 
 impl<P> AuthenticationBuilder<P>
@@ -235,18 +427,24 @@ where
         let jwt: Arc<dyn JwtProvider> = {
             #[cfg(any(feature = "jwt-aws-lc-rs", feature = "jwt-rust-crypto"))]
             {
-                Arc::new(JsonwebtokenProvider)
+                Arc::new(JsonwebtokenProvider::default())
             }
             #[cfg(not(any(feature = "jwt-aws-lc-rs", feature = "jwt-rust-crypto")))]
             {
                 Arc::new(NoJwtProvider)
             }
         };
+        jwt.load_key(&opts.jwt)?;
 
         Ok(Self {
             options: opts,
             strategies: HashMap::new(),
             jwt,
+            refresh_tokens: Arc::new(MemoryRefreshTokenStore::new()),
+            revoked_tokens: Arc::new(MemoryTokenRevocationStore::new()),
+            login_attempts: Arc::new(MemoryAttemptStore::new()),
+            mfa: None,
+            mfa_challenges: Arc::new(MemoryMfaChallengeStore::new()),
         })
     }
 
@@ -258,11 +456,47 @@ where
         self.strategies.insert(name.into(), strategy);
     }
 
+    /// Replace the default in-memory `RefreshTokenStore`.
+    pub fn refresh_token_store(&mut self, store: Arc<dyn RefreshTokenStore>) {
+        self.refresh_tokens = store;
+    }
+
+    /// Replace the default in-memory `TokenRevocationStore`.
+    pub fn revocation_store(&mut self, store: Arc<dyn TokenRevocationStore>) {
+        self.revoked_tokens = store;
+    }
+
+    /// Replace the default in-memory `AttemptStore` used by the login
+    /// throttle.
+    pub fn attempt_store(&mut self, store: Arc<dyn AttemptStore>) {
+        self.login_attempts = store;
+    }
+
+    /// Require a TOTP code after primary authentication for the identities
+    /// `resolver` returns a secret for.
+    pub fn mfa(&mut self, resolver: Arc<dyn MfaSecretResolver>) {
+        self.mfa = Some(resolver);
+    }
+
+    /// Replace the default in-memory `MfaChallengeStore`.
+    pub fn mfa_challenge_store(&mut self, store: Arc<dyn MfaChallengeStore>) {
+        self.mfa_challenges = store;
+    }
+
     pub fn build(self) -> AuthenticationBase<P> {
         AuthenticationBase {
+            keys: RwLock::new(KeyRing {
+                current: self.options.jwt.clone(),
+                retired: Vec::new(),
+            }),
             options: self.options,
             strategies: self.strategies,
             jwt: self.jwt,
+            refresh_tokens: self.refresh_tokens,
+            revoked_tokens: self.revoked_tokens,
+            login_attempts: self.login_attempts,
+            mfa: self.mfa,
+            mfa_challenges: self.mfa_challenges,
         }
     }
 }
@@ -274,6 +508,45 @@ where
     options: Arc<AuthOptions>,
     strategies: HashMap<String, Arc<dyn AuthenticationStrategy<P>>>,
     jwt: Arc<dyn JwtProvider>,
+    refresh_tokens: Arc<dyn RefreshTokenStore>,
+    revoked_tokens: Arc<dyn TokenRevocationStore>,
+    login_attempts: Arc<dyn AttemptStore>,
+    mfa: Option<Arc<dyn MfaSecretResolver>>,
+    mfa_challenges: Arc<dyn MfaChallengeStore>,
+    keys: RwLock<KeyRing>,
+}
+
+/// The key tokens are signed with, and the keys it replaced. Retired keys
+/// still verify tokens until those tokens could have expired.
+struct KeyRing {
+    current: JwtOptions,
+    retired: Vec<(JwtOptions, Instant)>,
+}
+
+impl KeyRing {
+    fn keys(&self) -> impl Iterator<Item = &JwtOptions> {
+        std::iter::once(&self.current).chain(self.retired.iter().map(|(jwt, _)| jwt))
+    }
+
+    /// How long a retired key keeps verifying: the longest token lifetime.
+    fn lifetime(&self) -> Duration {
+        self.current
+            .access_token_expires_in
+            .max(self.current.refresh_token_expires_in)
+    }
+
+    fn has_expired(&self) -> bool {
+        let lifetime = self.lifetime();
+        self.retired
+            .iter()
+            .any(|(_, retired_at)| retired_at.elapsed() >= lifetime)
+    }
+
+    fn drop_expired(&mut self) {
+        let lifetime = self.lifetime();
+        self.retired
+            .retain(|(_, retired_at)| retired_at.elapsed() < lifetime);
+    }
 }
 
 impl<P> AuthenticationBase<P>
@@ -288,8 +561,53 @@ where
         AuthenticationBuilder::new(builder, config_key, options)
     }
 
+    /// The options this was built with, with `jwt` reflecting the current
+    /// signing key.
     pub fn configuration(&self) -> AuthOptions {
-        (*self.options).clone()
+        let mut options = (*self.options).clone();
+        options.jwt = self.signing_jwt();
+        options
+    }
+
+    fn signing_jwt(&self) -> JwtOptions {
+        self.keys.read().unwrap().current.clone()
+    }
+
+    /// The key ring without expired retired keys. Takes the write lock only
+    /// when there is an expired key to drop.
+    fn live_keys(&self) -> RwLockReadGuard<'_, KeyRing> {
+        {
+            let keys = self.keys.read().unwrap();
+            if !keys.has_expired() {
+                return keys;
+            }
+        }
+        self.keys.write().unwrap().drop_expired();
+        self.keys.read().unwrap()
+    }
+
+    /// Sign new tokens with `key`. The previous key keeps verifying the
+    /// tokens it signed (selected by their `kid` header) and stays in
+    /// [`Self::jwks`] until those tokens have expired.
+    ///
+    /// Rotation is local to this instance; rotate every instance that
+    /// verifies the same tokens.
+    pub fn rotate_signing_key(&self, key: JwtKey) -> Result<()> {
+        let mut keys = self.keys.write().unwrap();
+        if keys
+            .keys()
+            .any(|k| k.key_id.as_deref() == Some(key.key_id.as_str()))
+        {
+            anyhow::bail!("JWT key id `{}` is already in use", key.key_id);
+        }
+        let next = key.apply_to(&keys.current);
+        next.validate().map_err(|e| anyhow::anyhow!(e))?;
+        self.jwt.load_key(&next)?;
+
+        let previous = std::mem::replace(&mut keys.current, next);
+        keys.retired.insert(0, (previous, Instant::now()));
+        keys.drop_expired();
+        Ok(())
     }
 
     pub fn strategy_names(&self) -> Vec<String> {
@@ -349,6 +667,8 @@ where
             .await
     }
 
+    /// Sign a refresh token without recording it; use
+    /// [`Self::issue_refresh_token`] for tokens that can be rotated.
     pub async fn create_refresh_token(
         &self,
         payload: Value,
@@ -359,7 +679,282 @@ where
     }
 
     pub async fn verify_access_token(&self, token: &str) -> Result<Value> {
-        self.verify_token(token, None).await
+        let claims = self.verify_token(token, None).await?;
+        if is_refresh(&claims) {
+            return Err(DogError::not_authenticated(
+                "A refresh token cannot be used as an access token",
+            )
+            .into_anyhow());
+        }
+        Ok(claims)
+    }
+
+    /// Create a refresh token for `payload` and record it in the
+    /// `RefreshTokenStore`, so it can be used once with [`Self::rotate_refresh_token`].
+    pub async fn issue_refresh_token(&self, payload: Value) -> Result<String> {
+        let (token, claims) = self.sign_token(payload, None, TokenType::Refresh)?;
+        let jti = claims
+            .get("jti")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        let exp = claims
+            .get("exp")
+            .and_then(|v| v.as_i64())
+            .unwrap_or_default();
+        self.refresh_tokens.insert(jti, exp).await?;
+        Ok(token)
+    }
+
+    pub async fn verify_refresh_token(&self, token: &str) -> Result<Value> {
+        let claims = self.verify_token(token, None).await?;
+        if !is_refresh(&claims) {
+            return Err(DogError::not_authenticated("Not a refresh token").into_anyhow());
+        }
+        Ok(claims)
+    }
+
+    /// Use up a refresh token and return its payload (the claims it was
+    /// issued with, minus the registered ones). Fails once the token has
+    /// been used or revoked, so a replayed token is rejected.
+    pub async fn rotate_refresh_token(&self, token: &str) -> Result<Value> {
+        let claims = self.verify_refresh_token(token).await?;
+        let jti = claims
+            .get("jti")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        if !self.refresh_tokens.take(jti).await? {
+            return Err(DogError::not_authenticated(
+                "Refresh token has already been used or was revoked",
+            )
+            .into_anyhow());
+        }
+
+        let mut payload = match claims {
+            Value::Object(m) => m,
+            _ => Map::new(),
+        };
+        for claim in ["iss", "aud", "iat", "exp", "jti", "typ"] {
+            payload.remove(claim);
+        }
+        Ok(Value::Object(payload))
+    }
+
+    /// Make a refresh token unusable, e.g. on logout.
+    pub async fn revoke_refresh_token(&self, token: &str) -> Result<()> {
+        let claims = self.verify_refresh_token(token).await?;
+        let jti = claims
+            .get("jti")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        self.refresh_tokens.revoke(jti).await
+    }
+
+    /// The PEM public key tokens are verified with, for RSA/ECDSA algorithms.
+    pub fn public_key_pem(&self) -> Result<Option<String>> {
+        let jwt = self.signing_jwt();
+        if jwt.is_hmac() {
+            return Ok(None);
+        }
+        jwt.public_key_pem().map_err(|e| anyhow::anyhow!(e))
+    }
+
+    /// JWK Set (`/.well-known/jwks.json`) other services can verify tokens
+    /// with: the current signing key and any retired keys whose tokens may
+    /// still be valid, each with its `kid`. Needs the private keys; HMAC keys
+    /// are left out.
+    pub fn jwks(&self) -> Result<Value> {
+        let keys = self.live_keys();
+
+        let mut out = Vec::new();
+        for jwt in keys.keys() {
+            if let Some(Value::Array(jwks)) = self.jwt.jwks(jwt)?.get_mut("keys").map(Value::take) {
+                out.extend(jwks);
+            }
+        }
+        Ok(json!({ "keys": out }))
+    }
+
+    /// Revoke the token with id `jti`. The id alone does not say when the
+    /// token expires, so it stays revoked for the longest configured token
+    /// lifetime; prefer [`Self::revoke_token`] when the token is at hand.
+    pub async fn revoke(&self, jti: &str) -> Result<()> {
+        let jwt = self.signing_jwt();
+        let ttl = jwt
+            .access_token_expires_in
+            .max(jwt.refresh_token_expires_in);
+        self.revoked_tokens.revoke(jti, ttl).await
+    }
+
+    /// Revoke `token` for the rest of its lifetime.
+    pub async fn revoke_token(&self, token: &str) -> Result<()> {
+        let claims = self.verify_token(token, None).await?;
+        let jti = claims
+            .get("jti")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| DogError::bad_request("Token has no `jti` claim").into_anyhow())?;
+        let exp = claims
+            .get("exp")
+            .and_then(|v| v.as_i64())
+            .unwrap_or_default();
+        let remaining = (exp - Utc::now().timestamp()).max(0) as u64;
+        self.revoked_tokens
+            .revoke(jti, Duration::from_secs(remaining))
+            .await
+    }
+
+    /// Refuse a login for `identity` while it is locked out, with
+    /// `TooManyRequests` (429). A no-op unless `login_throttle` is configured.
+    ///
+    /// Strategies that check secrets call this first, then
+    /// [`Self::record_login_failure`] or [`Self::record_login_success`].
+    pub async fn check_login_allowed(&self, identity: &str) -> Result<()> {
+        if self.options.login_throttle.is_none() {
+            return Ok(());
+        }
+        let now = Utc::now().timestamp_millis();
+        let locked_until = self
+            .login_attempts
+            .get(identity)
+            .await?
+            .and_then(|a| a.locked_until)
+            .filter(|until| *until > now);
+        match locked_until {
+            Some(until) => {
+                let retry_after = (until - now + 999) / 1000;
+                Err(DogError::too_many_requests(format!(
+                    "Too many failed login attempts, try again in {retry_after}s"
+                ))
+                .with_data(json!({ "retryAfter": retry_after }))
+                .with_source(LoginFailure::new(LoginFailureReason::Locked, identity).into())
+                .into_anyhow())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Count a failed login for `identity`, locking it once
+    /// `max_attempts` failures fall within `window`. Each lockout lasts twice
+    /// as long as the previous one, up to `max_lockout`.
+    pub async fn record_login_failure(&self, identity: &str) -> Result<()> {
+        let Some(throttle) = self.options.login_throttle.as_ref() else {
+            return Ok(());
+        };
+        let mut attempts = self
+            .login_attempts
+            .increment(identity, throttle.window)
+            .await?;
+        if attempts.failures < throttle.max_attempts {
+            return Ok(());
+        }
+
+        let now = Utc::now().timestamp_millis();
+        attempts.lockouts += 1;
+        attempts.failures = 0;
+        let lockout = throttle.lockout_for(attempts.lockouts);
+        attempts.locked_until = Some(now + lockout.as_millis() as i64);
+
+        let ttl = Self::attempts_ttl(throttle, &attempts, now);
+        self.login_attempts.put(identity, attempts, ttl).await
+    }
+
+    /// Forget the failed logins of `identity`.
+    pub async fn record_login_success(&self, identity: &str) -> Result<()> {
+        if self.options.login_throttle.is_none() {
+            return Ok(());
+        }
+        self.login_attempts.clear(identity).await
+    }
+
+    /// Keep the record while its window or lockout runs, and for a window
+    /// after that so a quick relapse escalates the lockout.
+    fn attempts_ttl(
+        throttle: &LoginThrottleOptions,
+        attempts: &LoginAttempts,
+        now: i64,
+    ) -> Duration {
+        let locked = attempts
+            .locked_until
+            .map_or(0, |until| (until - now).max(0) as u64);
+        Duration::from_millis(locked) + throttle.window
+    }
+
+    /// Start the second factor for a primary authentication `result`, if its
+    /// identity has one. Returns the id of the challenge to complete with
+    /// [`Self::complete_mfa`], or `None` when no second factor is needed.
+    pub async fn start_mfa(
+        &self,
+        result: &AuthenticationResult,
+        payload: Value,
+    ) -> Result<Option<String>> {
+        let Some(resolver) = self.mfa.as_ref() else {
+            return Ok(None);
+        };
+        let Some(factor) = resolver.totp_factor(result).await? else {
+            return Ok(None);
+        };
+
+        let options = &self.options.mfa;
+        let id = Uuid::new_v4().to_string();
+        let challenge = MfaChallenge {
+            factor,
+            result: result.clone(),
+            payload,
+            attempts: 0,
+            expires_at: Utc::now().timestamp() + options.challenge_expires_in.as_secs() as i64,
+        };
+        self.mfa_challenges
+            .insert(&id, challenge, options.challenge_expires_in)
+            .await?;
+        Ok(Some(id))
+    }
+
+    /// Check the TOTP `code` for `challenge` and return the challenge, whose
+    /// primary result and payload the caller turns into tokens.
+    ///
+    /// A challenge completes once. Codes outside the skew window, codes
+    /// already used by the identity and unknown or expired challenges fail
+    /// with `NotAuthenticated`; so does the challenge after `max_attempts`
+    /// wrong codes.
+    pub async fn complete_mfa(&self, challenge: &str, code: &str) -> Result<MfaChallenge> {
+        let invalid = || DogError::not_authenticated("Invalid or expired MFA challenge");
+        let mut pending = self
+            .mfa_challenges
+            .take(challenge)
+            .await?
+            .ok_or_else(|| invalid().into_anyhow())?;
+        let now = Utc::now().timestamp();
+        if pending.expires_at <= now {
+            return Err(invalid().into_anyhow());
+        }
+
+        let options = &self.options.mfa;
+        let step = verify_totp_at(&pending.factor.secret, code, options, now);
+        let fresh = match step {
+            Some(step) => {
+                // Long enough for the step to fall out of the skew window.
+                let used_for = options.period * (2 * options.skew + 1);
+                self.mfa_challenges
+                    .use_step(&pending.factor.identity, step, used_for)
+                    .await?
+            }
+            None => false,
+        };
+        if !fresh {
+            pending.attempts += 1;
+            if pending.attempts < options.max_attempts {
+                let remaining = Duration::from_secs((pending.expires_at - now) as u64);
+                self.mfa_challenges
+                    .insert(challenge, pending, remaining)
+                    .await?;
+            }
+            let message = if step.is_some() {
+                "MFA code has already been used"
+            } else {
+                "Invalid MFA code"
+            };
+            return Err(DogError::not_authenticated(message).into_anyhow());
+        }
+        Ok(pending)
     }
 
     async fn create_token(
@@ -368,6 +963,16 @@ where
         overrides: Option<JwtOverrides>,
         default_type: TokenType,
     ) -> Result<String> {
+        self.sign_token(payload, overrides, default_type)
+            .map(|(token, _)| token)
+    }
+
+    fn sign_token(
+        &self,
+        payload: Value,
+        overrides: Option<JwtOverrides>,
+        default_type: TokenType,
+    ) -> Result<(String, Map<String, Value>)> {
         let cfg = self.configuration();
         let jwt = cfg.jwt;
 
@@ -412,18 +1017,46 @@ where
         claims.insert("iat".to_string(), Value::Number(now.into()));
         claims.insert("exp".to_string(), Value::Number(exp.into()));
         claims.insert("jti".to_string(), Value::String(jti));
+        if token_type == TokenType::Refresh {
+            claims.insert("typ".to_string(), Value::String("refresh".to_string()));
+        }
 
         // Merge custom claims from config (config overrides payload)
         for (k, v) in jwt.custom_claims.clone() {
             claims.insert(k, v);
         }
 
-        self.jwt.sign(&jwt, claims, token_type)
+        let token = self.jwt.sign(&jwt, claims.clone(), token_type)?;
+        Ok((token, claims))
+    }
+
+    /// The key whose `kid` matches the token's; tokens without a `kid` use
+    /// the key configured without one.
+    fn verifying_jwt(&self, token: &str) -> Result<JwtOptions> {
+        let kid = self.jwt.key_id(token);
+        let jwt = self
+            .live_keys()
+            .keys()
+            .find(|jwt| jwt.key_id == kid)
+            .cloned();
+        jwt.ok_or_else(|| {
+            DogError::not_authenticated("Token was signed with an unknown key").into_anyhow()
+        })
     }
 
     async fn verify_token(&self, token: &str, overrides: Option<JwtOverrides>) -> Result<Value> {
-        let cfg = self.configuration();
-        let jwt = cfg.jwt;
-        self.jwt.verify(&jwt, token, overrides.as_ref())
+        let jwt = self.verifying_jwt(token)?;
+        let claims = self.jwt.verify(&jwt, token, overrides.as_ref())?;
+
+        if let Some(jti) = claims.get("jti").and_then(|v| v.as_str()) {
+            if self.revoked_tokens.is_revoked(jti).await? {
+                return Err(DogError::not_authenticated("Token has been revoked").into_anyhow());
+            }
+        }
+        Ok(claims)
     }
 }
+
+fn is_refresh(claims: &Value) -> bool {
+    claims.get("typ").and_then(|v| v.as_str()) == Some("refresh")
+}
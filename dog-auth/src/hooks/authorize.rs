@@ -0,0 +1,172 @@
+// Authorize hook.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use dog_core::errors::DogError;
+use dog_core::hooks::DogBeforeHook;
+use dog_core::HookContext;
+use serde_json::Value;
+
+use crate::core::AuthenticationResult;
+
+pub trait AuthorizeHookParams: Clone + Send + Sync {
+    fn provider(&self) -> Option<&str>;
+    fn auth_result(&self) -> Option<&AuthenticationResult>;
+}
+
+impl<P> AuthorizeHookParams for super::authenticate::AuthParams<P>
+where
+    P: Clone + Send + Sync,
+{
+    fn provider(&self) -> Option<&str> {
+        self.provider.as_deref()
+    }
+
+    fn auth_result(&self) -> Option<&AuthenticationResult> {
+        self.auth_result.as_ref()
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Requirement {
+    AnyRole(Vec<String>),
+    Permission(String),
+}
+
+/// Rejects external calls whose authenticated identity lacks a role or
+/// permission, with `Forbidden` (403).
+///
+/// Run it after `AuthenticateHook`. Roles and permissions are read from the
+/// `roles` / `permissions` field of the authenticated entity (`"user"` by
+/// default, as attached by the local and JWT strategies), falling back to
+/// the token payload's claims. A field may hold a string or an array of
+/// strings. Internal calls (no provider) are let through.
+pub struct AuthorizeHook<P>
+where
+    P: AuthorizeHookParams + 'static,
+{
+    _marker: std::marker::PhantomData<P>,
+    requirement: Requirement,
+    entity: String,
+    field: String,
+}
+
+impl<P> AuthorizeHook<P>
+where
+    P: AuthorizeHookParams + 'static,
+{
+    /// Require at least one of `roles`.
+    pub fn roles<I, S>(roles: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::new(
+            Requirement::AnyRole(roles.into_iter().map(Into::into).collect()),
+            "roles",
+        )
+    }
+
+    /// Require `permission`. A granted `"posts:*"` covers every `posts:`
+    /// permission, and `"*"` covers all of them.
+    pub fn permission(permission: impl Into<String>) -> Self {
+        Self::new(Requirement::Permission(permission.into()), "permissions")
+    }
+
+    fn new(requirement: Requirement, field: &str) -> Self {
+        Self {
+            _marker: std::marker::PhantomData,
+            requirement,
+            entity: "user".to_string(),
+            field: field.to_string(),
+        }
+    }
+
+    /// Key of the entity in the authentication result (the auth
+    /// configuration's `entity`).
+    pub fn with_entity(mut self, entity: impl Into<String>) -> Self {
+        self.entity = entity.into();
+        self
+    }
+
+    /// Field holding the roles or permissions.
+    pub fn with_field(mut self, field: impl Into<String>) -> Self {
+        self.field = field.into();
+        self
+    }
+
+    fn granted<'a>(&self, auth_result: &'a AuthenticationResult) -> Vec<&'a str> {
+        let value = [self.entity.as_str(), "payload"]
+            .iter()
+            .find_map(|key| auth_result.get(*key)?.get(&self.field));
+        match value {
+            Some(Value::String(s)) => vec![s.as_str()],
+            Some(Value::Array(items)) => items.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn allows(&self, granted: &[&str]) -> bool {
+        match &self.requirement {
+            Requirement::AnyRole(roles) => roles.iter().any(|r| granted.contains(&r.as_str())),
+            Requirement::Permission(permission) => granted.iter().any(|g| {
+                *g == "*"
+                    || *g == permission
+                    || g.strip_suffix('*').is_some_and(|prefix| {
+                        prefix.ends_with(':') && permission.starts_with(prefix)
+                    })
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl<P> DogBeforeHook<Value, P> for AuthorizeHook<P>
+where
+    P: AuthorizeHookParams + Clone + Send + Sync + 'static,
+{
+    async fn run(&self, ctx: &mut HookContext<Value, P>) -> Result<()> {
+        let provider = ctx.params.provider().unwrap_or("");
+        if provider.trim().is_empty() {
+            // Internal call: allow through.
+            return Ok(());
+        }
+
+        let Some(auth_result) = ctx.params.auth_result() else {
+            return Err(DogError::not_authenticated("Not authenticated").into_anyhow());
+        };
+
+        if self.allows(&self.granted(auth_result)) {
+            return Ok(());
+        }
+        let message = match &self.requirement {
+            Requirement::AnyRole(roles) => {
+                format!("Requires one of the roles: {}", roles.join(", "))
+            }
+            Requirement::Permission(permission) => {
+                format!("Requires the '{permission}' permission")
+            }
+        };
+        Err(DogError::forbidden(message).into_anyhow())
+    }
+}
+
+/// `AuthorizeHook::roles`, ready for `before_*`.
+pub fn require_roles<P, I, S>(roles: I) -> Arc<dyn DogBeforeHook<Value, P>>
+where
+    P: AuthorizeHookParams + 'static,
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    Arc::new(AuthorizeHook::roles(roles))
+}
+
+/// `AuthorizeHook::permission`, ready for `before_*`.
+pub fn require_permission<P>(permission: impl Into<String>) -> Arc<dyn DogBeforeHook<Value, P>>
+where
+    P: AuthorizeHookParams + 'static,
+{
+    Arc::new(AuthorizeHook::permission(permission))
+}
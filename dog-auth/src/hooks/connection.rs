@@ -60,7 +60,7 @@ where
 
         let auth_result: AuthenticationResult = match result {
             dog_core::HookResult::One(v) => v.clone(),
-            dog_core::HookResult::Many(vs) => {
+            dog_core::HookResult::Many(vs) | dog_core::HookResult::Page { data: vs, .. } => {
                 serde_json::to_value(vs).map_err(|e| anyhow::anyhow!(e))?
             }
         };
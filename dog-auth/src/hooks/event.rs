@@ -4,8 +4,10 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use async_trait::async_trait;
+use dog_core::errors::{DogError, ErrorKind};
 use dog_core::hooks::DogAfterHook;
 use dog_core::HookContext;
+use serde::Serialize;
 use serde_json::Value;
 
 use crate::core::{AuthenticationResult, ConnectionEvent};
@@ -69,7 +71,7 @@ where
 
         let auth_result: AuthenticationResult = match result {
             dog_core::HookResult::One(v) => v.clone(),
-            dog_core::HookResult::Many(vs) => {
+            dog_core::HookResult::Many(vs) | dog_core::HookResult::Page { data: vs, .. } => {
                 serde_json::to_value(vs).map_err(|e| anyhow::anyhow!(e))?
             }
         };
@@ -87,3 +89,110 @@ where
         Ok(())
     }
 }
+
+/// Structured authentication events, emitted on the app event hub under
+/// the `authentication` path by `AuthServiceAdapter`:
+///
+/// ```ignore
+/// builder.on_str("authentication user.login_failed", Arc::new(|data, _ctx| {
+///     Box::pin(async move {
+///         if let ServiceEventData::Custom(event) = data {
+///             let event = event.downcast_ref::<AuthEvent>().unwrap();
+///             // audit log, anomaly detection, ...
+///         }
+///         Ok(())
+///     })
+/// }))?;
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum AuthEventKind {
+    #[serde(rename = "user.login")]
+    Login,
+    #[serde(rename = "user.login_failed")]
+    LoginFailed,
+    #[serde(rename = "user.logout")]
+    Logout,
+    #[serde(rename = "token.refreshed")]
+    TokenRefreshed,
+}
+
+impl AuthEventKind {
+    /// Event name on the hub.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthEventKind::Login => "user.login",
+            AuthEventKind::LoginFailed => "user.login_failed",
+            AuthEventKind::Logout => "user.logout",
+            AuthEventKind::TokenRefreshed => "token.refreshed",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AuthEvent {
+    pub kind: AuthEventKind,
+    pub tenant: String,
+    /// The token subject (entity id) after a successful login, or the
+    /// strategy-qualified login name (e.g. `local:alice@example.com`) after
+    /// a failed one, when known.
+    pub identity: Option<String>,
+    pub strategy: Option<String>,
+    /// Set for `user.login_failed`.
+    pub reason: Option<LoginFailureReason>,
+}
+
+/// Why a login failed, for audit trails only: clients get the same
+/// `NotAuthenticated` error whatever the reason.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoginFailureReason {
+    UnknownUser,
+    BadPassword,
+    Locked,
+    /// Any other rejected credentials (bad token, wrong MFA code, ...).
+    InvalidCredentials,
+}
+
+/// A failed login, attached by strategies as the source of their
+/// `DogError` (which is never sent to clients).
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("login failed: {reason:?}")]
+pub struct LoginFailure {
+    pub reason: LoginFailureReason,
+    pub identity: Option<String>,
+}
+
+impl LoginFailure {
+    pub fn new(reason: LoginFailureReason, identity: impl Into<String>) -> Self {
+        Self {
+            reason,
+            identity: Some(identity.into()),
+        }
+    }
+
+    /// `DogError::not_authenticated(message)` carrying this failure.
+    pub fn into_error(self, message: impl Into<String>) -> anyhow::Error {
+        DogError::not_authenticated(message)
+            .with_source(self.into())
+            .into_anyhow()
+    }
+
+    /// The failure behind a login error; `None` for errors that are not
+    /// about the credentials (e.g. a database outage).
+    pub fn from_error(err: &anyhow::Error) -> Option<Self> {
+        let dog = DogError::from_anyhow(err)?;
+        let fallback = match dog.kind {
+            ErrorKind::NotAuthenticated => LoginFailureReason::InvalidCredentials,
+            ErrorKind::TooManyRequests => LoginFailureReason::Locked,
+            _ => return None,
+        };
+        let attached = dog
+            .source_ref()
+            .and_then(|source| source.downcast_ref::<LoginFailure>())
+            .cloned();
+        Some(attached.unwrap_or(LoginFailure {
+            reason: fallback,
+            identity: None,
+        }))
+    }
+}
@@ -1,7 +1,9 @@
 pub mod authenticate;
+pub mod authorize;
 pub mod connection;
 pub mod event;
 
 pub use authenticate::*;
+pub use authorize::*;
 pub use connection::*;
 pub use event::*;
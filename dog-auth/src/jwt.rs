@@ -173,7 +173,9 @@ where
                 })?
                 .to_string();
 
-            let svc = ctx.services.service(&service_name)?;
+            // The service itself, not its hooks: entity lookups must see fields
+            // that after hooks hide from clients.
+            let svc = ctx.services.service(&service_name)?.inner().clone();
             let entity = svc.get(&ctx.tenant, &entity_id, ctx.params.clone()).await?;
 
             if let Some(map) = out.as_object_mut() {
@@ -3,15 +3,26 @@
 pub mod core;
 pub mod hooks;
 pub mod jwt;
+pub mod mfa;
 pub mod options;
+pub mod password;
+pub mod refresh;
+pub mod revocation;
 pub mod service;
 pub mod service_adapter;
 pub mod strategy;
+pub mod tenant;
+pub mod throttle;
 
 pub use core::*;
 pub use hooks::*;
 pub use jwt::*;
+pub use mfa::*;
 pub use options::*;
+pub use refresh::*;
+pub use revocation::*;
 pub use service::*;
 pub use service_adapter::*;
 pub use strategy::*;
+pub use tenant::*;
+pub use throttle::*;
@@ -0,0 +1,214 @@
+// Multi-factor authentication (TOTP).
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use password_hash::rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha1::Sha1;
+
+use crate::core::AuthenticationResult;
+use crate::options::MfaOptions;
+
+/// A new random TOTP secret (160 bits, base32), to store for the identity
+/// and show to the user through [`otpauth_uri`].
+pub fn generate_totp_secret() -> String {
+    let mut bytes = [0u8; 20];
+    OsRng.fill_bytes(&mut bytes);
+    BASE32_NOPAD.encode(&bytes)
+}
+
+/// `otpauth://` URI for provisioning `secret` in an authenticator app,
+/// usually rendered as a QR code.
+pub fn otpauth_uri(secret: &str, account: &str, options: &MfaOptions) -> String {
+    let label = format!("{}:{}", options.issuer, account);
+    format!(
+        "otpauth://totp/{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        uri_encode(&label),
+        secret,
+        uri_encode(&options.issuer),
+        options.digits,
+        options.period.as_secs()
+    )
+}
+
+/// The TOTP code (RFC 6238, HMAC-SHA1) for `secret` at `unix_time`.
+pub fn totp_code(secret: &str, unix_time: i64, options: &MfaOptions) -> Result<String> {
+    let key = decode_secret(secret)?;
+    Ok(hotp(&key, time_step(unix_time, options), options.digits))
+}
+
+/// Check `code` against `secret` now, with the default [`MfaOptions`].
+pub fn verify_totp(secret: &str, code: &str) -> bool {
+    verify_totp_at(secret, code, &MfaOptions::default(), Utc::now().timestamp()).is_some()
+}
+
+/// Check `code` against `secret` at `unix_time`, accepting `options.skew`
+/// time steps either side. Returns the matching time step, which callers
+/// remember to reject the code if it is replayed.
+pub fn verify_totp_at(
+    secret: &str,
+    code: &str,
+    options: &MfaOptions,
+    unix_time: i64,
+) -> Option<u64> {
+    let key = decode_secret(secret).ok()?;
+    let code = code.trim();
+    if code.len() != options.digits as usize {
+        return None;
+    }
+    let current = time_step(unix_time, options);
+    let skew = u64::from(options.skew);
+    (current.saturating_sub(skew)..=current.saturating_add(skew))
+        .find(|step| constant_time_eq(&hotp(&key, *step, options.digits), code))
+}
+
+fn time_step(unix_time: i64, options: &MfaOptions) -> u64 {
+    unix_time.max(0) as u64 / options.period.as_secs().max(1)
+}
+
+fn hotp(key: &[u8], counter: u64, digits: u32) -> String {
+    let mut mac = Hmac::<Sha1>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(&counter.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let value = u32::from_be_bytes([
+        hash[offset] & 0x7f,
+        hash[offset + 1],
+        hash[offset + 2],
+        hash[offset + 3],
+    ]);
+    format!(
+        "{:0width$}",
+        value % 10u32.pow(digits),
+        width = digits as usize
+    )
+}
+
+fn decode_secret(secret: &str) -> Result<Vec<u8>> {
+    let normalized: String = secret
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '=')
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    BASE32_NOPAD
+        .decode(normalized.as_bytes())
+        .map_err(|e| anyhow::anyhow!("Invalid TOTP secret: {e}"))
+}
+
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+fn uri_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+/// An identity's TOTP secret.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MfaFactor {
+    /// Stable id of the identity (e.g. the user id), used to reject replayed
+    /// codes across logins.
+    pub identity: String,
+    pub secret: String,
+}
+
+/// Decides whether a primary authentication needs a second factor.
+///
+/// Registered with `auth_builder.mfa(...)`. Return `None` for identities
+/// without MFA; their logins complete as before.
+#[async_trait]
+pub trait MfaSecretResolver: Send + Sync {
+    async fn totp_factor(&self, result: &AuthenticationResult) -> Result<Option<MfaFactor>>;
+}
+
+/// A primary authentication waiting for its TOTP code.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MfaChallenge {
+    pub factor: MfaFactor,
+    /// The primary authentication result, returned once the code checks out.
+    pub result: AuthenticationResult,
+    /// The JWT payload to sign once the code checks out.
+    pub payload: Value,
+    /// Wrong codes entered so far.
+    pub attempts: u32,
+    /// Unix seconds after which the challenge is void.
+    pub expires_at: i64,
+}
+
+/// Pending MFA challenges and the last time step used per identity.
+///
+/// `take` must be atomic so that one challenge cannot be completed twice.
+/// Share one store between instances for a challenge started on one to be
+/// completed on another.
+#[async_trait]
+pub trait MfaChallengeStore: Send + Sync {
+    async fn insert(&self, id: &str, challenge: MfaChallenge, ttl: Duration) -> Result<()>;
+
+    async fn take(&self, id: &str) -> Result<Option<MfaChallenge>>;
+
+    /// Record `step` as used by `identity`. Returns `false` if that step or a
+    /// later one was already used, i.e. the code is a replay.
+    async fn use_step(&self, identity: &str, step: u64, ttl: Duration) -> Result<bool>;
+}
+
+/// In-process `MfaChallengeStore`, the default.
+#[derive(Default)]
+pub struct MemoryMfaChallengeStore {
+    challenges: Mutex<HashMap<String, (MfaChallenge, Instant)>>,
+    used_steps: Mutex<HashMap<String, (u64, Instant)>>,
+}
+
+impl MemoryMfaChallengeStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl MfaChallengeStore for MemoryMfaChallengeStore {
+    async fn insert(&self, id: &str, challenge: MfaChallenge, ttl: Duration) -> Result<()> {
+        let now = Instant::now();
+        let mut challenges = self.challenges.lock().unwrap();
+        challenges.retain(|_, (_, until)| *until > now);
+        challenges.insert(id.to_string(), (challenge, now + ttl));
+        Ok(())
+    }
+
+    async fn take(&self, id: &str) -> Result<Option<MfaChallenge>> {
+        let mut challenges = self.challenges.lock().unwrap();
+        Ok(challenges
+            .remove(id)
+            .filter(|(_, until)| *until > Instant::now())
+            .map(|(challenge, _)| challenge))
+    }
+
+    async fn use_step(&self, identity: &str, step: u64, ttl: Duration) -> Result<bool> {
+        let now = Instant::now();
+        let mut used = self.used_steps.lock().unwrap();
+        used.retain(|_, (_, until)| *until > now);
+        if used.get(identity).is_some_and(|(last, _)| *last >= step) {
+            return Ok(false);
+        }
+        used.insert(identity.to_string(), (step, now + ttl));
+        Ok(true)
+    }
+}
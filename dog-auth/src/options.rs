@@ -74,6 +74,10 @@ pub struct AuthOptions {
     pub oauth_providers: HashMap<String, OAuthProvider>,
     /// API key configuration
     pub api_key: ApiKeyOptions,
+    /// Lock out identities after repeated failed logins; off when `None`
+    pub login_throttle: Option<LoginThrottleOptions>,
+    /// TOTP second factor settings (used once an MFA resolver is registered)
+    pub mfa: MfaOptions,
 }
 
 impl Default for AuthOptions {
@@ -86,6 +90,8 @@ impl Default for AuthOptions {
             jwt: JwtOptions::default(),
             oauth_providers: HashMap::new(),
             api_key: ApiKeyOptions::default(),
+            login_throttle: None,
+            mfa: MfaOptions::default(),
         }
     }
 }
@@ -133,6 +139,16 @@ impl AuthOptions {
                 .map_err(|e| format!("API key validation failed: {}", e))?;
         }
 
+        if let Some(ref throttle) = self.login_throttle {
+            throttle
+                .validate()
+                .map_err(|e| format!("Login throttle validation failed: {}", e))?;
+        }
+
+        self.mfa
+            .validate()
+            .map_err(|e| format!("MFA validation failed: {}", e))?;
+
         // Check for duplicate custom strategy names
         let mut custom_strategies = Vec::new();
         for strategy in &self.strategies {
@@ -177,6 +193,13 @@ pub struct JwtOptions {
     pub custom_claims: HashMap<String, serde_json::Value>,
     /// JWT signing secret (for HMAC algorithms)
     pub secret: Option<String>,
+    /// Key id, sent as the `kid` header and in the JWKS so verifiers can
+    /// pick the right key after a rotation
+    pub key_id: Option<String>,
+    /// PEM private key used to sign tokens (for RSA/ECDSA algorithms)
+    pub private_key: Option<String>,
+    /// PEM public key used to verify tokens (for RSA/ECDSA algorithms)
+    pub public_key: Option<String>,
     /// Path to private key file (for RSA/ECDSA algorithms)
     pub private_key_path: Option<String>,
     /// Path to public key file (for RSA/ECDSA algorithms)
@@ -194,6 +217,9 @@ impl Default for JwtOptions {
             token_type: TokenType::default(),
             custom_claims: HashMap::new(),
             secret: None,
+            key_id: None,
+            private_key: None,
+            public_key: None,
             private_key_path: None,
             public_key_path: None,
         }
@@ -201,6 +227,34 @@ impl Default for JwtOptions {
 }
 
 impl JwtOptions {
+    /// Whether tokens are signed with a shared secret rather than a key pair.
+    pub fn is_hmac(&self) -> bool {
+        matches!(
+            self.algorithm,
+            JwtAlgorithm::HS256 | JwtAlgorithm::HS384 | JwtAlgorithm::HS512
+        )
+    }
+
+    /// The PEM private key, from `private_key` or `private_key_path`.
+    pub fn private_key_pem(&self) -> Result<Option<String>, String> {
+        Self::pem(&self.private_key, &self.private_key_path)
+    }
+
+    /// The PEM public key, from `public_key` or `public_key_path`.
+    pub fn public_key_pem(&self) -> Result<Option<String>, String> {
+        Self::pem(&self.public_key, &self.public_key_path)
+    }
+
+    fn pem(inline: &Option<String>, path: &Option<String>) -> Result<Option<String>, String> {
+        match (inline, path) {
+            (Some(pem), _) => Ok(Some(pem.clone())),
+            (None, Some(path)) => std::fs::read_to_string(path)
+                .map(Some)
+                .map_err(|e| format!("Failed to read key file {path}: {e}")),
+            (None, None) => Ok(None),
+        }
+    }
+
     /// Validate JWT configuration
     pub fn validate(&self) -> Result<(), String> {
         if self.issuer.is_empty() {
@@ -223,8 +277,12 @@ impl JwtOptions {
             | JwtAlgorithm::RS512
             | JwtAlgorithm::ES256
             | JwtAlgorithm::ES384 => {
-                if self.private_key_path.is_none() && self.public_key_path.is_none() {
-                    return Err("RSA/ECDSA algorithms require key files".to_string());
+                if self.private_key.is_none()
+                    && self.private_key_path.is_none()
+                    && self.public_key.is_none()
+                    && self.public_key_path.is_none()
+                {
+                    return Err("RSA/ECDSA algorithms require a private or public key".to_string());
                 }
             }
         }
@@ -241,6 +299,40 @@ impl JwtOptions {
     }
 }
 
+/// A new signing key for `AuthenticationBase::rotate_signing_key`.
+///
+/// Replaces the algorithm and key material of `JwtOptions`; issuer,
+/// audience and lifetimes stay as configured.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct JwtKey {
+    /// Key id (`kid`); must differ from the ids of the keys it replaces
+    pub key_id: String,
+    /// JWT signing algorithm
+    pub algorithm: JwtAlgorithm,
+    /// Signing secret (for HMAC algorithms)
+    pub secret: Option<String>,
+    /// PEM private key (for RSA/ECDSA algorithms)
+    pub private_key: Option<String>,
+    /// PEM public key; derived from `private_key` when unset
+    pub public_key: Option<String>,
+}
+
+impl JwtKey {
+    /// `jwt` signing with this key instead.
+    pub fn apply_to(&self, jwt: &JwtOptions) -> JwtOptions {
+        JwtOptions {
+            algorithm: self.algorithm.clone(),
+            key_id: Some(self.key_id.clone()),
+            secret: self.secret.clone(),
+            private_key: self.private_key.clone(),
+            public_key: self.public_key.clone(),
+            private_key_path: None,
+            public_key_path: None,
+            ..jwt.clone()
+        }
+    }
+}
+
 /// OAuth provider configuration
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct OAuthProvider {
@@ -362,6 +454,111 @@ impl ApiKeyOptions {
     }
 }
 
+/// Failed login throttling options
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LoginThrottleOptions {
+    /// Failed attempts allowed within `window` before the identity is locked
+    pub max_attempts: u32,
+    /// Period failed attempts are counted over
+    #[serde(with = "humantime_serde")]
+    pub window: Duration,
+    /// First lockout; each further lockout doubles it
+    #[serde(with = "humantime_serde")]
+    pub lockout: Duration,
+    /// Upper bound for the doubled lockout
+    #[serde(with = "humantime_serde")]
+    pub max_lockout: Duration,
+}
+
+impl Default for LoginThrottleOptions {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            window: Duration::from_secs(900), // 15 minutes
+            lockout: Duration::from_secs(60),
+            max_lockout: Duration::from_secs(3600), // 1 hour
+        }
+    }
+}
+
+impl LoginThrottleOptions {
+    /// Validate login throttle configuration
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_attempts == 0 {
+            return Err("max_attempts must be at least 1".to_string());
+        }
+
+        if self.window.is_zero() || self.lockout.is_zero() {
+            return Err("window and lockout must be greater than zero".to_string());
+        }
+
+        if self.max_lockout < self.lockout {
+            return Err("max_lockout cannot be shorter than lockout".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// How long the `n`th lockout (1-based) lasts.
+    pub fn lockout_for(&self, n: u32) -> Duration {
+        let factor = 2u32.saturating_pow(n.saturating_sub(1));
+        self.lockout
+            .checked_mul(factor)
+            .map_or(self.max_lockout, |d| d.min(self.max_lockout))
+    }
+}
+
+/// TOTP multi-factor authentication options
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MfaOptions {
+    /// Issuer shown by authenticator apps
+    pub issuer: String,
+    /// Code length (6 to 8)
+    pub digits: u32,
+    /// Time step each code is valid for
+    #[serde(with = "humantime_serde")]
+    pub period: Duration,
+    /// Time steps accepted before and after the current one, for clock drift
+    pub skew: u32,
+    /// How long a pending challenge can be completed
+    #[serde(with = "humantime_serde")]
+    pub challenge_expires_in: Duration,
+    /// Wrong codes allowed per challenge
+    pub max_attempts: u32,
+}
+
+impl Default for MfaOptions {
+    fn default() -> Self {
+        Self {
+            issuer: "DogRS".to_string(),
+            digits: 6,
+            period: Duration::from_secs(30),
+            skew: 1,
+            challenge_expires_in: Duration::from_secs(300), // 5 minutes
+            max_attempts: 5,
+        }
+    }
+}
+
+impl MfaOptions {
+    /// Validate MFA configuration
+    pub fn validate(&self) -> Result<(), String> {
+        if !(6..=8).contains(&self.digits) {
+            return Err("TOTP codes must have 6 to 8 digits".to_string());
+        }
+
+        if self.period.as_secs() == 0 {
+            return Err("TOTP period must be at least one second".to_string());
+        }
+
+        if self.max_attempts == 0 {
+            return Err("max_attempts must be at least 1".to_string());
+        }
+
+        Ok(())
+    }
+}
+
 /// Builder pattern for AuthOptions configuration
 #[derive(Clone, Debug, Default)]
 pub struct AuthOptionsBuilder {
@@ -369,6 +566,8 @@ pub struct AuthOptionsBuilder {
     jwt: Option<JwtOptions>,
     oauth_providers: HashMap<String, OAuthProvider>,
     api_key: Option<ApiKeyOptions>,
+    login_throttle: Option<LoginThrottleOptions>,
+    mfa: Option<MfaOptions>,
 }
 
 impl AuthOptionsBuilder {
@@ -407,6 +606,18 @@ impl AuthOptionsBuilder {
         self
     }
 
+    /// Configure failed login throttling
+    pub fn login_throttle(mut self, login_throttle: LoginThrottleOptions) -> Self {
+        self.login_throttle = Some(login_throttle);
+        self
+    }
+
+    /// Configure TOTP multi-factor authentication
+    pub fn mfa(mut self, mfa_options: MfaOptions) -> Self {
+        self.mfa = Some(mfa_options);
+        self
+    }
+
     /// Build the final AuthOptions configuration
     pub fn build(self) -> AuthOptions {
         AuthOptions {
@@ -421,6 +632,8 @@ impl AuthOptionsBuilder {
             jwt: self.jwt.unwrap_or_default(),
             oauth_providers: self.oauth_providers,
             api_key: self.api_key.unwrap_or_default(),
+            login_throttle: self.login_throttle,
+            mfa: self.mfa.unwrap_or_default(),
         }
     }
 
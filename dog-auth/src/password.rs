@@ -0,0 +1,61 @@
+// Password hashing.
+
+use argon2::{Algorithm, Argon2, Params, PasswordHash, PasswordHasher, PasswordVerifier, Version};
+use password_hash::rand_core::OsRng;
+use password_hash::SaltString;
+
+fn argon2() -> Argon2<'static> {
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, Params::default())
+}
+
+/// Hash `plain` with argon2id and a random salt, as a PHC string
+/// (`$argon2id$v=19$m=...`).
+pub fn hash_password(plain: &str) -> String {
+    let salt = SaltString::generate(&mut OsRng);
+    argon2()
+        .hash_password(plain.as_bytes(), &salt)
+        .expect("argon2id with the default parameters accepts any password")
+        .to_string()
+}
+
+/// Check `plain` against a hash from [`hash_password`].
+///
+/// bcrypt hashes (`$2a$`, `$2b$`, `$2y$`) from before the switch to argon2id
+/// are still accepted. A malformed hash never matches.
+pub fn verify_password(plain: &str, hash: &str) -> bool {
+    if is_bcrypt(hash) {
+        return bcrypt::verify(plain, hash).unwrap_or(false);
+    }
+    match PasswordHash::new(hash) {
+        Ok(parsed) => argon2().verify_password(plain.as_bytes(), &parsed).is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// Whether `hash` should be replaced by a fresh [`hash_password`] the next
+/// time the plain password is at hand (e.g. after a successful login): true
+/// for bcrypt hashes and for argon2 hashes made with other parameters.
+pub fn needs_rehash(hash: &str) -> bool {
+    if is_bcrypt(hash) {
+        return true;
+    }
+    let Ok(parsed) = PasswordHash::new(hash) else {
+        return true;
+    };
+    if parsed.algorithm != Algorithm::Argon2id.ident()
+        || parsed.version != Some(Version::V0x13.into())
+    {
+        return true;
+    }
+    let current = Params::default();
+    Params::try_from(&parsed).map_or(true, |params| {
+        (params.m_cost(), params.t_cost(), params.p_cost())
+            != (current.m_cost(), current.t_cost(), current.p_cost())
+    })
+}
+
+fn is_bcrypt(hash: &str) -> bool {
+    ["$2a$", "$2b$", "$2x$", "$2y$"]
+        .iter()
+        .any(|prefix| hash.starts_with(prefix))
+}
@@ -0,0 +1,95 @@
+// Refresh token bookkeeping.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Mutex;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+
+/// Records which refresh tokens (by `jti`) are still usable.
+///
+/// `AuthenticationBase` inserts the id of every refresh token it issues and
+/// takes it back out when the token is used, so each refresh token works
+/// once. Share one store between instances (e.g. a database or Redis) for
+/// rotation and revocation to hold across them.
+#[async_trait]
+pub trait RefreshTokenStore: Send + Sync {
+    /// Record a newly issued token id, usable until `expires_at` (unix seconds).
+    async fn insert(&self, jti: &str, expires_at: i64) -> Result<()>;
+
+    /// Remove `jti` and report whether it was still usable.
+    ///
+    /// Must be atomic: of concurrent calls for the same id, at most one may
+    /// return `true`.
+    async fn take(&self, jti: &str) -> Result<bool>;
+
+    /// Make `jti` unusable without using it.
+    async fn revoke(&self, jti: &str) -> Result<()>;
+}
+
+/// In-process `RefreshTokenStore`, the default.
+///
+/// Tokens issued by one instance cannot be refreshed on another; use a
+/// shared store when running more than one.
+#[derive(Default)]
+pub struct MemoryRefreshTokenStore {
+    tokens: Mutex<IssuedTokens>,
+}
+
+/// Usable token ids, with an index by expiry so expired ids are dropped
+/// without scanning the rest.
+#[derive(Default)]
+struct IssuedTokens {
+    expires_at: HashMap<String, i64>,
+    by_expiry: BTreeSet<(i64, String)>,
+}
+
+impl IssuedTokens {
+    fn remove(&mut self, jti: &str) -> Option<i64> {
+        let exp = self.expires_at.remove(jti)?;
+        self.by_expiry.remove(&(exp, jti.to_string()));
+        Some(exp)
+    }
+
+    fn drop_expired(&mut self, now: i64) {
+        while let Some((exp, _)) = self.by_expiry.first() {
+            if *exp > now {
+                break;
+            }
+            if let Some((_, jti)) = self.by_expiry.pop_first() {
+                self.expires_at.remove(&jti);
+            }
+        }
+    }
+}
+
+impl MemoryRefreshTokenStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl RefreshTokenStore for MemoryRefreshTokenStore {
+    async fn insert(&self, jti: &str, expires_at: i64) -> Result<()> {
+        let now = Utc::now().timestamp();
+        let mut tokens = self.tokens.lock().unwrap();
+        tokens.drop_expired(now);
+        tokens.remove(jti);
+        tokens.expires_at.insert(jti.to_string(), expires_at);
+        tokens.by_expiry.insert((expires_at, jti.to_string()));
+        Ok(())
+    }
+
+    async fn take(&self, jti: &str) -> Result<bool> {
+        let now = Utc::now().timestamp();
+        let exp = self.tokens.lock().unwrap().remove(jti);
+        Ok(exp.is_some_and(|exp| exp > now))
+    }
+
+    async fn revoke(&self, jti: &str) -> Result<()> {
+        self.tokens.lock().unwrap().remove(jti);
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+// Token revocation.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Token ids (`jti`) revoked before their expiry.
+///
+/// `AuthenticationBase` checks it whenever it verifies a token, so a revoked
+/// token is rejected by `JwtStrategy` and the authenticate hook. An entry
+/// only has to outlive the token it revokes: `ttl` is the token's remaining
+/// lifetime, after which the store may forget it. Share one store between
+/// instances (e.g. Redis with `SET ... EX`) for revocation to hold across them.
+#[async_trait]
+pub trait TokenRevocationStore: Send + Sync {
+    async fn revoke(&self, jti: &str, ttl: Duration) -> Result<()>;
+
+    async fn is_revoked(&self, jti: &str) -> Result<bool>;
+}
+
+/// In-process `TokenRevocationStore`, the default.
+#[derive(Default)]
+pub struct MemoryTokenRevocationStore {
+    revoked: Mutex<HashMap<String, Instant>>,
+}
+
+impl MemoryTokenRevocationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TokenRevocationStore for MemoryTokenRevocationStore {
+    async fn revoke(&self, jti: &str, ttl: Duration) -> Result<()> {
+        let now = Instant::now();
+        let mut revoked = self.revoked.lock().unwrap();
+        revoked.retain(|_, until| *until > now);
+        revoked.insert(jti.to_string(), now + ttl);
+        Ok(())
+    }
+
+    async fn is_revoked(&self, jti: &str) -> Result<bool> {
+        let revoked = self.revoked.lock().unwrap();
+        Ok(revoked
+            .get(jti)
+            .is_some_and(|until| *until > Instant::now()))
+    }
+}
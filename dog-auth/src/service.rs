@@ -13,7 +13,7 @@ use crate::core::{
     ConnectionEvent, JwtOverrides,
 };
 use crate::hooks::authenticate::AuthenticateHookParams;
-use crate::options::AuthOptions;
+use crate::options::{AuthOptions, JwtKey};
 
 pub const AUTHENTICATION_KEY: &str = "authentication";
 pub const AUTHENTICATION_OPTIONS_KEY: &str = "authentication.options";
@@ -65,6 +65,23 @@ where
         self.base.configuration()
     }
 
+    /// PEM public key for verifying this service's tokens elsewhere
+    /// (RSA/ECDSA only).
+    pub fn public_key_pem(&self) -> Result<Option<String>> {
+        self.base.public_key_pem()
+    }
+
+    /// JWK Set for verifying this service's tokens elsewhere (RSA/ECDSA only).
+    pub fn jwks(&self) -> Result<Value> {
+        self.base.jwks()
+    }
+
+    /// Sign new tokens with `key` while still verifying tokens signed with
+    /// the previous one. See [`AuthenticationBase::rotate_signing_key`].
+    pub fn rotate_signing_key(&self, key: JwtKey) -> Result<()> {
+        self.base.rotate_signing_key(key)
+    }
+
     pub async fn authenticate(
         &self,
         authentication: &AuthenticationRequest,
@@ -81,9 +98,12 @@ where
         let cfg = self.configuration();
         cfg.validate().map_err(|e| anyhow::anyhow!(e))?;
 
-        // Basic, Feathers-like sanity check: if JWT is enabled, a secret must be present.
-        // (Later, RSA/ECDSA key support can satisfy this instead.)
-        if cfg.strategies.contains(&crate::options::AuthStrategy::Jwt) && cfg.jwt.secret.is_none() {
+        // Basic, Feathers-like sanity check: if JWT is enabled with an HMAC algorithm, a
+        // secret must be present (RSA/ECDSA keys are checked by `validate`).
+        if cfg.strategies.contains(&crate::options::AuthStrategy::Jwt)
+            && cfg.jwt.is_hmac()
+            && cfg.jwt.secret.is_none()
+        {
             return Err(anyhow::anyhow!(
                 "A JWT secret must be provided in your authentication configuration"
             ));
@@ -164,10 +184,37 @@ where
 
         // Minimal Feathers-like behavior: sign the `params.payload` (or empty) as the JWT payload.
         let payload = self.get_payload(&auth_result, params).await?;
+
+        if let Some(challenge) = self.base.start_mfa(&auth_result, payload.clone()).await? {
+            return Ok(json!({
+                "mfaRequired": true,
+                "challenge": challenge,
+            }));
+        }
+
+        self.issue_tokens(auth_result, payload, jwt_overrides).await
+    }
+
+    /// Complete a login that `create` answered with `mfaRequired`, using the
+    /// TOTP `code` from the user's authenticator. Returns what `create` would
+    /// have returned without MFA.
+    pub async fn verify_mfa(&self, challenge: &str, code: &str) -> Result<AuthenticationResult> {
+        let pending = self.base.complete_mfa(challenge, code).await?;
+        self.issue_tokens(pending.result, pending.payload, None)
+            .await
+    }
+
+    async fn issue_tokens(
+        &self,
+        auth_result: AuthenticationResult,
+        payload: Value,
+        jwt_overrides: Option<JwtOverrides>,
+    ) -> Result<AuthenticationResult> {
         let access_token = self
             .base
-            .create_access_token(payload, jwt_overrides)
+            .create_access_token(payload.clone(), jwt_overrides)
             .await?;
+        let refresh_token = self.base.issue_refresh_token(payload).await?;
 
         let mut out = match auth_result {
             Value::Object(m) => m,
@@ -178,10 +225,25 @@ where
             }
         };
         out.insert("accessToken".to_string(), Value::String(access_token));
+        out.insert("refreshToken".to_string(), Value::String(refresh_token));
 
         Ok(Value::Object(out))
     }
 
+    /// Exchange a refresh token for a new access token and a new refresh
+    /// token. The old refresh token stops working, so replaying it fails
+    /// with `NotAuthenticated`.
+    pub async fn refresh(&self, refresh_token: &str) -> Result<AuthenticationResult> {
+        let payload = self.base.rotate_refresh_token(refresh_token).await?;
+        let access_token = self.base.create_access_token(payload.clone(), None).await?;
+        let refresh_token = self.base.issue_refresh_token(payload).await?;
+
+        Ok(json!({
+            "accessToken": access_token,
+            "refreshToken": refresh_token,
+        }))
+    }
+
     pub async fn remove(
         &self,
         access_token: Option<&str>,
@@ -194,15 +256,26 @@ where
             .or_else(|| crate::core::extract_bearer_token(&params.headers))
             .ok_or_else(|| DogError::not_authenticated("Invalid access token").into_anyhow())?;
 
-        // Default "logout" behavior: verify (authenticate) the access token.
+        // Default "logout" behavior: verify (authenticate) the access token,
+        // then revoke it so it cannot be used again before it expires.
         let mut data = serde_json::Map::new();
-        data.insert("accessToken".to_string(), Value::String(token));
+        data.insert("accessToken".to_string(), Value::String(token.clone()));
         let auth_req = AuthenticationRequest {
             strategy: Some("jwt".to_string()),
             data,
         };
 
-        self.authenticate(&auth_req, params, ctx, strategies).await
+        let result = self
+            .authenticate(&auth_req, params, ctx, strategies)
+            .await?;
+        self.base.revoke_token(&token).await?;
+        Ok(result)
+    }
+
+    /// Reject the token with id `jti` from now on, e.g. when it has been
+    /// compromised. See [`AuthenticationBase::revoke`].
+    pub async fn revoke(&self, jti: &str) -> Result<()> {
+        self.base.revoke(jti).await
     }
 
     pub async fn handle_connection(
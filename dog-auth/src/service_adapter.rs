@@ -2,13 +2,15 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use async_trait::async_trait;
+use dog_core::errors::DogError;
 use dog_core::tenant::TenantContext;
 use dog_core::{DogService, HookContext, ServiceCaller, ServiceCapabilities, ServiceMethodKind};
 use serde_json::Value;
 
-use crate::core::{AuthenticationParams, AuthenticationRequest};
+use crate::core::{AuthenticationParams, AuthenticationRequest, AuthenticationResult};
 use crate::hooks::authenticate::AuthenticateHookParams;
-use crate::service::AuthenticationService;
+use crate::hooks::event::{AuthEvent, AuthEventKind, LoginFailure, LoginFailureReason};
+use crate::service::{AuthenticationService, AUTHENTICATION_KEY};
 
 pub struct AuthServiceAdapter<P>
 where
@@ -36,6 +38,99 @@ where
     pub fn auth(&self) -> &Arc<AuthenticationService<P>> {
         &self.auth
     }
+
+    fn hook_context(
+        &self,
+        ctx: &TenantContext,
+        method: ServiceMethodKind,
+        params: P,
+    ) -> HookContext<Value, P> {
+        let app = self
+            .app
+            .get()
+            .expect("AuthServiceAdapter must be setup with DogApp");
+        let services = ServiceCaller::new(app.clone());
+        let config = app.config_snapshot();
+        HookContext::new(ctx.clone(), method, params, services, config)
+    }
+
+    /// Emit `kind` for a successful `result` on the app event hub.
+    async fn emit_success(
+        &self,
+        kind: AuthEventKind,
+        strategy: Option<String>,
+        result: &AuthenticationResult,
+        ctx: &HookContext<Value, P>,
+    ) {
+        let identity = self.identity(result).await;
+        self.emit(kind, identity, strategy, None, ctx).await;
+    }
+
+    /// Emit `user.login_failed` if `err` is about the credentials.
+    async fn emit_failure(
+        &self,
+        strategy: Option<String>,
+        err: &anyhow::Error,
+        ctx: &HookContext<Value, P>,
+    ) {
+        if let Some(failure) = LoginFailure::from_error(err) {
+            self.emit(
+                AuthEventKind::LoginFailed,
+                failure.identity,
+                strategy,
+                Some(failure.reason),
+                ctx,
+            )
+            .await;
+        }
+    }
+
+    async fn emit(
+        &self,
+        kind: AuthEventKind,
+        identity: Option<String>,
+        strategy: Option<String>,
+        reason: Option<LoginFailureReason>,
+        ctx: &HookContext<Value, P>,
+    ) {
+        let event = AuthEvent {
+            kind,
+            tenant: ctx.tenant.tenant_id.0.clone(),
+            identity,
+            strategy,
+            reason,
+        };
+        ctx.app()
+            .emit_custom(AUTHENTICATION_KEY, kind.as_str(), Arc::new(event), ctx)
+            .await;
+    }
+
+    /// The entity id behind `result`: the token payload's entity id claim,
+    /// the attached entity's `id`, or the subject of the new access token.
+    async fn identity(&self, result: &AuthenticationResult) -> Option<String> {
+        let cfg = self.auth.configuration();
+        let claim = cfg.entity_id_claim.as_deref().unwrap_or("sub");
+        let as_string = |v: &Value| match v {
+            Value::String(s) => Some(s.clone()),
+            Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        };
+
+        if let Some(id) = result.get("payload").and_then(|p| p.get(claim)) {
+            return as_string(id);
+        }
+        if let Some(id) = cfg
+            .entity
+            .as_deref()
+            .and_then(|entity| result.get(entity))
+            .and_then(|e| e.get("id"))
+        {
+            return as_string(id);
+        }
+        let token = result.get("accessToken")?.as_str()?;
+        let claims = self.auth.base.verify_access_token(token).await.ok()?;
+        claims.get(claim).and_then(as_string)
+    }
 }
 
 #[async_trait]
@@ -61,24 +156,61 @@ where
             secret: None,
             headers: params.headers().clone(),
         };
+        let mut hook_ctx = self.hook_context(ctx, ServiceMethodKind::Create, params);
+        let strategy = auth_req.strategy.clone();
 
-        let app = self
-            .app
-            .get()
-            .expect("AuthServiceAdapter must be setup with DogApp");
-        let services = ServiceCaller::new(app.clone());
-        let config = app.config_snapshot();
-        let mut hook_ctx = HookContext::new(
-            ctx.clone(),
-            ServiceMethodKind::Create,
-            params,
-            services,
-            config,
-        );
-
-        self.auth
-            .create(&auth_req, &auth_params, &mut hook_ctx, &strategies, None)
-            .await
+        // `{ "strategy": "refresh", "refreshToken": "..." }` rotates a refresh
+        // token, unless an app strategy is registered under that name.
+        if auth_req.strategy.as_deref() == Some("refresh")
+            && self.auth.base.get_strategy("refresh").is_none()
+        {
+            let refresh_token = auth_req
+                .data
+                .get("refreshToken")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| DogError::not_authenticated("No refresh token").into_anyhow())?;
+            let result = self.auth.refresh(refresh_token).await?;
+            self.emit_success(AuthEventKind::TokenRefreshed, strategy, &result, &hook_ctx)
+                .await;
+            return Ok(result);
+        }
+
+        // `{ "strategy": "mfa", "challenge": "...", "code": "123456" }`
+        // completes a login that answered `mfaRequired`.
+        let result = if auth_req.strategy.as_deref() == Some("mfa")
+            && self.auth.base.get_strategy("mfa").is_none()
+        {
+            let field = |name: &str| {
+                auth_req
+                    .data
+                    .get(name)
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| {
+                        DogError::not_authenticated(format!("No MFA {name}")).into_anyhow()
+                    })
+            };
+            match (field("challenge"), field("code")) {
+                (Ok(challenge), Ok(code)) => self.auth.verify_mfa(challenge, code).await,
+                (Err(e), _) | (_, Err(e)) => Err(e),
+            }
+        } else {
+            self.auth
+                .create(&auth_req, &auth_params, &mut hook_ctx, &strategies, None)
+                .await
+        };
+
+        match &result {
+            // No new tokens: either an `mfaRequired` challenge, or a strategy
+            // (such as `jwt`) re-authenticating with a token issued earlier.
+            // Only logins that issue tokens are `user.login`.
+            Ok(result) if result.get("refreshToken").is_none() => {}
+            Ok(result) => {
+                self.emit_success(AuthEventKind::Login, strategy, result, &hook_ctx)
+                    .await
+            }
+            Err(err) => self.emit_failure(strategy, err, &hook_ctx).await,
+        }
+        result
     }
 
     async fn remove(&self, ctx: &TenantContext, id: Option<&str>, params: P) -> Result<Value> {
@@ -91,23 +223,19 @@ where
             secret: None,
             headers: params.headers().clone(),
         };
+        let mut hook_ctx = self.hook_context(ctx, ServiceMethodKind::Remove, params);
 
-        let app = self
-            .app
-            .get()
-            .expect("AuthServiceAdapter must be setup with DogApp");
-        let services = ServiceCaller::new(app.clone());
-        let config = app.config_snapshot();
-        let mut hook_ctx = HookContext::new(
-            ctx.clone(),
-            ServiceMethodKind::Remove,
-            params,
-            services,
-            config,
-        );
-
-        self.auth
+        let result = self
+            .auth
             .remove(id, &auth_params, &mut hook_ctx, &strategies)
-            .await
+            .await?;
+        let strategy = result
+            .get("authentication")
+            .and_then(|a| a.get("strategy"))
+            .and_then(|s| s.as_str())
+            .map(str::to_string);
+        self.emit_success(AuthEventKind::Logout, strategy, &result, &hook_ctx)
+            .await;
+        Ok(result)
     }
 }
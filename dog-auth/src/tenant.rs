@@ -0,0 +1,57 @@
+// Tenant resolution from access tokens.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use dog_core::tenant::{TenantContext, TenantResolver};
+use dog_core::RequestMeta;
+use serde_json::Value;
+
+use crate::core::{extract_bearer_token, AuthenticationBase};
+
+/// The tenant named by a claim of the request's bearer token, `tenant` by
+/// default.
+///
+/// The token is verified first, so a forged or expired token resolves to
+/// nothing and the next resolver (or the default tenant) applies. Rejecting
+/// such requests is still the authenticate hook's job.
+pub struct JwtClaimTenant<P>
+where
+    P: Send + Clone + 'static,
+{
+    base: Arc<AuthenticationBase<P>>,
+    claim: String,
+}
+
+impl<P> JwtClaimTenant<P>
+where
+    P: Send + Clone + 'static,
+{
+    pub fn new(base: Arc<AuthenticationBase<P>>) -> Self {
+        Self {
+            base,
+            claim: "tenant".to_string(),
+        }
+    }
+
+    pub fn with_claim(mut self, claim: impl Into<String>) -> Self {
+        self.claim = claim.into();
+        self
+    }
+}
+
+#[async_trait]
+impl<P> TenantResolver for JwtClaimTenant<P>
+where
+    P: Send + Sync + Clone + 'static,
+{
+    async fn resolve(&self, request: &RequestMeta) -> Option<TenantContext> {
+        let token = extract_bearer_token(&request.headers)?;
+        let claims = self.base.verify_access_token(&token).await.ok()?;
+        match claims.get(&self.claim)? {
+            Value::String(s) if !s.is_empty() => Some(TenantContext::new(s.as_str())),
+            Value::Number(n) => Some(TenantContext::new(n.to_string())),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,102 @@
+// Login attempt throttling.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Failed logins recorded for one identity.
+///
+/// Times are unix milliseconds so stores can persist the record as is.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoginAttempts {
+    /// Failures in the current window.
+    pub failures: u32,
+    /// When the first failure of the current window happened.
+    pub window_started_at: i64,
+    /// How many times the identity has been locked; each lockout doubles.
+    pub lockouts: u32,
+    /// Logins are refused until then.
+    pub locked_until: Option<i64>,
+}
+
+/// Failed login attempts per identity (e.g. `local:alice@example.com`).
+///
+/// Failures are counted with [`AttemptStore::increment`], which must be
+/// atomic so a burst of concurrent guesses can't all read the same count
+/// and slip past the lockout. `put` only records lockouts. `ttl` is how
+/// long the record matters; after it the store may forget it. Share one
+/// store between instances (e.g. Redis with `INCR` + `EXPIRE` for the
+/// count and `SET ... EX` for the record) to throttle across them.
+#[async_trait]
+pub trait AttemptStore: Send + Sync {
+    async fn get(&self, key: &str) -> Result<Option<LoginAttempts>>;
+
+    /// Count one failure and return the updated record, in one atomic step.
+    /// Failures older than `ttl` no longer count: the first failure after
+    /// the window has passed starts a new one. The record is kept for at
+    /// least `ttl` from now.
+    async fn increment(&self, key: &str, ttl: Duration) -> Result<LoginAttempts>;
+
+    async fn put(&self, key: &str, attempts: LoginAttempts, ttl: Duration) -> Result<()>;
+
+    async fn clear(&self, key: &str) -> Result<()>;
+}
+
+/// In-process `AttemptStore`, the default.
+#[derive(Default)]
+pub struct MemoryAttemptStore {
+    attempts: Mutex<HashMap<String, (LoginAttempts, Instant)>>,
+}
+
+impl MemoryAttemptStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl AttemptStore for MemoryAttemptStore {
+    async fn get(&self, key: &str) -> Result<Option<LoginAttempts>> {
+        let attempts = self.attempts.lock().unwrap();
+        Ok(attempts
+            .get(key)
+            .filter(|(_, until)| *until > Instant::now())
+            .map(|(a, _)| a.clone()))
+    }
+
+    async fn increment(&self, key: &str, ttl: Duration) -> Result<LoginAttempts> {
+        let now = Instant::now();
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let mut attempts = self.attempts.lock().unwrap();
+        let (record, until) = attempts
+            .entry(key.to_string())
+            .or_insert_with(|| (LoginAttempts::default(), now));
+        if *until <= now {
+            *record = LoginAttempts::default();
+        }
+        if record.failures == 0 || now_ms - record.window_started_at > ttl.as_millis() as i64 {
+            record.failures = 0;
+            record.window_started_at = now_ms;
+        }
+        record.failures += 1;
+        *until = (*until).max(now + ttl);
+        Ok(record.clone())
+    }
+
+    async fn put(&self, key: &str, record: LoginAttempts, ttl: Duration) -> Result<()> {
+        let now = Instant::now();
+        let mut attempts = self.attempts.lock().unwrap();
+        attempts.retain(|_, (_, until)| *until > now);
+        attempts.insert(key.to_string(), (record, now + ttl));
+        Ok(())
+    }
+
+    async fn clear(&self, key: &str) -> Result<()> {
+        self.attempts.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
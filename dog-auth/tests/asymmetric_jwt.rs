@@ -0,0 +1,163 @@
+//! Keys in `tests/keys` are test-only and were generated with:
+//!
+//! ```sh
+//! openssl genpkey -algorithm RSA -pkeyopt rsa_keygen_bits:2048 -out rsa_private.pem
+//! openssl pkey -in rsa_private.pem -pubout -out rsa_public.pem
+//! openssl ecparam -genkey -noout -name prime256v1 | openssl pkcs8 -topk8 -nocrypt -out ec_private.pem
+//! openssl pkey -in ec_private.pem -pubout -out ec_public.pem
+//! ```
+
+#![cfg(any(feature = "jwt-aws-lc-rs", feature = "jwt-rust-crypto"))]
+
+use std::sync::Arc;
+
+use dog_auth::{AuthOptions, AuthenticationService, JwtAlgorithm, JwtOptions};
+use dog_core::DogAppBuilder;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+use serde_json::{json, Value};
+
+const RSA_PRIVATE: &str = include_str!("keys/rsa_private.pem");
+const RSA_PUBLIC: &str = include_str!("keys/rsa_public.pem");
+const EC_PRIVATE: &str = include_str!("keys/ec_private.pem");
+const EC_PUBLIC: &str = include_str!("keys/ec_public.pem");
+
+fn auth_service(jwt: JwtOptions) -> AuthenticationService<()> {
+    let mut builder = DogAppBuilder::<Value, ()>::new();
+    let options = AuthOptions {
+        jwt,
+        ..AuthOptions::default()
+    };
+    let auth = AuthenticationService::builder(&mut builder, Some(options)).unwrap();
+    AuthenticationService::new(Arc::new(auth.build()))
+}
+
+fn signer(algorithm: JwtAlgorithm, private_key: &str) -> AuthenticationService<()> {
+    auth_service(JwtOptions {
+        algorithm,
+        private_key: Some(private_key.to_string()),
+        ..JwtOptions::default()
+    })
+}
+
+fn verifier(algorithm: JwtAlgorithm, public_key: &str) -> AuthenticationService<()> {
+    auth_service(JwtOptions {
+        algorithm,
+        public_key: Some(public_key.to_string()),
+        ..JwtOptions::default()
+    })
+}
+
+fn validation(alg: Algorithm) -> Validation {
+    let defaults = JwtOptions::default();
+    let mut validation = Validation::new(alg);
+    validation.set_issuer(&[defaults.issuer]);
+    validation.set_audience(&defaults.audience);
+    validation
+}
+
+#[tokio::test]
+async fn rs256_tokens_verify_with_the_public_key() {
+    let signer = signer(JwtAlgorithm::RS256, RSA_PRIVATE);
+    let token = signer
+        .base
+        .create_access_token(json!({ "sub": "user-1" }), None)
+        .await
+        .unwrap();
+
+    // Another service holding only the public key.
+    let claims = verifier(JwtAlgorithm::RS256, RSA_PUBLIC)
+        .base
+        .verify_access_token(&token)
+        .await
+        .unwrap();
+    assert_eq!(claims["sub"], "user-1");
+
+    // Any JWT library, given the PEM.
+    let key = DecodingKey::from_rsa_pem(RSA_PUBLIC.as_bytes()).unwrap();
+    let decoded = decode::<Value>(&token, &key, &validation(Algorithm::RS256)).unwrap();
+    assert_eq!(decoded.header.alg, Algorithm::RS256);
+    assert_eq!(decoded.claims["sub"], "user-1");
+
+    // The signer verifies its own tokens without a configured public key.
+    signer.base.verify_access_token(&token).await.unwrap();
+}
+
+#[tokio::test]
+async fn rs256_tokens_verify_with_the_jwks() {
+    let signer = signer(JwtAlgorithm::RS256, RSA_PRIVATE);
+    let token = signer
+        .base
+        .create_access_token(json!({ "sub": "user-1" }), None)
+        .await
+        .unwrap();
+
+    let jwks: JwkSet = serde_json::from_value(signer.jwks().unwrap()).unwrap();
+    assert_eq!(jwks.keys.len(), 1);
+    let key = DecodingKey::from_jwk(&jwks.keys[0]).unwrap();
+    let decoded = decode::<Value>(&token, &key, &validation(Algorithm::RS256)).unwrap();
+    assert_eq!(decoded.claims["sub"], "user-1");
+}
+
+#[tokio::test]
+async fn tokens_signed_with_another_key_are_rejected() {
+    let token = signer(JwtAlgorithm::ES256, EC_PRIVATE)
+        .base
+        .create_access_token(json!({ "sub": "user-1" }), None)
+        .await
+        .unwrap();
+
+    verifier(JwtAlgorithm::ES256, EC_PUBLIC)
+        .base
+        .verify_access_token(&token)
+        .await
+        .unwrap();
+    assert!(verifier(JwtAlgorithm::RS256, RSA_PUBLIC)
+        .base
+        .verify_access_token(&token)
+        .await
+        .is_err());
+}
+
+#[test]
+fn hmac_secrets_are_never_published() {
+    let auth = auth_service(JwtOptions {
+        secret: Some("test-secret".to_string()),
+        ..JwtOptions::default()
+    });
+    assert_eq!(auth.jwks().unwrap(), json!({ "keys": [] }));
+    assert_eq!(auth.public_key_pem().unwrap(), None);
+}
+
+#[tokio::test]
+async fn keys_are_parsed_once_when_the_service_is_built() {
+    let path = std::env::temp_dir().join(format!("dog-auth-{}.pem", uuid::Uuid::new_v4()));
+    std::fs::write(&path, RSA_PRIVATE).unwrap();
+    let signer = auth_service(JwtOptions {
+        algorithm: JwtAlgorithm::RS256,
+        private_key_path: Some(path.to_string_lossy().into_owned()),
+        ..JwtOptions::default()
+    });
+    // Signing no longer reads the key file.
+    std::fs::remove_file(&path).unwrap();
+
+    let token = signer
+        .base
+        .create_access_token(json!({ "sub": "user-1" }), None)
+        .await
+        .unwrap();
+    let claims = signer.base.verify_access_token(&token).await.unwrap();
+    assert_eq!(claims["sub"], "user-1");
+
+    // A bad key fails the build, not the first login.
+    let mut builder = DogAppBuilder::<Value, ()>::new();
+    let options = AuthOptions {
+        jwt: JwtOptions {
+            algorithm: JwtAlgorithm::RS256,
+            private_key: Some("not a pem".to_string()),
+            ..JwtOptions::default()
+        },
+        ..AuthOptions::default()
+    };
+    assert!(AuthenticationService::builder(&mut builder, Some(options)).is_err());
+}
@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use dog_auth::hooks::{require_permission, require_roles, AuthParams, AuthenticateHook};
+use dog_auth::{AuthOptions, AuthenticationService, JwtOptions, JwtStrategy};
+use dog_core::errors::{DogError, ErrorKind};
+use dog_core::tenant::TenantContext;
+use dog_core::{DogApp, DogAppBuilder, DogService};
+use serde_json::{json, Value};
+
+type Params = AuthParams<()>;
+
+struct Posts;
+
+#[async_trait]
+impl DogService<Value, Params> for Posts {
+    async fn create(
+        &self,
+        _ctx: &TenantContext,
+        data: Value,
+        _params: Params,
+    ) -> anyhow::Result<Value> {
+        Ok(data)
+    }
+
+    async fn remove(
+        &self,
+        _ctx: &TenantContext,
+        id: Option<&str>,
+        _params: Params,
+    ) -> anyhow::Result<Value> {
+        Ok(json!({ "id": id }))
+    }
+}
+
+fn app() -> (DogApp<Value, Params>, Arc<AuthenticationService<Params>>) {
+    let mut builder = DogAppBuilder::new();
+    let options = AuthOptions {
+        jwt: JwtOptions {
+            secret: Some("test-secret".to_string()),
+            ..JwtOptions::default()
+        },
+        ..AuthOptions::default()
+    };
+    let mut auth = AuthenticationService::builder(&mut builder, Some(options)).unwrap();
+    auth.register("jwt", Arc::new(JwtStrategy::new()));
+    let auth = Arc::new(AuthenticationService::new(Arc::new(auth.build())));
+
+    builder.register_service("posts", Arc::new(Posts));
+    let authenticate = Arc::new(AuthenticateHook::new(
+        Arc::clone(&auth),
+        vec!["jwt".to_string()],
+    ));
+    builder.service_hooks("posts", |h| {
+        h.before_all(authenticate);
+        h.before_create(require_roles(["admin"]));
+        h.before_remove(require_permission("posts:delete"));
+    });
+    (builder.build(), auth)
+}
+
+async fn external(auth: &AuthenticationService<Params>, claims: Value) -> Params {
+    let token = auth.base.create_access_token(claims, None).await.unwrap();
+    Params {
+        provider: Some("rest".to_string()),
+        headers: [("authorization".to_string(), format!("Bearer {token}"))].into(),
+        ..Params::default()
+    }
+}
+
+fn kind(err: anyhow::Error) -> Option<ErrorKind> {
+    DogError::from_anyhow(&err).map(|e| e.kind)
+}
+
+#[tokio::test]
+async fn create_requires_the_admin_role() {
+    let (app, auth) = app();
+    let posts = app.service("posts").unwrap();
+    let tenant = TenantContext::new("default");
+
+    let reader = external(&auth, json!({ "sub": "u1", "roles": ["reader"] })).await;
+    let err = posts
+        .create(tenant.clone(), json!({ "title": "Hi" }), reader)
+        .await
+        .unwrap_err();
+    assert_eq!(kind(err), Some(ErrorKind::Forbidden));
+
+    let admin = external(&auth, json!({ "sub": "u2", "roles": ["reader", "admin"] })).await;
+    let created = posts
+        .create(tenant.clone(), json!({ "title": "Hi" }), admin)
+        .await
+        .unwrap();
+    assert_eq!(created["title"], "Hi");
+
+    // Internal calls are not subject to authorization.
+    posts
+        .create(tenant, json!({ "title": "Hi" }), Params::default())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn remove_requires_the_permission() {
+    let (app, auth) = app();
+    let posts = app.service("posts").unwrap();
+    let tenant = TenantContext::new("default");
+
+    let writer = external(
+        &auth,
+        json!({ "sub": "u1", "permissions": ["posts:write"] }),
+    )
+    .await;
+    let err = posts
+        .remove(tenant.clone(), Some("1"), writer)
+        .await
+        .unwrap_err();
+    assert_eq!(kind(err), Some(ErrorKind::Forbidden));
+
+    let moderator = external(&auth, json!({ "sub": "u2", "permissions": "posts:*" })).await;
+    posts.remove(tenant, Some("1"), moderator).await.unwrap();
+}
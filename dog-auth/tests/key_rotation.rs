@@ -0,0 +1,127 @@
+//! `keys/rsa_next_private.pem` was generated like `keys/rsa_private.pem`, see
+//! `asymmetric_jwt.rs`.
+
+#![cfg(any(feature = "jwt-aws-lc-rs", feature = "jwt-rust-crypto"))]
+
+use std::sync::Arc;
+
+use dog_auth::{AuthOptions, AuthenticationService, JwtAlgorithm, JwtKey, JwtOptions};
+use dog_core::DogAppBuilder;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use serde_json::{json, Value};
+
+const RSA_PRIVATE: &str = include_str!("keys/rsa_private.pem");
+const RSA_NEXT_PRIVATE: &str = include_str!("keys/rsa_next_private.pem");
+
+fn auth_service() -> AuthenticationService<()> {
+    let mut builder = DogAppBuilder::<Value, ()>::new();
+    let options = AuthOptions {
+        jwt: JwtOptions {
+            algorithm: JwtAlgorithm::RS256,
+            key_id: Some("2026-01".to_string()),
+            private_key: Some(RSA_PRIVATE.to_string()),
+            ..JwtOptions::default()
+        },
+        ..AuthOptions::default()
+    };
+    let auth = AuthenticationService::builder(&mut builder, Some(options)).unwrap();
+    AuthenticationService::new(Arc::new(auth.build()))
+}
+
+fn next_key() -> JwtKey {
+    JwtKey {
+        key_id: "2026-02".to_string(),
+        algorithm: JwtAlgorithm::RS256,
+        private_key: Some(RSA_NEXT_PRIVATE.to_string()),
+        ..JwtKey::default()
+    }
+}
+
+async fn sign(auth: &AuthenticationService<()>) -> String {
+    auth.base
+        .create_access_token(json!({ "sub": "user-1" }), None)
+        .await
+        .unwrap()
+}
+
+fn kid(token: &str) -> Option<String> {
+    decode_header(token).unwrap().kid
+}
+
+#[tokio::test]
+async fn tokens_signed_before_rotation_still_verify() {
+    let auth = auth_service();
+    let before = sign(&auth).await;
+
+    auth.rotate_signing_key(next_key()).unwrap();
+    let after = sign(&auth).await;
+
+    assert_eq!(kid(&before).as_deref(), Some("2026-01"));
+    assert_eq!(kid(&after).as_deref(), Some("2026-02"));
+    assert_eq!(
+        auth.base.verify_access_token(&before).await.unwrap()["sub"],
+        "user-1"
+    );
+    assert_eq!(
+        auth.base.verify_access_token(&after).await.unwrap()["sub"],
+        "user-1"
+    );
+}
+
+#[tokio::test]
+async fn jwks_lists_current_and_previous_keys_by_kid() {
+    let auth = auth_service();
+    let before = sign(&auth).await;
+    auth.rotate_signing_key(next_key()).unwrap();
+    let after = sign(&auth).await;
+
+    let jwks: JwkSet = serde_json::from_value(auth.jwks().unwrap()).unwrap();
+    let kids: Vec<_> = jwks
+        .keys
+        .iter()
+        .map(|k| k.common.key_id.clone().unwrap())
+        .collect();
+    assert_eq!(kids, ["2026-02", "2026-01"]);
+
+    // A verifier using only the JWKS picks the key by the token's `kid`.
+    let mut validation = Validation::new(Algorithm::RS256);
+    validation.set_issuer(&[JwtOptions::default().issuer]);
+    validation.set_audience(&JwtOptions::default().audience);
+    for token in [before, after] {
+        let jwk = jwks.find(&kid(&token).unwrap()).unwrap();
+        let key = DecodingKey::from_jwk(jwk).unwrap();
+        decode::<Value>(&token, &key, &validation).unwrap();
+    }
+}
+
+#[tokio::test]
+async fn unknown_kid_is_rejected() {
+    let auth = auth_service();
+    let other = auth_service();
+    other.rotate_signing_key(next_key()).unwrap();
+    other
+        .rotate_signing_key(JwtKey {
+            key_id: "2026-03".to_string(),
+            ..next_key()
+        })
+        .unwrap();
+
+    assert!(auth
+        .base
+        .verify_access_token(&sign(&other).await)
+        .await
+        .is_err());
+}
+
+#[test]
+fn key_ids_cannot_be_reused() {
+    let auth = auth_service();
+    let err = auth
+        .rotate_signing_key(JwtKey {
+            key_id: "2026-01".to_string(),
+            ..next_key()
+        })
+        .unwrap_err();
+    assert!(err.to_string().contains("already in use"));
+}
@@ -0,0 +1,200 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use dog_auth::mfa::{
+    generate_totp_secret, otpauth_uri, totp_code, verify_totp, verify_totp_at, MfaFactor,
+    MfaSecretResolver,
+};
+use dog_auth::{
+    AuthOptions, AuthenticationBase, AuthenticationParams, AuthenticationRequest,
+    AuthenticationResult, AuthenticationService, AuthenticationStrategy, JwtOptions, MfaOptions,
+};
+use dog_core::errors::{DogError, ErrorKind};
+use dog_core::tenant::TenantContext;
+use dog_core::{DogApp, DogAppBuilder, HookContext, ServiceCaller, ServiceMethodKind};
+use serde_json::{json, Value};
+
+// RFC 6238 appendix B: the ASCII key "12345678901234567890".
+const RFC_SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+#[test]
+fn codes_match_the_rfc_test_vectors() {
+    let options = MfaOptions {
+        digits: 8,
+        ..MfaOptions::default()
+    };
+    for (time, code) in [
+        (59, "94287082"),
+        (1111111109, "07081804"),
+        (1234567890, "89005924"),
+        (20000000000, "65353130"),
+    ] {
+        assert_eq!(totp_code(RFC_SECRET, time, &options).unwrap(), code);
+    }
+}
+
+#[test]
+fn codes_verify_within_the_skew_window_only() {
+    let secret = generate_totp_secret();
+    let options = MfaOptions::default();
+    let now = Utc::now().timestamp();
+    let code_at = |t: i64| totp_code(&secret, t, &options).unwrap();
+
+    assert!(verify_totp(&secret, &code_at(now)));
+    assert!(verify_totp_at(&secret, &code_at(now - 30), &options, now).is_some());
+    assert!(verify_totp_at(&secret, &code_at(now + 30), &options, now).is_some());
+    assert!(verify_totp_at(&secret, &code_at(now - 90), &options, now).is_none());
+
+    let strict = MfaOptions {
+        skew: 0,
+        ..MfaOptions::default()
+    };
+    let step_start = now - now % 30;
+    assert!(verify_totp_at(&secret, &code_at(step_start - 1), &strict, step_start).is_none());
+}
+
+#[test]
+fn otpauth_uri_carries_the_secret_and_issuer() {
+    let uri = otpauth_uri(
+        "JBSWY3DPEHPK3PXP",
+        "alice@example.com",
+        &MfaOptions::default(),
+    );
+    assert_eq!(
+        uri,
+        "otpauth://totp/DogRS%3Aalice%40example.com?secret=JBSWY3DPEHPK3PXP\
+         &issuer=DogRS&algorithm=SHA1&digits=6&period=30"
+    );
+}
+
+/// Authenticates anyone as `{ "user": { "id": <username> } }`.
+struct Trusting;
+
+#[async_trait]
+impl AuthenticationStrategy<()> for Trusting {
+    async fn authenticate(
+        &self,
+        authentication: &AuthenticationRequest,
+        _params: &AuthenticationParams,
+        _ctx: &mut HookContext<Value, ()>,
+        _auth: &AuthenticationBase<()>,
+    ) -> Result<AuthenticationResult> {
+        Ok(json!({ "user": { "id": authentication.data["username"] } }))
+    }
+}
+
+/// MFA is enabled for `admin` only.
+struct AdminsOnly {
+    secret: String,
+}
+
+#[async_trait]
+impl MfaSecretResolver for AdminsOnly {
+    async fn totp_factor(&self, result: &AuthenticationResult) -> Result<Option<MfaFactor>> {
+        Ok((result["user"]["id"] == "admin").then(|| MfaFactor {
+            identity: "admin".to_string(),
+            secret: self.secret.clone(),
+        }))
+    }
+}
+
+fn auth_service(secret: &str) -> (DogApp<Value, ()>, AuthenticationService<()>) {
+    let mut builder = DogAppBuilder::new();
+    let options = AuthOptions {
+        jwt: JwtOptions {
+            secret: Some("test-secret".to_string()),
+            ..JwtOptions::default()
+        },
+        ..AuthOptions::default()
+    };
+    let mut auth = AuthenticationService::builder(&mut builder, Some(options)).unwrap();
+    auth.register("trusting", Arc::new(Trusting));
+    auth.mfa(Arc::new(AdminsOnly {
+        secret: secret.to_string(),
+    }));
+    let auth = AuthenticationService::new(Arc::new(auth.build()));
+    (builder.build(), auth)
+}
+
+async fn login(app: &DogApp<Value, ()>, auth: &AuthenticationService<()>, user: &str) -> Value {
+    let request: AuthenticationRequest =
+        serde_json::from_value(json!({ "strategy": "trusting", "username": user })).unwrap();
+    let mut ctx = HookContext::new(
+        TenantContext::new("default"),
+        ServiceMethodKind::Create,
+        (),
+        ServiceCaller::new(app.clone()),
+        app.config_snapshot(),
+    );
+    auth.create(
+        &request,
+        &AuthenticationParams::default(),
+        &mut ctx,
+        &["trusting".to_string()],
+        None,
+    )
+    .await
+    .unwrap()
+}
+
+fn assert_not_authenticated(err: anyhow::Error) {
+    let kind = DogError::from_anyhow(&err).map(|e| e.kind);
+    assert_eq!(kind, Some(ErrorKind::NotAuthenticated), "{err}");
+}
+
+#[tokio::test]
+async fn login_without_mfa_returns_tokens() {
+    let (app, auth) = auth_service(&generate_totp_secret());
+    let result = login(&app, &auth, "reader").await;
+    assert!(result["accessToken"].is_string());
+    assert!(result.get("mfaRequired").is_none());
+}
+
+#[tokio::test]
+async fn mfa_login_needs_a_valid_code() {
+    let secret = generate_totp_secret();
+    let (app, auth) = auth_service(&secret);
+
+    let result = login(&app, &auth, "admin").await;
+    assert_eq!(result["mfaRequired"], true);
+    assert!(result.get("accessToken").is_none());
+    let challenge = result["challenge"].as_str().unwrap();
+
+    assert_not_authenticated(auth.verify_mfa(challenge, "000000x").await.unwrap_err());
+
+    let code = totp_code(&secret, Utc::now().timestamp(), &MfaOptions::default()).unwrap();
+    let result = auth.verify_mfa(challenge, &code).await.unwrap();
+    assert_eq!(result["user"]["id"], "admin");
+    let token = result["accessToken"].as_str().unwrap();
+    auth.base.verify_access_token(token).await.unwrap();
+
+    // The challenge is used up.
+    assert_not_authenticated(auth.verify_mfa(challenge, &code).await.unwrap_err());
+}
+
+#[tokio::test]
+async fn replayed_and_expired_codes_are_rejected() {
+    let secret = generate_totp_secret();
+    let (app, auth) = auth_service(&secret);
+    let options = MfaOptions::default();
+    let now = Utc::now().timestamp();
+
+    let first = login(&app, &auth, "admin").await;
+    let code = totp_code(&secret, now, &options).unwrap();
+    auth.verify_mfa(first["challenge"].as_str().unwrap(), &code)
+        .await
+        .unwrap();
+
+    // Same code on a fresh login.
+    let second = login(&app, &auth, "admin").await;
+    let challenge = second["challenge"].as_str().unwrap();
+    let err = auth.verify_mfa(challenge, &code).await.unwrap_err();
+    assert!(err.to_string().contains("already been used"), "{err}");
+
+    // A code from two minutes ago.
+    let expired = totp_code(&secret, now - 120, &options).unwrap();
+    let err = auth.verify_mfa(challenge, &expired).await.unwrap_err();
+    assert!(err.to_string().contains("Invalid MFA code"), "{err}");
+}
@@ -0,0 +1,33 @@
+use dog_auth::password::{hash_password, needs_rehash, verify_password};
+
+#[test]
+fn hashes_verify_against_the_right_password_only() {
+    let hash = hash_password("correct horse");
+    assert!(hash.starts_with("$argon2id$"));
+    assert!(verify_password("correct horse", &hash));
+    assert!(!verify_password("battery staple", &hash));
+    assert!(!verify_password("correct horse", "not a hash"));
+}
+
+#[test]
+fn hashing_is_salted() {
+    let first = hash_password("correct horse");
+    let second = hash_password("correct horse");
+    assert_ne!(first, second);
+    assert!(verify_password("correct horse", &first));
+    assert!(verify_password("correct horse", &second));
+}
+
+#[test]
+fn legacy_and_outdated_hashes_need_rehash() {
+    assert!(!needs_rehash(&hash_password("correct horse")));
+
+    let bcrypt = bcrypt::hash("correct horse", 4).unwrap();
+    assert!(verify_password("correct horse", &bcrypt));
+    assert!(needs_rehash(&bcrypt));
+
+    // Same password, weaker parameters (m=8 KiB, t=1).
+    let outdated =
+        "$argon2id$v=19$m=8,t=1,p=1$c29tZXNhbHQ$bz0Mdk6a8FBsq7lSVaRSD+ak3pVJzLgWdEOXBgGZdZQ";
+    assert!(needs_rehash(outdated));
+}
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use dog_auth::{
+    AuthOptions, AuthenticationService, JwtOptions, MemoryRefreshTokenStore, RefreshTokenStore,
+};
+use dog_core::errors::{DogError, ErrorKind};
+use dog_core::DogAppBuilder;
+use serde_json::{json, Value};
+
+fn auth_service() -> AuthenticationService<()> {
+    let mut builder = DogAppBuilder::<Value, ()>::new();
+    let options = AuthOptions {
+        jwt: JwtOptions {
+            secret: Some("test-secret".to_string()),
+            ..JwtOptions::default()
+        },
+        ..AuthOptions::default()
+    };
+    let auth = AuthenticationService::builder(&mut builder, Some(options)).unwrap();
+    AuthenticationService::new(Arc::new(auth.build()))
+}
+
+fn token(result: &Value, key: &str) -> String {
+    result[key].as_str().unwrap().to_string()
+}
+
+fn assert_not_authenticated(err: anyhow::Error) {
+    let kind = DogError::from_anyhow(&err).map(|e| e.kind);
+    assert_eq!(kind, Some(ErrorKind::NotAuthenticated), "{err}");
+}
+
+#[tokio::test]
+async fn refresh_rotates_the_token_and_keeps_the_payload() {
+    let auth = auth_service();
+    let first = auth
+        .base
+        .issue_refresh_token(json!({ "sub": "user-1" }))
+        .await
+        .unwrap();
+
+    let result = auth.refresh(&first).await.unwrap();
+    let second = token(&result, "refreshToken");
+    assert_ne!(first, second);
+
+    let claims = auth
+        .base
+        .verify_access_token(&token(&result, "accessToken"))
+        .await
+        .unwrap();
+    assert_eq!(claims["sub"], "user-1");
+
+    // The new refresh token works in turn.
+    let result = auth.refresh(&second).await.unwrap();
+    assert!(result["refreshToken"].is_string());
+}
+
+#[tokio::test]
+async fn a_rotated_refresh_token_cannot_be_reused() {
+    let auth = auth_service();
+    let first = auth
+        .base
+        .issue_refresh_token(json!({ "sub": "user-1" }))
+        .await
+        .unwrap();
+
+    auth.refresh(&first).await.unwrap();
+    assert_not_authenticated(auth.refresh(&first).await.unwrap_err());
+}
+
+#[tokio::test]
+async fn revoked_refresh_tokens_are_rejected() {
+    let auth = auth_service();
+    let refresh = auth
+        .base
+        .issue_refresh_token(json!({ "sub": "user-1" }))
+        .await
+        .unwrap();
+
+    auth.base.revoke_refresh_token(&refresh).await.unwrap();
+    assert_not_authenticated(auth.refresh(&refresh).await.unwrap_err());
+}
+
+#[tokio::test]
+async fn access_and_refresh_tokens_are_not_interchangeable() {
+    let auth = auth_service();
+    let refresh = auth
+        .base
+        .issue_refresh_token(json!({ "sub": "user-1" }))
+        .await
+        .unwrap();
+    let access = auth
+        .base
+        .create_access_token(json!({ "sub": "user-1" }), None)
+        .await
+        .unwrap();
+
+    assert_not_authenticated(auth.base.verify_access_token(&refresh).await.unwrap_err());
+    assert_not_authenticated(auth.refresh(&access).await.unwrap_err());
+}
+
+#[tokio::test]
+async fn memory_store_drops_expired_ids() {
+    let store = MemoryRefreshTokenStore::new();
+    let now = chrono::Utc::now().timestamp();
+
+    store.insert("expired", now - 1).await.unwrap();
+    store.insert("live", now + 60).await.unwrap();
+    store.insert("revoked", now + 60).await.unwrap();
+    store.revoke("revoked").await.unwrap();
+
+    assert!(!store.take("expired").await.unwrap());
+    assert!(!store.take("revoked").await.unwrap());
+    assert!(store.take("live").await.unwrap());
+    assert!(!store.take("live").await.unwrap());
+}
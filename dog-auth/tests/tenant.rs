@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use dog_auth::hooks::AuthParams;
+use dog_auth::{
+    AuthOptions, AuthenticationBase, AuthenticationService, JwtClaimTenant, JwtOptions,
+};
+use dog_core::tenant::TenantResolver;
+use dog_core::{DogAppBuilder, RequestMeta};
+use serde_json::{json, Value};
+
+type Params = AuthParams<()>;
+
+fn auth_base(secret: &str) -> Arc<AuthenticationBase<Params>> {
+    let mut builder = DogAppBuilder::<Value, Params>::new();
+    let options = AuthOptions {
+        jwt: JwtOptions {
+            secret: Some(secret.to_string()),
+            ..JwtOptions::default()
+        },
+        ..AuthOptions::default()
+    };
+    let auth = AuthenticationService::builder(&mut builder, Some(options)).unwrap();
+    Arc::new(auth.build())
+}
+
+fn bearer(token: &str) -> RequestMeta {
+    RequestMeta::new("rest", "GET", "/").with_header("Authorization", format!("Bearer {token}"))
+}
+
+async fn resolved(resolver: &dyn TenantResolver, request: &RequestMeta) -> Option<String> {
+    resolver.resolve(request).await.map(|t| t.tenant_id.0)
+}
+
+#[tokio::test]
+async fn tenant_comes_from_the_token_claim() {
+    let base = auth_base("test-secret");
+    let token = base
+        .create_access_token(
+            json!({ "sub": "user-1", "tenant": "acme", "org": 42 }),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let resolver = JwtClaimTenant::new(Arc::clone(&base));
+    assert_eq!(
+        resolved(&resolver, &bearer(&token)).await.as_deref(),
+        Some("acme")
+    );
+
+    let by_org = JwtClaimTenant::new(Arc::clone(&base)).with_claim("org");
+    assert_eq!(
+        resolved(&by_org, &bearer(&token)).await.as_deref(),
+        Some("42")
+    );
+
+    let missing = JwtClaimTenant::new(base).with_claim("workspace");
+    assert_eq!(resolved(&missing, &bearer(&token)).await, None);
+}
+
+#[tokio::test]
+async fn unverified_tokens_resolve_to_nothing() {
+    let base = auth_base("test-secret");
+    let forged = auth_base("other-secret")
+        .create_access_token(json!({ "sub": "user-1", "tenant": "acme" }), None)
+        .await
+        .unwrap();
+    let resolver = JwtClaimTenant::new(base);
+
+    assert_eq!(resolved(&resolver, &bearer(&forged)).await, None);
+    assert_eq!(resolved(&resolver, &bearer("not-a-jwt")).await, None);
+    assert_eq!(
+        resolved(&resolver, &RequestMeta::new("rest", "GET", "/")).await,
+        None
+    );
+}
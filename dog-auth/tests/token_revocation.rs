@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use dog_auth::hooks::{AuthParams, AuthenticateHook};
+use dog_auth::{AuthOptions, AuthenticationService, JwtOptions, JwtStrategy};
+use dog_core::errors::{DogError, ErrorKind};
+use dog_core::hooks::DogBeforeHook;
+use dog_core::tenant::TenantContext;
+use dog_core::{DogAppBuilder, HookContext, ServiceCaller, ServiceMethodKind};
+use serde_json::{json, Value};
+
+type Params = AuthParams<()>;
+
+fn auth_service(builder: &mut DogAppBuilder<Value, Params>) -> Arc<AuthenticationService<Params>> {
+    let options = AuthOptions {
+        jwt: JwtOptions {
+            secret: Some("test-secret".to_string()),
+            ..JwtOptions::default()
+        },
+        ..AuthOptions::default()
+    };
+    let mut auth = AuthenticationService::builder(builder, Some(options)).unwrap();
+    auth.register("jwt", Arc::new(JwtStrategy::new()));
+    Arc::new(AuthenticationService::new(Arc::new(auth.build())))
+}
+
+/// Run the authenticate hook for an external call bearing `token`.
+async fn authenticate(
+    auth: &Arc<AuthenticationService<Params>>,
+    builder: DogAppBuilder<Value, Params>,
+    token: &str,
+) -> anyhow::Result<()> {
+    let app = builder.build();
+    let params = Params {
+        provider: Some("rest".to_string()),
+        headers: HashMap::from([("authorization".to_string(), format!("Bearer {token}"))]),
+        ..Params::default()
+    };
+    let mut ctx = HookContext::new(
+        TenantContext::new("default"),
+        ServiceMethodKind::Find,
+        params,
+        ServiceCaller::new(app.clone()),
+        app.config_snapshot(),
+    );
+    AuthenticateHook::new(Arc::clone(auth), vec!["jwt".to_string()])
+        .run(&mut ctx)
+        .await
+}
+
+fn assert_not_authenticated(err: anyhow::Error) {
+    let kind = DogError::from_anyhow(&err).map(|e| e.kind);
+    assert_eq!(kind, Some(ErrorKind::NotAuthenticated), "{err}");
+}
+
+#[tokio::test]
+async fn revoked_token_is_rejected_by_the_authenticate_hook() {
+    let mut builder = DogAppBuilder::new();
+    let auth = auth_service(&mut builder);
+    let token = auth
+        .base
+        .create_access_token(json!({ "sub": "user-1" }), None)
+        .await
+        .unwrap();
+    let jti = auth.base.verify_access_token(&token).await.unwrap()["jti"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    auth.revoke(&jti).await.unwrap();
+
+    assert_not_authenticated(auth.base.verify_access_token(&token).await.unwrap_err());
+    assert_not_authenticated(authenticate(&auth, builder, &token).await.unwrap_err());
+}
+
+#[tokio::test]
+async fn revoking_one_token_leaves_others_valid() {
+    let mut builder = DogAppBuilder::new();
+    let auth = auth_service(&mut builder);
+    let revoked = auth
+        .base
+        .create_access_token(json!({ "sub": "user-1" }), None)
+        .await
+        .unwrap();
+    let other = auth
+        .base
+        .create_access_token(json!({ "sub": "user-1" }), None)
+        .await
+        .unwrap();
+
+    auth.base.revoke_token(&revoked).await.unwrap();
+
+    assert_not_authenticated(auth.base.verify_access_token(&revoked).await.unwrap_err());
+    authenticate(&auth, builder, &other).await.unwrap();
+}
@@ -97,6 +97,8 @@ where
     pub app: Arc<DogApp<R, P>>,
     pub router: Router<()>,
     pending_middleware: Vec<MiddlewareFn>,
+    /// `(path, service name)` of every service mounted so far, for the OpenAPI spec.
+    mounted_services: Vec<(String, String)>,
 }
 
 impl<R, P> Clone for AxumApp<R, P>
@@ -109,6 +111,7 @@ where
             app: Arc::clone(&self.app),
             router: self.router.clone(),
             pending_middleware: vec![], // Can't clone closures, so start fresh
+            mounted_services: self.mounted_services.clone(),
         }
     }
 }
@@ -127,6 +130,7 @@ where
             app,
             router: layer_defaults(Router::new().with_state(state)),
             pending_middleware: vec![],
+            mounted_services: vec![],
         }
     }
 
@@ -176,10 +180,47 @@ where
     /// If you previously relied on that implicit registration, pass the Arc here as the third
     /// argument — it will be registered under `service_name` automatically.
     pub fn use_service_as(
+        self,
+        path: &'static str,
+        service_name: &'static str,
+        service: Arc<dyn DogService<R, P>>,
+    ) -> Self
+    where
+        R: Serialize + DeserializeOwned,
+        P: FromRestParams,
+    {
+        self.mount_service(path, service_name, service, rest::DEFAULT_BODY_LIMIT)
+    }
+
+    /// Like [`AxumApp::use_service`], but JSON bodies sent to this service may
+    /// be up to `max_bytes` (default [`rest::DEFAULT_BODY_LIMIT`]). Larger
+    /// bodies get `413 PayloadTooLarge`; other mounts keep their own limit.
+    ///
+    /// ```rust,ignore
+    /// let server = axum(app)
+    ///     .use_service_with_body_limit("/music", music, 200 * 1024 * 1024)
+    ///     .use_service_with_body_limit("/messages", messages, 64 * 1024);
+    /// ```
+    pub fn use_service_with_body_limit(
+        self,
+        path: &'static str,
+        service: Arc<dyn DogService<R, P>>,
+        max_bytes: usize,
+    ) -> Self
+    where
+        R: Serialize + DeserializeOwned,
+        P: FromRestParams,
+    {
+        let name = path.trim_start_matches('/');
+        self.mount_service(path, name, service, max_bytes)
+    }
+
+    fn mount_service(
         mut self,
         path: &'static str,
         service_name: &'static str,
         service: Arc<dyn DogService<R, P>>,
+        body_limit: usize,
     ) -> Self
     where
         R: Serialize + DeserializeOwned,
@@ -187,19 +228,69 @@ where
     {
         // Register the service so it can be resolved at request time.
         self.app.register_service(service_name, service);
+        self.mounted_services
+            .push((path.to_string(), service_name.to_string()));
 
         let service_name = Arc::new(service_name.to_string());
-        let mut router = rest::service_router(Arc::clone(&service_name), Arc::clone(&self.app));
+        let router = rest::service_router_with_body_limit(
+            Arc::clone(&service_name),
+            Arc::clone(&self.app),
+            body_limit,
+        );
+        self.nest_service_router(path, router)
+    }
 
-        // Apply pending middleware to this service router
+    /// Nest a service's routes at `path` behind the pending middleware.
+    fn nest_service_router(mut self, path: &str, mut router: Router<()>) -> Self {
         for middleware_fn in &self.pending_middleware {
             router = middleware_fn(router);
         }
+        let router = rest::resolve_tenant_layer(router, Arc::clone(&self.app));
 
         self.router = layer_defaults(self.router.nest(path, router));
         self
     }
 
+    /// Mount every service already registered on the app (typically via
+    /// `DogAppBuilder::register_service`) at `/{name}`. See
+    /// [`rest::router_for_app`] for the route table. Services already
+    /// mounted with `use_service` and friends, and names whose path is
+    /// already taken, are skipped. Pending middleware from `use_middleware`
+    /// applies to all of them.
+    pub fn use_registered_services(mut self) -> Self
+    where
+        R: Serialize + DeserializeOwned,
+        P: FromRestParams,
+    {
+        let unmounted: Vec<(String, String)> = self
+            .app
+            .service_names()
+            .into_iter()
+            .map(|name| (format!("/{name}"), name))
+            .filter(|(path, name)| {
+                !self
+                    .mounted_services
+                    .iter()
+                    .any(|(p, n)| n == name || p == path)
+            })
+            .collect();
+        let mut router = unmounted
+            .iter()
+            .fold(Router::new(), |router, (path, name)| {
+                router.nest(
+                    path,
+                    rest::service_router(Arc::new(name.clone()), Arc::clone(&self.app)),
+                )
+            });
+        self.mounted_services.extend(unmounted);
+        for middleware_fn in &self.pending_middleware {
+            router = middleware_fn(router);
+        }
+        let router = rest::resolve_tenant_layer(router, Arc::clone(&self.app));
+        self.router = layer_defaults(self.router.merge(router));
+        self
+    }
+
     pub fn use_service_with<L>(
         self,
         path: &'static str,
@@ -237,17 +328,118 @@ where
     {
         // Register the service so it can be resolved at request time.
         self.app.register_service(service_name, service);
+        self.mounted_services
+            .push((path.to_string(), service_name.to_string()));
 
         let service_name = Arc::new(service_name.to_string());
         let router = rest::service_router(Arc::clone(&service_name), Arc::clone(&self.app));
 
         // Apply the specific middleware to this service router
-        let router = router.layer(middleware);
+        let router = rest::resolve_tenant_layer(router.layer(middleware), Arc::clone(&self.app));
 
         self.router = layer_defaults(self.router.nest(path, router));
         self
     }
 
+    /// Serve tus resumable uploads into `adapter` at `path` (see
+    /// [`crate::blob::tus_router`]). The adapter needs an upload coordinator
+    /// whose rules allow variable part sizes.
+    ///
+    /// The endpoint does no authentication itself and runs no service
+    /// hooks: anyone who can reach it can upload. Use
+    /// [`AxumApp::use_tus_with`] to put a guard in front of it.
+    ///
+    /// ```rust,ignore
+    /// let server = axum(app).use_tus("/uploads", Arc::new(adapter));
+    /// ```
+    #[cfg(feature = "blob")]
+    pub fn use_tus(self, path: &str, adapter: Arc<dog_blob::BlobAdapter>) -> Self {
+        let router = crate::blob::tus_router(Arc::clone(&self.app), adapter);
+        self.use_router(path, router)
+    }
+
+    /// Like [`AxumApp::use_tus`], with `middleware` wrapping every tus
+    /// request, e.g. a layer that rejects unauthenticated clients.
+    ///
+    /// ```rust,ignore
+    /// let server = axum(app).use_tus_with(
+    ///     "/uploads",
+    ///     Arc::new(adapter),
+    ///     axum::middleware::from_fn(require_session),
+    /// );
+    /// ```
+    #[cfg(feature = "blob")]
+    pub fn use_tus_with<L>(
+        self,
+        path: &str,
+        adapter: Arc<dog_blob::BlobAdapter>,
+        middleware: L,
+    ) -> Self
+    where
+        L: tower::layer::Layer<axum::routing::Route> + Clone + Send + Sync + 'static,
+        L::Service:
+            tower::Service<Request<Body>, Response = Response> + Clone + Send + Sync + 'static,
+        <L::Service as tower::Service<Request<Body>>>::Future: Send,
+        <L::Service as tower::Service<Request<Body>>>::Error: Into<std::convert::Infallible>,
+    {
+        let router = crate::blob::tus_router(Arc::clone(&self.app), adapter).layer(middleware);
+        self.use_router(path, router)
+    }
+
+    /// OpenAPI 3 document for the services mounted so far.
+    /// See [`crate::openapi`] for what it covers.
+    pub fn openapi_spec(&self) -> serde_json::Value {
+        crate::openapi::spec(&self.app, self.mounted_services.iter().map(|(p, n)| (p, n)))
+    }
+
+    /// Serve [`AxumApp::openapi_spec`] as JSON at `path`, typically
+    /// `/openapi.json`. The spec is built now, so call this after mounting
+    /// the services it should describe.
+    pub fn use_openapi(self, path: &str) -> Self {
+        let spec = axum::Json(self.openapi_spec());
+        let router = Router::new().route("/", get(move || async move { spec }));
+        self.use_router(path, router)
+    }
+
+    /// Serve the JWK Set of `auth` at `/.well-known/jwks.json`, for services
+    /// that verify its RSA/ECDSA tokens. Built per request, so it follows
+    /// `rotate_signing_key`.
+    #[cfg(feature = "auth")]
+    pub fn use_jwks<AP>(self, auth: Arc<dog_auth::AuthenticationService<AP>>) -> Self
+    where
+        AP: Send + Clone + 'static,
+    {
+        let router = Router::new().route(
+            "/",
+            get(move || {
+                let auth = Arc::clone(&auth);
+                async move { Ok::<_, crate::DogAxumError>(axum::Json(auth.jwks()?)) }
+            }),
+        );
+        self.use_router("/.well-known/jwks.json", router)
+    }
+
+    /// Stream service events as Server-Sent Events at `path`.
+    /// See [`crate::sse`] for filtering, tenant scoping and resumption.
+    pub fn use_sse(self, path: &str) -> Self
+    where
+        R: Serialize,
+    {
+        let router = crate::sse::router(Arc::clone(&self.app));
+        self.use_router(path, router)
+    }
+
+    /// Serve real-time service events over WebSocket at `path`.
+    /// See [`crate::ws`] for the frame protocol and tenant scoping.
+    #[cfg(feature = "ws")]
+    pub fn use_ws(self, path: &str) -> Self
+    where
+        R: Serialize,
+    {
+        let router = crate::ws::router(Arc::clone(&self.app));
+        self.use_router(path, router)
+    }
+
     pub fn use_middleware<L>(mut self, layer: L) -> Self
     where
         L: tower::layer::Layer<axum::routing::Route> + Clone + Send + Sync + 'static,
@@ -272,6 +464,38 @@ where
     }
 }
 
+#[cfg(feature = "blob")]
+impl<P> AxumApp<serde_json::Value, P>
+where
+    P: FromRestParams + Send + Sync + Clone + 'static,
+{
+    /// Mount a [`dog_blob::BlobService`] at `path`. Besides the usual service
+    /// routes (`find`, `get` → receipt, `create`, `remove`) this serves the
+    /// bytes at `GET {path}/{id}/content`, honoring `Range`
+    /// (see [`crate::blob::content_router`]).
+    ///
+    /// ```rust,ignore
+    /// let server = axum(app)
+    ///     .use_blob_service("/media", Arc::new(BlobService::new(store)));
+    /// ```
+    pub fn use_blob_service(
+        mut self,
+        path: &'static str,
+        blobs: Arc<dog_blob::BlobService>,
+    ) -> Self {
+        let name = path.trim_start_matches('/');
+        self.app.register_service(name, blobs.clone());
+        self.mounted_services
+            .push((path.to_string(), name.to_string()));
+
+        let service_name = Arc::new(name.to_string());
+        let router = rest::service_router(Arc::clone(&service_name), Arc::clone(&self.app)).merge(
+            crate::blob::content_router(service_name, Arc::clone(&self.app), blobs),
+        );
+        self.nest_service_router(path, router)
+    }
+}
+
 pub fn axum<R, P>(app: DogApp<R, P>) -> AxumApp<R, P>
 where
     R: Send + Sync + 'static,
@@ -0,0 +1,435 @@
+//! HTTP routes for dog-blob: byte downloads for a [`BlobService`] mounted
+//! with [`crate::AxumApp::use_blob_service`], and tus resumable uploads
+//! ([`crate::AxumApp::use_tus`]).
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, OriginalUri, Path, Query},
+    http::{header, HeaderMap, HeaderValue, Request, StatusCode},
+    middleware,
+    response::{IntoResponse, Redirect, Response},
+    routing, Extension, Router,
+};
+use dog_blob::tus::{parse_upload_metadata, TUS_EXTENSIONS, TUS_VERSION};
+use dog_blob::{
+    BlobAdapter, BlobCtx, BlobService, ByteRange, ByteStream, OpenedContent, TusUpload, UploadId,
+};
+use dog_core::{DogApp, DogError, TenantContext};
+use futures::TryStreamExt;
+use serde_json::Value;
+
+use crate::params::{FromRestParams, RestParams};
+use crate::rest::request_meta;
+use crate::DogAxumError;
+
+/// What a `Range` header asks of a blob `size` bytes long.
+#[derive(Debug, PartialEq, Eq)]
+enum RangeRequest {
+    Full,
+    Part(ByteRange),
+    Unsatisfiable,
+}
+
+/// Parse a single `bytes=` range. Multiple ranges and malformed headers are
+/// ignored (the whole blob is served), as RFC 9110 allows.
+fn parse_range(header: Option<&str>, size: u64) -> RangeRequest {
+    let Some(spec) = header.and_then(|h| h.trim().strip_prefix("bytes=")) else {
+        return RangeRequest::Full;
+    };
+    if spec.contains(',') {
+        return RangeRequest::Full;
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return RangeRequest::Full;
+    };
+
+    let range = match (start.parse::<u64>(), end.parse::<u64>()) {
+        // `bytes=-N`: the last N bytes
+        (Err(_), Ok(suffix)) if start.is_empty() => {
+            if suffix == 0 {
+                return RangeRequest::Unsatisfiable;
+            }
+            ByteRange::from_start(size.saturating_sub(suffix))
+        }
+        (Ok(start), Err(_)) if end.is_empty() => ByteRange::from_start(start),
+        (Ok(start), Ok(end)) if start <= end => {
+            ByteRange::new(start, Some(end.min(size.saturating_sub(1))))
+        }
+        _ => return RangeRequest::Full,
+    };
+
+    if range.start >= size {
+        RangeRequest::Unsatisfiable
+    } else {
+        RangeRequest::Part(range)
+    }
+}
+
+/// Run the service's `get` (hooks, authorization) for `id` and return the
+/// tenant it resolved to along with the receipt.
+async fn content_receipt<P>(
+    app: &DogApp<Value, P>,
+    service_name: &str,
+    method: &str,
+    headers: &HeaderMap,
+    query: HashMap<String, String>,
+    connect: Option<Extension<ConnectInfo<SocketAddr>>>,
+    uri: &axum::http::Uri,
+    id: &str,
+) -> Result<(TenantContext, Value), DogAxumError>
+where
+    P: FromRestParams + Send + Sync + Clone + 'static,
+{
+    let meta = request_meta(headers, method, uri, connect);
+    let tenant = app.resolve_tenant(&meta).await;
+
+    let params = RestParams::from_parts("rest", headers, query, method, uri);
+    let params = P::from_rest_params(params);
+
+    let svc = app.service(service_name)?.with_request(meta);
+    let receipt = svc.get(tenant.clone(), id, params).await?;
+    Ok((tenant, receipt))
+}
+
+/// `Content-Type`, `Content-Length`, `ETag` and `Accept-Ranges` for a blob.
+fn content_headers(
+    headers: &mut HeaderMap,
+    content_type: Option<&str>,
+    content_length: u64,
+    etag: Option<&str>,
+    accepts_ranges: bool,
+) {
+    let content_type = content_type.unwrap_or("application/octet-stream");
+    if let Ok(value) = HeaderValue::from_str(content_type) {
+        headers.insert(header::CONTENT_TYPE, value);
+    }
+    headers.insert(header::CONTENT_LENGTH, HeaderValue::from(content_length));
+    if let Some(value) = etag.and_then(|etag| HeaderValue::from_str(etag).ok()) {
+        headers.insert(header::ETAG, value);
+    }
+    if accepts_ranges {
+        headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    }
+}
+
+/// `GET /{id}/content`: run the service's `get` (hooks, authorization), then
+/// stream the blob. A `Range` header gets `206 Partial Content` when the
+/// store supports ranges; stores that hand out signed URLs get a redirect.
+///
+/// `HEAD /{id}/content` answers from the same receipt, which comes from the
+/// store's `head`, so players can learn the size and range support without
+/// the object being opened.
+pub fn content_router<P>(
+    service_name: Arc<String>,
+    app: Arc<DogApp<Value, P>>,
+    blobs: Arc<BlobService>,
+) -> Router<()>
+where
+    P: FromRestParams + Send + Sync + Clone + 'static,
+{
+    let head = {
+        let service_name = Arc::clone(&service_name);
+        let app = Arc::clone(&app);
+        move |headers: HeaderMap,
+              Query(query): Query<HashMap<String, String>>,
+              connect: Option<Extension<ConnectInfo<SocketAddr>>>,
+              OriginalUri(uri): OriginalUri,
+              Path(id): Path<String>| async move {
+            let (_, receipt) = content_receipt(
+                &app,
+                &service_name,
+                "HEAD",
+                &headers,
+                query,
+                connect,
+                &uri,
+                &id,
+            )
+            .await?;
+
+            let mut res = Response::new(Body::empty());
+            content_headers(
+                res.headers_mut(),
+                receipt["content_type"].as_str(),
+                receipt["size_bytes"].as_u64().unwrap_or(0),
+                receipt["etag"].as_str(),
+                receipt["accepts_ranges"].as_bool().unwrap_or(false),
+            );
+            Ok::<_, DogAxumError>(res)
+        }
+    };
+
+    let get = move |headers: HeaderMap,
+                    Query(query): Query<HashMap<String, String>>,
+                    connect: Option<Extension<ConnectInfo<SocketAddr>>>,
+                    OriginalUri(uri): OriginalUri,
+                    Path(id): Path<String>| async move {
+        let (tenant, receipt) = content_receipt(
+            &app,
+            &service_name,
+            "GET",
+            &headers,
+            query,
+            connect,
+            &uri,
+            &id,
+        )
+        .await?;
+        let size = receipt["size_bytes"].as_u64().unwrap_or(0);
+
+        let range = match parse_range(
+            headers.get(header::RANGE).and_then(|v| v.to_str().ok()),
+            size,
+        ) {
+            RangeRequest::Full => None,
+            RangeRequest::Part(range) => Some(range),
+            RangeRequest::Unsatisfiable => {
+                return Ok::<_, DogAxumError>(
+                    (
+                        StatusCode::RANGE_NOT_SATISFIABLE,
+                        [(header::CONTENT_RANGE, format!("bytes */{size}"))],
+                    )
+                        .into_response(),
+                );
+            }
+        };
+
+        let partial = range.is_some();
+        let opened = blobs
+            .open(&tenant, &id, range)
+            .await
+            .map_err(DogError::from)?;
+        let content_length = opened.content_length();
+        let receipt = opened.receipt;
+
+        let (stream, resolved_range) = match opened.content {
+            OpenedContent::SignedUrl { url, .. } => {
+                return Ok(Redirect::temporary(&url).into_response());
+            }
+            OpenedContent::Stream {
+                stream,
+                resolved_range,
+            } => (stream, resolved_range),
+        };
+
+        let mut res = Response::new(Body::from_stream(stream));
+        content_headers(
+            res.headers_mut(),
+            receipt.content_type.as_deref(),
+            content_length,
+            receipt.etag.as_deref(),
+            receipt.accepts_ranges,
+        );
+        if let Some(resolved) = resolved_range.filter(|_| partial) {
+            *res.status_mut() = StatusCode::PARTIAL_CONTENT;
+            let value = format!(
+                "bytes {}-{}/{}",
+                resolved.start, resolved.end, resolved.total_size
+            );
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                res.headers_mut().insert(header::CONTENT_RANGE, value);
+            }
+        }
+        Ok(res)
+    };
+
+    Router::new().route("/{id}/content", routing::head(head).get(get))
+}
+
+/// Error response that still carries `Tus-Resumable`, added by [`tus_router`].
+fn tus_error(status: StatusCode, message: &str) -> Response {
+    (status, message.to_string()).into_response()
+}
+
+fn header_u64(headers: &HeaderMap, name: &str) -> Option<u64> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+}
+
+fn tus_upload_headers(res: &mut Response, upload: &TusUpload) {
+    let headers = res.headers_mut();
+    headers.insert("upload-offset", HeaderValue::from(upload.offset));
+    headers.insert("upload-length", HeaderValue::from(upload.length));
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+}
+
+/// The tus 1.0.0 resumable upload protocol (core plus `creation`) over
+/// `adapter`'s upload coordinator (see [`dog_blob::tus`]):
+///
+/// - `OPTIONS /` → `Tus-Version`, `Tus-Extension`, `Tus-Max-Size`
+/// - `POST /` with `Upload-Length` (and optionally `Upload-Metadata`) →
+///   `201` with `Location` and `Upload-Offset: 0`
+/// - `HEAD /{id}` → `Upload-Offset` and `Upload-Length`
+/// - `PATCH /{id}` with `Upload-Offset` and an
+///   `application/offset+octet-stream` body → `204` with the new offset,
+///   `409` when the offset is stale or another `PATCH` to the upload is
+///   still running
+///
+/// Requests other than `OPTIONS` must send `Tus-Resumable: 1.0.0`, otherwise
+/// they get `412` and the supported `Tus-Version`.
+///
+/// The router does no authentication and runs no service hooks; it only
+/// resolves the tenant. Layer a guard on it (or mount it with
+/// [`AxumApp::use_tus_with`](crate::AxumApp::use_tus_with)) to restrict who
+/// can upload.
+pub fn tus_router<R, P>(app: Arc<DogApp<R, P>>, adapter: Arc<BlobAdapter>) -> Router<()>
+where
+    R: Send + Sync + 'static,
+    P: Send + Sync + Clone + 'static,
+{
+    let max_size = adapter.config().max_blob_bytes;
+
+    let create = {
+        let app = Arc::clone(&app);
+        let adapter = Arc::clone(&adapter);
+        move |headers: HeaderMap,
+              OriginalUri(uri): OriginalUri,
+              connect: Option<Extension<ConnectInfo<SocketAddr>>>| async move {
+            let Some(length) = header_u64(&headers, "upload-length") else {
+                return Ok::<_, DogAxumError>(tus_error(
+                    StatusCode::BAD_REQUEST,
+                    "Upload-Length header is required",
+                ));
+            };
+            if length > max_size {
+                return Ok(tus_error(
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                    "Upload-Length exceeds Tus-Max-Size",
+                ));
+            }
+            let metadata = match headers.get("upload-metadata").map(|v| v.to_str()) {
+                Some(Ok(raw)) => parse_upload_metadata(raw).map_err(DogError::from)?,
+                Some(Err(_)) => {
+                    return Ok(tus_error(
+                        StatusCode::BAD_REQUEST,
+                        "Upload-Metadata is not valid ASCII",
+                    ))
+                }
+                None => Default::default(),
+            };
+
+            let meta = request_meta(&headers, "POST", &uri, connect);
+            let tenant = app.resolve_tenant(&meta).await;
+            let upload = adapter
+                .tus_create(BlobCtx::new(tenant.tenant_id.0), length, &metadata)
+                .await
+                .map_err(DogError::from)?;
+
+            let location = format!("{}/{}", uri.path().trim_end_matches('/'), upload.upload_id);
+            let mut res = StatusCode::CREATED.into_response();
+            tus_upload_headers(&mut res, &upload);
+            if let Ok(value) = HeaderValue::from_str(&location) {
+                res.headers_mut().insert(header::LOCATION, value);
+            }
+            Ok(res)
+        }
+    };
+
+    let status = {
+        let app = Arc::clone(&app);
+        let adapter = Arc::clone(&adapter);
+        move |headers: HeaderMap,
+              OriginalUri(uri): OriginalUri,
+              Path(id): Path<String>,
+              connect: Option<Extension<ConnectInfo<SocketAddr>>>| async move {
+            let meta = request_meta(&headers, "HEAD", &uri, connect);
+            let tenant = app.resolve_tenant(&meta).await;
+            let upload = adapter
+                .tus_status(BlobCtx::new(tenant.tenant_id.0), UploadId::from_string(id))
+                .await
+                .map_err(DogError::from)?;
+
+            let mut res = StatusCode::OK.into_response();
+            tus_upload_headers(&mut res, &upload);
+            Ok::<_, DogAxumError>(res)
+        }
+    };
+
+    let append = move |headers: HeaderMap,
+                       OriginalUri(uri): OriginalUri,
+                       Path(id): Path<String>,
+                       connect: Option<Extension<ConnectInfo<SocketAddr>>>,
+                       request: Request<Body>| async move {
+        let content_type = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok());
+        if content_type != Some("application/offset+octet-stream") {
+            return Ok::<_, DogAxumError>(tus_error(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "Content-Type must be application/offset+octet-stream",
+            ));
+        }
+        let Some(offset) = header_u64(&headers, "upload-offset") else {
+            return Ok(tus_error(
+                StatusCode::BAD_REQUEST,
+                "Upload-Offset header is required",
+            ));
+        };
+
+        let meta = request_meta(&headers, "PATCH", &uri, connect);
+        let tenant = app.resolve_tenant(&meta).await;
+        let body: ByteStream = Box::pin(
+            request
+                .into_body()
+                .into_data_stream()
+                .map_err(std::io::Error::other),
+        );
+        let upload = adapter
+            .tus_append(
+                BlobCtx::new(tenant.tenant_id.0),
+                UploadId::from_string(id),
+                offset,
+                body,
+            )
+            .await
+            .map_err(DogError::from)?;
+
+        let mut res = StatusCode::NO_CONTENT.into_response();
+        tus_upload_headers(&mut res, &upload);
+        Ok(res)
+    };
+
+    let options = move || async move {
+        let mut res = StatusCode::NO_CONTENT.into_response();
+        let headers = res.headers_mut();
+        headers.insert("tus-version", HeaderValue::from_static(TUS_VERSION));
+        headers.insert("tus-extension", HeaderValue::from_static(TUS_EXTENSIONS));
+        headers.insert("tus-max-size", HeaderValue::from(max_size));
+        res
+    };
+
+    Router::new()
+        .route("/", routing::post(create))
+        .route("/{id}", routing::head(status).patch(append))
+        .layer(middleware::from_fn(require_tus_resumable))
+        .route("/", routing::options(options))
+}
+
+/// Reject requests without `Tus-Resumable: 1.0.0` and stamp the header on
+/// every response.
+async fn require_tus_resumable(request: Request<Body>, next: middleware::Next) -> Response {
+    let version = request
+        .headers()
+        .get("tus-resumable")
+        .and_then(|v| v.to_str().ok());
+    let mut res = if version == Some(TUS_VERSION) {
+        next.run(request).await
+    } else {
+        let mut res = tus_error(
+            StatusCode::PRECONDITION_FAILED,
+            "Unsupported or missing Tus-Resumable version",
+        );
+        res.headers_mut()
+            .insert("tus-version", HeaderValue::from_static(TUS_VERSION));
+        res
+    };
+    res.headers_mut()
+        .insert("tus-resumable", HeaderValue::from_static(TUS_VERSION));
+    res
+}
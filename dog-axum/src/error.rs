@@ -5,6 +5,17 @@ use axum::{
 };
 use dog_core::errors::DogError;
 
+/// Error type for Axum handlers.
+///
+/// Renders as the Feathers-style JSON body of the underlying [`DogError`]
+/// (`{ name, message, code, className, data?, errors? }`) with `code` as the
+/// HTTP status, so a `DogError::unprocessable(..).with_errors(..)` raised by a
+/// schema hook reaches the client as a 422 with its field errors. Errors that
+/// carry no `DogError` become a 500 `GeneralError`.
+///
+/// `DogError` itself cannot implement `IntoResponse` (both live outside this
+/// crate), so handlers return `Result<_, DogAxumError>` and use `?` on either
+/// `anyhow::Error` or `DogError`.
 #[derive(Debug)]
 pub struct DogAxumError(pub anyhow::Error);
 
@@ -14,20 +25,26 @@ impl From<anyhow::Error> for DogAxumError {
     }
 }
 
+impl From<DogError> for DogAxumError {
+    fn from(e: DogError) -> Self {
+        Self(e.into_anyhow())
+    }
+}
+
 impl IntoResponse for DogAxumError {
     fn into_response(self) -> Response {
         // If it’s a DogError (even if wrapped by anyhow contexts), preserve Feathers-ish fields
-        if let Some(dog) = self.0.chain().find_map(|e| e.downcast_ref::<DogError>()) {
-            let safe = dog.sanitize_for_client();
-            let status =
-                StatusCode::from_u16(safe.code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
-            return (status, Json(safe.to_json())).into_response();
+        if let Some(dog) = DogError::from_anyhow(&self.0) {
+            return dog_error_response(dog);
         }
 
         // Fallback: wrap any non-DogError as a DogError::GeneralError
-        let dog = DogError::general_error(self.0.to_string());
-        let safe = dog.sanitize_for_client();
-        let status = StatusCode::from_u16(safe.code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
-        (status, Json(safe.to_json())).into_response()
+        dog_error_response(&DogError::general_error(self.0.to_string()))
     }
 }
+
+fn dog_error_response(dog: &DogError) -> Response {
+    let safe = dog.sanitize_for_client();
+    let status = StatusCode::from_u16(safe.code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    (status, Json(safe.to_json())).into_response()
+}
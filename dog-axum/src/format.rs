@@ -0,0 +1,168 @@
+//! Response format negotiation (`Accept`).
+//!
+//! Service routes answer in JSON unless the client asks for MessagePack:
+//!
+//! - `application/json`, `application/*`, `*/*` or no `Accept` → JSON
+//! - `application/msgpack` (also `application/x-msgpack`,
+//!   `application/vnd.msgpack`) → MessagePack
+//! - anything else → `406 NotAcceptable`
+//!
+//! Entries are tried in order of their `q` value; `q=0` rules a type out.
+//! Errors are negotiated like results, so a MessagePack client also gets
+//! its `DogError` bodies as MessagePack. The 406 itself is JSON, since the
+//! client accepts nothing we can produce.
+
+use axum::body::Body;
+use axum::extract::Request;
+use axum::http::{header, HeaderMap, HeaderValue};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use dog_core::errors::DogError;
+
+use crate::DogAxumError;
+
+/// Largest response body re-encoded as MessagePack.
+const MAX_ENCODED_BODY: usize = 64 * 1024 * 1024;
+
+/// Body encoding chosen from the request's `Accept` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResponseFormat {
+    #[default]
+    Json,
+    MsgPack,
+}
+
+impl ResponseFormat {
+    /// The format to answer `headers` with, or `None` if the client accepts
+    /// none of them.
+    pub fn from_accept(headers: &HeaderMap) -> Option<Self> {
+        let accept = headers
+            .get_all(header::ACCEPT)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .collect::<Vec<_>>()
+            .join(",");
+        if accept.trim().is_empty() {
+            return Some(Self::Json);
+        }
+
+        let mut ranges: Vec<(&str, f32)> = accept
+            .split(',')
+            .filter_map(|range| {
+                let mut parts = range.split(';');
+                let media = parts.next()?.trim();
+                let q = parts
+                    .filter_map(|p| p.trim().strip_prefix("q="))
+                    .find_map(|q| q.trim().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (!media.is_empty()).then_some((media, q))
+            })
+            .collect();
+        // Stable, so equal q values keep the client's order.
+        ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        ranges
+            .into_iter()
+            .filter(|(_, q)| *q > 0.0)
+            .find_map(|(media, _)| Self::from_media_type(media))
+    }
+
+    fn from_media_type(media: &str) -> Option<Self> {
+        match media.to_ascii_lowercase().as_str() {
+            "application/json" | "application/*" | "*/*" => Some(Self::Json),
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                Some(Self::MsgPack)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::MsgPack => "application/msgpack",
+        }
+    }
+}
+
+/// Middleware that negotiates the response format for the routes it wraps.
+///
+/// Handlers keep returning JSON; for MessagePack clients the JSON response
+/// is re-encoded on the way out. `rest::service_router` applies it to every
+/// service route.
+pub async fn negotiate(req: Request, next: Next) -> Response {
+    let Some(format) = ResponseFormat::from_accept(req.headers()) else {
+        return DogAxumError::from(DogError::not_acceptable(
+            "Supported response formats: application/json, application/msgpack",
+        ))
+        .into_response();
+    };
+
+    let res = next.run(req).await;
+    match format {
+        ResponseFormat::Json => res,
+        ResponseFormat::MsgPack => to_msgpack(res).await,
+    }
+}
+
+async fn to_msgpack(res: Response) -> Response {
+    let is_json = res
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("application/json"));
+    if !is_json {
+        return res;
+    }
+
+    let (mut parts, body) = res.into_parts();
+    let encoded = async {
+        let bytes = axum::body::to_bytes(body, MAX_ENCODED_BODY).await?;
+        let value: serde_json::Value = serde_json::from_slice(&bytes)?;
+        anyhow::Ok(rmp_serde::to_vec_named(&NativeNumbers(&value))?)
+    }
+    .await;
+
+    match encoded {
+        Ok(bytes) => {
+            parts.headers.insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(ResponseFormat::MsgPack.content_type()),
+            );
+            parts.headers.remove(header::CONTENT_LENGTH);
+            Response::from_parts(parts, Body::from(bytes))
+        }
+        Err(e) => DogAxumError::from(e.context("Failed to encode response as MessagePack"))
+            .into_response(),
+    }
+}
+
+/// A JSON value that serializes its numbers as plain ints and floats.
+///
+/// With serde_json's `arbitrary_precision` (dog-axum's `arbitrary-precision`
+/// feature) a `Number` serializes as a marker struct only serde_json
+/// understands, so MessagePack would get a map instead of a number.
+/// MessagePack has no wider integers, so ones beyond 64 bits become floats.
+struct NativeNumbers<'a>(&'a serde_json::Value);
+
+impl serde::Serialize for NativeNumbers<'_> {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        use serde_json::Value;
+
+        match self.0 {
+            Value::Number(n) => {
+                if let Some(i) = n.as_i64() {
+                    s.serialize_i64(i)
+                } else if let Some(u) = n.as_u64() {
+                    s.serialize_u64(u)
+                } else {
+                    let f = n.as_f64();
+                    s.serialize_f64(f.unwrap_or_else(|| n.to_string().parse().unwrap_or(f64::NAN)))
+                }
+            }
+            Value::Array(items) => s.collect_seq(items.iter().map(NativeNumbers)),
+            Value::Object(map) => s.collect_map(map.iter().map(|(k, v)| (k, NativeNumbers(v)))),
+            other => other.serialize(s),
+        }
+    }
+}
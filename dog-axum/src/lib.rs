@@ -6,13 +6,22 @@
 //! from DogRS services and apps.
 
 pub mod app;
+#[cfg(feature = "blob")]
+pub mod blob;
 mod error;
+pub mod format;
 pub mod middlewares;
 pub mod oauth;
+pub mod openapi;
 pub mod params;
+mod realtime;
 pub mod rest;
+pub mod sse;
 pub mod state;
+#[cfg(feature = "ws")]
+pub mod ws;
 pub use error::DogAxumError;
 pub use state::DogAxumState;
 
 pub use app::{axum, AxumApp};
+pub use rest::router_for_app;
@@ -0,0 +1,133 @@
+use axum::http::{HeaderName, HeaderValue, Method};
+use dog_core::DogApp;
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
+
+/// Methods allowed when origins are configured but `http.cors.methods` is not:
+/// the verbs the REST adapter mounts.
+const DEFAULT_METHODS: [Method; 5] = [
+    Method::GET,
+    Method::POST,
+    Method::PUT,
+    Method::PATCH,
+    Method::DELETE,
+];
+
+/// Headers allowed when origins are configured but `http.cors.headers` is not.
+const DEFAULT_HEADERS: [&str; 3] = ["authorization", "content-type", "x-tenant-id"];
+
+/// Build a `CorsLayer` from the app's `http.cors.*` config
+///
+/// | key                          | value                                         |
+/// |------------------------------|-----------------------------------------------|
+/// | `http.cors.origins`          | origins allowed to call the API, or `*`       |
+/// | `http.cors.methods`          | defaults to `GET, POST, PUT, PATCH, DELETE`   |
+/// | `http.cors.headers`          | defaults to `authorization, content-type, x-tenant-id` |
+/// | `http.cors.allow_credentials`| `true` to allow cookies / auth headers        |
+///
+/// Lists may be JSON arrays (`set_value`) or comma-separated strings
+/// (`set`, env). Without `http.cors.origins` no cross-origin request is
+/// allowed; same-origin requests are unaffected.
+///
+/// Errors on values that are not valid origins, methods or header names, and
+/// on `*` combined with `allow_credentials`, which browsers reject.
+///
+/// ```rust,ignore
+/// builder.set("http.cors.origins", "https://app.example.com");
+/// let mut ax = dog_axum::axum(builder.build());
+/// ax.router = ax.router.layer(cors_from_config(&ax.app)?);
+/// ```
+pub fn cors_from_config<R, P>(app: &DogApp<R, P>) -> anyhow::Result<CorsLayer>
+where
+    R: Send + 'static,
+    P: Send + Clone + 'static,
+{
+    let Some(origins) = list(app, "http.cors.origins") else {
+        return Ok(CorsLayer::new());
+    };
+    let credentials = app
+        .get_json::<bool>("http.cors.allow_credentials")
+        .unwrap_or(false);
+
+    let mut wildcard = is_wildcard(&origins);
+    let origin = if wildcard {
+        AllowOrigin::any()
+    } else {
+        let origins = origins
+            .iter()
+            .map(|o| {
+                HeaderValue::from_str(o)
+                    .map_err(|_| anyhow::anyhow!("Invalid http.cors.origins entry: {o}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        AllowOrigin::list(origins)
+    };
+
+    let methods = match list(app, "http.cors.methods") {
+        Some(m) if is_wildcard(&m) => {
+            wildcard = true;
+            AllowMethods::any()
+        }
+        Some(m) => AllowMethods::list(
+            m.iter()
+                .map(|m| {
+                    Method::from_bytes(m.to_uppercase().as_bytes())
+                        .map_err(|_| anyhow::anyhow!("Invalid http.cors.methods entry: {m}"))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?,
+        ),
+        None => AllowMethods::list(DEFAULT_METHODS),
+    };
+
+    let headers = match list(app, "http.cors.headers") {
+        Some(h) if is_wildcard(&h) => {
+            wildcard = true;
+            AllowHeaders::any()
+        }
+        Some(h) => AllowHeaders::list(
+            h.iter()
+                .map(|h| {
+                    HeaderName::from_bytes(h.to_lowercase().as_bytes())
+                        .map_err(|_| anyhow::anyhow!("Invalid http.cors.headers entry: {h}"))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?,
+        ),
+        None => AllowHeaders::list(DEFAULT_HEADERS.map(HeaderName::from_static)),
+    };
+
+    if credentials && wildcard {
+        anyhow::bail!("http.cors.allow_credentials cannot be combined with `*`");
+    }
+
+    Ok(CorsLayer::new()
+        .allow_origin(origin)
+        .allow_methods(methods)
+        .allow_headers(headers)
+        .allow_credentials(credentials))
+}
+
+/// A config list as a JSON array or a comma-separated string; `None` when
+/// unset or empty.
+fn list<R, P>(app: &DogApp<R, P>, key: &str) -> Option<Vec<String>>
+where
+    R: Send + 'static,
+    P: Send + Clone + 'static,
+{
+    let items = match app.get_json::<Vec<String>>(key) {
+        Some(items) => items,
+        None => app
+            .get_as::<String>(key)?
+            .split(',')
+            .map(|s| s.to_string())
+            .collect(),
+    };
+    let items: Vec<String> = items
+        .into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    (!items.is_empty()).then_some(items)
+}
+
+fn is_wildcard(items: &[String]) -> bool {
+    items.iter().any(|s| s == "*")
+}
@@ -1,3 +1,9 @@
+pub mod cors;
 pub mod multipart;
+pub mod rate_limit;
 
+pub use cors::cors_from_config;
 pub use multipart::{FieldContext, FieldProcessor, FileEncoding, MultipartConfig, MultipartToJson};
+#[cfg(feature = "redis")]
+pub use rate_limit::RedisRateLimitStore;
+pub use rate_limit::{MemoryRateLimitStore, RateLimit, RateLimitKey, RateLimitStore, WindowCount};
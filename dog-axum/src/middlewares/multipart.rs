@@ -1,6 +1,8 @@
 use axum::{body::Body, extract::Request, http::StatusCode, response::Response};
 use serde_json::json;
 use std::collections::{HashMap, HashSet};
+#[cfg(feature = "blob")]
+use std::sync::Arc;
 use tower::{Layer, Service};
 
 /// Field processing context passed to hooks
@@ -38,6 +40,9 @@ pub struct MultipartConfig {
     pub field_processors: HashMap<String, FieldProcessor>,
     /// Global processors that run on all file fields
     pub global_processors: Vec<FieldProcessor>,
+    /// Blob storage that `FileEncoding::Stream` pipes file fields into
+    #[cfg(feature = "blob")]
+    pub blob_adapter: Option<Arc<dog_blob::BlobAdapter>>,
 }
 
 impl Clone for MultipartConfig {
@@ -52,6 +57,8 @@ impl Clone for MultipartConfig {
             include_metadata: self.include_metadata,
             field_processors: HashMap::new(), // Can't clone function pointers
             global_processors: Vec::new(),    // Can't clone function pointers
+            #[cfg(feature = "blob")]
+            blob_adapter: self.blob_adapter.clone(),
         }
     }
 }
@@ -65,6 +72,10 @@ pub enum FileEncoding {
     Metadata,
     /// Skip files entirely
     Skip,
+    /// Pipe file contents into the configured blob store as they arrive and
+    /// insert only the resulting blob reference (requires the `blob` feature)
+    #[cfg(feature = "blob")]
+    Stream,
 }
 
 impl Default for MultipartConfig {
@@ -79,6 +90,8 @@ impl Default for MultipartConfig {
             include_metadata: true,
             field_processors: HashMap::new(),
             global_processors: Vec::new(),
+            #[cfg(feature = "blob")]
+            blob_adapter: None,
         }
     }
 }
@@ -112,6 +125,18 @@ impl MultipartConfig {
         self
     }
 
+    /// Stream file fields straight into `adapter` (`FileEncoding::Stream`)
+    ///
+    /// Each file is stored under the tenant from `x-tenant-id` (`"default"`
+    /// when absent) and replaced in the JSON by
+    /// `{ "blob_id", "key", "filename", "content_type", "size" }`.
+    #[cfg(feature = "blob")]
+    pub fn stream_to_blobs(mut self, adapter: Arc<dog_blob::BlobAdapter>) -> Self {
+        self.file_encoding = FileEncoding::Stream;
+        self.blob_adapter = Some(adapter);
+        self
+    }
+
     /// Add field name to treat as file
     pub fn file_field(mut self, field_name: &str) -> Self {
         self.file_fields.insert(field_name.to_string());
@@ -268,11 +293,12 @@ async fn convert_multipart_to_json(
     req: Request<Body>,
     config: &MultipartConfig,
 ) -> Result<Request<Body>, Box<dyn std::error::Error + Send + Sync>> {
-    // Store original headers before extracting multipart data
-    let original_headers = req.headers().clone();
+    // Keep the request parts (headers, extensions) for the JSON request
+    let (mut parts, body) = req.into_parts();
 
     // Extract boundary from content-type header
-    let content_type = original_headers
+    let content_type = parts
+        .headers
         .get("content-type")
         .and_then(|v| v.to_str().ok())
         .unwrap_or("");
@@ -280,17 +306,22 @@ async fn convert_multipart_to_json(
     let boundary = content_type
         .split("boundary=")
         .nth(1)
-        .ok_or("Missing boundary in multipart content-type")?;
-
-    // Use multer instead of Axum's parser for large file support
-    let body_stream = req.into_body();
-    let body_bytes = axum::body::to_bytes(body_stream, 200 * 1024 * 1024)
-        .await // 200MB limit
-        .map_err(|e| format!("Failed to read request body: {}", e))?;
-
-    let mut multipart = multer::Multipart::new(
-        futures::stream::once(async { Ok::<bytes::Bytes, multer::Error>(body_bytes) }),
+        .ok_or("Missing boundary in multipart content-type")?
+        .to_string();
+
+    // Use multer instead of Axum's parser for large file support. The body is
+    // fed in as it arrives, so files are never held in memory as a whole.
+    let mut limits = multer::SizeLimit::new();
+    if let Some(max_total) = config.max_total_size {
+        limits = limits.whole_stream(max_total as u64);
+    }
+    if let Some(max_file) = config.max_file_size {
+        limits = limits.per_field(max_file as u64);
+    }
+    let mut multipart = multer::Multipart::with_constraints(
+        body.into_data_stream(),
         boundary,
+        multer::Constraints::new().size_limit(limits),
     );
     let mut json_map = HashMap::new();
 
@@ -312,6 +343,13 @@ async fn convert_multipart_to_json(
                     .is_some_and(|ct| !ct.starts_with("text/"))
         };
 
+        #[cfg(feature = "blob")]
+        if is_file_field && config.file_encoding == FileEncoding::Stream {
+            let blob_ref = stream_field_to_blob(field, &parts, config).await?;
+            json_map.insert(name, blob_ref);
+            continue;
+        }
+
         if is_file_field {
             // Handle file field with BlobRef approach - stream to temp storage
             println!(
@@ -411,12 +449,7 @@ async fn convert_multipart_to_json(
     let json_body = json!(json_map);
     let json_bytes = serde_json::to_vec(&json_body)?;
 
-    // Create new request with JSON body and preserve original headers
-    let (mut parts, _) = Request::new(Body::empty()).into_parts();
-
-    // Copy all original headers
-    parts.headers = original_headers;
-
+    // Create new request with JSON body, keeping the original parts
     // Update content-type and content-length for JSON body
     parts
         .headers
@@ -433,3 +466,56 @@ async fn convert_multipart_to_json(
 
     Ok(new_req)
 }
+
+/// `FileEncoding::Stream`: hand the field to the blob adapter chunk by chunk.
+#[cfg(feature = "blob")]
+async fn stream_field_to_blob(
+    field: multer::Field<'static>,
+    parts: &axum::http::request::Parts,
+    config: &MultipartConfig,
+) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
+    use futures::TryStreamExt;
+
+    let adapter = config
+        .blob_adapter
+        .as_ref()
+        .ok_or("FileEncoding::Stream requires a blob adapter (see stream_to_blobs)")?;
+
+    let name = field.name().unwrap_or("unknown").to_string();
+    let content_type = field.content_type().map(|ct| ct.to_string());
+    let filename = field.file_name().map(|f| f.to_string());
+
+    // Nothing has been stored yet, so reject before the upload starts.
+    if !config.allowed_content_types.is_empty() {
+        if let Some(ct) = &content_type {
+            if !config.allowed_content_types.contains(ct) {
+                return Err(
+                    format!("Content type '{}' not allowed for file '{}'", ct, name).into(),
+                );
+            }
+        }
+    }
+
+    let mut put = dog_blob::BlobPut::new();
+    if let Some(ct) = &content_type {
+        put = put.with_content_type(ct.clone());
+    }
+    if let Some(f) = &filename {
+        put = put.with_filename(f.clone());
+    }
+
+    let tenant = crate::rest::request_tenant(&parts.extensions, &parts.headers).tenant_id;
+    let body: dog_blob::ByteStream = Box::pin(field.map_err(std::io::Error::other));
+    let receipt = adapter
+        .put_streamed(dog_blob::BlobCtx::new(tenant.0), put, body)
+        .await
+        .map_err(|e| format!("Failed to store file '{}': {}", name, e))?;
+
+    Ok(json!({
+        "blob_id": receipt.id.as_str(),
+        "key": receipt.key,
+        "filename": receipt.filename,
+        "content_type": receipt.content_type,
+        "size": receipt.size_bytes
+    }))
+}
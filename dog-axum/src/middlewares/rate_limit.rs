@@ -0,0 +1,286 @@
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, Request},
+    http::{header, HeaderValue},
+    response::{IntoResponse, Response},
+};
+use dog_core::errors::DogError;
+use std::collections::{HashMap, VecDeque};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tower::{Layer, Service};
+
+use crate::DogAxumError;
+
+/// Derives the bucket a request is counted against; `None` leaves the
+/// request unlimited.
+pub type RateLimitKey = Arc<dyn Fn(&Request<Body>) -> Option<String> + Send + Sync>;
+
+/// Requests counted so far in the current window of one key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowCount {
+    /// Requests in the window, including the one just counted
+    pub count: u64,
+    /// Time until the window starts over
+    pub resets_in: Duration,
+}
+
+/// Where request counts live.
+///
+/// The in-memory store only limits a single instance; a shared store such as
+/// `RedisRateLimitStore` (`redis` feature) makes the limit apply across
+/// every instance behind the load balancer.
+#[async_trait::async_trait]
+pub trait RateLimitStore: Send + Sync {
+    /// Count one request for `key` in its current fixed window of `window`.
+    async fn hit(&self, key: &str, window: Duration) -> anyhow::Result<WindowCount>;
+}
+
+/// Per-process fixed-window counters (the default store).
+#[derive(Default)]
+pub struct MemoryRateLimitStore {
+    windows: Mutex<Windows>,
+}
+
+#[derive(Default)]
+struct Windows {
+    /// End and request count of each key's current window
+    counts: HashMap<String, (Instant, u64)>,
+    /// Window ends in the order the windows started, to drop ended windows
+    /// without scanning the rest
+    ends: VecDeque<(Instant, String)>,
+}
+
+impl MemoryRateLimitStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl RateLimitStore for MemoryRateLimitStore {
+    async fn hit(&self, key: &str, window: Duration) -> anyhow::Result<WindowCount> {
+        let now = Instant::now();
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        let Windows { counts, ends } = &mut *windows;
+
+        while let Some((end, _)) = ends.front() {
+            if *end > now {
+                break;
+            }
+            if let Some((end, key)) = ends.pop_front() {
+                // The key may have started a newer window since.
+                if counts.get(&key).is_some_and(|(current, _)| *current == end) {
+                    counts.remove(&key);
+                }
+            }
+        }
+
+        let entry = counts.entry(key.to_string()).or_insert((now, 0));
+        if entry.0 <= now {
+            *entry = (now + window, 0);
+            ends.push_back((entry.0, key.to_string()));
+        }
+        entry.1 += 1;
+
+        Ok(WindowCount {
+            count: entry.1,
+            resets_in: entry.0.saturating_duration_since(now),
+        })
+    }
+}
+
+/// Fixed-window counters in Redis, shared by every instance using the same
+/// server and prefix.
+#[cfg(feature = "redis")]
+pub struct RedisRateLimitStore {
+    conn: redis::aio::ConnectionManager,
+    prefix: String,
+}
+
+#[cfg(feature = "redis")]
+impl RedisRateLimitStore {
+    pub fn new(conn: redis::aio::ConnectionManager) -> Self {
+        Self {
+            conn,
+            prefix: "dog:ratelimit:".to_string(),
+        }
+    }
+
+    /// Namespace for the counter keys (default `dog:ratelimit:`)
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+}
+
+#[cfg(feature = "redis")]
+#[async_trait::async_trait]
+impl RateLimitStore for RedisRateLimitStore {
+    async fn hit(&self, key: &str, window: Duration) -> anyhow::Result<WindowCount> {
+        // INCR and the first PEXPIRE must be atomic, or a crash in between
+        // leaves a counter that never resets.
+        let script = redis::Script::new(
+            r"
+            local count = redis.call('INCR', KEYS[1])
+            if count == 1 then
+                redis.call('PEXPIRE', KEYS[1], ARGV[1])
+            end
+            return { count, redis.call('PTTL', KEYS[1]) }
+            ",
+        );
+        let mut conn = self.conn.clone();
+        let (count, ttl_ms): (u64, i64) = script
+            .key(format!("{}{}", self.prefix, key))
+            .arg(window.as_millis() as u64)
+            .invoke_async(&mut conn)
+            .await?;
+
+        Ok(WindowCount {
+            count,
+            resets_in: Duration::from_millis(ttl_ms.max(0) as u64),
+        })
+    }
+}
+
+/// Middleware that rejects requests over `limit` per `window` with `429`
+///
+/// Requests are counted per key, derived with [`RateLimit::key_by`] (the
+/// client IP by default). Rejected requests get a `TooManyRequests` error
+/// body and a `Retry-After` header with the seconds until the window resets.
+///
+/// If the store fails the request is let through and the error logged, so an
+/// unreachable Redis does not take the API down with it.
+///
+/// ```rust,ignore
+/// let server = AxumApp::new(app).use_service_with(
+///     "/messages",
+///     messages,
+///     RateLimit::new(100, Duration::from_secs(60)).key_by(RateLimit::tenant_key),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct RateLimit {
+    limit: u64,
+    window: Duration,
+    key: RateLimitKey,
+    store: Arc<dyn RateLimitStore>,
+}
+
+impl RateLimit {
+    /// Allow `limit` requests per client IP per `window`, counted in memory
+    pub fn new(limit: u64, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            key: Arc::new(Self::ip_key),
+            store: Arc::new(MemoryRateLimitStore::new()),
+        }
+    }
+
+    /// Count requests per key returned by `key`: [`RateLimit::ip_key`],
+    /// [`RateLimit::tenant_key`] or your own, e.g. the auth subject
+    pub fn key_by<F>(mut self, key: F) -> Self
+    where
+        F: Fn(&Request<Body>) -> Option<String> + Send + Sync + 'static,
+    {
+        self.key = Arc::new(key);
+        self
+    }
+
+    /// Keep counts in `store` instead of process memory
+    pub fn store<S: RateLimitStore + 'static>(mut self, store: S) -> Self {
+        self.store = Arc::new(store);
+        self
+    }
+
+    /// Key on the peer address. Requires serving with
+    /// `into_make_service_with_connect_info::<SocketAddr>()`; without it
+    /// requests are not limited.
+    pub fn ip_key(req: &Request<Body>) -> Option<String> {
+        req.extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| format!("ip:{}", addr.ip()))
+    }
+
+    /// Key on the tenant the app's `TenantResolver`s pick, as for REST.
+    /// Outside an `AxumApp` service mount this is the `x-tenant-id` header
+    /// (`"default"` when absent).
+    pub fn tenant_key(req: &Request<Body>) -> Option<String> {
+        let tenant = crate::rest::request_tenant(req.extensions(), req.headers());
+        Some(format!("tenant:{}", tenant.tenant_id.0))
+    }
+}
+
+impl<S> Layer<S> for RateLimit {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            config: self.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    config: RateLimit,
+}
+
+impl<S> Service<Request<Body>> for RateLimitService<S>
+where
+    S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let mut inner = self.inner.clone();
+        let config = self.config.clone();
+
+        Box::pin(async move {
+            let Some(key) = (config.key)(&req) else {
+                return inner.call(req).await;
+            };
+
+            match config.store.hit(&key, config.window).await {
+                Ok(hit) if hit.count > config.limit => Ok(too_many_requests(&config, hit)),
+                Ok(_) => inner.call(req).await,
+                Err(e) => {
+                    tracing::warn!("rate limit store failed, allowing request: {e:#}");
+                    inner.call(req).await
+                }
+            }
+        })
+    }
+}
+
+fn too_many_requests(config: &RateLimit, hit: WindowCount) -> Response {
+    let error = DogError::too_many_requests(format!(
+        "Rate limit of {} requests per {}s exceeded",
+        config.limit,
+        config.window.as_secs()
+    ));
+    let mut response = DogAxumError::from(error).into_response();
+
+    // Whole seconds, rounded up so clients never retry too early.
+    let retry_after = hit.resets_in.as_secs() + u64::from(hit.resets_in.subsec_nanos() > 0);
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(retry_after.max(1)));
+    response
+}
@@ -1,7 +1,10 @@
 use std::marker::PhantomData;
 
-use axum::extract::{OriginalUri, Query};
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, OriginalUri, Query};
 use axum::http::HeaderMap;
+use axum::Extension;
 use serde::de::DeserializeOwned;
 use serde_json::Value;
 
@@ -86,7 +89,9 @@ where
         let service_name = cfg.service_name;
         let method = cfg.login_method;
         let http_method = cfg.http_method;
-        move |headers: HeaderMap, OriginalUri(uri): OriginalUri| {
+        move |headers: HeaderMap,
+              connect: Option<Extension<ConnectInfo<SocketAddr>>>,
+              OriginalUri(uri): OriginalUri| {
             let app_arc = std::sync::Arc::clone(&app_arc);
             async move {
                 rest::call_custom_redirect_location::<Value, P>(
@@ -97,6 +102,7 @@ where
                     Default::default(),
                     http_method,
                     &uri,
+                    connect,
                     None,
                 )
                 .await
@@ -110,7 +116,10 @@ where
         let method = cfg.callback_method;
         let http_method = cfg.http_method;
         let payload = cfg.callback_payload.clone();
-        move |headers: HeaderMap, Query(query): Query<Q>, OriginalUri(uri): OriginalUri| {
+        move |headers: HeaderMap,
+              Query(query): Query<Q>,
+              connect: Option<Extension<ConnectInfo<SocketAddr>>>,
+              OriginalUri(uri): OriginalUri| {
             let app_arc = std::sync::Arc::clone(&app_arc);
             let payload = payload.clone();
             async move {
@@ -123,6 +132,7 @@ where
                     &query,
                     http_method,
                     &uri,
+                    connect,
                     &data,
                 )
                 .await
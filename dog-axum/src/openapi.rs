@@ -0,0 +1,233 @@
+//! OpenAPI 3 description of the REST routes mounted for services.
+//!
+//! [`spec`] describes the routes `rest::service_router` serves for each
+//! service, limited to the methods in its `ServiceCapabilities`:
+//!
+//! | route          | method   | service method |
+//! |----------------|----------|----------------|
+//! | `/{path}`      | `GET`    | `find`         |
+//! | `/{path}`      | `POST`   | `create`       |
+//! | `/{path}/{id}` | `GET`    | `get`          |
+//! | `/{path}/{id}` | `PUT`    | `update`       |
+//! | `/{path}/{id}` | `PATCH`  | `patch`        |
+//! | `/{path}/{id}` | `DELETE` | `remove`       |
+//!
+//! Request bodies use the service's JSON Schemas when it has them
+//! (`DogAppBuilder::set_service_schema`, which `#[schema]` modules call);
+//! otherwise, and for every response body, the spec only says "object".
+//! Custom methods (`x-service-method`) are not described.
+//!
+//! `info.title` and `info.version` come from the `openapi.title` and
+//! `openapi.version` config keys.
+
+use dog_core::{DogApp, ServiceMethodKind};
+use serde_json::{json, Map, Value};
+
+/// Build the spec for `services`, given as `(mount path, service name)`
+/// pairs. Names that are not registered on `app` are skipped.
+pub fn spec<R, P, I, S>(app: &DogApp<R, P>, services: I) -> Value
+where
+    R: Send + 'static,
+    P: Send + Clone + 'static,
+    I: IntoIterator<Item = (S, S)>,
+    S: AsRef<str>,
+{
+    let mut paths = Map::new();
+    let mut schemas = Map::new();
+    schemas.insert("Error".to_string(), error_schema());
+
+    for (path, name) in services {
+        let (path, name) = (path.as_ref(), name.as_ref());
+        let Ok(svc) = app.service(name) else {
+            continue;
+        };
+        let caps = svc.inner().capabilities();
+        let allows = |m: ServiceMethodKind| caps.allowed_methods.contains(&m);
+
+        let service_schema = app.service_schema(name);
+        let create_ref = service_schema.and_then(|s| s.get("create")).map(|create| {
+            schemas.insert(component_name(name, ""), create.clone());
+            schema_ref(&component_name(name, ""))
+        });
+        let patch_ref = service_schema.and_then(|s| s.get("patch")).map(|patch| {
+            schemas.insert(component_name(name, "Patch"), patch.clone());
+            schema_ref(&component_name(name, "Patch"))
+        });
+        // PUT falls back to the create schema when there is no update one.
+        let update_ref = service_schema
+            .and_then(|s| s.get("update"))
+            .map(|update| {
+                schemas.insert(component_name(name, "Update"), update.clone());
+                schema_ref(&component_name(name, "Update"))
+            })
+            .or_else(|| create_ref.clone());
+        let body = |schema: Option<Value>| schema.unwrap_or_else(|| json!({ "type": "object" }));
+
+        let base = format!("/{}", path.trim_matches('/'));
+        let mut collection = Map::new();
+        let mut item = Map::new();
+
+        if allows(ServiceMethodKind::Find) {
+            let list = if caps.paginate {
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "total": { "type": "integer" },
+                        "limit": { "type": "integer" },
+                        "skip": { "type": "integer" },
+                        "data": { "type": "array", "items": { "type": "object" } },
+                    },
+                })
+            } else {
+                json!({ "type": "array", "items": { "type": "object" } })
+            };
+            let mut op = operation(name, "find", None, list);
+            op["parameters"] = json!([
+                tenant_header(),
+                query_param("limit", "integer", "Page size"),
+                query_param("skip", "integer", "Records to skip"),
+                query_param(
+                    "sort",
+                    "string",
+                    "Comma-separated `field[:dir]`, e.g. `created_at:-1`"
+                ),
+            ]);
+            collection.insert("get".to_string(), op);
+        }
+        if allows(ServiceMethodKind::Create) {
+            let body = body(create_ref.clone());
+            collection.insert(
+                "post".to_string(),
+                operation(name, "create", Some(body), json!({ "type": "object" })),
+            );
+        }
+        for (verb, method, request) in [
+            ("get", ServiceMethodKind::Get, None),
+            (
+                "put",
+                ServiceMethodKind::Update,
+                Some(body(update_ref.clone())),
+            ),
+            (
+                "patch",
+                ServiceMethodKind::Patch,
+                Some(body(patch_ref.clone())),
+            ),
+            ("delete", ServiceMethodKind::Remove, None),
+        ] {
+            if allows(method.clone()) {
+                let mut op = operation(name, method.as_str(), request, json!({ "type": "object" }));
+                op["parameters"] = json!([id_param(), tenant_header()]);
+                item.insert(verb.to_string(), op);
+            }
+        }
+
+        if !collection.is_empty() {
+            paths.insert(base.clone(), Value::Object(collection));
+        }
+        if !item.is_empty() {
+            let item_path = format!("{}/{{id}}", base.trim_end_matches('/'));
+            paths.insert(item_path, Value::Object(item));
+        }
+    }
+
+    let title = app
+        .get_as::<String>("openapi.title")
+        .unwrap_or_else(|| "DogRS API".to_string());
+    let version = app
+        .get_as::<String>("openapi.version")
+        .unwrap_or_else(|| "1.0.0".to_string());
+
+    json!({
+        "openapi": "3.0.3",
+        "info": { "title": title, "version": version },
+        "paths": paths,
+        "components": { "schemas": schemas },
+    })
+}
+
+fn operation(service: &str, method: &str, request: Option<Value>, response: Value) -> Value {
+    let mut op = json!({
+        "operationId": format!("{service}.{method}"),
+        "tags": [service],
+        "parameters": [tenant_header()],
+        "responses": {
+            "200": {
+                "description": format!("Result of `{method}`"),
+                "content": { "application/json": { "schema": response } },
+            },
+            "default": {
+                "description": "Error",
+                "content": { "application/json": { "schema": schema_ref("Error") } },
+            },
+        },
+    });
+    if let Some(schema) = request {
+        op["requestBody"] = json!({
+            "required": true,
+            "content": { "application/json": { "schema": schema } },
+        });
+    }
+    op
+}
+
+/// `posts` → `Posts`, `posts` + `Patch` → `PostsPatch`.
+fn component_name(service: &str, suffix: &str) -> String {
+    let mut name: String = service
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect();
+    name.push_str(suffix);
+    name
+}
+
+fn schema_ref(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{name}") })
+}
+
+fn id_param() -> Value {
+    json!({ "name": "id", "in": "path", "required": true, "schema": { "type": "string" } })
+}
+
+fn tenant_header() -> Value {
+    json!({
+        "name": "x-tenant-id",
+        "in": "header",
+        "required": false,
+        "description": "Tenant the call runs as (`default` when absent)",
+        "schema": { "type": "string" },
+    })
+}
+
+fn query_param(name: &str, ty: &str, description: &str) -> Value {
+    json!({
+        "name": name,
+        "in": "query",
+        "required": false,
+        "description": description,
+        "schema": { "type": ty },
+    })
+}
+
+/// The Feathers-style body `DogAxumError` renders.
+fn error_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["name", "message", "code", "className"],
+        "properties": {
+            "name": { "type": "string" },
+            "message": { "type": "string" },
+            "code": { "type": "integer" },
+            "className": { "type": "string" },
+            "data": {},
+            "errors": {},
+        },
+    })
+}
@@ -1,7 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use axum::http::HeaderMap;
 use axum::http::Uri;
+use dog_core::errors::DogError;
 
 #[derive(Debug, Clone, Default)]
 pub struct RestParams {
@@ -38,6 +39,142 @@ impl RestParams {
 
         out
     }
+
+    /// Parse the standard list params (`limit`, `skip`, `sort`, `filter[..]`)
+    /// from the query string. See [`ListQuery::parse`].
+    pub fn list_query(&self) -> Result<ListQuery, DogError> {
+        ListQuery::parse(&self.query)
+    }
+}
+
+/// Sort direction for one [`ListQuery::sort`] entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    Asc,
+    Desc,
+}
+
+/// Standard list params for `find`, parsed from the query string:
+///
+/// `?limit=20&skip=40&sort=created_at:-1,title&filter[status]=active`
+///
+/// - `limit`: page size, defaults to [`ListQuery::DEFAULT_LIMIT`] and is
+///   clamped to [`ListQuery::MAX_LIMIT`]. `0` is allowed (count-only pages).
+/// - `skip`: records to skip, defaults to 0.
+/// - `sort`: comma-separated `field[:dir]`, where `dir` is `1`/`asc` or
+///   `-1`/`desc` (ascending when omitted). Order of fields is kept.
+/// - `filter[field]=value`: equality filters, one per field.
+///
+/// Other query keys are left alone for services to read from
+/// [`RestParams::query`]. Malformed values are a `BadRequest` rather than
+/// being silently ignored, so a `?` in the service turns them into a 400.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListQuery {
+    pub limit: usize,
+    pub skip: usize,
+    pub sort: Vec<(String, Order)>,
+    pub filter: BTreeMap<String, String>,
+}
+
+impl Default for ListQuery {
+    fn default() -> Self {
+        Self {
+            limit: Self::DEFAULT_LIMIT,
+            skip: 0,
+            sort: Vec::new(),
+            filter: BTreeMap::new(),
+        }
+    }
+}
+
+impl ListQuery {
+    pub const DEFAULT_LIMIT: usize = 10;
+    pub const MAX_LIMIT: usize = 50;
+
+    /// Parse with the default bounds.
+    pub fn parse(query: &HashMap<String, String>) -> Result<Self, DogError> {
+        Self::parse_with_bounds(query, Self::DEFAULT_LIMIT, Self::MAX_LIMIT)
+    }
+
+    /// Parse with a service-specific default and maximum page size.
+    pub fn parse_with_bounds(
+        query: &HashMap<String, String>,
+        default_limit: usize,
+        max_limit: usize,
+    ) -> Result<Self, DogError> {
+        let mut out = Self {
+            limit: default_limit.min(max_limit),
+            ..Self::default()
+        };
+
+        for (key, value) in query {
+            match key.as_str() {
+                "limit" => out.limit = parse_count("limit", value)?.min(max_limit),
+                "skip" => out.skip = parse_count("skip", value)?,
+                "sort" => out.sort = parse_sort(value)?,
+                _ => {
+                    if let Some(field) = filter_field(key)? {
+                        out.filter.insert(field.to_string(), value.clone());
+                    }
+                }
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+fn parse_count(name: &str, value: &str) -> Result<usize, DogError> {
+    value.trim().parse().map_err(|_| {
+        DogError::bad_request(format!(
+            "Invalid '{name}': expected a non-negative integer, got '{value}'"
+        ))
+    })
+}
+
+fn parse_sort(value: &str) -> Result<Vec<(String, Order)>, DogError> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let (field, dir) = match part.split_once(':') {
+                Some((field, dir)) => (field.trim(), dir.trim()),
+                None => (part, "1"),
+            };
+            if field.is_empty() {
+                return Err(DogError::bad_request(format!(
+                    "Invalid 'sort': missing field name in '{part}'"
+                )));
+            }
+            let order = match dir.to_ascii_lowercase().as_str() {
+                "1" | "asc" => Order::Asc,
+                "-1" | "desc" => Order::Desc,
+                _ => {
+                    return Err(DogError::bad_request(format!(
+                        "Invalid 'sort' direction for '{field}': expected 1, -1, asc or desc, got '{dir}'"
+                    )))
+                }
+            };
+            Ok((field.to_string(), order))
+        })
+        .collect()
+}
+
+/// `filter[status]` → `Some("status")`; unrelated keys → `None`.
+fn filter_field(key: &str) -> Result<Option<&str>, DogError> {
+    if key != "filter" && !key.starts_with("filter[") {
+        return Ok(None);
+    }
+    match key["filter".len()..]
+        .strip_prefix('[')
+        .and_then(|r| r.strip_suffix(']'))
+    {
+        Some(field) if !field.is_empty() && !field.contains(['[', ']']) => Ok(Some(field)),
+        _ => Err(DogError::bad_request(format!(
+            "Invalid filter key '{key}': expected filter[field]"
+        ))),
+    }
 }
 
 pub trait FromRestParams: Sized {
@@ -0,0 +1,49 @@
+//! Shared pieces of the real-time transports (`ws`, `sse`).
+
+use dog_core::events::{method_to_standard_event, ServiceEventData};
+use dog_core::{HookContext, HookResult};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// A standard service event in the shape sent to clients.
+pub(crate) struct ServiceEvent {
+    pub service: String,
+    pub event: String,
+    pub data: Value,
+}
+
+impl ServiceEvent {
+    /// Listeners are not told the event kind; for standard events it follows
+    /// from the method. Custom events carry no name, so they yield `None`, as
+    /// do contexts without a service name.
+    pub fn from_emit<R, P>(data: &ServiceEventData<'_, R>, ctx: &HookContext<R, P>) -> Option<Self>
+    where
+        R: Serialize + Send + 'static,
+        P: Send + Clone + 'static,
+    {
+        let ServiceEventData::Standard(result) = data else {
+            return None;
+        };
+        let event = method_to_standard_event(&ctx.method)?;
+        let data = match result {
+            HookResult::One(r) => serde_json::to_value(r),
+            HookResult::Many(rs) => serde_json::to_value(rs),
+            HookResult::Page { data, .. } => serde_json::to_value(data),
+        }
+        .ok()?;
+        Some(Self {
+            service: ctx.service.clone()?,
+            event: event.as_str().to_string(),
+            data,
+        })
+    }
+
+    /// `{ service, event, data }`
+    pub fn to_json(&self) -> Value {
+        json!({
+            "service": self.service,
+            "event": self.event,
+            "data": self.data,
+        })
+    }
+}
@@ -1,14 +1,15 @@
+use std::net::SocketAddr;
 use std::sync::Arc;
 
 use axum::{
-    body::Body,
-    extract::{OriginalUri, Path, Query, State},
-    http::{HeaderMap, Request},
+    body::{Body, Bytes},
+    extract::{ConnectInfo, DefaultBodyLimit, FromRequest, OriginalUri, Path, Query, State},
+    http::{HeaderMap, Request, StatusCode},
     response::Redirect,
-    routing, Json, Router,
+    routing, Extension, Json, Router,
 };
 use dog_core::errors::DogError;
-use dog_core::{tenant::TenantContext, DogApp, ServiceMethodKind};
+use dog_core::{tenant::TenantContext, DogApp, RequestMeta, ServiceMethodKind};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
@@ -17,6 +18,9 @@ use crate::{
     DogAxumError, DogAxumState,
 };
 
+/// The `x-tenant-id` tenant, or `"default"`. Service routes use
+/// [`DogApp::resolve_tenant`] instead, which honors the app's
+/// `TenantResolver`s; this is for middleware used outside an `AxumApp`.
 pub fn tenant_from_headers(headers: &HeaderMap) -> TenantContext {
     headers
         .get("x-tenant-id")
@@ -25,6 +29,75 @@ pub fn tenant_from_headers(headers: &HeaderMap) -> TenantContext {
         .unwrap_or_else(|| TenantContext::new("default"))
 }
 
+/// The tenant of a request, for middleware on a service mount: the one the
+/// app's `TenantResolver`s picked (see [`resolve_tenant_layer`]), else
+/// [`tenant_from_headers`].
+pub fn request_tenant(extensions: &axum::http::Extensions, headers: &HeaderMap) -> TenantContext {
+    extensions
+        .get::<TenantContext>()
+        .cloned()
+        .unwrap_or_else(|| tenant_from_headers(headers))
+}
+
+/// Resolve each request's tenant with [`DogApp::resolve_tenant`] before
+/// `router`'s middleware runs, and store it as a [`TenantContext`] request
+/// extension. `AxumApp` wraps every service mount in it, so middleware such
+/// as `RateLimit::tenant_key` and multipart blob streaming use the same
+/// tenant as the service call.
+pub fn resolve_tenant_layer<R, P>(router: Router<()>, app: Arc<DogApp<R, P>>) -> Router<()>
+where
+    R: Send + Sync + 'static,
+    P: Send + Sync + Clone + 'static,
+{
+    router.layer(axum::middleware::from_fn_with_state(
+        app,
+        insert_resolved_tenant::<R, P>,
+    ))
+}
+
+async fn insert_resolved_tenant<R, P>(
+    State(app): State<Arc<DogApp<R, P>>>,
+    mut req: Request<Body>,
+    next: axum::middleware::Next,
+) -> axum::response::Response
+where
+    R: Send + Sync + 'static,
+    P: Send + Sync + Clone + 'static,
+{
+    let connect = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .cloned()
+        .map(Extension);
+    let meta = request_meta(req.headers(), req.method().as_str(), req.uri(), connect);
+    let tenant = app.resolve_tenant(&meta).await;
+    req.extensions_mut().insert(tenant);
+    next.run(req).await
+}
+
+/// The [`RequestMeta`] hooks see for a REST call. `connect` is only there
+/// when the server was started with `into_make_service_with_connect_info`.
+pub fn request_meta(
+    headers: &HeaderMap,
+    method: &str,
+    uri: &axum::http::Uri,
+    connect: Option<Extension<ConnectInfo<SocketAddr>>>,
+) -> RequestMeta {
+    let mut meta = RequestMeta::new("rest", method, uri.path());
+    for (name, value) in headers.iter() {
+        if let Ok(value) = value.to_str() {
+            meta = meta.with_header(name.as_str(), value);
+        }
+    }
+    if let Some(Extension(ConnectInfo(addr))) = connect {
+        meta = meta.with_remote_addr(addr);
+    }
+    meta
+}
+
+/// Call custom `method` on `service_name` for a REST request. Pass the
+/// handler's `connect` extractor so hooks see the caller's address, as for
+/// CRUD routes.
 pub async fn call_custom<R, P>(
     app: &DogApp<R, P>,
     service_name: &str,
@@ -33,18 +106,20 @@ pub async fn call_custom<R, P>(
     query: std::collections::HashMap<String, String>,
     http_method: &'static str,
     uri: &axum::http::Uri,
+    connect: Option<Extension<ConnectInfo<SocketAddr>>>,
     data: Option<R>,
 ) -> Result<serde_json::Value, DogAxumError>
 where
     R: Serialize + DeserializeOwned + Send + Sync + 'static,
     P: FromRestParams + Send + Sync + Clone + 'static,
 {
-    let tenant = tenant_from_headers(headers);
+    let meta = request_meta(headers, http_method, uri, connect);
+    let tenant = app.resolve_tenant(&meta).await;
 
     let params = RestParams::from_parts("rest", headers, query, http_method, uri);
     let params = P::from_rest_params(params);
 
-    let svc = app.service(service_name)?;
+    let svc = app.service(service_name)?.with_request(meta);
 
     // Fail fast with a clear error if the service does not expose this custom method.
     ensure_custom_method_supported(service_name, &svc, method)?;
@@ -88,6 +163,7 @@ pub async fn call_custom_json<R, P>(
     query: std::collections::HashMap<String, String>,
     http_method: &'static str,
     uri: &axum::http::Uri,
+    connect: Option<Extension<ConnectInfo<SocketAddr>>>,
     data: Option<R>,
 ) -> Result<axum::Json<serde_json::Value>, DogAxumError>
 where
@@ -103,12 +179,14 @@ where
             query,
             http_method,
             uri,
+            connect,
             data,
         )
         .await?,
     ))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn call_custom_redirect<R, P>(
     app: &DogApp<R, P>,
     service_name: &str,
@@ -117,6 +195,7 @@ pub async fn call_custom_redirect<R, P>(
     query: std::collections::HashMap<String, String>,
     http_method: &'static str,
     uri: &axum::http::Uri,
+    connect: Option<Extension<ConnectInfo<SocketAddr>>>,
     data: Option<R>,
     location_key: &'static str,
 ) -> Result<Redirect, DogAxumError>
@@ -132,6 +211,7 @@ where
         query,
         http_method,
         uri,
+        connect,
         data,
     )
     .await?;
@@ -157,6 +237,7 @@ pub async fn call_custom_redirect_location<R, P>(
     query: std::collections::HashMap<String, String>,
     http_method: &'static str,
     uri: &axum::http::Uri,
+    connect: Option<Extension<ConnectInfo<SocketAddr>>>,
     data: Option<R>,
 ) -> Result<Redirect, DogAxumError>
 where
@@ -171,6 +252,7 @@ where
         query,
         http_method,
         uri,
+        connect,
         data,
         "location",
     )
@@ -254,6 +336,7 @@ pub async fn call_custom_json_qd<R, P, Q, D>(
     query: &Q,
     http_method: &'static str,
     uri: &axum::http::Uri,
+    connect: Option<Extension<ConnectInfo<SocketAddr>>>,
     data: &D,
 ) -> Result<axum::Json<serde_json::Value>, DogAxumError>
 where
@@ -274,6 +357,7 @@ where
         q,
         http_method,
         uri,
+        connect,
         Some(body),
     )
     .await
@@ -287,6 +371,7 @@ pub async fn call_custom_redirect_qd<R, P, Q, D>(
     query: &Q,
     http_method: &'static str,
     uri: &axum::http::Uri,
+    connect: Option<Extension<ConnectInfo<SocketAddr>>>,
     data: &D,
 ) -> Result<Redirect, DogAxumError>
 where
@@ -307,6 +392,7 @@ where
         q,
         http_method,
         uri,
+        connect,
         Some(body),
     )
     .await
@@ -320,6 +406,7 @@ pub async fn call_custom_json_q<R, P, Q>(
     query: &Q,
     http_method: &'static str,
     uri: &axum::http::Uri,
+    connect: Option<Extension<ConnectInfo<SocketAddr>>>,
 ) -> Result<axum::Json<serde_json::Value>, DogAxumError>
 where
     R: Serialize + DeserializeOwned + Send + Sync + 'static,
@@ -335,6 +422,7 @@ where
         q,
         http_method,
         uri,
+        connect,
         None,
     )
     .await
@@ -348,6 +436,7 @@ pub async fn call_custom_redirect_q<R, P, Q>(
     query: &Q,
     http_method: &'static str,
     uri: &axum::http::Uri,
+    connect: Option<Extension<ConnectInfo<SocketAddr>>>,
 ) -> Result<Redirect, DogAxumError>
 where
     R: Serialize + DeserializeOwned + Send + Sync + 'static,
@@ -363,11 +452,25 @@ where
         q,
         http_method,
         uri,
+        connect,
         None,
     )
     .await
 }
 
+/// Feathers-style paginated response body.
+fn page_envelope<R: Serialize>(
+    page: dog_core::Paginated<R>,
+) -> Result<serde_json::Value, DogAxumError> {
+    let data = serde_json::to_value(page.data).map_err(|e| anyhow::anyhow!(e))?;
+    Ok(serde_json::json!({
+        "total": page.total,
+        "limit": page.limit,
+        "skip": page.skip,
+        "data": data,
+    }))
+}
+
 async fn handle_custom_method<R, P>(
     service_name: &str,
     svc: &dog_core::app::ServiceHandle<R, P>,
@@ -384,11 +487,14 @@ where
     let capabilities = svc.inner().capabilities();
 
     // Check if any custom method with this name exists in capabilities
-    let method_name: Option<&'static str> =
-        capabilities.allowed_methods.iter().find_map(|m| match m {
-            ServiceMethodKind::Custom(name) if name.eq_ignore_ascii_case(method) => Some(*name),
-            _ => None,
-        });
+    let method_name: Option<String> =
+        capabilities
+            .allowed_methods
+            .into_iter()
+            .find_map(|m| match m {
+                ServiceMethodKind::Custom(name) if name.eq_ignore_ascii_case(method) => Some(name),
+                _ => None,
+            });
 
     let Some(method_name) = method_name else {
         return Err(DogError::bad_request(format!(
@@ -400,12 +506,63 @@ where
     };
 
     // Call the custom method through the DogRS pipeline so hooks run
-    let result = svc.custom(tenant, method_name, data, params).await?;
+    let result = svc.custom(tenant, &method_name, data, params).await?;
     let json_result = serde_json::to_value(result).map_err(|e| anyhow::anyhow!(e))?;
     Ok(axum::Json(json_result))
 }
 
+/// Mount every service registered on `app` under `/{name}`.
+///
+/// Each service gets the routes of [`service_router`]:
+///
+/// - `GET /{name}` → `find` (`find_paginated` when the service declares pagination)
+/// - `GET /{name}/{id}` → `get`
+/// - `POST /{name}` → `create`
+/// - `PUT /{name}/{id}` → `update`
+/// - `PATCH /{name}/{id}` → `patch`
+/// - `DELETE /{name}/{id}` → `remove`
+///
+/// `GET` or `POST /{name}` with an `x-service-method` header calls that custom
+/// method instead. The tenant comes from `x-tenant-id`, params from
+/// [`FromRestParams`], and errors render through [`DogAxumError`]. Responses
+/// are JSON or MessagePack depending on `Accept` (see [`crate::format`]).
+///
+/// Only services registered when this is called are mounted. Use
+/// [`crate::AxumApp::use_registered_services`] to get the default
+/// middleware as well.
+pub fn router_for_app<R, P>(app: &DogApp<R, P>) -> Router<()>
+where
+    R: Serialize + DeserializeOwned + Send + Sync + 'static,
+    P: FromRestParams + Send + Sync + Clone + 'static,
+{
+    let shared = Arc::new(app.clone());
+    app.service_names()
+        .into_iter()
+        .fold(Router::new(), |router, name| {
+            let path = format!("/{name}");
+            router.nest(&path, service_router(Arc::new(name), Arc::clone(&shared)))
+        })
+}
+
+/// Largest JSON body the service routes accept unless the mount sets its own
+/// limit.
+pub const DEFAULT_BODY_LIMIT: usize = 10 * 1024 * 1024;
+
 pub fn service_router<R, P>(service_name: Arc<String>, app: Arc<DogApp<R, P>>) -> Router<()>
+where
+    R: Serialize + DeserializeOwned + Send + Sync + 'static,
+    P: FromRestParams + Send + Sync + Clone + 'static,
+{
+    service_router_with_body_limit(service_name, app, DEFAULT_BODY_LIMIT)
+}
+
+/// [`service_router`] with its `DefaultBodyLimit` set to `body_limit` bytes.
+/// Bodies over the limit are rejected with `413 PayloadTooLarge`.
+pub fn service_router_with_body_limit<R, P>(
+    service_name: Arc<String>,
+    app: Arc<DogApp<R, P>>,
+    body_limit: usize,
+) -> Router<()>
 where
     R: Serialize + DeserializeOwned + Send + Sync + 'static,
     P: FromRestParams + Send + Sync + Clone + 'static,
@@ -420,13 +577,15 @@ where
                 move |State(state): State<DogAxumState<R, P>>,
                       headers: HeaderMap,
                       Query(query): Query<std::collections::HashMap<String, String>>,
+                      connect: Option<Extension<ConnectInfo<SocketAddr>>>,
                       OriginalUri(uri): OriginalUri| async move {
-                    let tenant = tenant_from_headers(&headers);
+                    let meta = request_meta(&headers, "GET", &uri, connect);
+                    let tenant = state.app.resolve_tenant(&meta).await;
 
                     let params = RestParams::from_parts("rest", &headers, query, "GET", &uri);
                     let params = P::from_rest_params(params);
 
-                    let svc = state.app.service(&service_name)?;
+                    let svc = state.app.service(&service_name)?.with_request(meta);
 
                     // Check for custom method header
                     if let Some(custom_method) = headers
@@ -444,6 +603,13 @@ where
                         .await;
                     }
 
+                    // Services that opt into pagination get the Feathers envelope
+                    // `{ total, limit, skip, data }`; everyone else a bare array.
+                    if svc.inner().capabilities().paginate {
+                        let page = svc.find_paginated(tenant, params).await?;
+                        return Ok::<_, DogAxumError>(Json(page_envelope(page)?));
+                    }
+
                     let res = svc.find(tenant, params).await?;
                     Ok::<_, DogAxumError>(Json(
                         serde_json::to_value(res).map_err(|e| anyhow::anyhow!(e))?,
@@ -455,20 +621,14 @@ where
                 move |State(state): State<DogAxumState<R, P>>,
                       headers: HeaderMap,
                       Query(query): Query<std::collections::HashMap<String, String>>,
+                      connect: Option<Extension<ConnectInfo<SocketAddr>>>,
                       OriginalUri(uri): OriginalUri,
                       request: Request<Body>| async move {
-                    let tenant = tenant_from_headers(&headers);
-
-                    // Use clean Json extractor - multipart is handled by middleware
-                    let body_bytes = axum::body::to_bytes(request.into_body(), 10 * 1024 * 1024)
-                        .await // 10MB limit for JSON
-                        .map_err(|e| {
-                            dog_core::errors::DogError::bad_request(format!(
-                                "Failed to read request body: {}",
-                                e
-                            ))
-                            .into_anyhow()
-                        })?;
+                    let meta = request_meta(&headers, "POST", &uri, connect);
+                    let tenant = state.app.resolve_tenant(&meta).await;
+
+                    // Plain JSON body - multipart is handled by middleware
+                    let body_bytes = read_body(request).await?;
 
                     let data: R = serde_json::from_slice(&body_bytes).map_err(|e| {
                         dog_core::errors::DogError::bad_request(format!(
@@ -484,7 +644,7 @@ where
                     let params = RestParams::from_parts("rest", &headers, query, "POST", &uri);
                     let params = P::from_rest_params(params);
 
-                    let svc = state.app.service(&service_name)?;
+                    let svc = state.app.service(&service_name)?.with_request(meta);
 
                     // Check for custom method header
                     if let Some(custom_method) = headers
@@ -516,14 +676,16 @@ where
                 move |State(state): State<DogAxumState<R, P>>,
                       headers: HeaderMap,
                       Query(query): Query<std::collections::HashMap<String, String>>,
+                      connect: Option<Extension<ConnectInfo<SocketAddr>>>,
                       OriginalUri(uri): OriginalUri,
                       Path(id): Path<String>| async move {
-                    let tenant = tenant_from_headers(&headers);
+                    let meta = request_meta(&headers, "GET", &uri, connect);
+                    let tenant = state.app.resolve_tenant(&meta).await;
 
                     let params = RestParams::from_parts("rest", &headers, query, "GET", &uri);
                     let params = P::from_rest_params(params);
 
-                    let svc = state.app.service(&service_name)?;
+                    let svc = state.app.service(&service_name)?.with_request(meta);
                     let res = svc.get(tenant, &id, params).await?;
                     Ok::<_, DogAxumError>(Json(res))
                 }
@@ -533,20 +695,14 @@ where
                 move |State(state): State<DogAxumState<R, P>>,
                       headers: HeaderMap,
                       Query(query): Query<std::collections::HashMap<String, String>>,
+                      connect: Option<Extension<ConnectInfo<SocketAddr>>>,
                       OriginalUri(uri): OriginalUri,
                       Path(id): Path<String>,
                       request: Request<Body>| async move {
-                    let tenant = tenant_from_headers(&headers);
-
-                    let body_bytes = axum::body::to_bytes(request.into_body(), 10 * 1024 * 1024)
-                        .await
-                        .map_err(|e| {
-                            dog_core::errors::DogError::bad_request(format!(
-                                "Failed to read request body: {}",
-                                e
-                            ))
-                            .into_anyhow()
-                        })?;
+                    let meta = request_meta(&headers, "PUT", &uri, connect);
+                    let tenant = state.app.resolve_tenant(&meta).await;
+
+                    let body_bytes = read_body(request).await?;
 
                     let data: R = serde_json::from_slice(&body_bytes).map_err(|e| {
                         dog_core::errors::DogError::bad_request(format!(
@@ -562,7 +718,7 @@ where
                     let params = RestParams::from_parts("rest", &headers, query, "PUT", &uri);
                     let params = P::from_rest_params(params);
 
-                    let svc = state.app.service(&service_name)?;
+                    let svc = state.app.service(&service_name)?.with_request(meta);
                     let res = svc.update(tenant, &id, data, params).await?;
                     Ok::<_, DogAxumError>(Json(res))
                 }
@@ -572,20 +728,14 @@ where
                 move |State(state): State<DogAxumState<R, P>>,
                       headers: HeaderMap,
                       Query(query): Query<std::collections::HashMap<String, String>>,
+                      connect: Option<Extension<ConnectInfo<SocketAddr>>>,
                       OriginalUri(uri): OriginalUri,
                       Path(id): Path<String>,
                       request: Request<Body>| async move {
-                    let tenant = tenant_from_headers(&headers);
-
-                    let body_bytes = axum::body::to_bytes(request.into_body(), 10 * 1024 * 1024)
-                        .await
-                        .map_err(|e| {
-                            dog_core::errors::DogError::bad_request(format!(
-                                "Failed to read request body: {}",
-                                e
-                            ))
-                            .into_anyhow()
-                        })?;
+                    let meta = request_meta(&headers, "PATCH", &uri, connect);
+                    let tenant = state.app.resolve_tenant(&meta).await;
+
+                    let body_bytes = read_body(request).await?;
 
                     let data: R = serde_json::from_slice(&body_bytes).map_err(|e| {
                         dog_core::errors::DogError::bad_request(format!(
@@ -601,7 +751,7 @@ where
                     let params = RestParams::from_parts("rest", &headers, query, "PATCH", &uri);
                     let params = P::from_rest_params(params);
 
-                    let svc = state.app.service(&service_name)?;
+                    let svc = state.app.service(&service_name)?.with_request(meta);
                     let res = svc.patch(tenant, Some(&id), data, params).await?;
                     Ok::<_, DogAxumError>(Json(res))
                 }
@@ -611,18 +761,38 @@ where
                 move |State(state): State<DogAxumState<R, P>>,
                       headers: HeaderMap,
                       Query(query): Query<std::collections::HashMap<String, String>>,
+                      connect: Option<Extension<ConnectInfo<SocketAddr>>>,
                       OriginalUri(uri): OriginalUri,
                       Path(id): Path<String>| async move {
-                    let tenant = tenant_from_headers(&headers);
+                    let meta = request_meta(&headers, "DELETE", &uri, connect);
+                    let tenant = state.app.resolve_tenant(&meta).await;
 
                     let params = RestParams::from_parts("rest", &headers, query, "DELETE", &uri);
                     let params = P::from_rest_params(params);
 
-                    let svc = state.app.service(&service_name)?;
+                    let svc = state.app.service(&service_name)?.with_request(meta);
                     let res = svc.remove(tenant, Some(&id), params).await?;
                     Ok::<_, DogAxumError>(Json(res))
                 }
             }),
         )
+        .layer(DefaultBodyLimit::max(body_limit))
+        .layer(axum::middleware::from_fn(crate::format::negotiate))
         .with_state(state)
 }
+
+/// Buffer a JSON request body, within the `DefaultBodyLimit` of the route.
+async fn read_body(request: Request<Body>) -> Result<Bytes, DogError> {
+    Bytes::from_request(request, &())
+        .await
+        .map_err(|rejection| {
+            if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE {
+                DogError::payload_too_large("Request body is too large")
+            } else {
+                DogError::bad_request(format!(
+                    "Failed to read request body: {}",
+                    rejection.body_text()
+                ))
+            }
+        })
+}
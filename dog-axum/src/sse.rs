@@ -0,0 +1,263 @@
+//! Service events as a Server-Sent Events stream.
+//!
+//! A lighter alternative to the WebSocket channel for read-only consumers
+//! such as dashboards or upload-progress displays. Mount with [`AxumApp::use_sse`](crate::AxumApp::use_sse) or
+//! [`router`]; clients then `GET` the endpoint:
+//!
+//! - The stream is scoped to the tenant of the request, resolved as for
+//!   REST (`DogApp::resolve_tenant`).
+//! - `?services=posts,authors` limits it to those services; without it every
+//!   service's events are sent.
+//! - Each SSE message has an `id` and `{ service, event, data }` as its JSON
+//!   data, where `event` is `created`, `updated`, `patched` or `removed`.
+//! - A reconnecting client that sends `Last-Event-ID` first receives the
+//!   events it missed, as long as they are still among the last
+//!   [`REPLAY_BUFFER`] events of the endpoint.
+//!
+//! [`json_stream`] sends any stream of JSON values, such as the answers of a
+//! large find, as an SSE response of its own.
+//!
+//! The endpoint registers a single `DogEventHub` listener when the router is
+//! built and removes it when the router is dropped. Connections only hold a
+//! receiver on that listener's channel, so a disconnect releases everything.
+//! A client that falls more than [`REPLAY_BUFFER`] events behind is
+//! disconnected; its `EventSource` reconnects with `Last-Event-ID` and
+//! catches up from the buffer.
+
+use std::collections::{HashSet, VecDeque};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use axum::extract::{ConnectInfo, OriginalUri, Query, State};
+use axum::http::HeaderMap;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Extension, Router};
+use dog_core::events::{EventPat, ListenerId, ServiceEventPattern, ServiceNamePat};
+use dog_core::{DogApp, TenantId};
+use futures::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::broadcast;
+
+use crate::realtime::ServiceEvent;
+use crate::rest::request_meta;
+
+/// Recent events kept per endpoint for `Last-Event-ID` resumption.
+pub const REPLAY_BUFFER: usize = 1024;
+
+#[derive(Clone)]
+struct LoggedEvent {
+    id: u64,
+    tenant: TenantId,
+    event: Arc<ServiceEvent>,
+}
+
+/// Ids and replay buffer for one endpoint. Ids start at 1 and only grow.
+struct EventLog {
+    next_id: u64,
+    recent: VecDeque<LoggedEvent>,
+    tx: broadcast::Sender<LoggedEvent>,
+}
+
+impl EventLog {
+    fn push(&mut self, tenant: TenantId, event: ServiceEvent) {
+        self.next_id += 1;
+        let logged = LoggedEvent {
+            id: self.next_id,
+            tenant,
+            event: Arc::new(event),
+        };
+        if self.recent.len() == REPLAY_BUFFER {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(logged.clone());
+        // No receivers just means nobody is connected right now.
+        let _ = self.tx.send(logged);
+    }
+}
+
+/// Removes the endpoint's hub listener once the last router clone is gone.
+struct ListenerGuard<R, P>
+where
+    R: Send + 'static,
+    P: Send + Clone + 'static,
+{
+    app: Arc<DogApp<R, P>>,
+    id: ListenerId,
+}
+
+impl<R, P> Drop for ListenerGuard<R, P>
+where
+    R: Send + 'static,
+    P: Send + Clone + 'static,
+{
+    fn drop(&mut self) {
+        self.app.off(self.id);
+    }
+}
+
+struct SseState<R, P>
+where
+    R: Send + 'static,
+    P: Send + Clone + 'static,
+{
+    log: Arc<Mutex<EventLog>>,
+    listener: ListenerGuard<R, P>,
+}
+
+#[derive(Deserialize)]
+struct SseQuery {
+    services: Option<String>,
+}
+
+/// A router serving the SSE stream at `/`; nest it wherever it should live
+/// (`AxumApp::use_sse` mounts it at the given path).
+pub fn router<R, P>(app: Arc<DogApp<R, P>>) -> Router<()>
+where
+    R: Serialize + Send + Sync + 'static,
+    P: Send + Sync + Clone + 'static,
+{
+    let (tx, _) = broadcast::channel(REPLAY_BUFFER);
+    let log = Arc::new(Mutex::new(EventLog {
+        next_id: 0,
+        recent: VecDeque::with_capacity(REPLAY_BUFFER),
+        tx,
+    }));
+
+    let id = app.on_pattern(
+        ServiceEventPattern {
+            service: ServiceNamePat::Any,
+            event: EventPat::Any,
+        },
+        {
+            let log = Arc::clone(&log);
+            Arc::new(move |data, ctx| {
+                if let Some(event) = ServiceEvent::from_emit(data, ctx) {
+                    log.lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .push(ctx.tenant.tenant_id.clone(), event);
+                }
+                Box::pin(async { Ok(()) })
+            })
+        },
+    );
+
+    let state = Arc::new(SseState {
+        log,
+        listener: ListenerGuard { app, id },
+    });
+
+    Router::new()
+        .route("/", get(stream_events::<R, P>))
+        .with_state(state)
+}
+
+async fn stream_events<R, P>(
+    State(state): State<Arc<SseState<R, P>>>,
+    headers: HeaderMap,
+    connect: Option<Extension<ConnectInfo<SocketAddr>>>,
+    OriginalUri(uri): OriginalUri,
+    Query(query): Query<SseQuery>,
+) -> impl IntoResponse
+where
+    R: Send + 'static,
+    P: Send + Clone + 'static,
+{
+    let meta = request_meta(&headers, "GET", &uri, connect);
+    let tenant = state.listener.app.resolve_tenant(&meta).await.tenant_id;
+    let services: Option<HashSet<String>> = query.services.map(|s| {
+        s.split(',')
+            .map(|name| name.trim().trim_start_matches('/').to_string())
+            .filter(|name| !name.is_empty())
+            .collect()
+    });
+    let last_seen = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok());
+
+    // Subscribe before reading the buffer so nothing falls in between;
+    // anything already replayed is skipped on the live side by id.
+    let (rx, replay) = {
+        let log = state.log.lock().unwrap_or_else(|e| e.into_inner());
+        let rx = log.tx.subscribe();
+        let replay: Vec<LoggedEvent> = match last_seen {
+            Some(last) => log.recent.iter().filter(|e| e.id > last).cloned().collect(),
+            None => Vec::new(),
+        };
+        (rx, replay)
+    };
+    let replayed_up_to = replay.last().map(|e| e.id).or(last_seen).unwrap_or(0);
+
+    let visible = move |e: &LoggedEvent| {
+        e.tenant == tenant
+            && services
+                .as_ref()
+                .is_none_or(|names| names.contains(&e.event.service))
+    };
+    let visible_live = visible.clone();
+
+    let live = stream::unfold(rx, |mut rx| async move {
+        match rx.recv().await {
+            Ok(e) => Some((e, rx)),
+            // Lagged: end the stream so the client resumes from the buffer.
+            Err(_) => None,
+        }
+    })
+    .filter(move |e| std::future::ready(e.id > replayed_up_to && visible_live(e)));
+
+    let events = stream::iter(replay)
+        .filter(move |e| std::future::ready(visible(e)))
+        .chain(live)
+        .map(to_sse_event);
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+fn to_sse_event(e: LoggedEvent) -> Result<Event, Infallible> {
+    Ok(Event::default()
+        .id(e.id.to_string())
+        .data(e.event.to_json().to_string()))
+}
+
+/// Send `items` as Server-Sent Events as they are produced, for results
+/// too large to buffer in one response body.
+///
+/// Each item is a message with its 1-based position as `id` and the value
+/// as JSON `data`. The stream ends with an `end` event, or with an `error`
+/// event carrying `{ "message" }` at the first error. Dropping the response
+/// (e.g. when the client disconnects) drops `items`.
+pub fn json_stream<S>(items: S) -> impl IntoResponse
+where
+    S: Stream<Item = anyhow::Result<Value>> + Send + 'static,
+{
+    let events = stream::unfold(
+        (items.boxed(), 0u64, false),
+        |(mut items, sent, done)| async move {
+            if done {
+                return None;
+            }
+            let (event, done) = match items.next().await {
+                Some(Ok(value)) => (
+                    Event::default()
+                        .id((sent + 1).to_string())
+                        .data(value.to_string()),
+                    false,
+                ),
+                Some(Err(e)) => (
+                    Event::default()
+                        .event("error")
+                        .data(json!({ "message": e.to_string() }).to_string()),
+                    true,
+                ),
+                None => (Event::default().event("end").data(""), true),
+            };
+            Some((Ok::<_, Infallible>(event), (items, sent + 1, done)))
+        },
+    );
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
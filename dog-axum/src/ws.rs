@@ -0,0 +1,177 @@
+//! Real-time service events over WebSocket (Feathers-style channels).
+//!
+//! Mount with [`AxumApp::use_ws`](crate::AxumApp::use_ws) or [`router`].
+//! Each connection is scoped to the tenant of the upgrade request, resolved
+//! as for REST (`DogApp::resolve_tenant`), and only sees events whose hook
+//! context carries the same tenant.
+//!
+//! Client → server frames:
+//! ```json
+//! { "type": "subscribe", "service": "messages" }
+//! { "type": "unsubscribe", "service": "messages" }
+//! ```
+//! Each is acknowledged with `{ "type": "subscribed" | "unsubscribed", "service": ... }`,
+//! sent after the subscription change has taken effect.
+//!
+//! Server → client event frames:
+//! ```json
+//! { "type": "event", "service": "messages", "event": "created", "data": { ... } }
+//! ```
+//! `event` is one of `created`, `updated`, `patched`, `removed` and `data` is
+//! the method result. Custom events (`DogApp::emit_custom`) are not forwarded.
+//! Malformed client frames get `{ "type": "error", "message": ... }`.
+//!
+//! Events are queued per connection; a client that falls
+//! [`CONNECTION_BUFFER`] frames behind drops the newest events rather than
+//! slowing down the service call that emitted them.
+
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{ConnectInfo, OriginalUri, State};
+use axum::http::HeaderMap;
+use axum::response::Response;
+use axum::routing::get;
+use axum::{Extension, Router};
+use dog_core::events::{EventListener, EventPat, ServiceEventPattern, ServiceNamePat};
+use dog_core::{DogApp, TenantId};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+
+use crate::realtime::ServiceEvent;
+use crate::rest::request_meta;
+use crate::DogAxumState;
+
+/// Outgoing frames queued per connection before events are dropped.
+pub const CONNECTION_BUFFER: usize = 256;
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum ClientFrame {
+    Subscribe { service: String },
+    Unsubscribe { service: String },
+}
+
+/// A router serving the WebSocket endpoint at `/`; nest it wherever it
+/// should live (`AxumApp::use_ws` mounts it at the given path).
+pub fn router<R, P>(app: Arc<DogApp<R, P>>) -> Router<()>
+where
+    R: Serialize + Send + Sync + 'static,
+    P: Send + Sync + Clone + 'static,
+{
+    Router::new()
+        .route("/", get(upgrade::<R, P>))
+        .with_state(DogAxumState { app })
+}
+
+async fn upgrade<R, P>(
+    State(state): State<DogAxumState<R, P>>,
+    headers: HeaderMap,
+    connect: Option<Extension<ConnectInfo<SocketAddr>>>,
+    OriginalUri(uri): OriginalUri,
+    ws: WebSocketUpgrade,
+) -> Response
+where
+    R: Serialize + Send + Sync + 'static,
+    P: Send + Sync + Clone + 'static,
+{
+    let meta = request_meta(&headers, "GET", &uri, connect);
+    let tenant = state.app.resolve_tenant(&meta).await.tenant_id;
+    ws.on_upgrade(move |socket| serve(socket, state.app, tenant))
+}
+
+async fn serve<R, P>(mut socket: WebSocket, app: Arc<DogApp<R, P>>, tenant: TenantId)
+where
+    R: Serialize + Send + Sync + 'static,
+    P: Send + Sync + Clone + 'static,
+{
+    let subscriptions: Arc<RwLock<HashSet<String>>> = Arc::default();
+    let (tx, mut rx) = mpsc::channel::<Value>(CONNECTION_BUFFER);
+
+    let listener_id = app.on_pattern(
+        ServiceEventPattern {
+            service: ServiceNamePat::Any,
+            event: EventPat::Any,
+        },
+        forward_events(tenant, Arc::clone(&subscriptions), tx),
+    );
+
+    loop {
+        tokio::select! {
+            incoming = socket.recv() => {
+                let text = match incoming {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => continue,
+                };
+                let reply = handle_client_frame(text.as_str(), &subscriptions);
+                if send(&mut socket, &reply).await.is_err() {
+                    break;
+                }
+            }
+            Some(frame) = rx.recv() => {
+                if send(&mut socket, &frame).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    app.off(listener_id);
+}
+
+fn handle_client_frame(text: &str, subscriptions: &RwLock<HashSet<String>>) -> Value {
+    let frame = match serde_json::from_str::<ClientFrame>(text) {
+        Ok(frame) => frame,
+        Err(e) => return json!({ "type": "error", "message": format!("Invalid frame: {e}") }),
+    };
+    let mut subs = subscriptions.write().unwrap_or_else(|e| e.into_inner());
+    match frame {
+        ClientFrame::Subscribe { service } => {
+            let service = service.trim_start_matches('/').to_string();
+            subs.insert(service.clone());
+            json!({ "type": "subscribed", "service": service })
+        }
+        ClientFrame::Unsubscribe { service } => {
+            let service = service.trim_start_matches('/').to_string();
+            subs.remove(&service);
+            json!({ "type": "unsubscribed", "service": service })
+        }
+    }
+}
+
+async fn send(socket: &mut WebSocket, frame: &Value) -> Result<(), axum::Error> {
+    socket.send(Message::Text(frame.to_string().into())).await
+}
+
+/// Event listener that queues matching events for one connection.
+fn forward_events<R, P>(
+    tenant: TenantId,
+    subscriptions: Arc<RwLock<HashSet<String>>>,
+    tx: mpsc::Sender<Value>,
+) -> EventListener<R, P>
+where
+    R: Serialize + Send + Sync + 'static,
+    P: Send + Sync + Clone + 'static,
+{
+    Arc::new(move |data, ctx| {
+        let subscribed = ctx.service.as_deref().is_some_and(|service| {
+            subscriptions
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .contains(service)
+        });
+        if subscribed && ctx.tenant.tenant_id == tenant {
+            if let Some(event) = ServiceEvent::from_emit(data, ctx) {
+                let mut frame = event.to_json();
+                frame["type"] = json!("event");
+                // Full buffer or closed socket: drop, never block the emitter.
+                let _ = tx.try_send(frame);
+            }
+        }
+        Box::pin(async { Ok(()) })
+    })
+}
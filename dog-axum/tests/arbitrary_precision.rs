@@ -0,0 +1,85 @@
+#![cfg(feature = "arbitrary-precision")]
+//! Run with `cargo test -p dog-axum --features arbitrary-precision`.
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::Request;
+use dog_axum::axum;
+use dog_core::tenant::TenantContext;
+use dog_core::{DogApp, DogService};
+use http_body_util::BodyExt;
+use serde_json::Value;
+use tower::ServiceExt;
+
+#[dog_schema::schema(service = "ledger")]
+mod ledger_schema {
+    #[create]
+    pub struct CreateEntry {
+        pub id: u128,
+        pub amount: f64,
+    }
+}
+
+struct Ledger;
+
+#[async_trait::async_trait]
+impl DogService<Value, ()> for Ledger {
+    async fn create(
+        &self,
+        _ctx: &TenantContext,
+        data: Value,
+        _params: (),
+    ) -> anyhow::Result<Value> {
+        Ok(data)
+    }
+}
+
+async fn create(body: &'static str, accept: &str) -> axum::response::Response {
+    let mut builder = DogApp::<Value, ()>::builder();
+    ledger_schema::register(&mut builder).unwrap();
+    let ax = axum(builder.build()).use_service("/ledger", Arc::new(Ledger));
+
+    let req = Request::post("/ledger")
+        .header("content-type", "application/json")
+        .header("accept", accept)
+        .body(Body::from(body))
+        .unwrap();
+    ax.router.oneshot(req).await.unwrap()
+}
+
+async fn body_bytes(res: axum::response::Response) -> Vec<u8> {
+    res.into_body().collect().await.unwrap().to_bytes().to_vec()
+}
+
+#[tokio::test]
+async fn wide_integers_and_long_decimals_round_trip_unchanged() {
+    let res = create(
+        r#"{"id":123456789012345678901234567890,"amount":0.1000000000000000000001}"#,
+        "application/json",
+    )
+    .await;
+    assert_eq!(res.status(), 200);
+
+    let body: Value = serde_json::from_slice(&body_bytes(res).await).unwrap();
+    assert_eq!(body["id"].to_string(), "123456789012345678901234567890");
+    assert_eq!(body["amount"].to_string(), "0.1000000000000000000001");
+
+    // Still an integer field: a fraction is rejected.
+    let res = create(r#"{"id":1.5,"amount":1}"#, "application/json").await;
+    assert_eq!(res.status(), 422);
+}
+
+#[tokio::test]
+async fn msgpack_gets_plain_numbers() {
+    let res = create(
+        r#"{"id":18446744073709551615,"amount":2.5}"#,
+        "application/msgpack",
+    )
+    .await;
+    assert_eq!(res.status(), 200);
+
+    let body: Value = rmp_serde::from_slice(&body_bytes(res).await).unwrap();
+    assert_eq!(body["id"].as_u64(), Some(u64::MAX));
+    assert_eq!(body["amount"].as_f64(), Some(2.5));
+}
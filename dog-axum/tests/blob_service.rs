@@ -0,0 +1,313 @@
+#![cfg(feature = "blob")]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::body::{Body, Bytes};
+use axum::http::Request;
+use axum::Router;
+use base64::Engine;
+use dog_axum::axum;
+use dog_axum::middlewares::{MultipartConfig, MultipartToJson};
+use dog_blob::adapter::BlobState;
+use dog_blob::store::ResolvedRange;
+use dog_blob::{
+    BlobAdapter, BlobConfig, BlobError, BlobInfo, BlobResult, BlobService, BlobStore, ByteRange,
+    ByteStream, GetResult, ObjectHead, PutResult, StoreCapabilities,
+};
+use dog_core::DogApp;
+use futures::StreamExt;
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+/// Keeps blobs in memory and serves byte ranges.
+#[derive(Default)]
+struct MemoryStore(Mutex<HashMap<String, (Bytes, Option<String>)>>);
+
+#[async_trait::async_trait]
+impl BlobStore for MemoryStore {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    async fn put(
+        &self,
+        key: &str,
+        content_type: Option<&str>,
+        mut stream: ByteStream,
+    ) -> BlobResult<PutResult> {
+        let mut data = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            data.extend_from_slice(&chunk?);
+        }
+        let size_bytes = data.len() as u64;
+        self.0.lock().unwrap().insert(
+            key.to_string(),
+            (Bytes::from(data), content_type.map(str::to_string)),
+        );
+        Ok(PutResult {
+            etag: None,
+            size_bytes,
+            checksum: None,
+        })
+    }
+
+    async fn get(&self, key: &str, range: Option<ByteRange>) -> BlobResult<GetResult> {
+        let (data, content_type) = self
+            .0
+            .lock()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .ok_or_else(|| BlobError::not_found(key))?;
+        let total_size = data.len() as u64;
+        let resolved_range = range.map(|r| ResolvedRange {
+            start: r.start,
+            end: r.end.unwrap_or(total_size - 1),
+            total_size,
+        });
+        let body = match &resolved_range {
+            Some(r) => data.slice(r.start as usize..=r.end as usize),
+            None => data,
+        };
+        Ok(GetResult {
+            stream: Box::pin(futures::stream::once(async move { Ok(body) })),
+            size_bytes: total_size,
+            content_type,
+            etag: Some("\"v1\"".to_string()),
+            resolved_range,
+        })
+    }
+
+    async fn head(&self, key: &str) -> BlobResult<ObjectHead> {
+        let store = self.0.lock().unwrap();
+        let (data, content_type) = store.get(key).ok_or_else(|| BlobError::not_found(key))?;
+        Ok(ObjectHead {
+            size_bytes: data.len() as u64,
+            content_type: content_type.clone(),
+            etag: Some("\"v1\"".to_string()),
+            last_modified: None,
+        })
+    }
+
+    async fn delete(&self, key: &str) -> BlobResult<()> {
+        self.0.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    async fn list(&self, prefix: Option<&str>, _limit: Option<usize>) -> BlobResult<Vec<BlobInfo>> {
+        let store = self.0.lock().unwrap();
+        Ok(store
+            .iter()
+            .filter(|(key, _)| prefix.is_none_or(|p| key.starts_with(p)))
+            .map(|(key, (data, content_type))| BlobInfo {
+                key: key.clone(),
+                size_bytes: data.len() as u64,
+                content_type: content_type.clone(),
+                filename: None,
+                etag: None,
+                last_modified: None,
+                metadata: Default::default(),
+            })
+            .collect())
+    }
+
+    fn capabilities(&self) -> StoreCapabilities {
+        StoreCapabilities::basic().with_range()
+    }
+}
+
+fn router() -> Router {
+    let blobs = Arc::new(BlobService::new(MemoryStore::default()));
+    axum(DogApp::<Value, ()>::builder().build())
+        .use_blob_service("/media", blobs)
+        .router
+}
+
+async fn send(router: &Router, request: Request<Body>) -> (u16, HashMap<String, String>, Bytes) {
+    let res = router.clone().oneshot(request).await.unwrap();
+    let status = res.status().as_u16();
+    let headers = res
+        .headers()
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_str().unwrap().to_string()))
+        .collect();
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    (status, headers, body)
+}
+
+fn head(uri: &str) -> Request<Body> {
+    Request::builder()
+        .method("HEAD")
+        .uri(uri)
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn get(uri: &str, range: Option<&str>) -> Request<Body> {
+    let mut req = Request::builder().uri(uri);
+    if let Some(range) = range {
+        req = req.header("range", range);
+    }
+    req.body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn blob_service_uploads_streams_ranges_and_removes() {
+    let router = router();
+    let upload = json!({
+        "file": base64::engine::general_purpose::STANDARD.encode(b"0123456789"),
+        "filename": "digits.txt",
+        "content_type": "text/plain",
+    });
+
+    let (status, _, body) = send(
+        &router,
+        Request::builder()
+            .method("POST")
+            .uri("/media")
+            .header("content-type", "application/json")
+            .body(Body::from(upload.to_string()))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, 200);
+    let receipt: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(receipt["size_bytes"], 10);
+    assert_eq!(receipt["filename"], "digits.txt");
+    let id = receipt["id"].as_str().unwrap().to_string();
+
+    let (status, _, body) = send(&router, get(&format!("/media/{id}"), None)).await;
+    assert_eq!(status, 200);
+    assert_eq!(serde_json::from_slice::<Value>(&body).unwrap()["id"], id);
+
+    let content = format!("/media/{id}/content");
+    let (status, headers, body) = send(&router, get(&content, None)).await;
+    assert_eq!(status, 200);
+    assert_eq!(&body[..], b"0123456789");
+    assert_eq!(headers["content-type"], "text/plain");
+    assert_eq!(headers["accept-ranges"], "bytes");
+
+    let (status, headers, body) = send(&router, head(&content)).await;
+    assert_eq!(status, 200);
+    assert!(body.is_empty());
+    assert_eq!(headers["content-length"], "10");
+    assert_eq!(headers["content-type"], "text/plain");
+    assert_eq!(headers["etag"], "\"v1\"");
+    assert_eq!(headers["accept-ranges"], "bytes");
+
+    let (status, _, _) = send(&router, head("/media/missing/content")).await;
+    assert_eq!(status, 404);
+
+    let (status, headers, body) = send(&router, get(&content, Some("bytes=2-5"))).await;
+    assert_eq!(status, 206);
+    assert_eq!(&body[..], b"2345");
+    assert_eq!(headers["content-range"], "bytes 2-5/10");
+    assert_eq!(headers["content-length"], "4");
+
+    let (status, headers, body) = send(&router, get(&content, Some("bytes=-3"))).await;
+    assert_eq!(status, 206);
+    assert_eq!(&body[..], b"789");
+    assert_eq!(headers["content-range"], "bytes 7-9/10");
+
+    let (status, headers, _) = send(&router, get(&content, Some("bytes=10-"))).await;
+    assert_eq!(status, 416);
+    assert_eq!(headers["content-range"], "bytes */10");
+
+    let (status, _, _) = send(
+        &router,
+        Request::builder()
+            .method("DELETE")
+            .uri(format!("/media/{id}"))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, 200);
+
+    let (status, _, _) = send(&router, get(&content, None)).await;
+    assert_eq!(status, 404);
+}
+
+#[tokio::test]
+async fn blobs_are_scoped_to_the_tenant() {
+    let router = router();
+    let upload = json!({
+        "file": base64::engine::general_purpose::STANDARD.encode(b"secret"),
+    });
+    let (_, _, body) = send(
+        &router,
+        Request::builder()
+            .method("POST")
+            .uri("/media")
+            .header("content-type", "application/json")
+            .header("x-tenant-id", "acme")
+            .body(Body::from(upload.to_string()))
+            .unwrap(),
+    )
+    .await;
+    let id = serde_json::from_slice::<Value>(&body).unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let (status, _, _) = send(&router, get(&format!("/media/{id}/content"), None)).await;
+    assert_eq!(status, 404);
+}
+
+fn streaming_router() -> Router {
+    let state = Arc::new(BlobState::new(
+        MemoryStore::default(),
+        BlobConfig::default(),
+    ));
+    let config = MultipartConfig::new().stream_to_blobs(Arc::new(BlobAdapter::new(state.clone())));
+    axum(DogApp::<Value, ()>::builder().build())
+        .use_middleware(MultipartToJson::with_config(config))
+        .use_blob_service("/media", Arc::new(BlobService::from_state(state)))
+        .router
+}
+
+fn create_json(body: Value) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/media")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn only_streamed_blob_ids_are_accepted_and_only_once() {
+    let router = streaming_router();
+    let multipart = "--b\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a.txt\"\r\n\
+                     Content-Type: text/plain\r\n\r\nhello\r\n--b--\r\n";
+    let (status, _, body) = send(
+        &router,
+        Request::builder()
+            .method("POST")
+            .uri("/media")
+            .header("content-type", "multipart/form-data; boundary=b")
+            .body(Body::from(multipart))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, 200);
+    let receipt: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(receipt["size_bytes"], 5);
+    let id = receipt["id"].as_str().unwrap().to_string();
+
+    let (_, _, body) = send(&router, get("/media", None)).await;
+    let listed: Value = serde_json::from_slice(&body).unwrap();
+    assert!(listed.as_array().unwrap().iter().any(|b| b["id"] == id));
+
+    // Naming the streamed blob again, or one the client made up, is refused.
+    for blob_id in [id.as_str(), "someone-elses-blob"] {
+        let (status, _, _) = send(
+            &router,
+            create_json(json!({ "file": { "blob_id": blob_id } })),
+        )
+        .await;
+        assert_eq!(status, 400, "blob_id {blob_id} was accepted");
+    }
+}
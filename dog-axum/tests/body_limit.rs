@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::Request;
+use axum::Router;
+use dog_axum::axum;
+use dog_core::tenant::TenantContext;
+use dog_core::{DogApp, DogService, ServiceCapabilities, ServiceMethodKind};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+struct Echo;
+
+#[async_trait::async_trait]
+impl DogService<Value, ()> for Echo {
+    fn capabilities(&self) -> ServiceCapabilities {
+        ServiceCapabilities::from_methods(vec![ServiceMethodKind::Create])
+    }
+
+    async fn create(
+        &self,
+        _ctx: &TenantContext,
+        data: Value,
+        _params: (),
+    ) -> anyhow::Result<Value> {
+        Ok(data)
+    }
+}
+
+fn router() -> Router {
+    let app: DogApp<Value, ()> = DogApp::default();
+    axum(app)
+        .use_service_with_body_limit("/messages", Arc::new(Echo), 1024)
+        .use_service_with_body_limit("/music", Arc::new(Echo), 64 * 1024)
+        .router
+}
+
+/// A JSON body of roughly `size` bytes.
+fn body_of(size: usize) -> String {
+    json!({ "text": "x".repeat(size) }).to_string()
+}
+
+async fn post(router: &Router, uri: &str, body: String) -> (u16, Value) {
+    let res = router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = res.status().as_u16();
+    let bytes = res.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn each_mount_enforces_its_own_limit() {
+    let router = router();
+
+    let (status, _) = post(&router, "/messages", body_of(512)).await;
+    assert_eq!(status, 200);
+
+    let (status, body) = post(&router, "/messages", body_of(8 * 1024)).await;
+    assert_eq!(status, 413);
+    assert_eq!(body["name"], "PayloadTooLarge");
+    assert_eq!(body["code"], 413);
+    assert_eq!(body["className"], "payload-too-large");
+
+    // The same body fits under the larger limit of the other mount.
+    let (status, body) = post(&router, "/music", body_of(8 * 1024)).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["text"].as_str().unwrap().len(), 8 * 1024);
+
+    let (status, _) = post(&router, "/music", body_of(128 * 1024)).await;
+    assert_eq!(status, 413);
+}
+
+#[tokio::test]
+async fn default_mounts_keep_the_default_limit() {
+    let app: DogApp<Value, ()> = DogApp::default();
+    let router = axum(app)
+        .use_service_with_body_limit("/messages", Arc::new(Echo), 1024)
+        .use_service("/posts", Arc::new(Echo))
+        .router;
+
+    let (status, _) = post(&router, "/posts", body_of(8 * 1024)).await;
+    assert_eq!(status, 200);
+}
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{HeaderMap, HeaderValue, Request};
+use dog_axum::axum;
+use dog_axum::format::ResponseFormat;
+use dog_core::errors::DogError;
+use dog_core::tenant::TenantContext;
+use dog_core::{DogApp, DogService, ServiceCapabilities, ServiceMethodKind};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+struct Posts;
+
+#[async_trait::async_trait]
+impl DogService<Value, ()> for Posts {
+    fn capabilities(&self) -> ServiceCapabilities {
+        ServiceCapabilities::from_methods(vec![ServiceMethodKind::Get])
+    }
+
+    async fn get(&self, _ctx: &TenantContext, id: &str, _params: ()) -> anyhow::Result<Value> {
+        if id == "missing" {
+            return Err(DogError::not_found("No post missing").into_anyhow());
+        }
+        Ok(json!({ "id": id, "title": "Hello", "tags": ["a", "b"] }))
+    }
+}
+
+async fn get(uri: &str, accept: Option<&str>) -> axum::response::Response {
+    let app: DogApp<Value, ()> = DogApp::default();
+    let ax = axum(app).use_service("/posts", Arc::new(Posts));
+
+    let mut req = Request::builder().uri(uri);
+    if let Some(accept) = accept {
+        req = req.header("accept", accept);
+    }
+    ax.router
+        .oneshot(req.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+fn content_type(res: &axum::response::Response) -> &str {
+    res.headers()["content-type"].to_str().unwrap()
+}
+
+async fn body_bytes(res: axum::response::Response) -> Vec<u8> {
+    res.into_body().collect().await.unwrap().to_bytes().to_vec()
+}
+
+#[tokio::test]
+async fn json_is_the_default() {
+    for accept in [None, Some("application/json"), Some("*/*")] {
+        let res = get("/posts/1", accept).await;
+        assert_eq!(res.status(), 200);
+        assert!(content_type(&res).starts_with("application/json"));
+        let body: Value = serde_json::from_slice(&body_bytes(res).await).unwrap();
+        assert_eq!(body["title"], "Hello");
+    }
+}
+
+#[tokio::test]
+async fn msgpack_is_returned_when_accepted() {
+    let res = get("/posts/1", Some("application/msgpack")).await;
+    assert_eq!(res.status(), 200);
+    assert_eq!(content_type(&res), "application/msgpack");
+    let body: Value = rmp_serde::from_slice(&body_bytes(res).await).unwrap();
+    assert_eq!(
+        body,
+        json!({ "id": "1", "title": "Hello", "tags": ["a", "b"] })
+    );
+}
+
+#[tokio::test]
+async fn errors_are_negotiated_too() {
+    let res = get("/posts/missing", Some("application/msgpack")).await;
+    assert_eq!(res.status(), 404);
+    assert_eq!(content_type(&res), "application/msgpack");
+    let body: Value = rmp_serde::from_slice(&body_bytes(res).await).unwrap();
+    assert_eq!(body["name"], "NotFound");
+    assert_eq!(body["code"], 404);
+}
+
+#[tokio::test]
+async fn unsupported_accept_is_406() {
+    let res = get("/posts/1", Some("text/html")).await;
+    assert_eq!(res.status(), 406);
+    let body: Value = serde_json::from_slice(&body_bytes(res).await).unwrap();
+    assert_eq!(body["name"], "NotAcceptable");
+    assert_eq!(body["code"], 406);
+}
+
+#[test]
+fn accept_is_ordered_by_q_value() {
+    let accept = |value: &str| {
+        let mut headers = HeaderMap::new();
+        headers.insert("accept", HeaderValue::from_str(value).unwrap());
+        ResponseFormat::from_accept(&headers)
+    };
+
+    assert_eq!(
+        accept("application/json;q=0.5, application/msgpack"),
+        Some(ResponseFormat::MsgPack)
+    );
+    assert_eq!(
+        accept("text/html, application/x-msgpack;q=0.9, */*;q=0.1"),
+        Some(ResponseFormat::MsgPack)
+    );
+    assert_eq!(
+        accept("application/msgpack;q=0, application/json"),
+        Some(ResponseFormat::Json)
+    );
+    assert_eq!(accept("application/msgpack;q=0"), None);
+}
@@ -0,0 +1,100 @@
+use axum::body::Body;
+use axum::http::{Request, Response};
+use axum::routing::get;
+use axum::Router;
+use dog_axum::middlewares::cors_from_config;
+use dog_core::DogApp;
+use serde_json::json;
+use tower::ServiceExt;
+
+fn app_with(config: &[(&str, serde_json::Value)]) -> DogApp<(), ()> {
+    let mut builder = DogApp::<(), ()>::builder();
+    for (key, value) in config {
+        builder.set_value(*key, value.clone());
+    }
+    builder.build()
+}
+
+async fn preflight(app: &DogApp<(), ()>, origin: &str) -> Response<Body> {
+    let router = Router::new()
+        .route("/posts", get(|| async { "[]" }))
+        .layer(cors_from_config(app).unwrap());
+    router
+        .oneshot(
+            Request::builder()
+                .method("OPTIONS")
+                .uri("/posts")
+                .header("origin", origin)
+                .header("access-control-request-method", "POST")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+fn header<'a>(res: &'a Response<Body>, name: &str) -> Option<&'a str> {
+    res.headers().get(name).and_then(|v| v.to_str().ok())
+}
+
+#[tokio::test]
+async fn configured_origins_are_allowed_and_others_are_not() {
+    let app = app_with(&[
+        (
+            "http.cors.origins",
+            json!(["https://app.example.com", "https://admin.example.com"]),
+        ),
+        ("http.cors.methods", json!("get, post")),
+        ("http.cors.allow_credentials", json!(true)),
+    ]);
+
+    let res = preflight(&app, "https://admin.example.com").await;
+    assert_eq!(
+        header(&res, "access-control-allow-origin"),
+        Some("https://admin.example.com")
+    );
+    assert_eq!(
+        header(&res, "access-control-allow-methods"),
+        Some("GET,POST")
+    );
+    assert_eq!(
+        header(&res, "access-control-allow-credentials"),
+        Some("true")
+    );
+    let allowed_headers = header(&res, "access-control-allow-headers").unwrap();
+    assert!(allowed_headers.contains("x-tenant-id"));
+
+    let res = preflight(&app, "https://evil.example.com").await;
+    assert_eq!(header(&res, "access-control-allow-origin"), None);
+}
+
+#[tokio::test]
+async fn unset_config_denies_cross_origin_requests() {
+    let res = preflight(&app_with(&[]), "https://app.example.com").await;
+    assert_eq!(header(&res, "access-control-allow-origin"), None);
+}
+
+#[tokio::test]
+async fn comma_separated_strings_are_accepted() {
+    let mut builder = DogApp::<(), ()>::builder();
+    builder.set(
+        "http.cors.origins",
+        "https://a.example.com, https://b.example.com",
+    );
+    let app = builder.build();
+
+    let res = preflight(&app, "https://b.example.com").await;
+    assert_eq!(
+        header(&res, "access-control-allow-origin"),
+        Some("https://b.example.com")
+    );
+}
+
+#[test]
+fn wildcard_with_credentials_is_rejected() {
+    let app = app_with(&[
+        ("http.cors.origins", json!("*")),
+        ("http.cors.allow_credentials", json!(true)),
+    ]);
+    assert!(cors_from_config(&app).is_err());
+}
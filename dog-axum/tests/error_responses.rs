@@ -0,0 +1,77 @@
+use axum::response::IntoResponse;
+use dog_axum::DogAxumError;
+use dog_core::errors::{DogError, ErrorKind};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+
+async fn render(err: DogAxumError) -> (u16, Value) {
+    let res = err.into_response();
+    let status = res.status().as_u16();
+    let bytes = res.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn every_error_kind_maps_to_its_status() {
+    let cases = [
+        (ErrorKind::BadRequest, 400),
+        (ErrorKind::NotAuthenticated, 401),
+        (ErrorKind::Forbidden, 403),
+        (ErrorKind::NotFound, 404),
+        (ErrorKind::MethodNotAllowed, 405),
+        (ErrorKind::NotAcceptable, 406),
+        (ErrorKind::Timeout, 408),
+        (ErrorKind::Conflict, 409),
+        (ErrorKind::Gone, 410),
+        (ErrorKind::LengthRequired, 411),
+        (ErrorKind::PayloadTooLarge, 413),
+        (ErrorKind::Unprocessable, 422),
+        (ErrorKind::TooManyRequests, 429),
+        (ErrorKind::GeneralError, 500),
+        (ErrorKind::NotImplemented, 501),
+        (ErrorKind::BadGateway, 502),
+        (ErrorKind::Unavailable, 503),
+    ];
+
+    for (kind, expected) in cases {
+        let (status, body) = render(DogError::new(kind, "nope").into()).await;
+        assert_eq!(status, expected, "{kind:?}");
+        assert_eq!(body["code"], expected, "{kind:?}");
+        assert_eq!(body["name"], kind.name());
+        assert_eq!(body["message"], "nope");
+    }
+}
+
+#[tokio::test]
+async fn validation_errors_keep_field_details_through_context() {
+    let err = DogError::unprocessable("Invalid post")
+        .with_errors(json!({ "title": ["required"] }))
+        .into_anyhow()
+        .context("validating create");
+
+    let (status, body) = render(err.into()).await;
+
+    assert_eq!(status, 422);
+    assert_eq!(body["message"], "Invalid post");
+    assert_eq!(body["errors"], json!({ "title": ["required"] }));
+}
+
+#[tokio::test]
+async fn source_is_not_sent_to_the_client() {
+    let err = DogError::bad_gateway("upstream failed")
+        .with_source(anyhow::anyhow!("secret connection string"));
+
+    let (status, body) = render(err.into()).await;
+
+    assert_eq!(status, 502);
+    assert!(!body.to_string().contains("secret"));
+}
+
+#[tokio::test]
+async fn plain_anyhow_errors_become_general_errors() {
+    let (status, body) = render(anyhow::anyhow!("boom").into()).await;
+
+    assert_eq!(status, 500);
+    assert_eq!(body["name"], "GeneralError");
+    assert_eq!(body["message"], "boom");
+}
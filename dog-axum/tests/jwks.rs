@@ -0,0 +1,69 @@
+#![cfg(feature = "auth")]
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::Request;
+use dog_auth::{AuthOptions, AuthenticationService, JwtAlgorithm, JwtKey, JwtOptions};
+use dog_axum::axum;
+use dog_core::{DogApp, DogAppBuilder};
+use http_body_util::BodyExt;
+use serde_json::Value;
+use tower::ServiceExt;
+
+const RSA_PRIVATE: &str = include_str!("../../dog-auth/tests/keys/rsa_private.pem");
+const RSA_NEXT_PRIVATE: &str = include_str!("../../dog-auth/tests/keys/rsa_next_private.pem");
+
+#[tokio::test]
+async fn jwks_is_served_at_the_well_known_path() {
+    let mut builder = DogAppBuilder::<Value, ()>::new();
+    let options = AuthOptions {
+        jwt: JwtOptions {
+            algorithm: JwtAlgorithm::RS256,
+            key_id: Some("k1".to_string()),
+            private_key: Some(RSA_PRIVATE.to_string()),
+            ..JwtOptions::default()
+        },
+        ..AuthOptions::default()
+    };
+    let auth = AuthenticationService::builder(&mut builder, Some(options)).unwrap();
+    let auth = Arc::new(AuthenticationService::new(Arc::new(auth.build())));
+
+    let app: DogApp<Value, ()> = DogApp::default();
+    let router = axum(app).use_jwks(Arc::clone(&auth)).router;
+
+    let kids = |router: axum::Router| async move {
+        let res = router
+            .oneshot(
+                Request::builder()
+                    .uri("/.well-known/jwks.json")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let jwks: Value = serde_json::from_slice(&bytes).unwrap();
+        jwks["keys"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|k| {
+                assert_eq!(k["kty"], "RSA");
+                k["kid"].as_str().unwrap().to_string()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(kids(router.clone()).await, ["k1"]);
+
+    auth.rotate_signing_key(JwtKey {
+        key_id: "k2".to_string(),
+        algorithm: JwtAlgorithm::RS256,
+        private_key: Some(RSA_NEXT_PRIVATE.to_string()),
+        ..JwtKey::default()
+    })
+    .unwrap();
+    assert_eq!(kids(router).await, ["k2", "k1"]);
+}
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::Request;
+use dog_axum::axum;
+use dog_axum::params::{ListQuery, Order, RestParams};
+use dog_core::tenant::TenantContext;
+use dog_core::{DogApp, DogService, ServiceCapabilities, ServiceMethodKind};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+fn query(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn parses_limit_skip_sort_and_filters() {
+    let q = ListQuery::parse(&query(&[
+        ("limit", "20"),
+        ("skip", "40"),
+        ("sort", "created_at:-1, title, score:asc"),
+        ("filter[status]", "active"),
+        ("filter[owner]", "ada"),
+        ("include", "author"),
+    ]))
+    .unwrap();
+
+    assert_eq!(q.limit, 20);
+    assert_eq!(q.skip, 40);
+    assert_eq!(
+        q.sort,
+        vec![
+            ("created_at".to_string(), Order::Desc),
+            ("title".to_string(), Order::Asc),
+            ("score".to_string(), Order::Asc),
+        ]
+    );
+    assert_eq!(q.filter.len(), 2);
+    assert_eq!(q.filter["status"], "active");
+    assert_eq!(q.filter["owner"], "ada");
+}
+
+#[test]
+fn defaults_and_bounds() {
+    assert_eq!(
+        ListQuery::parse(&HashMap::new()).unwrap(),
+        ListQuery::default()
+    );
+    assert_eq!(ListQuery::default().limit, ListQuery::DEFAULT_LIMIT);
+
+    let q = ListQuery::parse(&query(&[("limit", "100000")])).unwrap();
+    assert_eq!(q.limit, ListQuery::MAX_LIMIT);
+
+    let q = ListQuery::parse_with_bounds(&query(&[("limit", "500")]), 25, 200).unwrap();
+    assert_eq!(q.limit, 200);
+    let q = ListQuery::parse_with_bounds(&HashMap::new(), 25, 200).unwrap();
+    assert_eq!(q.limit, 25);
+}
+
+#[test]
+fn malformed_params_are_bad_requests() {
+    let cases: &[(&str, &str)] = &[
+        ("limit", "ten"),
+        ("limit", "-1"),
+        ("skip", "1.5"),
+        ("sort", "name:sideways"),
+        ("sort", ":-1"),
+        ("filter[", "x"),
+        ("filter[]", "x"),
+        ("filter", "x"),
+    ];
+
+    for (key, value) in cases {
+        let err = ListQuery::parse(&query(&[(key, value)])).unwrap_err();
+        assert_eq!(err.code(), 400, "{key}={value}");
+    }
+}
+
+/// Echoes the parsed list query back, the way a backend adapter would use it.
+struct Listing;
+
+#[async_trait::async_trait]
+impl DogService<Value, RestParams> for Listing {
+    fn capabilities(&self) -> ServiceCapabilities {
+        ServiceCapabilities::from_methods(vec![ServiceMethodKind::Find])
+    }
+
+    async fn find(&self, _ctx: &TenantContext, params: RestParams) -> anyhow::Result<Vec<Value>> {
+        let q = params.list_query()?;
+        Ok(vec![json!({
+            "limit": q.limit,
+            "skip": q.skip,
+            "sort": q.sort.iter().map(|(f, o)| format!("{f}:{o:?}")).collect::<Vec<_>>(),
+            "filter": q.filter,
+        })])
+    }
+}
+
+async fn get(uri: &str) -> (u16, Value) {
+    let app: DogApp<Value, RestParams> = DogApp::default();
+    let ax = axum(app).use_service("/items", Arc::new(Listing));
+    let res = ax
+        .router
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = res.status().as_u16();
+    let bytes = res.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn rest_find_sees_structured_list_query() {
+    let (status, body) = get("/items?limit=5&sort=name:-1&filter%5Bstatus%5D=active").await;
+
+    assert_eq!(status, 200);
+    assert_eq!(
+        body,
+        json!([{
+            "limit": 5,
+            "skip": 0,
+            "sort": ["name:Desc"],
+            "filter": { "status": "active" },
+        }])
+    );
+}
+
+#[tokio::test]
+async fn rest_find_rejects_malformed_list_query_with_400() {
+    let (status, body) = get("/items?limit=lots").await;
+
+    assert_eq!(status, 400);
+    assert_eq!(body["name"], "BadRequest");
+    assert!(body["message"].as_str().unwrap().contains("limit"));
+}
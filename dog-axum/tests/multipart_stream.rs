@@ -0,0 +1,270 @@
+#![cfg(feature = "blob")]
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use axum::body::{Body, Bytes};
+use axum::http::Request;
+use axum::routing::post;
+use axum::{Json, Router};
+use dog_axum::middlewares::{MultipartConfig, MultipartToJson};
+use dog_blob::adapter::BlobState;
+use dog_blob::{
+    BlobAdapter, BlobConfig, BlobError, BlobResult, BlobStore, ByteRange, ByteStream, GetResult,
+    ObjectHead, PutResult, StoreCapabilities,
+};
+use futures::StreamExt;
+use http_body_util::BodyExt;
+use serde_json::Value;
+use tower::ServiceExt;
+
+const BOUNDARY: &str = "dogrs-boundary";
+const CHUNK: usize = 64 * 1024;
+const CHUNKS: usize = 1024; // 64MB file
+/// Most bytes allowed between the client sending and the store receiving.
+const MAX_IN_FLIGHT: u64 = 1024 * 1024;
+
+/// Bytes the request body has handed out so far.
+#[derive(Default)]
+struct Produced(AtomicU64);
+
+/// What the store saw; shared with the test.
+#[derive(Default)]
+struct Received {
+    max_in_flight: AtomicU64,
+    keys: Mutex<Vec<String>>,
+}
+
+/// Drains uploads without keeping them, noting how far behind the client
+/// each chunk arrives.
+struct CountingStore {
+    produced: Arc<Produced>,
+    received: Arc<Received>,
+}
+
+#[async_trait::async_trait]
+impl BlobStore for CountingStore {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    async fn put(
+        &self,
+        key: &str,
+        _content_type: Option<&str>,
+        mut stream: ByteStream,
+    ) -> BlobResult<PutResult> {
+        let mut size = 0u64;
+        while let Some(chunk) = stream.next().await {
+            size += chunk.map_err(|e| BlobError::invalid(e.to_string()))?.len() as u64;
+            let in_flight = self.produced.0.load(Ordering::SeqCst) - size;
+            self.received
+                .max_in_flight
+                .fetch_max(in_flight, Ordering::SeqCst);
+        }
+        self.received.keys.lock().unwrap().push(key.to_string());
+        Ok(PutResult {
+            etag: None,
+            size_bytes: size,
+            checksum: None,
+        })
+    }
+
+    async fn get(&self, key: &str, _range: Option<ByteRange>) -> BlobResult<GetResult> {
+        Err(BlobError::not_found(key))
+    }
+
+    async fn head(&self, key: &str) -> BlobResult<ObjectHead> {
+        Err(BlobError::not_found(key))
+    }
+
+    async fn delete(&self, _key: &str) -> BlobResult<()> {
+        Ok(())
+    }
+
+    fn capabilities(&self) -> StoreCapabilities {
+        StoreCapabilities::basic()
+    }
+}
+
+/// A multipart body with one text field and one large file, generated lazily.
+fn streamed_upload(produced: Arc<Produced>) -> Body {
+    let head = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"title\"\r\n\r\nBig tune\r\n\
+         --{BOUNDARY}\r\nContent-Disposition: form-data; name=\"audio\"; filename=\"tune.wav\"\r\n\
+         Content-Type: audio/wav\r\n\r\n"
+    );
+    let tail = format!("\r\n--{BOUNDARY}--\r\n");
+
+    let file = futures::stream::iter(0..CHUNKS).map(move |_| {
+        produced.0.fetch_add(CHUNK as u64, Ordering::SeqCst);
+        Bytes::from(vec![b'x'; CHUNK])
+    });
+    let parts = futures::stream::once(async move { Bytes::from(head) })
+        .chain(file)
+        .chain(futures::stream::once(async move { Bytes::from(tail) }))
+        .map(Ok::<_, std::io::Error>);
+    Body::from_stream(parts)
+}
+
+fn counting_adapter(produced: &Arc<Produced>) -> (Arc<BlobAdapter>, Arc<Received>) {
+    let received = Arc::new(Received::default());
+    let store = CountingStore {
+        produced: Arc::clone(produced),
+        received: Arc::clone(&received),
+    };
+    let state = Arc::new(BlobState::new(store, BlobConfig::default()));
+    (Arc::new(BlobAdapter::new(state)), received)
+}
+
+#[tokio::test]
+async fn large_file_is_streamed_into_the_blob_store() {
+    let produced = Arc::new(Produced::default());
+    let (adapter, received) = counting_adapter(&produced);
+
+    let config = MultipartConfig::new()
+        .max_file_size(128 * 1024 * 1024)
+        .stream_to_blobs(adapter);
+    let router = Router::new()
+        .route(
+            "/upload",
+            post(|Json(body): Json<Value>| async { Json(body) }),
+        )
+        .layer(MultipartToJson::with_config(config));
+
+    let res = router
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/upload")
+                .header(
+                    "content-type",
+                    format!("multipart/form-data; boundary={BOUNDARY}"),
+                )
+                .header("x-tenant-id", "acme")
+                .body(streamed_upload(produced))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), 200);
+    let bytes = res.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+
+    assert_eq!(body["title"], "Big tune");
+    let audio = &body["audio"];
+    assert!(audio["blob_id"].as_str().is_some_and(|id| !id.is_empty()));
+    assert_eq!(audio["filename"], "tune.wav");
+    assert_eq!(audio["content_type"], "audio/wav");
+    assert_eq!(audio["size"], (CHUNK * CHUNKS) as u64);
+    assert!(audio.get("data").is_none() && audio.get("temp_path").is_none());
+
+    let max_in_flight = received.max_in_flight.load(Ordering::SeqCst);
+    assert!(
+        max_in_flight <= MAX_IN_FLIGHT,
+        "store lagged {max_in_flight} bytes behind the client"
+    );
+    let keys = received.keys.lock().unwrap();
+    assert_eq!(keys.len(), 1);
+    assert!(
+        keys[0].contains("acme"),
+        "key {} is not tenant-scoped",
+        keys[0]
+    );
+}
+
+#[tokio::test]
+async fn disallowed_content_type_is_rejected_before_storing() {
+    let produced = Arc::new(Produced::default());
+    let (adapter, received) = counting_adapter(&produced);
+    let config = MultipartConfig::new()
+        .allow_content_type("image/png")
+        .stream_to_blobs(adapter);
+    let router = Router::new()
+        .route(
+            "/upload",
+            post(|Json(body): Json<Value>| async { Json(body) }),
+        )
+        .layer(MultipartToJson::with_config(config));
+
+    let res = router
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/upload")
+                .header(
+                    "content-type",
+                    format!("multipart/form-data; boundary={BOUNDARY}"),
+                )
+                .body(streamed_upload(produced))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), 400);
+    assert!(received.keys.lock().unwrap().is_empty());
+}
+
+/// Returns what it was asked to create.
+struct Echo;
+
+#[async_trait::async_trait]
+impl dog_core::DogService<Value, ()> for Echo {
+    async fn create(
+        &self,
+        _ctx: &dog_core::tenant::TenantContext,
+        data: Value,
+        _params: (),
+    ) -> anyhow::Result<Value> {
+        Ok(data)
+    }
+}
+
+#[tokio::test]
+async fn streamed_files_go_to_the_tenant_the_app_resolves() {
+    let produced = Arc::new(Produced::default());
+    let (adapter, received) = counting_adapter(&produced);
+
+    let mut builder = dog_core::DogApp::<Value, ()>::builder();
+    builder.tenant_resolver(Arc::new(dog_core::tenant::SubdomainTenant::new(
+        "example.com",
+    )));
+    let config = MultipartConfig::new()
+        .max_file_size(128 * 1024 * 1024)
+        .stream_to_blobs(adapter);
+    let server = dog_axum::axum(builder.build()).use_service_with(
+        "/uploads",
+        Arc::new(Echo),
+        MultipartToJson::with_config(config),
+    );
+
+    let res = server
+        .router
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/uploads")
+                .header("host", "acme.example.com")
+                .header(
+                    "content-type",
+                    format!("multipart/form-data; boundary={BOUNDARY}"),
+                )
+                .header("x-tenant-id", "globex")
+                .body(streamed_upload(produced))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), 200);
+    let keys = received.keys.lock().unwrap();
+    assert_eq!(keys.len(), 1);
+    assert!(
+        keys[0].contains("acme"),
+        "key {} ignores the resolver",
+        keys[0]
+    );
+    assert!(!keys[0].contains("globex"));
+}
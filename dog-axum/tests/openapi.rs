@@ -0,0 +1,154 @@
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::Request;
+use dog_axum::axum;
+use dog_core::tenant::TenantContext;
+use dog_core::{DogApp, DogService, ServiceCapabilities, ServiceMethodKind};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+#[dog_schema::schema(service = "posts")]
+mod posts_schema {
+    #[create]
+    pub struct CreatePost {
+        #[dog(trim, min_len(1))]
+        pub title: String,
+        pub views: u32,
+        #[dog(default = false)]
+        pub published: bool,
+        pub tags: Option<Vec<String>>,
+    }
+
+    #[patch]
+    pub struct PatchPost {
+        pub title: Option<String>,
+    }
+}
+
+struct Crud;
+
+#[async_trait::async_trait]
+impl DogService<Value, ()> for Crud {
+    async fn find(&self, _ctx: &TenantContext, _params: ()) -> anyhow::Result<Vec<Value>> {
+        Ok(vec![])
+    }
+}
+
+struct ReadOnly;
+
+#[async_trait::async_trait]
+impl DogService<Value, ()> for ReadOnly {
+    fn capabilities(&self) -> ServiceCapabilities {
+        ServiceCapabilities::from_methods(vec![ServiceMethodKind::Find, ServiceMethodKind::Get])
+    }
+
+    async fn find(&self, _ctx: &TenantContext, _params: ()) -> anyhow::Result<Vec<Value>> {
+        Ok(vec![])
+    }
+}
+
+fn app() -> dog_axum::AxumApp<Value, ()> {
+    let mut builder = DogApp::<Value, ()>::builder();
+    builder.set("openapi.title", "Blog API");
+    posts_schema::register(&mut builder).unwrap();
+    axum(builder.build())
+        .use_service("/posts", Arc::new(Crud))
+        .use_service_as("/v1/audit-log", "audit", Arc::new(ReadOnly))
+}
+
+#[test]
+fn spec_has_an_entry_per_mounted_service() {
+    let spec = app().openapi_spec();
+
+    assert_eq!(spec["openapi"], "3.0.3");
+    assert_eq!(spec["info"]["title"], "Blog API");
+
+    let paths = spec["paths"].as_object().unwrap();
+    let mut keys: Vec<&str> = paths.keys().map(String::as_str).collect();
+    keys.sort();
+    assert_eq!(
+        keys,
+        [
+            "/posts",
+            "/posts/{id}",
+            "/v1/audit-log",
+            "/v1/audit-log/{id}"
+        ]
+    );
+
+    let posts = &paths["/posts"];
+    assert_eq!(posts["get"]["operationId"], "posts.find");
+    assert_eq!(posts["post"]["operationId"], "posts.create");
+    let item = &paths["/posts/{id}"];
+    for verb in ["get", "put", "patch", "delete"] {
+        assert!(item.get(verb).is_some(), "missing {verb} /posts/{{id}}");
+    }
+
+    // Only the methods the service allows are described.
+    assert!(paths["/v1/audit-log"].get("post").is_none());
+    assert_eq!(
+        paths["/v1/audit-log/{id}"]["get"]["operationId"],
+        "audit.get"
+    );
+    assert!(paths["/v1/audit-log/{id}"].get("delete").is_none());
+}
+
+#[test]
+fn schema_module_rules_become_request_schemas() {
+    let spec = app().openapi_spec();
+
+    assert_eq!(
+        spec["paths"]["/posts"]["post"]["requestBody"]["content"]["application/json"]["schema"],
+        json!({ "$ref": "#/components/schemas/Posts" })
+    );
+    assert_eq!(
+        spec["paths"]["/posts/{id}"]["patch"]["requestBody"]["content"]["application/json"]
+            ["schema"],
+        json!({ "$ref": "#/components/schemas/PostsPatch" })
+    );
+    // No #[update] struct, so PUT takes the create schema.
+    assert_eq!(
+        spec["paths"]["/posts/{id}"]["put"]["requestBody"]["content"]["application/json"]["schema"],
+        json!({ "$ref": "#/components/schemas/Posts" })
+    );
+
+    let create = &spec["components"]["schemas"]["Posts"];
+    assert_eq!(create["required"], json!(["title", "views"]));
+    assert_eq!(
+        create["properties"]["title"],
+        json!({ "type": "string", "minLength": 1 })
+    );
+    assert_eq!(create["properties"]["views"], json!({ "type": "integer" }));
+    assert_eq!(
+        create["properties"]["published"],
+        json!({ "type": "boolean", "default": false })
+    );
+    assert_eq!(create["properties"]["tags"], json!({ "type": "array" }));
+
+    let patch = &spec["components"]["schemas"]["PostsPatch"];
+    assert_eq!(patch["required"], json!([]));
+}
+
+#[tokio::test]
+async fn spec_is_served_as_json() {
+    let ax = app().use_openapi("/openapi.json");
+    let expected = ax.openapi_spec();
+
+    let res = ax
+        .router
+        .oneshot(
+            Request::builder()
+                .uri("/openapi.json")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), 200);
+    let bytes = res.into_body().collect().await.unwrap().to_bytes();
+    let served: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(served, expected);
+}
@@ -0,0 +1,98 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::Request;
+use axum::routing::get;
+use axum::Router;
+use dog_axum::middlewares::{MemoryRateLimitStore, RateLimit, RateLimitStore};
+use http_body_util::BodyExt;
+use serde_json::Value;
+use tower::ServiceExt;
+
+const LIMIT: u64 = 3;
+
+fn limited(layer: RateLimit) -> Router {
+    Router::new()
+        .route("/ping", get(|| async { "pong" }))
+        .layer(layer)
+}
+
+fn from_tenant(tenant: &str) -> Request<Body> {
+    Request::builder()
+        .uri("/ping")
+        .header("x-tenant-id", tenant)
+        .body(Body::empty())
+        .unwrap()
+}
+
+async fn status(router: &Router, req: Request<Body>) -> u16 {
+    router.clone().oneshot(req).await.unwrap().status().as_u16()
+}
+
+#[tokio::test]
+async fn request_over_the_limit_gets_429_with_retry_after() {
+    let router =
+        limited(RateLimit::new(LIMIT, Duration::from_secs(60)).key_by(RateLimit::tenant_key));
+
+    for _ in 0..LIMIT {
+        let res = router.clone().oneshot(from_tenant("acme")).await.unwrap();
+        assert_eq!(res.status(), 200);
+    }
+
+    let res = router.clone().oneshot(from_tenant("acme")).await.unwrap();
+    assert_eq!(res.status(), 429);
+    let retry_after: u64 = res.headers()["retry-after"]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!((1..=60).contains(&retry_after));
+    let bytes = res.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["name"], "TooManyRequests");
+    assert_eq!(body["code"], 429);
+
+    // Other keys have their own budget.
+    let res = router.oneshot(from_tenant("globex")).await.unwrap();
+    assert_eq!(res.status(), 200);
+}
+
+#[tokio::test]
+async fn ip_key_uses_connect_info() {
+    let router = limited(RateLimit::new(1, Duration::from_secs(60)));
+    let from = |ip: &str| {
+        let mut req = Request::builder().uri("/ping").body(Body::empty()).unwrap();
+        let addr: SocketAddr = format!("{ip}:4000").parse().unwrap();
+        req.extensions_mut().insert(ConnectInfo(addr));
+        req
+    };
+
+    assert_eq!(status(&router, from("10.0.0.1")).await, 200);
+    assert_eq!(status(&router, from("10.0.0.1")).await, 429);
+    assert_eq!(status(&router, from("10.0.0.2")).await, 200);
+}
+
+#[tokio::test]
+async fn requests_without_a_key_are_not_limited() {
+    let router = limited(RateLimit::new(1, Duration::from_secs(60)).key_by(|_| None));
+
+    for _ in 0..3 {
+        assert_eq!(status(&router, from_tenant("acme")).await, 200);
+    }
+}
+
+#[tokio::test]
+async fn memory_store_starts_a_new_window_once_it_expires() {
+    let store = MemoryRateLimitStore::new();
+    let window = Duration::from_millis(50);
+
+    assert_eq!(store.hit("k", window).await.unwrap().count, 1);
+    assert_eq!(store.hit("k", window).await.unwrap().count, 2);
+
+    tokio::time::sleep(window).await;
+    let hit = store.hit("k", window).await.unwrap();
+    assert_eq!(hit.count, 1);
+    assert!(hit.resets_in <= window);
+}
@@ -0,0 +1,176 @@
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::{HeaderMap, Request};
+use dog_axum::router_for_app;
+use dog_core::tenant::TenantContext;
+use dog_core::{
+    DogApp, DogBeforeHook, DogService, HookContext, ServiceCapabilities, ServiceMethodKind,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+struct Items;
+
+#[async_trait::async_trait]
+impl DogService<Value, ()> for Items {
+    async fn find(&self, _ctx: &TenantContext, _params: ()) -> anyhow::Result<Vec<Value>> {
+        Ok(vec![])
+    }
+
+    async fn create(
+        &self,
+        _ctx: &TenantContext,
+        data: Value,
+        _params: (),
+    ) -> anyhow::Result<Value> {
+        Ok(data)
+    }
+
+    fn capabilities(&self) -> ServiceCapabilities {
+        ServiceCapabilities::from_methods(vec![
+            ServiceMethodKind::Find,
+            ServiceMethodKind::Create,
+            ServiceMethodKind::custom("ping"),
+        ])
+    }
+
+    async fn custom(
+        &self,
+        _ctx: &TenantContext,
+        _method: &str,
+        _data: Option<Value>,
+        _params: (),
+    ) -> anyhow::Result<Value> {
+        Ok(json!("pong"))
+    }
+}
+
+/// Records what each call's hooks saw of the request.
+struct Capture(Arc<Mutex<Vec<Value>>>);
+
+#[async_trait::async_trait]
+impl DogBeforeHook<Value, ()> for Capture {
+    async fn run(&self, ctx: &mut HookContext<Value, ()>) -> anyhow::Result<()> {
+        let seen = match &ctx.request {
+            Some(req) => json!({
+                "provider": req.provider,
+                "method": req.method,
+                "path": req.path,
+                "correlation": ctx.header("X-Correlation-Id"),
+                "forwarded": ctx.header("x-forwarded-for"),
+                "remote": req.remote_addr.map(|a| a.to_string()),
+            }),
+            None => Value::Null,
+        };
+        self.0.lock().unwrap().push(seen);
+        Ok(())
+    }
+}
+
+fn app(seen: &Arc<Mutex<Vec<Value>>>) -> DogApp<Value, ()> {
+    let mut builder = DogApp::<Value, ()>::builder();
+    builder.register_service("items", Arc::new(Items));
+    builder.service_hooks("items", |h| {
+        h.before_all(Arc::new(Capture(seen.clone())));
+    });
+    builder.build()
+}
+
+#[tokio::test]
+async fn hooks_read_headers_set_by_the_adapter() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let router = router_for_app(&app(&seen));
+
+    router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/items?limit=5")
+                .header("x-correlation-id", "req-42")
+                .header("x-forwarded-for", "203.0.113.1")
+                .header("x-forwarded-for", "10.0.0.1")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let mut post = Request::builder()
+        .method("POST")
+        .uri("/items")
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"name":"a"}"#))
+        .unwrap();
+    let addr: SocketAddr = "10.0.0.7:4000".parse().unwrap();
+    post.extensions_mut().insert(ConnectInfo(addr));
+    router.oneshot(post).await.unwrap();
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(
+        seen[0],
+        json!({
+            "provider": "rest",
+            "method": "GET",
+            "path": "/items",
+            "correlation": "req-42",
+            "forwarded": "203.0.113.1, 10.0.0.1",
+            "remote": null,
+        })
+    );
+    assert_eq!(seen[1]["method"], "POST");
+    assert_eq!(seen[1]["correlation"], Value::Null);
+    assert_eq!(seen[1]["remote"], "10.0.0.7:4000");
+}
+
+#[tokio::test]
+async fn calls_from_code_have_no_request() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let app = app(&seen);
+
+    app.service("items")
+        .unwrap()
+        .find(TenantContext::new("t"), ())
+        .await
+        .unwrap();
+
+    assert_eq!(*seen.lock().unwrap(), vec![Value::Null]);
+}
+
+#[tokio::test]
+async fn custom_calls_see_the_callers_address() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let app = app(&seen);
+    let addr: SocketAddr = "10.0.0.7:4000".parse().unwrap();
+
+    let res = dog_axum::rest::call_custom::<Value, ()>(
+        &app,
+        "items",
+        "ping",
+        &HeaderMap::new(),
+        Default::default(),
+        "POST",
+        &"/items/ping".parse().unwrap(),
+        Some(axum::Extension(ConnectInfo(addr))),
+        None,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(res, json!("pong"));
+    assert_eq!(seen.lock().unwrap()[0]["remote"], "10.0.0.7:4000");
+}
+
+#[test]
+fn repeated_headers_keep_every_value() {
+    let meta = dog_core::RequestMeta::new("rest", "GET", "/")
+        .with_header("Accept", "text/html")
+        .with_header("accept", "application/json")
+        .with_header("cookie", "a=1")
+        .with_header("cookie", "b=2");
+
+    assert_eq!(meta.header("accept"), Some("text/html, application/json"));
+    assert_eq!(meta.header("cookie"), Some("a=1; b=2"));
+}
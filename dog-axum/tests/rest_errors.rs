@@ -130,6 +130,29 @@ async fn dogerror_unprocessable_preserves_422_and_shape() {
     assert_eq!(body["errors"], json!({"title": ["required"]}));
 }
 
+#[tokio::test]
+async fn unadvertised_method_returns_405() {
+    let app: DogApp<Value, ()> = DogApp::default();
+    let ax = axum(app).use_service("/posts", Arc::new(BoomOnCreate));
+
+    let res = ax
+        .router
+        .oneshot(
+            Request::builder()
+                .method("DELETE")
+                .uri("/posts/1")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status().as_u16(), 405);
+    let body = json_body(res).await;
+    assert_eq!(body["name"], "MethodNotAllowed");
+    assert_eq!(body["className"], "method-not-allowed");
+}
+
 #[tokio::test]
 async fn non_dogerror_maps_to_generalerror_shape() {
     let app: DogApp<Value, ()> = DogApp::default();
@@ -155,3 +178,105 @@ async fn non_dogerror_maps_to_generalerror_shape() {
     assert_eq!(body["className"], "general-error");
     assert!(body["message"].as_str().unwrap().contains("boom"));
 }
+
+struct ReadOnlyQueries;
+
+#[async_trait::async_trait]
+impl DogService<Value, ()> for ReadOnlyQueries {
+    fn capabilities(&self) -> ServiceCapabilities {
+        ServiceCapabilities::from_methods(vec![
+            ServiceMethodKind::custom("read"),
+            ServiceMethodKind::custom("write"),
+        ])
+    }
+
+    async fn custom(
+        &self,
+        _ctx: &TenantContext,
+        method: &str,
+        data: Option<Value>,
+        _params: (),
+    ) -> anyhow::Result<Value> {
+        match method {
+            "read" => Ok(json!({ "query": data, "rows": [] })),
+            other => Err(DogError::not_implemented(format!(
+                "Custom method not implemented: {other}"
+            ))
+            .into_anyhow()),
+        }
+    }
+}
+
+struct TagCustomCall(&'static str);
+
+#[async_trait::async_trait]
+impl dog_core::DogBeforeHook<Value, ()> for TagCustomCall {
+    async fn run(&self, ctx: &mut dog_core::HookContext<Value, ()>) -> anyhow::Result<()> {
+        assert_eq!(ctx.method, ServiceMethodKind::custom("read"));
+        if let Some(Value::Object(data)) = ctx.data.as_mut() {
+            data.insert("tagged_by".into(), json!(self.0));
+        }
+        Ok(())
+    }
+}
+
+struct StampCustomResult;
+
+#[async_trait::async_trait]
+impl dog_core::DogAfterHook<Value, ()> for StampCustomResult {
+    async fn run(&self, ctx: &mut dog_core::HookContext<Value, ()>) -> anyhow::Result<()> {
+        if let Some(dog_core::HookResult::One(Value::Object(result))) = ctx.result.as_mut() {
+            result.insert("after".into(), json!(true));
+        }
+        Ok(())
+    }
+}
+
+fn custom_method_request(method: &str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/queries")
+        .header("content-type", "application/json")
+        .header("x-service-method", method)
+        .body(Body::from(r#"{"q":"match $x;"}"#))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn custom_method_runs_method_specific_hooks() {
+    let mut builder = DogApp::<Value, ()>::builder();
+    builder.service_hooks("queries", |h| {
+        h.before_custom("read", Arc::new(TagCustomCall("read-hook")));
+        h.after_custom("read", Arc::new(StampCustomResult));
+        // Hooks for another custom method must not fire for "read".
+        h.before_custom("write", Arc::new(TagCustomCall("write-hook")));
+    });
+    let ax = axum(builder.build()).use_service("/queries", Arc::new(ReadOnlyQueries));
+
+    let res = ax
+        .router
+        .oneshot(custom_method_request("read"))
+        .await
+        .unwrap();
+
+    assert_eq!(res.status().as_u16(), 200);
+    let body = json_body(res).await;
+    assert_eq!(body["query"]["tagged_by"], "read-hook");
+    assert_eq!(body["after"], true);
+}
+
+#[tokio::test]
+async fn unimplemented_custom_method_returns_not_implemented() {
+    let app: DogApp<Value, ()> = DogApp::default();
+    let ax = axum(app).use_service("/queries", Arc::new(ReadOnlyQueries));
+
+    let res = ax
+        .router
+        .oneshot(custom_method_request("write"))
+        .await
+        .unwrap();
+
+    assert_eq!(res.status().as_u16(), 501);
+    let body = json_body(res).await;
+    assert_eq!(body["name"], "NotImplemented");
+}
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::Request;
+use dog_axum::axum;
+use dog_core::tenant::TenantContext;
+use dog_core::{
+    DogAfterHook, DogApp, DogService, HookContext, HookResult, Paginated, ServiceCapabilities,
+    ServiceMethodKind,
+};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+const ROWS: [&str; 5] = ["a", "b", "c", "d", "e"];
+
+/// Serves a fixed second page (`skip = 2, limit = 2`) of five records.
+struct Letters {
+    paginate: bool,
+}
+
+#[async_trait::async_trait]
+impl DogService<Value, ()> for Letters {
+    fn capabilities(&self) -> ServiceCapabilities {
+        let caps = ServiceCapabilities::from_methods(vec![ServiceMethodKind::Find]);
+        if self.paginate {
+            caps.with_pagination()
+        } else {
+            caps
+        }
+    }
+
+    async fn find(&self, _ctx: &TenantContext, _params: ()) -> anyhow::Result<Vec<Value>> {
+        Ok(ROWS.iter().map(|id| json!({ "id": id })).collect())
+    }
+
+    async fn find_paginated(
+        &self,
+        _ctx: &TenantContext,
+        _params: (),
+    ) -> anyhow::Result<Paginated<Value>> {
+        let data = ROWS[2..4].iter().map(|id| json!({ "id": id })).collect();
+        Ok(Paginated::new(data, ROWS.len() as u64, 2, 2))
+    }
+}
+
+/// Drops `{ "id": "d" }` from a page and keeps `total` consistent.
+struct HideD;
+
+#[async_trait::async_trait]
+impl DogAfterHook<Value, ()> for HideD {
+    async fn run(&self, ctx: &mut HookContext<Value, ()>) -> anyhow::Result<()> {
+        if let Some(HookResult::Page { data, total, .. }) = ctx.result.as_mut() {
+            let before = data.len();
+            data.retain(|v| v["id"] != "d");
+            *total -= (before - data.len()) as u64;
+        }
+        Ok(())
+    }
+}
+
+async fn get_json(router: axum::Router, uri: &str) -> (u16, Value) {
+    let res = router
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = res.status().as_u16();
+    let bytes = res.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn paginated_service_returns_feathers_envelope() {
+    let app: DogApp<Value, ()> = DogApp::default();
+    let ax = axum(app).use_service("/letters", Arc::new(Letters { paginate: true }));
+
+    let (status, body) = get_json(ax.router, "/letters").await;
+
+    assert_eq!(status, 200);
+    assert_eq!(
+        body,
+        json!({
+            "total": 5,
+            "limit": 2,
+            "skip": 2,
+            "data": [{ "id": "c" }, { "id": "d" }],
+        })
+    );
+}
+
+#[tokio::test]
+async fn unpaginated_service_still_returns_bare_array() {
+    let app: DogApp<Value, ()> = DogApp::default();
+    let ax = axum(app).use_service("/letters", Arc::new(Letters { paginate: false }));
+
+    let (status, body) = get_json(ax.router, "/letters").await;
+
+    assert_eq!(status, 200);
+    assert_eq!(body.as_array().map(Vec::len), Some(5));
+}
+
+#[tokio::test]
+async fn after_hooks_can_rewrite_the_page() {
+    let mut builder = DogApp::<Value, ()>::builder();
+    builder.service_hooks("letters", |h| {
+        h.after_find(Arc::new(HideD));
+    });
+    builder.register_service("letters", Arc::new(Letters { paginate: true }));
+    let app = builder.build();
+
+    let page = app
+        .service("letters")
+        .unwrap()
+        .find_paginated(TenantContext::new("t"), ())
+        .await
+        .unwrap();
+
+    assert_eq!(page.data, vec![json!({ "id": "c" })]);
+    assert_eq!(page.total, 4);
+    assert_eq!((page.limit, page.skip), (2, 2));
+    assert!(page.has_more());
+
+    // Plain `find` goes through the same hooks and is unaffected by paging.
+    let all = app
+        .service("letters")
+        .unwrap()
+        .find(TenantContext::new("t"), ())
+        .await
+        .unwrap();
+    assert_eq!(all.len(), 5);
+}
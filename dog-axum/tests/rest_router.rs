@@ -0,0 +1,223 @@
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::Request;
+use dog_axum::{axum, router_for_app};
+use dog_core::errors::DogError;
+use dog_core::tenant::TenantContext;
+use dog_core::{DogApp, DogService, ServiceCapabilities, ServiceMethodKind};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+/// Echoes which method was called so each route can be checked.
+struct Echo;
+
+#[async_trait::async_trait]
+impl DogService<Value, ()> for Echo {
+    fn capabilities(&self) -> ServiceCapabilities {
+        ServiceCapabilities::from_methods(vec![
+            ServiceMethodKind::Find,
+            ServiceMethodKind::Get,
+            ServiceMethodKind::Create,
+            ServiceMethodKind::Update,
+            ServiceMethodKind::Patch,
+            ServiceMethodKind::Remove,
+            ServiceMethodKind::custom("ping"),
+        ])
+    }
+
+    async fn find(&self, ctx: &TenantContext, _params: ()) -> anyhow::Result<Vec<Value>> {
+        Ok(vec![json!({ "method": "find", "tenant": ctx.tenant_id.0 })])
+    }
+
+    async fn get(&self, _ctx: &TenantContext, id: &str, _params: ()) -> anyhow::Result<Value> {
+        if id == "missing" {
+            return Err(DogError::not_found(format!("No record {id}")).into_anyhow());
+        }
+        Ok(json!({ "method": "get", "id": id }))
+    }
+
+    async fn create(
+        &self,
+        _ctx: &TenantContext,
+        data: Value,
+        _params: (),
+    ) -> anyhow::Result<Value> {
+        Ok(json!({ "method": "create", "data": data }))
+    }
+
+    async fn update(
+        &self,
+        _ctx: &TenantContext,
+        id: &str,
+        _data: Value,
+        _params: (),
+    ) -> anyhow::Result<Value> {
+        Ok(json!({ "method": "update", "id": id }))
+    }
+
+    async fn patch(
+        &self,
+        _ctx: &TenantContext,
+        id: Option<&str>,
+        _data: Value,
+        _params: (),
+    ) -> anyhow::Result<Value> {
+        Ok(json!({ "method": "patch", "id": id }))
+    }
+
+    async fn remove(
+        &self,
+        _ctx: &TenantContext,
+        id: Option<&str>,
+        _params: (),
+    ) -> anyhow::Result<Value> {
+        Ok(json!({ "method": "remove", "id": id }))
+    }
+
+    async fn custom(
+        &self,
+        _ctx: &TenantContext,
+        method: &str,
+        _data: Option<Value>,
+        _params: (),
+    ) -> anyhow::Result<Value> {
+        Ok(json!({ "method": method }))
+    }
+}
+
+fn app() -> DogApp<Value, ()> {
+    let mut builder = DogApp::<Value, ()>::builder();
+    builder.register_service("echo", Arc::new(Echo));
+    builder.register_service("other", Arc::new(Echo));
+    builder.build()
+}
+
+async fn send(router: axum::Router, req: Request<Body>) -> (u16, Value) {
+    let res = router.oneshot(req).await.unwrap();
+    let status = res.status().as_u16();
+    let bytes = res.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+fn build(method: &str, uri: &str, body: Option<Value>) -> Request<Body> {
+    let builder = Request::builder().method(method).uri(uri);
+    match body {
+        Some(body) => builder
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string())),
+        None => builder.body(Body::empty()),
+    }
+    .unwrap()
+}
+
+#[tokio::test]
+async fn every_rest_route_reaches_its_service_method() {
+    let router = router_for_app(&app());
+    let cases = [
+        (
+            "GET",
+            "/echo/1",
+            None,
+            json!({ "method": "get", "id": "1" }),
+        ),
+        (
+            "POST",
+            "/echo",
+            Some(json!({ "a": 1 })),
+            json!({ "method": "create", "data": { "a": 1 } }),
+        ),
+        (
+            "PUT",
+            "/echo/1",
+            Some(json!({})),
+            json!({ "method": "update", "id": "1" }),
+        ),
+        (
+            "PATCH",
+            "/echo/1",
+            Some(json!({})),
+            json!({ "method": "patch", "id": "1" }),
+        ),
+        (
+            "DELETE",
+            "/echo/1",
+            None,
+            json!({ "method": "remove", "id": "1" }),
+        ),
+    ];
+
+    for (method, uri, body, expected) in cases {
+        let (status, got) = send(router.clone(), build(method, uri, body)).await;
+        assert_eq!(status, 200, "{method} {uri}");
+        assert_eq!(got, expected, "{method} {uri}");
+    }
+}
+
+#[tokio::test]
+async fn find_reads_tenant_from_headers_on_every_registered_service() {
+    let router = router_for_app(&app());
+
+    for uri in ["/echo", "/other"] {
+        let req = Request::builder()
+            .uri(uri)
+            .header("x-tenant-id", "acme")
+            .body(Body::empty())
+            .unwrap();
+        let (status, body) = send(router.clone(), req).await;
+        assert_eq!(status, 200);
+        assert_eq!(body, json!([{ "method": "find", "tenant": "acme" }]));
+    }
+}
+
+#[tokio::test]
+async fn custom_method_header_dispatches_to_custom() {
+    let router = router_for_app(&app());
+    let req = Request::builder()
+        .method("POST")
+        .uri("/echo")
+        .header("content-type", "application/json")
+        .header("x-service-method", "ping")
+        .body(Body::from("{}"))
+        .unwrap();
+
+    let (status, body) = send(router, req).await;
+
+    assert_eq!(status, 200);
+    assert_eq!(body, json!({ "method": "ping" }));
+}
+
+#[tokio::test]
+async fn service_errors_are_mapped_to_status_codes() {
+    let router = router_for_app(&app());
+
+    let (status, body) = send(router, build("GET", "/echo/missing", None)).await;
+
+    assert_eq!(status, 404);
+    assert_eq!(body["name"], "NotFound");
+    assert_eq!(body["message"], "No record missing");
+}
+
+#[tokio::test]
+async fn axum_app_mounts_registered_services() {
+    let ax = axum(app()).use_registered_services();
+
+    let (status, body) = send(ax.router, build("GET", "/other/7", None)).await;
+
+    assert_eq!(status, 200);
+    assert_eq!(body, json!({ "method": "get", "id": "7" }));
+}
+
+#[tokio::test]
+async fn registered_services_skip_ones_already_mounted() {
+    let ax = axum(app())
+        .use_service("/echo", Arc::new(Echo))
+        .use_registered_services();
+
+    for uri in ["/echo/7", "/other/7"] {
+        let (status, body) = send(ax.router.clone(), build("GET", uri, None)).await;
+        assert_eq!(status, 200, "{uri}");
+        assert_eq!(body, json!({ "method": "get", "id": "7" }));
+    }
+}
@@ -0,0 +1,230 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::http::Request;
+use axum::response::Response;
+use axum::routing::get;
+use dog_axum::axum;
+use dog_axum::sse::json_stream;
+use dog_core::tenant::TenantContext;
+use dog_core::{DogApp, DogService, ServiceCapabilities, ServiceMethodKind};
+use futures::channel::mpsc::{unbounded, UnboundedReceiver};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+struct Store;
+
+#[async_trait::async_trait]
+impl DogService<Value, ()> for Store {
+    fn capabilities(&self) -> ServiceCapabilities {
+        ServiceCapabilities::from_methods(vec![ServiceMethodKind::Create])
+    }
+
+    async fn create(
+        &self,
+        _ctx: &TenantContext,
+        data: Value,
+        _params: (),
+    ) -> anyhow::Result<Value> {
+        Ok(data)
+    }
+}
+
+fn setup() -> (DogApp<Value, ()>, axum::Router) {
+    let mut builder = DogApp::<Value, ()>::builder();
+    builder.register_service("messages", Arc::new(Store));
+    builder.register_service("notes", Arc::new(Store));
+    let app = builder.build();
+    let router = axum(app.clone()).use_sse("/events").router;
+    (app, router)
+}
+
+async fn open(router: &axum::Router, uri: &str, last_event_id: Option<&str>) -> Response {
+    let mut req = Request::builder().uri(uri).header("x-tenant-id", "acme");
+    if let Some(id) = last_event_id {
+        req = req.header("last-event-id", id);
+    }
+    let res = router
+        .clone()
+        .oneshot(req.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.headers()["content-type"], "text/event-stream");
+    res
+}
+
+/// Next SSE message as `(id, data)`, skipping keep-alive comments.
+async fn next_event(res: &mut Response) -> (String, Value) {
+    loop {
+        let frame = tokio::time::timeout(Duration::from_secs(5), res.body_mut().frame())
+            .await
+            .expect("timed out waiting for an event")
+            .unwrap()
+            .unwrap();
+        let Ok(bytes) = frame.into_data() else {
+            continue;
+        };
+        let text = String::from_utf8(bytes.to_vec()).unwrap();
+        let field = |name: &str| {
+            text.lines()
+                .find_map(|l| l.strip_prefix(name))
+                .map(str::to_string)
+        };
+        if let (Some(id), Some(data)) = (field("id: "), field("data: ")) {
+            return (id, serde_json::from_str(&data).unwrap());
+        }
+    }
+}
+
+async fn create(app: &DogApp<Value, ()>, service: &str, tenant: &str, data: Value) {
+    app.service(service)
+        .unwrap()
+        .create(TenantContext::new(tenant), data, ())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn streams_tenant_events_for_selected_services() {
+    let (app, router) = setup();
+    let mut res = open(&router, "/events?services=messages", None).await;
+
+    create(&app, "notes", "acme", json!({ "n": 1 })).await;
+    create(&app, "messages", "globex", json!({ "n": 2 })).await;
+    create(&app, "messages", "acme", json!({ "n": 3 })).await;
+
+    let (id, data) = next_event(&mut res).await;
+    assert_eq!(id, "3");
+    assert_eq!(
+        data,
+        json!({ "service": "messages", "event": "created", "data": { "n": 3 } })
+    );
+}
+
+#[tokio::test]
+async fn last_event_id_replays_missed_events() {
+    let (app, router) = setup();
+
+    create(&app, "messages", "acme", json!({ "n": 1 })).await;
+    create(&app, "notes", "acme", json!({ "n": 2 })).await;
+    create(&app, "messages", "acme", json!({ "n": 3 })).await;
+
+    let mut res = open(&router, "/events", Some("1")).await;
+    let (id, data) = next_event(&mut res).await;
+    assert_eq!((id.as_str(), &data["data"]), ("2", &json!({ "n": 2 })));
+    let (id, _) = next_event(&mut res).await;
+    assert_eq!(id, "3");
+
+    // Live events continue after the replay without repeating it.
+    create(&app, "messages", "acme", json!({ "n": 4 })).await;
+    let (id, data) = next_event(&mut res).await;
+    assert_eq!((id.as_str(), &data["data"]), ("4", &json!({ "n": 4 })));
+}
+
+#[tokio::test]
+async fn disconnected_clients_do_not_block_emitters() {
+    let (app, router) = setup();
+    drop(open(&router, "/events", None).await);
+
+    for n in 0..(dog_axum::sse::REPLAY_BUFFER + 10) {
+        create(&app, "messages", "acme", json!({ "n": n })).await;
+    }
+
+    // Only the most recent events are kept for replay.
+    let mut res = open(&router, "/events", Some("0")).await;
+    let (id, _) = next_event(&mut res).await;
+    assert_eq!(id, "11");
+}
+
+/// Next `json_stream` message as `(event, id, data)`; `event` is empty for
+/// plain messages.
+async fn next_message(res: &mut Response) -> (String, String, String) {
+    loop {
+        let frame = tokio::time::timeout(Duration::from_secs(5), res.body_mut().frame())
+            .await
+            .expect("timed out waiting for a message")
+            .unwrap()
+            .unwrap();
+        let Ok(bytes) = frame.into_data() else {
+            continue;
+        };
+        let text = String::from_utf8(bytes.to_vec()).unwrap();
+        let field = |name: &str| {
+            text.lines()
+                .find_map(|l| l.strip_prefix(name))
+                .unwrap_or_default()
+                .to_string()
+        };
+        if text
+            .lines()
+            .any(|l| l.starts_with("data:") || l.starts_with("event:"))
+        {
+            return (field("event: "), field("id: "), field("data: "));
+        }
+    }
+}
+
+fn export_router(rx: UnboundedReceiver<anyhow::Result<Value>>) -> ::axum::Router {
+    let rx = Arc::new(Mutex::new(Some(rx)));
+    ::axum::Router::new().route(
+        "/export",
+        get(move || async move { json_stream(rx.lock().unwrap().take().unwrap()) }),
+    )
+}
+
+async fn open_export(router: ::axum::Router) -> Response {
+    let req = Request::builder()
+        .uri("/export")
+        .body(Body::empty())
+        .unwrap();
+    let res = router.oneshot(req).await.unwrap();
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.headers()["content-type"], "text/event-stream");
+    res
+}
+
+#[tokio::test]
+async fn json_stream_sends_items_as_they_are_produced() {
+    let (tx, rx) = unbounded();
+    let mut res = open_export(export_router(rx)).await;
+
+    // Each item arrives before the next one exists.
+    tx.unbounded_send(Ok(json!({ "n": 1 }))).unwrap();
+    assert_eq!(
+        next_message(&mut res).await,
+        ("".into(), "1".into(), r#"{"n":1}"#.into())
+    );
+    tx.unbounded_send(Ok(json!({ "n": 2 }))).unwrap();
+    assert_eq!(
+        next_message(&mut res).await,
+        ("".into(), "2".into(), r#"{"n":2}"#.into())
+    );
+
+    drop(tx);
+    let (event, _, _) = next_message(&mut res).await;
+    assert_eq!(event, "end");
+    assert!(res.body_mut().frame().await.is_none());
+}
+
+#[tokio::test]
+async fn json_stream_ends_with_an_error_event() {
+    let (tx, rx) = unbounded();
+    tx.unbounded_send(Ok(json!({ "n": 1 }))).unwrap();
+    tx.unbounded_send(Err(anyhow::anyhow!("transaction closed")))
+        .unwrap();
+    tx.unbounded_send(Ok(json!({ "n": 2 }))).unwrap();
+    let mut res = open_export(export_router(rx)).await;
+
+    let (_, id, _) = next_message(&mut res).await;
+    assert_eq!(id, "1");
+    let (event, _, data) = next_message(&mut res).await;
+    assert_eq!(event, "error");
+    assert_eq!(
+        serde_json::from_str::<Value>(&data).unwrap(),
+        json!({ "message": "transaction closed" })
+    );
+    assert!(res.body_mut().frame().await.is_none());
+}
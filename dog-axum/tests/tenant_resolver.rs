@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::Request;
+use dog_axum::router_for_app;
+use dog_core::tenant::{SubdomainTenant, TenantContext};
+use dog_core::{DogApp, DogService};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+/// Answers `find` with the tenant it was called for.
+struct WhoAmI;
+
+#[async_trait::async_trait]
+impl DogService<Value, ()> for WhoAmI {
+    async fn find(&self, ctx: &TenantContext, _params: ()) -> anyhow::Result<Vec<Value>> {
+        Ok(vec![json!(ctx.tenant_id.0)])
+    }
+}
+
+async fn tenant_for(router: &axum::Router, host: &str) -> Value {
+    let res = router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/whoami")
+                .header("host", host)
+                .header("x-tenant-id", "from-header")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice::<Value>(&body).unwrap()[0].clone()
+}
+
+#[tokio::test]
+async fn rest_routes_use_the_installed_resolver() {
+    let mut builder = DogApp::<Value, ()>::builder();
+    builder.register_service("whoami", Arc::new(WhoAmI));
+    builder.tenant_resolver(Arc::new(SubdomainTenant::new("example.com")));
+    let router = router_for_app(&builder.build());
+
+    assert_eq!(tenant_for(&router, "acme.example.com").await, "acme");
+    // Not under the domain: the default tenant, not the header.
+    assert_eq!(tenant_for(&router, "localhost:3030").await, "default");
+}
+
+#[tokio::test]
+async fn rate_limit_tenant_key_uses_the_installed_resolver() {
+    use dog_axum::middlewares::RateLimit;
+    use std::time::Duration;
+
+    let mut builder = DogApp::<Value, ()>::builder();
+    builder.tenant_resolver(Arc::new(SubdomainTenant::new("example.com")));
+    let server = dog_axum::axum(builder.build()).use_service_with(
+        "/whoami",
+        Arc::new(WhoAmI),
+        RateLimit::new(1, Duration::from_secs(60)).key_by(RateLimit::tenant_key),
+    );
+    let status = |host: &'static str, header: &'static str| {
+        let router = server.router.clone();
+        async move {
+            let req = Request::builder()
+                .uri("/whoami")
+                .header("host", host)
+                .header("x-tenant-id", header)
+                .body(Body::empty())
+                .unwrap();
+            router.oneshot(req).await.unwrap().status().as_u16()
+        }
+    };
+
+    assert_eq!(status("acme.example.com", "a").await, 200);
+    // Same resolved tenant; a different header does not buy a new budget.
+    assert_eq!(status("acme.example.com", "b").await, 429);
+    assert_eq!(status("globex.example.com", "a").await, 200);
+}
@@ -0,0 +1,311 @@
+#![cfg(feature = "blob")]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::body::{Body, Bytes};
+use axum::http::Request;
+use axum::response::IntoResponse;
+use axum::Router;
+use dog_axum::axum;
+use dog_blob::adapter::BlobState;
+use dog_blob::{
+    BlobAdapter, BlobConfig, BlobCtx, BlobError, BlobResult, BlobStore, ByteRange, ByteStream,
+    DefaultKeyStrategy, DefaultUploadCoordinator, GetResult, MemoryUploadSessionStore, ObjectHead,
+    OpenedContent, PutResult, StoreCapabilities, UploadId, UploadRules,
+};
+use dog_core::DogApp;
+use futures::StreamExt;
+use serde_json::Value;
+use tower::ServiceExt;
+
+/// In-memory store shared by the adapter and its upload coordinator.
+#[derive(Clone, Default)]
+struct MemoryStore(Arc<Mutex<HashMap<String, Bytes>>>);
+
+#[async_trait::async_trait]
+impl BlobStore for MemoryStore {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    async fn put(
+        &self,
+        key: &str,
+        _content_type: Option<&str>,
+        mut stream: ByteStream,
+    ) -> BlobResult<PutResult> {
+        let mut data = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            data.extend_from_slice(&chunk?);
+        }
+        let size_bytes = data.len() as u64;
+        self.0
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), Bytes::from(data));
+        Ok(PutResult {
+            etag: None,
+            size_bytes,
+            checksum: None,
+        })
+    }
+
+    async fn get(&self, key: &str, _range: Option<ByteRange>) -> BlobResult<GetResult> {
+        let data = self
+            .0
+            .lock()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .ok_or_else(|| BlobError::not_found(key))?;
+        Ok(GetResult {
+            size_bytes: data.len() as u64,
+            stream: Box::pin(futures::stream::once(async move { Ok(data) })),
+            content_type: None,
+            etag: None,
+            resolved_range: None,
+        })
+    }
+
+    async fn head(&self, key: &str) -> BlobResult<ObjectHead> {
+        let store = self.0.lock().unwrap();
+        let data = store.get(key).ok_or_else(|| BlobError::not_found(key))?;
+        Ok(ObjectHead {
+            size_bytes: data.len() as u64,
+            content_type: None,
+            etag: None,
+            last_modified: None,
+        })
+    }
+
+    async fn delete(&self, key: &str) -> BlobResult<()> {
+        self.0.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    fn capabilities(&self) -> StoreCapabilities {
+        StoreCapabilities::basic()
+    }
+}
+
+fn tus_adapter() -> Arc<BlobAdapter> {
+    let store = MemoryStore::default();
+    let config =
+        BlobConfig::default().with_upload_rules(UploadRules::new().allow_variable_part_sizes());
+    let coordinator = DefaultUploadCoordinator::new(
+        store.clone(),
+        MemoryUploadSessionStore::new(),
+        DefaultKeyStrategy,
+        config.clone(),
+    );
+    let state = BlobState::new(store, config).with_uploads(coordinator);
+    Arc::new(BlobAdapter::new(Arc::new(state)))
+}
+
+fn setup() -> (Router, Arc<BlobAdapter>) {
+    let adapter = tus_adapter();
+    let router = axum(DogApp::<Value, ()>::builder().build())
+        .use_tus("/files", Arc::clone(&adapter))
+        .router;
+    (router, adapter)
+}
+
+fn tus(method: &str, uri: &str) -> axum::http::request::Builder {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("tus-resumable", "1.0.0")
+}
+
+fn patch(uri: &str, offset: u64, body: Body) -> Request<Body> {
+    tus("PATCH", uri)
+        .header("content-type", "application/offset+octet-stream")
+        .header("upload-offset", offset.to_string())
+        .body(body)
+        .unwrap()
+}
+
+async fn send(router: &Router, request: Request<Body>) -> axum::http::Response<Body> {
+    router.clone().oneshot(request).await.unwrap()
+}
+
+fn header(res: &axum::http::Response<Body>, name: &str) -> String {
+    res.headers()[name].to_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn interrupted_upload_resumes_from_the_reported_offset() {
+    let (router, adapter) = setup();
+
+    let res = send(
+        &router,
+        tus("POST", "/files")
+            .header("upload-length", "10")
+            // filename "digits.txt", filetype "text/plain"
+            .header(
+                "upload-metadata",
+                "filename ZGlnaXRzLnR4dA==,filetype dGV4dC9wbGFpbg==",
+            )
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(res.status(), 201);
+    assert_eq!(header(&res, "tus-resumable"), "1.0.0");
+    assert_eq!(header(&res, "upload-offset"), "0");
+    let location = header(&res, "location");
+    assert!(location.starts_with("/files/"));
+
+    let res = send(&router, patch(&location, 0, Body::from("01234"))).await;
+    assert_eq!(res.status(), 204);
+    assert_eq!(header(&res, "upload-offset"), "5");
+
+    // The connection drops partway through the next chunk.
+    let cut_off = futures::stream::iter(vec![
+        Ok(Bytes::from("567")),
+        Err(std::io::Error::other("connection reset")),
+    ]);
+    let res = send(&router, patch(&location, 5, Body::from_stream(cut_off))).await;
+    assert!(res.status().is_server_error());
+
+    let res = send(&router, tus("HEAD", &location).body(Body::empty()).unwrap()).await;
+    assert_eq!(res.status(), 200);
+    assert_eq!(header(&res, "upload-offset"), "5");
+    assert_eq!(header(&res, "upload-length"), "10");
+    assert_eq!(header(&res, "cache-control"), "no-store");
+
+    let res = send(&router, patch(&location, 3, Body::from("34567"))).await;
+    assert_eq!(res.status(), 409);
+
+    let res = send(&router, patch(&location, 5, Body::from("56789"))).await;
+    assert_eq!(res.status(), 204);
+    assert_eq!(header(&res, "upload-offset"), "10");
+
+    let upload_id = UploadId::from_string(location.trim_start_matches("/files/").to_string());
+    let ctx = BlobCtx::new("default".to_string());
+    let session = adapter
+        .get_upload_session(ctx.clone(), upload_id)
+        .await
+        .unwrap();
+    let opened = adapter.open(ctx, session.blob_id, None).await.unwrap();
+    assert_eq!(opened.receipt.size_bytes, 10);
+    let OpenedContent::Stream { mut stream, .. } = opened.content else {
+        panic!("expected a stream");
+    };
+    let mut content = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        content.extend_from_slice(&chunk.unwrap());
+    }
+    assert_eq!(content, b"0123456789");
+    assert_eq!(session.filename.as_deref(), Some("digits.txt"));
+    assert_eq!(session.content_type, "text/plain");
+}
+
+#[tokio::test]
+async fn requests_must_speak_a_supported_version() {
+    let (router, _) = setup();
+
+    let res = send(
+        &router,
+        Request::builder()
+            .method("OPTIONS")
+            .uri("/files")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(res.status(), 204);
+    assert_eq!(header(&res, "tus-version"), "1.0.0");
+    assert_eq!(header(&res, "tus-extension"), "creation");
+
+    let res = send(
+        &router,
+        Request::builder()
+            .method("POST")
+            .uri("/files")
+            .header("tus-resumable", "0.2.2")
+            .header("upload-length", "10")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(res.status(), 412);
+    assert_eq!(header(&res, "tus-version"), "1.0.0");
+
+    let res = send(&router, tus("POST", "/files").body(Body::empty()).unwrap()).await;
+    assert_eq!(res.status(), 400);
+}
+
+#[tokio::test]
+async fn concurrent_patches_at_one_offset_let_only_one_through() {
+    let (router, _) = setup();
+
+    let res = send(
+        &router,
+        tus("POST", "/files")
+            .header("upload-length", "10")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    let location = header(&res, "location");
+
+    // The first PATCH is still receiving when the second arrives.
+    let (release, held) = tokio::sync::oneshot::channel::<()>();
+    let slow = futures::stream::once(async move {
+        let _ = held.await;
+        Ok::<_, std::io::Error>(Bytes::from("01234"))
+    });
+    let first = send(&router, patch(&location, 0, Body::from_stream(slow)));
+    let second = async {
+        let res = send(&router, patch(&location, 0, Body::from("abcde"))).await;
+        release.send(()).unwrap();
+        res
+    };
+    let (first, second) = tokio::join!(first, second);
+
+    assert_eq!(first.status(), 204);
+    assert_eq!(header(&first, "upload-offset"), "5");
+    assert_eq!(second.status(), 409);
+
+    let res = send(&router, patch(&location, 5, Body::from("56789"))).await;
+    assert_eq!(res.status(), 204);
+    assert_eq!(header(&res, "upload-offset"), "10");
+}
+
+#[tokio::test]
+async fn guard_layer_rejects_unauthenticated_uploads() {
+    async fn require_auth(
+        request: Request<Body>,
+        next: axum::middleware::Next,
+    ) -> axum::response::Response {
+        if request.headers().contains_key("authorization") {
+            next.run(request).await
+        } else {
+            axum::http::StatusCode::UNAUTHORIZED.into_response()
+        }
+    }
+
+    let router = axum(DogApp::<Value, ()>::builder().build())
+        .use_tus_with(
+            "/files",
+            tus_adapter(),
+            axum::middleware::from_fn(require_auth),
+        )
+        .router;
+    let create = || tus("POST", "/files").header("upload-length", "10");
+
+    let res = send(&router, create().body(Body::empty()).unwrap()).await;
+    assert_eq!(res.status(), 401);
+
+    let res = send(
+        &router,
+        create()
+            .header("authorization", "Bearer token")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(res.status(), 201);
+}
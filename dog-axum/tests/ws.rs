@@ -0,0 +1,158 @@
+#![cfg(feature = "ws")]
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use dog_axum::axum;
+use dog_core::tenant::TenantContext;
+use dog_core::{DogApp, DogService, ServiceCapabilities, ServiceMethodKind};
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+struct Store;
+
+#[async_trait::async_trait]
+impl DogService<Value, ()> for Store {
+    fn capabilities(&self) -> ServiceCapabilities {
+        ServiceCapabilities::from_methods(vec![ServiceMethodKind::Create])
+    }
+
+    async fn create(
+        &self,
+        _ctx: &TenantContext,
+        data: Value,
+        _params: (),
+    ) -> anyhow::Result<Value> {
+        Ok(data)
+    }
+}
+
+async fn serve() -> (DogApp<Value, ()>, String) {
+    let mut builder = DogApp::<Value, ()>::builder();
+    builder.register_service("messages", Arc::new(Store));
+    builder.register_service("notes", Arc::new(Store));
+    let app = builder.build();
+
+    let ax = axum(app.clone()).use_ws("/ws");
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { ::axum::serve(listener, ax.router).await });
+
+    (app, format!("ws://{addr}/ws"))
+}
+
+async fn connect(url: &str, tenant: &str) -> Client {
+    let mut req = url.into_client_request().unwrap();
+    req.headers_mut()
+        .insert("x-tenant-id", tenant.parse().unwrap());
+    tokio_tungstenite::connect_async(req).await.unwrap().0
+}
+
+async fn send(client: &mut Client, frame: Value) {
+    client
+        .send(Message::Text(frame.to_string().into()))
+        .await
+        .unwrap();
+}
+
+async fn next(client: &mut Client) -> Value {
+    loop {
+        let msg = tokio::time::timeout(Duration::from_secs(5), client.next())
+            .await
+            .expect("timed out waiting for a frame")
+            .unwrap()
+            .unwrap();
+        if let Message::Text(text) = msg {
+            return serde_json::from_str(text.as_str()).unwrap();
+        }
+    }
+}
+
+async fn create(app: &DogApp<Value, ()>, service: &str, tenant: &str, data: Value) {
+    app.service(service)
+        .unwrap()
+        .create(TenantContext::new(tenant), data, ())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn subscribed_clients_receive_events_for_their_tenant() {
+    let (app, url) = serve().await;
+    let mut client = connect(&url, "acme").await;
+
+    send(
+        &mut client,
+        json!({ "type": "subscribe", "service": "messages" }),
+    )
+    .await;
+    assert_eq!(
+        next(&mut client).await,
+        json!({ "type": "subscribed", "service": "messages" })
+    );
+
+    // Another tenant's event is not delivered; the next frame is acme's.
+    create(&app, "messages", "globex", json!({ "text": "not yours" })).await;
+    create(&app, "messages", "acme", json!({ "text": "hello" })).await;
+    assert_eq!(
+        next(&mut client).await,
+        json!({
+            "type": "event",
+            "service": "messages",
+            "event": "created",
+            "data": { "text": "hello" },
+        })
+    );
+}
+
+#[tokio::test]
+async fn unsubscribe_stops_a_service_channel() {
+    let (app, url) = serve().await;
+    let mut client = connect(&url, "acme").await;
+
+    send(
+        &mut client,
+        json!({ "type": "subscribe", "service": "messages" }),
+    )
+    .await;
+    next(&mut client).await;
+    send(
+        &mut client,
+        json!({ "type": "unsubscribe", "service": "/messages" }),
+    )
+    .await;
+    assert_eq!(
+        next(&mut client).await,
+        json!({ "type": "unsubscribed", "service": "messages" })
+    );
+
+    create(&app, "messages", "acme", json!({ "text": "muted" })).await;
+
+    send(
+        &mut client,
+        json!({ "type": "subscribe", "service": "notes" }),
+    )
+    .await;
+    assert_eq!(next(&mut client).await["type"], "subscribed");
+    create(&app, "notes", "acme", json!({ "text": "note" })).await;
+
+    let frame = next(&mut client).await;
+    assert_eq!(frame["service"], "notes");
+    assert_eq!(frame["data"], json!({ "text": "note" }));
+}
+
+#[tokio::test]
+async fn malformed_frames_get_an_error_reply() {
+    let (_app, url) = serve().await;
+    let mut client = connect(&url, "acme").await;
+
+    send(&mut client, json!({ "type": "shout" })).await;
+
+    assert_eq!(next(&mut client).await["type"], "error");
+}
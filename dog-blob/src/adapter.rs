@@ -1,17 +1,26 @@
+use crate::checksum::{parse_sha256, StreamDigest};
 use crate::{
-    BlobConfig, BlobCtx, BlobError, BlobId, BlobKeyStrategy, BlobPut, BlobReceipt, BlobResult,
-    BlobStore, ByteRange, ByteStream, ChunkResult, ChunkSession, ChunkSessionId,
-    DefaultKeyStrategy, OpenedBlob, UploadCoordinator, UploadId, UploadIntent, UploadSession,
+    BlobConfig, BlobCtx, BlobError, BlobId, BlobIndex, BlobKeyStrategy, BlobPut, BlobReceipt,
+    BlobResult, BlobStore, ByteRange, ByteStream, ChunkResult, ChunkSession, ChunkSessionId,
+    ContentHashKeyStrategy, DefaultKeyStrategy, MemoryBlobIndex, MemoryQuotaStore, OpenedBlob,
+    PutResult, QuotaStore, UploadCoordinator, UploadId, UploadIntent, UploadSession,
 };
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 pub struct BlobState {
     store: Arc<dyn BlobStore>,
     keys: Arc<dyn BlobKeyStrategy>,
     uploads: Option<Arc<dyn UploadCoordinator>>,
+    quotas: Arc<dyn QuotaStore>,
+    index: Arc<dyn BlobIndex>,
     config: BlobConfig,
     chunk_sessions: Arc<tokio::sync::Mutex<HashMap<ChunkSessionId, ChunkSession>>>,
+    /// Uploads with a tus append in progress
+    appending: Arc<std::sync::Mutex<HashSet<UploadId>>>,
+    /// Blobs from `put_streamed` that no service call has claimed yet
+    streamed: std::sync::Mutex<StreamedBlobs>,
 }
 /// The main blob adapter - this is what DogService implementations embed
 pub struct BlobAdapter {
@@ -25,8 +34,12 @@ impl BlobState {
             store: Arc::new(store),
             keys: Arc::new(DefaultKeyStrategy),
             uploads: None,
+            quotas: Arc::new(MemoryQuotaStore::new()),
+            index: Arc::new(MemoryBlobIndex::new()),
             config,
             chunk_sessions: Arc::new(tokio::sync::Mutex::new(HashMap::new())),
+            appending: Arc::default(),
+            streamed: Default::default(),
         }
     }
 
@@ -40,8 +53,12 @@ impl BlobState {
             store: Arc::new(store),
             keys: Arc::new(keys),
             uploads: None,
+            quotas: Arc::new(MemoryQuotaStore::new()),
+            index: Arc::new(MemoryBlobIndex::new()),
             config,
             chunk_sessions: Arc::new(tokio::sync::Mutex::new(HashMap::new())),
+            appending: Arc::default(),
+            streamed: Default::default(),
         }
     }
 
@@ -50,6 +67,18 @@ impl BlobState {
         self.uploads = Some(Arc::new(coordinator));
         self
     }
+
+    /// Track tenant usage in `quotas` instead of in memory
+    pub fn with_quota_store<Q: QuotaStore + 'static>(mut self, quotas: Q) -> Self {
+        self.quotas = Arc::new(quotas);
+        self
+    }
+
+    /// Record blob keys in `index` instead of in memory
+    pub fn with_blob_index<I: BlobIndex + 'static>(mut self, index: I) -> Self {
+        self.index = Arc::new(index);
+        self
+    }
 }
 
 impl BlobAdapter {
@@ -73,30 +102,26 @@ impl BlobAdapter {
                     size, self.state.config.max_blob_bytes
                 )));
             }
+            self.check_quota(&ctx, size).await?;
         }
 
         let blob_id = BlobId::new();
-        let key = self
-            .state
-            .keys
-            .object_key(&ctx.tenant_id, blob_id.as_str(), &put.key_hints);
-
-        // Store the blob with metadata if filename is available
-        let result = if put.filename.is_some() {
-            self.state
-                .store
-                .put_with_metadata(
-                    &key,
-                    put.content_type.as_deref(),
-                    put.filename.as_deref(),
-                    body,
-                )
-                .await?
-        } else {
-            self.state
-                .store
-                .put(&key, put.content_type.as_deref(), body)
-                .await?
+        let key = self.object_key(&ctx, &blob_id, &put.key_hints);
+        let claimed = put
+            .key_hints
+            .get(ContentHashKeyStrategy::HINT)
+            .and_then(|hash| parse_sha256(hash).ok());
+
+        let result = match claimed {
+            Some(expected) => {
+                self.put_verified(&ctx, &blob_id, &key, &put, expected, body)
+                    .await?
+            }
+            None => {
+                let result = self.write(&key, &put, body).await?;
+                self.record(&ctx, &blob_id, &key, result.size_bytes).await?;
+                result
+            }
         };
 
         // Create receipt
@@ -124,6 +149,148 @@ impl BlobAdapter {
         Ok(receipt)
     }
 
+    /// Store the body at `key`, with metadata if a filename is available
+    async fn write(&self, key: &str, put: &BlobPut, body: ByteStream) -> BlobResult<PutResult> {
+        if put.filename.is_some() {
+            self.state
+                .store
+                .put_with_metadata(
+                    key,
+                    put.content_type.as_deref(),
+                    put.filename.as_deref(),
+                    body,
+                )
+                .await
+        } else {
+            self.state
+                .store
+                .put(key, put.content_type.as_deref(), body)
+                .await
+        }
+    }
+
+    /// Store a body that claims the content hash `expected`. It is staged
+    /// and hashed first, so a body that doesn't match is rejected with
+    /// `ChecksumMismatch` before it can reach `key`, which other blobs may
+    /// share. Content already referenced there is kept as is.
+    async fn put_verified(
+        &self,
+        ctx: &BlobCtx,
+        blob_id: &BlobId,
+        key: &str,
+        put: &BlobPut,
+        expected: String,
+        body: ByteStream,
+    ) -> BlobResult<PutResult> {
+        let staging_key = self
+            .state
+            .keys
+            .staging_key(&ctx.tenant_id, blob_id.as_str(), 0);
+        let digest = StreamDigest::default();
+        let staged = self
+            .state
+            .store
+            .put(
+                &staging_key,
+                Some("application/octet-stream"),
+                digest.wrap(body),
+            )
+            .await?;
+        let actual = digest.finish();
+        if actual != expected {
+            let _ = self.state.store.delete(&staging_key).await;
+            return Err(BlobError::ChecksumMismatch { expected, actual });
+        }
+
+        let first = match self.record(ctx, blob_id, key, staged.size_bytes).await {
+            Ok(first) => first,
+            Err(err) => {
+                let _ = self.state.store.delete(&staging_key).await;
+                return Err(err);
+            }
+        };
+        if !first {
+            let _ = self.state.store.delete(&staging_key).await;
+            let etag = self.state.store.head(key).await.ok().and_then(|h| h.etag);
+            return Ok(PutResult {
+                etag,
+                size_bytes: staged.size_bytes,
+                checksum: Some(actual),
+            });
+        }
+
+        let moved = match self.state.store.get(&staging_key, None).await {
+            Ok(staged) => self.write(key, put, staged.stream).await,
+            Err(err) => Err(err),
+        };
+        let _ = self.state.store.delete(&staging_key).await;
+        match moved {
+            Ok(result) => Ok(PutResult {
+                checksum: result.checksum.or(Some(actual)),
+                ..result
+            }),
+            Err(err) => {
+                if let Ok(Some((_, 0))) = self
+                    .state
+                    .index
+                    .remove(&ctx.tenant_id, blob_id.as_str())
+                    .await
+                {
+                    let _ = self.state.store.delete(key).await;
+                    let _ = self
+                        .state
+                        .quotas
+                        .release(&ctx.tenant_id, staged.size_bytes)
+                        .await;
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// [`BlobAdapter::put`] for a blob stored ahead of the service call
+    /// that uses it, as `MultipartToJson::stream_to_blobs` does. The call
+    /// takes it over with [`BlobAdapter::claim_streamed`], so an id sent by
+    /// the client can't stand in for an upload.
+    pub async fn put_streamed(
+        &self,
+        ctx: BlobCtx,
+        put: BlobPut,
+        body: ByteStream,
+    ) -> BlobResult<BlobReceipt> {
+        let tenant_id = ctx.tenant_id.clone();
+        let receipt = self.put(ctx, put, body).await?;
+        self.state
+            .streamed
+            .lock()
+            .unwrap()
+            .insert(tenant_id, receipt.id.clone(), Instant::now());
+        Ok(receipt)
+    }
+
+    /// Claim a blob the tenant stored with [`BlobAdapter::put_streamed`].
+    /// `false` if there is none, it was claimed already, or it was stored
+    /// more than an hour ago.
+    pub fn claim_streamed(&self, ctx: &BlobCtx, id: &BlobId) -> bool {
+        self.state
+            .streamed
+            .lock()
+            .unwrap()
+            .claim(&ctx.tenant_id, id, Instant::now())
+    }
+
+    /// Ids and keys of the tenant's blobs recorded in the blob index
+    pub async fn indexed(&self, ctx: &BlobCtx) -> BlobResult<Vec<(BlobId, String)>> {
+        Ok(self
+            .state
+            .index
+            .ids(&ctx.tenant_id)
+            .await?
+            .into_iter()
+            .map(|(id, key)| (BlobId::from_string(id), key))
+            .collect())
+    }
+
     /// Open a blob for reading
     pub async fn open(
         &self,
@@ -131,49 +298,113 @@ impl BlobAdapter {
         id: BlobId,
         range: Option<ByteRange>,
     ) -> BlobResult<OpenedBlob> {
-        let key = self.state.keys.object_key(
-            &ctx.tenant_id,
-            id.as_str(),
-            &std::collections::BTreeMap::new(),
-        );
+        let key = self.stored_key(&ctx, &id).await?;
 
         // Try signed URL first if available and no range requested
-        if range.is_none() && self.can_sign_urls() {
-            if let Ok(url) = self.sign_get_url(&key, 3600).await {
-                let expires_at = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_secs() as i64
-                    + 3600;
-
-                let receipt = self.build_receipt_from_key(&key, &id).await?;
-                return Ok(OpenedBlob::signed_url(receipt, url, expires_at));
+        if range.is_none() {
+            if let Some(opened) = signed_download(self.state.store.as_ref(), &key, &id).await? {
+                return Ok(opened);
             }
         }
 
-        // Fall back to streaming
-        let get_result = self.state.store.get(&key, range).await?;
-        let receipt = self.build_receipt_from_get_result(&get_result, id, key);
-
-        Ok(OpenedBlob::stream(
-            receipt,
-            get_result.stream,
-            get_result.resolved_range.map(|r| crate::ResolvedRange {
-                start: r.start,
-                end: r.end,
-                total_size: r.total_size,
-            }),
-        ))
+        proxy_download(self.state.store.as_ref(), key, id, range).await
     }
 
-    /// Delete a blob
+    /// Receipt for a stored blob without reading its content
+    pub async fn head(&self, ctx: BlobCtx, id: BlobId) -> BlobResult<BlobReceipt> {
+        let key = self.stored_key(&ctx, &id).await?;
+        self.build_receipt_from_key(&key, &id).await
+    }
+
+    /// Delete a blob. Content shared with other blobs (see
+    /// [`ContentHashKeyStrategy`](crate::ContentHashKeyStrategy)) stays
+    /// stored until the last of them is deleted.
     pub async fn delete(&self, ctx: BlobCtx, id: BlobId) -> BlobResult<()> {
-        let key = self.state.keys.object_key(
+        let key = match self.state.index.remove(&ctx.tenant_id, id.as_str()).await? {
+            Some((_, remaining)) if remaining > 0 => return Ok(()),
+            Some((key, _)) => key,
+            None => self.object_key(&ctx, &id, &std::collections::BTreeMap::new()),
+        };
+        let size = self.state.store.head(&key).await.ok().map(|h| h.size_bytes);
+        self.state.store.delete(&key).await?;
+        if let Some(size) = size {
+            self.state.quotas.release(&ctx.tenant_id, size).await?;
+        }
+        Ok(())
+    }
+
+    /// Key a blob was stored at: the one recorded on upload, else the key
+    /// strategy's key without hints
+    async fn stored_key(&self, ctx: &BlobCtx, id: &BlobId) -> BlobResult<String> {
+        match self.state.index.key(&ctx.tenant_id, id.as_str()).await? {
+            Some(key) => Ok(key),
+            None => Ok(self.object_key(ctx, id, &std::collections::BTreeMap::new())),
+        }
+    }
+
+    /// Hold `upload_id` for one append until the claim is dropped, or
+    /// `None` while another append holds it
+    pub(crate) fn claim_append(&self, upload_id: &UploadId) -> Option<AppendClaim> {
+        let mut appending = self.state.appending.lock().unwrap();
+        appending.insert(upload_id.clone()).then(|| AppendClaim {
+            appending: Arc::clone(&self.state.appending),
+            upload_id: upload_id.clone(),
+        })
+    }
+
+    /// Storage key for a tenant's blob
+    fn object_key(
+        &self,
+        ctx: &BlobCtx,
+        id: &BlobId,
+        hints: &std::collections::BTreeMap<String, String>,
+    ) -> String {
+        crate::store::tenant_object_key(
+            self.state.keys.as_ref(),
+            self.state.config.tenant_namespace.as_deref(),
             &ctx.tenant_id,
             id.as_str(),
-            &std::collections::BTreeMap::new(),
-        );
-        self.state.store.delete(&key).await
+            hints,
+        )
+    }
+
+    /// Bytes the tenant currently has stored
+    pub async fn quota_usage(&self, ctx: &BlobCtx) -> BlobResult<u64> {
+        self.state.quotas.usage(&ctx.tenant_id).await
+    }
+
+    /// Reject an upload of `size` bytes up front if it can't fit the quota
+    async fn check_quota(&self, ctx: &BlobCtx, size: u64) -> BlobResult<()> {
+        let Some(limit) = self.state.config.quota_for(&ctx.tenant_id) else {
+            return Ok(());
+        };
+        let used = self.state.quotas.usage(&ctx.tenant_id).await?;
+        if used.saturating_add(size) > limit {
+            return Err(BlobError::QuotaExceeded { used, limit });
+        }
+        Ok(())
+    }
+
+    /// Record a stored blob's key and count it against the tenant's
+    /// quota, undoing both if it doesn't fit. Content another blob already
+    /// references is only counted once; `false` if it was.
+    async fn record(&self, ctx: &BlobCtx, id: &BlobId, key: &str, size: u64) -> BlobResult<bool> {
+        let refs = self
+            .state
+            .index
+            .insert(&ctx.tenant_id, id.as_str(), key)
+            .await?;
+        if refs > 1 {
+            return Ok(false);
+        }
+        let limit = self.state.config.quota_for(&ctx.tenant_id);
+        if let Err(err) = self.state.quotas.reserve(&ctx.tenant_id, size, limit).await {
+            if let Ok(Some((_, 0))) = self.state.index.remove(&ctx.tenant_id, id.as_str()).await {
+                let _ = self.state.store.delete(key).await;
+            }
+            return Err(err);
+        }
+        Ok(true)
     }
 
     /// Begin a multipart upload
@@ -184,13 +415,14 @@ impl BlobAdapter {
             .as_ref()
             .ok_or_else(|| BlobError::invalid("Upload coordinator not configured"))?;
 
+        if let Some(size) = put.size_hint {
+            self.check_quota(&ctx, size).await?;
+        }
+
         let blob_id = BlobId::new();
-        let key = self
-            .state
-            .keys
-            .object_key(&ctx.tenant_id, blob_id.as_str(), &put.key_hints);
+        let key = self.object_key(&ctx, &blob_id, &put.key_hints);
 
-        let intent = UploadIntent::new(blob_id, key)
+        let mut intent = UploadIntent::new(blob_id, key)
             .with_content_type(
                 put.content_type
                     .unwrap_or_else(|| "application/octet-stream".to_string()),
@@ -202,6 +434,9 @@ impl BlobAdapter {
                 put.size_hint
                     .map(|s| s.div_ceil(self.state.config.upload_rules.part_size) as u32),
             );
+        if let Some(size) = put.size_hint {
+            intent = intent.with_size_hint(size);
+        }
 
         uploads.begin(ctx, intent).await
     }
@@ -225,6 +460,43 @@ impl BlobAdapter {
             .await
     }
 
+    /// Upload a part along with its `sha256:<hex>` checksum. A part whose
+    /// bytes don't match is rejected with `BlobError::PartChecksumMismatch`.
+    pub async fn upload_part_with_checksum(
+        &self,
+        ctx: BlobCtx,
+        upload_id: UploadId,
+        part_number: u32,
+        body: ByteStream,
+        checksum: &str,
+    ) -> BlobResult<crate::PartReceipt> {
+        let uploads = self
+            .state
+            .uploads
+            .as_ref()
+            .ok_or_else(|| BlobError::invalid("Upload coordinator not configured"))?;
+
+        uploads
+            .accept_part_with_checksum(ctx, &upload_id, part_number, body, checksum)
+            .await
+    }
+
+    /// Fix how many parts a multipart upload has
+    pub async fn set_total_parts(
+        &self,
+        ctx: BlobCtx,
+        upload_id: UploadId,
+        total_parts: u32,
+    ) -> BlobResult<UploadSession> {
+        let uploads = self
+            .state
+            .uploads
+            .as_ref()
+            .ok_or_else(|| BlobError::invalid("Upload coordinator not configured"))?;
+
+        uploads.set_total_parts(ctx, &upload_id, total_parts).await
+    }
+
     /// Complete a multipart upload
     pub async fn complete_multipart(
         &self,
@@ -237,7 +509,32 @@ impl BlobAdapter {
             .as_ref()
             .ok_or_else(|| BlobError::invalid("Upload coordinator not configured"))?;
 
-        uploads.complete(ctx, &upload_id).await
+        let receipt = uploads.complete(ctx.clone(), &upload_id).await?;
+        self.record(&ctx, &receipt.id, &receipt.key, receipt.size_bytes)
+            .await?;
+        Ok(receipt)
+    }
+
+    /// Complete a multipart upload, verifying the assembled blob against a
+    /// `sha256:<hex>` checksum
+    pub async fn complete_multipart_with_checksum(
+        &self,
+        ctx: BlobCtx,
+        upload_id: UploadId,
+        checksum: &str,
+    ) -> BlobResult<BlobReceipt> {
+        let uploads = self
+            .state
+            .uploads
+            .as_ref()
+            .ok_or_else(|| BlobError::invalid("Upload coordinator not configured"))?;
+
+        let receipt = uploads
+            .complete_with_checksum(ctx.clone(), &upload_id, checksum)
+            .await?;
+        self.record(&ctx, &receipt.id, &receipt.key, receipt.size_bytes)
+            .await?;
+        Ok(receipt)
     }
 
     /// Abort a multipart upload
@@ -266,59 +563,9 @@ impl BlobAdapter {
         uploads.get_session(ctx, &upload_id).await
     }
 
-    /// Check if store supports signed URLs
-    fn can_sign_urls(&self) -> bool {
-        // For now, assume no signed URL support
-        // This can be implemented later with proper trait bounds
-        false
-    }
-
-    /// Generate signed URL for reading (if supported)
-    async fn sign_get_url(&self, _key: &str, _expires_in_secs: u64) -> BlobResult<String> {
-        // For now, return unsupported
-        // This can be implemented later with proper trait bounds
-        Err(BlobError::Unsupported)
-    }
-
     /// Build receipt from key (for signed URLs)
     async fn build_receipt_from_key(&self, key: &str, id: &BlobId) -> BlobResult<BlobReceipt> {
-        let head = self.state.store.head(key).await?;
-
-        let mut receipt = BlobReceipt::new(id.clone(), key.to_string(), head.size_bytes);
-
-        if let Some(ct) = head.content_type {
-            receipt = receipt.with_content_type(ct);
-        }
-        if let Some(etag) = head.etag {
-            receipt = receipt.with_etag(etag);
-        }
-        if self.state.store.capabilities().supports_range {
-            receipt = receipt.with_range_support();
-        }
-
-        Ok(receipt)
-    }
-
-    /// Build receipt from get result
-    fn build_receipt_from_get_result(
-        &self,
-        get_result: &crate::store::GetResult,
-        id: BlobId,
-        key: String,
-    ) -> BlobReceipt {
-        let mut receipt = BlobReceipt::new(id, key, get_result.size_bytes);
-
-        if let Some(ct) = &get_result.content_type {
-            receipt = receipt.with_content_type(ct.clone());
-        }
-        if let Some(etag) = &get_result.etag {
-            receipt = receipt.with_etag(etag.clone());
-        }
-        if self.state.store.capabilities().supports_range {
-            receipt = receipt.with_range_support();
-        }
-
-        receipt
+        receipt_from_head(self.state.store.as_ref(), key, id).await
     }
 
     /// Get configuration
@@ -344,10 +591,11 @@ impl BlobAdapter {
         limit: Option<usize>,
     ) -> BlobResult<Vec<crate::BlobInfo>> {
         // Use tenant-specific prefix if provided
+        let tenant_prefix = self.state.config.tenant_prefix(&ctx.tenant_id);
         let full_prefix = if let Some(prefix) = prefix {
-            Some(format!("{}/{}", ctx.tenant_id, prefix))
+            Some(format!("{}/{}", tenant_prefix, prefix))
         } else {
-            Some(ctx.tenant_id.clone())
+            Some(tenant_prefix)
         };
 
         self.state.store.list(full_prefix.as_deref(), limit).await
@@ -574,3 +822,155 @@ impl BlobAdapter {
         }
     }
 }
+
+/// An upload held by one append; see [`BlobAdapter::claim_append`]
+pub(crate) struct AppendClaim {
+    appending: Arc<std::sync::Mutex<HashSet<UploadId>>>,
+    upload_id: UploadId,
+}
+
+impl Drop for AppendClaim {
+    fn drop(&mut self) {
+        self.appending.lock().unwrap().remove(&self.upload_id);
+    }
+}
+
+/// How long a blob from `put_streamed` can be claimed
+const STREAMED_CLAIM_TTL: Duration = Duration::from_secs(3600);
+
+/// Blobs from [`BlobAdapter::put_streamed`] awaiting a claim. Entries
+/// expire in the order they were stored, so expiry pops from the front.
+#[derive(Default)]
+struct StreamedBlobs {
+    pending: HashSet<(String, BlobId)>,
+    stored: VecDeque<(Instant, String, BlobId)>,
+}
+
+impl StreamedBlobs {
+    fn insert(&mut self, tenant_id: String, id: BlobId, now: Instant) {
+        self.expire(now);
+        self.pending.insert((tenant_id.clone(), id.clone()));
+        self.stored.push_back((now, tenant_id, id));
+    }
+
+    fn claim(&mut self, tenant_id: &str, id: &BlobId, now: Instant) -> bool {
+        self.expire(now);
+        self.pending.remove(&(tenant_id.to_string(), id.clone()))
+    }
+
+    fn expire(&mut self, now: Instant) {
+        while let Some((stored_at, _, _)) = self.stored.front() {
+            if now.duration_since(*stored_at) < STREAMED_CLAIM_TTL {
+                break;
+            }
+            if let Some((_, tenant_id, id)) = self.stored.pop_front() {
+                self.pending.remove(&(tenant_id, id));
+            }
+        }
+    }
+}
+
+/// How long a signed download URL stays valid
+const SIGNED_URL_TTL_SECS: u64 = 3600;
+
+/// Download a blob the best way `store` allows: a signed URL to redirect
+/// to when it advertises [`StoreCapabilities::supports_signed_urls`] and
+/// can sign, otherwise a stream to proxy through the app. Blobs are found
+/// under [`DefaultKeyStrategy`] keys; adapters with their own key strategy
+/// get the same behaviour from [`BlobAdapter::open`].
+///
+/// [`StoreCapabilities::supports_signed_urls`]: crate::StoreCapabilities::supports_signed_urls
+pub async fn download_url_or_proxy(
+    store: &dyn BlobStore,
+    ctx: &BlobCtx,
+    blob_id: &BlobId,
+) -> BlobResult<OpenedBlob> {
+    let key = DefaultKeyStrategy.object_key(
+        &ctx.tenant_id,
+        blob_id.as_str(),
+        &std::collections::BTreeMap::new(),
+    );
+    match signed_download(store, &key, blob_id).await? {
+        Some(opened) => Ok(opened),
+        None => proxy_download(store, key, blob_id.clone(), None).await,
+    }
+}
+
+/// A signed URL for `key`, or `None` when the store can't sign one.
+async fn signed_download(
+    store: &dyn BlobStore,
+    key: &str,
+    id: &BlobId,
+) -> BlobResult<Option<OpenedBlob>> {
+    if !store.capabilities().supports_signed_urls {
+        return Ok(None);
+    }
+    let Some(signer) = store.as_signed_url_store() else {
+        return Ok(None);
+    };
+    let url = match signer.sign_get(key, SIGNED_URL_TTL_SECS).await {
+        Ok(url) => url,
+        Err(_) => return Ok(None),
+    };
+    let expires_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+        + SIGNED_URL_TTL_SECS as i64;
+
+    // Signing doesn't touch the object, so make sure it exists
+    let receipt = receipt_from_head(store, key, id).await?;
+    Ok(Some(OpenedBlob::signed_url(receipt, url, expires_at)))
+}
+
+/// Stream `key` from the store.
+async fn proxy_download(
+    store: &dyn BlobStore,
+    key: String,
+    id: BlobId,
+    range: Option<ByteRange>,
+) -> BlobResult<OpenedBlob> {
+    let get_result = store.get(&key, range).await?;
+    let mut receipt = BlobReceipt::new(id, key, get_result.size_bytes);
+    if let Some(ct) = &get_result.content_type {
+        receipt = receipt.with_content_type(ct.clone());
+    }
+    if let Some(etag) = &get_result.etag {
+        receipt = receipt.with_etag(etag.clone());
+    }
+    if store.capabilities().supports_range {
+        receipt = receipt.with_range_support();
+    }
+
+    Ok(OpenedBlob::stream(
+        receipt,
+        get_result.stream,
+        get_result.resolved_range.map(|r| crate::ResolvedRange {
+            start: r.start,
+            end: r.end,
+            total_size: r.total_size,
+        }),
+    ))
+}
+
+async fn receipt_from_head(
+    store: &dyn BlobStore,
+    key: &str,
+    id: &BlobId,
+) -> BlobResult<BlobReceipt> {
+    let head = store.head(key).await?;
+
+    let mut receipt = BlobReceipt::new(id.clone(), key.to_string(), head.size_bytes);
+
+    if let Some(ct) = head.content_type {
+        receipt = receipt.with_content_type(ct);
+    }
+    if let Some(etag) = head.etag {
+        receipt = receipt.with_etag(etag);
+    }
+    if store.capabilities().supports_range {
+        receipt = receipt.with_range_support();
+    }
+
+    Ok(receipt)
+}
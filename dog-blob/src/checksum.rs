@@ -0,0 +1,50 @@
+use std::sync::{Arc, Mutex};
+
+use futures_util::StreamExt;
+use sha2::{Digest, Sha256};
+
+use crate::{BlobError, BlobResult, ByteStream};
+
+const SHA256_PREFIX: &str = "sha256:";
+
+/// Checksum of `data` in the form uploads are verified against
+/// (`sha256:<hex>`).
+pub fn sha256_checksum(data: &[u8]) -> String {
+    format!("{SHA256_PREFIX}{:x}", Sha256::digest(data))
+}
+
+/// Normalise a client checksum (`sha256:<hex>` or bare hex) to
+/// `sha256:<lowercase hex>`.
+pub(crate) fn parse_sha256(checksum: &str) -> BlobResult<String> {
+    let hex = checksum
+        .trim()
+        .strip_prefix(SHA256_PREFIX)
+        .unwrap_or(checksum.trim())
+        .to_ascii_lowercase();
+    if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(BlobError::invalid(format!(
+            "Unsupported checksum '{checksum}': expected sha256:<64 hex digits>"
+        )));
+    }
+    Ok(format!("{SHA256_PREFIX}{hex}"))
+}
+
+/// SHA-256 of everything that passed through [`StreamDigest::wrap`].
+#[derive(Clone, Default)]
+pub(crate) struct StreamDigest(Arc<Mutex<Sha256>>);
+
+impl StreamDigest {
+    pub(crate) fn wrap(&self, body: ByteStream) -> ByteStream {
+        let digest = self.clone();
+        Box::pin(body.inspect(move |chunk| {
+            if let Ok(bytes) = chunk {
+                digest.0.lock().unwrap().update(bytes);
+            }
+        }))
+    }
+
+    pub(crate) fn finish(&self) -> String {
+        let hasher = self.0.lock().unwrap().clone();
+        format!("{SHA256_PREFIX}{:x}", hasher.finalize())
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 /// Configuration for blob operations
 #[derive(Debug, Clone)]
 pub struct BlobConfig {
@@ -17,6 +19,15 @@ pub struct BlobConfig {
 
     /// Optional: compute checksums during upload/assembly (streaming)
     pub checksum_alg: Option<String>,
+
+    /// Byte quota for tenants without an entry in `tenant_quotas`
+    pub default_tenant_quota: Option<u64>,
+
+    /// Per-tenant byte quotas
+    pub tenant_quotas: HashMap<String, u64>,
+
+    /// Store each tenant's blobs under `<namespace>/<tenant_id>/`
+    pub tenant_namespace: Option<String>,
 }
 
 impl Default for BlobConfig {
@@ -27,6 +38,9 @@ impl Default for BlobConfig {
             upload_rules: UploadRules::default(),
             require_range_support: false,
             checksum_alg: None,
+            default_tenant_quota: None,
+            tenant_quotas: HashMap::new(),
+            tenant_namespace: None,
         }
     }
 }
@@ -93,6 +107,41 @@ impl BlobConfig {
         self.checksum_alg = Some(algorithm.into());
         self
     }
+
+    /// Limit every tenant to `bytes` unless it has its own quota
+    pub fn with_default_tenant_quota(mut self, bytes: u64) -> Self {
+        self.default_tenant_quota = Some(bytes);
+        self
+    }
+
+    /// Limit one tenant to `bytes`
+    pub fn with_tenant_quota<S: Into<String>>(mut self, tenant_id: S, bytes: u64) -> Self {
+        self.tenant_quotas.insert(tenant_id.into(), bytes);
+        self
+    }
+
+    /// Namespace keys per tenant (e.g. `tenants` → `tenants/<tenant_id>/...`)
+    /// so bucket policies and lifecycle rules can target one tenant
+    pub fn with_tenant_namespace<S: Into<String>>(mut self, namespace: S) -> Self {
+        self.tenant_namespace = Some(namespace.into().trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Prefix every key of a tenant starts with
+    pub fn tenant_prefix(&self, tenant_id: &str) -> String {
+        match &self.tenant_namespace {
+            Some(namespace) => format!("{}/{}", namespace, tenant_id),
+            None => tenant_id.to_string(),
+        }
+    }
+
+    /// The byte quota that applies to a tenant, if any
+    pub fn quota_for(&self, tenant_id: &str) -> Option<u64> {
+        self.tenant_quotas
+            .get(tenant_id)
+            .copied()
+            .or(self.default_tenant_quota)
+    }
 }
 
 impl UploadRules {
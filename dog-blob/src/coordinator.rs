@@ -1,19 +1,28 @@
 use async_trait::async_trait;
 use futures_util::StreamExt;
+use std::path::PathBuf;
 use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
+use crate::checksum::{parse_sha256, StreamDigest};
 use crate::{
     receipt::UploadInfo, BlobConfig, BlobCtx, BlobError, BlobKeyStrategy, BlobReceipt, BlobResult,
     BlobStore, ByteStream, PartReceipt, UploadCoordinator, UploadId, UploadIntent, UploadProgress,
-    UploadSession, UploadSessionStore, UploadStatus,
+    UploadSession, UploadSessionStore, UploadStatus, UploadStrategy,
 };
 
-/// Default upload coordinator that handles both native multipart and staged assembly
+/// Default upload coordinator.
+///
+/// Stores that advertise `supports_multipart` get their parts staged as
+/// objects in the store. Simple stores get them buffered on local disk
+/// (see [`DefaultUploadCoordinator::with_buffer_dir`]) and written with a
+/// single `put` on completion. The session's `strategy` says which.
 pub struct DefaultUploadCoordinator {
     store: Arc<dyn BlobStore>,
     sessions: Arc<dyn UploadSessionStore>,
     keys: Arc<dyn BlobKeyStrategy>,
     config: BlobConfig,
+    buffer_dir: PathBuf,
 }
 
 impl DefaultUploadCoordinator {
@@ -28,83 +37,105 @@ impl DefaultUploadCoordinator {
             sessions: Arc::new(sessions),
             keys: Arc::new(keys),
             config,
+            buffer_dir: std::env::temp_dir().join("dog_blob_uploads"),
         }
     }
 
-    /// Concatenate staged parts into a single stream
-    fn concat_part_streams(&self, part_keys: Vec<String>) -> ByteStream {
-        let store = self.store.clone();
-        let stream = async_stream::stream! {
-            for key in part_keys {
-                match store.get(&key, None).await {
-                    Ok(get_result) => {
-                        let mut part_stream = get_result.stream;
-                        while let Some(chunk) = StreamExt::next(&mut part_stream).await {
-                            yield chunk;
-                        }
-                    }
-                    Err(e) => {
-                        yield Err(std::io::Error::other(
-                            format!("Failed to read part {}: {}", key, e)
-                        ));
-                        return;
-                    }
-                }
-            }
-        };
-        Box::pin(stream)
+    /// Directory for parts of uploads to stores without multipart support
+    /// (default: `dog_blob_uploads` under the system temp dir).
+    pub fn with_buffer_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.buffer_dir = dir.into();
+        self
     }
 
-    /// Clean up staged parts
-    async fn cleanup_staged_parts(&self, tenant_id: &str, upload_id: &UploadId, part_count: u32) {
-        for part_num in 1..=part_count {
-            let key = self
-                .keys
-                .staging_key(tenant_id, upload_id.as_str(), part_num);
-            let _ = self.store.delete(&key).await; // Best effort cleanup
-        }
+    fn buffer_path(&self, upload_id: &UploadId) -> PathBuf {
+        self.buffer_dir.join(upload_id.as_str())
     }
-}
 
-#[async_trait]
-impl UploadCoordinator for DefaultUploadCoordinator {
-    async fn begin(&self, ctx: BlobCtx, intent: UploadIntent) -> BlobResult<UploadSession> {
-        let upload_id = UploadId::new();
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs() as i64;
+    fn buffered_part_path(&self, upload_id: &UploadId, part_number: u32) -> PathBuf {
+        self.buffer_path(upload_id)
+            .join(format!("part_{:05}", part_number))
+    }
 
-        let total_parts = match &intent.chunking {
-            crate::upload::Chunking::Parts { total_parts, .. } => *total_parts,
-            crate::upload::Chunking::Single => None,
+    /// Write a part to local disk. It only gets its final name once the
+    /// whole body arrived and `check` passed, so an interrupted or corrupted
+    /// part is never picked up.
+    async fn buffer_part<T>(
+        &self,
+        upload_id: &UploadId,
+        part_number: u32,
+        mut body: ByteStream,
+        check: impl FnOnce() -> BlobResult<T>,
+    ) -> BlobResult<(u64, T)> {
+        tokio::fs::create_dir_all(self.buffer_path(upload_id)).await?;
+        let part_path = self.buffered_part_path(upload_id, part_number);
+        let partial_path = part_path.with_extension("partial");
+
+        let mut file = tokio::fs::File::create(&partial_path).await?;
+        let mut size = 0u64;
+        while let Some(chunk) = body.next().await {
+            let written = match chunk {
+                Ok(chunk) => file.write_all(&chunk).await.map(|_| chunk.len() as u64),
+                Err(e) => Err(e),
+            };
+            match written {
+                Ok(len) => size += len,
+                Err(e) => {
+                    drop(file);
+                    let _ = tokio::fs::remove_file(&partial_path).await;
+                    return Err(e.into());
+                }
+            }
+        }
+        file.flush().await?;
+        drop(file);
+
+        let checked = match check() {
+            Ok(checked) => checked,
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&partial_path).await;
+                return Err(e);
+            }
         };
+        tokio::fs::rename(&partial_path, &part_path).await?;
+        Ok((size, checked))
+    }
 
-        let session = UploadSession {
-            upload_id: upload_id.clone(),
-            blob_id: intent.id,
-            tenant_id: ctx.tenant_id.clone(),
-            actor_id: ctx.actor_id.clone(),
-            created_at: now,
-            updated_at: now,
-            total_parts,
-            status: UploadStatus::Active,
-            content_type: intent.content_type,
-            filename: intent.filename,
-            size_hint: intent.size_hint,
-            attributes: intent.attributes,
-            progress: UploadProgress::default(),
+    /// Concatenate locally buffered parts into a single stream
+    fn concat_buffered_parts(&self, part_paths: Vec<PathBuf>) -> ByteStream {
+        let stream = async_stream::stream! {
+            for path in part_paths {
+                let mut file = match tokio::fs::File::open(&path).await {
+                    Ok(file) => file,
+                    Err(e) => {
+                        yield Err(e);
+                        return;
+                    }
+                };
+                let mut buf = vec![0u8; 64 * 1024];
+                loop {
+                    match file.read(&mut buf).await {
+                        Ok(0) => break,
+                        Ok(n) => yield Ok(bytes::Bytes::copy_from_slice(&buf[..n])),
+                        Err(e) => {
+                            yield Err(e);
+                            return;
+                        }
+                    }
+                }
+            }
         };
-
-        self.sessions.create(session).await
+        Box::pin(stream)
     }
 
-    async fn accept_part(
+    /// Store a part, verifying it against `checksum` when one is given.
+    async fn accept(
         &self,
         ctx: BlobCtx,
         upload_id: &UploadId,
         part_number: u32,
         body: ByteStream,
+        checksum: Option<&str>,
     ) -> BlobResult<PartReceipt> {
         // Validate part number
         if part_number == 0 || part_number > self.config.upload_rules.max_parts {
@@ -113,6 +144,7 @@ impl UploadCoordinator for DefaultUploadCoordinator {
                 part_number, self.config.upload_rules.max_parts
             )));
         }
+        let expected = checksum.map(parse_sha256).transpose()?;
 
         let session = self.sessions.get(upload_id).await?;
         if !matches!(session.status, UploadStatus::Active) {
@@ -124,21 +156,64 @@ impl UploadCoordinator for DefaultUploadCoordinator {
             .unwrap_or_default()
             .as_secs() as i64;
 
-        // Use staged assembly (simplified implementation)
-        let staging_key = self
-            .keys
-            .staging_key(&ctx.tenant_id, upload_id.as_str(), part_number);
-        let result = self
-            .store
-            .put(&staging_key, Some("application/octet-stream"), body)
-            .await?;
+        let digest = StreamDigest::default();
+        let body = match expected {
+            Some(_) => digest.wrap(body),
+            None => body,
+        };
+        // The verified checksum, if the client sent one.
+        let verify = || match &expected {
+            None => Ok(None),
+            Some(expected) => {
+                let actual = digest.finish();
+                if &actual == expected {
+                    Ok(Some(actual))
+                } else {
+                    Err(BlobError::PartChecksumMismatch {
+                        part_number,
+                        expected: expected.clone(),
+                        actual,
+                    })
+                }
+            }
+        };
 
-        let receipt = PartReceipt {
-            part_number,
-            size_bytes: result.size_bytes,
-            etag: result.etag,
-            checksum: result.checksum,
-            uploaded_at: now,
+        let receipt = match session.strategy {
+            UploadStrategy::Staged => {
+                let staging_key =
+                    self.keys
+                        .staging_key(&ctx.tenant_id, upload_id.as_str(), part_number);
+                let result = self
+                    .store
+                    .put(&staging_key, Some("application/octet-stream"), body)
+                    .await?;
+                let verified = match verify() {
+                    Ok(verified) => verified,
+                    Err(e) => {
+                        let _ = self.store.delete(&staging_key).await;
+                        return Err(e);
+                    }
+                };
+                PartReceipt {
+                    part_number,
+                    size_bytes: result.size_bytes,
+                    etag: result.etag,
+                    checksum: verified.or(result.checksum),
+                    uploaded_at: now,
+                }
+            }
+            UploadStrategy::LocalBuffer => {
+                let (size_bytes, verified) = self
+                    .buffer_part(upload_id, part_number, body, verify)
+                    .await?;
+                PartReceipt {
+                    part_number,
+                    size_bytes,
+                    etag: None,
+                    checksum: verified,
+                    uploaded_at: now,
+                }
+            }
         };
 
         // Record the part
@@ -149,45 +224,15 @@ impl UploadCoordinator for DefaultUploadCoordinator {
         Ok(receipt)
     }
 
-    async fn set_total_parts(
+    /// Write the parts out as one blob, verifying it against `checksum`
+    /// when one is given.
+    async fn assemble(
         &self,
-        _ctx: BlobCtx,
+        ctx: BlobCtx,
         upload_id: &UploadId,
-        total_parts: u32,
-    ) -> BlobResult<UploadSession> {
-        let mut session = self.sessions.get(upload_id).await?;
-
-        if !matches!(session.status, UploadStatus::Active) {
-            return Err(BlobError::invalid("Upload session is not active"));
-        }
-
-        if total_parts == 0 || total_parts > self.config.upload_rules.max_parts {
-            return Err(BlobError::invalid(format!(
-                "Invalid total parts: {} (must be 1-{})",
-                total_parts, self.config.upload_rules.max_parts
-            )));
-        }
-
-        // Can't shrink below already uploaded max part
-        if let Some(max_part) = session.progress.parts.keys().max() {
-            if total_parts < *max_part {
-                return Err(BlobError::invalid(format!(
-                    "Cannot set total parts to {} when part {} already exists",
-                    total_parts, max_part
-                )));
-            }
-        }
-
-        session.total_parts = Some(total_parts);
-        session.updated_at = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs() as i64;
-
-        self.sessions.update(session).await
-    }
-
-    async fn complete(&self, ctx: BlobCtx, upload_id: &UploadId) -> BlobResult<BlobReceipt> {
+        checksum: Option<&str>,
+    ) -> BlobResult<BlobReceipt> {
+        let expected = checksum.map(parse_sha256).transpose()?;
         let session = self.sessions.get(upload_id).await?;
 
         if !matches!(session.status, UploadStatus::Active) {
@@ -236,26 +281,53 @@ impl UploadCoordinator for DefaultUploadCoordinator {
             .unwrap_or_default()
             .as_secs() as i64;
 
-        let final_key = self.keys.object_key(
-            &ctx.tenant_id,
-            session.blob_id.as_str(),
-            &std::collections::BTreeMap::new(),
-        );
-
-        // Staged assembly (simplified implementation)
-        let part_keys: Vec<String> = (1..=total_parts)
-            .map(|p| self.keys.staging_key(&ctx.tenant_id, upload_id.as_str(), p))
-            .collect();
+        let final_key = match &session.key {
+            Some(key) => key.clone(),
+            None => crate::store::tenant_object_key(
+                self.keys.as_ref(),
+                self.config.tenant_namespace.as_deref(),
+                &ctx.tenant_id,
+                session.blob_id.as_str(),
+                &std::collections::BTreeMap::new(),
+            ),
+        };
 
-        let concatenated = self.concat_part_streams(part_keys);
+        let concatenated = match session.strategy {
+            UploadStrategy::Staged => self.concat_part_streams(
+                (1..=total_parts)
+                    .map(|p| self.keys.staging_key(&ctx.tenant_id, upload_id.as_str(), p))
+                    .collect(),
+            ),
+            UploadStrategy::LocalBuffer => self.concat_buffered_parts(
+                (1..=total_parts)
+                    .map(|p| self.buffered_part_path(upload_id, p))
+                    .collect(),
+            ),
+        };
+        let digest = StreamDigest::default();
+        let concatenated = match expected {
+            Some(_) => digest.wrap(concatenated),
+            None => concatenated,
+        };
         let result = self
             .store
             .put(&final_key, Some(&session.content_type), concatenated)
             .await?;
 
-        // Cleanup staged parts
-        self.cleanup_staged_parts(&ctx.tenant_id, upload_id, total_parts)
-            .await;
+        // On a mismatch the parts stay, so the upload can still be fixed up.
+        let verified = match expected {
+            None => None,
+            Some(expected) => {
+                let actual = digest.finish();
+                if actual != expected {
+                    let _ = self.store.delete(&final_key).await;
+                    return Err(BlobError::ChecksumMismatch { expected, actual });
+                }
+                Some(actual)
+            }
+        };
+
+        self.cleanup_parts(&session, total_parts).await;
 
         // Mark session completed
         self.sessions.mark_completed(upload_id, now).await?;
@@ -276,7 +348,7 @@ impl UploadCoordinator for DefaultUploadCoordinator {
         if let Some(etag) = result.etag {
             receipt = receipt.with_etag(etag);
         }
-        if let Some(checksum) = result.checksum {
+        if let Some(checksum) = verified.or(result.checksum) {
             receipt = receipt.with_checksum(checksum);
         }
         if self.store.capabilities().supports_range {
@@ -286,7 +358,169 @@ impl UploadCoordinator for DefaultUploadCoordinator {
         Ok(receipt)
     }
 
-    async fn abort(&self, ctx: BlobCtx, upload_id: &UploadId) -> BlobResult<()> {
+    /// Clean up parts of a finished upload, wherever they were kept
+    async fn cleanup_parts(&self, session: &UploadSession, part_count: u32) {
+        match session.strategy {
+            UploadStrategy::Staged => {
+                self.cleanup_staged_parts(&session.tenant_id, &session.upload_id, part_count)
+                    .await
+            }
+            UploadStrategy::LocalBuffer => {
+                let _ = tokio::fs::remove_dir_all(self.buffer_path(&session.upload_id)).await;
+            }
+        }
+    }
+
+    /// Concatenate staged parts into a single stream
+    fn concat_part_streams(&self, part_keys: Vec<String>) -> ByteStream {
+        let store = self.store.clone();
+        let stream = async_stream::stream! {
+            for key in part_keys {
+                match store.get(&key, None).await {
+                    Ok(get_result) => {
+                        let mut part_stream = get_result.stream;
+                        while let Some(chunk) = StreamExt::next(&mut part_stream).await {
+                            yield chunk;
+                        }
+                    }
+                    Err(e) => {
+                        yield Err(std::io::Error::other(
+                            format!("Failed to read part {}: {}", key, e)
+                        ));
+                        return;
+                    }
+                }
+            }
+        };
+        Box::pin(stream)
+    }
+
+    /// Clean up staged parts
+    async fn cleanup_staged_parts(&self, tenant_id: &str, upload_id: &UploadId, part_count: u32) {
+        for part_num in 1..=part_count {
+            let key = self
+                .keys
+                .staging_key(tenant_id, upload_id.as_str(), part_num);
+            let _ = self.store.delete(&key).await; // Best effort cleanup
+        }
+    }
+}
+
+#[async_trait]
+impl UploadCoordinator for DefaultUploadCoordinator {
+    async fn begin(&self, ctx: BlobCtx, intent: UploadIntent) -> BlobResult<UploadSession> {
+        let upload_id = UploadId::new();
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+
+        let total_parts = match &intent.chunking {
+            crate::upload::Chunking::Parts { total_parts, .. } => *total_parts,
+            crate::upload::Chunking::Single => None,
+        };
+
+        let strategy = if self.store.capabilities().supports_multipart {
+            UploadStrategy::Staged
+        } else {
+            UploadStrategy::LocalBuffer
+        };
+
+        let session = UploadSession {
+            upload_id: upload_id.clone(),
+            blob_id: intent.id,
+            tenant_id: ctx.tenant_id.clone(),
+            actor_id: ctx.actor_id.clone(),
+            created_at: now,
+            updated_at: now,
+            total_parts,
+            status: UploadStatus::Active,
+            strategy,
+            content_type: intent.content_type,
+            filename: intent.filename,
+            size_hint: intent.size_hint,
+            attributes: intent.attributes,
+            key: Some(intent.key),
+            progress: UploadProgress::default(),
+        };
+
+        self.sessions.create(session).await
+    }
+
+    async fn accept_part(
+        &self,
+        ctx: BlobCtx,
+        upload_id: &UploadId,
+        part_number: u32,
+        body: ByteStream,
+    ) -> BlobResult<PartReceipt> {
+        self.accept(ctx, upload_id, part_number, body, None).await
+    }
+
+    async fn accept_part_with_checksum(
+        &self,
+        ctx: BlobCtx,
+        upload_id: &UploadId,
+        part_number: u32,
+        body: ByteStream,
+        checksum: &str,
+    ) -> BlobResult<PartReceipt> {
+        self.accept(ctx, upload_id, part_number, body, Some(checksum))
+            .await
+    }
+
+    async fn set_total_parts(
+        &self,
+        _ctx: BlobCtx,
+        upload_id: &UploadId,
+        total_parts: u32,
+    ) -> BlobResult<UploadSession> {
+        let mut session = self.sessions.get(upload_id).await?;
+
+        if !matches!(session.status, UploadStatus::Active) {
+            return Err(BlobError::invalid("Upload session is not active"));
+        }
+
+        if total_parts == 0 || total_parts > self.config.upload_rules.max_parts {
+            return Err(BlobError::invalid(format!(
+                "Invalid total parts: {} (must be 1-{})",
+                total_parts, self.config.upload_rules.max_parts
+            )));
+        }
+
+        // Can't shrink below already uploaded max part
+        if let Some(max_part) = session.progress.parts.keys().max() {
+            if total_parts < *max_part {
+                return Err(BlobError::invalid(format!(
+                    "Cannot set total parts to {} when part {} already exists",
+                    total_parts, max_part
+                )));
+            }
+        }
+
+        session.total_parts = Some(total_parts);
+        session.updated_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+
+        self.sessions.update(session).await
+    }
+
+    async fn complete(&self, ctx: BlobCtx, upload_id: &UploadId) -> BlobResult<BlobReceipt> {
+        self.assemble(ctx, upload_id, None).await
+    }
+
+    async fn complete_with_checksum(
+        &self,
+        ctx: BlobCtx,
+        upload_id: &UploadId,
+        checksum: &str,
+    ) -> BlobResult<BlobReceipt> {
+        self.assemble(ctx, upload_id, Some(checksum)).await
+    }
+
+    async fn abort(&self, _ctx: BlobCtx, upload_id: &UploadId) -> BlobResult<()> {
         let session = self.sessions.get(upload_id).await?;
 
         let now = std::time::SystemTime::now()
@@ -294,10 +528,8 @@ impl UploadCoordinator for DefaultUploadCoordinator {
             .unwrap_or_default()
             .as_secs() as i64;
 
-        // Cleanup staged parts (simplified implementation)
         let total_parts = session.progress.parts.keys().max().copied().unwrap_or(0);
-        self.cleanup_staged_parts(&ctx.tenant_id, upload_id, total_parts)
-            .await;
+        self.cleanup_parts(&session, total_parts).await;
 
         // Mark session aborted
         self.sessions.mark_aborted(upload_id, now).await?;
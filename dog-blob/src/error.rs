@@ -18,6 +18,25 @@ pub enum BlobError {
     #[error("Upload session not found: {upload_id}")]
     UploadNotFound { upload_id: String },
 
+    #[error("Upload offset {actual} does not match current offset {expected}")]
+    OffsetMismatch { expected: u64, actual: u64 },
+
+    #[error("Upload {upload_id} is already receiving a chunk")]
+    UploadBusy { upload_id: String },
+
+    #[error("Checksum mismatch for part {part_number}: expected {expected}, got {actual}")]
+    PartChecksumMismatch {
+        part_number: u32,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Checksum mismatch: expected {expected}, got {actual}")]
+    ChecksumMismatch { expected: String, actual: String },
+
+    #[error("Storage quota exceeded: {used} of {limit} bytes already used")]
+    QuotaExceeded { used: u64, limit: u64 },
+
     #[error("Upload failed: {reason}")]
     UploadFailed { reason: String },
 
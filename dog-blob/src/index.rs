@@ -0,0 +1,127 @@
+use crate::BlobResult;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Remembers which object key each blob was written to
+///
+/// Key strategies may build keys from upload-time hints (a content hash, a
+/// filename) or the upload date, so a blob id alone can't rebuild its key.
+/// The adapter records the key on upload and looks it up on open, head and
+/// delete. Several blobs may share one key under
+/// [`ContentHashKeyStrategy`](crate::ContentHashKeyStrategy); the index
+/// counts them so the object is only deleted, and its bytes only released
+/// from the quota, once the last one goes.
+///
+/// Implement this over a shared store (Redis, a database) when several
+/// instances serve the same tenants or blobs must outlive a restart.
+#[async_trait]
+pub trait BlobIndex: Send + Sync {
+    /// Record that the tenant's blob is stored at `key` and return how
+    /// many blobs now reference `key`. Must be atomic.
+    async fn insert(&self, tenant_id: &str, blob_id: &str, key: &str) -> BlobResult<u64>;
+
+    /// Key the tenant's blob was stored at, if it was recorded
+    async fn key(&self, tenant_id: &str, blob_id: &str) -> BlobResult<Option<String>>;
+
+    /// Forget the tenant's blob and return its key with how many blobs
+    /// still reference it. Must be atomic.
+    async fn remove(&self, tenant_id: &str, blob_id: &str) -> BlobResult<Option<(String, u64)>>;
+
+    /// Every `(blob id, key)` recorded for the tenant
+    async fn ids(&self, tenant_id: &str) -> BlobResult<Vec<(String, String)>>;
+}
+
+/// In-memory blob index provided by dog-blob
+///
+/// Entries are lost on restart; blobs it no longer knows are looked up
+/// under the key strategy's key without hints, which only finds
+/// [`DefaultKeyStrategy`](crate::DefaultKeyStrategy) blobs from the same
+/// month. It suits development and single-instance deployments.
+#[derive(Clone, Default)]
+pub struct MemoryBlobIndex {
+    inner: Arc<Mutex<IndexState>>,
+}
+
+#[derive(Default)]
+struct IndexState {
+    /// tenant → blob id → key
+    keys: HashMap<String, HashMap<String, String>>,
+    refs: HashMap<String, u64>,
+}
+
+impl MemoryBlobIndex {
+    /// Create a new in-memory blob index
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl BlobIndex for MemoryBlobIndex {
+    async fn insert(&self, tenant_id: &str, blob_id: &str, key: &str) -> BlobResult<u64> {
+        let mut state = self.inner.lock().unwrap();
+        let previous = state
+            .keys
+            .entry(tenant_id.to_string())
+            .or_default()
+            .insert(blob_id.to_string(), key.to_string());
+        if let Some(old) = previous {
+            release(&mut state.refs, &old);
+        }
+        let refs = state.refs.entry(key.to_string()).or_insert(0);
+        *refs += 1;
+        Ok(*refs)
+    }
+
+    async fn key(&self, tenant_id: &str, blob_id: &str) -> BlobResult<Option<String>> {
+        let state = self.inner.lock().unwrap();
+        Ok(state
+            .keys
+            .get(tenant_id)
+            .and_then(|blobs| blobs.get(blob_id))
+            .cloned())
+    }
+
+    async fn remove(&self, tenant_id: &str, blob_id: &str) -> BlobResult<Option<(String, u64)>> {
+        let mut state = self.inner.lock().unwrap();
+        let Some(blobs) = state.keys.get_mut(tenant_id) else {
+            return Ok(None);
+        };
+        let Some(key) = blobs.remove(blob_id) else {
+            return Ok(None);
+        };
+        if blobs.is_empty() {
+            state.keys.remove(tenant_id);
+        }
+        let remaining = release(&mut state.refs, &key);
+        Ok(Some((key, remaining)))
+    }
+
+    async fn ids(&self, tenant_id: &str) -> BlobResult<Vec<(String, String)>> {
+        let state = self.inner.lock().unwrap();
+        Ok(state
+            .keys
+            .get(tenant_id)
+            .map(|blobs| {
+                blobs
+                    .iter()
+                    .map(|(id, key)| (id.clone(), key.clone()))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+}
+
+/// Drop one reference to `key`, returning how many are left
+fn release(refs: &mut HashMap<String, u64>, key: &str) -> u64 {
+    let Some(count) = refs.get_mut(key) else {
+        return 0;
+    };
+    *count = count.saturating_sub(1);
+    let remaining = *count;
+    if remaining == 0 {
+        refs.remove(key);
+    }
+    remaining
+}
@@ -82,31 +82,45 @@
 //! ```
 
 pub mod adapter;
+mod checksum;
 mod config;
 mod coordinator;
 mod error;
+mod index;
+mod quota;
 mod receipt;
 mod s3_store;
+#[cfg(feature = "service")]
+mod service;
 mod session_store;
 pub mod store;
+pub mod tus;
 mod types;
 mod upload;
 
 // Re-export main types for clean API
 pub use adapter::BlobAdapter;
+pub use checksum::sha256_checksum;
 pub use config::{BlobConfig, UploadRules};
 pub use coordinator::DefaultUploadCoordinator;
 pub use error::{BlobError, BlobResult};
-pub use receipt::{BlobReceipt, OpenedBlob, ResolvedRange};
+pub use index::{BlobIndex, MemoryBlobIndex};
+pub use quota::{MemoryQuotaStore, QuotaStore};
+pub use receipt::{BlobReceipt, OpenedBlob, OpenedContent, ResolvedRange};
 pub use s3_store::{S3CompatibleStore, S3Config};
+#[cfg(feature = "service")]
+pub use service::BlobService;
 pub use session_store::MemoryUploadSessionStore;
 pub use store::{
-    BlobInfo, BlobKeyStrategy, BlobMetadata, BlobStore, DefaultKeyStrategy, GetResult,
-    MultipartBlobStore, ObjectHead, PutResult, SignedUrlBlobStore, StoreCapabilities,
+    BlobInfo, BlobKeyStrategy, BlobMetadata, BlobStore, ContentHashKeyStrategy,
+    DatePrefixedKeyStrategy, DefaultKeyStrategy, GetResult, MultipartBlobStore, ObjectHead,
+    PreserveFilenameKeyStrategy, PresignedGetOptions, PutResult, SignedUrlBlobStore,
+    StoreCapabilities,
 };
+pub use tus::TusUpload;
 pub use types::{
     BlobCtx, BlobId, BlobPut, ByteRange, ByteStream, ChunkResult, ChunkSession, ChunkSessionId,
-    PartReceipt, UploadId, UploadProgress, UploadSession, UploadStatus,
+    PartReceipt, UploadId, UploadProgress, UploadSession, UploadStatus, UploadStrategy,
 };
 pub use upload::{UploadCoordinator, UploadIntent, UploadSessionStore};
 
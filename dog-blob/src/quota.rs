@@ -0,0 +1,67 @@
+use crate::{BlobError, BlobResult};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Tracks how many bytes each tenant has stored
+///
+/// The adapter reserves a blob's size once it is written and releases it
+/// when the blob is deleted. Implement this over a shared store (Redis, a
+/// database) when several instances serve the same tenants.
+#[async_trait]
+pub trait QuotaStore: Send + Sync {
+    /// Bytes currently stored by the tenant
+    async fn usage(&self, tenant_id: &str) -> BlobResult<u64>;
+
+    /// Add `bytes` to the tenant's usage, unless that would take it past
+    /// `limit`, in which case fail with `BlobError::QuotaExceeded` and
+    /// leave usage unchanged. Check and add must be atomic.
+    async fn reserve(&self, tenant_id: &str, bytes: u64, limit: Option<u64>) -> BlobResult<()>;
+
+    /// Take `bytes` off the tenant's usage
+    async fn release(&self, tenant_id: &str, bytes: u64) -> BlobResult<()>;
+}
+
+/// In-memory quota store provided by dog-blob
+///
+/// Usage starts at zero and is lost on restart, so it suits development and
+/// single-instance deployments.
+#[derive(Clone, Default)]
+pub struct MemoryQuotaStore {
+    usage: Arc<Mutex<HashMap<String, u64>>>,
+}
+
+impl MemoryQuotaStore {
+    /// Create a new in-memory quota store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl QuotaStore for MemoryQuotaStore {
+    async fn usage(&self, tenant_id: &str) -> BlobResult<u64> {
+        let usage = self.usage.lock().unwrap();
+        Ok(usage.get(tenant_id).copied().unwrap_or(0))
+    }
+
+    async fn reserve(&self, tenant_id: &str, bytes: u64, limit: Option<u64>) -> BlobResult<()> {
+        let mut usage = self.usage.lock().unwrap();
+        let used = usage.entry(tenant_id.to_string()).or_insert(0);
+        if let Some(limit) = limit {
+            if used.saturating_add(bytes) > limit {
+                return Err(BlobError::QuotaExceeded { used: *used, limit });
+            }
+        }
+        *used = used.saturating_add(bytes);
+        Ok(())
+    }
+
+    async fn release(&self, tenant_id: &str, bytes: u64) -> BlobResult<()> {
+        let mut usage = self.usage.lock().unwrap();
+        if let Some(used) = usage.get_mut(tenant_id) {
+            *used = used.saturating_sub(bytes);
+        }
+        Ok(())
+    }
+}
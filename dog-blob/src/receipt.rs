@@ -142,6 +142,19 @@ impl BlobReceipt {
         self.accepts_ranges = true;
         self
     }
+
+    /// Signed-URL overrides that serve the blob as an attachment under its
+    /// original filename and content type, whatever its storage key
+    pub fn download_options(&self) -> crate::PresignedGetOptions {
+        let mut options = match &self.filename {
+            Some(filename) => crate::PresignedGetOptions::attachment(filename),
+            None => crate::PresignedGetOptions::new(),
+        };
+        if let Some(content_type) = &self.content_type {
+            options = options.with_content_type(content_type);
+        }
+        options
+    }
 }
 
 impl OpenedBlob {
@@ -1,13 +1,14 @@
 use async_trait::async_trait;
 use aws_config::{BehaviorVersion, Region};
 use aws_credential_types::Credentials;
-use aws_sdk_s3::{primitives::ByteStream as AwsByteStream, Client};
+use aws_sdk_s3::{presigning::PresigningConfig, primitives::ByteStream as AwsByteStream, Client};
 use futures::StreamExt;
 use std::env;
+use std::time::Duration;
 
 use crate::{
     BlobError, BlobInfo, BlobMetadata, BlobResult, BlobStore, ByteRange, ByteStream, GetResult,
-    ObjectHead, PutResult, StoreCapabilities,
+    ObjectHead, PresignedGetOptions, PutResult, SignedUrlBlobStore, StoreCapabilities,
 };
 
 /// S3-compatible configuration from environment variables
@@ -105,6 +106,11 @@ impl S3CompatibleStore {
         BlobError::backend(err)
     }
 
+    fn presigning(expires_in_secs: u64) -> BlobResult<PresigningConfig> {
+        PresigningConfig::expires_in(Duration::from_secs(expires_in_secs))
+            .map_err(|e| BlobError::invalid(format!("Invalid signed URL expiry: {}", e)))
+    }
+
     /// Add metadata fields to S3 put request
     pub fn add_metadata_to_request(
         mut request: aws_sdk_s3::operation::put_object::builders::PutObjectFluentBuilder,
@@ -394,4 +400,53 @@ impl BlobStore for S3CompatibleStore {
     fn capabilities(&self) -> StoreCapabilities {
         StoreCapabilities::basic().with_range().with_signed_urls()
     }
+
+    fn as_signed_url_store(&self) -> Option<&dyn SignedUrlBlobStore> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl SignedUrlBlobStore for S3CompatibleStore {
+    async fn sign_get(&self, key: &str, expires_in_secs: u64) -> BlobResult<String> {
+        self.presigned_get(key, expires_in_secs, &PresignedGetOptions::new())
+            .await
+    }
+
+    async fn sign_put(
+        &self,
+        key: &str,
+        content_type: Option<&str>,
+        expires_in_secs: u64,
+    ) -> BlobResult<String> {
+        let request = self
+            .client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .set_content_type(content_type.map(str::to_string))
+            .presigned(Self::presigning(expires_in_secs)?)
+            .await
+            .map_err(Self::map_aws_error)?;
+        Ok(request.uri().to_string())
+    }
+
+    async fn presigned_get(
+        &self,
+        key: &str,
+        expires_in_secs: u64,
+        options: &PresignedGetOptions,
+    ) -> BlobResult<String> {
+        let request = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .set_response_content_disposition(options.response_content_disposition.clone())
+            .set_response_content_type(options.response_content_type.clone())
+            .presigned(Self::presigning(expires_in_secs)?)
+            .await
+            .map_err(Self::map_aws_error)?;
+        Ok(request.uri().to_string())
+    }
 }
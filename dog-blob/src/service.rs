@@ -0,0 +1,219 @@
+//! `BlobService`: a [`BlobAdapter`] mounted as a regular DogRS service.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use dog_core::tenant::TenantContext;
+use dog_core::{DogError, DogService, ServiceCapabilities, ServiceMethodKind};
+use serde_json::{json, Value};
+
+use crate::adapter::BlobState;
+use crate::{
+    BlobAdapter, BlobConfig, BlobCtx, BlobError, BlobId, BlobInfo, BlobResult, BlobStore,
+    ByteRange, ChunkResult, OpenedBlob,
+};
+
+/// Blob storage exposed through the DogRS service pipeline, so uploads and
+/// deletes run the app's hooks and emit `created`/`removed` events:
+///
+/// - `find`   → the tenant's blobs, with their `id` when the blob index
+///   knows it
+/// - `get`    → the [`BlobReceipt`](crate::BlobReceipt) for an id
+/// - `create` → upload `data.file` (a `MultipartToJson` blob or temp file
+///   reference, or a base64 string) and return its receipt. A `blob_id`
+///   reference is only accepted once, for a blob this tenant just streamed
+///   in with [`BlobAdapter::put_streamed`]
+/// - `remove` → delete a blob and return the receipt it had
+///
+/// `get` cannot return bytes, so HTTP adapters serve content with
+/// [`BlobService::open`] after running `get` for authorization (dog-axum's
+/// `AxumApp::use_blob_service` does this, honoring `Range`).
+///
+/// ```rust,ignore
+/// let media = Arc::new(BlobService::new(store));
+/// let server = dog_axum::axum(app).use_blob_service("/media", media);
+/// ```
+pub struct BlobService {
+    adapter: BlobAdapter,
+}
+
+impl BlobService {
+    /// Serve `store` with the default [`BlobConfig`].
+    pub fn new<S: BlobStore + 'static>(store: S) -> Self {
+        Self::from_state(Arc::new(BlobState::new(store, BlobConfig::default())))
+    }
+
+    /// Share a state already used by other services or middleware.
+    pub fn from_state(state: Arc<BlobState>) -> Self {
+        Self {
+            adapter: BlobAdapter::new(state),
+        }
+    }
+
+    pub fn adapter(&self) -> &BlobAdapter {
+        &self.adapter
+    }
+
+    /// Open a blob for reading, optionally only `range` of it.
+    pub async fn open(
+        &self,
+        ctx: &TenantContext,
+        id: &str,
+        range: Option<ByteRange>,
+    ) -> BlobResult<OpenedBlob> {
+        self.adapter
+            .open(blob_ctx(ctx), BlobId::from_string(id.to_string()), range)
+            .await
+    }
+}
+
+fn blob_ctx(ctx: &TenantContext) -> BlobCtx {
+    BlobCtx::new(ctx.tenant_id.0.clone())
+}
+
+/// Store errors as the matching HTTP-ish `DogError`.
+impl From<BlobError> for DogError {
+    fn from(err: BlobError) -> Self {
+        let message = err.to_string();
+        match err {
+            BlobError::NotFound { .. } | BlobError::UploadNotFound { .. } => {
+                DogError::not_found(message)
+            }
+            BlobError::Invalid { .. } => DogError::bad_request(message),
+            BlobError::OffsetMismatch { .. } | BlobError::UploadBusy { .. } => {
+                DogError::conflict(message)
+            }
+            BlobError::PartChecksumMismatch { .. } | BlobError::ChecksumMismatch { .. } => {
+                DogError::unprocessable(message)
+            }
+            BlobError::QuotaExceeded { .. } => DogError::payload_too_large(message),
+            BlobError::Unsupported => DogError::not_implemented(message),
+            other => DogError::general_error(message).with_source(anyhow::Error::new(other)),
+        }
+    }
+}
+
+fn service_error(err: BlobError) -> anyhow::Error {
+    DogError::from(err).into_anyhow()
+}
+
+fn info_to_json(info: BlobInfo) -> Value {
+    json!({
+        "key": info.key,
+        "size_bytes": info.size_bytes,
+        "content_type": info.content_type,
+        "filename": info.filename,
+        "etag": info.etag,
+        "last_modified": info.last_modified,
+    })
+}
+
+#[async_trait]
+impl<P> DogService<Value, P> for BlobService
+where
+    P: Send + Sync + 'static,
+{
+    fn capabilities(&self) -> ServiceCapabilities {
+        use ServiceMethodKind::*;
+        ServiceCapabilities::from_methods(vec![Find, Get, Create, Remove])
+    }
+
+    async fn find(&self, ctx: &TenantContext, _params: P) -> Result<Vec<Value>> {
+        let blobs = self
+            .adapter
+            .list(blob_ctx(ctx), None, None)
+            .await
+            .map_err(service_error)?;
+        let mut ids: HashMap<String, Vec<BlobId>> = HashMap::new();
+        for (id, key) in self
+            .adapter
+            .indexed(&blob_ctx(ctx))
+            .await
+            .map_err(service_error)?
+        {
+            ids.entry(key).or_default().push(id);
+        }
+
+        // Content shared by several blobs is listed once per blob.
+        let mut found = Vec::with_capacity(blobs.len());
+        for info in blobs {
+            let blob_ids = ids.remove(&info.key).unwrap_or_default();
+            let entry = info_to_json(info);
+            if blob_ids.is_empty() {
+                found.push(entry);
+                continue;
+            }
+            for id in blob_ids {
+                let mut entry = entry.clone();
+                entry["id"] = json!(id.as_str());
+                found.push(entry);
+            }
+        }
+        Ok(found)
+    }
+
+    async fn get(&self, ctx: &TenantContext, id: &str, _params: P) -> Result<Value> {
+        let receipt = self
+            .adapter
+            .head(blob_ctx(ctx), BlobId::from_string(id.to_string()))
+            .await
+            .map_err(service_error)?;
+        Ok(serde_json::to_value(receipt)?)
+    }
+
+    async fn create(&self, ctx: &TenantContext, data: Value, _params: P) -> Result<Value> {
+        // Already streamed into this store by `MultipartToJson::stream_to_blobs`.
+        if let Some(blob_id) = data.pointer("/file/blob_id").and_then(Value::as_str) {
+            let id = BlobId::from_string(blob_id.to_string());
+            if !self.adapter.claim_streamed(&blob_ctx(ctx), &id) {
+                return Err(DogError::bad_request(
+                    "file.blob_id must name a file streamed with this request",
+                )
+                .into_anyhow());
+            }
+            let mut receipt = self
+                .adapter
+                .head(blob_ctx(ctx), id)
+                .await
+                .map_err(service_error)?;
+            if let Some(filename) = data.pointer("/file/filename").and_then(Value::as_str) {
+                receipt = receipt.with_filename(filename);
+            }
+            return Ok(serde_json::to_value(receipt)?);
+        }
+
+        match self
+            .adapter
+            .put_from_multipart(blob_ctx(ctx), &data)
+            .await
+            .map_err(service_error)?
+        {
+            ChunkResult::Complete { receipt } => Ok(serde_json::to_value(*receipt)?),
+            ChunkResult::Partial {
+                chunks_received,
+                total_chunks,
+            } => Ok(json!({
+                "status": "partial",
+                "chunks_received": chunks_received,
+                "total_chunks": total_chunks,
+            })),
+        }
+    }
+
+    async fn remove(&self, ctx: &TenantContext, id: Option<&str>, _params: P) -> Result<Value> {
+        let id = id.ok_or_else(|| DogError::bad_request("Blob id is required").into_anyhow())?;
+        let id = BlobId::from_string(id.to_string());
+        let receipt = self
+            .adapter
+            .head(blob_ctx(ctx), id.clone())
+            .await
+            .map_err(service_error)?;
+        self.adapter
+            .delete(blob_ctx(ctx), id)
+            .await
+            .map_err(service_error)?;
+        Ok(serde_json::to_value(receipt)?)
+    }
+}
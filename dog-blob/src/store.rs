@@ -87,6 +87,11 @@ pub trait BlobStore: Send + Sync {
 
     /// Get store capabilities
     fn capabilities(&self) -> StoreCapabilities;
+
+    /// This store as a [`SignedUrlBlobStore`], if it can sign URLs
+    fn as_signed_url_store(&self) -> Option<&dyn SignedUrlBlobStore> {
+        None
+    }
 }
 
 /// Optional multipart upload support
@@ -127,6 +132,94 @@ pub trait SignedUrlBlobStore: BlobStore {
         content_type: Option<&str>,
         expires_in_secs: u64,
     ) -> BlobResult<String>;
+
+    /// Generate a signed URL for reading that overrides the response's
+    /// `Content-Disposition` / `Content-Type`. Stores that can't sign the
+    /// overrides return `Unsupported` unless there are none.
+    async fn presigned_get(
+        &self,
+        key: &str,
+        expires_in_secs: u64,
+        options: &PresignedGetOptions,
+    ) -> BlobResult<String> {
+        if options.is_empty() {
+            self.sign_get(key, expires_in_secs).await
+        } else {
+            Err(crate::BlobError::Unsupported)
+        }
+    }
+}
+
+/// Response-header overrides for a signed download URL (S3's
+/// `response-content-disposition` / `response-content-type`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PresignedGetOptions {
+    pub response_content_disposition: Option<String>,
+    pub response_content_type: Option<String>,
+}
+
+impl PresignedGetOptions {
+    /// Create options with no overrides
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Download as an attachment saved under `filename`
+    pub fn attachment(filename: &str) -> Self {
+        Self::new().with_content_disposition(content_disposition("attachment", filename))
+    }
+
+    /// Display inline, saved under `filename` if the user saves it
+    pub fn inline(filename: &str) -> Self {
+        Self::new().with_content_disposition(content_disposition("inline", filename))
+    }
+
+    /// Set the `Content-Disposition` the response is served with
+    pub fn with_content_disposition<S: Into<String>>(mut self, disposition: S) -> Self {
+        self.response_content_disposition = Some(disposition.into());
+        self
+    }
+
+    /// Set the `Content-Type` the response is served with
+    pub fn with_content_type<S: Into<String>>(mut self, content_type: S) -> Self {
+        self.response_content_type = Some(content_type.into());
+        self
+    }
+
+    /// True when nothing is overridden
+    pub fn is_empty(&self) -> bool {
+        self.response_content_disposition.is_none() && self.response_content_type.is_none()
+    }
+}
+
+/// `<disposition>; filename="..."`, plus an RFC 8187 `filename*` when the
+/// name isn't plain ASCII
+fn content_disposition(disposition: &str, filename: &str) -> String {
+    let fallback: String = filename
+        .chars()
+        .map(|c| match c {
+            '"' | '\\' => '_',
+            c if c.is_ascii() && !c.is_ascii_control() => c,
+            _ => '_',
+        })
+        .collect();
+    if fallback == filename {
+        return format!("{}; filename=\"{}\"", disposition, fallback);
+    }
+
+    let encoded: String = filename
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'.' | b'-' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect();
+    format!(
+        "{}; filename=\"{}\"; filename*=UTF-8''{}",
+        disposition, fallback, encoded
+    )
 }
 
 /// Result of a successful put operation
@@ -231,6 +324,37 @@ pub trait BlobKeyStrategy: Send + Sync {
 
     /// Generate a staging key for multipart uploads
     fn staging_key(&self, tenant_id: &str, upload_id: &str, part_number: u32) -> String;
+
+    /// Key for a blob when tenants are namespaced: `namespace/tenant_id/...`.
+    /// A leading tenant segment from `object_key` isn't repeated.
+    fn namespaced_key(
+        &self,
+        namespace: &str,
+        tenant_id: &str,
+        blob_id: &str,
+        hints: &std::collections::BTreeMap<String, String>,
+    ) -> String {
+        let key = self.object_key(tenant_id, blob_id, hints);
+        let rest = key
+            .strip_prefix(tenant_id)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .unwrap_or(&key);
+        format!("{}/{}/{}", namespace, tenant_id, rest)
+    }
+}
+
+/// Key for a blob, under `namespace` when one is configured
+pub(crate) fn tenant_object_key(
+    keys: &dyn BlobKeyStrategy,
+    namespace: Option<&str>,
+    tenant_id: &str,
+    blob_id: &str,
+    hints: &std::collections::BTreeMap<String, String>,
+) -> String {
+    match namespace {
+        Some(namespace) => keys.namespaced_key(namespace, tenant_id, blob_id, hints),
+        None => keys.object_key(tenant_id, blob_id, hints),
+    }
 }
 
 /// Default key strategy: tenant/year/month/blob_id
@@ -261,13 +385,161 @@ impl BlobKeyStrategy for DefaultKeyStrategy {
     }
 
     fn derived_key(&self, original_key: &str, kind: &str) -> String {
-        format!("{}.{}", original_key, kind)
+        default_derived_key(original_key, kind)
+    }
+
+    fn staging_key(&self, tenant_id: &str, upload_id: &str, part_number: u32) -> String {
+        default_staging_key(tenant_id, upload_id, part_number)
+    }
+}
+
+fn default_derived_key(original_key: &str, kind: &str) -> String {
+    format!("{}.{}", original_key, kind)
+}
+
+fn default_staging_key(tenant_id: &str, upload_id: &str, part_number: u32) -> String {
+    format!(
+        "__uploads/{}/{}/part-{:06}",
+        tenant_id, upload_id, part_number
+    )
+}
+
+/// Content-addressed keys: tenant/sha256/<hex>
+///
+/// The hash comes from the `content_hash` hint (`sha256:<hex>` or bare
+/// hex), so identical content maps to the same key and is stored once per
+/// tenant. [`BlobAdapter`](crate::BlobAdapter) checks the body against the
+/// hint before storing it, never overwrites content other blobs share, and
+/// keeps the object until the last blob sharing it is deleted. Without the
+/// hint the key falls back to tenant/blob_id.
+#[derive(Debug, Clone)]
+pub struct ContentHashKeyStrategy;
+
+impl ContentHashKeyStrategy {
+    /// Hint carrying the content hash
+    pub const HINT: &'static str = "content_hash";
+
+    /// Key hints for `content`
+    pub fn hints(content: &[u8]) -> std::collections::BTreeMap<String, String> {
+        std::collections::BTreeMap::from([(
+            Self::HINT.to_string(),
+            crate::sha256_checksum(content),
+        )])
+    }
+}
+
+impl BlobKeyStrategy for ContentHashKeyStrategy {
+    fn object_key(
+        &self,
+        tenant_id: &str,
+        blob_id: &str,
+        hints: &std::collections::BTreeMap<String, String>,
+    ) -> String {
+        match hints
+            .get(Self::HINT)
+            .and_then(|hash| crate::checksum::parse_sha256(hash).ok())
+        {
+            Some(checksum) => format!(
+                "{}/sha256/{}",
+                tenant_id,
+                checksum.trim_start_matches("sha256:")
+            ),
+            None => format!("{}/{}", tenant_id, blob_id),
+        }
+    }
+
+    fn derived_key(&self, original_key: &str, kind: &str) -> String {
+        default_derived_key(original_key, kind)
     }
 
     fn staging_key(&self, tenant_id: &str, upload_id: &str, part_number: u32) -> String {
+        default_staging_key(tenant_id, upload_id, part_number)
+    }
+}
+
+/// Date-first keys: yyyy/mm/dd/tenant/blob_id
+///
+/// Putting the upload day first lets one bucket lifecycle rule expire or
+/// archive a day's blobs across all tenants.
+#[derive(Debug, Clone)]
+pub struct DatePrefixedKeyStrategy;
+
+impl BlobKeyStrategy for DatePrefixedKeyStrategy {
+    fn object_key(
+        &self,
+        tenant_id: &str,
+        blob_id: &str,
+        _hints: &std::collections::BTreeMap<String, String>,
+    ) -> String {
+        let now = chrono::Utc::now();
         format!(
-            "__uploads/{}/{}/part-{:06}",
-            tenant_id, upload_id, part_number
+            "{:04}/{:02}/{:02}/{}/{}",
+            now.year(),
+            now.month(),
+            now.day(),
+            tenant_id,
+            blob_id
         )
     }
+
+    fn derived_key(&self, original_key: &str, kind: &str) -> String {
+        default_derived_key(original_key, kind)
+    }
+
+    fn staging_key(&self, tenant_id: &str, upload_id: &str, part_number: u32) -> String {
+        default_staging_key(tenant_id, upload_id, part_number)
+    }
+}
+
+/// Human-friendly keys: tenant/blob_id/<filename>
+///
+/// The filename comes from the `filename` hint, reduced to its last path
+/// segment with anything outside `[A-Za-z0-9._-]` replaced by `_`. Two
+/// uploads of `report.pdf` never collide because each sits under its own
+/// blob id; the filename is for people reading the URL, not for lookup.
+/// Without the hint the key is tenant/blob_id.
+#[derive(Debug, Clone)]
+pub struct PreserveFilenameKeyStrategy;
+
+impl PreserveFilenameKeyStrategy {
+    /// Hint carrying the original filename
+    pub const HINT: &'static str = "filename";
+}
+
+impl BlobKeyStrategy for PreserveFilenameKeyStrategy {
+    fn object_key(
+        &self,
+        tenant_id: &str,
+        blob_id: &str,
+        hints: &std::collections::BTreeMap<String, String>,
+    ) -> String {
+        let filename = hints
+            .get(Self::HINT)
+            .and_then(|name| name.rsplit(['/', '\\']).next())
+            .map(|name| {
+                name.chars()
+                    .map(|c| {
+                        if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') {
+                            c
+                        } else {
+                            '_'
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .filter(|name| !name.trim_matches('.').is_empty());
+
+        match filename {
+            Some(filename) => format!("{}/{}/{}", tenant_id, blob_id, filename),
+            None => format!("{}/{}", tenant_id, blob_id),
+        }
+    }
+
+    fn derived_key(&self, original_key: &str, kind: &str) -> String {
+        default_derived_key(original_key, kind)
+    }
+
+    fn staging_key(&self, tenant_id: &str, upload_id: &str, part_number: u32) -> String {
+        default_staging_key(tenant_id, upload_id, part_number)
+    }
 }
@@ -0,0 +1,205 @@
+//! tus resumable uploads (core protocol plus the `creation` extension)
+//! on top of the upload coordinator.
+//!
+//! Each `PATCH` becomes one part of a multipart upload and the upload's
+//! offset is the bytes those parts hold, so a chunk cut off mid-transfer
+//! leaves the offset where it was and the client resends it. Chunks come in
+//! whatever size the client picks, so the coordinator's [`UploadRules`]
+//! must allow variable part sizes.
+//!
+//! The wire format (headers, status codes) belongs to the HTTP adapter;
+//! dog-axum serves it with `AxumApp::use_tus`.
+//!
+//! [`UploadRules`]: crate::UploadRules
+
+use std::collections::BTreeMap;
+
+use base64::Engine;
+use futures_util::StreamExt;
+
+use crate::{
+    BlobAdapter, BlobCtx, BlobError, BlobPut, BlobReceipt, BlobResult, ByteStream, UploadId,
+    UploadSession, UploadStatus,
+};
+
+/// The only protocol version served (`Tus-Resumable` / `Tus-Version`).
+pub const TUS_VERSION: &str = "1.0.0";
+
+/// Extensions advertised in `Tus-Extension`.
+pub const TUS_EXTENSIONS: &str = "creation";
+
+/// Where a tus upload stands.
+#[derive(Debug, Clone)]
+pub struct TusUpload {
+    pub upload_id: UploadId,
+    /// Bytes received so far (`Upload-Offset`).
+    pub offset: u64,
+    /// Declared total size (`Upload-Length`).
+    pub length: u64,
+    /// Set by the append that received the last byte.
+    pub receipt: Option<BlobReceipt>,
+}
+
+impl TusUpload {
+    fn from_session(session: &UploadSession) -> Self {
+        let length = session.size_hint.unwrap_or(0);
+        let offset = match session.status {
+            UploadStatus::Completed { .. } => length,
+            _ => session.progress.received_bytes,
+        };
+        Self {
+            upload_id: session.upload_id.clone(),
+            offset,
+            length,
+            receipt: None,
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.offset == self.length
+    }
+}
+
+/// Parse an `Upload-Metadata` header: comma-separated `key base64value`
+/// pairs, where the value may be left out.
+pub fn parse_upload_metadata(header: &str) -> BlobResult<BTreeMap<String, String>> {
+    let mut metadata = BTreeMap::new();
+    for pair in header.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let mut parts = pair.splitn(2, ' ');
+        let key = parts.next().unwrap_or_default();
+        let value = match parts.next().map(str::trim) {
+            Some(encoded) if !encoded.is_empty() => {
+                let bytes = base64::engine::general_purpose::STANDARD
+                    .decode(encoded)
+                    .map_err(|e| BlobError::invalid(format!("Invalid metadata '{key}': {e}")))?;
+                String::from_utf8(bytes)
+                    .map_err(|e| BlobError::invalid(format!("Invalid metadata '{key}': {e}")))?
+            }
+            _ => String::new(),
+        };
+        metadata.insert(key.to_string(), value);
+    }
+    Ok(metadata)
+}
+
+/// Fail the stream once it carries more than `limit` bytes.
+fn limit_stream(mut body: ByteStream, limit: u64) -> ByteStream {
+    Box::pin(async_stream::stream! {
+        let mut seen = 0u64;
+        while let Some(chunk) = body.next().await {
+            if let Ok(bytes) = &chunk {
+                seen += bytes.len() as u64;
+                if seen > limit {
+                    yield Err(std::io::Error::other(format!(
+                        "Chunk runs past the declared Upload-Length ({limit} bytes left)"
+                    )));
+                    return;
+                }
+            }
+            yield chunk;
+        }
+    })
+}
+
+impl BlobAdapter {
+    /// Start a tus upload of `length` bytes. `filename` and `filetype`
+    /// (or `content_type`) from `metadata` are kept for the final blob.
+    pub async fn tus_create(
+        &self,
+        ctx: BlobCtx,
+        length: u64,
+        metadata: &BTreeMap<String, String>,
+    ) -> BlobResult<TusUpload> {
+        if length == 0 {
+            return Err(BlobError::invalid("Upload-Length must be greater than 0"));
+        }
+        if length > self.config().max_blob_bytes {
+            return Err(BlobError::invalid(format!(
+                "Upload-Length {} exceeds maximum {}",
+                length,
+                self.config().max_blob_bytes
+            )));
+        }
+
+        let mut put = BlobPut::new().with_size_hint(length);
+        if let Some(filename) = metadata.get("filename") {
+            put = put.with_filename(filename);
+        }
+        if let Some(content_type) = metadata
+            .get("filetype")
+            .or_else(|| metadata.get("content_type"))
+        {
+            put = put.with_content_type(content_type);
+        }
+
+        let session = self.begin_multipart(ctx, put).await?;
+        Ok(TusUpload::from_session(&session))
+    }
+
+    /// Current offset of an upload (`HEAD`).
+    pub async fn tus_status(&self, ctx: BlobCtx, upload_id: UploadId) -> BlobResult<TusUpload> {
+        let session = self.tus_session(ctx, upload_id).await?;
+        Ok(TusUpload::from_session(&session))
+    }
+
+    /// Append `body` at `offset` (`PATCH`). `offset` must equal the current
+    /// offset; the append that reaches `Upload-Length` assembles the blob.
+    ///
+    /// One append runs per upload at a time: a second one that arrives
+    /// while the first is still receiving fails with
+    /// `BlobError::UploadBusy` instead of writing the same part. The hold is
+    /// per adapter, so instances sharing a session store should route an
+    /// upload's requests to one of them.
+    pub async fn tus_append(
+        &self,
+        ctx: BlobCtx,
+        upload_id: UploadId,
+        offset: u64,
+        body: ByteStream,
+    ) -> BlobResult<TusUpload> {
+        let Some(_claim) = self.claim_append(&upload_id) else {
+            return Err(BlobError::UploadBusy {
+                upload_id: upload_id.to_string(),
+            });
+        };
+
+        let session = self.tus_session(ctx.clone(), upload_id.clone()).await?;
+        if !matches!(session.status, UploadStatus::Active) {
+            return Err(BlobError::invalid("Upload is no longer active"));
+        }
+
+        let mut upload = TusUpload::from_session(&session);
+        if offset != upload.offset {
+            return Err(BlobError::OffsetMismatch {
+                expected: upload.offset,
+                actual: offset,
+            });
+        }
+
+        let part_number = session.progress.parts.len() as u32 + 1;
+        let body = limit_stream(body, upload.length - upload.offset);
+        let part = self
+            .upload_part(ctx.clone(), upload_id.clone(), part_number, body)
+            .await?;
+        upload.offset += part.size_bytes;
+
+        if upload.is_complete() {
+            // The part count was estimated from Upload-Length; it is
+            // however many chunks the client actually sent.
+            self.set_total_parts(ctx.clone(), upload_id.clone(), part_number)
+                .await?;
+            upload.receipt = Some(self.complete_multipart(ctx, upload_id).await?);
+        }
+        Ok(upload)
+    }
+
+    /// The session behind a tus upload, hidden from other tenants.
+    async fn tus_session(&self, ctx: BlobCtx, upload_id: UploadId) -> BlobResult<UploadSession> {
+        let tenant_id = ctx.tenant_id.clone();
+        let session = self.get_upload_session(ctx, upload_id.clone()).await?;
+        if session.tenant_id != tenant_id {
+            return Err(BlobError::upload_not_found(upload_id.as_str()));
+        }
+        Ok(session)
+    }
+}
@@ -218,6 +218,17 @@ pub enum UploadStatus {
     Failed { failed_at: i64, reason: String },
 }
 
+/// Where an upload keeps its parts until it completes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UploadStrategy {
+    /// Parts are staged as objects in the store (stores with multipart support)
+    #[default]
+    Staged,
+    /// Parts are buffered in local temp storage and written with a single
+    /// `put` on completion (stores without multipart support)
+    LocalBuffer,
+}
+
 /// Upload session state
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UploadSession {
@@ -231,11 +242,16 @@ pub struct UploadSession {
 
     pub total_parts: Option<u32>,
     pub status: UploadStatus,
+    #[serde(default)]
+    pub strategy: UploadStrategy,
 
     pub content_type: String,
     pub filename: Option<String>,
     pub size_hint: Option<u64>,
     pub attributes: serde_json::Value,
+    /// Object key the blob will be written to, chosen when the upload began
+    #[serde(default)]
+    pub key: Option<String>,
 
     pub progress: UploadProgress,
 }
@@ -16,6 +16,21 @@ pub trait UploadCoordinator: Send + Sync {
         body: ByteStream,
     ) -> BlobResult<PartReceipt>;
 
+    /// Accept a part and verify it against the client's `checksum`
+    /// (`sha256:<hex>`). A mismatching part is discarded with
+    /// `BlobError::PartChecksumMismatch`, so only that part needs resending.
+    async fn accept_part_with_checksum(
+        &self,
+        ctx: BlobCtx,
+        upload_id: &UploadId,
+        part_number: u32,
+        body: ByteStream,
+        checksum: &str,
+    ) -> BlobResult<PartReceipt> {
+        let _ = (ctx, upload_id, part_number, body, checksum);
+        Err(crate::BlobError::Unsupported)
+    }
+
     /// Set total parts (optional, can be done later)
     async fn set_total_parts(
         &self,
@@ -27,6 +42,18 @@ pub trait UploadCoordinator: Send + Sync {
     /// Complete the upload and return final blob receipt
     async fn complete(&self, ctx: BlobCtx, upload_id: &UploadId) -> BlobResult<crate::BlobReceipt>;
 
+    /// Complete the upload, failing with `BlobError::ChecksumMismatch` if the
+    /// assembled blob does not match `checksum` (`sha256:<hex>`)
+    async fn complete_with_checksum(
+        &self,
+        ctx: BlobCtx,
+        upload_id: &UploadId,
+        checksum: &str,
+    ) -> BlobResult<crate::BlobReceipt> {
+        let _ = (ctx, upload_id, checksum);
+        Err(crate::BlobError::Unsupported)
+    }
+
     /// Abort the upload and cleanup
     async fn abort(&self, ctx: BlobCtx, upload_id: &UploadId) -> BlobResult<()>;
 
@@ -0,0 +1,429 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+
+use bytes::Bytes;
+use dog_blob::adapter::BlobState;
+use dog_blob::{
+    BlobAdapter, BlobConfig, BlobCtx, BlobError, BlobId, BlobKeyStrategy, BlobPut, BlobResult,
+    BlobStore, ByteRange, ByteStream, ContentHashKeyStrategy, DatePrefixedKeyStrategy,
+    DefaultUploadCoordinator, GetResult, MemoryUploadSessionStore, ObjectHead, OpenedContent,
+    PreserveFilenameKeyStrategy, PutResult, StoreCapabilities, UploadRules,
+};
+use futures::StreamExt;
+
+#[derive(Clone, Default)]
+struct MemoryStore(Arc<Mutex<HashMap<String, Bytes>>>);
+
+impl MemoryStore {
+    fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.0.lock().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+}
+
+#[async_trait::async_trait]
+impl BlobStore for MemoryStore {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    async fn put(
+        &self,
+        key: &str,
+        _content_type: Option<&str>,
+        mut stream: ByteStream,
+    ) -> BlobResult<PutResult> {
+        let mut data = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            data.extend_from_slice(&chunk?);
+        }
+        let size_bytes = data.len() as u64;
+        self.0
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), Bytes::from(data));
+        Ok(PutResult {
+            etag: None,
+            size_bytes,
+            checksum: None,
+        })
+    }
+
+    async fn get(&self, key: &str, _range: Option<ByteRange>) -> BlobResult<GetResult> {
+        let data = self
+            .0
+            .lock()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .ok_or_else(|| BlobError::not_found(key))?;
+        Ok(GetResult {
+            size_bytes: data.len() as u64,
+            stream: Box::pin(futures::stream::once(async move { Ok(data) })),
+            content_type: None,
+            etag: None,
+            resolved_range: None,
+        })
+    }
+
+    async fn head(&self, key: &str) -> BlobResult<ObjectHead> {
+        let store = self.0.lock().unwrap();
+        let data = store.get(key).ok_or_else(|| BlobError::not_found(key))?;
+        Ok(ObjectHead {
+            size_bytes: data.len() as u64,
+            content_type: None,
+            etag: None,
+            last_modified: None,
+        })
+    }
+
+    async fn delete(&self, key: &str) -> BlobResult<()> {
+        self.0.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    fn capabilities(&self) -> StoreCapabilities {
+        StoreCapabilities::basic()
+    }
+}
+
+/// Date-prefixed keys where every call is a new day
+struct EveryCallANewDay(AtomicU32);
+
+impl BlobKeyStrategy for EveryCallANewDay {
+    fn object_key(
+        &self,
+        tenant_id: &str,
+        blob_id: &str,
+        _hints: &BTreeMap<String, String>,
+    ) -> String {
+        let day = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+        format!("2024/01/{:02}/{}/{}", day, tenant_id, blob_id)
+    }
+
+    fn derived_key(&self, original_key: &str, kind: &str) -> String {
+        DatePrefixedKeyStrategy.derived_key(original_key, kind)
+    }
+
+    fn staging_key(&self, tenant_id: &str, upload_id: &str, part_number: u32) -> String {
+        DatePrefixedKeyStrategy.staging_key(tenant_id, upload_id, part_number)
+    }
+}
+
+fn adapter<K: BlobKeyStrategy + 'static>(store: &MemoryStore, keys: K) -> BlobAdapter {
+    BlobAdapter::new(Arc::new(BlobState::with_key_strategy(
+        store.clone(),
+        keys,
+        BlobConfig::default(),
+    )))
+}
+
+fn body(data: &'static [u8]) -> ByteStream {
+    Box::pin(futures::stream::once(async move {
+        Ok(Bytes::from_static(data))
+    }))
+}
+
+async fn read_all(adapter: &BlobAdapter, ctx: BlobCtx, id: BlobId) -> BlobResult<Vec<u8>> {
+    let opened = adapter.open(ctx, id, None).await?;
+    let OpenedContent::Stream { mut stream, .. } = opened.content else {
+        panic!("expected a stream");
+    };
+    let mut content = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        content.extend_from_slice(&chunk?);
+    }
+    Ok(content)
+}
+
+/// put → open → head → delete, checking the blob can't be read afterwards
+async fn round_trip(adapter: &BlobAdapter, store: &MemoryStore, put: BlobPut) -> String {
+    let acme = BlobCtx::new("acme".to_string());
+    let receipt = adapter
+        .put(acme.clone(), put, body(b"hello"))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        read_all(adapter, acme.clone(), receipt.id.clone())
+            .await
+            .unwrap(),
+        b"hello"
+    );
+    let head = adapter
+        .head(acme.clone(), receipt.id.clone())
+        .await
+        .unwrap();
+    assert_eq!(head.key, receipt.key);
+    assert_eq!(head.size_bytes, 5);
+
+    adapter
+        .delete(acme.clone(), receipt.id.clone())
+        .await
+        .unwrap();
+    assert!(store.keys().is_empty());
+    assert!(matches!(
+        adapter.head(acme, receipt.id).await,
+        Err(BlobError::NotFound { .. })
+    ));
+    receipt.key
+}
+
+fn filename(name: &str) -> BTreeMap<String, String> {
+    BTreeMap::from([(
+        PreserveFilenameKeyStrategy::HINT.to_string(),
+        name.to_string(),
+    )])
+}
+
+#[test]
+fn content_hash_keys_are_stable_for_identical_content() {
+    let keys = ContentHashKeyStrategy;
+    let first = keys.object_key("acme", "blob-1", &ContentHashKeyStrategy::hints(b"hello"));
+    let second = keys.object_key("acme", "blob-2", &ContentHashKeyStrategy::hints(b"hello"));
+    let other = keys.object_key("acme", "blob-3", &ContentHashKeyStrategy::hints(b"hullo"));
+
+    assert_eq!(
+        first,
+        "acme/sha256/2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+    );
+    assert_eq!(first, second);
+    assert_ne!(first, other);
+    assert_ne!(
+        first,
+        keys.object_key("globex", "blob-1", &ContentHashKeyStrategy::hints(b"hello"))
+    );
+    assert_eq!(
+        keys.object_key("acme", "blob-1", &BTreeMap::new()),
+        "acme/blob-1"
+    );
+}
+
+#[test]
+fn date_prefixed_keys_start_with_the_day() {
+    let key = DatePrefixedKeyStrategy.object_key("acme", "blob-1", &BTreeMap::new());
+    let segments: Vec<&str> = key.split('/').collect();
+
+    assert_eq!(segments.len(), 5);
+    for (segment, len) in segments[..3].iter().zip([4, 2, 2]) {
+        assert_eq!(segment.len(), len);
+        assert!(segment.chars().all(|c| c.is_ascii_digit()));
+    }
+    assert_eq!(segments[3..], ["acme", "blob-1"]);
+}
+
+#[test]
+fn filename_keys_keep_a_safe_filename_under_the_blob_id() {
+    let keys = PreserveFilenameKeyStrategy;
+
+    assert_eq!(
+        keys.object_key("acme", "blob-1", &filename("report.pdf")),
+        "acme/blob-1/report.pdf"
+    );
+    // Same name, different blob: no collision.
+    assert_eq!(
+        keys.object_key("acme", "blob-2", &filename("report.pdf")),
+        "acme/blob-2/report.pdf"
+    );
+    assert_eq!(
+        keys.object_key("acme", "blob-1", &filename("../../etc/my song?.mp3")),
+        "acme/blob-1/my_song_.mp3"
+    );
+    assert_eq!(
+        keys.object_key("acme", "blob-1", &filename("..")),
+        "acme/blob-1"
+    );
+    assert_eq!(
+        keys.object_key("acme", "blob-1", &BTreeMap::new()),
+        "acme/blob-1"
+    );
+}
+
+#[tokio::test]
+async fn content_hash_blobs_round_trip_through_the_adapter() {
+    let store = MemoryStore::default();
+    let adapter = adapter(&store, ContentHashKeyStrategy);
+    let key = round_trip(
+        &adapter,
+        &store,
+        BlobPut::new().with_key_hint(ContentHashKeyStrategy::HINT, sha256_of_hello()),
+    )
+    .await;
+    assert!(key.starts_with("acme/sha256/"));
+}
+
+#[tokio::test]
+async fn date_prefixed_blobs_are_found_after_the_day_changes() {
+    let store = MemoryStore::default();
+    round_trip(
+        &adapter(&store, DatePrefixedKeyStrategy),
+        &store,
+        BlobPut::new(),
+    )
+    .await;
+
+    let store = MemoryStore::default();
+    let key = round_trip(
+        &adapter(&store, EveryCallANewDay(AtomicU32::new(0))),
+        &store,
+        BlobPut::new(),
+    )
+    .await;
+    assert!(key.starts_with("2024/01/01/acme/"));
+}
+
+#[tokio::test]
+async fn filename_blobs_round_trip_through_the_adapter() {
+    let store = MemoryStore::default();
+    let adapter = adapter(&store, PreserveFilenameKeyStrategy);
+    let key = round_trip(
+        &adapter,
+        &store,
+        BlobPut::new().with_key_hint(PreserveFilenameKeyStrategy::HINT, "hello.txt"),
+    )
+    .await;
+    assert!(key.ends_with("/hello.txt"));
+}
+
+#[tokio::test]
+async fn shared_content_is_stored_and_counted_once() {
+    let store = MemoryStore::default();
+    let adapter = adapter(&store, ContentHashKeyStrategy);
+    let acme = BlobCtx::new("acme".to_string());
+    let put = || BlobPut::new().with_key_hint(ContentHashKeyStrategy::HINT, sha256_of_hello());
+
+    let first = adapter
+        .put(acme.clone(), put(), body(b"hello"))
+        .await
+        .unwrap();
+    let second = adapter
+        .put(acme.clone(), put(), body(b"hello"))
+        .await
+        .unwrap();
+    assert_eq!(first.key, second.key);
+    assert_eq!(store.keys(), vec![first.key.clone()]);
+    assert_eq!(adapter.quota_usage(&acme).await.unwrap(), 5);
+
+    // Deleting one blob leaves the content for the other.
+    adapter
+        .delete(acme.clone(), first.id.clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        read_all(&adapter, acme.clone(), second.id.clone())
+            .await
+            .unwrap(),
+        b"hello"
+    );
+    assert!(read_all(&adapter, acme.clone(), first.id).await.is_err());
+    assert_eq!(adapter.quota_usage(&acme).await.unwrap(), 5);
+
+    adapter.delete(acme.clone(), second.id).await.unwrap();
+    assert!(store.keys().is_empty());
+    assert_eq!(adapter.quota_usage(&acme).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn rejected_duplicate_keeps_the_shared_content() {
+    let store = MemoryStore::default();
+    let config = BlobConfig::default().with_tenant_quota("acme", 5);
+    let adapter = BlobAdapter::new(Arc::new(BlobState::with_key_strategy(
+        store.clone(),
+        ContentHashKeyStrategy,
+        config,
+    )));
+    let acme = BlobCtx::new("acme".to_string());
+    let hello = || BlobPut::new().with_key_hint(ContentHashKeyStrategy::HINT, sha256_of_hello());
+
+    let first = adapter
+        .put(acme.clone(), hello(), body(b"hello"))
+        .await
+        .unwrap();
+    // Same content again fits: it takes no extra space.
+    adapter
+        .put(acme.clone(), hello(), body(b"hello"))
+        .await
+        .unwrap();
+    assert!(matches!(
+        adapter.put(acme.clone(), BlobPut::new(), body(b"!")).await,
+        Err(BlobError::QuotaExceeded { .. })
+    ));
+    assert_eq!(read_all(&adapter, acme, first.id).await.unwrap(), b"hello");
+}
+
+#[tokio::test]
+async fn mismatched_body_under_a_shared_hash_is_rejected() {
+    let store = MemoryStore::default();
+    let adapter = adapter(&store, ContentHashKeyStrategy);
+    let acme = BlobCtx::new("acme".to_string());
+    let hello = || BlobPut::new().with_key_hint(ContentHashKeyStrategy::HINT, sha256_of_hello());
+
+    let first = adapter
+        .put(acme.clone(), hello(), body(b"hello"))
+        .await
+        .unwrap();
+    assert!(matches!(
+        adapter.put(acme.clone(), hello(), body(b"HACKED!")).await,
+        Err(BlobError::ChecksumMismatch { .. })
+    ));
+
+    assert_eq!(store.keys(), vec![first.key.clone()]);
+    assert_eq!(adapter.quota_usage(&acme).await.unwrap(), 5);
+    assert_eq!(read_all(&adapter, acme, first.id).await.unwrap(), b"hello");
+}
+
+#[tokio::test]
+async fn multipart_uploads_keep_the_key_chosen_at_begin() {
+    let store = MemoryStore::default();
+    let config = BlobConfig::default().with_upload_rules(UploadRules::new().with_part_size(4));
+    let buffer_dir = std::env::temp_dir().join(format!("dog_blob_test_{}", uuid::Uuid::new_v4()));
+    let coordinator = DefaultUploadCoordinator::new(
+        store.clone(),
+        MemoryUploadSessionStore::new(),
+        PreserveFilenameKeyStrategy,
+        config.clone(),
+    )
+    .with_buffer_dir(&buffer_dir);
+    let adapter = BlobAdapter::new(Arc::new(
+        BlobState::with_key_strategy(store.clone(), PreserveFilenameKeyStrategy, config)
+            .with_uploads(coordinator),
+    ));
+    let acme = BlobCtx::new("acme".to_string());
+
+    let session = adapter
+        .begin_multipart(
+            acme.clone(),
+            BlobPut::new().with_key_hint(PreserveFilenameKeyStrategy::HINT, "song.mp3"),
+        )
+        .await
+        .unwrap();
+    let upload = session.upload_id;
+    adapter
+        .upload_part(acme.clone(), upload.clone(), 1, body(b"0123"))
+        .await
+        .unwrap();
+    adapter
+        .upload_part(acme.clone(), upload.clone(), 2, body(b"45"))
+        .await
+        .unwrap();
+    let receipt = adapter
+        .complete_multipart(acme.clone(), upload)
+        .await
+        .unwrap();
+
+    assert!(receipt.key.ends_with("/song.mp3"));
+    assert_eq!(
+        read_all(&adapter, acme.clone(), receipt.id.clone())
+            .await
+            .unwrap(),
+        b"012345"
+    );
+    adapter.delete(acme, receipt.id).await.unwrap();
+    assert!(store.keys().is_empty());
+}
+
+fn sha256_of_hello() -> String {
+    dog_blob::sha256_checksum(b"hello")
+}
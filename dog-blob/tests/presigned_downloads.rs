@@ -0,0 +1,50 @@
+use dog_blob::{
+    BlobId, BlobReceipt, PresignedGetOptions, S3CompatibleStore, S3Config, SignedUrlBlobStore,
+};
+
+async fn store() -> S3CompatibleStore {
+    S3CompatibleStore::with_config(
+        "media".to_string(),
+        S3Config {
+            region: "us-east-1".to_string(),
+            access_key_id: "test-access-key".to_string(),
+            secret_access_key: "test-secret-key".to_string(),
+            endpoint_url: "http://localhost:9000".to_string(),
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn receipt_overrides_are_signed_into_the_url() {
+    let store = store().await;
+    let receipt = BlobReceipt::new(BlobId::new(), "acme/abc123".to_string(), 42)
+        .with_filename("invoice 2024.pdf".to_string())
+        .with_content_type("application/pdf".to_string());
+
+    let url = store
+        .presigned_get(&receipt.key, 300, &receipt.download_options())
+        .await
+        .unwrap();
+
+    assert!(url.starts_with("http://localhost:9000/media/acme/abc123?"));
+    assert!(url.contains(
+        "response-content-disposition=attachment%3B%20filename%3D%22invoice%202024.pdf%22"
+    ));
+    assert!(url.contains("response-content-type=application%2Fpdf"));
+    assert!(url.contains("X-Amz-Expires=300"));
+}
+
+#[tokio::test]
+async fn plain_signed_urls_carry_no_overrides() {
+    let store = store().await;
+
+    let url = store.sign_get("acme/abc123", 60).await.unwrap();
+    assert!(!url.contains("response-content-"));
+
+    let inline = PresignedGetOptions::inline("résumé.pdf");
+    assert_eq!(
+        inline.response_content_disposition.as_deref(),
+        Some("inline; filename=\"r_sum_.pdf\"; filename*=UTF-8''r%C3%A9sum%C3%A9.pdf")
+    );
+}
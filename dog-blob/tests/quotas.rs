@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use bytes::Bytes;
+use dog_blob::adapter::BlobState;
+use dog_blob::{
+    BlobAdapter, BlobConfig, BlobCtx, BlobError, BlobPut, BlobResult, BlobStore, ByteRange,
+    ByteStream, GetResult, ObjectHead, PutResult, StoreCapabilities,
+};
+use futures::StreamExt;
+
+#[derive(Clone, Default)]
+struct MemoryStore(Arc<Mutex<HashMap<String, Bytes>>>);
+
+#[async_trait::async_trait]
+impl BlobStore for MemoryStore {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    async fn put(
+        &self,
+        key: &str,
+        _content_type: Option<&str>,
+        mut stream: ByteStream,
+    ) -> BlobResult<PutResult> {
+        let mut data = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            data.extend_from_slice(&chunk?);
+        }
+        let size_bytes = data.len() as u64;
+        self.0
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), Bytes::from(data));
+        Ok(PutResult {
+            etag: None,
+            size_bytes,
+            checksum: None,
+        })
+    }
+
+    async fn get(&self, key: &str, _range: Option<ByteRange>) -> BlobResult<GetResult> {
+        let data = self
+            .0
+            .lock()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .ok_or_else(|| BlobError::not_found(key))?;
+        Ok(GetResult {
+            size_bytes: data.len() as u64,
+            stream: Box::pin(futures::stream::once(async move { Ok(data) })),
+            content_type: None,
+            etag: None,
+            resolved_range: None,
+        })
+    }
+
+    async fn head(&self, key: &str) -> BlobResult<ObjectHead> {
+        let store = self.0.lock().unwrap();
+        let data = store.get(key).ok_or_else(|| BlobError::not_found(key))?;
+        Ok(ObjectHead {
+            size_bytes: data.len() as u64,
+            content_type: None,
+            etag: None,
+            last_modified: None,
+        })
+    }
+
+    async fn delete(&self, key: &str) -> BlobResult<()> {
+        self.0.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    fn capabilities(&self) -> StoreCapabilities {
+        StoreCapabilities::basic()
+    }
+}
+
+fn body(data: &'static str) -> ByteStream {
+    Box::pin(futures::stream::once(async move {
+        Ok(Bytes::from_static(data.as_bytes()))
+    }))
+}
+
+#[tokio::test]
+async fn uploads_past_the_tenant_quota_are_rejected() {
+    let store = MemoryStore::default();
+    let config = BlobConfig::default().with_tenant_quota("acme", 10);
+    let adapter = BlobAdapter::new(Arc::new(BlobState::new(store.clone(), config)));
+    let acme = BlobCtx::new("acme".to_string());
+
+    let first = adapter
+        .put(acme.clone(), BlobPut::new(), body("012345"))
+        .await
+        .unwrap();
+
+    let err = adapter
+        .put(acme.clone(), BlobPut::new(), body("678901"))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        BlobError::QuotaExceeded { used: 6, limit: 10 }
+    ));
+    assert_eq!(store.0.lock().unwrap().len(), 1);
+
+    // Declared sizes are turned away before any bytes are stored.
+    let err = adapter
+        .put(
+            acme.clone(),
+            BlobPut::new().with_size_hint(6),
+            body("678901"),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, BlobError::QuotaExceeded { .. }));
+
+    adapter
+        .put(acme.clone(), BlobPut::new(), body("6789"))
+        .await
+        .unwrap();
+    assert_eq!(adapter.quota_usage(&acme).await.unwrap(), 10);
+
+    // Other tenants have no quota configured.
+    let globex = BlobCtx::new("globex".to_string());
+    adapter
+        .put(globex, BlobPut::new(), body("0123456789ab"))
+        .await
+        .unwrap();
+
+    adapter.delete(acme.clone(), first.id).await.unwrap();
+    assert_eq!(adapter.quota_usage(&acme).await.unwrap(), 4);
+    adapter
+        .put(acme, BlobPut::new(), body("012345"))
+        .await
+        .unwrap();
+}
@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use bytes::Bytes;
+use dog_blob::adapter::BlobState;
+use dog_blob::{
+    sha256_checksum, BlobAdapter, BlobConfig, BlobCtx, BlobError, BlobPut, BlobResult, BlobStore,
+    ByteRange, ByteStream, DefaultKeyStrategy, DefaultUploadCoordinator, GetResult,
+    MemoryUploadSessionStore, ObjectHead, OpenedContent, PutResult, StoreCapabilities, UploadRules,
+    UploadStrategy,
+};
+use futures::StreamExt;
+
+/// In-memory store that records every `put`.
+#[derive(Clone)]
+struct MemoryStore {
+    objects: Arc<Mutex<HashMap<String, Bytes>>>,
+    puts: Arc<Mutex<Vec<String>>>,
+    capabilities: StoreCapabilities,
+}
+
+impl MemoryStore {
+    fn new(capabilities: StoreCapabilities) -> Self {
+        Self {
+            objects: Arc::default(),
+            puts: Arc::default(),
+            capabilities,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BlobStore for MemoryStore {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    async fn put(
+        &self,
+        key: &str,
+        _content_type: Option<&str>,
+        mut stream: ByteStream,
+    ) -> BlobResult<PutResult> {
+        let mut data = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            data.extend_from_slice(&chunk?);
+        }
+        let size_bytes = data.len() as u64;
+        self.puts.lock().unwrap().push(key.to_string());
+        self.objects
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), Bytes::from(data));
+        Ok(PutResult {
+            etag: None,
+            size_bytes,
+            checksum: None,
+        })
+    }
+
+    async fn get(&self, key: &str, _range: Option<ByteRange>) -> BlobResult<GetResult> {
+        let data = self
+            .objects
+            .lock()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .ok_or_else(|| BlobError::not_found(key))?;
+        Ok(GetResult {
+            size_bytes: data.len() as u64,
+            stream: Box::pin(futures::stream::once(async move { Ok(data) })),
+            content_type: None,
+            etag: None,
+            resolved_range: None,
+        })
+    }
+
+    async fn head(&self, key: &str) -> BlobResult<ObjectHead> {
+        let objects = self.objects.lock().unwrap();
+        let data = objects.get(key).ok_or_else(|| BlobError::not_found(key))?;
+        Ok(ObjectHead {
+            size_bytes: data.len() as u64,
+            content_type: None,
+            etag: None,
+            last_modified: None,
+        })
+    }
+
+    async fn delete(&self, key: &str) -> BlobResult<()> {
+        self.objects.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    fn capabilities(&self) -> StoreCapabilities {
+        self.capabilities.clone()
+    }
+}
+
+fn adapter(store: &MemoryStore, buffer_dir: &std::path::Path) -> BlobAdapter {
+    let config = BlobConfig::default().with_upload_rules(UploadRules::new().with_part_size(4));
+    let coordinator = DefaultUploadCoordinator::new(
+        store.clone(),
+        MemoryUploadSessionStore::new(),
+        DefaultKeyStrategy,
+        config.clone(),
+    )
+    .with_buffer_dir(buffer_dir);
+    let state = BlobState::new(store.clone(), config).with_uploads(coordinator);
+    BlobAdapter::new(Arc::new(state))
+}
+
+fn body(data: &'static str) -> ByteStream {
+    Box::pin(futures::stream::once(async move {
+        Ok(Bytes::from_static(data.as_bytes()))
+    }))
+}
+
+async fn read_all(adapter: &BlobAdapter, ctx: BlobCtx, id: dog_blob::BlobId) -> Vec<u8> {
+    let opened = adapter.open(ctx, id, None).await.unwrap();
+    let OpenedContent::Stream { mut stream, .. } = opened.content else {
+        panic!("expected a stream");
+    };
+    let mut content = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        content.extend_from_slice(&chunk.unwrap());
+    }
+    content
+}
+
+#[tokio::test]
+async fn stores_without_multipart_buffer_parts_locally() {
+    let store = MemoryStore::new(StoreCapabilities::basic());
+    let buffer_dir = std::env::temp_dir().join(format!("dog_blob_test_{}", uuid::Uuid::new_v4()));
+    let adapter = adapter(&store, &buffer_dir);
+    let ctx = BlobCtx::new("acme".to_string());
+
+    let session = adapter
+        .begin_multipart(ctx.clone(), BlobPut::new().with_size_hint(10))
+        .await
+        .unwrap();
+    assert_eq!(session.strategy, UploadStrategy::LocalBuffer);
+    let id = session.upload_id.clone();
+
+    adapter
+        .upload_part(ctx.clone(), id.clone(), 1, body("0123"))
+        .await
+        .unwrap();
+
+    // Part 2 is cut off mid-transfer and sent again.
+    let cut_off: ByteStream = Box::pin(futures::stream::iter(vec![
+        Ok(Bytes::from_static(b"45")),
+        Err(std::io::Error::other("connection reset")),
+    ]));
+    assert!(adapter
+        .upload_part(ctx.clone(), id.clone(), 2, cut_off)
+        .await
+        .is_err());
+    let session = adapter
+        .get_upload_session(ctx.clone(), id.clone())
+        .await
+        .unwrap();
+    assert_eq!(session.progress.received_bytes, 4);
+
+    adapter
+        .upload_part(ctx.clone(), id.clone(), 2, body("4567"))
+        .await
+        .unwrap();
+    adapter
+        .upload_part(ctx.clone(), id.clone(), 3, body("89"))
+        .await
+        .unwrap();
+    assert!(store.puts.lock().unwrap().is_empty());
+
+    let receipt = adapter
+        .complete_multipart(ctx.clone(), id.clone())
+        .await
+        .unwrap();
+    assert_eq!(receipt.size_bytes, 10);
+    assert_eq!(*store.puts.lock().unwrap(), vec![receipt.key.clone()]);
+    assert_eq!(read_all(&adapter, ctx, receipt.id).await, b"0123456789");
+    assert!(!buffer_dir.join(id.as_str()).exists());
+}
+
+#[tokio::test]
+async fn multipart_stores_stage_parts_in_the_store() {
+    let store = MemoryStore::new(StoreCapabilities::basic().with_multipart(None, None));
+    let buffer_dir = std::env::temp_dir().join(format!("dog_blob_test_{}", uuid::Uuid::new_v4()));
+    let adapter = adapter(&store, &buffer_dir);
+    let ctx = BlobCtx::new("acme".to_string());
+
+    let session = adapter
+        .begin_multipart(ctx.clone(), BlobPut::new())
+        .await
+        .unwrap();
+    assert_eq!(session.strategy, UploadStrategy::Staged);
+    let id = session.upload_id;
+
+    adapter
+        .upload_part(ctx.clone(), id.clone(), 1, body("0123"))
+        .await
+        .unwrap();
+    adapter
+        .upload_part(ctx.clone(), id.clone(), 2, body("45"))
+        .await
+        .unwrap();
+    let receipt = adapter
+        .complete_multipart(ctx.clone(), id.clone())
+        .await
+        .unwrap();
+
+    // Two staged parts plus the assembled blob.
+    assert_eq!(store.puts.lock().unwrap().len(), 3);
+    assert_eq!(read_all(&adapter, ctx, receipt.id).await, b"012345");
+    assert!(!buffer_dir.exists());
+}
+
+#[tokio::test]
+async fn corrupted_part_is_rejected_on_its_own() {
+    let store = MemoryStore::new(StoreCapabilities::basic());
+    let buffer_dir = std::env::temp_dir().join(format!("dog_blob_test_{}", uuid::Uuid::new_v4()));
+    let adapter = adapter(&store, &buffer_dir);
+    let ctx = BlobCtx::new("acme".to_string());
+    let id = adapter
+        .begin_multipart(ctx.clone(), BlobPut::new())
+        .await
+        .unwrap()
+        .upload_id;
+
+    let part = adapter
+        .upload_part_with_checksum(
+            ctx.clone(),
+            id.clone(),
+            1,
+            body("0123"),
+            &sha256_checksum(b"0123"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(part.checksum, Some(sha256_checksum(b"0123")));
+
+    // A bit flipped in transit: "4567" was sent, "4577" arrived.
+    let err = adapter
+        .upload_part_with_checksum(
+            ctx.clone(),
+            id.clone(),
+            2,
+            body("4577"),
+            &sha256_checksum(b"4567"),
+        )
+        .await
+        .unwrap_err();
+    match err {
+        BlobError::PartChecksumMismatch {
+            part_number,
+            expected,
+            actual,
+        } => {
+            assert_eq!(part_number, 2);
+            assert_eq!(expected, sha256_checksum(b"4567"));
+            assert_eq!(actual, sha256_checksum(b"4577"));
+        }
+        other => panic!("expected PartChecksumMismatch, got {other}"),
+    }
+    let session = adapter
+        .get_upload_session(ctx.clone(), id.clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        session.progress.parts.keys().copied().collect::<Vec<_>>(),
+        vec![1]
+    );
+
+    adapter
+        .upload_part_with_checksum(
+            ctx.clone(),
+            id.clone(),
+            2,
+            body("4567"),
+            &sha256_checksum(b"4567"),
+        )
+        .await
+        .unwrap();
+
+    let err = adapter
+        .complete_multipart_with_checksum(ctx.clone(), id.clone(), &sha256_checksum(b"nope"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, BlobError::ChecksumMismatch { .. }));
+
+    let receipt = adapter
+        .complete_multipart_with_checksum(ctx.clone(), id, &sha256_checksum(b"01234567"))
+        .await
+        .unwrap();
+    assert_eq!(receipt.checksum, Some(sha256_checksum(b"01234567")));
+    assert_eq!(read_all(&adapter, ctx, receipt.id).await, b"01234567");
+}
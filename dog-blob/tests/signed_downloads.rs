@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use bytes::Bytes;
+use dog_blob::adapter::download_url_or_proxy;
+use dog_blob::{
+    BlobCtx, BlobError, BlobId, BlobKeyStrategy, BlobResult, BlobStore, ByteRange, ByteStream,
+    DefaultKeyStrategy, GetResult, ObjectHead, OpenedContent, PutResult, SignedUrlBlobStore,
+    StoreCapabilities,
+};
+use futures::StreamExt;
+
+/// In-memory store that signs URLs only when `signs` is set.
+struct MemoryStore {
+    objects: Mutex<HashMap<String, Bytes>>,
+    capabilities: StoreCapabilities,
+    signs: bool,
+}
+
+impl MemoryStore {
+    fn new(capabilities: StoreCapabilities, signs: bool) -> Self {
+        Self {
+            objects: Mutex::default(),
+            capabilities,
+            signs,
+        }
+    }
+
+    fn insert(&self, ctx: &BlobCtx, id: &BlobId, data: &'static [u8]) {
+        let key = DefaultKeyStrategy.object_key(&ctx.tenant_id, id.as_str(), &Default::default());
+        self.objects
+            .lock()
+            .unwrap()
+            .insert(key, Bytes::from_static(data));
+    }
+}
+
+#[async_trait::async_trait]
+impl BlobStore for MemoryStore {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    async fn put(
+        &self,
+        _key: &str,
+        _content_type: Option<&str>,
+        _stream: ByteStream,
+    ) -> BlobResult<PutResult> {
+        Err(BlobError::Unsupported)
+    }
+
+    async fn get(&self, key: &str, _range: Option<ByteRange>) -> BlobResult<GetResult> {
+        let data = self
+            .objects
+            .lock()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .ok_or_else(|| BlobError::not_found(key))?;
+        Ok(GetResult {
+            size_bytes: data.len() as u64,
+            stream: Box::pin(futures::stream::once(async move { Ok(data) })),
+            content_type: None,
+            etag: None,
+            resolved_range: None,
+        })
+    }
+
+    async fn head(&self, key: &str) -> BlobResult<ObjectHead> {
+        let objects = self.objects.lock().unwrap();
+        let data = objects.get(key).ok_or_else(|| BlobError::not_found(key))?;
+        Ok(ObjectHead {
+            size_bytes: data.len() as u64,
+            content_type: None,
+            etag: None,
+            last_modified: None,
+        })
+    }
+
+    async fn delete(&self, key: &str) -> BlobResult<()> {
+        self.objects.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    fn capabilities(&self) -> StoreCapabilities {
+        self.capabilities.clone()
+    }
+
+    fn as_signed_url_store(&self) -> Option<&dyn SignedUrlBlobStore> {
+        if self.signs {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl SignedUrlBlobStore for MemoryStore {
+    async fn sign_get(&self, key: &str, expires_in_secs: u64) -> BlobResult<String> {
+        Ok(format!(
+            "https://cdn.example/{key}?expires={expires_in_secs}"
+        ))
+    }
+
+    async fn sign_put(
+        &self,
+        _key: &str,
+        _content_type: Option<&str>,
+        _expires_in_secs: u64,
+    ) -> BlobResult<String> {
+        Err(BlobError::Unsupported)
+    }
+}
+
+async fn proxied_content(content: OpenedContent) -> Vec<u8> {
+    let OpenedContent::Stream { mut stream, .. } = content else {
+        panic!("expected a proxied stream");
+    };
+    let mut data = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        data.extend_from_slice(&chunk.unwrap());
+    }
+    data
+}
+
+#[tokio::test]
+async fn signing_stores_redirect_to_a_signed_url() {
+    let store = MemoryStore::new(StoreCapabilities::basic().with_signed_urls(), true);
+    let ctx = BlobCtx::new("acme".to_string());
+    let id = BlobId::new();
+    store.insert(&ctx, &id, b"hello");
+
+    let opened = download_url_or_proxy(&store, &ctx, &id).await.unwrap();
+    assert_eq!(opened.receipt.size_bytes, 5);
+    match opened.content {
+        OpenedContent::SignedUrl { url, .. } => {
+            assert_eq!(
+                url,
+                format!("https://cdn.example/{}?expires=3600", opened.receipt.key)
+            );
+        }
+        OpenedContent::Stream { .. } => panic!("expected a signed URL"),
+    }
+
+    let missing = download_url_or_proxy(&store, &ctx, &BlobId::new()).await;
+    assert!(matches!(missing, Err(BlobError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn other_stores_proxy_the_content() {
+    let ctx = BlobCtx::new("acme".to_string());
+    let id = BlobId::new();
+
+    let plain = MemoryStore::new(StoreCapabilities::basic(), false);
+    plain.insert(&ctx, &id, b"hello");
+    let opened = download_url_or_proxy(&plain, &ctx, &id).await.unwrap();
+    assert_eq!(proxied_content(opened.content).await, b"hello");
+
+    // Advertises signed URLs but has no signer to hand out.
+    let unsigned = MemoryStore::new(StoreCapabilities::basic().with_signed_urls(), false);
+    unsigned.insert(&ctx, &id, b"hello");
+    let opened = download_url_or_proxy(&unsigned, &ctx, &id).await.unwrap();
+    assert_eq!(proxied_content(opened.content).await, b"hello");
+}
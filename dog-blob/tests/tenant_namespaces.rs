@@ -0,0 +1,124 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use bytes::Bytes;
+use dog_blob::adapter::BlobState;
+use dog_blob::{
+    BlobAdapter, BlobConfig, BlobCtx, BlobError, BlobId, BlobKeyStrategy, BlobPut, BlobResult,
+    BlobStore, ByteRange, ByteStream, DefaultKeyStrategy, GetResult, ObjectHead, PutResult,
+    StoreCapabilities,
+};
+use futures::StreamExt;
+
+#[derive(Clone, Default)]
+struct MemoryStore(Arc<Mutex<HashMap<String, Bytes>>>);
+
+#[async_trait::async_trait]
+impl BlobStore for MemoryStore {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    async fn put(
+        &self,
+        key: &str,
+        _content_type: Option<&str>,
+        mut stream: ByteStream,
+    ) -> BlobResult<PutResult> {
+        let mut data = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            data.extend_from_slice(&chunk?);
+        }
+        let size_bytes = data.len() as u64;
+        self.0
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), Bytes::from(data));
+        Ok(PutResult {
+            etag: None,
+            size_bytes,
+            checksum: None,
+        })
+    }
+
+    async fn get(&self, key: &str, _range: Option<ByteRange>) -> BlobResult<GetResult> {
+        let data = self
+            .0
+            .lock()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .ok_or_else(|| BlobError::not_found(key))?;
+        Ok(GetResult {
+            size_bytes: data.len() as u64,
+            stream: Box::pin(futures::stream::once(async move { Ok(data) })),
+            content_type: None,
+            etag: None,
+            resolved_range: None,
+        })
+    }
+
+    async fn head(&self, key: &str) -> BlobResult<ObjectHead> {
+        let store = self.0.lock().unwrap();
+        let data = store.get(key).ok_or_else(|| BlobError::not_found(key))?;
+        Ok(ObjectHead {
+            size_bytes: data.len() as u64,
+            content_type: None,
+            etag: None,
+            last_modified: None,
+        })
+    }
+
+    async fn delete(&self, key: &str) -> BlobResult<()> {
+        self.0.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    fn capabilities(&self) -> StoreCapabilities {
+        StoreCapabilities::basic()
+    }
+}
+
+fn body(data: &'static str) -> ByteStream {
+    Box::pin(futures::stream::once(async move {
+        Ok(Bytes::from_static(data.as_bytes()))
+    }))
+}
+
+#[test]
+fn the_same_blob_id_gets_a_key_per_tenant() {
+    let id = BlobId::new();
+    let acme = DefaultKeyStrategy.namespaced_key("tenants", "acme", id.as_str(), &BTreeMap::new());
+    let globex =
+        DefaultKeyStrategy.namespaced_key("tenants", "globex", id.as_str(), &BTreeMap::new());
+
+    assert!(acme.starts_with("tenants/acme/"));
+    assert!(globex.starts_with("tenants/globex/"));
+    assert!(acme.ends_with(id.as_str()));
+    assert_ne!(acme, globex);
+}
+
+#[tokio::test]
+async fn tenants_cannot_read_each_others_blobs() {
+    let store = MemoryStore::default();
+    let config = BlobConfig::default().with_tenant_namespace("tenants");
+    let adapter = BlobAdapter::new(Arc::new(BlobState::new(store.clone(), config)));
+    let acme = BlobCtx::new("acme".to_string());
+    let globex = BlobCtx::new("globex".to_string());
+
+    let receipt = adapter
+        .put(acme.clone(), BlobPut::new(), body("secret"))
+        .await
+        .unwrap();
+    assert!(receipt.key.starts_with("tenants/acme/"));
+    assert!(store.0.lock().unwrap().contains_key(&receipt.key));
+
+    let opened = adapter.open(acme, receipt.id.clone(), None).await;
+    assert!(opened.is_ok());
+
+    // Guessing the id from another tenant resolves to a different key.
+    let guessed = adapter.open(globex.clone(), receipt.id.clone(), None).await;
+    assert!(matches!(guessed, Err(BlobError::NotFound { .. })));
+    let head = adapter.head(globex, receipt.id).await;
+    assert!(matches!(head, Err(BlobError::NotFound { .. })));
+}
@@ -1,31 +1,56 @@
 use crate::events::PublishFn;
 use anyhow::Result;
 use std::any::Any;
-use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
-
-use crate::hooks::{collect_method_hooks, HookFut};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+use crate::config::ConfigChange;
+use crate::hooks::metrics::{timing_hook, MetricsSink, METRICS_HOOK_PRIORITY};
+use crate::hooks::{collect_method_hooks, run_before_hook, sort_by_priority, HookFut};
+use crate::tenant::{HeaderTenant, TenantResolver, DEFAULT_TENANT};
 use crate::{
-    DogConfig, DogService, DogServiceRegistry, HookContext, HookResult, Next, ServiceHooks,
-    ServiceMethodKind, TenantContext,
+    DogConfig, DogService, DogServiceRegistry, HookContext, HookResult, Next, Paginated,
+    RequestMeta, ServiceHooks, ServiceMethodKind, TenantContext,
 };
 
-use crate::events::{method_to_standard_event, DogEventHub, ServiceEventData, ServiceEventKind};
+use crate::events::{
+    method_to_standard_event, DogEventHub, ListenerId, ServiceErrorEvent, ServiceEventData,
+    ServiceEventKind, ServiceEventPattern,
+};
 
 struct DogAppInner<R, P>
 where
     R: Send + 'static,
     P: Send + Clone + 'static,
 {
-    // Registry is the only field that allows post-build mutation:
-    // `AxumApp::use_service_as` registers services at router-build time.
-    // All other fields are fully frozen after `DogAppBuilder::build()`.
+    // Registry, hooks and config are the only fields that allow post-build
+    // mutation: `AxumApp::use_service_as` registers services at router-build
+    // time, `deregister_service` drops a service together with its hooks,
+    // `enable_metrics` adds a global hook, and `set`/`watch` update config at
+    // runtime. All other fields are fully frozen after `DogAppBuilder::build()`.
+    //
+    // Lock order: registry, then global_hooks, then service_hooks. The config
+    // lock is never held together with any of them, only with config_changes
+    // (config first).
     registry: RwLock<DogServiceRegistry<R, P>>,
-    global_hooks: ServiceHooks<R, P>,
-    service_hooks: HashMap<String, ServiceHooks<R, P>>,
-    config: DogConfig,
+    global_hooks: RwLock<ServiceHooks<R, P>>,
+    service_hooks: RwLock<HashMap<String, ServiceHooks<R, P>>>,
+    config: RwLock<DogConfig>,
+    config_changes: Mutex<ConfigChanges>,
     any_state: HashMap<String, Box<dyn Any + Send + Sync>>,
+    #[cfg(feature = "json")]
+    service_schemas: HashMap<String, serde_json::Value>,
     events: DogEventHub<R, P>,
+    tenant_resolvers: Vec<Arc<dyn TenantResolver>>,
+}
+
+/// Runtime config changes waiting for their watchers, in write order.
+#[derive(Default)]
+struct ConfigChanges {
+    queue: VecDeque<ConfigChange>,
+    /// Whether a thread is already reporting the queued changes.
+    draining: bool,
 }
 
 /// DogAppBuilder is the setup interface for DogRS.
@@ -39,7 +64,10 @@ where
     service_hooks: HashMap<String, ServiceHooks<R, P>>,
     config: DogConfig,
     any_state: HashMap<String, Box<dyn Any + Send + Sync>>,
+    #[cfg(feature = "json")]
+    service_schemas: HashMap<String, serde_json::Value>,
     events: DogEventHub<R, P>,
+    tenant_resolvers: Vec<Arc<dyn TenantResolver>>,
 }
 
 impl<R, P> Default for DogAppBuilder<R, P>
@@ -64,7 +92,10 @@ where
             service_hooks: HashMap::new(),
             config: DogConfig::new(),
             any_state: HashMap::new(),
+            #[cfg(feature = "json")]
+            service_schemas: HashMap::new(),
             events: DogEventHub::new(),
+            tenant_resolvers: Vec::new(),
         }
     }
 
@@ -90,11 +121,37 @@ where
         self.any_state.get(key).and_then(|b| T::from_any(b))
     }
 
-    pub fn register_service<S>(&mut self, name: S, service: Arc<dyn DogService<R, P>>)
+    /// Parse a config value with `FromStr`. See [`crate::DogConfig::get_as`].
+    pub fn get_as<T: std::str::FromStr>(&self, key: &str) -> Option<T> {
+        self.config.get_as(key)
+    }
+
+    /// Deserialize a config value. See [`crate::DogConfig::get_json`].
+    #[cfg(feature = "json")]
+    pub fn get_json<T: serde::de::DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.config.get_json(key)
+    }
+
+    /// Deserialize every `prefix.*` key into `T`. See [`crate::DogConfig::get_section`].
+    #[cfg(feature = "json")]
+    pub fn get_section<T: serde::de::DeserializeOwned>(&self, prefix: &str) -> Result<T> {
+        self.config.get_section(prefix)
+    }
+
+    /// Register `service` under `name`, returning a [`ServiceRef`] to call
+    /// it through the built app.
+    pub fn register_service<S>(
+        &mut self,
+        name: S,
+        service: Arc<dyn DogService<R, P>>,
+    ) -> ServiceRef<R, P>
     where
         S: Into<String>,
     {
-        self.registry.register(name.into(), service);
+        let service_ref = ServiceRef::new(name.into(), service);
+        self.registry
+            .register(service_ref.name(), service_ref.service.clone());
+        service_ref
     }
 
     pub fn hooks<F>(&mut self, f: F)
@@ -104,6 +161,19 @@ where
         f(&mut self.global_hooks);
     }
 
+    /// Add a resolver for [`DogApp::resolve_tenant`]. Resolvers are tried in
+    /// the order they were added; adding any replaces the default
+    /// `x-tenant-id` lookup.
+    pub fn tenant_resolver(&mut self, resolver: Arc<dyn TenantResolver>) {
+        self.tenant_resolvers.push(resolver);
+    }
+
+    /// Build-time form of [`DogApp::enable_metrics`].
+    pub fn enable_metrics(&mut self, sink: Arc<dyn MetricsSink>) {
+        self.global_hooks
+            .around_with_priority(METRICS_HOOK_PRIORITY, timing_hook(sink));
+    }
+
     pub fn service_hooks<F>(&mut self, service_name: &str, f: F)
     where
         F: FnOnce(&mut ServiceHooks<R, P>),
@@ -135,6 +205,66 @@ where
         }
     }
 
+    /// Load config from files and the environment.
+    ///
+    /// Every source is read before anything is merged, so a missing or
+    /// malformed file leaves the config untouched. Precedence is fixed at
+    /// file < env < explicit `set`/`set_value`, whatever order the sources
+    /// are listed in or whether `set` ran before this call:
+    ///
+    /// ```rust,ignore
+    /// app.configure_from([
+    ///     ConfigSource::json_file("config.json"),
+    ///     ConfigSource::env("DOG"), // DOG_HTTP__PORT → http.port
+    /// ])?;
+    /// ```
+    pub fn configure_from<I>(&mut self, sources: I) -> Result<()>
+    where
+        I: IntoIterator<Item = crate::ConfigSource>,
+    {
+        let loaded = sources
+            .into_iter()
+            .map(crate::ConfigSource::load)
+            .collect::<Result<Vec<_>>>()?;
+        for config in loaded {
+            self.config.merge(config);
+        }
+        Ok(())
+    }
+
+    /// Store a typed config value, e.g. `set_value("paginate.max", json!(50))`.
+    ///
+    /// The value stays readable through `get` (as its string rendering),
+    /// `get_as` and `get_json`.
+    #[cfg(feature = "json")]
+    pub fn set_value<K>(&mut self, key: K, value: serde_json::Value)
+    where
+        K: Into<String>,
+    {
+        self.config.set_value(key, value);
+    }
+
+    /// Call `watcher` with the old and new value whenever `key` is changed
+    /// with `set`, here or on the built app. See [`crate::DogConfig::watch`].
+    pub fn watch<K, F>(&mut self, key: K, watcher: F)
+    where
+        K: Into<String>,
+        F: Fn(Option<&str>, &str) + Send + Sync + 'static,
+    {
+        self.config.watch(key, watcher);
+    }
+
+    /// Describe a service's payloads with JSON Schemas, as
+    /// `{ "create": { .. }, "patch": { .. } }` (either may be absent).
+    ///
+    /// Purely descriptive: adapters use it for API docs (dog-axum's OpenAPI
+    /// spec), validation stays in hooks. `#[schema]` modules call this from
+    /// their generated `register`.
+    #[cfg(feature = "json")]
+    pub fn set_service_schema(&mut self, service: impl Into<String>, schema: serde_json::Value) {
+        self.service_schemas.insert(service.into(), schema);
+    }
+
     pub fn on(
         &mut self,
         path: impl Into<String>,
@@ -162,11 +292,15 @@ where
         DogApp {
             inner: Arc::new(DogAppInner {
                 registry: RwLock::new(self.registry),
-                global_hooks: self.global_hooks,
-                service_hooks: self.service_hooks,
-                config: self.config,
+                global_hooks: RwLock::new(self.global_hooks),
+                service_hooks: RwLock::new(self.service_hooks),
+                config: RwLock::new(self.config),
+                config_changes: Mutex::default(),
                 any_state: self.any_state,
+                #[cfg(feature = "json")]
+                service_schemas: self.service_schemas,
                 events: self.events,
+                tenant_resolvers: self.tenant_resolvers,
             }),
         }
     }
@@ -294,29 +428,236 @@ where
         T: FromAppValue,
     {
         // Try config first
-        if let Some(s) = self.inner.config.get(key) {
-            if let Some(v) = T::from_config(s) {
-                return Some(v);
-            }
+        if let Some(v) = self.config().get(key).and_then(T::from_config) {
+            return Some(v);
         }
         // Fallback to any_state
         self.inner.any_state.get(key).and_then(|b| T::from_any(b))
     }
 
-    /// Register a service at runtime.
+    /// Parse a config value with `FromStr`.
+    ///
+    /// `let timeout: u64 = app.get_as("tomtom.route.timeout").unwrap_or(15);`
+    pub fn get_as<T: std::str::FromStr>(&self, key: &str) -> Option<T> {
+        self.config().get_as(key)
+    }
+
+    /// Deserialize a config value, including structured values stored with
+    /// `DogAppBuilder::set_value`. See [`crate::DogConfig::get_json`].
+    #[cfg(feature = "json")]
+    pub fn get_json<T: serde::de::DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.config().get_json(key)
+    }
+
+    /// Deserialize a whole config block, e.g.
+    /// `let tomtom: TomTomConfig = app.get_section("tomtom")?;`.
+    /// See [`crate::DogConfig::get_section`] for the key mapping rules.
+    #[cfg(feature = "json")]
+    pub fn get_section<T: serde::de::DeserializeOwned>(&self, prefix: &str) -> Result<T> {
+        self.config().get_section(prefix)
+    }
+
+    /// Change a config value at runtime, e.g. to flip a feature flag.
+    ///
+    /// Calls already in flight keep the snapshot they started with; calls
+    /// that start afterwards see the new value. Watchers registered with
+    /// [`DogApp::watch`] run once the config lock is released, in the order
+    /// the changes were written. While another thread is notifying, this
+    /// change is left to that thread and `set` returns before its watchers
+    /// run. Unlike [`DogAppBuilder::set`], only string values can be set;
+    /// use `DogApp::set_value` for JSON values.
+    pub fn set<K, V>(&self, key: K, value: V)
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let mut config = self.config_mut();
+        let change = config.set_deferred(key.into(), value.into());
+        self.report_config_change(config, change);
+    }
+
+    /// Runtime form of [`DogAppBuilder::set_value`]; see [`DogApp::set`].
+    #[cfg(feature = "json")]
+    pub fn set_value<K>(&self, key: K, value: serde_json::Value)
+    where
+        K: Into<String>,
+    {
+        let mut config = self.config_mut();
+        let change = config.set_value_deferred(key.into(), value);
+        self.report_config_change(config, change);
+    }
+
+    /// Call `watcher` with the old and new value whenever `key` is changed
+    /// with [`DogApp::set`] or [`DogApp::set_value`].
+    ///
+    /// ```rust,ignore
+    /// let maintenance = Arc::new(AtomicBool::new(false));
+    /// let flag = maintenance.clone();
+    /// app.watch("maintenance", move |_, new| {
+    ///     flag.store(new == "true", Ordering::Relaxed);
+    /// });
+    /// ```
+    pub fn watch<K, F>(&self, key: K, watcher: F)
+    where
+        K: Into<String>,
+        F: Fn(Option<&str>, &str) + Send + Sync + 'static,
+    {
+        self.config_mut().watch(key, watcher);
+    }
+
+    fn config(&self) -> std::sync::RwLockReadGuard<'_, DogConfig> {
+        self.inner.config.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn config_mut(&self) -> std::sync::RwLockWriteGuard<'_, DogConfig> {
+        self.inner.config.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn config_changes(&self) -> std::sync::MutexGuard<'_, ConfigChanges> {
+        self.inner
+            .config_changes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Queue `change` while the config write lock is still held, so changes
+    /// queue in the order they were written, then tell the watchers.
+    ///
+    /// Only one thread reports at a time. A `set` made while another thread
+    /// (or a watcher on this one) is reporting leaves its change to that
+    /// thread, so watchers always see changes in write order and may call
+    /// `set` themselves.
+    fn report_config_change(
+        &self,
+        config: std::sync::RwLockWriteGuard<'_, DogConfig>,
+        change: Option<ConfigChange>,
+    ) {
+        let mut changes = self.config_changes();
+        if let Some(change) = change {
+            changes.queue.push_back(change);
+        }
+        drop(config);
+        if changes.draining {
+            return;
+        }
+        changes.draining = true;
+
+        // A panicking watcher must not leave the queue marked as draining.
+        struct Draining<'a>(&'a Mutex<ConfigChanges>);
+        impl Drop for Draining<'_> {
+            fn drop(&mut self) {
+                if std::thread::panicking() {
+                    let mut changes = self.0.lock().unwrap_or_else(|e| e.into_inner());
+                    changes.draining = false;
+                }
+            }
+        }
+        let _draining = Draining(&self.inner.config_changes);
+
+        while let Some(change) = changes.queue.pop_front() {
+            drop(changes);
+            change.notify();
+            changes = self.config_changes();
+        }
+        changes.draining = false;
+    }
+
+    /// Register `service` under `name` at runtime, returning a [`ServiceRef`]
+    /// to call it without looking it up by name again.
     ///
     /// Prefer `DogAppBuilder::register_service` for build-time registration.
     /// This method exists so that `AxumApp::use_service_as` can register services
     /// at router-setup time (after `build()`).
-    pub fn register_service<S>(&self, name: S, service: Arc<dyn DogService<R, P>>)
+    pub fn register_service<S>(
+        &self,
+        name: S,
+        service: Arc<dyn DogService<R, P>>,
+    ) -> ServiceRef<R, P>
     where
         S: Into<String>,
     {
+        let service_ref = ServiceRef::new(name.into(), service);
         self.inner
             .registry
             .write()
             .unwrap_or_else(|e| e.into_inner())
-            .register(name.into(), service);
+            .register(service_ref.name(), service_ref.service.clone());
+        service_ref
+    }
+
+    /// Names of every registered service, sorted.
+    pub fn service_names(&self) -> Vec<String> {
+        self.inner
+            .registry
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .names()
+    }
+
+    /// The JSON Schemas set with [`DogAppBuilder::set_service_schema`], if any.
+    #[cfg(feature = "json")]
+    pub fn service_schema(&self, name: &str) -> Option<&serde_json::Value> {
+        self.inner.service_schemas.get(name)
+    }
+
+    /// Remove a service and its service-specific hooks.
+    ///
+    /// Returns the removed service, or `None` if nothing was registered under
+    /// `name`. Both are removed under the same locks, so no call can observe
+    /// the hooks without the service or vice versa. Calls already in flight
+    /// (and `ServiceHandle`s obtained earlier) hold their own `Arc` to the old
+    /// service and hooks collected at call start, so they finish normally;
+    /// new lookups fail with "DogService not found".
+    pub fn deregister_service(&self, name: &str) -> Option<Arc<dyn DogService<R, P>>> {
+        let mut registry = self
+            .inner
+            .registry
+            .write()
+            .unwrap_or_else(|e| e.into_inner());
+        let mut hooks = self
+            .inner
+            .service_hooks
+            .write()
+            .unwrap_or_else(|e| e.into_inner());
+        hooks.remove(name);
+        registry.remove(name)
+    }
+
+    /// Swap the implementation behind an existing service name.
+    ///
+    /// Service hooks stay attached to the name, so a mock swapped in for a
+    /// test still runs through the real validation/auth pipeline. Like
+    /// `deregister_service`, in-flight calls keep using the old service.
+    /// Returns the previous service, or an error if `name` is not registered
+    /// (use `register_service` to add a new one).
+    pub fn replace_service(
+        &self,
+        name: &str,
+        service: Arc<dyn DogService<R, P>>,
+    ) -> Result<Arc<dyn DogService<R, P>>> {
+        let mut registry = self
+            .inner
+            .registry
+            .write()
+            .unwrap_or_else(|e| e.into_inner());
+        let previous = registry
+            .remove(name)
+            .ok_or_else(|| anyhow::anyhow!("DogService not found: {name}"))?;
+        registry.register(name, service);
+        Ok(previous)
+    }
+
+    /// Time every service call and report it to `sink`.
+    ///
+    /// Installs [`crate::hooks::metrics::timing_hook`] as the outermost global
+    /// around hook. Takes effect for calls that start afterwards; calling it
+    /// twice installs two hooks.
+    pub fn enable_metrics(&self, sink: Arc<dyn MetricsSink>) {
+        self.inner
+            .global_hooks
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .around_with_priority(METRICS_HOOK_PRIORITY, timing_hook(sink));
     }
 
     pub fn service(&self, name: &str) -> Result<ServiceHandle<R, P>> {
@@ -333,11 +674,160 @@ where
             app: self.clone(),
             name: name.to_string(),
             service: svc,
+            request: None,
+            correlation_id: None,
+            origin: None,
+            cross_tenant: false,
         })
     }
 
+    /// A [`ServiceRef`] for a service registered earlier by name.
+    ///
+    /// For code that only has the name, such as a service registered by
+    /// another crate: look it up once at startup and keep the ref, instead
+    /// of calling [`DogApp::service`] on every call.
+    pub fn service_ref(&self, name: &str) -> Result<ServiceRef<R, P>> {
+        let svc = self
+            .inner
+            .registry
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(name)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("DogService not found: {name}"))?;
+        Ok(ServiceRef::new(name.to_string(), svc))
+    }
+
+    /// The [`ServiceHandle`] behind `service_ref`, for calls that run the
+    /// service's hooks.
+    ///
+    /// Unlike [`DogApp::service`] this cannot fail. It uses the service
+    /// currently registered under the ref's name, so `replace_service` still
+    /// applies. If the name was deregistered, it falls back to the service
+    /// the ref was made from, as a `ServiceHandle` taken earlier would.
+    pub fn handle(&self, service_ref: &ServiceRef<R, P>) -> ServiceHandle<R, P> {
+        let svc = self
+            .inner
+            .registry
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(service_ref.name())
+            .cloned()
+            .unwrap_or_else(|| service_ref.service.clone());
+
+        ServiceHandle {
+            app: self.clone(),
+            name: service_ref.name().to_string(),
+            service: svc,
+            request: None,
+            correlation_id: None,
+            origin: None,
+            cross_tenant: false,
+        }
+    }
+
     pub fn config_snapshot(&self) -> crate::DogConfigSnapshot {
-        self.inner.config.snapshot()
+        self.config().snapshot()
+    }
+
+    /// The tenant of a transport request, for adapters to pass to service
+    /// calls.
+    ///
+    /// Tries the resolvers added with [`DogAppBuilder::tenant_resolver`] in
+    /// order, or the `x-tenant-id` header when there are none, and falls back
+    /// to [`DEFAULT_TENANT`].
+    pub async fn resolve_tenant(&self, request: &RequestMeta) -> TenantContext {
+        if self.inner.tenant_resolvers.is_empty() {
+            if let Some(tenant) = HeaderTenant::default().resolve(request).await {
+                return tenant;
+            }
+        }
+        for resolver in &self.inner.tenant_resolvers {
+            if let Some(tenant) = resolver.resolve(request).await {
+                return tenant;
+            }
+        }
+        TenantContext::new(DEFAULT_TENANT)
+    }
+}
+
+/// A typed handle to a registered service, returned by `register_service`.
+///
+/// A ref only works with an app of the same `R` and `P`, so a caller that
+/// keeps one gets a compile error, not a runtime one, if the types drift.
+/// Turn it into a [`ServiceHandle`] with [`DogApp::handle`] or
+/// [`ServiceCaller::handle`]:
+///
+/// ```rust
+/// # use std::sync::Arc;
+/// # use dog_core::{DogApp, DogService, TenantContext};
+/// struct Users;
+///
+/// #[async_trait::async_trait]
+/// impl DogService<String, ()> for Users {
+///     async fn find(&self, _ctx: &TenantContext, _params: ()) -> anyhow::Result<Vec<String>> {
+///         Ok(vec!["ada".to_string()])
+///     }
+/// }
+///
+/// # async fn run() -> anyhow::Result<()> {
+/// let app = DogApp::<String, ()>::default();
+/// let users = app.register_service("users", Arc::new(Users));
+///
+/// let found = app.handle(&users).find(TenantContext::new("t"), ()).await?;
+/// assert_eq!(found, vec!["ada"]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct ServiceRef<R, P>
+where
+    R: Send + 'static,
+    P: Send + 'static,
+{
+    name: Arc<str>,
+    service: Arc<dyn DogService<R, P>>,
+}
+
+impl<R, P> ServiceRef<R, P>
+where
+    R: Send + 'static,
+    P: Send + 'static,
+{
+    fn new(name: String, service: Arc<dyn DogService<R, P>>) -> Self {
+        Self {
+            name: name.into(),
+            service,
+        }
+    }
+
+    /// The name the service was registered under.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl<R, P> Clone for ServiceRef<R, P>
+where
+    R: Send + 'static,
+    P: Send + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            service: self.service.clone(),
+        }
+    }
+}
+
+impl<R, P> std::fmt::Debug for ServiceRef<R, P>
+where
+    R: Send + 'static,
+    P: Send + 'static,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServiceRef")
+            .field("name", &self.name)
+            .finish()
     }
 }
 
@@ -346,6 +836,21 @@ where
     R: Send + 'static,
     P: Send + Clone + 'static,
 {
+    /// Listen for events after `build()`, e.g. from a transport that
+    /// subscribes per connection. Returns an id for [`DogApp::off`].
+    pub fn on_pattern(
+        &self,
+        pattern: ServiceEventPattern,
+        listener: crate::events::EventListener<R, P>,
+    ) -> ListenerId {
+        self.inner.events.on_pattern(pattern, listener)
+    }
+
+    /// Remove a listener added with [`DogApp::on_pattern`].
+    pub fn off(&self, id: ListenerId) -> bool {
+        self.inner.events.off(id)
+    }
+
     pub async fn emit_custom(
         &self,
         path: &str,
@@ -372,6 +877,18 @@ where
     app: DogApp<R, P>,
     name: String,
     service: Arc<dyn DogService<R, P>>,
+    request: Option<RequestMeta>,
+    correlation_id: Option<String>,
+    /// The call this handle was taken from, for handles from `ctx.services`.
+    origin: Option<CallOrigin>,
+    cross_tenant: bool,
+}
+
+/// Tenant and correlation id of the call a [`ServiceCaller`] belongs to.
+#[derive(Debug, Clone)]
+struct CallOrigin {
+    tenant: TenantContext,
+    correlation_id: Option<String>,
 }
 
 impl<R, P> ServiceHandle<R, P>
@@ -382,6 +899,79 @@ where
     pub fn inner(&self) -> &Arc<dyn DogService<R, P>> {
         &self.service
     }
+
+    /// Attach the transport request behind the calls made with this handle.
+    /// Hooks see it as `ctx.request`.
+    pub fn with_request(mut self, request: RequestMeta) -> Self {
+        self.request = Some(request);
+        self
+    }
+
+    /// Tag the calls made with this handle with `id`, e.g. a trace id.
+    /// Hooks see it as `ctx.correlation_id`, and calls they make through
+    /// `ctx.services` carry it forward.
+    ///
+    /// Without one, the `x-request-id` header of the attached request is
+    /// used, or else the id of the call this handle was taken from.
+    pub fn with_correlation_id(mut self, id: impl Into<String>) -> Self {
+        self.correlation_id = Some(id.into());
+        self
+    }
+
+    /// Allow calls for a tenant other than the calling one.
+    ///
+    /// Handles taken from `ctx.services` run in the tenant of the call that
+    /// made them: passing a different tenant fails with `Forbidden` unless
+    /// the handle opts in here. Handles from the app are unaffected.
+    pub fn cross_tenant(mut self) -> Self {
+        self.cross_tenant = true;
+        self
+    }
+
+    fn context(
+        &self,
+        tenant: TenantContext,
+        method: ServiceMethodKind,
+        params: P,
+    ) -> Result<HookContext<R, P>> {
+        let tenant = match &self.origin {
+            Some(origin) if !self.cross_tenant => {
+                if origin.tenant.tenant_id != tenant.tenant_id {
+                    return Err(crate::DogError::forbidden(format!(
+                        "Service '{}' called for tenant '{}' from tenant '{}'; \
+                         use ServiceHandle::cross_tenant() for cross-tenant calls",
+                        self.name, tenant.tenant_id.0, origin.tenant.tenant_id.0
+                    ))
+                    .into_anyhow());
+                }
+                origin.tenant.clone()
+            }
+            _ => tenant,
+        };
+        let correlation_id = self
+            .correlation_id
+            .clone()
+            .or_else(|| {
+                self.request
+                    .as_ref()
+                    .and_then(|r| r.header("x-request-id"))
+                    .map(str::to_string)
+            })
+            .or_else(|| self.origin.as_ref()?.correlation_id.clone());
+
+        let services = ServiceCaller {
+            app: self.app.clone(),
+            origin: Some(CallOrigin {
+                tenant: tenant.clone(),
+                correlation_id: correlation_id.clone(),
+            }),
+        };
+        let config = self.app.config_snapshot();
+        let mut ctx = HookContext::new(tenant, method, params, services, config);
+        ctx.request = self.request.clone();
+        ctx.correlation_id = correlation_id;
+        Ok(ctx)
+    }
 }
 
 pub struct ServiceBuilderHandle<'a, R, P>
@@ -417,10 +1007,21 @@ where
     P: Send + Clone + 'static,
 {
     /// Collect hooks in Feathers order:
-    /// global first, then service.
+    /// global first, then service, then stably sorted by priority
+    /// (see the ordering notes on [`ServiceHooks`]).
     fn collect_hooks_for_method(&self, method: &ServiceMethodKind) -> HooksForMethod<R, P> {
-        let g = &self.app.inner.global_hooks;
-        let map = &self.app.inner.service_hooks;
+        let g = self
+            .app
+            .inner
+            .global_hooks
+            .read()
+            .unwrap_or_else(|e| e.into_inner());
+        let map = self
+            .app
+            .inner
+            .service_hooks
+            .read()
+            .unwrap_or_else(|e| e.into_inner());
         let s = map.get(&self.name);
 
         // GLOBAL
@@ -453,18 +1054,50 @@ where
             ));
         }
 
-        (around, before, after, error)
+        // Priority first, then the global → service order above.
+        sort_by_priority(&mut around);
+        sort_by_priority(&mut before);
+        sort_by_priority(&mut error);
+        // After hooks run in reverse, so order them lowest priority first;
+        // ties still unwind last-registered-first.
+        after.sort_by_key(|h| h.priority);
+
+        (
+            around.into_iter().map(|h| h.hook).collect(),
+            before.into_iter().map(|h| h.hook).collect(),
+            after.into_iter().map(|h| h.hook).collect(),
+            error.into_iter().map(|h| h.hook).collect(),
+        )
     }
 
     /// Core Feathers pipeline:
     /// around → before → service_call → after → error
+    ///
+    /// Methods missing from the service's `capabilities()` fail with
+    /// `MethodNotAllowed` (405) before any hook runs. Any other failure the
+    /// error hooks do not recover emits one `error` event.
     async fn run_pipeline(
         &self,
         method: ServiceMethodKind,
         mut ctx: HookContext<R, P>,
         service_call: ServiceCall<R, P>,
     ) -> Result<HookContext<R, P>> {
+        if !self
+            .service
+            .capabilities()
+            .allowed_methods
+            .contains(&method)
+        {
+            return Err(crate::DogError::method_not_allowed(format!(
+                "Service '{}' does not support method '{}'",
+                self.name,
+                method.as_str()
+            ))
+            .into_anyhow());
+        }
+
         let (around, before, after, error) = self.collect_hooks_for_method(&method);
+        ctx.service = Some(self.name.clone());
 
         let svc = self.service.clone();
         let service_call_inner = service_call.clone();
@@ -475,7 +1108,7 @@ where
             // After-hooks are success-path transformations only. Error cleanup belongs
             // in error hooks (registered via service_hooks(..).on_error(..)).
             for h in &before {
-                if let Err(e) = h.run(&mut ctx).await {
+                if let Err(e) = run_before_hook(h.as_ref(), &mut ctx).await {
                     ctx.error = Some(e);
                     break;
                 }
@@ -502,6 +1135,8 @@ where
                 Ok(())
             }
         } else {
+            let after_for_around = Arc::new(after.clone());
+
             // Inner: BEFORE -> service_call -> AFTER
             let mut next: Next<R, P> = Next {
                 call: Box::new(move |ctx: &mut HookContext<R, P>| -> HookFut<'_> {
@@ -512,7 +1147,7 @@ where
 
                     Box::pin(async move {
                         for h in &before {
-                            h.run(ctx).await?;
+                            run_before_hook(h.as_ref(), ctx).await?;
                         }
 
                         // sets ctx.result
@@ -527,14 +1162,36 @@ where
                 }),
             };
 
-            // AROUND chain: first hook is outermost
+            // AROUND chain: first hook is outermost. A hook that sets
+            // ctx.result without running `next` has handled the call; the
+            // after hooks still run on its result before outer hooks resume.
             for h in around.iter().rev() {
                 let hook = h.clone();
+                let after = after_for_around.clone();
                 let prev = next;
                 next = Next {
                     call: Box::new(move |ctx: &mut HookContext<R, P>| -> HookFut<'_> {
                         let hook = hook.clone();
-                        Box::pin(async move { hook.run(ctx, prev).await })
+                        Box::pin(async move {
+                            let ran = Arc::new(AtomicBool::new(false));
+                            let prev = Next {
+                                call: Box::new({
+                                    let ran = Arc::clone(&ran);
+                                    move |ctx: &mut HookContext<R, P>| -> HookFut<'_> {
+                                        ran.store(true, Ordering::SeqCst);
+                                        (prev.call)(ctx)
+                                    }
+                                }),
+                            };
+                            hook.run(ctx, prev).await?;
+
+                            if !ran.load(Ordering::SeqCst) && ctx.result.is_some() {
+                                for h in after.iter().rev() {
+                                    h.run(ctx).await?;
+                                }
+                            }
+                            Ok(())
+                        })
                     }),
                 };
             }
@@ -550,8 +1207,21 @@ where
                 let _ = h.run(&mut ctx).await;
             }
 
-            // If still error, return it
+            // If still error, report and return it. A hook that took the error
+            // and set ctx.result recovered the call: carry on down the success
+            // path.
             if let Some(err) = ctx.error.take() {
+                let report = ServiceErrorEvent::new(&self.name, &method, &err);
+                let data = ServiceEventData::Error(&report);
+                let listeners = self.app.inner.events.snapshot_emit(
+                    &self.name,
+                    &ServiceEventKind::Error,
+                    &data,
+                    &ctx,
+                );
+                for f in &listeners {
+                    let _ = f(&data, &ctx).await;
+                }
                 return Err(err);
             }
         }
@@ -586,9 +1256,7 @@ where
     pub async fn find(&self, tenant: TenantContext, params: P) -> Result<Vec<R>> {
         let method = ServiceMethodKind::Find;
 
-        let services = ServiceCaller::new(self.app.clone());
-        let config = self.app.config_snapshot();
-        let ctx = HookContext::new(tenant, method.clone(), params, services, config);
+        let ctx = self.context(tenant, method.clone(), params)?;
 
         let ctx = self
             .run_pipeline(
@@ -606,6 +1274,7 @@ where
 
         match ctx.result {
             Some(HookResult::Many(v)) => Ok(v),
+            Some(HookResult::Page { data, .. }) => Ok(data),
             Some(HookResult::One(_)) => Err(anyhow::anyhow!(
                 "find() produced HookResult::One unexpectedly"
             )),
@@ -613,12 +1282,50 @@ where
         }
     }
 
+    /// `find` returning a page with total/limit/skip.
+    ///
+    /// Runs the `find` hook pipeline, so find hooks fire as usual; the
+    /// service result is a `HookResult::Page` that after-hooks can inspect
+    /// or rewrite. If a hook replaces it with `HookResult::Many`, the list
+    /// is returned as a single page.
+    pub async fn find_paginated(&self, tenant: TenantContext, params: P) -> Result<Paginated<R>> {
+        let method = ServiceMethodKind::Find;
+
+        let ctx = self.context(tenant, method.clone(), params)?;
+
+        let ctx = self
+            .run_pipeline(
+                method,
+                ctx,
+                Arc::new(|svc, ctx| {
+                    Box::pin(async move {
+                        let page = svc.find_paginated(&ctx.tenant, ctx.params.clone()).await?;
+                        ctx.result = Some(page.into());
+                        Ok(())
+                    })
+                }),
+            )
+            .await?;
+
+        match ctx.result {
+            Some(HookResult::Page {
+                data,
+                total,
+                limit,
+                skip,
+            }) => Ok(Paginated::new(data, total, limit, skip)),
+            Some(HookResult::Many(v)) => Ok(Paginated::unpaged(v)),
+            Some(HookResult::One(_)) => Err(anyhow::anyhow!(
+                "find_paginated() produced HookResult::One unexpectedly"
+            )),
+            None => Ok(Paginated::unpaged(vec![])),
+        }
+    }
+
     pub async fn get(&self, tenant: TenantContext, id: &str, params: P) -> Result<R> {
         let method = ServiceMethodKind::Get;
 
-        let services = ServiceCaller::new(self.app.clone());
-        let config = self.app.config_snapshot();
-        let ctx = HookContext::new(tenant, method.clone(), params, services, config);
+        let ctx = self.context(tenant, method.clone(), params)?;
 
         let id = id.to_string();
 
@@ -639,8 +1346,9 @@ where
 
         match ctx.result {
             Some(HookResult::One(v)) => Ok(v),
-            Some(HookResult::Many(_)) => Err(anyhow::anyhow!(
-                "get() produced HookResult::Many unexpectedly"
+            Some(other) => Err(anyhow::anyhow!(
+                "get() produced HookResult::{} unexpectedly",
+                other.variant_name()
             )),
             None => Err(anyhow::anyhow!("get() produced no result")),
         }
@@ -649,9 +1357,7 @@ where
     pub async fn create(&self, tenant: TenantContext, data: R, params: P) -> Result<R> {
         let method = ServiceMethodKind::Create;
 
-        let services = ServiceCaller::new(self.app.clone());
-        let config = self.app.config_snapshot();
-        let mut ctx = HookContext::new(tenant, method.clone(), params, services, config);
+        let mut ctx = self.context(tenant, method.clone(), params)?;
         ctx.data = Some(data);
 
         let ctx = self
@@ -675,8 +1381,9 @@ where
 
         match ctx.result {
             Some(HookResult::One(v)) => Ok(v),
-            Some(HookResult::Many(_)) => Err(anyhow::anyhow!(
-                "create() produced HookResult::Many unexpectedly"
+            Some(other) => Err(anyhow::anyhow!(
+                "create() produced HookResult::{} unexpectedly",
+                other.variant_name()
             )),
             None => Err(anyhow::anyhow!("create() produced no result")),
         }
@@ -691,9 +1398,7 @@ where
     ) -> Result<R> {
         let method = ServiceMethodKind::Patch;
 
-        let services = ServiceCaller::new(self.app.clone());
-        let config = self.app.config_snapshot();
-        let mut ctx = HookContext::new(tenant, method.clone(), params, services, config);
+        let mut ctx = self.context(tenant, method.clone(), params)?;
         ctx.data = Some(data);
 
         let id: Option<String> = id.map(|s| s.to_string());
@@ -723,8 +1428,9 @@ where
 
         match ctx.result {
             Some(HookResult::One(v)) => Ok(v),
-            Some(HookResult::Many(_)) => Err(anyhow::anyhow!(
-                "patch() produced HookResult::Many unexpectedly"
+            Some(other) => Err(anyhow::anyhow!(
+                "patch() produced HookResult::{} unexpectedly",
+                other.variant_name()
             )),
             None => Err(anyhow::anyhow!("patch() produced no result")),
         }
@@ -733,9 +1439,7 @@ where
     pub async fn update(&self, tenant: TenantContext, id: &str, data: R, params: P) -> Result<R> {
         let method = ServiceMethodKind::Update;
 
-        let services = ServiceCaller::new(self.app.clone());
-        let config = self.app.config_snapshot();
-        let mut ctx = HookContext::new(tenant, method.clone(), params, services, config);
+        let mut ctx = self.context(tenant, method.clone(), params)?;
         ctx.data = Some(data);
 
         let id = id.to_string();
@@ -765,19 +1469,23 @@ where
 
         match ctx.result {
             Some(HookResult::One(v)) => Ok(v),
-            Some(HookResult::Many(_)) => Err(anyhow::anyhow!(
-                "update() produced HookResult::Many unexpectedly"
+            Some(other) => Err(anyhow::anyhow!(
+                "update() produced HookResult::{} unexpectedly",
+                other.variant_name()
             )),
             None => Err(anyhow::anyhow!("update() produced no result")),
         }
     }
 
+    /// Remove through the hook pipeline, then emit `removed`.
+    ///
+    /// The event carries the result after the after hooks. A batch remove
+    /// (`id = None`) emits one event for the whole call, with the batch
+    /// result the service returned, not one event per record.
     pub async fn remove(&self, tenant: TenantContext, id: Option<&str>, params: P) -> Result<R> {
         let method = ServiceMethodKind::Remove;
 
-        let services = ServiceCaller::new(self.app.clone());
-        let config = self.app.config_snapshot();
-        let ctx = HookContext::new(tenant, method.clone(), params, services, config);
+        let ctx = self.context(tenant, method.clone(), params)?;
 
         let id: Option<String> = id.map(|s| s.to_string());
 
@@ -801,26 +1509,160 @@ where
 
         match ctx.result {
             Some(HookResult::One(v)) => Ok(v),
-            Some(HookResult::Many(_)) => Err(anyhow::anyhow!(
-                "remove() produced HookResult::Many unexpectedly"
+            Some(other) => Err(anyhow::anyhow!(
+                "remove() produced HookResult::{} unexpectedly",
+                other.variant_name()
             )),
             None => Err(anyhow::anyhow!("remove() produced no result")),
         }
     }
 
+    /// Create a batch of records through one run of the `create` pipeline.
+    ///
+    /// Hooks see the records in `ctx.data_many` (`ctx.data` is `None`) and
+    /// the service's [`DogService::create_many`] result as
+    /// `HookResult::Many`. Before hooks that don't
+    /// [handle batches](crate::DogBeforeHook::handles_batches) run once per
+    /// record instead, so per-record validation still applies. Like a multi
+    /// `patch`/`remove`, the call emits a single `created` event carrying the
+    /// whole batch.
+    pub async fn create_many(
+        &self,
+        tenant: TenantContext,
+        data: Vec<R>,
+        params: P,
+    ) -> Result<Vec<R>> {
+        let method = ServiceMethodKind::Create;
+
+        let mut ctx = self.context(tenant, method.clone(), params)?;
+        ctx.data_many = Some(data);
+
+        let ctx = self
+            .run_pipeline(
+                method,
+                ctx,
+                Arc::new(|svc, ctx| {
+                    Box::pin(async move {
+                        let data = ctx.data_many.take().ok_or_else(|| {
+                            anyhow::anyhow!("create_many() requires ctx.data_many")
+                        })?;
+
+                        let created = svc
+                            .create_many(&ctx.tenant, data, ctx.params.clone())
+                            .await?;
+                        ctx.result = Some(HookResult::Many(created));
+                        Ok(())
+                    })
+                }),
+            )
+            .await?;
+
+        ctx.result
+            .map_or_else(|| Ok(vec![]), |r| r.into_many("create_many"))
+    }
+
+    /// Apply `data` to each of `ids` through one run of the `patch`
+    /// pipeline.
+    ///
+    /// Hooks see the patch in `ctx.data`, as for a single `patch`, and the
+    /// service's [`DogService::patch_many`] result as `HookResult::Many`.
+    /// One `patched` event carries the whole batch.
+    pub async fn patch_many(
+        &self,
+        tenant: TenantContext,
+        ids: &[&str],
+        data: R,
+        params: P,
+    ) -> Result<Vec<R>>
+    where
+        R: Clone,
+    {
+        let method = ServiceMethodKind::Patch;
+
+        let mut ctx = self.context(tenant, method.clone(), params)?;
+        ctx.data = Some(data);
+
+        let ids: Vec<String> = ids.iter().map(|s| s.to_string()).collect();
+
+        let ctx = self
+            .run_pipeline(
+                method,
+                ctx,
+                Arc::new(move |svc, ctx| {
+                    let ids = ids.clone();
+                    Box::pin(async move {
+                        let data = ctx
+                            .data
+                            .take()
+                            .ok_or_else(|| anyhow::anyhow!("patch_many() requires ctx.data"))?;
+
+                        let patched = svc
+                            .patch_many(&ctx.tenant, &ids, data, ctx.params.clone())
+                            .await?;
+                        ctx.result = Some(HookResult::Many(patched));
+                        Ok(())
+                    })
+                }),
+            )
+            .await?;
+
+        ctx.result
+            .map_or_else(|| Ok(vec![]), |r| r.into_many("patch_many"))
+    }
+
+    /// Remove each of `ids` through one run of the `remove` pipeline.
+    ///
+    /// The service's [`DogService::remove_many`] result reaches after hooks
+    /// as `HookResult::Many`, and one `removed` event carries the whole
+    /// batch.
+    pub async fn remove_many(
+        &self,
+        tenant: TenantContext,
+        ids: &[&str],
+        params: P,
+    ) -> Result<Vec<R>> {
+        let method = ServiceMethodKind::Remove;
+
+        let ctx = self.context(tenant, method.clone(), params)?;
+
+        let ids: Vec<String> = ids.iter().map(|s| s.to_string()).collect();
+
+        let ctx = self
+            .run_pipeline(
+                method,
+                ctx,
+                Arc::new(move |svc, ctx| {
+                    let ids = ids.clone();
+                    Box::pin(async move {
+                        let removed = svc
+                            .remove_many(&ctx.tenant, &ids, ctx.params.clone())
+                            .await?;
+                        ctx.result = Some(HookResult::Many(removed));
+                        Ok(())
+                    })
+                }),
+            )
+            .await?;
+
+        ctx.result
+            .map_or_else(|| Ok(vec![]), |r| r.into_many("remove_many"))
+    }
+
     /// Custom method that goes through the full dogrs pipeline (hooks, events, etc.)
+    ///
+    /// Hooks registered with `ServiceHooks::before_custom(method, ..)` (or
+    /// `before(ServiceMethodKind::custom(method), ..)`) fire alongside the
+    /// `*_all` hooks, exactly as they do for CRUD methods.
     pub async fn custom(
         &self,
         tenant: TenantContext,
-        method: &'static str,
+        method: &str,
         data: Option<R>,
         params: P,
     ) -> Result<R> {
-        let method_kind = ServiceMethodKind::Custom(method);
+        let method_kind = ServiceMethodKind::custom(method);
 
-        let services = ServiceCaller::new(self.app.clone());
-        let config = self.app.config_snapshot();
-        let mut ctx = HookContext::new(tenant, method_kind.clone(), params, services, config);
+        let mut ctx = self.context(tenant, method_kind.clone(), params)?;
         ctx.data = data;
 
         let method_name = method.to_string();
@@ -850,20 +1692,27 @@ where
 
         match ctx.result {
             Some(HookResult::One(v)) => Ok(v),
-            Some(HookResult::Many(_)) => Err(anyhow::anyhow!(
-                "custom() produced HookResult::Many unexpectedly"
+            Some(other) => Err(anyhow::anyhow!(
+                "custom() produced HookResult::{} unexpectedly",
+                other.variant_name()
             )),
             None => Err(anyhow::anyhow!("custom() produced no result")),
         }
     }
 }
 
+/// Access to other services from inside a call, as `ctx.services`.
+///
+/// Handles from [`ServiceCaller::handle`] carry the calling context's
+/// tenant and correlation id into the nested call; see
+/// [`ServiceHandle::cross_tenant`] for calls that must leave the tenant.
 pub struct ServiceCaller<R, P>
 where
     R: Send + 'static,
     P: Send + Clone + 'static,
 {
     app: DogApp<R, P>,
+    origin: Option<CallOrigin>,
 }
 
 impl<R, P> Clone for ServiceCaller<R, P>
@@ -874,6 +1723,7 @@ where
     fn clone(&self) -> Self {
         Self {
             app: self.app.clone(),
+            origin: self.origin.clone(),
         }
     }
 }
@@ -883,22 +1733,973 @@ where
     R: Send + 'static,
     P: Send + Clone + 'static,
 {
+    /// A caller outside of any call: handles from it keep no tenant.
     pub fn new(app: DogApp<R, P>) -> Self {
-        Self { app }
+        Self { app, origin: None }
+    }
+
+    /// Tenant of the call this caller belongs to; `None` for one made with
+    /// [`ServiceCaller::new`].
+    pub fn tenant(&self) -> Option<&TenantContext> {
+        self.origin.as_ref().map(|o| &o.tenant)
+    }
+
+    /// Correlation id of the call this caller belongs to, if it has one.
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.origin.as_ref()?.correlation_id.as_deref()
     }
 
     pub fn app(&self) -> &DogApp<R, P> {
         &self.app
     }
 
-    pub fn service(&self, name: &str) -> Result<Arc<dyn DogService<R, P>>> {
-        self.app
-            .inner
-            .registry
-            .read()
+    /// See [`DogApp::service`]. Like [`ServiceCaller::handle`], calls
+    /// through the handle run the service's hooks in the calling context's
+    /// tenant and carry its correlation id. Use [`ServiceHandle::inner`] to
+    /// reach the service itself, without hooks.
+    pub fn service(&self, name: &str) -> Result<ServiceHandle<R, P>> {
+        let mut handle = self.app.service(name)?;
+        handle.origin = self.origin.clone();
+        Ok(handle)
+    }
+
+    /// See [`DogApp::handle`]. Calls through the handle run in the calling
+    /// context's tenant and carry its correlation id.
+    pub fn handle(&self, service_ref: &ServiceRef<R, P>) -> ServiceHandle<R, P> {
+        let mut handle = self.app.handle(service_ref);
+        handle.origin = self.origin.clone();
+        handle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DogBeforeHook, HookContext, TenantContext};
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Named(&'static str);
+
+    #[async_trait]
+    impl DogService<String, ()> for Named {
+        async fn find(&self, _ctx: &TenantContext, _params: ()) -> Result<Vec<String>> {
+            Ok(vec![self.0.to_string()])
+        }
+    }
+
+    struct Count(Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl DogBeforeHook<String, ()> for Count {
+        async fn run(&self, _ctx: &mut HookContext<String, ()>) -> Result<()> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn app_with_counted_service(calls: &Arc<AtomicUsize>) -> DogApp<String, ()> {
+        let mut builder = DogApp::<String, ()>::builder();
+        builder.register_service("items", Arc::new(Named("real")));
+        builder.service_hooks("items", |h| {
+            h.before_all(Arc::new(Count(calls.clone())));
+        });
+        builder.build()
+    }
+
+    async fn find(handle: &ServiceHandle<String, ()>) -> Vec<String> {
+        handle.find(TenantContext::new("t"), ()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn deregister_removes_service_and_its_hooks() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app_with_counted_service(&calls);
+        let held = app.service("items").unwrap();
+
+        assert!(app.deregister_service("items").is_some());
+        assert!(app.deregister_service("items").is_none());
+        assert!(app.service("items").is_err());
+
+        // A handle taken before deregistration still reaches the old service,
+        // but the service hooks went away with the registration.
+        assert_eq!(find(&held).await, vec!["real"]);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        // Re-registering the name starts from a clean slate.
+        app.register_service("items", Arc::new(Named("fresh")));
+        assert_eq!(find(&app.service("items").unwrap()).await, vec!["fresh"]);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn replace_swaps_service_and_keeps_hooks() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app_with_counted_service(&calls);
+        let held = app.service("items").unwrap();
+
+        app.replace_service("items", Arc::new(Named("mock")))
+            .unwrap();
+
+        assert_eq!(find(&app.service("items").unwrap()).await, vec!["mock"]);
+        assert_eq!(find(&held).await, vec!["real"]);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let err = app
+            .replace_service("missing", Arc::new(Named("x")))
+            .err()
+            .expect("replacing an unknown service fails");
+        assert!(err.to_string().contains("DogService not found: missing"));
+        assert!(app.service("missing").is_err());
+    }
+
+    /// Counts its calls; `create` echoes the data.
+    struct Counted(Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl DogService<String, ()> for Counted {
+        async fn find(&self, _ctx: &TenantContext, _params: ()) -> Result<Vec<String>> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(vec!["fresh".to_string()])
+        }
+
+        async fn create(&self, _ctx: &TenantContext, data: String, _params: ()) -> Result<String> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(data)
+        }
+    }
+
+    /// Answers `find` from its cache when it can, without calling `next`.
+    struct CacheFind(std::sync::Mutex<Option<Vec<String>>>);
+
+    #[async_trait]
+    impl crate::DogAroundHook<String, ()> for CacheFind {
+        async fn run(
+            &self,
+            ctx: &mut HookContext<String, ()>,
+            next: Next<String, ()>,
+        ) -> Result<()> {
+            let hit = self.0.lock().unwrap().clone();
+            if let Some(records) = hit {
+                ctx.result = Some(HookResult::Many(records));
+                return Ok(());
+            }
+            next.run(ctx).await?;
+            if let Some(HookResult::Many(records)) = &ctx.result {
+                *self.0.lock().unwrap() = Some(records.clone());
+            }
+            Ok(())
+        }
+    }
+
+    /// Answers every `create` with a record it already has.
+    struct AlreadyCreated;
+
+    #[async_trait]
+    impl crate::DogAroundHook<String, ()> for AlreadyCreated {
+        async fn run(
+            &self,
+            ctx: &mut HookContext<String, ()>,
+            _next: Next<String, ()>,
+        ) -> Result<()> {
+            ctx.result = Some(HookResult::One("existing".to_string()));
+            Ok(())
+        }
+    }
+
+    struct CountAfter(Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl crate::DogAfterHook<String, ()> for CountAfter {
+        async fn run(&self, _ctx: &mut HookContext<String, ()>) -> Result<()> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    /// Records the `find` result it sees once the rest of the pipeline is done.
+    struct Outer(Arc<std::sync::Mutex<Vec<Vec<String>>>>);
+
+    #[async_trait]
+    impl crate::DogAroundHook<String, ()> for Outer {
+        async fn run(
+            &self,
+            ctx: &mut HookContext<String, ()>,
+            next: Next<String, ()>,
+        ) -> Result<()> {
+            next.run(ctx).await?;
+            if let Some(HookResult::Many(records)) = &ctx.result {
+                self.0.lock().unwrap().push(records.clone());
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn around_hook_without_next_skips_the_service() {
+        let service_calls = Arc::new(AtomicUsize::new(0));
+        let before_calls = Arc::new(AtomicUsize::new(0));
+        let after_calls = Arc::new(AtomicUsize::new(0));
+        let outer_seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let created = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let mut builder = DogApp::<String, ()>::builder();
+        builder.register_service("items", Arc::new(Counted(service_calls.clone())));
+        builder.service_hooks("items", |h| {
+            h.around_all(Arc::new(Outer(outer_seen.clone())));
+            h.around(
+                ServiceMethodKind::Find,
+                Arc::new(CacheFind(std::sync::Mutex::new(None))),
+            );
+            h.around(ServiceMethodKind::Create, Arc::new(AlreadyCreated));
+            h.before_all(Arc::new(Count(before_calls.clone())));
+            h.after_all(Arc::new(CountAfter(after_calls.clone())));
+        });
+        builder.on("items", ServiceEventKind::Created, {
+            let created = created.clone();
+            Arc::new(move |data, _ctx| {
+                if let ServiceEventData::Standard(HookResult::One(record)) = data {
+                    created.lock().unwrap().push(record.clone());
+                }
+                Box::pin(async { Ok(()) })
+            })
+        });
+        let items = builder.build().service("items").unwrap();
+
+        // Miss: the whole pipeline runs and the cache fills.
+        assert_eq!(find(&items).await, vec!["fresh"]);
+        // Hit: served by the around hook alone.
+        assert_eq!(find(&items).await, vec!["fresh"]);
+
+        assert_eq!(service_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(before_calls.load(Ordering::SeqCst), 1);
+        // After hooks run on the cached result too.
+        assert_eq!(after_calls.load(Ordering::SeqCst), 2);
+        // Outer around hooks still finish and see the cached result.
+        assert_eq!(
+            *outer_seen.lock().unwrap(),
+            vec![vec!["fresh"], vec!["fresh"]]
+        );
+
+        // A handled write returns the hook's result and still emits its event.
+        let stored = items
+            .create(TenantContext::new("t"), "new".to_string(), ())
+            .await
+            .unwrap();
+        assert_eq!(stored, "existing");
+        assert_eq!(service_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(after_calls.load(Ordering::SeqCst), 3);
+        assert_eq!(*created.lock().unwrap(), vec!["existing"]);
+    }
+
+    /// Removes records by id, or all of them when `id` is `None`.
+    struct Store(std::sync::Mutex<Vec<serde_json::Value>>);
+
+    #[async_trait]
+    impl DogService<serde_json::Value, ()> for Store {
+        async fn remove(
+            &self,
+            _ctx: &TenantContext,
+            id: Option<&str>,
+            _params: (),
+        ) -> Result<serde_json::Value> {
+            let mut records = self.0.lock().unwrap();
+            match id {
+                Some(id) => {
+                    let at = records
+                        .iter()
+                        .position(|r| r["id"] == id)
+                        .ok_or_else(|| anyhow::anyhow!("no record {id}"))?;
+                    Ok(records.remove(at))
+                }
+                None => Ok(serde_json::Value::Array(std::mem::take(&mut *records))),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn remove_emits_one_removed_event_with_the_result() {
+        use serde_json::json;
+
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut builder = DogApp::<serde_json::Value, ()>::builder();
+        builder.register_service(
+            "items",
+            Arc::new(Store(std::sync::Mutex::new(vec![
+                json!({ "id": "1" }),
+                json!({ "id": "2" }),
+                json!({ "id": "3" }),
+            ]))),
+        );
+        builder
+            .on_str("items removed", {
+                let seen = Arc::clone(&seen);
+                Arc::new(move |data, _ctx| {
+                    if let ServiceEventData::Standard(HookResult::One(record)) = data {
+                        seen.lock().unwrap().push(record.clone());
+                    }
+                    Box::pin(async { Ok(()) })
+                })
+            })
+            .unwrap();
+        let app = builder.build();
+        let items = app.service("items").unwrap();
+        let tenant = || TenantContext::new("t");
+
+        let removed = items.remove(tenant(), Some("2"), ()).await.unwrap();
+        assert_eq!(removed, json!({ "id": "2" }));
+        assert_eq!(*seen.lock().unwrap(), vec![json!({ "id": "2" })]);
+
+        // A failed remove emits nothing.
+        assert!(items.remove(tenant(), Some("2"), ()).await.is_err());
+        assert_eq!(seen.lock().unwrap().len(), 1);
+
+        // A batch remove emits a single event with every removed record.
+        items.remove(tenant(), None, ()).await.unwrap();
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![json!({ "id": "2" }), json!([{ "id": "1" }, { "id": "3" }]),]
+        );
+    }
+
+    /// Inserts batches in one go and records how many it got each time.
+    struct BatchInsert(std::sync::Mutex<Vec<usize>>);
+
+    #[async_trait]
+    impl DogService<String, ()> for BatchInsert {
+        async fn create_many(
+            &self,
+            _ctx: &TenantContext,
+            data: Vec<String>,
+            _params: (),
+        ) -> Result<Vec<String>> {
+            self.0.lock().unwrap().push(data.len());
+            Ok(data)
+        }
+    }
+
+    struct UppercaseBatch;
+
+    #[async_trait]
+    impl DogBeforeHook<String, ()> for UppercaseBatch {
+        async fn run(&self, ctx: &mut HookContext<String, ()>) -> Result<()> {
+            assert!(ctx.data.is_none());
+            for record in ctx.data_many.as_mut().unwrap() {
+                *record = record.to_uppercase();
+            }
+            Ok(())
+        }
+
+        fn handles_batches(&self) -> bool {
+            true
+        }
+    }
+
+    /// Written for single creates only.
+    struct RejectEmpty;
+
+    #[async_trait]
+    impl DogBeforeHook<String, ()> for RejectEmpty {
+        async fn run(&self, ctx: &mut HookContext<String, ()>) -> Result<()> {
+            let Some(data) = ctx.data.as_ref() else {
+                return Ok(());
+            };
+            if data.is_empty() {
+                return Err(crate::DogError::bad_request("empty").into_anyhow());
+            }
+            Ok(())
+        }
+    }
+
+    struct CountResults(Arc<std::sync::Mutex<Vec<usize>>>);
+
+    #[async_trait]
+    impl crate::DogAfterHook<String, ()> for CountResults {
+        async fn run(&self, ctx: &mut HookContext<String, ()>) -> Result<()> {
+            if let Some(HookResult::Many(records)) = &ctx.result {
+                self.0.lock().unwrap().push(records.len());
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn create_many_runs_the_pipeline_once_per_batch() {
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let after = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let service = Arc::new(BatchInsert(std::sync::Mutex::new(Vec::new())));
+        let before = Arc::new(AtomicUsize::new(0));
+
+        let mut builder = DogApp::<String, ()>::builder();
+        builder.register_service("items", service.clone());
+        builder.service_hooks("items", |h| {
+            h.before_create(Arc::new(Count(before.clone())));
+            h.before_create(Arc::new(UppercaseBatch));
+            h.after_create(Arc::new(CountResults(after.clone())));
+        });
+        builder
+            .on_str("items created", {
+                let events = Arc::clone(&events);
+                Arc::new(move |data, _ctx| {
+                    if let ServiceEventData::Standard(HookResult::Many(records)) = data {
+                        events.lock().unwrap().push(records.clone());
+                    }
+                    Box::pin(async { Ok(()) })
+                })
+            })
+            .unwrap();
+        let app = builder.build();
+
+        let created = app
+            .service("items")
             .unwrap()
-            .get(name)
-            .cloned()
-            .ok_or_else(|| anyhow::anyhow!("DogService not found: {name}"))
+            .create_many(
+                TenantContext::new("t"),
+                vec!["a".into(), "b".into(), "c".into()],
+                (),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(created, vec!["A", "B", "C"]);
+        // Once per record: `Count` doesn't handle batches.
+        assert_eq!(before.load(Ordering::SeqCst), 3);
+        assert_eq!(*after.lock().unwrap(), vec![3]);
+        assert_eq!(*service.0.lock().unwrap(), vec![3]);
+        assert_eq!(*events.lock().unwrap(), vec![vec!["A", "B", "C"]]);
+    }
+
+    #[tokio::test]
+    async fn single_record_validators_check_every_record_of_a_batch() {
+        let service = Arc::new(BatchInsert(std::sync::Mutex::new(Vec::new())));
+        let mut builder = DogApp::<String, ()>::builder();
+        builder.register_service("items", service.clone());
+        builder.service_hooks("items", |h| {
+            h.before_create(Arc::new(RejectEmpty));
+        });
+        let app = builder.build();
+
+        let err = app
+            .service("items")
+            .unwrap()
+            .create_many(
+                TenantContext::new("t"),
+                vec!["a".into(), "".into(), "c".into()],
+                (),
+            )
+            .await
+            .unwrap_err();
+
+        assert_eq!(crate::DogError::from_anyhow(&err).unwrap().message, "empty");
+        assert!(service.0.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn remove_many_defaults_to_one_remove_per_id() {
+        use serde_json::json;
+
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut builder = DogApp::<serde_json::Value, ()>::builder();
+        builder.register_service(
+            "items",
+            Arc::new(Store(std::sync::Mutex::new(vec![
+                json!({ "id": "1" }),
+                json!({ "id": "2" }),
+                json!({ "id": "3" }),
+            ]))),
+        );
+        builder
+            .on_str("items removed", {
+                let seen = Arc::clone(&seen);
+                Arc::new(move |data, _ctx| {
+                    if let ServiceEventData::Standard(HookResult::Many(records)) = data {
+                        seen.lock().unwrap().push(records.clone());
+                    }
+                    Box::pin(async { Ok(()) })
+                })
+            })
+            .unwrap();
+        let app = builder.build();
+        let items = app.service("items").unwrap();
+
+        let removed = items
+            .remove_many(TenantContext::new("t"), &["3", "1"], ())
+            .await
+            .unwrap();
+        assert_eq!(removed, vec![json!({ "id": "3" }), json!({ "id": "1" })]);
+        assert_eq!(*seen.lock().unwrap(), vec![removed]);
+    }
+
+    /// Knows no records: every lookup or write is a `NotFound`, except id
+    /// `bad`, which is a `BadRequest`.
+    struct Empty;
+
+    #[async_trait]
+    impl DogService<String, ()> for Empty {
+        async fn get(&self, _ctx: &TenantContext, id: &str, _params: ()) -> Result<String> {
+            Err(missing(id))
+        }
+
+        async fn create(&self, _ctx: &TenantContext, data: String, _params: ()) -> Result<String> {
+            Err(missing(&data))
+        }
+
+        async fn patch(
+            &self,
+            _ctx: &TenantContext,
+            id: Option<&str>,
+            _data: String,
+            _params: (),
+        ) -> Result<String> {
+            Err(missing(id.unwrap_or_default()))
+        }
+    }
+
+    fn missing(id: &str) -> anyhow::Error {
+        if id == "bad" {
+            crate::DogError::bad_request("bad id").into()
+        } else {
+            crate::DogError::not_found(format!("no record {id}")).into()
+        }
+    }
+
+    /// Answers a `NotFound` with a default record.
+    struct DefaultOnNotFound;
+
+    #[async_trait]
+    impl crate::DogErrorHook<String, ()> for DefaultOnNotFound {
+        async fn run(&self, ctx: &mut HookContext<String, ()>) -> Result<()> {
+            let not_found = ctx
+                .error
+                .as_ref()
+                .and_then(crate::DogError::from_anyhow)
+                .is_some_and(|e| e.kind == crate::ErrorKind::NotFound);
+            if not_found {
+                ctx.error = None;
+                ctx.result = Some(HookResult::One("default".to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn error_hook_can_recover_with_a_result() {
+        let patched = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut builder = DogApp::<String, ()>::builder();
+        builder.register_service("items", Arc::new(Empty));
+        builder.service_hooks("items", |h| {
+            h.error_all(Arc::new(DefaultOnNotFound));
+        });
+        builder.on("items", ServiceEventKind::Patched, {
+            let patched = patched.clone();
+            Arc::new(move |data, _ctx| {
+                if let ServiceEventData::Standard(HookResult::One(record)) = data {
+                    patched.lock().unwrap().push(record.clone());
+                }
+                Box::pin(async { Ok(()) })
+            })
+        });
+        let items = builder.build().service("items").unwrap();
+        let tenant = || TenantContext::new("t");
+
+        assert_eq!(items.get(tenant(), "1", ()).await.unwrap(), "default");
+
+        let record = items
+            .patch(tenant(), Some("1"), "x".to_string(), ())
+            .await
+            .unwrap();
+        assert_eq!(record, "default");
+        assert_eq!(*patched.lock().unwrap(), vec!["default"]);
+
+        // Errors the hook leaves alone still fail the call.
+        let err = items.get(tenant(), "bad", ()).await.unwrap_err();
+        let err = crate::DogError::from_anyhow(&err).unwrap();
+        assert_eq!(err.kind, crate::ErrorKind::BadRequest);
+    }
+
+    #[tokio::test]
+    async fn unrecovered_failure_emits_one_error_event() {
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut builder = DogApp::<String, ()>::builder();
+        builder.register_service("items", Arc::new(Empty));
+        builder.service_hooks("items", |h| {
+            h.error_all(Arc::new(DefaultOnNotFound));
+        });
+        builder
+            .on_str("*.error", {
+                let seen = seen.clone();
+                Arc::new(move |data, _ctx| {
+                    if let ServiceEventData::Error(report) = data {
+                        seen.lock().unwrap().push((*report).clone());
+                    }
+                    Box::pin(async { Ok(()) })
+                })
+            })
+            .unwrap();
+        let items = builder.build().service("items").unwrap();
+        let tenant = || TenantContext::new("t");
+
+        assert!(items.create(tenant(), "bad".to_string(), ()).await.is_err());
+        // Recovered by the error hook: no event.
+        assert_eq!(items.get(tenant(), "1", ()).await.unwrap(), "default");
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![crate::ServiceErrorEvent {
+                service: "items".to_string(),
+                method: ServiceMethodKind::Create,
+                kind: crate::ErrorKind::BadRequest,
+                message: "bad id".to_string(),
+            }]
+        );
+    }
+
+    type Origins = Arc<std::sync::Mutex<Vec<(String, Option<String>)>>>;
+
+    /// Records the tenant and correlation id of every call it sees.
+    struct SeeOrigin(Origins);
+
+    #[async_trait]
+    impl DogBeforeHook<String, ()> for SeeOrigin {
+        async fn run(&self, ctx: &mut HookContext<String, ()>) -> Result<()> {
+            self.0
+                .lock()
+                .unwrap()
+                .push((ctx.tenant.tenant_id.0.clone(), ctx.correlation_id.clone()));
+            Ok(())
+        }
+    }
+
+    /// Calls `audit` from inside the `items` pipeline, for `tenant` or the
+    /// calling one.
+    struct CallAudit {
+        audit: ServiceRef<String, ()>,
+        tenant: Option<&'static str>,
+        cross_tenant: bool,
+    }
+
+    #[async_trait]
+    impl DogBeforeHook<String, ()> for CallAudit {
+        async fn run(&self, ctx: &mut HookContext<String, ()>) -> Result<()> {
+            let tenant = self
+                .tenant
+                .map_or_else(|| ctx.tenant.clone(), TenantContext::new);
+            let mut audit = ctx.services.handle(&self.audit);
+            if self.cross_tenant {
+                audit = audit.cross_tenant();
+            }
+            audit.find(tenant, ()).await?;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn nested_calls_inherit_tenant_and_correlation_id() {
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let run = |tenant: Option<&'static str>, cross_tenant: bool| {
+            let seen = seen.clone();
+            async move {
+                let mut builder = DogApp::<String, ()>::builder();
+                let audit = builder.register_service("audit", Arc::new(Named("audit")));
+                builder.register_service("items", Arc::new(Named("items")));
+                builder.service_hooks("audit", |h| {
+                    h.before_all(Arc::new(SeeOrigin(seen)));
+                });
+                builder.service_hooks("items", |h| {
+                    h.before_all(Arc::new(CallAudit {
+                        audit,
+                        tenant,
+                        cross_tenant,
+                    }));
+                });
+                let request =
+                    RequestMeta::new("rest", "GET", "/items").with_header("x-request-id", "req-1");
+                builder
+                    .build()
+                    .service("items")
+                    .unwrap()
+                    .with_request(request)
+                    .find(TenantContext::new("acme"), ())
+                    .await
+            }
+        };
+
+        run(None, false).await.unwrap();
+        assert_eq!(
+            seen.lock().unwrap().pop(),
+            Some(("acme".to_string(), Some("req-1".to_string())))
+        );
+
+        let err = run(Some("other"), false).await.unwrap_err();
+        let err = crate::DogError::from_anyhow(&err).unwrap();
+        assert_eq!(err.kind, crate::ErrorKind::Forbidden);
+        assert!(seen.lock().unwrap().is_empty());
+
+        run(Some("other"), true).await.unwrap();
+        assert_eq!(
+            seen.lock().unwrap().pop(),
+            Some(("other".to_string(), Some("req-1".to_string())))
+        );
+    }
+
+    /// Calls `audit` by name from inside the `items` pipeline.
+    struct CallAuditByName;
+
+    #[async_trait]
+    impl DogBeforeHook<String, ()> for CallAuditByName {
+        async fn run(&self, ctx: &mut HookContext<String, ()>) -> Result<()> {
+            let audit = ctx.services.service("audit")?;
+            audit.find(ctx.tenant.clone(), ()).await?;
+            let err = audit
+                .find(TenantContext::new("other"), ())
+                .await
+                .unwrap_err();
+            let err = crate::DogError::from_anyhow(&err).unwrap();
+            assert_eq!(err.kind, crate::ErrorKind::Forbidden);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn services_looked_up_by_name_run_hooks_in_the_calling_tenant() {
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut builder = DogApp::<String, ()>::builder();
+        builder.register_service("audit", Arc::new(Named("audit")));
+        builder.register_service("items", Arc::new(Named("items")));
+        builder.service_hooks("audit", |h| {
+            h.before_all(Arc::new(SeeOrigin(seen.clone())));
+        });
+        builder.service_hooks("items", |h| {
+            h.before_all(Arc::new(CallAuditByName));
+        });
+
+        builder
+            .build()
+            .service("items")
+            .unwrap()
+            .with_correlation_id("trace-7")
+            .find(TenantContext::new("acme"), ())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![("acme".to_string(), Some("trace-7".to_string()))]
+        );
+    }
+
+    /// Lists records but advertises no writes.
+    struct ReadOnly;
+
+    #[async_trait]
+    impl DogService<String, ()> for ReadOnly {
+        fn capabilities(&self) -> crate::ServiceCapabilities {
+            crate::ServiceCapabilities::from_methods(vec![ServiceMethodKind::Find])
+        }
+
+        async fn find(&self, _ctx: &TenantContext, _params: ()) -> Result<Vec<String>> {
+            Ok(vec!["a".to_string()])
+        }
+
+        async fn create(&self, _ctx: &TenantContext, data: String, _params: ()) -> Result<String> {
+            Ok(data)
+        }
+    }
+
+    #[tokio::test]
+    async fn unsupported_method_is_rejected_before_hooks() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut builder = DogApp::<String, ()>::builder();
+        builder.register_service("items", Arc::new(ReadOnly));
+        builder.service_hooks("items", |h| {
+            h.before_all(Arc::new(Count(calls.clone())));
+        });
+        let items = builder.build().service("items").unwrap();
+
+        let err = items
+            .create(TenantContext::new("t"), "x".to_string(), ())
+            .await
+            .unwrap_err();
+        let err = crate::DogError::from_anyhow(&err).unwrap();
+        assert_eq!(err.kind, crate::ErrorKind::MethodNotAllowed);
+        assert_eq!(err.code(), 405);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        assert_eq!(find(&items).await, vec!["a"]);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn runtime_set_notifies_watchers_outside_the_lock() {
+        let mut builder = DogApp::<String, ()>::builder();
+        builder.set("maintenance", "false");
+        let app = builder.build();
+
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = seen.clone();
+        let reader = app.clone();
+        app.watch("maintenance", move |old, new| {
+            // Reading the app's config from a watcher must not deadlock.
+            let current: Option<String> = reader.get("maintenance");
+            log.lock()
+                .unwrap()
+                .push((old.map(str::to_string), new.to_string(), current));
+        });
+
+        app.set("maintenance", "true");
+        app.set("other", "x");
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![(
+                Some("false".to_string()),
+                "true".to_string(),
+                Some("true".to_string())
+            )]
+        );
+        assert_eq!(app.get_as::<bool>("maintenance"), Some(true));
+    }
+
+    #[test]
+    fn concurrent_sets_notify_watchers_in_write_order() {
+        let mut builder = DogApp::<String, ()>::builder();
+        builder.set("mode", "start");
+        let app = builder.build();
+
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = seen.clone();
+        app.watch("mode", move |old, new| {
+            log.lock()
+                .unwrap()
+                .push((old.unwrap().to_string(), new.to_string()));
+        });
+
+        std::thread::scope(|scope| {
+            for thread in 0..4 {
+                let app = app.clone();
+                scope.spawn(move || {
+                    for i in 0..200 {
+                        app.set("mode", format!("{thread}-{i}"));
+                    }
+                });
+            }
+        });
+
+        // Each change starts from the value the previous one wrote.
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 800);
+        assert_eq!(seen[0].0, "start");
+        for pair in seen.windows(2) {
+            assert_eq!(pair[0].1, pair[1].0);
+        }
+        assert_eq!(
+            app.get::<String>("mode").as_deref(),
+            Some(seen[799].1.as_str())
+        );
+    }
+
+    #[test]
+    fn watcher_may_set_config_itself() {
+        let app = DogApp::<String, ()>::builder().build();
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = seen.clone();
+        let setter = app.clone();
+        app.watch("a", move |_, new| {
+            setter.set("b", format!("from {new}"));
+        });
+        app.watch("b", move |_, new| log.lock().unwrap().push(new.to_string()));
+
+        app.set("a", "1");
+        app.set("a", "2");
+
+        assert_eq!(*seen.lock().unwrap(), vec!["from 1", "from 2"]);
+    }
+
+    struct SetPortMidCall(DogApp<String, ()>);
+
+    #[async_trait]
+    impl DogService<String, ()> for SetPortMidCall {
+        async fn find(&self, _ctx: &TenantContext, _params: ()) -> Result<Vec<String>> {
+            self.0.set("http.port", "4000");
+            Ok(vec![])
+        }
+    }
+
+    struct SeePort(Arc<std::sync::Mutex<Vec<Option<u16>>>>);
+
+    #[async_trait]
+    impl DogBeforeHook<String, ()> for SeePort {
+        async fn run(&self, ctx: &mut HookContext<String, ()>) -> Result<()> {
+            self.0.lock().unwrap().push(ctx.config.get_as("http.port"));
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl crate::DogAfterHook<String, ()> for SeePort {
+        async fn run(&self, ctx: &mut HookContext<String, ()>) -> Result<()> {
+            self.0.lock().unwrap().push(ctx.config.get_as("http.port"));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn in_flight_call_keeps_the_config_it_started_with() {
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut builder = DogApp::<String, ()>::builder();
+        builder.set("http.port", "3030");
+        builder.service_hooks("items", |h| {
+            h.before_all(Arc::new(SeePort(seen.clone())));
+            h.after_all(Arc::new(SeePort(seen.clone())));
+        });
+        let app = builder.build();
+        app.register_service("items", Arc::new(SetPortMidCall(app.clone())));
+
+        let items = app.service("items").unwrap();
+        find(&items).await;
+        // The service changed the port between the before and after hooks.
+        assert_eq!(*seen.lock().unwrap(), vec![Some(3030), Some(3030)]);
+        assert_eq!(app.get_as::<u16>("http.port"), Some(4000));
+
+        find(&items).await;
+        assert_eq!(seen.lock().unwrap()[2..], [Some(4000), Some(4000)]);
+    }
+
+    #[tokio::test]
+    async fn service_ref_calls_without_a_lookup() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut builder = DogApp::<String, ()>::builder();
+        let items = builder.register_service("items", Arc::new(Named("real")));
+        builder.service_hooks("items", |h| {
+            h.before_all(Arc::new(Count(calls.clone())));
+        });
+        let app = builder.build();
+
+        assert_eq!(items.name(), "items");
+        assert_eq!(find(&app.handle(&items)).await, vec!["real"]);
+        assert_eq!(
+            find(&ServiceCaller::new(app.clone()).handle(&items)).await,
+            vec!["real"]
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let looked_up = app.service_ref("items").unwrap();
+        assert_eq!(find(&app.handle(&looked_up)).await, vec!["real"]);
+        assert!(app.service_ref("missing").is_err());
+    }
+
+    #[tokio::test]
+    async fn service_ref_follows_replacements() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app_with_counted_service(&calls);
+        let items = app.service_ref("items").unwrap();
+
+        app.replace_service("items", Arc::new(Named("mock")))
+            .unwrap();
+        assert_eq!(find(&app.handle(&items)).await, vec!["mock"]);
+
+        // Once deregistered, the ref still reaches the service it was made from.
+        app.deregister_service("items");
+        assert_eq!(find(&app.handle(&items)).await, vec!["real"]);
     }
 }
@@ -16,53 +16,245 @@
 //! assert_eq!(app.get("paginate.default"), Some("10".to_string()));
 //! ```
 //!
-//! ## Environment overrides
-//! DogRS core is intentionally environment-agnostic. Applications
-//! may choose to load environment variables using any convention.
+//! ## Typed values
+//! Values set through the string API are stored as strings and parsed on
+//! read with [`DogConfig::get_as`], so call sites no longer need their own
+//! `.parse().unwrap_or(default)` chains:
 //!
-//! Here is a recommended helper:
+//! ```rust,ignore
+//! let timeout: u64 = app.get_as("tomtom.route.timeout").unwrap_or(15);
+//! ```
+//!
+//! With the `json` feature (on by default), structured values can be stored
+//! directly with `set_value` and read back with `get_json`:
 //!
 //! ```rust,ignore
-//! use dog_core::DogApp;
-//! pub fn load_env_config<R, P>(app: &mut DogApp<R, P>, prefix: &str)
-//! where
-//!     R: Send + 'static,
-//!     P: Send + Clone + 'static,
-//! {
-//!     for (key, value) in std::env::vars() {
-//!         if let Some(stripped) = key.strip_prefix(prefix) {
-//!             let normalized = stripped
-//!                 .to_lowercase()
-//!                 .replace("__", "."); // ADSDOG__PAGINATE__DEFAULT → paginate.default
-//!
-//!             app.set(normalized, value);
-//!         }
-//!     }
+//! app.set_value("paginate.max", serde_json::json!(50));
+//! app.set_value("cors.origins", serde_json::json!(["https://a.example"]));
+//!
+//! let max: Option<usize> = app.get_as("paginate.max");
+//! let origins: Option<Vec<String>> = app.get_json("cors.origins");
+//! ```
+//!
+//! Every entry keeps a string rendering, so `get` keeps working for typed
+//! values: JSON strings render without quotes, everything else renders as
+//! compact JSON (`50`, `true`, `["https://a.example"]`).
+//!
+//! ## Sections
+//! A whole dotted sub-tree can be read as one struct with `get_section`:
+//!
+//! ```rust,ignore
+//! #[derive(serde::Deserialize)]
+//! struct RouteConfig {
+//!     #[serde(default = "default_timeout")]
+//!     timeout: u64,
+//! }
+//!
+//! #[derive(serde::Deserialize)]
+//! struct TomTomConfig {
+//!     key: String,
+//!     #[serde(rename = "baseUrl")]
+//!     base_url: String,
+//!     route: RouteConfig,
 //! }
+//!
+//! // tomtom.key, tomtom.baseUrl, tomtom.route.timeout
+//! let tomtom: TomTomConfig = app.get_section("tomtom")?;
 //! ```
 //!
-//! Applications can now override configuration using:
+//! Each `.`-separated segment below the section becomes an object key.
+//! Objects stored with `set_value` are expanded into the same tree, and when
+//! entries overlap the more specific key wins (`tomtom.route.timeout`
+//! overrides a `timeout` inside an object stored at `tomtom.route`). String
+//! leaves are parsed leniently: `"15"` fills a `u64` field, while a `String`
+//! field always receives the raw text. Flat `get`/`set` are unaffected.
+//!
+//! ## Environment and file sources
+//! Configuration can also be loaded from environment variables and files
+//! and layered onto an app with `DogAppBuilder::configure_from`:
 //!
-//! ```bash
-//! export ADSDOG__PAGINATE__DEFAULT=25
+//! ```rust,ignore
+//! use dog_core::ConfigSource;
+//!
+//! let mut app = DogApp::<(), ()>::builder();
+//! app.configure_from([
+//!     ConfigSource::toml_file("config/default.toml"),
+//!     ConfigSource::env("DOG"),
+//! ])?;
+//! app.set("paginate.max", "100");
 //! ```
 //!
+//! `ConfigSource::env("DOG")` maps `DOG_HTTP__PORT=3030` to `http.port`:
+//! the prefix and the `_` after it are stripped, the rest is lowercased and
+//! every `__` becomes a `.` (a single `_` is kept, so
+//! `DOG_TOMTOM__ROUTE_TIMEOUT` is `tomtom.route_timeout`). Env values are
+//! stored as strings; read them with `get_as`/`get_json`.
+//!
+//! Files are flattened into dotted keys (`[http] port = 3030` becomes
+//! `http.port`) and keep their JSON types. Arrays are stored whole under
+//! their key. JSON files need the `json` feature (on by default) and TOML
+//! files the `toml` feature.
+//!
+//! Precedence does not depend on call order: **file < env < explicit
+//! `set`/`set_value`**. An env value never overwrites one set in code, and
+//! a file never overwrites either; among sources of the same kind, the one
+//! loaded last wins.
+//!
+//! ## Watching for changes
+//! Code that reconfigures itself at runtime (feature flags, maintenance
+//! mode) can register a callback with `watch`. It runs after every `set` or
+//! `set_value` that changes the key, with the old and new string values:
+//!
+//! ```rust,ignore
+//! app.watch("maintenance", |old, new| {
+//!     tracing::info!("maintenance: {old:?} -> {new}");
+//! });
+//!
+//! app.set("maintenance", "true"); // on the built DogApp, from any thread
+//! ```
+//!
+//! Setting a key to the value it already has does not notify, and values
+//! layered in with `merge`/`configure_from` never do. On a built `DogApp`
+//! the callbacks run after the config lock is released, so they may read
+//! or set the app's config, and they see concurrent changes in the order
+//! they were written.
+//!
 //! ## Why this design?
 //! - Works in any environment (cloud, edge, P2P, serverless)
-//! - No dependency on TOML/JSON/YAML formats
+//! - The core store does not depend on TOML/JSON/YAML formats
 //! - Zero stack lock-in
 //! - Multi-tenant friendly
 //! - Mirrors Feathers’ configuration style in a Rust-friendly way
 //!
-//! Higher-level loaders (TOML, JSON, Consul, Vault, etc.) are
-//! intentionally kept *out* of DogRS so each application remains
-//! free to choose its configuration strategy.
+//! The built-in loaders cover env vars and local files only. Remote stores
+//! (Consul, Vault, etc.) are left to applications: load them into a
+//! `DogConfig` and hand it to `ConfigSource::config`.
+
+#[cfg(feature = "json")]
+mod section;
+mod source;
+
+pub use source::ConfigSource;
 
 use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
 
-#[derive(Debug, Default)]
+/// Where a configuration entry came from, lowest precedence first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Origin {
+    /// File sources need the `json` feature
+    #[cfg(feature = "json")]
+    File,
+    Env,
+    Explicit,
+}
+
+/// A single configuration entry.
+///
+/// `raw` is the string view returned by `get`. When the entry was written
+/// through `set_value` or loaded from a file, `value` keeps the original
+/// JSON so `get_json` can deserialize it without a string round-trip.
+#[derive(Debug, Clone, PartialEq)]
+struct ConfigEntry {
+    raw: String,
+    #[cfg(feature = "json")]
+    value: Option<serde_json::Value>,
+    origin: Origin,
+}
+
+impl ConfigEntry {
+    fn from_string(raw: String, origin: Origin) -> Self {
+        Self {
+            raw,
+            #[cfg(feature = "json")]
+            value: None,
+            origin,
+        }
+    }
+
+    #[cfg(feature = "json")]
+    fn from_value(value: serde_json::Value, origin: Origin) -> Self {
+        let raw = match &value {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        Self {
+            raw,
+            value: Some(value),
+            origin,
+        }
+    }
+
+    fn parse<T: FromStr>(&self) -> Option<T> {
+        self.raw.trim().parse::<T>().ok()
+    }
+
+    /// JSON view of the entry.
+    ///
+    /// Typed entries return their stored value. String entries are parsed
+    /// as JSON first (so `"8080"` reads as a number and `"[1,2]"` as an
+    /// array) and fall back to a JSON string when they are not valid JSON.
+    #[cfg(feature = "json")]
+    fn json(&self) -> serde_json::Value {
+        match &self.value {
+            Some(v) => v.clone(),
+            None => serde_json::from_str(&self.raw)
+                .unwrap_or_else(|_| serde_json::Value::String(self.raw.clone())),
+        }
+    }
+
+    #[cfg(feature = "json")]
+    fn deserialize<T: serde::de::DeserializeOwned>(&self) -> Option<T> {
+        if let Ok(v) = serde_json::from_value(self.json()) {
+            return Some(v);
+        }
+        // A string that happens to be valid JSON (e.g. "10") must still
+        // deserialize into `String` when that is what the caller asked for.
+        serde_json::from_value(serde_json::Value::String(self.raw.clone())).ok()
+    }
+}
+
+/// Callback registered with [`DogConfig::watch`].
+///
+/// Called with the previous string value (`None` if the key was unset) and
+/// the new one.
+pub type ConfigWatcher = Arc<dyn Fn(Option<&str>, &str) + Send + Sync>;
+
+/// A change made by `set`/`set_value`, with the watchers to tell about it.
+///
+/// Split from the write so `DogApp` can notify after dropping its lock.
+#[must_use]
+pub(crate) struct ConfigChange {
+    old: Option<String>,
+    new: String,
+    watchers: Vec<ConfigWatcher>,
+}
+
+impl ConfigChange {
+    pub(crate) fn notify(self) {
+        for watcher in &self.watchers {
+            watcher(self.old.as_deref(), &self.new);
+        }
+    }
+}
+
+#[derive(Default)]
 pub struct DogConfig {
-    values: HashMap<String, String>,
+    values: HashMap<String, ConfigEntry>,
+    watchers: HashMap<String, Vec<ConfigWatcher>>,
+}
+
+impl std::fmt::Debug for DogConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DogConfig")
+            .field("values", &self.values)
+            .field(
+                "watchers",
+                &self.watchers.values().map(Vec::len).sum::<usize>(),
+            )
+            .finish()
+    }
 }
 
 impl DogConfig {
@@ -70,7 +262,55 @@ impl DogConfig {
     pub fn new() -> Self {
         Self {
             values: HashMap::new(),
+            watchers: HashMap::new(),
+        }
+    }
+
+    /// Call `watcher` whenever `key` is changed with `set` or `set_value`.
+    ///
+    /// The watcher gets the old string value (`None` if the key was unset)
+    /// and the new one. Writing the value a key already has does not notify,
+    /// and neither does `merge`.
+    pub fn watch<K, F>(&mut self, key: K, watcher: F)
+    where
+        K: Into<String>,
+        F: Fn(Option<&str>, &str) + Send + Sync + 'static,
+    {
+        self.watchers
+            .entry(key.into())
+            .or_default()
+            .push(Arc::new(watcher));
+    }
+
+    /// `set` without notifying: the caller reports the returned change.
+    pub(crate) fn set_deferred(&mut self, key: String, value: String) -> Option<ConfigChange> {
+        self.insert_explicit(key, ConfigEntry::from_string(value, Origin::Explicit))
+    }
+
+    /// `set_value` without notifying: the caller reports the returned change.
+    #[cfg(feature = "json")]
+    pub(crate) fn set_value_deferred(
+        &mut self,
+        key: String,
+        value: serde_json::Value,
+    ) -> Option<ConfigChange> {
+        self.insert_explicit(key, ConfigEntry::from_value(value, Origin::Explicit))
+    }
+
+    /// Store an explicit entry, returning the change to report if the value
+    /// differs from the previous one and anyone watches the key.
+    fn insert_explicit(&mut self, key: String, entry: ConfigEntry) -> Option<ConfigChange> {
+        let watchers = self.watchers.get(&key).cloned().unwrap_or_default();
+        let new = entry.raw.clone();
+        let previous = self.values.insert(key, entry);
+        if watchers.is_empty() || previous.as_ref().is_some_and(|p| p.raw == new) {
+            return None;
         }
+        Some(ConfigChange {
+            old: previous.map(|p| p.raw),
+            new,
+            watchers,
+        })
     }
 
     /// Set a configuration key to a string value.
@@ -81,48 +321,480 @@ impl DogConfig {
         K: Into<String>,
         V: Into<String>,
     {
-        self.values.insert(key.into(), value.into());
+        if let Some(change) = self.set_deferred(key.into(), value.into()) {
+            change.notify();
+        }
+    }
+
+    /// Set a configuration key to a typed JSON value.
+    ///
+    /// Example: app.set_value("paginate.max", json!(50))
+    #[cfg(feature = "json")]
+    pub fn set_value<K>(&mut self, key: K, value: serde_json::Value)
+    where
+        K: Into<String>,
+    {
+        if let Some(change) = self.set_value_deferred(key.into(), value) {
+            change.notify();
+        }
     }
 
     /// Get a configuration value by key.
     ///
     /// Returns None if the key is not present.
     pub fn get(&self, key: &str) -> Option<&str> {
-        self.values.get(key).map(|s| s.as_str())
+        self.values.get(key).map(|e| e.raw.as_str())
+    }
+
+    /// Parse a configuration value with `FromStr`.
+    ///
+    /// Returns None if the key is missing or does not parse as `T`.
+    /// Surrounding whitespace is ignored, so `" 10 "` reads as `10`.
+    pub fn get_as<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.values.get(key).and_then(ConfigEntry::parse)
+    }
+
+    /// Get the JSON view of a configuration value.
+    ///
+    /// See [`DogConfig::get_json`] for how string entries are interpreted.
+    #[cfg(feature = "json")]
+    pub fn get_value(&self, key: &str) -> Option<serde_json::Value> {
+        self.values.get(key).map(ConfigEntry::json)
+    }
+
+    /// Deserialize a configuration value into `T`.
+    ///
+    /// Entries written with `set_value` deserialize from their stored JSON.
+    /// Entries written with `set` are parsed as JSON first and otherwise
+    /// treated as a JSON string, so both `"8080"` → `u16` and
+    /// `"localhost"` → `String` work. Returns None on a type mismatch.
+    #[cfg(feature = "json")]
+    pub fn get_json<T: serde::de::DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.values.get(key).and_then(ConfigEntry::deserialize)
+    }
+
+    /// Deserialize every `prefix.*` key into `T`.
+    ///
+    /// `get_section::<TomTomConfig>("tomtom")` reads `tomtom.key`,
+    /// `tomtom.route.timeout`, ... as `{ "key": .., "route": { "timeout": .. } }`.
+    /// Missing keys are left to the struct's serde defaults, so a section with
+    /// no entries at all still loads when every field has a default. See the
+    /// module docs for how overlapping keys and string leaves are handled.
+    #[cfg(feature = "json")]
+    pub fn get_section<T: serde::de::DeserializeOwned>(&self, prefix: &str) -> anyhow::Result<T> {
+        section::deserialize_section(prefix, &self.values)
     }
 
     /// Check whether a key is present.
     pub fn has(&self, key: &str) -> bool {
         self.values.contains_key(key)
     }
+
+    /// Layer `other` onto this config.
+    ///
+    /// Each key from `other` replaces the existing entry unless the existing
+    /// one has higher precedence (file < env < explicit `set`), so merging
+    /// an env config never clobbers a value set in code.
+    pub fn merge(&mut self, other: DogConfig) {
+        for (key, entry) in other.values {
+            match self.values.get(&key) {
+                Some(existing) if existing.origin > entry.origin => {}
+                _ => {
+                    self.values.insert(key, entry);
+                }
+            }
+        }
+    }
+
+    /// Number of keys in the store.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether the store has no keys.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Copy the current values into an immutable [`DogConfigSnapshot`].
     pub fn snapshot(&self) -> DogConfigSnapshot {
         DogConfigSnapshot::new(self.values.clone())
     }
 }
 
+/// A frozen copy of a [`DogConfig`].
+///
+/// The service pipeline takes one when a call starts and hands it to every
+/// hook as `ctx.config`, so a call reads the same values from start to
+/// finish even if `DogApp::set` changes them while it is in flight. Cloning
+/// a snapshot is cheap: clones share the same values.
 #[derive(Debug, Clone, Default)]
 pub struct DogConfigSnapshot {
-    map: HashMap<String, String>,
+    map: Arc<HashMap<String, ConfigEntry>>,
 }
 
 impl DogConfigSnapshot {
-    pub(crate) fn new(map: HashMap<String, String>) -> Self {
-        Self { map }
+    fn new(map: HashMap<String, ConfigEntry>) -> Self {
+        Self { map: Arc::new(map) }
+    }
+
+    /// Check whether a key is present.
+    pub fn has(&self, key: &str) -> bool {
+        self.map.contains_key(key)
     }
 
     pub fn get(&self, key: &str) -> Option<&str> {
-        self.map.get(key).map(|s| s.as_str())
+        self.map.get(key).map(|e| e.raw.as_str())
     }
 
     pub fn get_string(&self, key: &str) -> Option<String> {
-        self.map.get(key).cloned()
+        self.get(key).map(str::to_string)
     }
 
     pub fn get_usize(&self, key: &str) -> Option<usize> {
-        self.get(key).and_then(|v| v.parse::<usize>().ok())
+        self.get_as(key)
     }
 
     pub fn get_bool(&self, key: &str) -> Option<bool> {
-        self.get(key).and_then(|v| v.parse::<bool>().ok())
+        self.get_as(key)
+    }
+
+    /// Parse a configuration value with `FromStr`. See [`DogConfig::get_as`].
+    pub fn get_as<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.map.get(key).and_then(ConfigEntry::parse)
+    }
+
+    /// Deserialize a configuration value into `T`. See [`DogConfig::get_json`].
+    #[cfg(feature = "json")]
+    pub fn get_json<T: serde::de::DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.map.get(key).and_then(ConfigEntry::deserialize)
+    }
+
+    /// Deserialize a config sub-tree into `T`. See [`DogConfig::get_section`].
+    #[cfg(feature = "json")]
+    pub fn get_section<T: serde::de::DeserializeOwned>(&self, prefix: &str) -> anyhow::Result<T> {
+        section::deserialize_section(prefix, self.map.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_as_parses_string_values() {
+        let mut config = DogConfig::new();
+        config.set("paginate.max", "50");
+        config.set("feature.enabled", "true");
+        config.set("ratio", " 0.25 ");
+        config.set("bad", "fifty");
+
+        assert_eq!(config.get_as::<usize>("paginate.max"), Some(50));
+        assert_eq!(config.get_as::<bool>("feature.enabled"), Some(true));
+        assert_eq!(config.get_as::<f64>("ratio"), Some(0.25));
+        assert_eq!(config.get_as::<usize>("bad"), None);
+        assert_eq!(config.get_as::<usize>("missing"), None);
+        // The string API is unchanged.
+        assert_eq!(config.get("paginate.max"), Some("50"));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn set_value_keeps_typed_json_and_a_string_view() {
+        use serde_json::json;
+
+        let mut config = DogConfig::new();
+        config.set_value("paginate.max", json!(50));
+        config.set_value("http.host", json!("localhost"));
+        config.set_value("cors.origins", json!(["https://a.example"]));
+
+        assert_eq!(config.get("paginate.max"), Some("50"));
+        assert_eq!(config.get("http.host"), Some("localhost"));
+        assert_eq!(config.get("cors.origins"), Some(r#"["https://a.example"]"#));
+
+        assert_eq!(config.get_as::<u32>("paginate.max"), Some(50));
+        assert_eq!(
+            config.get_json::<Vec<String>>("cors.origins"),
+            Some(vec!["https://a.example".to_string()])
+        );
+        assert_eq!(config.get_json::<u32>("http.host"), None);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn get_json_reads_string_entries() {
+        let mut config = DogConfig::new();
+        config.set("http.port", "8080");
+        config.set("http.host", "localhost");
+        config.set("ids", "[1, 2, 3]");
+
+        assert_eq!(config.get_json::<u16>("http.port"), Some(8080));
+        // Numeric-looking strings still read back as strings when asked.
+        assert_eq!(
+            config.get_json::<String>("http.port"),
+            Some("8080".to_string())
+        );
+        assert_eq!(
+            config.get_json::<String>("http.host"),
+            Some("localhost".to_string())
+        );
+        assert_eq!(config.get_json::<Vec<u8>>("ids"), Some(vec![1, 2, 3]));
+
+        let snapshot = config.snapshot();
+        assert_eq!(snapshot.get_json::<u16>("http.port"), Some(8080));
+        assert_eq!(snapshot.get_as::<u16>("http.port"), Some(8080));
+    }
+
+    #[test]
+    fn watch_reports_old_and_new_values_for_its_key_only() {
+        use std::sync::Mutex;
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut config = DogConfig::new();
+        let log = seen.clone();
+        config.watch("maintenance", move |old, new| {
+            log.lock()
+                .unwrap()
+                .push((old.map(str::to_string), new.to_string()));
+        });
+
+        config.set("maintenance", "false");
+        config.set("paginate.max", "50");
+        config.set("maintenance", "true");
+        // Same value again: nothing changed, nothing reported.
+        config.set("maintenance", "true");
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                (None, "false".to_string()),
+                (Some("false".to_string()), "true".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn env_vars_map_to_dotted_keys() {
+        let config = DogConfig::from_vars(
+            "DOG",
+            [
+                ("DOG_HTTP__PORT", "3030"),
+                ("DOG_TOMTOM__ROUTE__TIMEOUT", "5"),
+                ("DOG_PAGINATE_MAX", "50"),
+                ("DOGGY_HTTP__PORT", "1"),
+                ("OTHER_HTTP__PORT", "2"),
+                ("DOG_HTTP____HOST", "bad"),
+            ],
+        );
+
+        assert_eq!(config.get_as::<u16>("http.port"), Some(3030));
+        assert_eq!(config.get("tomtom.route.timeout"), Some("5"));
+        // A single underscore stays part of the segment.
+        assert_eq!(config.get("paginate_max"), Some("50"));
+        // Other prefixes and empty segments are ignored.
+        assert_eq!(config.len(), 3);
+
+        // A trailing underscore on the prefix is optional.
+        let config = DogConfig::from_vars("DOG_", [("DOG_HTTP__PORT", "3030")]);
+        assert_eq!(config.get("http.port"), Some("3030"));
+    }
+
+    #[test]
+    fn explicit_set_beats_env_regardless_of_order() {
+        let mut config = DogConfig::new();
+        config.set("http.port", "8080");
+        config.merge(DogConfig::from_vars(
+            "DOG",
+            [("DOG_HTTP__PORT", "3030"), ("DOG_HTTP__HOST", "0.0.0.0")],
+        ));
+
+        assert_eq!(config.get("http.port"), Some("8080"));
+        assert_eq!(config.get("http.host"), Some("0.0.0.0"));
+
+        config.set("http.host", "127.0.0.1");
+        assert_eq!(config.get("http.host"), Some("127.0.0.1"));
+    }
+
+    #[cfg(feature = "json")]
+    fn write_temp(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("dog-core-{}-{name}", std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_file_is_flattened_with_types() {
+        let path = write_temp(
+            "flatten.json",
+            r#"{ "http": { "port": 3030, "tls": false }, "cors": { "origins": ["a", "b"] } }"#,
+        );
+        let config = DogConfig::from_json_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(config.get("http.port"), Some("3030"));
+        assert_eq!(config.get_json::<bool>("http.tls"), Some(false));
+        assert_eq!(
+            config.get_json::<Vec<String>>("cors.origins"),
+            Some(vec!["a".to_string(), "b".to_string()])
+        );
+        assert!(!config.has("http"));
+
+        let err = DogConfig::from_json_file(path).unwrap_err();
+        assert!(err.to_string().contains("cannot read config file"), "{err}");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn configure_from_applies_file_then_env_then_explicit() {
+        let path = write_temp(
+            "precedence.json",
+            r#"{ "http": { "host": "file-host", "port": 1000 }, "paginate": { "max": 10 } }"#,
+        );
+
+        let mut app = crate::DogAppBuilder::<(), ()>::new();
+        app.set("paginate.max", "99");
+        // Env is listed first but still outranks the file.
+        app.configure_from([
+            ConfigSource::Config(DogConfig::from_vars("DOG", [("DOG_HTTP__PORT", "2000")])),
+            ConfigSource::json_file(&path),
+        ])
+        .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            app.get_as::<String>("http.host").as_deref(),
+            Some("file-host")
+        );
+        assert_eq!(app.get_as::<u16>("http.port"), Some(2000));
+        assert_eq!(app.get_as::<usize>("paginate.max"), Some(99));
+
+        // A failing source leaves the config untouched.
+        let err = app
+            .configure_from([
+                ConfigSource::Config(DogConfig::from_vars("DOG", [("DOG_HTTP__PORT", "3000")])),
+                ConfigSource::json_file("/nonexistent/dog-config.json"),
+            ])
+            .unwrap_err();
+        assert!(err.to_string().contains("cannot read config file"), "{err}");
+        assert_eq!(app.get_as::<u16>("http.port"), Some(2000));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_file_is_flattened_with_types() {
+        let path = write_temp(
+            "flatten.toml",
+            "[http]\nport = 3030\n\n[tomtom.route]\ntimeout = 15\nstarted = 2024-01-01T00:00:00Z\n",
+        );
+        let config = DogConfig::from_toml_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(config.get_as::<u16>("http.port"), Some(3030));
+        assert_eq!(config.get_json::<u64>("tomtom.route.timeout"), Some(15));
+        assert_eq!(
+            config.get("tomtom.route.started"),
+            Some("2024-01-01T00:00:00Z")
+        );
+    }
+
+    #[cfg(feature = "json")]
+    mod sections {
+        use super::*;
+        use serde::Deserialize;
+        use serde_json::json;
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct RouteConfig {
+            timeout: u64,
+            #[serde(default)]
+            avoid_tolls: bool,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct TomTomConfig {
+            key: String,
+            #[serde(rename = "baseUrl")]
+            base_url: String,
+            route: RouteConfig,
+            #[serde(default)]
+            retries: Option<u32>,
+        }
+
+        #[test]
+        fn dotted_keys_deserialize_into_nested_struct() {
+            let mut config = DogConfig::new();
+            config.set("tomtom.key", "12345");
+            config.set("tomtom.baseUrl", "https://api.tomtom.com");
+            config.set("tomtom.route.timeout", "15");
+            config.set("tomtom.route.avoid_tolls", "true");
+            config.set("tomtomish.key", "not-in-section");
+            config.set("other.key", "ignored");
+
+            let tomtom: TomTomConfig = config.get_section("tomtom").unwrap();
+            assert_eq!(
+                tomtom,
+                TomTomConfig {
+                    // Numeric-looking text still lands in a String field.
+                    key: "12345".to_string(),
+                    base_url: "https://api.tomtom.com".to_string(),
+                    route: RouteConfig {
+                        timeout: 15,
+                        avoid_tolls: true,
+                    },
+                    retries: None,
+                }
+            );
+            // Flat access is unchanged.
+            assert_eq!(config.get("tomtom.route.timeout"), Some("15"));
+        }
+
+        #[test]
+        fn more_specific_keys_override_stored_objects() {
+            let mut config = DogConfig::new();
+            config.set_value(
+                "tomtom",
+                json!({
+                    "key": "k",
+                    "baseUrl": "https://a.example",
+                    "route": { "timeout": 5, "avoid_tolls": true },
+                }),
+            );
+            config.set("tomtom.route.timeout", "30");
+            config.set_value("tomtom.retries", json!(3));
+
+            let tomtom: TomTomConfig = config.get_section("tomtom").unwrap();
+            assert_eq!(tomtom.route.timeout, 30);
+            assert!(tomtom.route.avoid_tolls);
+            assert_eq!(tomtom.retries, Some(3));
+            assert_eq!(tomtom.base_url, "https://a.example");
+
+            // A scalar is replaced by the sub-tree below it.
+            config.set("tomtom.route", "fast");
+            let tomtom: TomTomConfig = config.get_section("tomtom").unwrap();
+            assert_eq!(tomtom.route.timeout, 30);
+        }
+
+        #[test]
+        fn invalid_or_missing_fields_name_the_section() {
+            let mut config = DogConfig::new();
+            config.set("tomtom.key", "k");
+            config.set("tomtom.baseUrl", "u");
+            config.set("tomtom.route.timeout", "soon");
+
+            let err = config.get_section::<TomTomConfig>("tomtom").unwrap_err();
+            assert!(err.to_string().contains("config section 'tomtom'"), "{err}");
+
+            let err = config.get_section::<TomTomConfig>("missing").unwrap_err();
+            assert!(err.to_string().contains("missing field"), "{err}");
+
+            #[derive(Debug, Default, Deserialize, PartialEq)]
+            struct Paginate {
+                #[serde(default)]
+                max: Option<usize>,
+            }
+            let paginate: Paginate = config.get_section("paginate").unwrap();
+            assert_eq!(paginate, Paginate::default());
+        }
     }
 }
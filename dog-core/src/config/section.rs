@@ -0,0 +1,239 @@
+//! Sub-tree view of the flat config store, used by `get_section`.
+//!
+//! ## How dotted keys map to nested JSON
+//! - Keys are split on `.`; every segment is an object key. `tomtom.route.timeout`
+//!   under the section `tomtom` becomes `{ "route": { "timeout": ... } }`.
+//!   Segments are never treated as array indexes (`origins.0` is the key `"0"`).
+//! - Only keys strictly below the section (`tomtom.*`) contribute, plus the
+//!   section key itself when it holds a JSON object set with `set_value`.
+//! - Object values stored with `set_value` are expanded into the tree, so
+//!   `set_value("tomtom", json!({"route": {"timeout": 5}}))` and
+//!   `set("tomtom.route.timeout", "5")` describe the same tree.
+//! - When entries overlap, the more specific key wins: keys are applied from
+//!   shallowest to deepest, objects merge recursively and anything else is
+//!   replaced. A scalar at `a.b` is dropped once `a.b.c` exists.
+//! - Leaves are read leniently. String entries are parsed as JSON when they
+//!   can be (`"15"` feeds a `u64`, `"true"` a `bool`, `"[1,2]"` a `Vec`), but a
+//!   `String` field always receives the raw text, so `tomtom.key = "12345"`
+//!   still deserializes into `key: String`.
+
+use std::collections::BTreeMap;
+
+use serde::de::value::MapDeserializer;
+use serde::de::{DeserializeOwned, Deserializer, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+use serde_json::Value;
+
+use super::ConfigEntry;
+
+enum Node {
+    Leaf { raw: String, value: Value },
+    Map(BTreeMap<String, Node>),
+}
+
+impl Node {
+    fn from_entry(entry: &ConfigEntry) -> Self {
+        match entry.json() {
+            Value::Object(obj) => Node::from_object(obj),
+            value => Node::Leaf {
+                raw: entry.raw.clone(),
+                value,
+            },
+        }
+    }
+
+    fn from_value(value: Value) -> Self {
+        match value {
+            Value::Object(obj) => Node::from_object(obj),
+            Value::String(s) => Node::Leaf {
+                raw: s.clone(),
+                value: Value::String(s),
+            },
+            other => Node::Leaf {
+                raw: other.to_string(),
+                value: other,
+            },
+        }
+    }
+
+    fn from_object(obj: serde_json::Map<String, Value>) -> Self {
+        Node::Map(
+            obj.into_iter()
+                .map(|(k, v)| (k, Node::from_value(v)))
+                .collect(),
+        )
+    }
+
+    /// Merge `other` into `self`; `other` is the more specific entry.
+    fn merge(&mut self, other: Node) {
+        match (self, other) {
+            (Node::Map(mine), Node::Map(theirs)) => {
+                for (k, v) in theirs {
+                    match mine.get_mut(&k) {
+                        Some(existing) => existing.merge(v),
+                        None => {
+                            mine.insert(k, v);
+                        }
+                    }
+                }
+            }
+            (slot, other) => *slot = other,
+        }
+    }
+
+    /// Descend into `key`, turning scalars along the way into empty objects.
+    fn child(&mut self, key: &str) -> &mut Node {
+        if !matches!(self, Node::Map(_)) {
+            *self = Node::Map(BTreeMap::new());
+        }
+        match self {
+            Node::Map(map) => map
+                .entry(key.to_string())
+                .or_insert_with(|| Node::Map(BTreeMap::new())),
+            Node::Leaf { .. } => unreachable!("converted to a map above"),
+        }
+    }
+
+    fn into_value(self) -> Value {
+        match self {
+            Node::Leaf { value, .. } => value,
+            Node::Map(map) => {
+                Value::Object(map.into_iter().map(|(k, v)| (k, v.into_value())).collect())
+            }
+        }
+    }
+}
+
+/// Deserialize every entry at or below `prefix` into `T`.
+///
+/// An empty `prefix` selects the whole store. A section with no entries
+/// deserializes from an empty object, so structs whose fields all have
+/// `#[serde(default)]` still load.
+pub(super) fn deserialize_section<'a, T, I>(prefix: &str, entries: I) -> anyhow::Result<T>
+where
+    T: DeserializeOwned,
+    I: IntoIterator<Item = (&'a String, &'a ConfigEntry)>,
+{
+    let mut matched: Vec<(Vec<&str>, &ConfigEntry)> = entries
+        .into_iter()
+        .filter_map(|(key, entry)| {
+            let rest = if prefix.is_empty() {
+                key.as_str()
+            } else if key == prefix {
+                ""
+            } else {
+                key.strip_prefix(prefix)?.strip_prefix('.')?
+            };
+            let path = if rest.is_empty() {
+                Vec::new()
+            } else {
+                rest.split('.').collect()
+            };
+            Some((path, entry))
+        })
+        .collect();
+
+    // Shallow first so deeper keys override; ties sort by key for a stable result.
+    matched.sort_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+
+    let mut root = Node::Map(BTreeMap::new());
+    for (path, entry) in matched {
+        let node = Node::from_entry(entry);
+        if path.is_empty() {
+            // The section key itself only contributes when it is an object.
+            if matches!(node, Node::Map(_)) {
+                root.merge(node);
+            }
+            continue;
+        }
+        let (last, parents) = path.split_last().expect("path is non-empty");
+        let mut slot = &mut root;
+        for segment in parents {
+            slot = slot.child(segment);
+        }
+        slot.child(last).merge(node);
+    }
+
+    T::deserialize(root).map_err(|e| {
+        if prefix.is_empty() {
+            anyhow::anyhow!("invalid config: {e}")
+        } else {
+            anyhow::anyhow!("invalid config section '{prefix}': {e}")
+        }
+    })
+}
+
+impl<'de> IntoDeserializer<'de, serde_json::Error> for Node {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for Node {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Node::Leaf { value, .. } => value.deserialize_any(visitor),
+            Node::Map(map) => {
+                let mut de = MapDeserializer::new(map.into_iter());
+                let out = visitor.visit_map(&mut de)?;
+                de.end()?;
+                Ok(out)
+            }
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Node::Leaf { raw, .. } => visitor.visit_string(raw),
+            map => map.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Node::Leaf {
+                value: Value::Null, ..
+            } => visitor.visit_none(),
+            node => visitor.visit_some(node),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        // Unit variants are written as plain strings (`mode = "fast"`), which
+        // the JSON view may have turned into a number or bool.
+        let value = match self {
+            Node::Leaf { raw, value } if !value.is_object() && !value.is_array() => {
+                Value::String(raw)
+            }
+            node => node.into_value(),
+        };
+        value.deserialize_enum(name, variants, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char bytes
+        byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
@@ -0,0 +1,195 @@
+//! Loading `DogConfig` from environment variables and files.
+
+#[cfg(feature = "json")]
+use std::path::{Path, PathBuf};
+
+use super::{ConfigEntry, DogConfig, Origin};
+
+/// A place configuration can be loaded from, for `DogAppBuilder::configure_from`.
+///
+/// Sources are merged by precedence, not by position: files < env <
+/// explicit `set`. See the [module docs](crate::config) for the key rules.
+#[derive(Debug)]
+pub enum ConfigSource {
+    /// Environment variables starting with `{prefix}_`. See [`DogConfig::from_env`].
+    Env(String),
+    /// A JSON file. See [`DogConfig::from_json_file`].
+    #[cfg(feature = "json")]
+    JsonFile(PathBuf),
+    /// A TOML file. See [`DogConfig::from_toml_file`].
+    #[cfg(feature = "toml")]
+    TomlFile(PathBuf),
+    /// An already-built config, e.g. one fetched from a remote store.
+    /// Its entries keep the precedence they were created with.
+    Config(DogConfig),
+}
+
+impl ConfigSource {
+    pub fn env(prefix: impl Into<String>) -> Self {
+        ConfigSource::Env(prefix.into())
+    }
+
+    #[cfg(feature = "json")]
+    pub fn json_file(path: impl Into<PathBuf>) -> Self {
+        ConfigSource::JsonFile(path.into())
+    }
+
+    #[cfg(feature = "toml")]
+    pub fn toml_file(path: impl Into<PathBuf>) -> Self {
+        ConfigSource::TomlFile(path.into())
+    }
+
+    pub fn config(config: DogConfig) -> Self {
+        ConfigSource::Config(config)
+    }
+
+    /// Read the source into a standalone config.
+    pub fn load(self) -> anyhow::Result<DogConfig> {
+        match self {
+            ConfigSource::Env(prefix) => Ok(DogConfig::from_env(&prefix)),
+            #[cfg(feature = "json")]
+            ConfigSource::JsonFile(path) => DogConfig::from_json_file(path),
+            #[cfg(feature = "toml")]
+            ConfigSource::TomlFile(path) => DogConfig::from_toml_file(path),
+            ConfigSource::Config(config) => Ok(config),
+        }
+    }
+}
+
+impl DogConfig {
+    /// Load every `{prefix}_*` environment variable.
+    ///
+    /// `DOG_HTTP__PORT=3030` with prefix `"DOG"` becomes `http.port = "3030"`:
+    /// the prefix is stripped, the rest lowercased, and `__` separates key
+    /// segments. A trailing `_` on the prefix is optional. An empty prefix
+    /// loads every variable.
+    pub fn from_env(prefix: &str) -> Self {
+        Self::from_vars(prefix, std::env::vars())
+    }
+
+    /// Same as [`DogConfig::from_env`], but over an explicit list of
+    /// `(name, value)` pairs instead of the process environment.
+    pub fn from_vars<I, K, V>(prefix: &str, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        let prefix = prefix.trim_end_matches('_');
+        let mut config = DogConfig::new();
+        for (name, value) in vars {
+            let name = name.as_ref();
+            let rest = if prefix.is_empty() {
+                name
+            } else {
+                match name
+                    .strip_prefix(prefix)
+                    .and_then(|rest| rest.strip_prefix('_'))
+                {
+                    Some(rest) => rest,
+                    None => continue,
+                }
+            };
+            if let Some(key) = env_key(rest) {
+                config
+                    .values
+                    .insert(key, ConfigEntry::from_string(value.into(), Origin::Env));
+            }
+        }
+        config
+    }
+
+    /// Load a JSON file whose top level is an object.
+    ///
+    /// Nested objects are flattened into dotted keys and values keep their
+    /// JSON types; arrays are stored whole under their key.
+    #[cfg(feature = "json")]
+    pub fn from_json_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let text = read_file(path)?;
+        let value: serde_json::Value = serde_json::from_str(&text)
+            .map_err(|e| anyhow::anyhow!("invalid JSON config {}: {e}", path.display()))?;
+        Self::from_document(value, path)
+    }
+
+    /// Load a TOML file. Tables are flattened like JSON objects (see
+    /// [`DogConfig::from_json_file`]); datetimes are stored as strings.
+    #[cfg(feature = "toml")]
+    pub fn from_toml_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let text = read_file(path)?;
+        let table: toml::Table = text
+            .parse()
+            .map_err(|e| anyhow::anyhow!("invalid TOML config {}: {e}", path.display()))?;
+        Self::from_document(toml_to_json(toml::Value::Table(table)), path)
+    }
+
+    #[cfg(feature = "json")]
+    fn from_document(value: serde_json::Value, path: &Path) -> anyhow::Result<Self> {
+        let serde_json::Value::Object(obj) = value else {
+            anyhow::bail!("config file {} must contain an object", path.display());
+        };
+        let mut config = DogConfig::new();
+        flatten_into(&mut config, String::new(), obj);
+        Ok(config)
+    }
+}
+
+/// `HTTP__PORT` → `http.port`. Returns None for names with empty segments.
+fn env_key(name: &str) -> Option<String> {
+    let segments: Vec<String> = name.split("__").map(str::to_lowercase).collect();
+    if segments.iter().any(String::is_empty) {
+        return None;
+    }
+    Some(segments.join("."))
+}
+
+#[cfg(feature = "json")]
+fn read_file(path: &Path) -> anyhow::Result<String> {
+    std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("cannot read config file {}: {e}", path.display()))
+}
+
+#[cfg(feature = "json")]
+fn flatten_into(
+    config: &mut DogConfig,
+    prefix: String,
+    obj: serde_json::Map<String, serde_json::Value>,
+) {
+    for (key, value) in obj {
+        let key = if prefix.is_empty() {
+            key
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match value {
+            serde_json::Value::Object(inner) => flatten_into(config, key, inner),
+            value => {
+                config
+                    .values
+                    .insert(key, ConfigEntry::from_value(value, Origin::File));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "toml")]
+fn toml_to_json(value: toml::Value) -> serde_json::Value {
+    use serde_json::Value as Json;
+    match value {
+        toml::Value::String(s) => Json::String(s),
+        toml::Value::Integer(i) => Json::from(i),
+        toml::Value::Float(f) => serde_json::Number::from_f64(f)
+            .map(Json::Number)
+            .unwrap_or(Json::Null),
+        toml::Value::Boolean(b) => Json::Bool(b),
+        toml::Value::Datetime(dt) => Json::String(dt.to_string()),
+        toml::Value::Array(items) => Json::Array(items.into_iter().map(toml_to_json).collect()),
+        toml::Value::Table(table) => Json::Object(
+            table
+                .into_iter()
+                .map(|(k, v)| (k, toml_to_json(v)))
+                .collect(),
+        ),
+    }
+}
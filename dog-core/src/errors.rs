@@ -39,6 +39,7 @@ pub enum ErrorKind {
     Conflict,         // 409
     Gone,             // 410
     LengthRequired,   // 411
+    PayloadTooLarge,  // 413
     Unprocessable,    // 422
     TooManyRequests,  // 429
     GeneralError,     // 500
@@ -60,6 +61,7 @@ impl ErrorKind {
             ErrorKind::Conflict => 409,
             ErrorKind::Gone => 410,
             ErrorKind::LengthRequired => 411,
+            ErrorKind::PayloadTooLarge => 413,
             ErrorKind::Unprocessable => 422,
             ErrorKind::TooManyRequests => 429,
             ErrorKind::GeneralError => 500,
@@ -82,6 +84,7 @@ impl ErrorKind {
             ErrorKind::Conflict => "Conflict",
             ErrorKind::Gone => "Gone",
             ErrorKind::LengthRequired => "LengthRequired",
+            ErrorKind::PayloadTooLarge => "PayloadTooLarge",
             ErrorKind::Unprocessable => "Unprocessable",
             ErrorKind::TooManyRequests => "TooManyRequests",
             ErrorKind::GeneralError => "GeneralError",
@@ -104,6 +107,7 @@ impl ErrorKind {
             ErrorKind::Conflict => "conflict",
             ErrorKind::Gone => "gone",
             ErrorKind::LengthRequired => "length-required",
+            ErrorKind::PayloadTooLarge => "payload-too-large",
             ErrorKind::Unprocessable => "unprocessable",
             ErrorKind::TooManyRequests => "too-many-requests",
             ErrorKind::GeneralError => "general-error",
@@ -418,6 +422,9 @@ impl DogError {
     pub fn length_required(msg: impl Into<String>) -> Self {
         Self::new(ErrorKind::LengthRequired, msg)
     }
+    pub fn payload_too_large(msg: impl Into<String>) -> Self {
+        Self::new(ErrorKind::PayloadTooLarge, msg)
+    }
     pub fn unprocessable(msg: impl Into<String>) -> Self {
         Self::new(ErrorKind::Unprocessable, msg)
     }
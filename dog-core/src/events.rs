@@ -7,7 +7,7 @@ use std::sync::{
 use anyhow::Result;
 
 use crate::hooks::HookFut;
-use crate::{HookContext, HookResult, ServiceMethodKind};
+use crate::{DogError, ErrorKind, HookContext, HookResult, ServiceMethodKind};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ListenerId(u64);
@@ -25,6 +25,8 @@ pub enum ServiceEventKind {
     Updated,
     Patched,
     Removed,
+    /// A service call that still failed after its error hooks ran.
+    Error,
     Custom(String),
 }
 
@@ -32,12 +34,58 @@ impl ServiceEventKind {
     pub fn custom(name: impl Into<String>) -> Self {
         ServiceEventKind::Custom(name.into())
     }
+
+    /// Event name as sent to clients (`"created"`, ..., or the custom name).
+    pub fn as_str(&self) -> &str {
+        match self {
+            ServiceEventKind::Created => "created",
+            ServiceEventKind::Updated => "updated",
+            ServiceEventKind::Patched => "patched",
+            ServiceEventKind::Removed => "removed",
+            ServiceEventKind::Error => "error",
+            ServiceEventKind::Custom(name) => name,
+        }
+    }
 }
 
 /// Data delivered to event listeners.
 pub enum ServiceEventData<'a, R> {
+    /// The method's result after the after hooks. Standard events fire once
+    /// per call, so a multi `patch` or `remove` (`id = None`) delivers its
+    /// whole result in one event.
     Standard(&'a HookResult<R>),
     Custom(&'a Arc<dyn Any + Send + Sync>),
+    /// The failure behind an [`ServiceEventKind::Error`] event.
+    Error(&'a ServiceErrorEvent),
+}
+
+/// What an `error` event reports about a failed service call.
+///
+/// Emitted once per failed call, after the error hooks, so a hook that
+/// recovers the call suppresses it. Listen with `app.on_str("*.error", ..)`
+/// to centralize error logging or alerting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceErrorEvent {
+    pub service: String,
+    pub method: ServiceMethodKind,
+    /// `GeneralError` for errors that are not a [`DogError`].
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl ServiceErrorEvent {
+    pub(crate) fn new(service: &str, method: &ServiceMethodKind, err: &anyhow::Error) -> Self {
+        let (kind, message) = match DogError::from_anyhow(err) {
+            Some(dog) => (dog.kind, dog.message.clone()),
+            None => (ErrorKind::GeneralError, err.to_string()),
+        };
+        Self {
+            service: service.to_string(),
+            method: method.clone(),
+            kind,
+            message,
+        }
+    }
 }
 
 /// Listener signature (async).
@@ -170,7 +218,7 @@ where
 
     /// Exact: app.on("messages", Created, ...)
     pub fn on_exact(
-        &mut self,
+        &self,
         path: impl Into<String>,
         event: ServiceEventKind,
         listener: EventListener<R, P>,
@@ -180,7 +228,7 @@ where
 
     /// Sugar: app.on_str("messages.created", ...)
     pub fn on_pattern(
-        &mut self,
+        &self,
         pattern: ServiceEventPattern,
         listener: EventListener<R, P>,
     ) -> ListenerId {
@@ -200,7 +248,7 @@ where
 
     /// Feathers-ish: once(...)
     pub fn once_pattern(
-        &mut self,
+        &self,
         pattern: ServiceEventPattern,
         listener: EventListener<R, P>,
     ) -> ListenerId {
@@ -219,7 +267,7 @@ where
     }
 
     /// removeListener/off
-    pub fn off(&mut self, id: ListenerId) -> bool {
+    pub fn off(&self, id: ListenerId) -> bool {
         let mut listeners = self.listeners.write().unwrap_or_else(|e| e.into_inner());
         let before = listeners.len();
         listeners.retain(|e| e.id != id);
@@ -227,7 +275,7 @@ where
     }
 
     /// removeAllListeners (optionally scoped)
-    pub fn remove_all(&mut self, pattern: Option<&ServiceEventPattern>) -> usize {
+    pub fn remove_all(&self, pattern: Option<&ServiceEventPattern>) -> usize {
         let mut listeners = self.listeners.write().unwrap_or_else(|e| e.into_inner());
         let before = listeners.len();
         if let Some(p) = pattern {
@@ -353,6 +401,7 @@ pub fn parse_event_kind(s: &str) -> anyhow::Result<ServiceEventKind> {
         "updated" => Ok(ServiceEventKind::Updated),
         "patched" => Ok(ServiceEventKind::Patched),
         "removed" => Ok(ServiceEventKind::Removed),
+        "error" => Ok(ServiceEventKind::Error),
         other => Ok(ServiceEventKind::Custom(other.to_string())),
     }
 }
@@ -50,8 +50,9 @@
 //! genuinely need a separate service and DI is too rigid.
 //!
 //! DogRS may populate the hook context with:
-//! - `ctx.config`: a snapshot of app config at call time
-//! - `ctx.services`: a runtime service caller (typed downcast)
+//! - `ctx.config`: a snapshot of app config taken when the call starts; it
+//!   does not change mid-call, even if the app's config does
+//! - `ctx.services`: a runtime service caller, typed by the app
 //!
 //! ```rust,ignore
 //! use std::sync::Arc;
@@ -77,8 +78,8 @@
 //!         // Read config snapshot (if provided by the app pipeline):
 //!         let _max = ctx.config.get_usize("paginate.max").unwrap_or(50);
 //!
-//!         // Runtime lookup of another service (typed):
-//!         let users = ctx.services.service::<User, UserParams>("users")?;
+//!         // Runtime lookup of another service by name:
+//!         let users = ctx.services.service("users")?;
 //!
 //!         // NOTE: calling other services from hooks is powerful but risky.
 //!         // users.get(...).await?;
@@ -124,15 +125,26 @@
 //!
 //! ---
 //!
-//! ## Type safety and mismatches
+//! ## Type safety and `ServiceRef`
 //!
-//! `ctx.services.service::<R2, P2>("name")` performs a typed downcast.
-//! If you request a different `<R2, P2>` than what was registered,
-//! it returns a clear **type mismatch** error.
+//! `ctx.services` is typed by the app's `<R, P>`, so a hook can only reach
+//! services of the same record and params types. Looking one up by name can
+//! still fail at runtime, for example when the name is misspelled.
 //!
-//! This is deliberate: DogRS remains strongly typed even when providing
-//! a Feathers-like runtime lookup experience.
+//! To avoid that, keep the [`crate::ServiceRef`] that `register_service`
+//! returns and call `ctx.services.handle(&users)`. This cannot fail. To
+//! migrate code that only has a name, call `app.service_ref("users")?` once
+//! at startup and keep the ref, instead of `ctx.services.service("users")?`
+//! on every call.
 //!
+//! A handle from `ctx.services.handle(..)` or `ctx.services.service(..)`
+//! runs the service's hooks, stays in the calling request's tenant and
+//! carries its `ctx.correlation_id` into the nested call. Calling it for
+//! another tenant fails with `Forbidden` unless the handle opts in with
+//! `.cross_tenant()`.
+//!
+
+pub mod metrics;
 
 use std::collections::HashMap;
 use std::future::Future;
@@ -147,6 +159,49 @@ use crate::{ServiceMethodKind, TenantContext};
 pub enum HookResult<R> {
     One(R),
     Many(Vec<R>),
+    /// A page of `find` results, produced by `ServiceHandle::find_paginated`.
+    ///
+    /// After-hooks see (and may rewrite) the whole envelope: filtering
+    /// `data` or adjusting `total` here is reflected in what the caller
+    /// receives.
+    Page {
+        data: Vec<R>,
+        total: u64,
+        limit: usize,
+        skip: usize,
+    },
+}
+
+impl<R> HookResult<R> {
+    /// Variant name, for "produced HookResult::X unexpectedly" errors.
+    pub(crate) fn variant_name(&self) -> &'static str {
+        match self {
+            HookResult::One(_) => "One",
+            HookResult::Many(_) => "Many",
+            HookResult::Page { .. } => "Page",
+        }
+    }
+
+    /// The records of a `Many` or `Page` result, for the `*_many` methods.
+    pub(crate) fn into_many(self, method: &str) -> anyhow::Result<Vec<R>> {
+        match self {
+            HookResult::Many(v) | HookResult::Page { data: v, .. } => Ok(v),
+            HookResult::One(_) => Err(anyhow::anyhow!(
+                "{method}() produced HookResult::One unexpectedly"
+            )),
+        }
+    }
+}
+
+impl<R> From<crate::Paginated<R>> for HookResult<R> {
+    fn from(page: crate::Paginated<R>) -> Self {
+        HookResult::Page {
+            data: page.data,
+            total: page.total,
+            limit: page.limit,
+            skip: page.skip,
+        }
+    }
 }
 
 /// A typed, Feathers-inspired hook context.
@@ -166,9 +221,18 @@ where
     pub method: ServiceMethodKind,
     pub params: P,
 
+    /// Name of the service being called. Set by the app pipeline;
+    /// `None` for contexts built by hand (e.g. in tests or adapters).
+    pub service: Option<String>,
+
     /// Input data (create / patch / update)
     pub data: Option<R>,
 
+    /// Input records for `create_many`, which leaves `data` as `None`.
+    /// Before hooks only see it when [`DogBeforeHook::handles_batches`]
+    /// says so; the rest run once per record with it in `data`.
+    pub data_many: Option<Vec<R>>,
+
     /// Output result (after hooks)
     pub result: Option<HookResult<R>>,
 
@@ -178,8 +242,19 @@ where
     /// Feathers-style access to other services (runtime lookup)
     pub services: crate::ServiceCaller<R, P>,
 
-    /// Immutable snapshot of app config for this call
+    /// Immutable snapshot of app config, taken when the call started.
+    /// Runtime `DogApp::set` calls do not show up here until the next call.
     pub config: crate::DogConfigSnapshot,
+
+    /// Transport request behind the call, set by the adapter; `None` for
+    /// calls made from code
+    pub request: Option<crate::RequestMeta>,
+
+    /// Id tying this call to a request or trace: set with
+    /// `ServiceHandle::with_correlation_id`, taken from the request's
+    /// `x-request-id` header, or inherited from the call that made this one
+    /// through `ctx.services`.
+    pub correlation_id: Option<String>,
 }
 
 impl<R, P> HookContext<R, P>
@@ -198,17 +273,32 @@ where
             tenant,
             method,
             params,
+            service: None,
             data: None,
+            data_many: None,
             result: None,
             error: None,
             services,
             config,
+            request: None,
+            correlation_id: None,
         }
     }
 
     pub fn app(&self) -> &crate::DogApp<R, P> {
         self.services.app()
     }
+
+    /// A header of the transport request, if there is one.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.request.as_ref().and_then(|r| r.header(name))
+    }
+}
+
+/// Stable sort so higher priorities come first; equal priorities keep
+/// their collection order.
+pub(crate) fn sort_by_priority<H>(hooks: &mut [PrioritizedHook<H>]) {
+    hooks.sort_by_key(|h| std::cmp::Reverse(h.priority));
 }
 
 /// Helper used by the pipeline:
@@ -232,7 +322,12 @@ pub type HookFut<'a> = Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
 
 type NextCall<R, P> = dyn for<'a> FnOnce(&'a mut HookContext<R, P>) -> HookFut<'a> + Send;
 
-/// Around hooks wrap the entire pipeline (like Feathers `around.all`)
+/// The rest of the pipeline, handed to each [`DogAroundHook`].
+///
+/// Running it runs the inner around hooks, then the before hooks, the
+/// service call and the after hooks. Dropping it without running it skips
+/// all of those but the after hooks, which still run on a result the
+/// around hook set (see [`DogAroundHook`]).
 pub struct Next<R, P>
 where
     R: Send + 'static,
@@ -258,6 +353,47 @@ where
     P: Send + Clone + 'static,
 {
     async fn run(&self, ctx: &mut HookContext<R, P>) -> Result<()>;
+
+    /// Whether this hook reads `ctx.data_many` itself. Hooks that don't (the
+    /// default) run once per record of a `create_many` batch, with the
+    /// record in `ctx.data`, so a hook written for single creates checks
+    /// and shapes every record of a batch too.
+    fn handles_batches(&self) -> bool {
+        false
+    }
+}
+
+/// Run a before hook, feeding a `create_many` batch through it one record
+/// at a time unless it handles batches itself.
+pub(crate) async fn run_before_hook<R, P>(
+    hook: &dyn DogBeforeHook<R, P>,
+    ctx: &mut HookContext<R, P>,
+) -> Result<()>
+where
+    R: Send + 'static,
+    P: Send + Clone + 'static,
+{
+    if hook.handles_batches() {
+        return hook.run(ctx).await;
+    }
+    let Some(batch) = ctx.data_many.take() else {
+        return hook.run(ctx).await;
+    };
+
+    let mut records = batch.into_iter();
+    let mut done = Vec::with_capacity(records.len());
+    let mut outcome = Ok(());
+    for record in records.by_ref() {
+        ctx.data = Some(record);
+        outcome = hook.run(ctx).await;
+        done.extend(ctx.data.take());
+        if outcome.is_err() {
+            break;
+        }
+    }
+    done.extend(records);
+    ctx.data_many = Some(done);
+    outcome
 }
 
 #[async_trait]
@@ -269,6 +405,26 @@ where
     async fn run(&self, ctx: &mut HookContext<R, P>) -> Result<()>;
 }
 
+/// Runs when a before hook, the service or an after hook fails, with the
+/// failure in `ctx.error`.
+///
+/// Every error hook runs, in priority order. If `ctx.error` is still set
+/// once they are done, the call fails with it. A hook can recover instead:
+/// take the error and put a result in `ctx.result`, and the call succeeds
+/// with that result and emits its event as usual. After hooks do not run
+/// again for a recovered call.
+///
+/// ```rust,ignore
+/// async fn run(&self, ctx: &mut HookContext<Value, ()>) -> anyhow::Result<()> {
+///     let not_found = ctx.error.as_ref().and_then(DogError::from_anyhow)
+///         .is_some_and(|e| e.kind == ErrorKind::NotFound);
+///     if not_found {
+///         ctx.error = None;
+///         ctx.result = Some(HookResult::One(json!({ "name": "guest" })));
+///     }
+///     Ok(())
+/// }
+/// ```
 #[async_trait]
 pub trait DogErrorHook<R, P>: Send + Sync
 where
@@ -278,6 +434,52 @@ where
     async fn run(&self, ctx: &mut HookContext<R, P>) -> Result<()>;
 }
 
+/// Wraps the rest of the pipeline (like Feathers `around.all`).
+///
+/// A hook may handle the call itself: set `ctx.result` and return `Ok(())`
+/// without calling `next.run`. The pipeline then treats the call as done:
+///
+/// - inner around hooks, before hooks and the service call are skipped;
+/// - after hooks run on the hook's result, as they would on a service
+///   result, before outer around hooks resume. A cache that stores what it
+///   saw after `next.run` gets them applied again on a hit, so they should
+///   be safe to repeat;
+/// - outer around hooks carry on after their own `next.run` as usual;
+/// - the method returns `ctx.result`, and writes emit their standard event
+///   with it. `find` with no result returns an empty list; other methods
+///   fail with "produced no result".
+///
+/// For example, a cache of `find` results per tenant:
+///
+/// ```rust
+/// # use std::collections::HashMap;
+/// # use std::sync::Mutex;
+/// use async_trait::async_trait;
+/// use dog_core::{DogAroundHook, HookContext, HookResult, Next};
+///
+/// #[derive(Default)]
+/// struct CacheFind {
+///     cache: Mutex<HashMap<String, Vec<String>>>,
+/// }
+///
+/// #[async_trait]
+/// impl DogAroundHook<String, ()> for CacheFind {
+///     async fn run(&self, ctx: &mut HookContext<String, ()>, next: Next<String, ()>) -> anyhow::Result<()> {
+///         let key = ctx.tenant.tenant_id.0.clone();
+///         if let Some(hit) = self.cache.lock().unwrap().get(&key).cloned() {
+///             // Handled: `next` is dropped, so the service is never called.
+///             ctx.result = Some(HookResult::Many(hit));
+///             return Ok(());
+///         }
+///
+///         next.run(ctx).await?;
+///         if let Some(HookResult::Many(records)) = &ctx.result {
+///             self.cache.lock().unwrap().insert(key, records.clone());
+///         }
+///         Ok(())
+///     }
+/// }
+/// ```
 #[async_trait]
 pub trait DogAroundHook<R, P>: Send + Sync
 where
@@ -287,6 +489,36 @@ where
     async fn run(&self, ctx: &mut HookContext<R, P>, next: Next<R, P>) -> Result<()>;
 }
 
+/// Priority used by every registration method without `_with_priority`.
+pub const DEFAULT_HOOK_PRIORITY: i32 = 0;
+
+/// A registered hook plus its priority.
+///
+/// Derefs to the hook itself, so `hooks.before_all[0].run(ctx)` works as
+/// it did before priorities existed.
+#[derive(Clone)]
+pub struct PrioritizedHook<H> {
+    pub priority: i32,
+    pub hook: H,
+}
+
+impl<H> PrioritizedHook<H> {
+    pub fn new(priority: i32, hook: H) -> Self {
+        Self { priority, hook }
+    }
+}
+
+impl<H> std::ops::Deref for PrioritizedHook<H> {
+    type Target = H;
+
+    fn deref(&self) -> &H {
+        &self.hook
+    }
+}
+
+/// The hooks registered in one bucket (e.g. `before_all`), in registration order.
+pub type HookList<H> = Vec<PrioritizedHook<Arc<H>>>;
+
 /// Feathers-style hooks container:
 ///
 /// {
@@ -295,16 +527,43 @@ where
 ///   after:  { all, find },
 ///   error:  { all, create }
 /// }
+///
+/// ## Ordering
+/// For each call the pipeline gathers, per phase, the global `all` hooks,
+/// the global method hooks, the service `all` hooks and the service method
+/// hooks, in that order and each in registration order. That list is then
+/// stably sorted by priority, so:
+///
+/// - a **higher** priority always runs **earlier** within its phase
+///   (outermost for around hooks), whether it was registered globally or on
+///   the service — a service hook with priority 10 runs before a global hook
+///   with the default priority;
+/// - hooks with equal priority keep the order above. Every registration
+///   method without `_with_priority` uses [`DEFAULT_HOOK_PRIORITY`] (`0`),
+///   so apps that never set a priority see exactly the old ordering.
+///
+/// After hooks unwind in reverse: among equal priorities the last
+/// registered runs first (as before), but a higher priority still runs
+/// before a lower one.
+///
+/// ```rust,ignore
+/// app.hooks(|h| {
+///     h.before_with_priority(100, Arc::new(Authenticate)); // always first
+/// });
+/// app.service_hooks("posts", |h| {
+///     h.before_create(Arc::new(ValidatePost)); // priority 0
+/// });
+/// ```
 pub struct ServiceHooks<R, P> {
-    pub around_all: Vec<Arc<dyn DogAroundHook<R, P>>>,
-    pub before_all: Vec<Arc<dyn DogBeforeHook<R, P>>>,
-    pub after_all: Vec<Arc<dyn DogAfterHook<R, P>>>,
-    pub error_all: Vec<Arc<dyn DogErrorHook<R, P>>>,
-
-    pub around_by_method: HashMap<ServiceMethodKind, Vec<Arc<dyn DogAroundHook<R, P>>>>,
-    pub before_by_method: HashMap<ServiceMethodKind, Vec<Arc<dyn DogBeforeHook<R, P>>>>,
-    pub after_by_method: HashMap<ServiceMethodKind, Vec<Arc<dyn DogAfterHook<R, P>>>>,
-    pub error_by_method: HashMap<ServiceMethodKind, Vec<Arc<dyn DogErrorHook<R, P>>>>,
+    pub around_all: HookList<dyn DogAroundHook<R, P>>,
+    pub before_all: HookList<dyn DogBeforeHook<R, P>>,
+    pub after_all: HookList<dyn DogAfterHook<R, P>>,
+    pub error_all: HookList<dyn DogErrorHook<R, P>>,
+
+    pub around_by_method: HashMap<ServiceMethodKind, HookList<dyn DogAroundHook<R, P>>>,
+    pub before_by_method: HashMap<ServiceMethodKind, HookList<dyn DogBeforeHook<R, P>>>,
+    pub after_by_method: HashMap<ServiceMethodKind, HookList<dyn DogAfterHook<R, P>>>,
+    pub error_by_method: HashMap<ServiceMethodKind, HookList<dyn DogErrorHook<R, P>>>,
 }
 
 impl<R, P> Default for ServiceHooks<R, P> {
@@ -341,7 +600,16 @@ impl<R, P> ServiceHooks<R, P> {
     // ─────────── AROUND ───────────
 
     pub fn around_all(&mut self, hook: Arc<dyn DogAroundHook<R, P>>) -> &mut Self {
-        self.around_all.push(hook);
+        self.around_with_priority(DEFAULT_HOOK_PRIORITY, hook)
+    }
+
+    /// `around_all` with an explicit priority; higher runs earlier.
+    pub fn around_with_priority(
+        &mut self,
+        priority: i32,
+        hook: Arc<dyn DogAroundHook<R, P>>,
+    ) -> &mut Self {
+        self.around_all.push(PrioritizedHook::new(priority, hook));
         self
     }
 
@@ -350,14 +618,36 @@ impl<R, P> ServiceHooks<R, P> {
         method: ServiceMethodKind,
         hook: Arc<dyn DogAroundHook<R, P>>,
     ) -> &mut Self {
-        self.around_by_method.entry(method).or_default().push(hook);
+        self.around_method_with_priority(method, DEFAULT_HOOK_PRIORITY, hook)
+    }
+
+    /// `around(method, ..)` with an explicit priority; higher runs earlier.
+    pub fn around_method_with_priority(
+        &mut self,
+        method: ServiceMethodKind,
+        priority: i32,
+        hook: Arc<dyn DogAroundHook<R, P>>,
+    ) -> &mut Self {
+        self.around_by_method
+            .entry(method)
+            .or_default()
+            .push(PrioritizedHook::new(priority, hook));
         self
     }
 
     // ─────────── BEFORE ───────────
 
     pub fn before_all(&mut self, hook: Arc<dyn DogBeforeHook<R, P>>) -> &mut Self {
-        self.before_all.push(hook);
+        self.before_with_priority(DEFAULT_HOOK_PRIORITY, hook)
+    }
+
+    /// `before_all` with an explicit priority; higher runs earlier.
+    pub fn before_with_priority(
+        &mut self,
+        priority: i32,
+        hook: Arc<dyn DogBeforeHook<R, P>>,
+    ) -> &mut Self {
+        self.before_all.push(PrioritizedHook::new(priority, hook));
         self
     }
 
@@ -366,7 +656,20 @@ impl<R, P> ServiceHooks<R, P> {
         method: ServiceMethodKind,
         hook: Arc<dyn DogBeforeHook<R, P>>,
     ) -> &mut Self {
-        self.before_by_method.entry(method).or_default().push(hook);
+        self.before_method_with_priority(method, DEFAULT_HOOK_PRIORITY, hook)
+    }
+
+    /// `before(method, ..)` with an explicit priority; higher runs earlier.
+    pub fn before_method_with_priority(
+        &mut self,
+        method: ServiceMethodKind,
+        priority: i32,
+        hook: Arc<dyn DogBeforeHook<R, P>>,
+    ) -> &mut Self {
+        self.before_by_method
+            .entry(method)
+            .or_default()
+            .push(PrioritizedHook::new(priority, hook));
         self
     }
 
@@ -394,10 +697,27 @@ impl<R, P> ServiceHooks<R, P> {
         self.before(ServiceMethodKind::Remove, hook)
     }
 
+    pub fn before_custom(
+        &mut self,
+        method: impl Into<String>,
+        hook: Arc<dyn DogBeforeHook<R, P>>,
+    ) -> &mut Self {
+        self.before(ServiceMethodKind::custom(method), hook)
+    }
+
     // ─────────── AFTER ───────────
 
     pub fn after_all(&mut self, hook: Arc<dyn DogAfterHook<R, P>>) -> &mut Self {
-        self.after_all.push(hook);
+        self.after_with_priority(DEFAULT_HOOK_PRIORITY, hook)
+    }
+
+    /// `after_all` with an explicit priority; higher runs earlier.
+    pub fn after_with_priority(
+        &mut self,
+        priority: i32,
+        hook: Arc<dyn DogAfterHook<R, P>>,
+    ) -> &mut Self {
+        self.after_all.push(PrioritizedHook::new(priority, hook));
         self
     }
 
@@ -406,7 +726,20 @@ impl<R, P> ServiceHooks<R, P> {
         method: ServiceMethodKind,
         hook: Arc<dyn DogAfterHook<R, P>>,
     ) -> &mut Self {
-        self.after_by_method.entry(method).or_default().push(hook);
+        self.after_method_with_priority(method, DEFAULT_HOOK_PRIORITY, hook)
+    }
+
+    /// `after(method, ..)` with an explicit priority; higher runs earlier.
+    pub fn after_method_with_priority(
+        &mut self,
+        method: ServiceMethodKind,
+        priority: i32,
+        hook: Arc<dyn DogAfterHook<R, P>>,
+    ) -> &mut Self {
+        self.after_by_method
+            .entry(method)
+            .or_default()
+            .push(PrioritizedHook::new(priority, hook));
         self
     }
 
@@ -418,10 +751,27 @@ impl<R, P> ServiceHooks<R, P> {
         self.after(ServiceMethodKind::Find, hook)
     }
 
+    pub fn after_custom(
+        &mut self,
+        method: impl Into<String>,
+        hook: Arc<dyn DogAfterHook<R, P>>,
+    ) -> &mut Self {
+        self.after(ServiceMethodKind::custom(method), hook)
+    }
+
     // ─────────── ERROR ───────────
 
     pub fn error_all(&mut self, hook: Arc<dyn DogErrorHook<R, P>>) -> &mut Self {
-        self.error_all.push(hook);
+        self.error_with_priority(DEFAULT_HOOK_PRIORITY, hook)
+    }
+
+    /// `error_all` with an explicit priority; higher runs earlier.
+    pub fn error_with_priority(
+        &mut self,
+        priority: i32,
+        hook: Arc<dyn DogErrorHook<R, P>>,
+    ) -> &mut Self {
+        self.error_all.push(PrioritizedHook::new(priority, hook));
         self
     }
 
@@ -430,7 +780,123 @@ impl<R, P> ServiceHooks<R, P> {
         method: ServiceMethodKind,
         hook: Arc<dyn DogErrorHook<R, P>>,
     ) -> &mut Self {
-        self.error_by_method.entry(method).or_default().push(hook);
+        self.error_method_with_priority(method, DEFAULT_HOOK_PRIORITY, hook)
+    }
+
+    /// `error(method, ..)` with an explicit priority; higher runs earlier.
+    pub fn error_method_with_priority(
+        &mut self,
+        method: ServiceMethodKind,
+        priority: i32,
+        hook: Arc<dyn DogErrorHook<R, P>>,
+    ) -> &mut Self {
+        self.error_by_method
+            .entry(method)
+            .or_default()
+            .push(PrioritizedHook::new(priority, hook));
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DogApp, DogService};
+    use std::sync::Mutex;
+
+    type Log = Arc<Mutex<Vec<&'static str>>>;
+
+    struct Record(&'static str, Log);
+
+    #[async_trait]
+    impl DogBeforeHook<(), ()> for Record {
+        async fn run(&self, _ctx: &mut HookContext<(), ()>) -> Result<()> {
+            self.1.lock().unwrap().push(self.0);
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl DogAfterHook<(), ()> for Record {
+        async fn run(&self, _ctx: &mut HookContext<(), ()>) -> Result<()> {
+            self.1.lock().unwrap().push(self.0);
+            Ok(())
+        }
+    }
+
+    struct Noop;
+
+    #[async_trait]
+    impl DogService<(), ()> for Noop {
+        async fn find(&self, _ctx: &TenantContext, _params: ()) -> Result<Vec<()>> {
+            Ok(vec![])
+        }
+    }
+
+    async fn run_find(app: &DogApp<(), ()>) {
+        app.service("items")
+            .unwrap()
+            .find(TenantContext::new("t"), ())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn before_hooks_run_by_priority_across_global_and_service() {
+        let log: Log = Arc::default();
+        let mut builder = DogApp::<(), ()>::builder();
+        builder.register_service("items", Arc::new(Noop));
+        builder.hooks(|h| {
+            h.before_all(Arc::new(Record("global-default", log.clone())));
+            h.before_with_priority(-5, Arc::new(Record("global-late", log.clone())));
+        });
+        builder.service_hooks("items", |h| {
+            h.before_find(Arc::new(Record("service-default", log.clone())));
+            h.before_method_with_priority(
+                ServiceMethodKind::Find,
+                100,
+                Arc::new(Record("service-auth", log.clone())),
+            );
+            h.before_with_priority(100, Arc::new(Record("service-auth-2", log.clone())));
+        });
+        run_find(&builder.build()).await;
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                // Same priority: service `all` before service method hooks.
+                "service-auth-2",
+                "service-auth",
+                // Default priority keeps the global → service order.
+                "global-default",
+                "service-default",
+                "global-late",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn after_hooks_unwind_but_respect_priority() {
+        let log: Log = Arc::default();
+        let mut builder = DogApp::<(), ()>::builder();
+        builder.register_service("items", Arc::new(Noop));
+        builder.service_hooks("items", |h| {
+            h.after_all(Arc::new(Record("first-registered", log.clone())));
+            h.after_all(Arc::new(Record("second-registered", log.clone())));
+            h.after_with_priority(10, Arc::new(Record("priority", log.clone())));
+        });
+        run_find(&builder.build()).await;
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["priority", "second-registered", "first-registered"]
+        );
+    }
+
+    #[test]
+    fn default_registration_uses_default_priority() {
+        let mut hooks: ServiceHooks<(), ()> = ServiceHooks::new();
+        hooks.before_all(Arc::new(Record("x", Arc::default())));
+        assert_eq!(hooks.before_all[0].priority, DEFAULT_HOOK_PRIORITY);
+    }
+}
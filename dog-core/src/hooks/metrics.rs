@@ -0,0 +1,157 @@
+//! Built-in per-method timing.
+//!
+//! `timing_hook(sink)` is an around hook that measures every service call and
+//! hands one [`MetricSample`] per call to a [`MetricsSink`]. Most apps install
+//! it globally with `DogApp::enable_metrics(sink)`; it can also be registered
+//! on a single service like any other around hook.
+//!
+//! The hook is registered with [`METRICS_HOOK_PRIORITY`], so it is the
+//! outermost around hook and the duration covers the other around hooks,
+//! before hooks, the service call and after hooks. Error hooks run after the
+//! sample is recorded, so an error that an error hook later swallows is still
+//! counted as [`Outcome::Error`].
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use super::{DogAroundHook, HookContext, Next};
+
+/// Priority `enable_metrics` registers the timing hook with: outermost.
+pub const METRICS_HOOK_PRIORITY: i32 = i32::MAX;
+
+/// Whether a timed call returned `Ok` or `Err`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Success,
+    Error,
+}
+
+impl Outcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Outcome::Success => "success",
+            Outcome::Error => "error",
+        }
+    }
+}
+
+/// One timed service call.
+#[derive(Debug, Clone)]
+pub struct MetricSample {
+    /// Service name; empty when the context was built outside a `ServiceHandle`.
+    pub service: String,
+    /// `ServiceMethodKind::as_str`, e.g. `"find"` or a custom method name.
+    pub method: String,
+    pub tenant: String,
+    pub outcome: Outcome,
+    pub duration: Duration,
+}
+
+/// Where timing samples go: a Prometheus registry, a StatsD client, a log line.
+///
+/// `record` is called inline on every service call, so it should be cheap
+/// and must not block.
+pub trait MetricsSink: Send + Sync {
+    fn record(&self, sample: MetricSample);
+}
+
+/// Around hook that reports each call to a [`MetricsSink`].
+pub struct TimingHook {
+    sink: Arc<dyn MetricsSink>,
+}
+
+impl TimingHook {
+    pub fn new(sink: Arc<dyn MetricsSink>) -> Self {
+        Self { sink }
+    }
+}
+
+#[async_trait]
+impl<R, P> DogAroundHook<R, P> for TimingHook
+where
+    R: Send + 'static,
+    P: Send + Clone + 'static,
+{
+    async fn run(&self, ctx: &mut HookContext<R, P>, next: Next<R, P>) -> Result<()> {
+        let started = Instant::now();
+        let res = next.run(ctx).await;
+        self.sink.record(MetricSample {
+            service: ctx.service.clone().unwrap_or_default(),
+            method: ctx.method.as_str().to_string(),
+            tenant: ctx.tenant.tenant_id.0.clone(),
+            outcome: if res.is_ok() {
+                Outcome::Success
+            } else {
+                Outcome::Error
+            },
+            duration: started.elapsed(),
+        });
+        res
+    }
+}
+
+/// Build a timing hook that reports to `sink`.
+pub fn timing_hook<R, P>(sink: Arc<dyn MetricsSink>) -> Arc<dyn DogAroundHook<R, P>>
+where
+    R: Send + 'static,
+    P: Send + Clone + 'static,
+{
+    Arc::new(TimingHook::new(sink))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DogApp, DogService, TenantContext};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recording(Mutex<Vec<MetricSample>>);
+
+    impl MetricsSink for Recording {
+        fn record(&self, sample: MetricSample) {
+            self.0.lock().unwrap().push(sample);
+        }
+    }
+
+    struct Items;
+
+    #[async_trait]
+    impl DogService<(), ()> for Items {
+        async fn find(&self, _ctx: &TenantContext, _params: ()) -> Result<Vec<()>> {
+            Ok(vec![()])
+        }
+
+        async fn get(&self, _ctx: &TenantContext, id: &str, _params: ()) -> Result<()> {
+            anyhow::bail!("no item {id}")
+        }
+    }
+
+    #[tokio::test]
+    async fn each_call_produces_one_sample() {
+        let sink = Arc::new(Recording::default());
+        let mut builder = DogApp::<(), ()>::builder();
+        builder.register_service("items", Arc::new(Items));
+        let app = builder.build();
+        app.enable_metrics(sink.clone());
+
+        let items = app.service("items").unwrap();
+        items.find(TenantContext::new("acme"), ()).await.unwrap();
+        items
+            .get(TenantContext::new("acme"), "42", ())
+            .await
+            .unwrap_err();
+
+        let samples = sink.0.lock().unwrap();
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0].service, "items");
+        assert_eq!(samples[0].method, "find");
+        assert_eq!(samples[0].tenant, "acme");
+        assert_eq!(samples[0].outcome, Outcome::Success);
+        assert_eq!(samples[1].method, "get");
+        assert_eq!(samples[1].outcome, Outcome::Error);
+    }
+}
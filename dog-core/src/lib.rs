@@ -6,6 +6,7 @@ pub mod errors;
 pub mod events;
 pub mod hooks;
 pub mod registry;
+pub mod request;
 pub mod service;
 pub mod tenant;
 
@@ -14,16 +15,23 @@ pub mod adapters;
 
 // Branch: DogAppBuilder, ServiceHandle, ServiceBuilderHandle (builder-pattern refactor)
 // Main: ErrorValue, DogValue re-exports (format-agnostic serde PR)
-pub use app::{DogApp, DogAppBuilder, ServiceBuilderHandle, ServiceCaller, ServiceHandle};
-pub use config::{DogConfig, DogConfigSnapshot};
+pub use app::{
+    DogApp, DogAppBuilder, ServiceBuilderHandle, ServiceCaller, ServiceHandle, ServiceRef,
+};
+pub use config::{ConfigSource, DogConfig, DogConfigSnapshot};
 #[cfg(all(feature = "serde", not(feature = "json")))]
 pub use errors::DogValue;
 pub use errors::{DogError, DogResult, ErrorKind, ErrorValue};
-pub use events::{method_to_standard_event, DogEventHub, ServiceEventData, ServiceEventKind};
+pub use events::{
+    method_to_standard_event, DogEventHub, ServiceErrorEvent, ServiceEventData, ServiceEventKind,
+};
 pub use hooks::{
-    DogAfterHook, DogAroundHook, DogBeforeHook, DogErrorHook, HookContext, HookResult, Next,
-    ServiceHooks,
+    DogAfterHook, DogAroundHook, DogBeforeHook, DogErrorHook, HookContext, HookList, HookResult,
+    Next, PrioritizedHook, ServiceHooks, DEFAULT_HOOK_PRIORITY,
 };
 pub use registry::DogServiceRegistry;
-pub use service::{DogService, ServiceCapabilities, ServiceMethodKind};
-pub use tenant::{TenantContext, TenantId};
+pub use request::RequestMeta;
+pub use service::{DogService, Paginated, ServiceCapabilities, ServiceMethodKind};
+pub use tenant::{
+    HeaderTenant, SubdomainTenant, TenantContext, TenantId, TenantResolver, DEFAULT_TENANT,
+};
@@ -36,6 +36,18 @@ where
     pub fn get(&self, name: &str) -> Option<&Arc<dyn DogService<R, P>>> {
         self.services.get(name)
     }
+
+    /// Registered service names, sorted.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.services.keys().cloned().collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Remove a service, returning it if it was registered.
+    pub fn remove(&mut self, name: &str) -> Option<Arc<dyn DogService<R, P>>> {
+        self.services.remove(name)
+    }
 }
 
 impl<R, P> Default for DogServiceRegistry<R, P>
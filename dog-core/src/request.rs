@@ -0,0 +1,67 @@
+//! Transport details of the request behind a service call.
+//!
+//! Adapters fill in a [`RequestMeta`] and attach it with
+//! [`crate::ServiceHandle::with_request`], so hooks can read headers, the
+//! path or the caller's address through `ctx.request` the same way for
+//! every transport. Calls made from code (tests, jobs, `ctx.services`) have
+//! no request, and `ctx.request` is `None`.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+#[derive(Debug, Clone, Default)]
+pub struct RequestMeta {
+    /// Transport that received the request, e.g. `rest`.
+    pub provider: String,
+    /// Transport method, e.g. `GET`.
+    pub method: String,
+    pub path: String,
+    /// Header values by lowercase name. A header sent more than once holds
+    /// all of its values, joined as [`RequestMeta::with_header`] does.
+    pub headers: HashMap<String, String>,
+    pub remote_addr: Option<SocketAddr>,
+}
+
+impl RequestMeta {
+    pub fn new(
+        provider: impl Into<String>,
+        method: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Self {
+        Self {
+            provider: provider.into(),
+            method: method.into(),
+            path: path.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Add a header value. Repeated headers are combined into one value the
+    /// way HTTP allows: joined with `, `, or `; ` for `cookie`.
+    pub fn with_header(mut self, name: &str, value: impl Into<String>) -> Self {
+        let name = name.to_ascii_lowercase();
+        let value = value.into();
+        match self.headers.get_mut(&name) {
+            Some(existing) => {
+                existing.push_str(if name == "cookie" { "; " } else { ", " });
+                existing.push_str(&value);
+            }
+            None => {
+                self.headers.insert(name, value);
+            }
+        }
+        self
+    }
+
+    pub fn with_remote_addr(mut self, addr: SocketAddr) -> Self {
+        self.remote_addr = Some(addr);
+        self
+    }
+
+    /// A header value, matching `name` case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+}
@@ -1,6 +1,7 @@
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 
+use crate::errors::DogError;
 use crate::tenant::TenantContext;
 
 /// Macro to generate custom methods in the DogService trait
@@ -23,7 +24,9 @@ macro_rules! custom_methods {
 /// Standard service methods, similar to Feathers:
 /// find, get, create, update, patch, remove.
 ///
-/// Custom methods are declared via `Custom("methodName")`.
+/// Custom methods are declared via `ServiceMethodKind::custom("methodName")`.
+/// The name is owned so adapters can build it from a request (e.g. the
+/// `x-service-method` header) without leaking strings.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ServiceMethodKind {
     Find,
@@ -32,7 +35,28 @@ pub enum ServiceMethodKind {
     Update,
     Patch,
     Remove,
-    Custom(&'static str),
+    Custom(String),
+}
+
+impl ServiceMethodKind {
+    /// Shorthand for `ServiceMethodKind::Custom(name.into())`.
+    pub fn custom(name: impl Into<String>) -> Self {
+        ServiceMethodKind::Custom(name.into())
+    }
+
+    /// Method name as used in capabilities and adapters
+    /// (`"find"`, ..., or the custom method's own name).
+    pub fn as_str(&self) -> &str {
+        match self {
+            ServiceMethodKind::Find => "find",
+            ServiceMethodKind::Get => "get",
+            ServiceMethodKind::Create => "create",
+            ServiceMethodKind::Update => "update",
+            ServiceMethodKind::Patch => "patch",
+            ServiceMethodKind::Remove => "remove",
+            ServiceMethodKind::Custom(name) => name,
+        }
+    }
 }
 
 /// Capabilities describe which methods a service wants to expose
@@ -42,6 +66,9 @@ pub enum ServiceMethodKind {
 #[derive(Debug, Clone)]
 pub struct ServiceCapabilities {
     pub allowed_methods: Vec<ServiceMethodKind>,
+    /// Whether `find` should be served as a page (`{ total, limit, skip, data }`)
+    /// rather than a bare list. Off by default; see [`ServiceCapabilities::with_pagination`].
+    pub paginate: bool,
 }
 
 impl ServiceCapabilities {
@@ -51,6 +78,7 @@ impl ServiceCapabilities {
         use ServiceMethodKind::*;
         Self {
             allowed_methods: vec![Find, Get, Create, Update, Patch, Remove],
+            paginate: false,
         }
     }
 
@@ -59,6 +87,7 @@ impl ServiceCapabilities {
         use ServiceMethodKind::*;
         Self {
             allowed_methods: vec![Find, Create],
+            paginate: false,
         }
     }
 
@@ -66,15 +95,56 @@ impl ServiceCapabilities {
     pub fn from_methods(methods: Vec<ServiceMethodKind>) -> Self {
         Self {
             allowed_methods: methods,
+            paginate: false,
         }
     }
+
+    /// Ask adapters to call `find_paginated` and return the Feathers page
+    /// envelope for list requests.
+    pub fn with_pagination(mut self) -> Self {
+        self.paginate = true;
+        self
+    }
+}
+
+/// One page of `find` results plus the paging info a list endpoint needs.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Paginated<R> {
+    pub data: Vec<R>,
+    /// Total number of matching records across all pages.
+    pub total: u64,
+    pub limit: usize,
+    pub skip: usize,
+}
+
+impl<R> Paginated<R> {
+    pub fn new(data: Vec<R>, total: u64, limit: usize, skip: usize) -> Self {
+        Self {
+            data,
+            total,
+            limit,
+            skip,
+        }
+    }
+
+    /// Treat a plain list as a single page holding everything.
+    pub fn unpaged(data: Vec<R>) -> Self {
+        let len = data.len();
+        Self::new(data, len as u64, len, 0)
+    }
+
+    /// Whether records exist beyond this page.
+    pub fn has_more(&self) -> bool {
+        (self.skip as u64).saturating_add(self.data.len() as u64) < self.total
+    }
 }
 
 /// Core DogRS service trait, inspired by FeathersJS:
 ///
 /// - `find`   → list/query many
 /// - `get`    → fetch one by id
-/// - `create` → create one (or conceptually many)
+/// - `create` → create one (`create_many` for a batch)
 /// - `update` → full replace
 /// - `patch`  → partial update
 /// - `remove` → delete one or many
@@ -92,7 +162,9 @@ where
     /// Describe which methods this service wants to expose.
     ///
     /// Adapters (HTTP, P2P, etc.) should respect this when deciding
-    /// what is callable from the outside world.
+    /// what is callable from the outside world. `ServiceHandle` enforces
+    /// it too: calling a method that is not listed fails with
+    /// `MethodNotAllowed` (405) before any hook runs.
     fn capabilities(&self) -> ServiceCapabilities {
         // By default, assume full CRUD.
         ServiceCapabilities::standard_crud()
@@ -103,6 +175,15 @@ where
         Err(anyhow!("Method not implemented: find"))
     }
 
+    /// Find one page of records, including the total match count.
+    ///
+    /// Services that can count matches (or apply `$limit`/`$skip` from their
+    /// params) should override this. The default calls `find` and returns
+    /// everything as a single page.
+    async fn find_paginated(&self, ctx: &TenantContext, params: P) -> Result<Paginated<R>> {
+        Ok(Paginated::unpaged(self.find(ctx, params).await?))
+    }
+
     /// Get a single record by id.
     async fn get(&self, _ctx: &TenantContext, _id: &str, _params: P) -> Result<R> {
         Err(anyhow!("Method not implemented: get"))
@@ -110,12 +191,27 @@ where
 
     /// Create a new record.
     ///
-    /// For many-record semantics, an adapter or higher-level
-    /// service can wrap this in a loop or accept Vec<R>.
+    /// For many-record semantics see `create_many`.
     async fn create(&self, _ctx: &TenantContext, _data: R, _params: P) -> Result<R> {
         Err(anyhow!("Method not implemented: create"))
     }
 
+    /// Create several records in one call, returning them in input order.
+    ///
+    /// `ServiceHandle::create_many` calls this once per batch. Override it
+    /// to batch-insert; the default calls `create` for each record and stops
+    /// at the first error.
+    async fn create_many(&self, ctx: &TenantContext, data: Vec<R>, params: P) -> Result<Vec<R>>
+    where
+        P: Clone,
+    {
+        let mut created = Vec::with_capacity(data.len());
+        for record in data {
+            created.push(self.create(ctx, record, params.clone()).await?);
+        }
+        Ok(created)
+    }
+
     /// Fully replace an existing record.
     ///
     /// `id` is required (no multi-update here at core level).
@@ -137,23 +233,74 @@ where
         Err(anyhow!("Method not implemented: patch"))
     }
 
-    /// Remove an existing record.
+    /// Apply the same patch to each of `ids`, returning the patched records
+    /// in order.
+    ///
+    /// The default calls `patch` once per id and stops at the first error.
+    async fn patch_many(
+        &self,
+        ctx: &TenantContext,
+        ids: &[String],
+        data: R,
+        params: P,
+    ) -> Result<Vec<R>>
+    where
+        R: Clone,
+        P: Clone,
+    {
+        let mut patched = Vec::with_capacity(ids.len());
+        for id in ids {
+            patched.push(
+                self.patch(ctx, Some(id), data.clone(), params.clone())
+                    .await?,
+            );
+        }
+        Ok(patched)
+    }
+
+    /// Remove an existing record and return it.
     ///
     /// `id` can be `None` to indicate "multi" semantics if
-    /// an adapter / implementation supports it.
+    /// an adapter / implementation supports it; return every removed record
+    /// in one `R` then (e.g. a JSON array). Either way the app emits a
+    /// single `removed` event carrying what this returns.
     async fn remove(&self, _ctx: &TenantContext, _id: Option<&str>, _params: P) -> Result<R> {
         Err(anyhow!("Method not implemented: remove"))
     }
 
+    /// Remove each of `ids`, returning the removed records in order.
+    ///
+    /// The default calls `remove` once per id and stops at the first error.
+    async fn remove_many(&self, ctx: &TenantContext, ids: &[String], params: P) -> Result<Vec<R>>
+    where
+        P: Clone,
+    {
+        let mut removed = Vec::with_capacity(ids.len());
+        for id in ids {
+            removed.push(self.remove(ctx, Some(id), params.clone()).await?);
+        }
+        Ok(removed)
+    }
+
     /// Handle custom methods - the best we can do in Rust for dynamic dispatch
     /// Services implement this to route to their specific custom methods
+    ///
+    /// Calls made through `ServiceHandle::custom` run the same hook pipeline
+    /// as CRUD methods, with `ctx.method == ServiceMethodKind::Custom(method)`.
+    /// The service must list `ServiceMethodKind::custom("name")` in
+    /// [`DogService::capabilities`] (`standard_crud` lists no custom methods);
+    /// otherwise the call fails with `MethodNotAllowed` (405) before any hook
+    /// runs. The default returns a `NotImplemented` (501) `DogError`.
     async fn custom(
         &self,
         _ctx: &TenantContext,
-        _method: &str,
+        method: &str,
         _data: Option<R>,
         _params: P,
     ) -> Result<R> {
-        Err(anyhow!("Custom methods not implemented by this service"))
+        Err(
+            DogError::not_implemented(format!("Custom method not implemented: {method}"))
+                .into_anyhow(),
+        )
     }
 }
@@ -1,5 +1,12 @@
 //! Core multi-tenant types for DogRS.
 
+use async_trait::async_trait;
+
+use crate::RequestMeta;
+
+/// Tenant used when no resolver finds one.
+pub const DEFAULT_TENANT: &str = "default";
+
 /// A simple tenant identifier.
 /// Later this can be a UUID, slug, or composite key.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -23,3 +30,167 @@ impl TenantContext {
         }
     }
 }
+
+/// Works out which tenant a transport request belongs to.
+///
+/// Install resolvers once with `DogAppBuilder::tenant_resolver`; adapters
+/// call `DogApp::resolve_tenant` for each request. Resolvers are tried in
+/// the order they were installed, and the first `Some` wins.
+#[async_trait]
+pub trait TenantResolver: Send + Sync {
+    /// The tenant of `request`, or `None` to let the next resolver try.
+    async fn resolve(&self, request: &RequestMeta) -> Option<TenantContext>;
+}
+
+/// The tenant named by a header, `x-tenant-id` by default.
+///
+/// This is what adapters do when no resolver is installed.
+#[derive(Debug, Clone)]
+pub struct HeaderTenant {
+    header: String,
+}
+
+impl HeaderTenant {
+    pub fn new(header: impl Into<String>) -> Self {
+        Self {
+            header: header.into(),
+        }
+    }
+}
+
+impl Default for HeaderTenant {
+    fn default() -> Self {
+        Self::new("x-tenant-id")
+    }
+}
+
+#[async_trait]
+impl TenantResolver for HeaderTenant {
+    async fn resolve(&self, request: &RequestMeta) -> Option<TenantContext> {
+        request
+            .header(&self.header)
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(TenantContext::new)
+    }
+}
+
+/// The tenant named by the label of the `host` header just under `domain`,
+/// e.g. `acme` for `acme.example.com` or `www.acme.example.com` with
+/// `domain` `example.com`.
+///
+/// The bare domain and hosts outside it resolve to nothing.
+#[derive(Debug, Clone)]
+pub struct SubdomainTenant {
+    domain: String,
+}
+
+impl SubdomainTenant {
+    pub fn new(domain: impl Into<String>) -> Self {
+        Self {
+            domain: domain.into().trim_matches('.').to_ascii_lowercase(),
+        }
+    }
+}
+
+#[async_trait]
+impl TenantResolver for SubdomainTenant {
+    async fn resolve(&self, request: &RequestMeta) -> Option<TenantContext> {
+        let host = request.header("host")?.to_ascii_lowercase();
+        // Drop the port, if any.
+        let host = host.rsplit_once(':').map_or(host.as_str(), |(h, port)| {
+            if port.chars().all(|c| c.is_ascii_digit()) {
+                h
+            } else {
+                host.as_str()
+            }
+        });
+        let sub = host.strip_suffix(&self.domain)?.strip_suffix('.')?;
+        let tenant = sub.rsplit('.').next()?;
+        (!tenant.is_empty()).then(|| TenantContext::new(tenant))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DogApp;
+    use std::sync::Arc;
+
+    fn request(headers: &[(&str, &str)]) -> RequestMeta {
+        headers
+            .iter()
+            .fold(RequestMeta::new("rest", "GET", "/"), |meta, (k, v)| {
+                meta.with_header(k, *v)
+            })
+    }
+
+    async fn tenant(app: &DogApp<(), ()>, headers: &[(&str, &str)]) -> String {
+        app.resolve_tenant(&request(headers)).await.tenant_id.0
+    }
+
+    async fn resolved(resolver: &dyn TenantResolver, headers: &[(&str, &str)]) -> Option<String> {
+        resolver
+            .resolve(&request(headers))
+            .await
+            .map(|t| t.tenant_id.0)
+    }
+
+    #[tokio::test]
+    async fn header_resolver_reads_its_header() {
+        let resolver = HeaderTenant::new("x-org");
+
+        assert_eq!(
+            resolved(&resolver, &[("X-Org", " acme ")]).await.as_deref(),
+            Some("acme")
+        );
+        assert_eq!(resolved(&resolver, &[("x-org", "")]).await, None);
+        assert_eq!(resolved(&resolver, &[("x-tenant-id", "acme")]).await, None);
+    }
+
+    #[tokio::test]
+    async fn subdomain_resolver_takes_the_label_under_the_domain() {
+        let resolver = SubdomainTenant::new("example.com");
+        let cases = [
+            ("acme.example.com", Some("acme")),
+            ("ACME.Example.com:8080", Some("acme")),
+            ("www.acme.example.com", Some("acme")),
+            ("example.com", None),
+            ("acme.example.org", None),
+            ("notexample.com", None),
+        ];
+
+        for (host, tenant) in cases {
+            assert_eq!(
+                resolved(&resolver, &[("host", host)]).await.as_deref(),
+                tenant,
+                "{host}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn app_without_resolvers_uses_the_tenant_header() {
+        let app = DogApp::<(), ()>::default();
+
+        assert_eq!(tenant(&app, &[("x-tenant-id", "acme")]).await, "acme");
+        assert_eq!(tenant(&app, &[]).await, DEFAULT_TENANT);
+    }
+
+    #[tokio::test]
+    async fn resolvers_are_tried_in_order_then_fall_back_to_default() {
+        let mut builder = DogApp::<(), ()>::builder();
+        builder.tenant_resolver(Arc::new(SubdomainTenant::new("example.com")));
+        builder.tenant_resolver(Arc::new(HeaderTenant::new("x-org")));
+        let app = builder.build();
+
+        let both = [("host", "acme.example.com"), ("x-org", "globex")];
+        assert_eq!(tenant(&app, &both).await, "acme");
+        assert_eq!(tenant(&app, &[("x-org", "globex")]).await, "globex");
+        // Installed resolvers replace the default header lookup.
+        assert_eq!(
+            tenant(&app, &[("x-tenant-id", "initech")]).await,
+            DEFAULT_TENANT
+        );
+    }
+}
@@ -1,9 +1,8 @@
 use std::sync::Arc;
 
-use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use dog_axum::oauth;
-use dog_axum::AxumApp;
+use dog_axum::{AxumApp, DogAxumError};
 use serde_json::Value;
 
 use crate::services::AuthDemoParams;
@@ -66,7 +65,7 @@ pub fn mount(ax: AxumApp<Value, AuthDemoParams>) -> AxumApp<Value, AuthDemoParam
             async move {
                 let res: Response = match google_login_service_handler(app_arc).await {
                     Ok(r) => r.into_response(),
-                    Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+                    Err(e) => DogAxumError::from(e).into_response(),
                 };
                 res
             }
@@ -35,7 +35,7 @@ fn google_provider_with_redirect(
             "email".to_string(),
             "profile".to_string(),
         ],
-        userinfo_url: Some("https://openidconnect.googleapis.com/v1/userinfo".to_string()),
+        user_info_url: Some("https://openidconnect.googleapis.com/v1/userinfo".to_string()),
     })
 }
 
@@ -64,7 +64,7 @@ impl OAuthEntityResolver<AuthDemoParams> for GoogleEntityResolver {
             return Ok(None);
         }
 
-        let all = users.find(&ctx.tenant, ctx.params.clone()).await?;
+        let all = users.find(ctx.tenant.clone(), ctx.params.clone()).await?;
         if let Some(existing) = all
             .into_iter()
             .find(|u| u.get("googleId").and_then(|v| v.as_str()) == Some(google_id))
@@ -82,7 +82,7 @@ impl OAuthEntityResolver<AuthDemoParams> for GoogleEntityResolver {
         let random_pw = uuid::Uuid::new_v4().to_string();
         let created = users
             .create(
-                &ctx.tenant,
+                ctx.tenant.clone(),
                 json!({
                     "username": username,
                     "password": random_pw,
@@ -18,7 +18,7 @@ impl DogAfterHook<Value, AuthDemoParams> for StripPasswordFromAuthResult {
                     user.remove("password");
                 }
             }
-            HookResult::Many(items) => {
+            HookResult::Many(items) | HookResult::Page { data: items, .. } => {
                 for v in items {
                     if let Some(user) = v.get_mut("user").and_then(|u| u.as_object_mut()) {
                         user.remove("password");
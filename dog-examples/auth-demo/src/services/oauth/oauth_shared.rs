@@ -5,8 +5,8 @@ use std::sync::Arc;
 pub fn crud_capabilities() -> dog_core::ServiceCapabilities {
     use dog_core::ServiceMethodKind;
     dog_core::ServiceCapabilities::from_methods(vec![
-        ServiceMethodKind::Custom("google_login"),
-        ServiceMethodKind::Custom("google_callback"),
+        ServiceMethodKind::custom("google_login"),
+        ServiceMethodKind::custom("google_callback"),
     ])
 }
 
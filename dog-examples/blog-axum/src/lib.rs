@@ -20,6 +20,8 @@ pub async fn build() -> Result<AxumApp<Value, BlogParams>> {
     let ax = dog_axum::axum(builder.build())
         .use_service("/posts", svcs.posts)
         .use_service("/authors", svcs.authors)
+        .use_openapi("/openapi.json")
+        .use_ws("/ws")
         .service("/health", || async { "ok" });
 
     Ok(ax)
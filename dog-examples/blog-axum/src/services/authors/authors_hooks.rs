@@ -61,7 +61,7 @@ impl DogBeforeHook<Value, BlogParams> for EnforceAuthorOnDelete {
         params
             .query
             .insert("includeDrafts".to_string(), "true".to_string());
-        let all_posts = posts.find(&ctx.tenant, params.clone()).await?;
+        let all_posts = posts.find(ctx.tenant.clone(), params.clone()).await?;
 
         let referencing: Vec<Value> = all_posts
             .into_iter()
@@ -82,7 +82,7 @@ impl DogBeforeHook<Value, BlogParams> for EnforceAuthorOnDelete {
                 for p in referencing {
                     if let Some(id) = p.get("id").and_then(|v| v.as_str()) {
                         let _ = posts
-                            .remove(&ctx.tenant, Some(id), ctx.params.clone())
+                            .remove(ctx.tenant.clone(), Some(id), ctx.params.clone())
                             .await?;
                     }
                 }
@@ -93,7 +93,7 @@ impl DogBeforeHook<Value, BlogParams> for EnforceAuthorOnDelete {
                     if let Some(id) = p.get("id").and_then(|v| v.as_str()) {
                         let patch = json!({"author_id": Value::Null});
                         let _ = posts
-                            .patch(&ctx.tenant, Some(id), patch, ctx.params.clone())
+                            .patch(ctx.tenant.clone(), Some(id), patch, ctx.params.clone())
                             .await?;
                     }
                 }
@@ -52,7 +52,7 @@ impl DogBeforeHook<Value, BlogParams> for ValidatePostAuthorExists {
         // Ensure the author exists in this tenant.
         let authors = ctx.services.service("authors")?;
         let res = authors
-            .get(&ctx.tenant, author_id, ctx.params.clone())
+            .get(ctx.tenant.clone(), author_id, ctx.params.clone())
             .await;
         if res.is_err() {
             return Err(DogError::unprocessable("Posts schema validation failed")
@@ -90,7 +90,7 @@ async fn expand_one_author(ctx: &HookContext<Value, BlogParams>, mut v: Value) -
 
     let authors = ctx.services.service("authors")?;
     if let Ok(author) = authors
-        .get(&ctx.tenant, author_id, ctx.params.clone())
+        .get(ctx.tenant.clone(), author_id, ctx.params.clone())
         .await
     {
         obj.insert("author".to_string(), author);
@@ -121,6 +121,23 @@ impl DogAfterHook<Value, BlogParams> for ExpandPostAuthor {
                 }
                 HookResult::Many(out)
             }
+            HookResult::Page {
+                data,
+                total,
+                limit,
+                skip,
+            } => {
+                let mut out = Vec::with_capacity(data.len());
+                for v in data {
+                    out.push(expand_one_author(ctx, v).await?);
+                }
+                HookResult::Page {
+                    data: out,
+                    total,
+                    limit,
+                    skip,
+                }
+            }
         });
 
         Ok(())
@@ -204,6 +221,20 @@ impl DogAfterHook<Value, BlogParams> for NormalizePostsResult {
                     .map(|v| normalize_one(v, &default_body))
                     .collect(),
             ),
+            HookResult::Page {
+                data,
+                total,
+                limit,
+                skip,
+            } => HookResult::Page {
+                data: data
+                    .into_iter()
+                    .map(|v| normalize_one(v, &default_body))
+                    .collect(),
+                total,
+                limit,
+                skip,
+            },
         });
 
         Ok(())
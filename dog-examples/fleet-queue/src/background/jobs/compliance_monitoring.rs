@@ -30,7 +30,12 @@ impl Job for ComplianceMonitoringJob {
     const PRIORITY: JobPriority = JobPriority::High;
     const MAX_RETRIES: u32 = 3;
 
-    async fn execute(&self, ctx: Self::Context) -> Result<Self::Result, JobError> {
+    async fn execute(
+        &self,
+        ctx: Self::Context,
+        _cancel: CancellationToken,
+        _progress: ProgressReporter,
+    ) -> Result<Self::Result, JobError> {
         let tenant_ctx = TenantContext::new(ctx.tenant_id.clone());
         let params = FleetParams::default();
 
@@ -7,11 +7,16 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GPSTrackingJob {
     pub assignment_id: String,
+    /// Tenant of the request that enqueued the job; its writes go there too
+    pub tenant_id: String,
 }
 
 impl GPSTrackingJob {
-    pub fn new(assignment_id: String) -> Self {
-        Self { assignment_id }
+    pub fn new(assignment_id: String, tenant_id: String) -> Self {
+        Self {
+            assignment_id,
+            tenant_id,
+        }
     }
 }
 
@@ -24,13 +29,18 @@ impl Job for GPSTrackingJob {
     const PRIORITY: JobPriority = JobPriority::Normal;
     const MAX_RETRIES: u32 = 3;
 
-    async fn execute(&self, ctx: Self::Context) -> Result<Self::Result, JobError> {
+    async fn execute(
+        &self,
+        ctx: Self::Context,
+        _cancel: CancellationToken,
+        _progress: ProgressReporter,
+    ) -> Result<Self::Result, JobError> {
         println!(
             "🚀 GPS JOB EXECUTING for assignment: {}",
             self.assignment_id
         );
 
-        let tenant_ctx = TenantContext::new(ctx.tenant_id.clone());
+        let tenant_ctx = TenantContext::new(self.tenant_id.clone());
         let params = FleetParams::default();
 
         let operations_service = ctx
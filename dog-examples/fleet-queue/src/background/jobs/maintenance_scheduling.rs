@@ -37,7 +37,12 @@ impl Job for MaintenanceSchedulingJob {
     const PRIORITY: JobPriority = JobPriority::Normal;
     const MAX_RETRIES: u32 = 3;
 
-    async fn execute(&self, ctx: Self::Context) -> Result<Self::Result, JobError> {
+    async fn execute(
+        &self,
+        ctx: Self::Context,
+        _cancel: CancellationToken,
+        _progress: ProgressReporter,
+    ) -> Result<Self::Result, JobError> {
         let tenant_ctx = TenantContext::new(ctx.tenant_id.clone());
         let params = FleetParams::default();
 
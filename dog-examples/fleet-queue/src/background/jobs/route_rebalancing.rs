@@ -9,6 +9,8 @@ pub struct RouteRebalancingJob {
     pub affected_routes: Vec<String>,
     pub traffic_delay_minutes: i32,
     pub trigger_reason: String,
+    /// Tenant of the request that enqueued the job; its writes go there too
+    pub tenant_id: String,
 }
 
 impl RouteRebalancingJob {
@@ -16,11 +18,13 @@ impl RouteRebalancingJob {
         affected_routes: Vec<String>,
         traffic_delay_minutes: i32,
         trigger_reason: String,
+        tenant_id: String,
     ) -> Self {
         Self {
             affected_routes,
             traffic_delay_minutes,
             trigger_reason,
+            tenant_id,
         }
     }
 }
@@ -34,13 +38,18 @@ impl Job for RouteRebalancingJob {
     const PRIORITY: JobPriority = JobPriority::High;
     const MAX_RETRIES: u32 = 2;
 
-    async fn execute(&self, ctx: Self::Context) -> Result<Self::Result, JobError> {
+    async fn execute(
+        &self,
+        ctx: Self::Context,
+        _cancel: CancellationToken,
+        _progress: ProgressReporter,
+    ) -> Result<Self::Result, JobError> {
         println!(
             "🛣️  ROUTE REBALANCING JOB EXECUTING | tenant={} | routes={:?} | reason={}",
-            ctx.tenant_id, self.affected_routes, self.trigger_reason
+            self.tenant_id, self.affected_routes, self.trigger_reason
         );
 
-        let tenant_ctx = TenantContext::new(ctx.tenant_id.clone());
+        let tenant_ctx = TenantContext::new(self.tenant_id.clone());
         let params = FleetParams::default();
 
         let operations_service = ctx
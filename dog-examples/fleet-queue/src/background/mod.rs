@@ -5,6 +5,7 @@ pub mod jobs;
 
 use crate::services::FleetParams;
 use anyhow::Result;
+use dog_core::tenant::TenantContext;
 use dog_core::DogApp;
 use dog_queue::backend::memory::MemoryBackend;
 use dog_queue::prelude::*;
@@ -77,24 +78,37 @@ impl BackgroundSystem {
         Ok(())
     }
 
-    /// Enqueue a GPS tracking job for a specific assignment
-    pub async fn enqueue_gps_tracking(&self, assignment_id: String) -> Result<()> {
+    /// Enqueue a GPS tracking job for a specific assignment, run for `tenant`
+    ///
+    /// The queue context stays `fleet_tenant`, the one the workers lease
+    /// from; the job itself carries the tenant its service calls run in.
+    pub async fn enqueue_gps_tracking(
+        &self,
+        tenant: &TenantContext,
+        assignment_id: String,
+    ) -> Result<()> {
         let ctx = QueueCtx::new("fleet_tenant".to_string());
-        let job = GPSTrackingJob::new(assignment_id);
+        let job = GPSTrackingJob::new(assignment_id, tenant.tenant_id.0.clone());
 
         self.adapter.enqueue(ctx, job).await?;
         Ok(())
     }
 
-    /// Enqueue a Route Rebalancing job
+    /// Enqueue a Route Rebalancing job, run for `tenant`
     pub async fn enqueue_route_rebalancing(
         &self,
+        tenant: &TenantContext,
         affected_routes: Vec<String>,
         traffic_delay_minutes: i32,
         trigger_reason: String,
     ) -> Result<()> {
         let ctx = QueueCtx::new("fleet_tenant".to_string());
-        let job = RouteRebalancingJob::new(affected_routes, traffic_delay_minutes, trigger_reason);
+        let job = RouteRebalancingJob::new(
+            affected_routes,
+            traffic_delay_minutes,
+            trigger_reason,
+            tenant.tenant_id.0.clone(),
+        );
 
         self.adapter.enqueue(ctx, job).await?;
         Ok(())
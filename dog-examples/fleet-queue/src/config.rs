@@ -1,6 +1,6 @@
 use crate::services::FleetParams;
 use anyhow::Result;
-use dog_core::{tenant::TenantContext, DogApp};
+use dog_core::{tenant::TenantContext, ConfigSource, DogApp};
 use serde_json::{json, Value};
 use std::env;
 
@@ -61,6 +61,8 @@ fn configure_http(dog_app: &mut dog_core::DogAppBuilder<Value, FleetParams>) ->
 
     dog_app.set("http.host", host);
     dog_app.set("http.port", port);
+    // e.g. DOG_HTTP__CORS__ORIGINS=https://app.example.com
+    dog_app.configure_from([ConfigSource::env("DOG")])?;
     Ok(())
 }
 
@@ -55,15 +55,11 @@ pub async fn build() -> anyhow::Result<AxumApp<Value, FleetParams>> {
     // Start background system with built app
     background_system.start(dog_app).await?;
 
-    // Add CORS middleware to allow browser requests
+    // Cross-origin access comes from `http.cors.*` (denied when unset)
+    let cors = dog_axum::middlewares::cors_from_config(&ax.app)?;
     ax.router = ax
         .router
-        .layer(
-            tower_http::cors::CorsLayer::new()
-                .allow_origin(tower_http::cors::Any)
-                .allow_methods(tower_http::cors::Any)
-                .allow_headers(tower_http::cors::Any),
-        )
+        .layer(cors)
         .fallback_service(tower_http::services::ServeDir::new("static"));
 
     Ok(ax)
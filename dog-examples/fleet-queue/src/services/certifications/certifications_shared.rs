@@ -3,8 +3,8 @@ use dog_core::{ServiceCapabilities, ServiceMethodKind};
 
 pub fn capabilities() -> ServiceCapabilities {
     ServiceCapabilities::from_methods(vec![
-        ServiceMethodKind::Custom("read"),
-        ServiceMethodKind::Custom("write"),
+        ServiceMethodKind::custom("read"),
+        ServiceMethodKind::custom("write"),
     ])
 }
 
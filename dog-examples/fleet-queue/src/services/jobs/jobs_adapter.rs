@@ -1,5 +1,6 @@
 use crate::background::BackgroundSystem;
 use anyhow::Result;
+use dog_core::tenant::TenantContext;
 use serde_json::Value;
 use std::sync::Arc;
 
@@ -12,7 +13,7 @@ impl JobsAdapter {
         Ok(Self { background_system })
     }
 
-    pub async fn enqueue_job(&self, data: Value) -> Result<Value> {
+    pub async fn enqueue_job(&self, tenant: &TenantContext, data: Value) -> Result<Value> {
         let job_type = data
             .get("job_type")
             .and_then(|v| v.as_str())
@@ -26,7 +27,7 @@ impl JobsAdapter {
                     .ok_or_else(|| anyhow::anyhow!("assignment_id is required for gps_tracking"))?;
 
                 self.background_system
-                    .enqueue_gps_tracking(assignment_id.to_string())
+                    .enqueue_gps_tracking(tenant, assignment_id.to_string())
                     .await?;
 
                 Ok(serde_json::json!({
@@ -45,7 +46,7 @@ impl JobsAdapter {
                     .to_string();
 
                 self.background_system
-                    .enqueue_route_rebalancing(vec!["ALL".to_string()], 0, trigger_reason)
+                    .enqueue_route_rebalancing(tenant, vec!["ALL".to_string()], 0, trigger_reason)
                     .await?;
 
                 Ok(serde_json::json!({
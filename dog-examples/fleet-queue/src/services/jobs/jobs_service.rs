@@ -29,7 +29,7 @@ impl DogService<Value, FleetParams> for JobsService {
 
     async fn custom(
         &self,
-        _ctx: &TenantContext,
+        ctx: &TenantContext,
         method: &str,
         data: Option<Value>,
         _params: FleetParams,
@@ -40,7 +40,7 @@ impl DogService<Value, FleetParams> for JobsService {
                     DogError::new(ErrorKind::BadRequest, "Missing job data".to_string())
                 })?;
 
-                self.adapter.enqueue_job(data).await.map_err(|e| {
+                self.adapter.enqueue_job(ctx, data).await.map_err(|e| {
                     DogError::new(ErrorKind::GeneralError, e.to_string()).into_anyhow()
                 })
             }
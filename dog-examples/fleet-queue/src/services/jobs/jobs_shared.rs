@@ -4,19 +4,19 @@ use serde_json::Value;
 
 pub fn capabilities() -> ServiceCapabilities {
     ServiceCapabilities::from_methods(vec![
-        ServiceMethodKind::Custom("enqueue"),
-        ServiceMethodKind::Custom("stats"),
+        ServiceMethodKind::custom("enqueue"),
+        ServiceMethodKind::custom("stats"),
     ])
 }
 
 pub fn register_hooks(app: &mut DogAppBuilder<Value, FleetParams>) -> anyhow::Result<()> {
     app.service_hooks("jobs", |h| {
         h.before(
-            ServiceMethodKind::Custom("enqueue"),
+            ServiceMethodKind::custom("enqueue"),
             std::sync::Arc::new(super::jobs_hooks::BeforeEnqueue),
         );
         h.after(
-            ServiceMethodKind::Custom("enqueue"),
+            ServiceMethodKind::custom("enqueue"),
             std::sync::Arc::new(super::jobs_hooks::AfterEnqueue),
         );
     });
@@ -4,8 +4,8 @@ use std::sync::Arc;
 
 pub fn capabilities() -> ServiceCapabilities {
     ServiceCapabilities::from_methods(vec![
-        ServiceMethodKind::Custom("read"),
-        ServiceMethodKind::Custom("write"),
+        ServiceMethodKind::custom("read"),
+        ServiceMethodKind::custom("write"),
     ])
 }
 
@@ -22,11 +22,11 @@ pub fn register_hooks(
             Arc::new(super::operations_hooks::AfterRead),
         );
         h.before(
-            dog_core::ServiceMethodKind::Custom("write"),
+            dog_core::ServiceMethodKind::custom("write"),
             Arc::new(super::operations_hooks::BeforeWrite),
         );
         h.after(
-            dog_core::ServiceMethodKind::Custom("write"),
+            dog_core::ServiceMethodKind::custom("write"),
             Arc::new(super::operations_hooks::AfterWrite),
         );
     });
@@ -4,8 +4,8 @@ use std::sync::Arc;
 
 pub fn capabilities() -> ServiceCapabilities {
     ServiceCapabilities::from_methods(vec![
-        ServiceMethodKind::Custom("read"),
-        ServiceMethodKind::Custom("write"),
+        ServiceMethodKind::custom("read"),
+        ServiceMethodKind::custom("write"),
     ])
 }
 
@@ -14,19 +14,19 @@ pub fn register_hooks(
 ) -> anyhow::Result<()> {
     app.service_hooks("rules", |h| {
         h.before(
-            dog_core::ServiceMethodKind::Custom("read"),
+            dog_core::ServiceMethodKind::custom("read"),
             Arc::new(super::rules_hooks::BeforeRead),
         );
         h.after(
-            dog_core::ServiceMethodKind::Custom("read"),
+            dog_core::ServiceMethodKind::custom("read"),
             Arc::new(super::rules_hooks::AfterRead),
         );
         h.before(
-            dog_core::ServiceMethodKind::Custom("write"),
+            dog_core::ServiceMethodKind::custom("write"),
             Arc::new(super::rules_hooks::BeforeWrite),
         );
         h.after(
-            dog_core::ServiceMethodKind::Custom("write"),
+            dog_core::ServiceMethodKind::custom("write"),
             Arc::new(super::rules_hooks::AfterWrite),
         );
     });
@@ -1,9 +1,67 @@
 use crate::services::FleetParams;
 use anyhow::Result;
+use dog_typedb::Query;
 use reqwest::Client;
+use serde::Deserialize;
 use serde_json::{json, Value};
 use std::time::Duration;
 
+/// The `tomtom.*` config block, loaded once with `get_section`.
+///
+/// `tomtom.key` and `tomtom.baseUrl` are required; per-endpoint timeouts
+/// (`tomtom.route.timeout`, ...) and traffic thresholds fall back to defaults.
+#[derive(Debug, Deserialize)]
+struct TomTomConfig {
+    key: String,
+    #[serde(rename = "baseUrl")]
+    base_url: String,
+    #[serde(default = "EndpointConfig::short")]
+    geocode: EndpointConfig,
+    #[serde(default = "EndpointConfig::short")]
+    search: EndpointConfig,
+    #[serde(default = "EndpointConfig::long")]
+    route: EndpointConfig,
+    #[serde(default = "EndpointConfig::long")]
+    eta: EndpointConfig,
+    #[serde(default = "EndpointConfig::short")]
+    reverse_geocode: EndpointConfig,
+    #[serde(default)]
+    traffic: TrafficConfig,
+}
+
+#[derive(Debug, Deserialize)]
+struct EndpointConfig {
+    /// Request timeout in seconds.
+    timeout: u64,
+}
+
+impl EndpointConfig {
+    fn short() -> Self {
+        Self { timeout: 10 }
+    }
+
+    fn long() -> Self {
+        Self { timeout: 15 }
+    }
+}
+
+/// Traffic delay thresholds in seconds.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct TrafficConfig {
+    heavy_threshold: i64,
+    moderate_threshold: i64,
+}
+
+impl Default for TrafficConfig {
+    fn default() -> Self {
+        Self {
+            heavy_threshold: 600,
+            moderate_threshold: 300,
+        }
+    }
+}
+
 /// TomTom adapter that makes direct API calls to TomTom services
 pub struct TomTomAdapter {
     client: Client,
@@ -21,64 +79,23 @@ pub struct TomTomAdapter {
 
 impl TomTomAdapter {
     pub fn new(app: &mut dog_core::DogAppBuilder<Value, FleetParams>) -> Result<Self> {
-        let api_key = app
-            .get("tomtom.key")
-            .ok_or_else(|| anyhow::anyhow!("Missing 'tomtom.key' field"))?;
-        let base_url = app
-            .get("tomtom.baseUrl")
-            .ok_or_else(|| anyhow::anyhow!("Missing 'tomtom.baseUrl' field"))?;
-
-        let geocode_timeout = app
-            .get("tomtom.geocode.timeout")
-            .unwrap_or_else(|| "10".to_string())
-            .parse()
-            .unwrap_or(10);
-        let search_timeout = app
-            .get("tomtom.search.timeout")
-            .unwrap_or_else(|| "10".to_string())
-            .parse()
-            .unwrap_or(10);
-        let route_timeout = app
-            .get("tomtom.route.timeout")
-            .unwrap_or_else(|| "15".to_string())
-            .parse()
-            .unwrap_or(15);
-        let eta_timeout = app
-            .get("tomtom.eta.timeout")
-            .unwrap_or_else(|| "15".to_string())
-            .parse()
-            .unwrap_or(15);
-        let reverse_geocode_timeout = app
-            .get("tomtom.reverse_geocode.timeout")
-            .unwrap_or_else(|| "10".to_string())
-            .parse()
-            .unwrap_or(10);
-        let heavy_threshold = app
-            .get("tomtom.traffic.heavy_threshold")
-            .unwrap_or_else(|| "600".to_string())
-            .parse()
-            .unwrap_or(600);
-        let moderate_threshold = app
-            .get("tomtom.traffic.moderate_threshold")
-            .unwrap_or_else(|| "300".to_string())
-            .parse()
-            .unwrap_or(300);
+        let config: TomTomConfig = app.get_section("tomtom")?;
         let api_base_url = app
             .get("api.baseUrl")
             .unwrap_or_else(|| "http://localhost:3036".to_string());
 
         Ok(Self {
             client: Client::new(),
-            api_key,
-            base_url,
+            api_key: config.key,
+            base_url: config.base_url,
             api_base_url,
-            geocode_timeout,
-            search_timeout,
-            route_timeout,
-            eta_timeout,
-            reverse_geocode_timeout,
-            heavy_threshold,
-            moderate_threshold,
+            geocode_timeout: config.geocode.timeout,
+            search_timeout: config.search.timeout,
+            route_timeout: config.route.timeout,
+            eta_timeout: config.eta.timeout,
+            reverse_geocode_timeout: config.reverse_geocode.timeout,
+            heavy_threshold: config.traffic.heavy_threshold,
+            moderate_threshold: config.traffic.moderate_threshold,
         })
     }
 
@@ -590,21 +607,28 @@ impl TomTomAdapter {
 
     /// Get vehicle specifications from database for routing
     async fn get_vehicle_specs(&self, vehicle_id: &str) -> Result<Value> {
-        let query = format!(
-            r#"
-            match $v isa vehicle, has vehicle-id "{}",
-                has engine-type $engine,
-                has max-speed $speed,
-                has vehicle-weight $weight,
-                has axle-weight $axle,
-                has vehicle-length $length,
-                has vehicle-width $width,
-                has vehicle-height $height,
-                has is-commercial $commercial;
-            select $engine, $speed, $weight, $axle, $length, $width, $height, $commercial;
-            "#,
-            vehicle_id
-        );
+        let query = Query::match_entity("vehicle")
+            .var("v")
+            .has("vehicle-id", vehicle_id)
+            .has_var("engine-type", "engine")
+            .has_var("max-speed", "speed")
+            .has_var("vehicle-weight", "weight")
+            .has_var("axle-weight", "axle")
+            .has_var("vehicle-length", "length")
+            .has_var("vehicle-width", "width")
+            .has_var("vehicle-height", "height")
+            .has_var("is-commercial", "commercial")
+            .select([
+                "engine",
+                "speed",
+                "weight",
+                "axle",
+                "length",
+                "width",
+                "height",
+                "commercial",
+            ])
+            .build()?;
 
         let response = self
             .client
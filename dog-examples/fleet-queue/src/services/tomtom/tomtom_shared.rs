@@ -3,13 +3,13 @@ use dog_core::{ServiceCapabilities, ServiceMethodKind};
 
 pub fn capabilities() -> ServiceCapabilities {
     ServiceCapabilities::from_methods(vec![
-        ServiceMethodKind::Custom("geocode"),
-        ServiceMethodKind::Custom("reverse-geocode"),
-        ServiceMethodKind::Custom("search"),
-        ServiceMethodKind::Custom("route"),
-        ServiceMethodKind::Custom("eta"),
-        ServiceMethodKind::Custom("traffic"),
-        ServiceMethodKind::Custom("stats"),
+        ServiceMethodKind::custom("geocode"),
+        ServiceMethodKind::custom("reverse-geocode"),
+        ServiceMethodKind::custom("search"),
+        ServiceMethodKind::custom("route"),
+        ServiceMethodKind::custom("eta"),
+        ServiceMethodKind::custom("traffic"),
+        ServiceMethodKind::custom("stats"),
     ])
 }
 
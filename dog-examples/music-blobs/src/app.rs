@@ -1,6 +1,6 @@
 use crate::services::MusicParams;
 use anyhow::Result;
-use dog_core::DogAppBuilder;
+use dog_core::{ConfigSource, DogAppBuilder};
 use serde_json::Value;
 
 pub async fn build_builder() -> Result<DogAppBuilder<Value, MusicParams>> {
@@ -12,6 +12,8 @@ pub async fn build_builder() -> Result<DogAppBuilder<Value, MusicParams>> {
 
     builder.set("http.host", host);
     builder.set("http.port", port);
+    // e.g. DOG_HTTP__CORS__ORIGINS=https://app.example.com
+    builder.configure_from([ConfigSource::env("DOG")])?;
     crate::hooks::global_hooks(&mut builder)?;
     crate::channels::configure(&mut builder)?;
 
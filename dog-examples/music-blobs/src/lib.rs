@@ -9,9 +9,10 @@ mod services;
 use std::sync::Arc;
 
 use dog_axum::{
-    middlewares::{FileEncoding, MultipartConfig, MultipartToJson},
+    middlewares::{MultipartConfig, MultipartToJson},
     AxumApp,
 };
+use dog_blob::{BlobAdapter, BlobService};
 use serde_json::Value;
 
 pub use services::MusicParams;
@@ -24,34 +25,36 @@ impl MusicMultipartDefaults {
     const ALLOWED_TYPES: &'static str =
         "audio/mpeg,audio/wav,audio/flac,audio/aac,audio/ogg,application/octet-stream";
     const INCLUDE_METADATA: bool = true;
-    const FILE_ENCODING: &'static str = "base64";
 }
 
 pub async fn build() -> anyhow::Result<AxumApp<Value, MusicParams>> {
-    let mut builder = app::build_builder().await?;
+    let builder = app::build_builder().await?;
 
     let state = builder
         .get::<Arc<rustfs::RustFsState>>("rustfs")
         .ok_or(anyhow::anyhow!("RustFsState not found"))?;
 
-    let svcs = services::configure(&mut builder, Arc::clone(&state))?;
-
-    let config = multipart_config();
+    // Tracks are plain blobs: multipart uploads stream straight into RustFS
+    // and `BlobService` handles create, listing, range downloads and removal.
+    let config = multipart_config()
+        .stream_to_blobs(Arc::new(BlobAdapter::new(Arc::clone(&state.blob_state))));
 
     let mut ax = dog_axum::axum(builder.build())
-        .use_service_with("/music", svcs.music, MultipartToJson::with_config(config))
+        .use_middleware(MultipartToJson::with_config(config))
+        .use_blob_service(
+            "/music",
+            Arc::new(BlobService::from_state(Arc::clone(&state.blob_state))),
+        )
         .service("/health", || async { "ok" });
 
+    // Cross-origin access comes from `http.cors.*` (denied when unset)
+    let cors = dog_axum::middlewares::cors_from_config(&ax.app)?;
+
     // Add other middleware layers to router
     ax.router = ax
         .router
         .layer(axum::extract::DefaultBodyLimit::max(100 * 1024 * 1024)) // 100MB to match dog-blob config
-        .layer(
-            tower_http::cors::CorsLayer::new()
-                .allow_origin(tower_http::cors::Any)
-                .allow_methods(tower_http::cors::Any)
-                .allow_headers(tower_http::cors::Any),
-        )
+        .layer(cors)
         .fallback_service(tower_http::services::ServeDir::new(
             std::env::var("STATIC_DIR")
                 .unwrap_or_else(|_| format!("{}/static", env!("CARGO_MANIFEST_DIR"))),
@@ -60,69 +63,23 @@ pub async fn build() -> anyhow::Result<AxumApp<Value, MusicParams>> {
     Ok(ax)
 }
 
-fn env_var_or<T>(key: &str, default: T) -> T
-where
-    T: std::str::FromStr + std::fmt::Display,
-    T::Err: std::fmt::Debug,
-{
-    std::env::var(key)
-        .unwrap_or_else(|_| default.to_string())
-        .parse()
-        .unwrap_or(default)
-}
-
-fn process_audio_file(
-    ctx: &mut dog_axum::middlewares::FieldContext,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    // Audio file processed successfully
-
-    if let Some(filename) = &ctx.filename {
-        let format = if filename.ends_with(".mp3") {
-            "MP3"
-        } else if filename.ends_with(".wav") {
-            "WAV"
-        } else {
-            "Audio"
-        };
-        ctx.metadata
-            .insert("format".to_string(), serde_json::json!(format));
-    }
-
-    Ok(())
-}
-
 fn multipart_config() -> MultipartConfig {
-    let max_file_mb = env_var_or(
-        "MUSIC_MAX_FILE_SIZE_MB",
-        MusicMultipartDefaults::MAX_FILE_SIZE_MB,
-    );
-    let max_total_mb = env_var_or(
-        "MUSIC_MAX_TOTAL_SIZE_MB",
-        MusicMultipartDefaults::MAX_TOTAL_SIZE_MB,
-    );
-    let include_metadata = env_var_or(
-        "MUSIC_INCLUDE_METADATA",
-        MusicMultipartDefaults::INCLUDE_METADATA,
-    );
-    let encoding = match env_var_or(
-        "MUSIC_FILE_ENCODING",
-        MusicMultipartDefaults::FILE_ENCODING.to_string(),
-    )
-    .to_lowercase()
-    .as_str()
-    {
-        "metadata" => FileEncoding::Metadata,
-        "skip" => FileEncoding::Skip,
-        _ => FileEncoding::Base64,
-    };
-
+    // MUSIC_MAX_FILE_SIZE_MB → max_file_size_mb, etc.
+    let env = dog_core::DogConfig::from_env("MUSIC");
+    let max_file_mb = env
+        .get_as("max_file_size_mb")
+        .unwrap_or(MusicMultipartDefaults::MAX_FILE_SIZE_MB);
+    let max_total_mb = env
+        .get_as("max_total_size_mb")
+        .unwrap_or(MusicMultipartDefaults::MAX_TOTAL_SIZE_MB);
+    let include_metadata = env
+        .get_as("include_metadata")
+        .unwrap_or(MusicMultipartDefaults::INCLUDE_METADATA);
     let mut config = MultipartConfig::new()
         .max_file_size(max_file_mb * 1024 * 1024)
         .max_total_size(max_total_mb * 1024 * 1024)
         .file_field("file")
-        .file_encoding(encoding)
-        .include_metadata(include_metadata)
-        .field_processor("file", process_audio_file);
+        .include_metadata(include_metadata);
 
     // Add each allowed content type
     for content_type in MusicMultipartDefaults::ALLOWED_TYPES.split(',') {
@@ -82,19 +82,6 @@ impl AudioMetadataExtractor {
         Some(metadata)
     }
 
-    /// Extract raw album art bytes from MP3 data
-    pub fn extract_raw_album_art(data: &[u8]) -> Option<(String, Vec<u8>)> {
-        let tag = id3::Tag::read_from2(std::io::Cursor::new(data)).ok()?;
-        for frame in tag.frames() {
-            if frame.id() == "APIC" {
-                if let Some(picture) = frame.content().picture() {
-                    return Some((picture.mime_type.clone(), picture.data.clone()));
-                }
-            }
-        }
-        None
-    }
-
     /// Extract technical audio properties using symphonia
     fn extract_audio_properties_with_symphonia(data: &[u8]) -> Option<AudioProperties> {
         println!(
@@ -1,5 +1,3 @@
 pub mod audio;
-pub mod mime;
 
 pub use audio::AudioMetadataExtractor;
-pub use mime::MimeDecoder;
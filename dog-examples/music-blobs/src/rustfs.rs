@@ -10,7 +10,6 @@ use std::sync::Arc;
 /// RustFsState contains a BlobState following RustFS documentation pattern
 pub struct RustFsState {
     pub blob_state: Arc<BlobState>,
-    pub rustfs_store: RustFSStore,
 }
 
 impl RustFsState {
@@ -32,6 +31,7 @@ impl RustFsState {
             },
             require_range_support: false,
             checksum_alg: None,
+            ..BlobConfig::default()
         };
 
         // Configuration applied
@@ -46,13 +46,9 @@ impl RustFsState {
         );
 
         // Create BlobState and then RustFsState containing it
-        let blob_state =
-            Arc::new(BlobState::new(storage.clone(), config).with_uploads(coordinator));
+        let blob_state = Arc::new(BlobState::new(storage, config).with_uploads(coordinator));
 
-        let state = Arc::new(RustFsState {
-            blob_state,
-            rustfs_store: storage,
-        });
+        let state = Arc::new(RustFsState { blob_state });
         app.set("rustfs", state);
 
         Ok(())
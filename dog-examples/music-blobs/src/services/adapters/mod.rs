@@ -1,3 +0,0 @@
-mod rustfs_adapter;
-
-pub use rustfs_adapter::RustFsAdapter;
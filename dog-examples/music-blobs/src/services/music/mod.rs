@@ -1,5 +0,0 @@
-pub mod music_hooks;
-pub mod music_service;
-pub mod music_shared;
-
-pub use music_service::MusicService;
@@ -11,7 +11,6 @@ class MusicPlayer {
     this.musicLibrary = [];
 
     this.currentAudio = null;
-    this.currentObjectUrl = null;
     this.currentTrackId = null;
 
     this.isPlaying = false;
@@ -124,7 +123,8 @@ class MusicPlayer {
       if (!response.ok) throw new Error(`Failed to load library: ${response.status}`);
 
       const data = await response.json();
-      this.musicLibrary = Array.isArray(data) ? data : [];
+      // Only blobs the server knows an id for can be played or removed
+      this.musicLibrary = Array.isArray(data) ? data.filter((t) => t.id) : [];
 
       this.renderTracks();
       this.showStatus(`Loaded ${this.musicLibrary.length} tracks`, "success");
@@ -156,7 +156,7 @@ class MusicPlayer {
 
     trackList.innerHTML = this.musicLibrary
       .map((track, index) => {
-        const trackId = track.key;
+        const trackId = track.id;
         const title = track.metadata?.title || track.filename || "Unknown Title";
         const artist = track.metadata?.artist || "Unknown Artist";
         const duration = this.formatDuration(track.metadata?.duration || 0);
@@ -286,56 +286,7 @@ class MusicPlayer {
       });
     });
 
-    // Initialize static waveforms with real peaks from backend
-    this.musicLibrary.forEach((t) => this.loadWaveformPeaks(t.key));
-
-    // Load track covers
-    this.musicLibrary.forEach(track => {
-      if (track.metadata?.album_art_url) {
-        this.loadTrackCover(track.key);
-      }
-    });
-  }
-
-  async loadTrackCover(trackId) {
-    try {
-      const resp = await fetch("/music", {
-        method: "POST",
-        headers: {
-          "Content-Type": "application/json",
-          "x-service-method": "cover",
-        },
-        body: JSON.stringify({ key: trackId }),
-      });
-      if (!resp.ok) return;
-      const data = await resp.json();
-      if (data.cover_content) {
-        const img = document.createElement("img");
-        img.src = `data:${data.content_type || "image/jpeg"};base64,${data.cover_content}`;
-        img.alt = "Album Art";
-        const container = document.getElementById(`artwork-${this.safeId(trackId)}`);
-        if (container) {
-          container.innerHTML = "";
-          container.appendChild(img);
-        }
-
-        // Update the player artwork if this track is currently playing
-        if (this.currentTrackId === trackId) {
-          const playerArtwork = document.getElementById("playerArtwork");
-          if (playerArtwork) {
-            playerArtwork.src = img.src;
-            playerArtwork.style.display = "block";
-          }
-        }
-      } else {
-        const container = document.getElementById(`artwork-${this.safeId(trackId)}`);
-        if (container) {
-          container.innerHTML = `<div class="placeholder"><iconify-icon icon="ph:music-notes-simple-fill"></iconify-icon></div>`;
-        }
-      }
-    } catch (e) {
-      console.error("Failed to fetch cover", e);
-    }
+    this.musicLibrary.forEach((t) => this.ensureWaveformCanvas(t.id));
   }
 
   createWaveformContainer(trackId) {
@@ -383,100 +334,6 @@ class MusicPlayer {
     return this.waveformCanvases.get(trackId);
   }
 
-  async loadWaveformPeaks(trackId) {
-    try {
-      const response = await fetch("/music", {
-        method: "POST",
-        headers: {
-          "Content-Type": "application/json",
-          "x-service-method": "peaks",
-        },
-        body: JSON.stringify({ key: trackId }),
-      });
-
-      if (!response.ok) {
-        console.warn(`Failed to load peaks for ${trackId}: ${response.status}`);
-        // Fall back to static waveform
-        this.ensureWaveformCanvas(trackId);
-        return;
-      }
-
-      const peaksData = await response.json();
-      if (peaksData.status === "success" && peaksData.peaks) {
-        // Store peaks data for this track
-        const wf = this.ensureWaveformCanvas(trackId);
-        if (wf) {
-          wf.peaks = peaksData.peaks;
-          wf.duration = peaksData.duration || 0;
-          this.drawRealWaveform(trackId, peaksData.peaks);
-        }
-      } else {
-        // Fall back to static waveform
-        this.ensureWaveformCanvas(trackId);
-      }
-    } catch (error) {
-      console.warn(`Error loading peaks for ${trackId}:`, error);
-      // Fall back to static waveform
-      this.ensureWaveformCanvas(trackId);
-    }
-  }
-
-  drawRealWaveform(trackId, peaks) {
-    const w = this.waveformCanvases.get(trackId);
-    if (!w || !peaks || peaks.length === 0) return;
-
-    const { ctx, cssW: width, cssH: height } = w;
-
-    // Clear canvas with proper bounds
-    ctx.clearRect(0, 0, width, height);
-
-    // Optimize bar width for 2000 peaks
-    const barW = Math.max(1.5, width / peaks.length);
-    const gap = Math.max(0.5, barW * 0.1);
-    const maxH = height * 0.85;
-
-    for (let i = 0; i < peaks.length; i++) {
-      const x = i * (barW + gap);
-      if (x >= width) break; // Don't draw beyond canvas
-
-      const amp = Math.max(0.01, Math.min(1.0, peaks[i]));
-      const h = Math.max(2, amp * maxH);
-      const y = (height - h) / 2;
-
-      // Enhanced gradient with better contrast
-      const alpha = 0.3 + amp * 0.6;
-      const grad = ctx.createLinearGradient(x, y, x, y + h);
-
-      if (amp > 0.7) {
-        // High amplitude - warmer colors
-        grad.addColorStop(0, `rgba(200,210,220,${alpha})`);
-        grad.addColorStop(0.5, `rgba(170,180,190,${alpha * 0.9})`);
-        grad.addColorStop(1, `rgba(140,150,160,${alpha * 0.8})`);
-      } else if (amp > 0.3) {
-        // Medium amplitude - neutral colors
-        grad.addColorStop(0, `rgba(180,190,200,${alpha})`);
-        grad.addColorStop(0.5, `rgba(150,160,170,${alpha * 0.9})`);
-        grad.addColorStop(1, `rgba(120,130,140,${alpha * 0.8})`);
-      } else {
-        // Low amplitude - cooler colors
-        grad.addColorStop(0, `rgba(160,170,180,${alpha})`);
-        grad.addColorStop(0.5, `rgba(130,140,150,${alpha * 0.9})`);
-        grad.addColorStop(1, `rgba(100,110,120,${alpha * 0.8})`);
-      }
-
-      ctx.fillStyle = grad;
-      ctx.fillRect(x, y, barW, h);
-
-      // Enhanced highlights for peaks
-      if (amp > 0.8) {
-        const highlightH = Math.max(1, h * 0.15);
-        ctx.fillStyle = `rgba(220,230,240,${amp * 0.3})`;
-        ctx.fillRect(x, y, barW, highlightH);
-        ctx.fillRect(x, y + h - highlightH, barW, highlightH);
-      }
-    }
-  }
-
   drawStaticWaveform(trackId) {
     const w = this.waveformCanvases.get(trackId);
     if (!w) return;
@@ -550,36 +407,13 @@ class MusicPlayer {
         await this.stopTrack(this.currentTrackId);
       }
 
-      // Stream from backend
-      const streamResponse = await fetch("/music", {
-        method: "POST",
-        headers: {
-          "Content-Type": "application/json",
-          "x-service-method": "stream",
-        },
-        body: JSON.stringify({ key: trackId }),
-      });
-
-      if (!streamResponse.ok) throw new Error(`Failed to start stream: ${streamResponse.status}`);
-
-      const streamInfo = await streamResponse.json();
-      if (!streamInfo.audio_data) throw new Error("No audio data available in stream response");
-
-      const track = this.musicLibrary.find((t) => t.key === trackId);
+      const track = this.musicLibrary.find((t) => t.id === trackId);
 
-      // Make audio element
-      this.currentAudio = new Audio();
+      // The browser streams the content with range requests, so seeking
+      // doesn't download the whole file first
+      this.currentAudio = new Audio(this.contentUrl(trackId));
       this.currentTrackId = trackId;
 
-      // Convert base64 -> blob URL
-      const bytes = this.base64ToBytes(streamInfo.audio_data);
-      const blob = new Blob([bytes], { type: streamInfo.content_type || "audio/mpeg" });
-
-      // Cleanup old url
-      if (this.currentObjectUrl) URL.revokeObjectURL(this.currentObjectUrl);
-      this.currentObjectUrl = URL.createObjectURL(blob);
-      this.currentAudio.src = this.currentObjectUrl;
-
       this.setupAudioEventListeners(trackId);
 
       // Ensure waveform canvas exists
@@ -661,13 +495,6 @@ class MusicPlayer {
 
   async pauseTrack(trackId) {
     try {
-      // backend pause (optional)
-      await fetch("/music", {
-        method: "POST",
-        headers: { "Content-Type": "application/json", "x-service-method": "pause" },
-        body: JSON.stringify({ key: trackId }),
-      });
-
       if (this.currentAudio) {
         this.currentAudio.pause();
         this.isPaused = true;
@@ -683,13 +510,6 @@ class MusicPlayer {
 
   async resumeTrack(trackId) {
     try {
-      // backend resume (optional)
-      await fetch("/music", {
-        method: "POST",
-        headers: { "Content-Type": "application/json", "x-service-method": "resume" },
-        body: JSON.stringify({ key: trackId }),
-      });
-
       if (this.currentAudio) {
         await this.currentAudio.play();
         this.isPaused = false;
@@ -706,14 +526,6 @@ class MusicPlayer {
 
   async stopTrack(trackId) {
     try {
-      // backend cancel (optional)
-      await fetch("/music", {
-        method: "POST",
-        headers: { "Content-Type": "application/json", "x-service-method": "cancel" },
-        body: JSON.stringify({ key: trackId }),
-      });
-
-      // frontend stop
       this.stopWaveformAnimation();
       this.cleanupAudio();
 
@@ -751,15 +563,6 @@ class MusicPlayer {
 
       this.currentAudio = null;
     }
-
-    if (this.currentObjectUrl) {
-      try {
-        URL.revokeObjectURL(this.currentObjectUrl);
-      } catch (e) {
-        console.warn("Error revoking object URL:", e);
-      }
-      this.currentObjectUrl = null;
-    }
   }
 
   // -----------------------------
@@ -838,65 +641,36 @@ class MusicPlayer {
 
       this.analyser.getByteFrequencyData(this.dataArray);
 
-      // If we have real peaks data, use it as the base and overlay live frequency data
-      if (wf.peaks && wf.peaks.length > 0) {
-        this.drawRealWaveform(trackId, wf.peaks);
-
-        // Overlay live frequency visualization
-        const barW = width / wf.peaks.length;
-        const maxH = height * 0.9;
-
-        for (let i = 0; i < Math.min(wf.peaks.length, this.dataArray.length); i++) {
-          const x = i * barW;
-          const staticAmp = wf.peaks[i];
-          const liveFreq = this.dataArray[Math.floor((i / wf.peaks.length) * this.dataArray.length)] / 255;
-
-          // Combine static waveform with live frequency data
-          const combinedAmp = Math.max(staticAmp, liveFreq * 0.3);
-          const h = combinedAmp * maxH;
-          const y = (height - h) / 2;
-
-          // Add live frequency overlay with accent color
-          if (liveFreq > 0.1) {
-            const liveH = liveFreq * maxH * 0.4;
-            const liveY = (height - liveH) / 2;
-            ctx.fillStyle = `rgba(0,212,170,${liveFreq * 0.3})`;
-            ctx.fillRect(x, liveY, Math.max(0.5, barW - 0.1), liveH);
-          }
-        }
-      } else {
-        // Fall back to original frequency-only visualization
-        ctx.clearRect(0, 0, width, height);
-        ctx.fillStyle = "rgba(255,255,255,0.02)";
-        ctx.fillRect(0, 0, width, height);
-
-        const barW = 0.9;
-        const gap = 0.25;
-        const step = barW + gap;
-        const count = Math.min(Math.floor(width / step), this.dataArray.length);
-
-        for (let i = 0; i < count; i++) {
-          const x = i * step;
-          const idx = Math.floor((i / count) * this.dataArray.length);
-          const f = this.dataArray[idx] / 255;
-
-          const maxH = height * 0.82;
-          const h = Math.max(0.8, f * maxH);
-          const y = (height - h) / 2;
-
-          const a = 0.25 + f * 0.7;
-          const grad = ctx.createLinearGradient(x, y, x, y + h);
-          grad.addColorStop(0, `rgba(190,200,210,${a})`);
-          grad.addColorStop(0.5, `rgba(165,175,185,${a * 0.92})`);
-          grad.addColorStop(1, `rgba(140,150,160,${a * 0.82})`);
-
-          ctx.fillStyle = grad;
-          ctx.fillRect(x, y, barW, h);
-
-          if (f > 0.65) {
-            ctx.fillStyle = `rgba(220,230,240,${f * 0.35})`;
-            ctx.fillRect(x, y, barW, Math.max(1, h * 0.28));
-          }
+      ctx.clearRect(0, 0, width, height);
+      ctx.fillStyle = "rgba(255,255,255,0.02)";
+      ctx.fillRect(0, 0, width, height);
+
+      const barW = 0.9;
+      const gap = 0.25;
+      const step = barW + gap;
+      const count = Math.min(Math.floor(width / step), this.dataArray.length);
+
+      for (let i = 0; i < count; i++) {
+        const x = i * step;
+        const idx = Math.floor((i / count) * this.dataArray.length);
+        const f = this.dataArray[idx] / 255;
+
+        const maxH = height * 0.82;
+        const h = Math.max(0.8, f * maxH);
+        const y = (height - h) / 2;
+
+        const a = 0.25 + f * 0.7;
+        const grad = ctx.createLinearGradient(x, y, x, y + h);
+        grad.addColorStop(0, `rgba(190,200,210,${a})`);
+        grad.addColorStop(0.5, `rgba(165,175,185,${a * 0.92})`);
+        grad.addColorStop(1, `rgba(140,150,160,${a * 0.82})`);
+
+        ctx.fillStyle = grad;
+        ctx.fillRect(x, y, barW, h);
+
+        if (f > 0.65) {
+          ctx.fillStyle = `rgba(220,230,240,${f * 0.35})`;
+          ctx.fillRect(x, y, barW, Math.max(1, h * 0.28));
         }
       }
 
@@ -967,7 +741,7 @@ class MusicPlayer {
 
     if (artwork) {
       if (track.metadata?.album_art_url) {
-        const listImg = document.querySelector(`#artwork-${this.safeId(track.key)} img`);
+        const listImg = document.querySelector(`#artwork-${this.safeId(track.id)} img`);
         if (listImg) {
           artwork.src = listImg.src;
           artwork.style.display = "block";
@@ -1026,14 +800,14 @@ class MusicPlayer {
 
   previousTrack() {
     if (!this.currentTrackId) return;
-    const i = this.musicLibrary.findIndex((t) => t.key === this.currentTrackId);
-    if (i > 0) this.playTrack(this.musicLibrary[i - 1].key);
+    const i = this.musicLibrary.findIndex((t) => t.id === this.currentTrackId);
+    if (i > 0) this.playTrack(this.musicLibrary[i - 1].id);
   }
 
   nextTrack() {
     if (!this.currentTrackId) return;
-    const i = this.musicLibrary.findIndex((t) => t.key === this.currentTrackId);
-    if (i >= 0 && i < this.musicLibrary.length - 1) this.playTrack(this.musicLibrary[i + 1].key);
+    const i = this.musicLibrary.findIndex((t) => t.id === this.currentTrackId);
+    if (i >= 0 && i < this.musicLibrary.length - 1) this.playTrack(this.musicLibrary[i + 1].id);
   }
 
   // -----------------------------
@@ -1043,21 +817,10 @@ class MusicPlayer {
     this.showStatus("📥 Starting download...", "info");
 
     try {
-      const response = await fetch("/music", {
-        method: "POST",
-        headers: { "Content-Type": "application/json", "x-service-method": "stream" },
-        body: JSON.stringify({ key: trackId }),
-      });
-
+      const response = await fetch(this.contentUrl(trackId));
       if (!response.ok) throw new Error(`Download failed: ${response.status}`);
 
-      const streamInfo = await response.json();
-      if (!streamInfo.audio_data) throw new Error("No audio data available for download");
-
-      const bytes = this.base64ToBytes(streamInfo.audio_data);
-      const blob = new Blob([bytes], { type: streamInfo.content_type || "audio/mpeg" });
-
-      const url = URL.createObjectURL(blob);
+      const url = URL.createObjectURL(await response.blob());
       const a = document.createElement("a");
       a.href = url;
       a.download = `${trackTitle}.mp3`;
@@ -1086,7 +849,7 @@ class MusicPlayer {
         await this.stopTrack(trackId);
       }
 
-      this.musicLibrary = this.musicLibrary.filter((t) => t.key !== trackId);
+      this.musicLibrary = this.musicLibrary.filter((t) => t.id !== trackId);
       this.renderTracks();
       this.showStatus(`🗑️ Deleted: ${trackTitle}`, "success");
     } catch (e) {
@@ -1120,11 +883,7 @@ class MusicPlayer {
       const formData = new FormData();
       formData.append("file", file);
 
-      const response = await fetch("/music", {
-        method: "POST",
-        headers: { "x-service-method": "upload" },
-        body: formData,
-      });
+      const response = await fetch("/music", { method: "POST", body: formData });
 
       if (!response.ok) throw new Error(`Upload failed: ${response.status}`);
 
@@ -1185,7 +944,7 @@ class MusicPlayer {
 
   downloadCurrentTrack() {
     if (!this.currentTrackId) return;
-    const track = this.musicLibrary.find((t) => t.key === this.currentTrackId); // FIX: key not id
+    const track = this.musicLibrary.find((t) => t.id === this.currentTrackId);
     const title = track?.metadata?.title || track?.filename || "Unknown Title";
     this.downloadTrack(this.currentTrackId, title);
   }
@@ -1193,11 +952,8 @@ class MusicPlayer {
   // -----------------------------
   // Utils
   // -----------------------------
-  base64ToBytes(b64) {
-    const bin = atob(b64);
-    const bytes = new Uint8Array(bin.length);
-    for (let i = 0; i < bin.length; i++) bytes[i] = bin.charCodeAt(i);
-    return bytes;
+  contentUrl(trackId) {
+    return `/music/${encodeURIComponent(trackId)}/content`;
   }
 
   formatDuration(seconds) {
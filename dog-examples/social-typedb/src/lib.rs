@@ -26,15 +26,11 @@ pub async fn build() -> anyhow::Result<AxumApp<Value, SocialParams>> {
         .use_service("/comments", svcs.comments)
         .service("/health", || async { "ok" });
 
-    // Add CORS middleware to allow browser requests
+    // Cross-origin access comes from `http.cors.*` (denied when unset)
+    let cors = dog_axum::middlewares::cors_from_config(&ax.app)?;
     ax.router = ax
         .router
-        .layer(
-            tower_http::cors::CorsLayer::new()
-                .allow_origin(tower_http::cors::Any)
-                .allow_methods(tower_http::cors::Any)
-                .allow_headers(tower_http::cors::Any),
-        )
+        .layer(cors)
         .fallback_service(tower_http::services::ServeDir::new(
             "dog-examples/social-typedb/static",
         ));
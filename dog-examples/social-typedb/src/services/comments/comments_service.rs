@@ -26,8 +26,8 @@ impl CommentsService {
 impl DogService<Value, SocialParams> for CommentsService {
     fn capabilities(&self) -> ServiceCapabilities {
         ServiceCapabilities::from_methods(vec![
-            ServiceMethodKind::Custom("read"),
-            ServiceMethodKind::Custom("write"),
+            ServiceMethodKind::custom("read"),
+            ServiceMethodKind::custom("write"),
         ])
     }
 
@@ -26,8 +26,8 @@ impl PostsService {
 impl DogService<Value, SocialParams> for PostsService {
     fn capabilities(&self) -> ServiceCapabilities {
         ServiceCapabilities::from_methods(vec![
-            ServiceMethodKind::Custom("read"),
-            ServiceMethodKind::Custom("write"),
+            ServiceMethodKind::custom("read"),
+            ServiceMethodKind::custom("write"),
         ])
     }
 
@@ -36,10 +36,7 @@ impl TypeDBState {
         let password = app
             .get::<String>("typedb.password")
             .unwrap_or_else(|| "password".to_string());
-        let tls = app
-            .get::<String>("typedb.tls")
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(false);
+        let tls = app.get_as("typedb.tls").unwrap_or(false);
 
         let credentials = Credentials::new(&username, &password);
         let tls_config = if tls {
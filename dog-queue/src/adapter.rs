@@ -1,21 +1,29 @@
+use futures::StreamExt;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{oneshot, RwLock};
+use tokio::sync::{oneshot, watch, RwLock};
 use tokio::task::JoinHandle;
-use tracing::{debug, error, info, instrument, warn};
+use tokio::time::Instant;
+use tracing::{debug, error, info, instrument, warn, Instrument};
 
 use crate::{
     backend::QueueBackend,
     codec::{CodecRegistry, EnqueueOptions},
-    job::JobRegistry,
+    job::{JobHandler, JobRegistry, RetryBackoff},
     observability::ObservabilityLayer,
-    Job, JobId, QueueCtx, QueueError, QueueResult,
+    types::{EventFilter, JobGroup, JobGroupId, JobTypeFilter, LeaseToken},
+    CancellationToken, DeliveryMode, Job, JobError, JobId, JobMessage, JobStatus, ProgressReporter,
+    QueueCtx, QueueError, QueueResult,
 };
 
 /// Configuration for queue adapter
 #[derive(Debug, Clone)]
 pub struct QueueConfig {
-    /// Maximum number of concurrent workers per queue
+    /// Maximum number of concurrent workers started by `start_workers`.
+    ///
+    /// Without [`Self::per_queue_concurrency`] every worker polls every queue.
+    /// With it, this is the global cap across all dedicated and shared workers.
     pub max_workers: usize,
     /// How long a worker may remain idle (no jobs available) before it
     /// self-terminates. Keeps the pool size proportional to actual load.
@@ -28,6 +36,13 @@ pub struct QueueConfig {
     pub max_retry_backoff: Duration,
     /// Base retry backoff duration
     pub base_retry_backoff: Duration,
+    /// Whether the default exponential retry policy applies full jitter.
+    ///
+    /// When `true` (the default) each retry delay is sampled uniformly from
+    /// `[0, min(base_retry_backoff × 2^(attempt-1), max_retry_backoff)]`. Set to
+    /// `false` for deterministic delays. Job types that override
+    /// [`Job::retry_backoff`](crate::Job::retry_backoff) are unaffected.
+    pub retry_jitter: bool,
     /// How long a worker sleeps between dequeue polls when the queue is empty.
     /// Lower values reduce job latency at the cost of more backend round-trips.
     pub poll_interval: Duration,
@@ -47,6 +62,22 @@ pub struct QueueConfig {
     /// How long a worker backs off after an infrastructure error (e.g. backend
     /// unavailable) before retrying the dequeue loop.
     pub error_backoff: Duration,
+    /// How often a worker re-reads a running job's status to notice that it
+    /// was canceled, for backends without an event stream.
+    ///
+    /// Backends that support [`QueueBackend::event_stream_filtered`] deliver
+    /// the `canceled` event directly and never poll. Either way the job's
+    /// [`CancellationToken`](crate::CancellationToken) fires once the cancel
+    /// is observed. Defaults to `1s`.
+    pub cancel_poll_interval: Duration,
+    /// Minimum time between stored progress reports from one job.
+    ///
+    /// [`ProgressReporter::report`](crate::ProgressReporter::report) holds
+    /// back reports that arrive sooner (except 100%) and sends only the
+    /// latest once the interval expires or the job finishes, so a job
+    /// reporting in a tight loop doesn't flood the backend and event stream.
+    /// Zero disables the debounce. Defaults to `1s`.
+    pub progress_interval: Duration,
     /// Hard timeout for `execute_now`. `None` means no timeout is applied.
     ///
     /// This is intentionally separate from `lease_duration` — `execute_now`
@@ -59,12 +90,42 @@ pub struct QueueConfig {
     ///
     /// `None` (the default) applies no limit.
     ///
-    /// When set, [`QueueAdapter::enqueue_opts`] returns
-    /// [`QueueError::PayloadTooLarge`] if the encoded payload exceeds this
-    /// threshold, before the job reaches the backend. Set this to protect
-    /// downstream systems (database column width, message-broker limits, etc.)
-    /// from oversized payloads at the enqueue boundary.
+    /// When set, every enqueue path (`enqueue`, `enqueue_opts`, `enqueue_at`,
+    /// `enqueue_in`, `fan_out`) returns [`QueueError::PayloadTooLarge`] if the
+    /// payload exceeds this threshold, before the job reaches the backend. The
+    /// limit is inclusive and measured on the bytes *after* the codec runs, so
+    /// a compressing codec is judged by what is actually stored.
+    ///
+    /// Payloads are meant to carry references (`BlobId`, `TrackId`, row IDs),
+    /// not the data itself; a few KiB is a generous cap that catches a blob
+    /// accidentally embedded in a job. It also protects downstream systems
+    /// (database column width, message-broker limits) at the enqueue boundary.
     pub max_payload_size: Option<usize>,
+
+    /// How long an idempotency key deduplicates enqueues.
+    ///
+    /// `None` (the default) keeps a key active for as long as its job is not
+    /// terminal. With `Some(ttl)`, a key stops deduplicating `ttl` after the
+    /// enqueue that claimed it — measured on the backend's
+    /// [`Clock`](crate::Clock) — so the same logical job can be enqueued again
+    /// later even if the original never finished. Backends drop expired keys
+    /// in their periodic cleanup (the memory backend sweeps them alongside
+    /// lease reaping).
+    pub idempotency_ttl: Option<Duration>,
+
+    /// Dedicated worker count per queue name.
+    ///
+    /// Empty (the default) means every worker polls every queue passed to
+    /// `start_workers`. When a queue is listed here, `start_workers` spawns
+    /// exactly that many workers polling *only* that queue, so a saturated
+    /// high-concurrency queue (e.g. `gps_tracking`) cannot starve a
+    /// low-concurrency one (e.g. `compliance_monitoring`), and jobs beyond a
+    /// queue's concurrency wait rather than spilling into another queue's
+    /// workers. Queues not listed share the remaining `max_workers` budget.
+    ///
+    /// The sum of all entries must be `<= max_workers`
+    /// (enforced by [`QueueConfig::validate`]).
+    pub per_queue_concurrency: HashMap<String, usize>,
 }
 
 impl Default for QueueConfig {
@@ -76,11 +137,16 @@ impl Default for QueueConfig {
             heartbeat_interval: Duration::from_secs(30),
             max_retry_backoff: Duration::from_secs(3600), // 1 hour
             base_retry_backoff: Duration::from_secs(1),
+            retry_jitter: true,
             poll_interval: Duration::from_millis(100),
             poll_jitter: Duration::from_millis(10), // 10% of poll_interval
             error_backoff: Duration::from_secs(1),
+            cancel_poll_interval: Duration::from_secs(1),
+            progress_interval: Duration::from_secs(1),
             execute_timeout: None,  // no timeout by default
             max_payload_size: None, // no limit by default
+            idempotency_ttl: None,  // keys live as long as their job
+            per_queue_concurrency: HashMap::new(),
         }
     }
 }
@@ -95,7 +161,11 @@ impl QueueConfig {
     ///   expired; the reaper reclaims the job mid-execution, causing silent double-execution)
     /// - `poll_interval` is zero (busy-wait spin loop against the backend)
     /// - `error_backoff` is zero (immediate tight retry loop after backend errors)
+    /// - `cancel_poll_interval` is zero (status polled in a tight loop while a job runs)
+    /// - `idempotency_ttl` is zero (every key would expire as it is written)
+    /// - `max_payload_size` is zero (every enqueue would be rejected)
     /// - `poll_jitter` > `poll_interval` (jitter larger than the base interval is incoherent)
+    /// - any `per_queue_concurrency` entry is 0, or the entries sum to more than `max_workers`
     pub fn validate(&self) -> QueueResult<()> {
         if self.max_workers == 0 {
             return Err(QueueError::InvalidConfig(
@@ -130,6 +200,27 @@ impl QueueConfig {
                     .to_string(),
             ));
         }
+        if self.max_payload_size == Some(0) {
+            return Err(QueueError::InvalidConfig(
+                "max_payload_size must be > 0 — a zero limit rejects every enqueue \
+                 (use None for no limit)"
+                    .to_string(),
+            ));
+        }
+        if self.idempotency_ttl.is_some_and(|ttl| ttl.is_zero()) {
+            return Err(QueueError::InvalidConfig(
+                "idempotency_ttl must be > 0 — a zero TTL expires every key as it is \
+                 written, silently disabling deduplication (use None for no expiry)"
+                    .to_string(),
+            ));
+        }
+        if self.cancel_poll_interval.is_zero() {
+            return Err(QueueError::InvalidConfig(
+                "cancel_poll_interval must be > 0 — a zero interval re-reads the status \
+                 of every running job in a tight loop"
+                    .to_string(),
+            ));
+        }
         if self.poll_jitter > self.poll_interval {
             return Err(QueueError::InvalidConfig(format!(
                 "poll_jitter ({:?}) must be <= poll_interval ({:?}) — \
@@ -137,8 +228,96 @@ impl QueueConfig {
                 self.poll_jitter, self.poll_interval,
             )));
         }
+        if let Some((queue, _)) = self.per_queue_concurrency.iter().find(|(_, n)| **n == 0) {
+            return Err(QueueError::InvalidConfig(format!(
+                "per_queue_concurrency for queue '{queue}' must be >= 1 \
+                 (0 workers would never process its jobs)"
+            )));
+        }
+        let dedicated: usize = self.per_queue_concurrency.values().sum();
+        if dedicated > self.max_workers {
+            return Err(QueueError::InvalidConfig(format!(
+                "per_queue_concurrency entries sum to {dedicated}, exceeding max_workers ({}) \
+                 — max_workers is the global cap across all queues",
+                self.max_workers,
+            )));
+        }
         Ok(())
     }
+
+    /// Assign queues to workers for `start_workers`.
+    ///
+    /// Returns one queue list per worker: `per_queue_concurrency[q]` dedicated
+    /// workers for each listed queue, plus a shared pool polling every unlisted
+    /// queue sized to whatever remains of `max_workers`.
+    fn worker_assignments(&self, queues: &[String]) -> QueueResult<Vec<Vec<String>>> {
+        if self.per_queue_concurrency.is_empty() {
+            return Ok(vec![queues.to_vec(); self.max_workers]);
+        }
+
+        let mut assignments = Vec::with_capacity(self.max_workers);
+        let mut shared: Vec<String> = Vec::new();
+        for queue in queues {
+            match self.per_queue_concurrency.get(queue) {
+                Some(&n) => assignments.extend(std::iter::repeat_n(vec![queue.clone()], n)),
+                None => shared.push(queue.clone()),
+            }
+        }
+
+        if !shared.is_empty() {
+            // validate() guarantees the dedicated total never exceeds max_workers.
+            let remaining = self.max_workers - assignments.len();
+            if remaining == 0 {
+                return Err(QueueError::InvalidConfig(format!(
+                    "per_queue_concurrency uses all {} workers, leaving none for queues {:?} \
+                     — list them in per_queue_concurrency or raise max_workers",
+                    self.max_workers, shared,
+                )));
+            }
+            assignments.extend(std::iter::repeat_n(shared, remaining));
+        }
+
+        Ok(assignments)
+    }
+}
+
+/// Options controlling how [`WorkerHandle::shutdown_with`] drains in-flight jobs.
+#[derive(Debug, Clone)]
+pub struct DrainOptions {
+    /// How long in-flight jobs may keep running after shutdown is requested.
+    ///
+    /// Workers stop leasing new jobs immediately. A job that finishes inside
+    /// this window is acknowledged normally; a job that is still running when
+    /// it elapses has its lease released so another worker can pick it up
+    /// straight away instead of waiting for the reaper to expire the lease.
+    pub drain_timeout: Duration,
+}
+
+impl Default for DrainOptions {
+    fn default() -> Self {
+        Self {
+            drain_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl DrainOptions {
+    /// Drain with the given timeout.
+    pub fn with_timeout(drain_timeout: Duration) -> Self {
+        Self { drain_timeout }
+    }
+}
+
+/// Outcome of a graceful shutdown, returned by [`WorkerHandle::shutdown_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DrainSummary {
+    /// In-flight jobs that finished (and were acknowledged) within the drain window.
+    pub completed: usize,
+    /// In-flight jobs whose leases were released because the drain window elapsed.
+    pub released: usize,
+    /// In-flight [`DeliveryMode::AtMostOnce`](crate::DeliveryMode::AtMostOnce)
+    /// jobs failed rather than released because the drain window elapsed.
+    pub abandoned: usize,
 }
 
 /// Handle for managing the lifecycle of a worker pool.
@@ -146,8 +325,11 @@ impl QueueConfig {
 /// Dropping this handle without calling `shutdown()` leaves the workers
 /// running until the runtime shuts down.
 pub struct WorkerHandle {
-    shutdown_txs: Vec<oneshot::Sender<()>>,
-    join_handles: Vec<JoinHandle<QueueResult<()>>>,
+    /// Shutdown signal shared by every worker. `None` while running;
+    /// `Some(deadline)` once shutdown is requested, where `deadline` is the end
+    /// of the drain window for in-flight jobs.
+    shutdown_tx: watch::Sender<Option<Instant>>,
+    join_handles: Vec<JoinHandle<QueueResult<DrainSummary>>>,
     /// Shutdown signal for the integrated reaper task (if one was spawned).
     reaper_shutdown_tx: Option<oneshot::Sender<()>>,
     /// Join handle for the integrated reaper task.
@@ -157,21 +339,42 @@ pub struct WorkerHandle {
 impl WorkerHandle {
     /// Gracefully signal all workers and the integrated reaper to stop, then wait
     /// for them all to finish.
+    ///
+    /// Equivalent to [`Self::shutdown_with`] with [`DrainOptions::default`],
+    /// discarding the summary.
     pub async fn shutdown(self) -> QueueResult<()> {
+        self.shutdown_with(DrainOptions::default())
+            .await
+            .map(|_| ())
+    }
+
+    /// Gracefully stop the pool, draining in-flight jobs.
+    ///
+    /// Workers stop leasing new jobs as soon as this is called. Jobs already
+    /// executing are given until `opts.drain_timeout` to finish; any still
+    /// running after that have their leases released (without consuming a
+    /// retry attempt) so another worker can pick them up. The returned
+    /// [`DrainSummary`] reports how many in-flight jobs took each path.
+    pub async fn shutdown_with(self, opts: DrainOptions) -> QueueResult<DrainSummary> {
         // Signal every worker and the reaper first so they can all drain concurrently.
-        for tx in self.shutdown_txs {
-            let _ = tx.send(());
-        }
+        // All workers share one deadline so the whole pool drains within the window.
+        let deadline = Instant::now() + opts.drain_timeout;
+        let _ = self.shutdown_tx.send(Some(deadline));
         if let Some(tx) = self.reaper_shutdown_tx {
             let _ = tx.send(());
         }
         // Await each handle, collecting all errors rather than stopping at the first.
         // Log each error individually so operators have structured granularity
         // (worker panic vs. reaper error) before the errors are merged.
+        let mut summary = DrainSummary::default();
         let mut errors: Vec<String> = Vec::new();
         for handle in self.join_handles {
             match handle.await {
-                Ok(Ok(())) => {}
+                Ok(Ok(drained)) => {
+                    summary.completed += drained.completed;
+                    summary.released += drained.released;
+                    summary.abandoned += drained.abandoned;
+                }
                 Ok(Err(e)) => {
                     error!("Worker shutdown error: {e}");
                     errors.push(e.to_string());
@@ -196,7 +399,11 @@ impl WorkerHandle {
             }
         }
         if errors.is_empty() {
-            Ok(())
+            info!(
+                "Worker pool drained: {} in-flight job(s) completed, {} released, {} abandoned",
+                summary.completed, summary.released, summary.abandoned
+            );
+            Ok(summary)
         } else {
             Err(QueueError::Internal(format!(
                 "{} shutdown error(s): {}",
@@ -207,6 +414,25 @@ impl WorkerHandle {
     }
 }
 
+/// Wait until shutdown has been requested and return the drain deadline.
+///
+/// If the [`WorkerHandle`] was dropped without calling `shutdown()`, the
+/// sender is gone and shutdown can never be requested — pend forever so the
+/// worker keeps running, as documented on `WorkerHandle`.
+async fn shutdown_requested(shutdown_rx: &mut watch::Receiver<Option<Instant>>) -> Instant {
+    // Copy the deadline out before any further await: the watch `Ref` guard
+    // is not `Send` and must not be held across a suspension point.
+    let deadline = shutdown_rx
+        .wait_for(|state| state.is_some())
+        .await
+        .ok()
+        .and_then(|state| *state);
+    match deadline {
+        Some(deadline) => deadline,
+        None => std::future::pending().await,
+    }
+}
+
 /// Production-grade queue adapter with multi-tenant semantics
 pub struct QueueAdapter<B: QueueBackend + ?Sized> {
     backend: Arc<B>,
@@ -291,6 +517,41 @@ impl<B: QueueBackend + Send + Sync + 'static> QueueAdapter<B> {
         self.enqueue_opts(ctx, job, EnqueueOptions::default()).await
     }
 
+    /// Enqueue a job that becomes eligible for processing no earlier than `when`.
+    ///
+    /// Workers will not lease the job before `when`; a `when` in the past runs
+    /// immediately. Shortcut for `enqueue_opts` with [`EnqueueOptions::scheduled`].
+    #[instrument(skip(self, job), fields(job_type = J::JOB_TYPE, tenant_id = %ctx.tenant_id))]
+    pub async fn enqueue_at<J: Job>(
+        &self,
+        ctx: QueueCtx,
+        job: J,
+        when: chrono::DateTime<chrono::Utc>,
+    ) -> QueueResult<JobId> {
+        self.enqueue_opts(ctx, job, EnqueueOptions::scheduled(when))
+            .await
+    }
+
+    /// Enqueue a job that becomes eligible for processing after `delay`.
+    ///
+    /// Returns [`QueueError::InvalidConfig`] if `delay` is too large to add to
+    /// the current time.
+    #[instrument(skip(self, job), fields(job_type = J::JOB_TYPE, tenant_id = %ctx.tenant_id))]
+    pub async fn enqueue_in<J: Job>(
+        &self,
+        ctx: QueueCtx,
+        job: J,
+        delay: Duration,
+    ) -> QueueResult<JobId> {
+        let when = chrono::Duration::from_std(delay)
+            .ok()
+            .and_then(|d| self.backend.clock().now().checked_add_signed(d))
+            .ok_or_else(|| {
+                QueueError::InvalidConfig(format!("enqueue_in delay {delay:?} is out of range"))
+            })?;
+        self.enqueue_at(ctx, job, when).await
+    }
+
     /// Enqueue a job with caller-supplied options (queue name, delayed run_at).
     #[instrument(skip(self, job), fields(job_type = J::JOB_TYPE, tenant_id = %ctx.tenant_id))]
     pub async fn enqueue_opts<J: Job>(
@@ -299,8 +560,52 @@ impl<B: QueueBackend + Send + Sync + 'static> QueueAdapter<B> {
         job: J,
         opts: EnqueueOptions,
     ) -> QueueResult<JobId> {
+        let message = self.encode_message(&job, opts)?;
+
+        // Capture the real queue name before the message is moved into the backend.
+        let queue_name = message.queue.clone();
+
+        // Enqueue to backend
+        let job_id = self.backend.enqueue(ctx.clone(), message).await?;
+
+        // Record metrics — pass the real queue name, not a hardcoded default.
+        self.observability
+            .record_job_enqueued(&ctx, &job_id, J::JOB_TYPE, &queue_name);
+
+        info!("Enqueued job {} of type {}", job_id, J::JOB_TYPE);
+        Ok(job_id)
+    }
+
+    /// Encode `job` into the message handed to the backend, applying the
+    /// adapter-level defaults and limits shared by every enqueue path.
+    fn encode_message<J: Job>(&self, job: &J, opts: EnqueueOptions) -> QueueResult<JobMessage> {
+        // Resolve the job's default run_at against the backend's clock rather
+        // than letting the codec fall back to wall-clock time, so a TestClock
+        // installed on the backend governs eligibility end to end.
+        let now = self.backend.clock().now();
+        let opts = EnqueueOptions {
+            run_at: Some(
+                opts.run_at
+                    .unwrap_or_else(|| crate::codec::default_run_at::<J>(now)),
+            ),
+            ..opts
+        };
+
         // Encode job using codec registry
-        let message = self.codec_registry.encode_job(&job, opts)?;
+        let mut message = self.codec_registry.encode_job(job, opts)?;
+
+        // A TTL too large for chrono never expires, same as no TTL.
+        if message.idempotency_key.is_some() {
+            message.idempotency_expires_at = self
+                .config
+                .idempotency_ttl
+                .and_then(|ttl| chrono::Duration::from_std(ttl).ok())
+                .and_then(|ttl| now.checked_add_signed(ttl));
+        }
+
+        // Carry the producer's trace context so the worker can parent its
+        // execution span to this (instrumented) enqueue span.
+        message.traceparent = crate::observability::tracing::current_traceparent();
 
         // Enforce the configured payload size limit.
         // This check is at the adapter (not codec) layer because encode_job
@@ -312,18 +617,78 @@ impl<B: QueueBackend + Send + Sync + 'static> QueueAdapter<B> {
             }
         }
 
-        // Capture the real queue name before the message is moved into the backend.
-        let queue_name = message.queue.clone();
+        Ok(message)
+    }
 
-        // Enqueue to backend
-        let job_id = self.backend.enqueue(ctx.clone(), message).await?;
+    /// Enqueue `children` as one group and run an aggregation job once all of
+    /// them have finished.
+    ///
+    /// For map-style work — process every track of an album, then build the
+    /// album summary. `on_complete` receives the new group's ID and builds the
+    /// aggregation job; it runs here, at fan-out time, because the aggregation
+    /// job is stored (encoded) alongside the group and may be fired by any
+    /// process sharing the backend. The aggregation job typically keeps the
+    /// ID in its payload and calls [`Self::group_status`] to find out which
+    /// children failed or were canceled — a partial failure does not hold the
+    /// aggregation back.
+    ///
+    /// Every payload is encoded before anything is written, so an oversized or
+    /// unserializable child fails the call without creating a group. If the
+    /// backend fails part-way through enqueueing children, the group never
+    /// finishes; the error is returned to the caller.
+    ///
+    /// Children should not carry idempotency keys: a child deduplicated onto an
+    /// existing job is never counted towards the group.
+    ///
+    /// Requires a backend implementing [`QueueBackend::create_group`].
+    #[instrument(skip(self, children, on_complete), fields(job_type = J::JOB_TYPE, aggregate_type = A::JOB_TYPE, tenant_id = %ctx.tenant_id))]
+    pub async fn fan_out<J, A, F>(
+        &self,
+        ctx: QueueCtx,
+        children: Vec<J>,
+        on_complete: F,
+    ) -> QueueResult<JobGroupId>
+    where
+        J: Job,
+        A: Job,
+        F: FnOnce(JobGroupId) -> A,
+    {
+        let group_id = JobGroupId::new();
+
+        let aggregate = on_complete(group_id.clone());
+        let aggregate_message = self.encode_message(&aggregate, EnqueueOptions::default())?;
+        let child_messages = children
+            .iter()
+            .map(|child| {
+                let mut message = self.encode_message(child, EnqueueOptions::default())?;
+                message.group_id = Some(group_id.clone());
+                Ok(message)
+            })
+            .collect::<QueueResult<Vec<_>>>()?;
+
+        self.backend
+            .create_group(
+                ctx.clone(),
+                group_id.clone(),
+                child_messages.len(),
+                aggregate_message,
+            )
+            .await?;
+
+        for message in child_messages {
+            let queue_name = message.queue.clone();
+            let job_id = self.backend.enqueue(ctx.clone(), message).await?;
+            self.observability
+                .record_job_enqueued(&ctx, &job_id, J::JOB_TYPE, &queue_name);
+        }
 
-        // Record metrics — pass the real queue name, not a hardcoded default.
-        self.observability
-            .record_job_enqueued(&ctx, &job_id, J::JOB_TYPE, &queue_name);
+        info!("Fanned out group {} of type {}", group_id, J::JOB_TYPE);
+        Ok(group_id)
+    }
 
-        info!("Enqueued job {} of type {}", job_id, J::JOB_TYPE);
-        Ok(job_id)
+    /// Current progress of a group created by [`Self::fan_out`].
+    pub async fn group_status(&self, ctx: QueueCtx, group_id: JobGroupId) -> QueueResult<JobGroup> {
+        self.backend.get_group(ctx, group_id).await
     }
 
     /// Execute a job immediately, bypassing durable storage.
@@ -345,7 +710,13 @@ impl<B: QueueBackend + Send + Sync + 'static> QueueAdapter<B> {
         // `execute_timeout` is distinct from `lease_duration`: the lease controls
         // backend claim recycling, while this timeout guards the direct execution
         // path which has no lease, no reaper, and no heartbeat.
-        let execute_fut = job.execute(execution_context);
+        // There is no stored job to cancel or report progress on, so the
+        // token never fires and reports are discarded.
+        let execute_fut = job.execute(
+            execution_context,
+            CancellationToken::new(),
+            ProgressReporter::noop(),
+        );
         match self.config.execute_timeout {
             Some(limit) => tokio::time::timeout(limit, execute_fut)
                 .await
@@ -360,8 +731,9 @@ impl<B: QueueBackend + Send + Sync + 'static> QueueAdapter<B> {
     /// Returns `true` if the job was found and successfully canceled, `false`
     /// if it was already in a terminal state (`Completed`, `Failed`, or already
     /// `Canceled`).  Cancel-wins semantics apply: if a worker is currently
-    /// executing the job, the next `ack_complete` call will return
-    /// [`QueueError::JobCanceled`] and the result will be discarded.
+    /// executing the job, the [`CancellationToken`] passed to
+    /// [`Job::execute`] fires so the job can stop early, and whatever it
+    /// returns is discarded rather than acked.
     ///
     /// Cancellation is recorded in the observability layer so `jobs_canceled`
     /// reflects all cancellations that go through this adapter method.
@@ -391,6 +763,15 @@ impl<B: QueueBackend + Send + Sync + 'static> QueueAdapter<B> {
         Ok(canceled)
     }
 
+    /// The shared backend handle, type-erased (used by the `ui` dashboard).
+    #[cfg_attr(not(feature = "ui"), allow(dead_code))]
+    pub(crate) fn dyn_backend(&self) -> Arc<dyn QueueBackend + Send + Sync>
+    where
+        B: 'static,
+    {
+        self.backend.clone()
+    }
+
     /// Erase the concrete backend type to `dyn QueueBackend + Send + Sync`.
     ///
     /// Used internally by `start_workers` to share one type-erased adapter
@@ -409,18 +790,41 @@ impl<B: QueueBackend + Send + Sync + 'static> QueueAdapter<B> {
         }
     }
 
-    /// Start a pool of `config.max_workers` concurrent workers.
+    /// Start a pool of up to `config.max_workers` concurrent workers.
     ///
-    /// All workers share the same `Arc`-wrapped state (backend, registry,
-    /// observability) and are coordinated by the returned [`WorkerHandle`].
+    /// Queues listed in [`QueueConfig::per_queue_concurrency`] get their own
+    /// dedicated workers; the rest share the remaining budget. All workers
+    /// share the same `Arc`-wrapped state (backend, registry, observability)
+    /// and are coordinated by the returned [`WorkerHandle`].
     /// Call [`WorkerHandle::shutdown`] to gracefully stop them all.
-    #[instrument(skip(self, context), fields(tenant_id = %ctx.tenant_id, queues = ?queues))]
     pub async fn start_workers<C>(
         &self,
         ctx: QueueCtx,
         context: C,
         queues: Vec<String>,
     ) -> QueueResult<WorkerHandle>
+    where
+        C: Clone + Send + Sync + 'static,
+    {
+        self.start_workers_filtered(ctx, context, queues, JobTypeFilter::All)
+            .await
+    }
+
+    /// Like [`Self::start_workers`], but the pool only leases job types that
+    /// `job_types` permits; other jobs on the same queues are left for other
+    /// pools.
+    ///
+    /// Requires a backend that implements
+    /// [`QueueBackend::dequeue_filtered`] unless `job_types` is
+    /// [`JobTypeFilter::All`].
+    #[instrument(skip(self, context), fields(tenant_id = %ctx.tenant_id, queues = ?queues, job_types = ?job_types))]
+    pub async fn start_workers_filtered<C>(
+        &self,
+        ctx: QueueCtx,
+        context: C,
+        queues: Vec<String>,
+        job_types: JobTypeFilter,
+    ) -> QueueResult<WorkerHandle>
     where
         C: Clone + Send + Sync + 'static,
     {
@@ -428,26 +832,26 @@ impl<B: QueueBackend + Send + Sync + 'static> QueueAdapter<B> {
         // try_with_config returns an error, new() uses a hard-coded valid default).
         // Re-validating here would be unreachable dead code.
 
-        let worker_count = self.config.max_workers;
-        let mut shutdown_txs = Vec::with_capacity(worker_count);
+        let assignments = self.config.worker_assignments(&queues)?;
+        let worker_count = assignments.len();
+        let (shutdown_tx, shutdown_rx) = watch::channel(None);
         let mut join_handles = Vec::with_capacity(worker_count);
 
         // Build one type-erased adapter shared across all workers.
         let dyn_adapter = Arc::new(self.to_dyn_shared());
 
-        for _ in 0..worker_count {
-            let (shutdown_tx, shutdown_rx) = oneshot::channel();
-
+        for worker_queues in assignments {
             let worker = Worker {
                 adapter: dyn_adapter.clone(),
                 ctx: ctx.clone(),
                 context: Arc::new(context.clone()),
-                queues: queues.clone(),
+                queues: worker_queues,
+                job_types: job_types.clone(),
             };
 
-            let join_handle = tokio::spawn(async move { worker.run(shutdown_rx).await });
+            let worker_shutdown_rx = shutdown_rx.clone();
+            let join_handle = tokio::spawn(async move { worker.run(worker_shutdown_rx).await });
 
-            shutdown_txs.push(shutdown_tx);
             join_handles.push(join_handle);
         }
 
@@ -501,7 +905,7 @@ impl<B: QueueBackend + Send + Sync + 'static> QueueAdapter<B> {
                                             &ctx,
                                             &outcome.job_id,
                                             &outcome.job_type,
-                                            "Lease expired — max retries exceeded",
+                                            "Lease expired — not retried",
                                         );
                                     } else if let Some(retry_at) = outcome.retry_at {
                                         reaper_observability.record_job_retrying(
@@ -523,7 +927,7 @@ impl<B: QueueBackend + Send + Sync + 'static> QueueAdapter<B> {
         });
 
         Ok(WorkerHandle {
-            shutdown_txs,
+            shutdown_tx,
             join_handles,
             reaper_shutdown_tx: Some(reaper_shutdown_tx),
             reaper_handle: Some(reaper_handle),
@@ -585,6 +989,83 @@ impl<B: QueueBackend + Send + Sync + 'static> QueueAdapter<B> {
 
         Ok(Some(result))
     }
+
+    /// Wait for a job to finish and return its typed result.
+    ///
+    /// Intended for request/response-style callers — e.g. an HTTP handler
+    /// that enqueues work and holds the request open for the outcome. The
+    /// wait follows the backend's event stream; backends without one are
+    /// polled every `poll_interval`. A job that is already terminal when this
+    /// is called resolves immediately: the event subscription is opened
+    /// *before* the status is read, so a transition landing between the two
+    /// is never missed.
+    ///
+    /// # Errors
+    ///
+    /// Mirrors [`Self::execute_now`] — the job's own failure surfaces as
+    /// [`QueueError::JobFailed`]:
+    ///
+    /// - [`QueueError::JobFailed`] — the job failed permanently; the inner
+    ///   [`JobError::Permanent`] carries the error recorded by the worker.
+    /// - [`QueueError::JobCanceled`] — the job was canceled.
+    /// - [`QueueError::Timeout`] — the job was not terminal within `timeout`.
+    ///   The job itself is unaffected and keeps running.
+    /// - [`QueueError::SerializationError`] — the stored result cannot be
+    ///   deserialized to `J::Result`.
+    /// - [`QueueError::JobNotFound`] — the job ID does not exist for `ctx`.
+    #[instrument(skip(self), fields(job_type = J::JOB_TYPE, tenant_id = %ctx.tenant_id, job_id = %job_id))]
+    pub async fn await_result<J: Job>(
+        &self,
+        ctx: QueueCtx,
+        job_id: JobId,
+        timeout: Duration,
+    ) -> QueueResult<J::Result> {
+        let wait = async {
+            let filter = EventFilter::new().job_id(job_id.clone()).events([
+                "completed",
+                "failed",
+                "canceled",
+            ]);
+            let mut events = self.backend.event_stream_filtered(ctx.clone(), filter).ok();
+
+            loop {
+                let record = self.backend.get_record(ctx.clone(), job_id.clone()).await?;
+                match record.status {
+                    JobStatus::Completed { .. } => {
+                        // Jobs returning `()` store "null"; treat a missing value the same.
+                        let stored = record.result.as_deref().unwrap_or("null");
+                        return serde_json::from_str(stored).map_err(|e| {
+                            QueueError::SerializationError(format!(
+                                "Failed to deserialize stored result for job type '{}': {e}",
+                                J::JOB_TYPE
+                            ))
+                        });
+                    }
+                    JobStatus::Failed { error, .. } => {
+                        return Err(QueueError::JobFailed(JobError::Permanent(error)));
+                    }
+                    JobStatus::Canceled { .. } => return Err(QueueError::JobCanceled),
+                    _ => {}
+                }
+
+                // Not terminal yet: wait for the next terminal event, then
+                // re-read the record for the result. A stream that ends (e.g.
+                // the subscriber lagged) degrades to polling.
+                match events.as_mut() {
+                    Some(stream) => {
+                        if stream.next().await.is_none() {
+                            events = None;
+                        }
+                    }
+                    None => tokio::time::sleep(self.config.poll_interval).await,
+                }
+            }
+        };
+
+        tokio::time::timeout(timeout, wait)
+            .await
+            .map_err(|_| QueueError::Timeout(timeout))?
+    }
 }
 
 impl<B: QueueBackend> Clone for QueueAdapter<B> {
@@ -599,13 +1080,56 @@ impl<B: QueueBackend> Clone for QueueAdapter<B> {
     }
 }
 
+/// Fire `cancel` once `job_id` is canceled in the backend.
+///
+/// Subscribes to the job's `canceled` event when the backend has filtered
+/// event streams, and otherwise re-reads the job status every
+/// `poll_interval`. Either way the status is checked once after subscribing,
+/// so a cancel that landed between dequeue and subscription is not missed.
+/// Any other terminal status, or a backend error while polling, ends the
+/// watch without firing — the worker's ack path handles those.
+async fn watch_for_cancel<B: QueueBackend + ?Sized>(
+    backend: Arc<B>,
+    ctx: QueueCtx,
+    job_id: JobId,
+    cancel: CancellationToken,
+    poll_interval: Duration,
+) {
+    let filter = EventFilter::new()
+        .job_id(job_id.clone())
+        .events(["canceled"]);
+    let mut events = backend.event_stream_filtered(ctx.clone(), filter).ok();
+
+    loop {
+        match backend.get_status(ctx.clone(), job_id.clone()).await {
+            Ok(JobStatus::Canceled { .. }) => {
+                cancel.cancel();
+                return;
+            }
+            Ok(JobStatus::Processing { .. }) => {}
+            Ok(_) | Err(_) => return,
+        }
+
+        match events.as_mut() {
+            Some(stream) => {
+                if stream.next().await.is_some() {
+                    cancel.cancel();
+                }
+                return;
+            }
+            None => tokio::time::sleep(poll_interval).await,
+        }
+    }
+}
+
 /// RAII guard that aborts the wrapped task when dropped.
 ///
 /// When `tokio::select!` cancels a future that owns a `JoinHandle`, Tokio
 /// **detaches** (does not abort) the spawned task.  Wrapping the heartbeat
 /// handle in `AbortOnDrop` ensures the task is always terminated when
-/// `process_next_job` is cancelled by a shutdown signal, preventing orphaned
-/// heartbeat tasks that extend leases indefinitely after the worker exits.
+/// `process_next_job` is cancelled or returns early (e.g. after releasing a
+/// lease at the end of a shutdown drain), preventing orphaned heartbeat tasks
+/// that extend leases indefinitely after the worker exits.
 struct AbortOnDrop(tokio::task::JoinHandle<()>);
 
 impl Drop for AbortOnDrop {
@@ -614,12 +1138,29 @@ impl Drop for AbortOnDrop {
     }
 }
 
+/// What a single [`Worker::process_next_job`] call did.
+enum JobOutcome {
+    /// No eligible job was available.
+    Idle,
+    /// A job was executed and acknowledged (successfully or not).
+    Processed,
+    /// Shutdown was requested mid-execution and the job finished inside the
+    /// drain window.
+    Drained,
+    /// Shutdown was requested mid-execution and the drain window elapsed; the
+    /// job's lease was handed back to the queue.
+    Released,
+    /// As `Released`, but the job was at-most-once, so it was failed instead.
+    Abandoned,
+}
+
 /// Worker for processing jobs from queues
 struct Worker<C> {
     adapter: Arc<QueueAdapter<dyn QueueBackend + Send + Sync>>,
     ctx: QueueCtx,
     context: Arc<C>,
     queues: Vec<String>,
+    job_types: JobTypeFilter,
     // NOTE: shutdown_rx is NOT stored here — it is passed directly to run()
     // so that process_next_job can borrow self without a partial-move conflict.
 }
@@ -627,11 +1168,19 @@ struct Worker<C> {
 impl<C: Send + Sync + 'static> Worker<C> {
     /// Run the worker loop, terminating on shutdown signal or after the
     /// configured idle timeout elapses with no jobs available.
-    async fn run(self, mut shutdown_rx: oneshot::Receiver<()>) -> QueueResult<()> {
+    ///
+    /// Returns how many in-flight jobs were drained or released during shutdown
+    /// (at most one of each, since a worker runs one job at a time).
+    async fn run(
+        self,
+        mut shutdown_rx: watch::Receiver<Option<Instant>>,
+    ) -> QueueResult<DrainSummary> {
         let queue_refs: Vec<&str> = self.queues.iter().map(|s| s.as_str()).collect();
 
         info!("Worker started for queues: {:?}", self.queues);
 
+        let mut summary = DrainSummary::default();
+
         // Track consecutive idle time so the worker can self-terminate.
         // Reset to `None` whenever a job is successfully processed.
         let mut idle_since: Option<std::time::Instant> = None;
@@ -646,116 +1195,128 @@ impl<C: Send + Sync + 'static> Worker<C> {
         let mut consecutive_errors: u32 = 0;
 
         loop {
-            tokio::select! {
-                _ = &mut shutdown_rx => {
-                    info!("Worker shutdown requested");
-                    break;
-                }
+            // Stop leasing new jobs as soon as shutdown is requested. The check
+            // sits before dequeue (rather than racing it in a select!) so a
+            // dequeue is never cancelled half-way through leasing a job.
+            if shutdown_rx.borrow().is_some() {
+                info!("Worker shutdown requested");
+                break;
+            }
 
-                result = self.process_next_job(&queue_refs) => {
-                    match result {
-                        Ok(true) => {
-                            // A job ran — reset both the idle clock and error counter.
-                            if consecutive_errors > 0 {
-                                info!(
-                                    "Backend recovered after {} consecutive error(s)",
-                                    consecutive_errors
-                                );
-                                consecutive_errors = 0;
-                            }
-                            idle_since = None;
-                        }
-                        Ok(false) => {
-                            // No jobs available — reset error counter, track idle duration.
-                            if consecutive_errors > 0 {
-                                info!(
-                                    "Backend recovered after {} consecutive error(s)",
-                                    consecutive_errors
-                                );
-                                consecutive_errors = 0;
-                            }
-                            let idle_start = *idle_since.get_or_insert_with(std::time::Instant::now);
-                            if idle_start.elapsed() >= self.adapter.config.worker_idle_timeout {
-                                info!(
-                                    "Worker idle for {:?}, shutting down",
-                                    self.adapter.config.worker_idle_timeout
-                                );
-                                break;
-                            }
-                            // Sleep before next poll, adding a random jitter in
-                            // [0, poll_jitter] to stagger workers across the pool.
-                            // Without jitter, all workers wake and issue dequeue
-                            // requests at the same instant — a thundering herd for
-                            // Redis/Postgres backends.
-                            let jitter_nanos = if self.adapter.config.poll_jitter.is_zero() {
-                                0u64
-                            } else {
-                                // rand::random_range is the top-level free function
-                                // in rand 0.10 — no Rng trait import required.
-                                rand::random_range(
-                                    0u64..=self.adapter.config.poll_jitter.as_nanos() as u64
-                                )
-                            };
-                            let sleep_duration = self.adapter.config.poll_interval
-                                + Duration::from_nanos(jitter_nanos);
-                            tokio::time::sleep(sleep_duration).await;
-                        }
-                        Err(e) => {
-                            consecutive_errors += 1;
-                            // Log every first error and subsequent powers-of-two to stay
-                            // informed without flooding log ingestion during long outages.
-                            // Pattern: error at 1, warn at 2, 4, 8, 16, … → silences
-                            // intermediate lines while preserving a clear escalation trail.
-                            if consecutive_errors == 1 {
-                                error!(
-                                    "Backend error (will back off exponentially): {}", e
-                                );
-                            } else if consecutive_errors.is_power_of_two() {
-                                warn!(
-                                    "Backend still unavailable after {} error(s): {}",
-                                    consecutive_errors, e
-                                );
-                            }
-                            // Exponential backoff capped at 30s:
-                            //   error #1 → 1s, #2 → 2s, #3 → 4s, #4 → 8s,
-                            //   #5 → 16s, #6+ → 30s (cap).
-                            // error_backoff (default 1s) is the base; min() caps at 30s.
-                            // Using saturating_pow to prevent overflow on very long outages.
-                            let exponent = consecutive_errors.saturating_sub(1).min(5);
-                            let backoff = self
-                                .adapter
-                                .config
-                                .error_backoff
-                                .saturating_mul(2u32.saturating_pow(exponent))
-                                .min(Duration::from_secs(30));
-                            // Reset idle_since: distinguish degraded backend (worker is
-                            // active, just failing) from empty queue (no jobs to process).
-                            // Without this reset, an outage longer than worker_idle_timeout
-                            // self-terminates all workers exactly when recovery throughput
-                            // is most needed.
-                            idle_since = None;
-                            tokio::time::sleep(backoff).await;
-                        }
+            match self.process_next_job(&queue_refs, &mut shutdown_rx).await {
+                Ok(JobOutcome::Drained) => summary.completed += 1,
+                Ok(JobOutcome::Released) => summary.released += 1,
+                Ok(JobOutcome::Abandoned) => summary.abandoned += 1,
+                Ok(JobOutcome::Processed) => {
+                    // A job ran — reset both the idle clock and error counter.
+                    if consecutive_errors > 0 {
+                        info!(
+                            "Backend recovered after {} consecutive error(s)",
+                            consecutive_errors
+                        );
+                        consecutive_errors = 0;
+                    }
+                    idle_since = None;
+                }
+                Ok(JobOutcome::Idle) => {
+                    // No jobs available — reset error counter, track idle duration.
+                    if consecutive_errors > 0 {
+                        info!(
+                            "Backend recovered after {} consecutive error(s)",
+                            consecutive_errors
+                        );
+                        consecutive_errors = 0;
+                    }
+                    let idle_start = *idle_since.get_or_insert_with(std::time::Instant::now);
+                    if idle_start.elapsed() >= self.adapter.config.worker_idle_timeout {
+                        info!(
+                            "Worker idle for {:?}, shutting down",
+                            self.adapter.config.worker_idle_timeout
+                        );
+                        break;
+                    }
+                    // Sleep before next poll, adding a random jitter in
+                    // [0, poll_jitter] to stagger workers across the pool.
+                    // Without jitter, all workers wake and issue dequeue
+                    // requests at the same instant — a thundering herd for
+                    // Redis/Postgres backends.
+                    let jitter_nanos = if self.adapter.config.poll_jitter.is_zero() {
+                        0u64
+                    } else {
+                        // rand::random_range is the top-level free function
+                        // in rand 0.10 — no Rng trait import required.
+                        rand::random_range(0u64..=self.adapter.config.poll_jitter.as_nanos() as u64)
+                    };
+                    let sleep_duration =
+                        self.adapter.config.poll_interval + Duration::from_nanos(jitter_nanos);
+                    tokio::select! {
+                        _ = tokio::time::sleep(sleep_duration) => {}
+                        _ = shutdown_requested(&mut shutdown_rx) => {}
+                    }
+                }
+                Err(e) => {
+                    consecutive_errors += 1;
+                    // Log every first error and subsequent powers-of-two to stay
+                    // informed without flooding log ingestion during long outages.
+                    // Pattern: error at 1, warn at 2, 4, 8, 16, … → silences
+                    // intermediate lines while preserving a clear escalation trail.
+                    if consecutive_errors == 1 {
+                        error!("Backend error (will back off exponentially): {}", e);
+                    } else if consecutive_errors.is_power_of_two() {
+                        warn!(
+                            "Backend still unavailable after {} error(s): {}",
+                            consecutive_errors, e
+                        );
+                    }
+                    // Exponential backoff capped at 30s:
+                    //   error #1 → 1s, #2 → 2s, #3 → 4s, #4 → 8s,
+                    //   #5 → 16s, #6+ → 30s (cap).
+                    // error_backoff (default 1s) is the base; min() caps at 30s.
+                    // Using saturating_pow to prevent overflow on very long outages.
+                    let exponent = consecutive_errors.saturating_sub(1).min(5);
+                    let backoff = self
+                        .adapter
+                        .config
+                        .error_backoff
+                        .saturating_mul(2u32.saturating_pow(exponent))
+                        .min(Duration::from_secs(30));
+                    // Reset idle_since: distinguish degraded backend (worker is
+                    // active, just failing) from empty queue (no jobs to process).
+                    // Without this reset, an outage longer than worker_idle_timeout
+                    // self-terminates all workers exactly when recovery throughput
+                    // is most needed.
+                    idle_since = None;
+                    tokio::select! {
+                        _ = tokio::time::sleep(backoff) => {}
+                        _ = shutdown_requested(&mut shutdown_rx) => {}
                     }
                 }
             }
         }
 
         info!("Worker stopped");
-        Ok(())
+        Ok(summary)
     }
 
-    /// Process the next available job
-    async fn process_next_job(&self, queues: &[&str]) -> QueueResult<bool> {
+    /// Process the next available job.
+    ///
+    /// `shutdown_rx` is watched while the handler executes: once shutdown is
+    /// requested the job may keep running until the drain deadline, after which
+    /// its lease is released and the handler future is dropped.
+    async fn process_next_job(
+        &self,
+        queues: &[&str],
+        shutdown_rx: &mut watch::Receiver<Option<Instant>>,
+    ) -> QueueResult<JobOutcome> {
         // Dequeue next job
         let leased_job = match self
             .adapter
             .backend
-            .dequeue(self.ctx.clone(), queues)
+            .dequeue_filtered(self.ctx.clone(), queues, &self.job_types)
             .await?
         {
             Some(job) => job,
-            None => return Ok(false), // No jobs available
+            None => return Ok(JobOutcome::Idle), // No jobs available
         };
 
         let job_id = leased_job.record.job_id.clone();
@@ -784,6 +1345,10 @@ impl<C: Send + Sync + 'static> Worker<C> {
         // If the job is canceled or the lease token is invalidated, heartbeat_extend
         // returns an error; the heartbeat loop exits and the main worker's
         // ack_complete will surface the JobCanceled / InvalidLeaseToken error.
+        // A JobCanceled error also fires the cancellation token, as a backstop
+        // for the cancel watcher below.
+        let cancel = CancellationToken::new();
+        let hb_cancel = cancel.clone();
         let hb_backend = self.adapter.backend.clone();
         let hb_ctx = self.ctx.clone();
         let hb_job_id = job_id.clone();
@@ -803,6 +1368,10 @@ impl<C: Send + Sync + 'static> Worker<C> {
                     .await
                 {
                     Ok(()) => {}
+                    Err(QueueError::JobCanceled) => {
+                        hb_cancel.cancel();
+                        break;
+                    }
                     Err(e) => {
                         warn!(
                             "Heartbeat extension failed for job {} (stopping heartbeat): {}",
@@ -814,6 +1383,17 @@ impl<C: Send + Sync + 'static> Worker<C> {
             }
         }));
 
+        // Watch for a cancel of this job while it runs, so the job can stop
+        // cooperatively instead of finishing work whose result cancel-wins
+        // would throw away anyway.
+        let cancel_watch = AbortOnDrop(tokio::spawn(watch_for_cancel(
+            self.adapter.backend.clone(),
+            self.ctx.clone(),
+            job_id.clone(),
+            cancel.clone(),
+            self.adapter.config.cancel_poll_interval,
+        )));
+
         // Decode the payload through the registered codec before handing it to the handler.
         // `encode_bytes` was called at enqueue time; `decode_bytes` must be called here to
         // reverse any transformation (compression, encryption, alternate wire format).
@@ -841,6 +1421,7 @@ impl<C: Send + Sync + 'static> Worker<C> {
                 // AbortOnDrop will abort the heartbeat task as it goes out of scope;
                 // drop explicitly here to abort BEFORE calling ack_fail.
                 drop(heartbeat_handle);
+                drop(cancel_watch);
 
                 // Permanently fail the job so it leaves Processing immediately.
                 // Ignore ack_fail errors here — we cannot do anything useful with
@@ -861,10 +1442,10 @@ impl<C: Send + Sync + 'static> Worker<C> {
                     .observability
                     .record_job_failed(&self.ctx, &job_id, job_type, &error_str);
 
-                // Return Ok(true) — we did process a job (it permanently failed).
-                // Returning Ok(false) would trigger the idle timer for an empty queue;
+                // Return Processed — we did process a job (it permanently failed).
+                // Returning Idle would trigger the idle timer for an empty queue;
                 // Err would trigger the error backoff; neither is correct here.
-                return Ok(true);
+                return Ok(JobOutcome::Processed);
             }
         };
         let mut decoded_message = leased_job.record.message.clone();
@@ -873,14 +1454,74 @@ impl<C: Send + Sync + 'static> Worker<C> {
         // Time the execute() call for performance metrics.
         // The elapsed duration is recorded after the drop of the heartbeat handle
         // so that heartbeat teardown overhead is not counted as job execution time.
+        // The execution span continues the producer's trace (see
+        // `JobMessage::traceparent`) rather than the worker loop's, so traces
+        // run end-to-end from the enqueuing request to this execution.
+        let execute_span = tracing::info_span!(
+            "dog_queue.execute",
+            job_id = %job_id,
+            job_type = %job_type,
+            attempt = leased_job.record.attempt,
+        );
+        if let Some(traceparent) = &leased_job.record.message.traceparent {
+            crate::observability::tracing::set_remote_parent(&execute_span, traceparent);
+        }
+
+        let progress = ProgressReporter::new(
+            self.adapter.backend.clone(),
+            self.ctx.clone(),
+            job_id.clone(),
+            leased_job.lease_token.clone(),
+            self.adapter.config.progress_interval,
+        );
+
         let execute_start = std::time::Instant::now();
-        let result = handler
-            .execute(&decoded_message, self.context.clone())
-            .await;
+        let execute_fut = handler
+            .execute(
+                &decoded_message,
+                self.context.clone(),
+                cancel.clone(),
+                progress.clone(),
+            )
+            .instrument(execute_span);
+        tokio::pin!(execute_fut);
+
+        // Race execution against a shutdown request. Once shutdown is requested
+        // the job keeps running until the shared drain deadline; if it is still
+        // running then, hand the lease back rather than letting the job sit in
+        // Processing until the reaper expires it.
+        let mut drained = false;
+        let result = tokio::select! {
+            result = &mut execute_fut => result,
+            deadline = shutdown_requested(shutdown_rx) => {
+                drained = true;
+                match tokio::time::timeout_at(deadline, &mut execute_fut).await {
+                    Ok(result) => result,
+                    Err(_) => {
+                        drop(heartbeat_handle);
+                        drop(cancel_watch);
+                        let lease_token = leased_job.lease_token;
+                        return Ok(match leased_job.record.message.delivery {
+                            DeliveryMode::AtLeastOnce => {
+                                self.release_in_flight(&job_id, lease_token).await
+                            }
+                            DeliveryMode::AtMostOnce => {
+                                self.abandon_in_flight(&job_id, job_type, lease_token).await
+                            }
+                        });
+                    }
+                }
+            }
+        };
         let execute_elapsed = execute_start.elapsed();
 
-        // Job finished — drop the AbortOnDrop guard, which aborts the heartbeat task.
+        // Store the last debounced progress report while the lease is held.
+        progress.finish().await;
+
+        // Job finished — drop the AbortOnDrop guards, which abort the heartbeat
+        // and cancel-watch tasks.
         drop(heartbeat_handle);
+        drop(cancel_watch);
 
         // Record execution timing — this is the first caller of record_execution_time;
         // previously the PerformanceMetrics ring buffer was permanently empty.
@@ -889,6 +1530,23 @@ impl<C: Send + Sync + 'static> Worker<C> {
             .metrics()
             .record_execution_time(job_type, execute_elapsed);
 
+        if cancel.is_cancelled() {
+            // The job was canceled mid-flight and the backend already holds the
+            // terminal Canceled status. Acking would only fail with JobCanceled,
+            // and ack_fail must not schedule a retry for a job the caller gave
+            // up on. As below, record_job_canceled() was already called by
+            // QueueAdapter::cancel(), so no observability call here.
+            info!(
+                "Job {} observed cancellation and stopped — result discarded (cancel-wins)",
+                job_id
+            );
+            return Ok(if drained {
+                JobOutcome::Drained
+            } else {
+                JobOutcome::Processed
+            });
+        }
+
         match result {
             Ok(result_ref) => {
                 // Job completed successfully — ack with the backend.
@@ -951,7 +1609,7 @@ impl<C: Send + Sync + 'static> Worker<C> {
                 let retry_at = if is_retryable
                     && leased_job.record.attempt <= leased_job.record.message.max_retries
                 {
-                    Some(self.calculate_retry_time(leased_job.record.attempt))
+                    Some(self.calculate_retry_time(handler.as_ref(), leased_job.record.attempt))
                 } else {
                     None
                 };
@@ -988,36 +1646,101 @@ impl<C: Send + Sync + 'static> Worker<C> {
             }
         }
 
-        Ok(true)
+        Ok(if drained {
+            JobOutcome::Drained
+        } else {
+            JobOutcome::Processed
+        })
     }
 
-    /// Calculate retry time using full-jitter exponential backoff.
+    /// Hand an in-flight job's lease back to the backend after the drain window
+    /// elapsed, so another worker can pick the job up immediately.
     ///
-    /// "Full jitter" (AWS recommendation): instead of `sleep = clamp(2^attempt * base, cap)`,
-    /// pick uniformly from `[0, clamp(2^attempt * base, cap)]`. This desynchronises
-    /// concurrent retriers that all failed at the same instant — preventing the thundering
-    /// herd that pure exponential backoff causes on mass failures.
-    fn calculate_retry_time(&self, attempt: u32) -> chrono::DateTime<chrono::Utc> {
-        let cap = self.adapter.config.max_retry_backoff.as_secs();
-        let base = self
+    /// A release failure is logged rather than propagated: the worker is shutting
+    /// down either way, and the reaper reclaims the job once its lease expires.
+    async fn release_in_flight(&self, job_id: &JobId, lease_token: LeaseToken) -> JobOutcome {
+        match self
             .adapter
-            .config
-            .base_retry_backoff
-            .as_secs()
-            .saturating_mul(2_u64.pow(attempt.saturating_sub(1)));
-        let ceiling = base.min(cap);
-
-        // Uniform sample in [0, ceiling] — each retrier picks a different slot.
-        // rand::random_range is the rand 0.10 top-level API used throughout this file;
-        // consistent with the poll_jitter sampling below. Inclusive upper bound
-        // matches the documented semantics ("pick uniformly from [0, cap]").
-        let jitter_secs = if ceiling > 0 {
-            rand::random_range(0u64..=ceiling)
-        } else {
-            0
-        };
+            .backend
+            .release_lease(self.ctx.clone(), job_id.clone(), lease_token)
+            .await
+        {
+            Ok(()) => warn!(
+                "Job {} still running after drain timeout — lease released for another worker",
+                job_id
+            ),
+            Err(e) => warn!(
+                "Job {} still running after drain timeout and its lease could not be released \
+                 ({e}) — the reaper will reclaim it once the lease expires",
+                job_id
+            ),
+        }
+        JobOutcome::Released
+    }
+
+    /// Permanently fail an in-flight at-most-once job after the drain window
+    /// elapsed. Its partial side effects are unknown, so it must not be handed
+    /// to another worker.
+    async fn abandon_in_flight(
+        &self,
+        job_id: &JobId,
+        job_type: &str,
+        lease_token: LeaseToken,
+    ) -> JobOutcome {
+        let error = "Still running after drain timeout — at-most-once job not retried";
+        match self
+            .adapter
+            .backend
+            .ack_fail(
+                self.ctx.clone(),
+                job_id.clone(),
+                lease_token,
+                error.to_string(),
+                None,
+            )
+            .await
+        {
+            Ok(()) => {
+                self.adapter
+                    .observability
+                    .record_job_failed(&self.ctx, job_id, job_type, error);
+                warn!("Job {} {}", job_id, error);
+            }
+            Err(e) => warn!(
+                "Job {} still running after drain timeout and could not be failed ({e}) — \
+                 the reaper will fail it once the lease expires",
+                job_id
+            ),
+        }
+        JobOutcome::Abandoned
+    }
 
-        chrono::Utc::now() + chrono::Duration::seconds(jitter_secs as i64)
+    /// Calculate the retry time for a failed attempt.
+    ///
+    /// The job type's [`Job::retry_backoff`](crate::Job::retry_backoff) override
+    /// wins; otherwise the adapter-wide policy applies: exponential backoff from
+    /// `base_retry_backoff`, capped at `max_retry_backoff`, with full jitter
+    /// unless `retry_jitter` is disabled (see [`RetryBackoff::Exponential`]).
+    fn calculate_retry_time(
+        &self,
+        handler: &dyn JobHandler,
+        attempt: u32,
+    ) -> chrono::DateTime<chrono::Utc> {
+        let config = &self.adapter.config;
+        let delay = handler.retry_backoff(attempt).unwrap_or_else(|| {
+            RetryBackoff::Exponential {
+                base: config.base_retry_backoff,
+                max: config.max_retry_backoff,
+                jitter: config.retry_jitter,
+            }
+            .delay(attempt)
+        });
+
+        // Out-of-range durations (absurd overrides) saturate to the cap.
+        let delay = chrono::Duration::from_std(delay).unwrap_or_else(|_| {
+            chrono::Duration::seconds(config.max_retry_backoff.as_secs() as i64)
+        });
+        self.adapter.backend.clock().now() + delay
     }
 }
 
@@ -1047,7 +1770,12 @@ mod tests {
         const PRIORITY: crate::JobPriority = JobPriority::Normal;
         const MAX_RETRIES: u32 = 3;
 
-        async fn execute(&self, ctx: Self::Context) -> Result<Self::Result, JobError> {
+        async fn execute(
+            &self,
+            ctx: Self::Context,
+            _cancel: CancellationToken,
+            _progress: ProgressReporter,
+        ) -> Result<Self::Result, JobError> {
             Ok(format!(
                 "Processed: {} with context: {}",
                 self.data, ctx.value
@@ -1063,6 +1791,50 @@ mod tests {
         assert_eq!(adapter.config().max_workers, 10);
     }
 
+    #[test]
+    fn test_per_queue_concurrency_validation() {
+        let mut config = QueueConfig {
+            max_workers: 4,
+            ..QueueConfig::default()
+        };
+        config.per_queue_concurrency.insert("a".to_string(), 3);
+        config.per_queue_concurrency.insert("b".to_string(), 1);
+        assert!(config.validate().is_ok());
+
+        // Dedicated workers may not exceed the global cap.
+        config.per_queue_concurrency.insert("b".to_string(), 2);
+        assert!(matches!(
+            config.validate(),
+            Err(QueueError::InvalidConfig(_))
+        ));
+
+        // A queue with zero workers would never be processed.
+        config.per_queue_concurrency.insert("b".to_string(), 0);
+        assert!(matches!(
+            config.validate(),
+            Err(QueueError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_worker_assignments_share_remaining_budget() {
+        let mut config = QueueConfig {
+            max_workers: 5,
+            ..QueueConfig::default()
+        };
+        config.per_queue_concurrency.insert("gps".to_string(), 3);
+
+        let queues = vec!["gps".to_string(), "a".to_string(), "b".to_string()];
+        let assignments = config.worker_assignments(&queues).unwrap();
+        assert_eq!(assignments.len(), 5);
+        assert_eq!(assignments.iter().filter(|q| *q == &["gps"]).count(), 3);
+        assert_eq!(assignments.iter().filter(|q| *q == &["a", "b"]).count(), 2);
+
+        // No budget left for unlisted queues is a configuration error.
+        config.per_queue_concurrency.insert("gps".to_string(), 5);
+        assert!(config.worker_assignments(&queues).is_err());
+    }
+
     #[tokio::test]
     async fn test_job_registration() {
         let backend = MemoryBackend::new();
@@ -1089,4 +1861,54 @@ mod tests {
         let result = adapter.enqueue(ctx, job).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_payload_size_limit_applies_to_encoded_bytes() {
+        let job = TestJob {
+            data: "x".repeat(1024),
+        };
+        let encoded_len = CodecRegistry::new()
+            .encode_job(&job, EnqueueOptions::default())
+            .unwrap()
+            .payload_bytes
+            .len();
+        let with_limit = |max| {
+            QueueAdapter::with_config(
+                MemoryBackend::new(),
+                QueueConfig {
+                    max_payload_size: Some(max),
+                    ..QueueConfig::default()
+                },
+            )
+        };
+        let ctx = QueueCtx::new("test_tenant");
+
+        // Just under (and exactly at) the limit is accepted.
+        let adapter = with_limit(encoded_len + 1);
+        assert!(adapter.enqueue(ctx.clone(), job.clone()).await.is_ok());
+        let adapter = with_limit(encoded_len);
+        assert!(adapter.enqueue(ctx.clone(), job.clone()).await.is_ok());
+
+        // One byte over is rejected before reaching the backend.
+        let adapter = with_limit(encoded_len - 1);
+        let err = adapter.enqueue(ctx.clone(), job).await.unwrap_err();
+        assert!(
+            matches!(err, QueueError::PayloadTooLarge { size, max }
+                if size == encoded_len && max == encoded_len - 1),
+            "got {err:?}"
+        );
+        assert!(adapter.backend().list_queues(ctx).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_zero_payload_limit_is_invalid() {
+        let config = QueueConfig {
+            max_payload_size: Some(0),
+            ..QueueConfig::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(QueueError::InvalidConfig(_))
+        ));
+    }
 }
@@ -9,7 +9,7 @@ use crate::{
         memory::storage::{priority_insert, MemoryBackend},
         ReapOutcome,
     },
-    JobEvent, JobStatus, QueueResult,
+    DeliveryMode, JobEvent, JobStatus, QueueResult,
 };
 
 /// Lease expiry reaper for reclaiming expired jobs
@@ -103,7 +103,7 @@ impl LeaseReaper {
     /// - Retry re-enqueue uses `priority_insert` (not `push_back`) to preserve priority
     ///   ordering — a reclaimed Critical job is not placed behind Normal/Low entries.
     pub async fn reap_expired_leases(&self) -> QueueResult<Vec<ReapOutcome>> {
-        let now = Utc::now();
+        let now = self.backend.clock.now();
 
         // ── Phase 1: Collect IDs of expired leases under jobs.read() ───────────────
         // Only the job IDs are collected, not full records. The authoritative
@@ -135,8 +135,9 @@ impl LeaseReaper {
             crate::JobId,
             chrono::DateTime<Utc>,
         )> = Vec::new();
-        let mut events: Vec<JobEvent> = Vec::new();
+        let mut events: Vec<(String, String, JobEvent)> = Vec::new();
         let mut outcomes: Vec<ReapOutcome> = Vec::new();
+        let mut settled: Vec<(Option<crate::JobGroupId>, crate::JobId, JobStatus)> = Vec::new();
 
         {
             let mut jobs = self.backend.jobs.write().await;
@@ -159,24 +160,46 @@ impl LeaseReaper {
 
                 // Clear the lease.
                 record.lease_token = None;
-                record.set_error("Lease expired".to_string());
-                record.updated_at = now;
+                record.set_error("Lease expired".to_string(), now);
 
                 // The reaper does not hold the adapter's retry budget; it uses the
                 // same attempt > max_retries threshold the adapter uses (attempt is
                 // the count after the last dequeue, so this is a conservative check).
-                if record.attempt > record.message.max_retries {
+                // An expired lease is an ambiguous outcome, which at-most-once
+                // jobs never retry.
+                let failure = if record.message.delivery == DeliveryMode::AtMostOnce {
+                    Some("Lease expired — at-most-once job not retried")
+                } else if record.attempt > record.message.max_retries {
+                    Some("Max retries exceeded due to lease expiry")
+                } else {
+                    None
+                };
+                if let Some(error) = failure {
                     record.status = JobStatus::Failed {
                         failed_at: now,
-                        error: "Max retries exceeded due to lease expiry".to_string(),
+                        error: error.to_string(),
                     };
+                    if record.message.group_id.is_some() {
+                        settled.push((
+                            record.message.group_id.clone(),
+                            job_id.clone(),
+                            record.status.clone(),
+                        ));
+                    }
 
-                    events.push(JobEvent::Failed {
-                        job_id: job_id.clone(),
-                        tenant_id: record.tenant_id.clone(),
-                        error: "Max retries exceeded due to lease expiry".to_string(),
-                        at: now,
-                    });
+                    events.push((
+                        record.message.queue.clone(),
+                        record.message.job_type.clone(),
+                        JobEvent::Failed {
+                            job_id: job_id.clone(),
+                            tenant_id: record.tenant_id.clone(),
+                            queue: record.message.queue.clone(),
+                            attempt: record.attempt,
+                            duration_ms: None,
+                            error: error.to_string(),
+                            at: now,
+                        },
+                    ));
 
                     outcomes.push(ReapOutcome {
                         tenant_id: record.tenant_id.clone(),
@@ -200,13 +223,20 @@ impl LeaseReaper {
                         retry_at,
                     ));
 
-                    events.push(JobEvent::Retrying {
-                        job_id: job_id.clone(),
-                        tenant_id: record.tenant_id.clone(),
-                        retry_at,
-                        error: "Lease expired".to_string(),
-                        at: now,
-                    });
+                    events.push((
+                        record.message.queue.clone(),
+                        record.message.job_type.clone(),
+                        JobEvent::Retrying {
+                            job_id: job_id.clone(),
+                            tenant_id: record.tenant_id.clone(),
+                            queue: record.message.queue.clone(),
+                            attempt: record.attempt,
+                            duration_ms: None,
+                            retry_at,
+                            error: "Lease expired".to_string(),
+                            at: now,
+                        },
+                    ));
 
                     outcomes.push(ReapOutcome {
                         tenant_id: record.tenant_id.clone(),
@@ -232,8 +262,15 @@ impl LeaseReaper {
         } // queues write lock released
 
         // ── Phase 4: Broadcast events (outside any lock) ────────────────────────────
-        for event in events {
-            let _ = self.backend.event_broadcaster.send(event);
+        for (queue, job_type, event) in events {
+            self.backend.emit(&queue, &job_type, event);
+        }
+
+        // ── Phase 5: Count permanent failures towards their fan-out groups ──────────
+        for (group_id, job_id, status) in settled {
+            self.backend
+                .settle_group_member(group_id, &job_id, &status)
+                .await?;
         }
 
         Ok(outcomes)
@@ -250,8 +287,9 @@ impl MemoryBackend {
                 ref mut lease_until,
             } = record.status
             {
-                *lease_until = Utc::now() - chrono::Duration::seconds(1);
-                record.updated_at = Utc::now();
+                let now = self.clock.now();
+                *lease_until = now - chrono::Duration::seconds(1);
+                record.updated_at = now;
             }
         }
         Ok(())
@@ -264,10 +302,10 @@ impl MemoryBackend {
         Ok(())
     }
 
-    /// Advance time concept (test helper - for Memory backend, this is a no-op since we use real time)
+    /// Advance time concept (test helper — a no-op: the backend cannot move
+    /// its own clock). Install a [`TestClock`](crate::TestClock) with
+    /// `MemoryBackend::with_clock` and call `TestClock::set` instead.
     pub async fn advance_time_to(&self, _target_time: chrono::DateTime<Utc>) -> QueueResult<()> {
-        // For memory backend, we can't actually advance time
-        // Tests should use force_lease_expiry or similar helpers
         Ok(())
     }
 }
@@ -276,7 +314,7 @@ impl MemoryBackend {
 mod tests {
     use super::*;
     use crate::backend::QueueBackend;
-    use crate::{JobMessage, JobPriority, QueueCtx};
+    use crate::{Clock, DeliveryMode, JobMessage, JobPriority, QueueCtx};
 
     fn create_test_context() -> QueueCtx {
         QueueCtx::new("test_tenant")
@@ -292,6 +330,10 @@ mod tests {
             max_retries: 3,
             run_at: chrono::Utc::now(),
             idempotency_key: None,
+            idempotency_expires_at: None,
+            traceparent: None,
+            group_id: None,
+            delivery: crate::DeliveryMode::default(),
         }
     }
 
@@ -363,6 +405,69 @@ mod tests {
         assert!(matches!(status, JobStatus::Failed { .. }));
     }
 
+    /// A lost lease is retried for at-least-once jobs but fails at-most-once
+    /// jobs outright, even with retries left.
+    #[tokio::test]
+    async fn test_lost_lease_depends_on_delivery_mode() {
+        let backend = Arc::new(MemoryBackend::new());
+        let ctx = create_test_context();
+
+        let lease_then_lose = |delivery: DeliveryMode| {
+            let backend = backend.clone();
+            let ctx = ctx.clone();
+            async move {
+                let message = create_test_job_message().with_delivery(delivery);
+                let job_id = backend.enqueue(ctx.clone(), message).await.unwrap();
+                let leased = backend
+                    .dequeue(ctx.clone(), &["default"])
+                    .await
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(leased.record.job_id, job_id);
+                backend.force_lease_expiry(job_id.clone()).await.unwrap();
+                (job_id, leased.lease_token)
+            }
+        };
+        let (at_least_once, _) = lease_then_lose(DeliveryMode::AtLeastOnce).await;
+        let (at_most_once, stale_token) = lease_then_lose(DeliveryMode::AtMostOnce).await;
+
+        let reaper = LeaseReaper::new(backend.clone()).with_backoff(Duration::ZERO);
+        let reclaimed = reaper.reap_expired_leases().await.unwrap();
+        assert_eq!(reclaimed.len(), 2);
+
+        let retried = backend
+            .get_record(ctx.clone(), at_least_once.clone())
+            .await
+            .unwrap();
+        assert!(matches!(retried.status, JobStatus::Retrying { .. }));
+        let failed = backend
+            .get_record(ctx.clone(), at_most_once.clone())
+            .await
+            .unwrap();
+        assert!(matches!(
+            failed.status,
+            JobStatus::Failed { ref error, .. } if error.contains("at-most-once")
+        ));
+
+        // Only the at-least-once job is handed out again, and the worker that
+        // lost the at-most-once lease can no longer report it as done.
+        let again = backend
+            .dequeue(ctx.clone(), &["default"])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(again.record.job_id, at_least_once);
+        assert!(backend
+            .dequeue(ctx.clone(), &["default"])
+            .await
+            .unwrap()
+            .is_none());
+        assert!(backend
+            .ack_complete(ctx, at_most_once, stale_token, None)
+            .await
+            .is_err());
+    }
+
     /// Verify the TOCTOU guard: if a worker acks the job between the reaper's
     /// collection phase and its write phase, the reaper must NOT overwrite the
     /// terminal record.
@@ -393,7 +498,7 @@ mod tests {
         {
             let mut jobs = backend.jobs.write().await;
             if let Some(record) = jobs.get_mut(&job_id) {
-                record.complete();
+                record.complete(Utc::now());
             }
         }
 
@@ -414,4 +519,36 @@ mod tests {
             "completed job must not be overwritten by reaper"
         );
     }
+
+    #[tokio::test]
+    async fn test_lease_expires_when_test_clock_passes_lease_until() {
+        let clock = crate::TestClock::starting_now();
+        let backend = Arc::new(
+            MemoryBackend::new()
+                .with_lease_duration(Duration::from_secs(300))
+                .with_clock(clock.clone()),
+        );
+        let ctx = create_test_context();
+
+        // run_at must come from the frozen clock, otherwise the job is
+        // "in the future" and never becomes eligible.
+        let mut message = create_test_job_message();
+        message.run_at = clock.now();
+        let job_id = backend.enqueue(ctx.clone(), message).await.unwrap();
+        backend
+            .dequeue(ctx.clone(), &["default"])
+            .await
+            .unwrap()
+            .unwrap();
+
+        let reaper = LeaseReaper::new(backend.clone()).with_backoff(Duration::from_secs(0));
+        clock.advance(Duration::from_secs(299));
+        assert!(reaper.reap_expired_leases().await.unwrap().is_empty());
+
+        clock.advance(Duration::from_secs(2));
+        let reclaimed = reaper.reap_expired_leases().await.unwrap();
+        assert_eq!(reclaimed.len(), 1);
+        assert_eq!(reclaimed[0].job_id, job_id);
+        assert!(backend.dequeue(ctx, &["default"]).await.unwrap().is_some());
+    }
 }
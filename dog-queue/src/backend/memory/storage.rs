@@ -1,13 +1,17 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use tokio::sync::broadcast;
 use tokio::sync::RwLock;
 
 use crate::{
-    backend::{BoxStream, QueueBackend},
-    types::LeaseToken,
+    backend::{BoxStream, QueueBackend, QueueStats, QueueSummary},
+    clock::{SharedClock, SystemClock},
+    types::{
+        CursoredEvent, EventCursor, EventFilter, JobGroup, JobGroupId, JobProgress, JobTypeFilter,
+        LeaseToken,
+    },
     JobEvent, JobId, JobMessage, JobRecord, JobStatus, LeasedJob, QueueCapabilities, QueueCtx,
     QueueError, QueueResult,
 };
@@ -19,7 +23,19 @@ use crate::{
 // This eliminates all nested-lock cross-reads between `queues` and `jobs`.
 type QueueEntry = (crate::JobPriority, DateTime<Utc>, JobId);
 type TenantQueues = HashMap<String, HashMap<String, VecDeque<QueueEntry>>>;
-type IdempotencyMap = HashMap<(String, String, String, String), JobId>;
+/// (tenant_id, queue, job_type, key) -> (job_id, expires_at)
+type IdempotencyMap = HashMap<(String, String, String, String), (JobId, Option<DateTime<Utc>>)>;
+
+/// A fan-out group plus the state needed to fire its aggregation job once.
+pub(crate) struct GroupEntry {
+    tenant_id: String,
+    group: JobGroup,
+    /// Taken by whichever settle finishes the group, so the aggregation job is
+    /// enqueued exactly once even when the last children settle concurrently.
+    on_complete: Option<JobMessage>,
+    /// Children already counted — a requeued child settling again is ignored.
+    settled: HashSet<JobId>,
+}
 
 // ---------------------------------------------------------------------------
 // Priority-ordered insertion helper
@@ -38,6 +54,61 @@ pub(super) fn priority_insert(queue: &mut VecDeque<QueueEntry>, entry: QueueEntr
     queue.insert(pos, entry);
 }
 
+// ---------------------------------------------------------------------------
+// Bounded event log (cursor replay)
+// ---------------------------------------------------------------------------
+
+/// Default number of events retained for `replay_from`. Matches the broadcast
+/// channel capacity, so a consumer that lags the live stream can still replay.
+const DEFAULT_EVENT_LOG_CAPACITY: usize = 1000;
+
+/// An event as stored in the log and sent on the broadcast channel.
+///
+/// `seq` is the cursor (monotonic, starting at 1, so `"0"` means "before the
+/// first event"). `queue` and `job_type` are captured at emission so filters
+/// never need to look the job record up again.
+#[derive(Debug, Clone)]
+pub(crate) struct LoggedEvent {
+    seq: u64,
+    queue: String,
+    job_type: String,
+    event: JobEvent,
+}
+
+impl LoggedEvent {
+    fn into_cursored(self) -> CursoredEvent {
+        CursoredEvent {
+            cursor: EventCursor::new(self.seq.to_string()),
+            queue: self.queue,
+            job_type: self.job_type,
+            event: self.event,
+        }
+    }
+}
+
+/// Ring buffer of the most recent events.
+pub(crate) struct EventLog {
+    next_seq: u64,
+    capacity: usize,
+    entries: VecDeque<LoggedEvent>,
+}
+
+impl EventLog {
+    fn new(capacity: usize) -> Self {
+        Self {
+            next_seq: 1,
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Sequence number of the oldest event still retained (or `next_seq` when
+    /// the log is empty).
+    fn first_retained(&self) -> u64 {
+        self.next_seq - self.entries.len() as u64
+    }
+}
+
 /// In-memory backend for testing and development
 pub struct MemoryBackend {
     /// Job records indexed by job_id
@@ -50,11 +121,26 @@ pub struct MemoryBackend {
     pub(crate) idempotency: Arc<RwLock<IdempotencyMap>>,
 
     /// Event broadcaster for observability
-    pub(crate) event_broadcaster: broadcast::Sender<JobEvent>,
+    pub(crate) event_broadcaster: broadcast::Sender<LoggedEvent>,
+
+    /// Bounded log of recent events backing `replay_from`.
+    ///
+    /// A synchronous mutex: it is only held for the append + broadcast send in
+    /// `emit` and the subscribe + snapshot in `replay_from`, never across an
+    /// `.await`. Doing both under the same lock is what makes replay gap-free —
+    /// an event is either in the snapshot or delivered to the new receiver.
+    pub(crate) event_log: Arc<parking_lot::Mutex<EventLog>>,
 
     /// How long a dequeued lease is valid. Defaults to 5 minutes.
     /// Set via `MemoryBackend::with_lease_duration`.
     pub(crate) lease_duration: chrono::Duration,
+
+    /// Time source for eligibility, lease, and timestamp decisions.
+    /// `SystemClock` by default; set via `MemoryBackend::with_clock`.
+    pub(crate) clock: SharedClock,
+
+    /// Fan-out groups by ID. A leaf lock: never held while acquiring another.
+    pub(crate) groups: Arc<RwLock<HashMap<JobGroupId, GroupEntry>>>,
 }
 
 impl MemoryBackend {
@@ -66,7 +152,12 @@ impl MemoryBackend {
             queues: Arc::new(RwLock::new(HashMap::new())),
             idempotency: Arc::new(RwLock::new(HashMap::new())),
             event_broadcaster,
+            event_log: Arc::new(parking_lot::Mutex::new(EventLog::new(
+                DEFAULT_EVENT_LOG_CAPACITY,
+            ))),
             lease_duration: chrono::Duration::seconds(300), // 5-minute default
+            clock: Arc::new(SystemClock),
+            groups: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -77,6 +168,111 @@ impl MemoryBackend {
             .expect("lease_duration is out of chrono::Duration range");
         self
     }
+
+    /// Read time from `clock` instead of the system clock.
+    ///
+    /// Pass a [`TestClock`](crate::TestClock) to drive delayed-job
+    /// eligibility and lease expiry deterministically in tests.
+    pub fn with_clock(mut self, clock: impl crate::Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Override how many recent events are retained for `replay_from`
+    /// (default 1000). Older events are evicted; replaying from a cursor that
+    /// precedes the retained window returns `QueueError::EventCursorExpired`.
+    pub fn with_event_log_capacity(self, capacity: usize) -> Self {
+        *self.event_log.lock() = EventLog::new(capacity.max(1));
+        self
+    }
+
+    /// Count `job_id`'s terminal `status` towards its fan-out group, and
+    /// enqueue the group's aggregation job if this was the last child.
+    ///
+    /// Must be called after the terminal transition is committed and with no
+    /// `jobs`/`queues` lock held, since finishing the group enqueues a job.
+    pub(crate) async fn settle_group_member(
+        &self,
+        group_id: Option<JobGroupId>,
+        job_id: &JobId,
+        status: &JobStatus,
+    ) -> QueueResult<()> {
+        let Some(group_id) = group_id else {
+            return Ok(());
+        };
+
+        let (tenant_id, mut on_complete) = {
+            let mut groups = self.groups.write().await;
+            let Some(entry) = groups.get_mut(&group_id) else {
+                return Ok(());
+            };
+            if !entry.settled.insert(job_id.clone()) {
+                return Ok(());
+            }
+            match status {
+                JobStatus::Completed { .. } => entry.group.completed += 1,
+                JobStatus::Failed { .. } => entry.group.failed += 1,
+                JobStatus::Canceled { .. } => entry.group.canceled += 1,
+                _ => return Ok(()),
+            }
+            if !entry.group.is_finished() {
+                return Ok(());
+            }
+            match entry.on_complete.take() {
+                Some(message) => (entry.tenant_id.clone(), message),
+                None => return Ok(()),
+            }
+        }; // groups lock released before enqueue
+
+        self.fire_group_aggregate(&tenant_id, &group_id, &mut on_complete)
+            .await
+    }
+
+    /// Enqueue a finished group's aggregation job and record its ID.
+    async fn fire_group_aggregate(
+        &self,
+        tenant_id: &str,
+        group_id: &JobGroupId,
+        on_complete: &mut JobMessage,
+    ) -> QueueResult<()> {
+        on_complete.run_at = self.clock.now();
+        let aggregate_id = self
+            .enqueue(QueueCtx::new(tenant_id), on_complete.clone())
+            .await?;
+        if let Some(entry) = self.groups.write().await.get_mut(group_id) {
+            entry.group.aggregate_job_id = Some(aggregate_id);
+        }
+        Ok(())
+    }
+
+    /// Drop idempotency keys whose TTL has passed, returning how many went.
+    ///
+    /// Expired keys already stop deduplicating at enqueue time; the sweep only
+    /// bounds the map's memory. Runs with every `reclaim_expired_leases` cycle.
+    pub(crate) async fn sweep_expired_idempotency_keys(&self) -> usize {
+        let now = self.clock.now();
+        let mut idempotency = self.idempotency.write().await;
+        let before = idempotency.len();
+        idempotency.retain(|_, (_, expires_at)| expires_at.is_none_or(|t| t > now));
+        before - idempotency.len()
+    }
+
+    /// Append `event` to the event log and broadcast it to live subscribers.
+    pub(crate) fn emit(&self, queue: &str, job_type: &str, event: JobEvent) {
+        let mut log = self.event_log.lock();
+        let entry = LoggedEvent {
+            seq: log.next_seq,
+            queue: queue.to_string(),
+            job_type: job_type.to_string(),
+            event,
+        };
+        log.next_seq += 1;
+        if log.entries.len() == log.capacity {
+            log.entries.pop_front();
+        }
+        log.entries.push_back(entry.clone());
+        let _ = self.event_broadcaster.send(entry);
+    }
 }
 
 #[async_trait]
@@ -107,7 +303,15 @@ impl QueueBackend for MemoryBackend {
         };
 
         if let Some(ref scope) = idempotency_scope {
-            if let Some(existing_id) = optional_guard.as_mut().unwrap().get(scope).cloned() {
+            let now = self.clock.now();
+            let live = optional_guard
+                .as_mut()
+                .unwrap()
+                .get(scope)
+                .filter(|(_, expires_at)| expires_at.is_none_or(|t| t > now))
+                .map(|(id, _)| id.clone());
+            // An expired key is treated as absent and overwritten below.
+            if let Some(existing_id) = live {
                 // Check terminal status under jobs.read().
                 // Holding idempotency.write() while acquiring jobs.read() is safe
                 // because no other code path holds jobs.write() and then tries to
@@ -126,10 +330,11 @@ impl QueueBackend for MemoryBackend {
         }
 
         let job_id = JobId::new();
-        let now = Utc::now();
+        let now = self.clock.now();
 
         // Create and store the job record.
-        let record = JobRecord::new(job_id.clone(), &ctx.tenant_id, message.clone());
+        let record = JobRecord::new(job_id.clone(), &ctx.tenant_id, message.clone(), now)
+            .with_metadata(ctx.metadata.clone());
         self.jobs.write().await.insert(job_id.clone(), record);
 
         // Insert into the priority-ordered queue.
@@ -139,12 +344,18 @@ impl QueueBackend for MemoryBackend {
         priority_insert(queue, (message.priority, message.run_at, job_id.clone()));
         drop(queues);
 
+        if let Some(group_id) = &message.group_id {
+            if let Some(entry) = self.groups.write().await.get_mut(group_id) {
+                entry.group.children.push(job_id.clone());
+            }
+        }
+
         // Register/update the idempotency entry (still under the write lock — no race).
         if let Some(scope) = idempotency_scope {
             optional_guard
                 .as_mut()
                 .unwrap()
-                .insert(scope, job_id.clone());
+                .insert(scope, (job_id.clone(), message.idempotency_expires_at));
         }
 
         // Emit enqueue event after all locks are released.
@@ -153,15 +364,26 @@ impl QueueBackend for MemoryBackend {
             tenant_id: ctx.tenant_id.clone(),
             queue: message.queue.clone(),
             job_type: message.job_type.clone(),
+            metadata: ctx.metadata,
             at: now,
         };
-        let _ = self.event_broadcaster.send(event);
+        self.emit(&message.queue, &message.job_type, event);
 
         Ok(job_id)
     }
 
     async fn dequeue(&self, ctx: QueueCtx, queues: &[&str]) -> QueueResult<Option<LeasedJob>> {
-        let now = Utc::now();
+        self.dequeue_filtered(ctx, queues, &JobTypeFilter::All)
+            .await
+    }
+
+    async fn dequeue_filtered(
+        &self,
+        ctx: QueueCtx,
+        queues: &[&str],
+        job_types: &JobTypeFilter,
+    ) -> QueueResult<Option<LeasedJob>> {
+        let now = self.clock.now();
 
         // ── Fast-path: Advisory Read Lock ───────────────────────────────────────
         // Advisory only: another worker may remove the candidate before we acquire
@@ -195,14 +417,32 @@ impl QueueBackend for MemoryBackend {
             // high-priority entries at the head are scanned every poll.
             // For in-memory use at small scale this is acceptable; a split
             // ready-queue / future-heap structure would make this O(1).
+            //
+            // Queue entries don't carry the job type, so a restricted worker
+            // also reads `jobs` to skip types it may not take. The read lock is
+            // taken before `queues`, the same order as enqueue and import.
             let candidate = {
+                let jobs_read = if job_types.is_all() {
+                    None
+                } else {
+                    Some(self.jobs.read().await)
+                };
                 let mut queues_lock = self.queues.write().await;
                 let mut candidate_id = None;
 
                 if let Some(tq) = queues_lock.get_mut(&ctx.tenant_id) {
                     let mut should_remove_queue = false;
                     if let Some(queue) = tq.get_mut(*queue_name) {
-                        let pos = queue.iter().position(|(_, run_at, _)| *run_at <= now);
+                        let pos = queue.iter().position(|(_, run_at, job_id)| {
+                            *run_at <= now
+                                && jobs_read.as_ref().is_none_or(|jobs| {
+                                    // Missing records fall through to the
+                                    // tombstone cleanup in phase 2.
+                                    jobs.get(job_id).is_none_or(|record| {
+                                        job_types.permits(&record.message.job_type)
+                                    })
+                                })
+                        });
                         if let Some(i) = pos {
                             let (_, _, job_id) = queue.remove(i).unwrap();
                             candidate_id = Some(job_id);
@@ -234,15 +474,17 @@ impl QueueBackend for MemoryBackend {
                             let lease_until = now + self.lease_duration;
 
                             record.attempt += 1;
-                            record.start_processing(lease_token.clone(), lease_until);
+                            record.start_processing(lease_token.clone(), lease_until, now);
 
                             let event = JobEvent::Leased {
                                 job_id: job_id.clone(),
                                 tenant_id: record.tenant_id.clone(),
+                                queue: record.message.queue.clone(),
+                                attempt: record.attempt,
                                 lease_until,
                                 at: now,
                             };
-                            let _ = self.event_broadcaster.send(event);
+                            self.emit(&record.message.queue, &record.message.job_type, event);
 
                             return Ok(Some(LeasedJob {
                                 record: record.clone(),
@@ -270,7 +512,7 @@ impl QueueBackend for MemoryBackend {
         lease_token: LeaseToken,
         result_ref: Option<String>,
     ) -> QueueResult<()> {
-        let now = Utc::now();
+        let now = self.clock.now();
         let mut jobs = self.jobs.write().await;
 
         let record = jobs
@@ -310,17 +552,22 @@ impl QueueBackend for MemoryBackend {
         record.result = result_ref;
 
         // Update to completed
-        record.complete();
+        record.complete(now);
 
         // Emit event
         let event = JobEvent::Completed {
             job_id: job_id.clone(),
             tenant_id: ctx.tenant_id.clone(),
+            queue: record.message.queue.clone(),
+            attempt: record.attempt,
+            duration_ms: record.attempt_duration_ms(now),
             at: now,
         };
-        let _ = self.event_broadcaster.send(event);
+        self.emit(&record.message.queue, &record.message.job_type, event);
 
-        Ok(())
+        let (group_id, status) = (record.message.group_id.clone(), record.status.clone());
+        drop(jobs);
+        self.settle_group_member(group_id, &job_id, &status).await
     }
 
     async fn ack_fail(
@@ -331,7 +578,7 @@ impl QueueBackend for MemoryBackend {
         error: String,
         retry_at: Option<DateTime<Utc>>,
     ) -> QueueResult<()> {
-        let now = Utc::now();
+        let now = self.clock.now();
         let mut jobs = self.jobs.write().await;
 
         let record = jobs
@@ -378,8 +625,8 @@ impl QueueBackend for MemoryBackend {
             // Use priority_insert (not push_back) so the retrying job is placed
             // at the correct position — push_back would cause priority inversion,
             // processing Critical retries after newly-enqueued Low jobs.
-            record.schedule_retry(retry_time);
-            record.set_error(error.clone());
+            record.schedule_retry(retry_time, now);
+            record.set_error(error.clone(), now);
 
             let mut queues = self.queues.write().await;
             let priority = record.message.priority;
@@ -391,27 +638,93 @@ impl QueueBackend for MemoryBackend {
             let event = JobEvent::Retrying {
                 job_id: job_id.clone(),
                 tenant_id: ctx.tenant_id.clone(),
+                queue: record.message.queue.clone(),
+                attempt: record.attempt,
+                duration_ms: record.attempt_duration_ms(now),
                 retry_at: retry_time,
                 error: error.clone(),
                 at: now,
             };
-            let _ = self.event_broadcaster.send(event);
+            self.emit(&record.message.queue, &record.message.job_type, event);
         } else {
             // Permanent failure: record as-is with the verbatim error.
-            record.fail(error.clone());
+            record.fail(error.clone(), now);
 
             let event = JobEvent::Failed {
                 job_id: job_id.clone(),
                 tenant_id: ctx.tenant_id.clone(),
+                queue: record.message.queue.clone(),
+                attempt: record.attempt,
+                duration_ms: record.attempt_duration_ms(now),
                 error,
                 at: now,
             };
-            let _ = self.event_broadcaster.send(event);
+            self.emit(&record.message.queue, &record.message.job_type, event);
+
+            let (group_id, status) = (record.message.group_id.clone(), record.status.clone());
+            drop(jobs);
+            return self.settle_group_member(group_id, &job_id, &status).await;
         }
 
         Ok(())
     }
 
+    async fn report_progress(
+        &self,
+        ctx: QueueCtx,
+        job_id: JobId,
+        lease_token: LeaseToken,
+        percent: u8,
+        message: String,
+    ) -> QueueResult<()> {
+        let now = self.clock.now();
+        let mut jobs = self.jobs.write().await;
+
+        let record = jobs
+            .get_mut(&job_id)
+            .ok_or_else(|| QueueError::JobNotFound(job_id.clone()))?;
+
+        // Verify tenant access
+        if record.tenant_id != ctx.tenant_id {
+            return Err(QueueError::JobNotFound(job_id.clone()));
+        }
+
+        if matches!(record.status, JobStatus::Canceled { .. }) {
+            return Err(QueueError::JobCanceled);
+        }
+
+        // Only the current attempt may report; the lease token is cleared
+        // whenever the job leaves Processing.
+        if record.lease_token.as_ref() != Some(&lease_token) {
+            return Err(QueueError::InvalidLeaseToken {
+                job_id: job_id.clone(),
+            });
+        }
+
+        let percent = percent.min(100);
+        record.progress = Some(JobProgress {
+            percent,
+            message: message.clone(),
+            at: now,
+        });
+        record.updated_at = now;
+        let queue_name = record.message.queue.clone();
+        let job_type = record.message.job_type.clone();
+        drop(jobs);
+
+        let event = JobEvent::Progress {
+            job_id,
+            tenant_id: ctx.tenant_id,
+            queue: queue_name.clone(),
+            percent,
+            message,
+            at: now,
+        };
+        self.emit(&queue_name, &job_type, event);
+
+        Ok(())
+    }
+
     async fn heartbeat_extend(
         &self,
         ctx: QueueCtx,
@@ -419,7 +732,7 @@ impl QueueBackend for MemoryBackend {
         lease_token: LeaseToken,
         extra_time: std::time::Duration,
     ) -> QueueResult<()> {
-        let now = Utc::now();
+        let now = self.clock.now();
         let mut jobs = self.jobs.write().await;
 
         let record = jobs
@@ -470,22 +783,83 @@ impl QueueBackend for MemoryBackend {
 
         // Capture the new deadline before releasing the write lock.
         let new_lease_until = record.lease_until().unwrap_or(now);
+        let queue_name = record.message.queue.clone();
+        let job_type = record.message.job_type.clone();
         drop(jobs);
 
         // Emit event outside the lock so subscribers don't block mutations.
         let event = JobEvent::HeartbeatExtended {
             job_id: job_id.clone(),
             tenant_id: ctx.tenant_id.clone(),
+            queue: queue_name.clone(),
             new_lease_until,
             at: now,
         };
-        let _ = self.event_broadcaster.send(event);
+        self.emit(&queue_name, &job_type, event);
+
+        Ok(())
+    }
+
+    async fn release_lease(
+        &self,
+        ctx: QueueCtx,
+        job_id: JobId,
+        lease_token: LeaseToken,
+    ) -> QueueResult<()> {
+        let now = self.clock.now();
+        let mut jobs = self.jobs.write().await;
+
+        let record = jobs
+            .get_mut(&job_id)
+            .ok_or_else(|| QueueError::JobNotFound(job_id.clone()))?;
+
+        // Verify tenant access
+        if record.tenant_id != ctx.tenant_id {
+            return Err(QueueError::JobNotFound(job_id.clone()));
+        }
+
+        // Same guard order as ack_complete: cancel-wins, then terminal, then token.
+        match &record.status {
+            JobStatus::Canceled { .. } => return Err(QueueError::JobCanceled),
+            JobStatus::Completed { .. } | JobStatus::Failed { .. } => {
+                return Err(QueueError::JobAlreadyTerminal);
+            }
+            _ => {}
+        }
+
+        if record.lease_token.as_ref() != Some(&lease_token) {
+            return Err(QueueError::InvalidLeaseToken {
+                job_id: job_id.clone(),
+            });
+        }
+
+        record.release(now);
+        let priority = record.message.priority;
+        let queue_name = record.message.queue.clone();
+        let job_type = record.message.job_type.clone();
+        drop(jobs);
+
+        // Re-insert as immediately eligible. priority_insert keeps the released
+        // job ahead of lower-priority work, same as ack_fail's retry path.
+        let mut queues = self.queues.write().await;
+        let tenant_queues = queues.entry(ctx.tenant_id.clone()).or_default();
+        let queue = tenant_queues.entry(queue_name.clone()).or_default();
+        priority_insert(queue, (priority, now, job_id.clone()));
+        drop(queues);
+
+        let event = JobEvent::Released {
+            job_id,
+            tenant_id: ctx.tenant_id,
+            queue: queue_name.clone(),
+            at: now,
+        };
+        self.emit(&queue_name, &job_type, event);
 
         Ok(())
     }
 
     async fn cancel(&self, ctx: QueueCtx, job_id: JobId) -> QueueResult<bool> {
-        let now = Utc::now();
+        let now = self.clock.now();
         let mut jobs = self.jobs.write().await;
 
         let record = jobs
@@ -509,15 +883,20 @@ impl QueueBackend for MemoryBackend {
         // fail(), and schedule_retry() used by the other ack methods. This ensures
         // all cancellation-side effects (status, lease_token, updated_at) stay in
         // sync with any future additions to JobRecord::cancel().
-        record.cancel();
+        record.cancel(now);
 
         // Emit event
         let event = JobEvent::Canceled {
             job_id: job_id.clone(),
             tenant_id: ctx.tenant_id.clone(),
+            queue: record.message.queue.clone(),
             at: now,
         };
-        let _ = self.event_broadcaster.send(event);
+        self.emit(&record.message.queue, &record.message.job_type, event);
+
+        let (group_id, status) = (record.message.group_id.clone(), record.status.clone());
+        drop(jobs);
+        self.settle_group_member(group_id, &job_id, &status).await?;
 
         Ok(true)
     }
@@ -550,6 +929,268 @@ impl QueueBackend for MemoryBackend {
         Ok(record.clone())
     }
 
+    async fn list_queues(&self, ctx: QueueCtx) -> QueueResult<Vec<QueueSummary>> {
+        // Queues that drained to empty are removed from `queues` by dequeue, so
+        // also report every queue the tenant has records in (depth 0).
+        let mut depths: HashMap<String, usize> = self
+            .jobs
+            .read()
+            .await
+            .values()
+            .filter(|r| r.tenant_id == ctx.tenant_id)
+            .map(|r| (r.message.queue.clone(), 0))
+            .collect();
+        if let Some(tenant_queues) = self.queues.read().await.get(&ctx.tenant_id) {
+            for (name, entries) in tenant_queues {
+                depths.insert(name.clone(), entries.len());
+            }
+        }
+
+        let mut summaries: Vec<QueueSummary> = depths
+            .into_iter()
+            .map(|(queue, depth)| QueueSummary { queue, depth })
+            .collect();
+        summaries.sort_by(|a, b| a.queue.cmp(&b.queue));
+        Ok(summaries)
+    }
+
+    async fn create_group(
+        &self,
+        ctx: QueueCtx,
+        group_id: JobGroupId,
+        total: usize,
+        mut on_complete: JobMessage,
+    ) -> QueueResult<()> {
+        let group = JobGroup::new(group_id.clone(), total, self.clock.now());
+        let entry = GroupEntry {
+            tenant_id: ctx.tenant_id.clone(),
+            group,
+            on_complete: (total > 0).then(|| on_complete.clone()),
+            settled: HashSet::new(),
+        };
+        self.groups.write().await.insert(group_id.clone(), entry);
+
+        // Nothing to wait for: the aggregation runs straight away.
+        if total == 0 {
+            self.fire_group_aggregate(&ctx.tenant_id, &group_id, &mut on_complete)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn get_group(&self, ctx: QueueCtx, group_id: JobGroupId) -> QueueResult<JobGroup> {
+        let groups = self.groups.read().await;
+        match groups.get(&group_id) {
+            Some(entry) if entry.tenant_id == ctx.tenant_id => Ok(entry.group.clone()),
+            _ => Err(QueueError::JobGroupNotFound(group_id)),
+        }
+    }
+
+    async fn queue_stats(&self, ctx: QueueCtx, queue: &str) -> QueueResult<QueueStats> {
+        let now = self.clock.now();
+        let mut stats = QueueStats {
+            queue: queue.to_string(),
+            depth: 0,
+            oldest_run_at: None,
+            leased_count: 0,
+            scheduled_count: 0,
+        };
+
+        // Derived from record status rather than the queue entries, which can
+        // still hold tombstones for canceled jobs until dequeue skips them.
+        let jobs = self.jobs.read().await;
+        for record in jobs
+            .values()
+            .filter(|r| r.tenant_id == ctx.tenant_id && r.message.queue == queue)
+        {
+            let due_at = match &record.status {
+                JobStatus::Enqueued => record.message.run_at,
+                JobStatus::Retrying { retry_at } => *retry_at,
+                JobStatus::Processing { .. } => {
+                    stats.leased_count += 1;
+                    continue;
+                }
+                _ => continue,
+            };
+            if due_at <= now {
+                stats.depth += 1;
+                stats.oldest_run_at = Some(stats.oldest_run_at.map_or(due_at, |t| t.min(due_at)));
+            } else {
+                stats.scheduled_count += 1;
+            }
+        }
+
+        Ok(stats)
+    }
+
+    async fn recent_records(&self, ctx: QueueCtx, limit: usize) -> QueueResult<Vec<JobRecord>> {
+        let jobs = self.jobs.read().await;
+        let mut records: Vec<JobRecord> = jobs
+            .values()
+            .filter(|r| r.tenant_id == ctx.tenant_id)
+            .cloned()
+            .collect();
+        drop(jobs);
+
+        records.sort_by_key(|r| std::cmp::Reverse(r.updated_at));
+        records.truncate(limit);
+        Ok(records)
+    }
+
+    async fn export_records(&self, ctx: QueueCtx) -> QueueResult<Vec<JobRecord>> {
+        // Lock ordering: jobs → queues, as in enqueue.
+        let jobs = self.jobs.read().await;
+        let queues = self.queues.read().await;
+
+        let mut exported = HashSet::new();
+        let mut records = Vec::new();
+        if let Some(tenant_queues) = queues.get(&ctx.tenant_id) {
+            let mut names: Vec<&String> = tenant_queues.keys().collect();
+            names.sort();
+            for name in names {
+                for (_, _, job_id) in &tenant_queues[name] {
+                    // Skip tombstones left behind by cancel.
+                    match jobs.get(job_id) {
+                        Some(record)
+                            if matches!(
+                                record.status,
+                                JobStatus::Enqueued | JobStatus::Retrying { .. }
+                            ) && exported.insert(job_id.clone()) =>
+                        {
+                            records.push(record.clone());
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        records.extend(
+            jobs.values()
+                .filter(|r| r.tenant_id == ctx.tenant_id && !exported.contains(&r.job_id))
+                .cloned(),
+        );
+        Ok(records)
+    }
+
+    async fn import_record(&self, ctx: QueueCtx, record: JobRecord) -> QueueResult<bool> {
+        let due_at = match &record.status {
+            JobStatus::Enqueued => record.message.run_at,
+            JobStatus::Retrying { retry_at } => *retry_at,
+            other => {
+                return Err(QueueError::InvalidConfig(format!(
+                    "import_record: only waiting jobs can be imported, job {} is {}",
+                    record.job_id,
+                    other.name()
+                )))
+            }
+        };
+
+        // Lock ordering: idempotency → jobs → queues, as in enqueue.
+        let mut idempotency = self.idempotency.write().await;
+        let mut jobs = self.jobs.write().await;
+        if jobs.contains_key(&record.job_id) {
+            return Ok(false);
+        }
+        let scope = record.message.idempotency_key.as_ref().map(|key| {
+            (
+                ctx.tenant_id.clone(),
+                record.message.queue.clone(),
+                record.message.job_type.clone(),
+                key.clone(),
+            )
+        });
+        let now = self.clock.now();
+        if let Some((existing, expires_at)) = scope.as_ref().and_then(|s| idempotency.get(s)) {
+            if expires_at.is_none_or(|t| t > now)
+                && jobs.get(existing).is_some_and(|r| !r.status.is_terminal())
+            {
+                return Ok(false);
+            }
+        }
+        let expires_at = record.message.idempotency_expires_at;
+
+        let job_id = record.job_id.clone();
+        let (queue_name, job_type, metadata) = (
+            record.message.queue.clone(),
+            record.message.job_type.clone(),
+            record.metadata.clone(),
+        );
+        let priority = record.message.priority;
+        let record = JobRecord {
+            tenant_id: ctx.tenant_id.clone(),
+            lease_token: None,
+            ..record
+        };
+        jobs.insert(job_id.clone(), record);
+
+        let mut queues = self.queues.write().await;
+        let tenant_queues = queues.entry(ctx.tenant_id.clone()).or_default();
+        let queue = tenant_queues.entry(queue_name.clone()).or_default();
+        priority_insert(queue, (priority, due_at, job_id.clone()));
+        drop(queues);
+        drop(jobs);
+
+        if let Some(scope) = scope {
+            idempotency.insert(scope, (job_id.clone(), expires_at));
+        }
+        drop(idempotency);
+
+        let event = JobEvent::Enqueued {
+            job_id,
+            tenant_id: ctx.tenant_id,
+            queue: queue_name.clone(),
+            job_type: job_type.clone(),
+            metadata,
+            at: now,
+        };
+        self.emit(&queue_name, &job_type, event);
+
+        Ok(true)
+    }
+
+    async fn requeue_failed(&self, ctx: QueueCtx, job_id: JobId) -> QueueResult<bool> {
+        let now = self.clock.now();
+        let mut jobs = self.jobs.write().await;
+
+        let record = jobs
+            .get_mut(&job_id)
+            .ok_or_else(|| QueueError::JobNotFound(job_id.clone()))?;
+
+        // Verify tenant access
+        if record.tenant_id != ctx.tenant_id {
+            return Err(QueueError::JobNotFound(job_id.clone()));
+        }
+
+        if !matches!(record.status, JobStatus::Failed { .. }) {
+            return Ok(false);
+        }
+
+        record.requeue(now);
+        let priority = record.message.priority;
+        let queue_name = record.message.queue.clone();
+        let job_type = record.message.job_type.clone();
+        let metadata = record.metadata.clone();
+        drop(jobs);
+
+        let mut queues = self.queues.write().await;
+        let tenant_queues = queues.entry(ctx.tenant_id.clone()).or_default();
+        let queue = tenant_queues.entry(queue_name.clone()).or_default();
+        priority_insert(queue, (priority, now, job_id.clone()));
+        drop(queues);
+
+        let event = JobEvent::Enqueued {
+            job_id,
+            tenant_id: ctx.tenant_id,
+            queue: queue_name.clone(),
+            job_type: job_type.clone(),
+            metadata,
+            at: now,
+        };
+        self.emit(&queue_name, &job_type, event);
+
+        Ok(true)
+    }
+
     fn event_stream(&self, ctx: QueueCtx) -> BoxStream<JobEvent> {
         let receiver = self.event_broadcaster.subscribe();
         use tokio_stream::{wrappers::BroadcastStream, StreamExt};
@@ -558,10 +1199,75 @@ impl QueueBackend for MemoryBackend {
         // JobEvent::tenant_id() returns the originating tenant for every variant.
         let stream = BroadcastStream::new(receiver)
             .filter_map(|result| result.ok())
+            .map(|logged| logged.event)
             .filter(move |e| e.tenant_id() == tenant_id);
         Box::pin(stream)
     }
 
+    fn event_stream_filtered(
+        &self,
+        ctx: QueueCtx,
+        filter: EventFilter,
+    ) -> QueueResult<BoxStream<CursoredEvent>> {
+        use tokio_stream::{wrappers::BroadcastStream, StreamExt};
+        let tenant_id = ctx.tenant_id;
+        let stream = BroadcastStream::new(self.event_broadcaster.subscribe())
+            .filter_map(|result| result.ok())
+            .filter(move |e| {
+                e.event.tenant_id() == tenant_id && filter.matches(&e.queue, &e.job_type, &e.event)
+            })
+            .map(LoggedEvent::into_cursored);
+        Ok(Box::pin(stream))
+    }
+
+    fn replay_from(
+        &self,
+        ctx: QueueCtx,
+        cursor: EventCursor,
+        filter: EventFilter,
+    ) -> QueueResult<BoxStream<CursoredEvent>> {
+        use tokio_stream::{wrappers::BroadcastStream, StreamExt};
+
+        let after: u64 = cursor
+            .as_str()
+            .parse()
+            .map_err(|_| QueueError::InvalidEventCursor(cursor.to_string()))?;
+
+        // Subscribe and snapshot under the log lock: `emit` appends and sends
+        // under the same lock, so every event after the snapshot reaches the
+        // receiver and none reaches both.
+        let (backlog, receiver) = {
+            let log = self.event_log.lock();
+            if after >= log.next_seq {
+                return Err(QueueError::InvalidEventCursor(cursor.to_string()));
+            }
+            if after + 1 < log.first_retained() {
+                return Err(QueueError::EventCursorExpired(cursor.to_string()));
+            }
+            let backlog: Vec<LoggedEvent> = log
+                .entries
+                .iter()
+                .filter(|e| e.seq > after)
+                .cloned()
+                .collect();
+            (backlog, self.event_broadcaster.subscribe())
+        };
+
+        let live = BroadcastStream::new(receiver).filter_map(|result| result.ok());
+        let tenant_id = ctx.tenant_id;
+        let stream = tokio_stream::iter(backlog)
+            .chain(live)
+            .filter(move |e| {
+                e.event.tenant_id() == tenant_id && filter.matches(&e.queue, &e.job_type, &e.event)
+            })
+            .map(LoggedEvent::into_cursored);
+        Ok(Box::pin(stream))
+    }
+
+    fn clock(&self) -> SharedClock {
+        self.clock.clone()
+    }
+
     fn capabilities(&self) -> QueueCapabilities {
         QueueCapabilities {
             delayed: true,
@@ -579,6 +1285,7 @@ impl QueueBackend for MemoryBackend {
     /// `MemoryBackend::clone()` clones the `Arc<RwLock<>>` fields (not the underlying
     /// maps), so the temporary reaper operates on the same shared data as this instance.
     async fn reclaim_expired_leases(&self) -> QueueResult<Vec<crate::backend::ReapOutcome>> {
+        self.sweep_expired_idempotency_keys().await;
         let reaper =
             crate::backend::memory::reaper::LeaseReaper::new(std::sync::Arc::new(self.clone()));
         reaper.reap_expired_leases().await
@@ -606,7 +1313,10 @@ impl Clone for MemoryBackend {
             queues: self.queues.clone(),
             idempotency: self.idempotency.clone(),
             event_broadcaster: self.event_broadcaster.clone(),
+            event_log: self.event_log.clone(),
             lease_duration: self.lease_duration,
+            clock: self.clock.clone(),
+            groups: self.groups.clone(),
         }
     }
 }
@@ -630,6 +1340,10 @@ mod tests {
             max_retries: 3,
             run_at: chrono::Utc::now(),
             idempotency_key: None,
+            idempotency_expires_at: None,
+            traceparent: None,
+            group_id: None,
+            delivery: crate::DeliveryMode::default(),
         }
     }
 
@@ -688,4 +1402,378 @@ mod tests {
             .await;
         assert!(matches!(result, Err(QueueError::JobCanceled)));
     }
+
+    #[tokio::test]
+    async fn test_requeue_failed_job() {
+        let backend = MemoryBackend::new();
+        let ctx = create_test_context();
+
+        let job_id = backend
+            .enqueue(ctx.clone(), create_test_job_message())
+            .await
+            .unwrap();
+        // Only failed jobs can be requeued.
+        assert!(!backend
+            .requeue_failed(ctx.clone(), job_id.clone())
+            .await
+            .unwrap());
+
+        let leased = backend
+            .dequeue(ctx.clone(), &["default"])
+            .await
+            .unwrap()
+            .unwrap();
+        backend
+            .ack_fail(
+                ctx.clone(),
+                job_id.clone(),
+                leased.lease_token,
+                "boom".to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(backend.list_queues(ctx.clone()).await.unwrap()[0].depth, 0);
+
+        assert!(backend
+            .requeue_failed(ctx.clone(), job_id.clone())
+            .await
+            .unwrap());
+        let queues = backend.list_queues(ctx.clone()).await.unwrap();
+        assert_eq!(
+            queues,
+            vec![QueueSummary {
+                queue: "default".to_string(),
+                depth: 1
+            }]
+        );
+
+        let record = backend.recent_records(ctx.clone(), 10).await.unwrap();
+        assert_eq!(record.len(), 1);
+        assert!(matches!(record[0].status, JobStatus::Enqueued));
+        assert_eq!(record[0].attempt, 0);
+        assert_eq!(record[0].last_error.as_deref(), Some("boom"));
+
+        let released = backend.dequeue(ctx, &["default"]).await.unwrap().unwrap();
+        assert_eq!(released.record.job_id, job_id);
+    }
+
+    #[tokio::test]
+    async fn test_group_surfaces_partial_failure_and_fires_once() {
+        let backend = MemoryBackend::new();
+        let ctx = create_test_context();
+        let group_id = JobGroupId::new();
+        let mut aggregate = create_test_job_message();
+        aggregate.queue = "aggregate".to_string();
+        backend
+            .create_group(ctx.clone(), group_id.clone(), 2, aggregate)
+            .await
+            .unwrap();
+
+        for _ in 0..2 {
+            let mut child = create_test_job_message();
+            child.group_id = Some(group_id.clone());
+            backend.enqueue(ctx.clone(), child).await.unwrap();
+        }
+
+        let first = backend
+            .dequeue(ctx.clone(), &["default"])
+            .await
+            .unwrap()
+            .unwrap();
+        backend
+            .ack_complete(ctx.clone(), first.record.job_id, first.lease_token, None)
+            .await
+            .unwrap();
+        let group = backend
+            .get_group(ctx.clone(), group_id.clone())
+            .await
+            .unwrap();
+        assert_eq!((group.completed, group.settled()), (1, 1));
+        assert!(group.aggregate_job_id.is_none());
+
+        let second = backend
+            .dequeue(ctx.clone(), &["default"])
+            .await
+            .unwrap()
+            .unwrap();
+        backend
+            .ack_fail(
+                ctx.clone(),
+                second.record.job_id.clone(),
+                second.lease_token,
+                "boom".to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+        // A settled child that is requeued and completes must not count twice.
+        assert!(backend
+            .requeue_failed(ctx.clone(), second.record.job_id)
+            .await
+            .unwrap());
+        let again = backend
+            .dequeue(ctx.clone(), &["default"])
+            .await
+            .unwrap()
+            .unwrap();
+        backend
+            .ack_complete(ctx.clone(), again.record.job_id, again.lease_token, None)
+            .await
+            .unwrap();
+
+        let group = backend
+            .get_group(ctx.clone(), group_id.clone())
+            .await
+            .unwrap();
+        assert_eq!((group.completed, group.failed, group.canceled), (1, 1, 0));
+        assert!(group.is_finished() && group.has_failures());
+        let aggregate_id = group.aggregate_job_id.expect("aggregation enqueued");
+        let stats = backend.queue_stats(ctx.clone(), "aggregate").await.unwrap();
+        assert_eq!(stats.depth, 1);
+        let leased = backend
+            .dequeue(ctx.clone(), &["aggregate"])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(leased.record.job_id, aggregate_id);
+
+        // Other tenants cannot see the group.
+        let other = QueueCtx::new("other_tenant");
+        assert!(matches!(
+            backend.get_group(other, group_id).await,
+            Err(QueueError::JobGroupNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_empty_group_fires_aggregate_immediately() {
+        let backend = MemoryBackend::new();
+        let ctx = create_test_context();
+        let group_id = JobGroupId::new();
+        backend
+            .create_group(ctx.clone(), group_id.clone(), 0, create_test_job_message())
+            .await
+            .unwrap();
+
+        let group = backend.get_group(ctx.clone(), group_id).await.unwrap();
+        assert!(group.is_finished());
+        assert!(group.aggregate_job_id.is_some());
+        assert!(backend.dequeue(ctx, &["default"]).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_queue_stats_tracks_depth_and_leases() {
+        use crate::{Clock, TestClock};
+
+        let clock = TestClock::starting_now();
+        let backend = MemoryBackend::new().with_clock(clock.clone());
+        let ctx = create_test_context();
+        let start = clock.now();
+
+        for _ in 0..3 {
+            let mut message = create_test_job_message();
+            message.run_at = start;
+            backend.enqueue(ctx.clone(), message).await.unwrap();
+        }
+        let mut later = create_test_job_message();
+        later.run_at = start + chrono::Duration::minutes(5);
+        backend.enqueue(ctx.clone(), later).await.unwrap();
+
+        let stats = backend.queue_stats(ctx.clone(), "default").await.unwrap();
+        assert_eq!(stats.depth, 3);
+        assert_eq!(stats.scheduled_count, 1);
+        assert_eq!(stats.leased_count, 0);
+        assert_eq!(stats.oldest_run_at, Some(start));
+
+        backend
+            .dequeue(ctx.clone(), &["default"])
+            .await
+            .unwrap()
+            .unwrap();
+        let stats = backend.queue_stats(ctx.clone(), "default").await.unwrap();
+        assert_eq!(stats.depth, 2);
+        assert_eq!(stats.leased_count, 1);
+        assert_eq!(stats.scheduled_count, 1);
+
+        // Once due, the scheduled job counts towards depth.
+        clock.advance(std::time::Duration::from_secs(300));
+        let stats = backend.queue_stats(ctx.clone(), "default").await.unwrap();
+        assert_eq!(stats.depth, 3);
+        assert_eq!(stats.scheduled_count, 0);
+
+        let empty = backend.queue_stats(ctx, "unknown").await.unwrap();
+        assert_eq!((empty.depth, empty.leased_count), (0, 0));
+        assert_eq!(empty.oldest_run_at, None);
+    }
+
+    #[tokio::test]
+    async fn test_replay_from_cursor_returns_later_events_in_order() {
+        use tokio_stream::StreamExt;
+
+        let backend = MemoryBackend::new();
+        let ctx = create_test_context();
+
+        let first = backend
+            .enqueue(ctx.clone(), create_test_job_message())
+            .await
+            .unwrap();
+        let mut live = backend
+            .event_stream_filtered(ctx.clone(), EventFilter::new().job_id(first.clone()))
+            .unwrap();
+        let leased = backend
+            .dequeue(ctx.clone(), &["default"])
+            .await
+            .unwrap()
+            .unwrap();
+
+        // The consumer saw the Leased event, then "disconnected".
+        let seen = live.next().await.unwrap();
+        assert_eq!(seen.event.event_name(), "leased");
+        drop(live);
+
+        backend
+            .ack_complete(ctx.clone(), first.clone(), leased.lease_token, None)
+            .await
+            .unwrap();
+        let second = backend
+            .enqueue(ctx.clone(), create_test_job_message())
+            .await
+            .unwrap();
+
+        let mut replay = backend
+            .replay_from(ctx.clone(), seen.cursor.clone(), EventFilter::new())
+            .unwrap();
+        let completed = replay.next().await.unwrap();
+        assert_eq!(completed.event.event_name(), "completed");
+        assert_eq!(completed.event.job_id(), &first);
+        let enqueued = replay.next().await.unwrap();
+        assert_eq!(enqueued.event.event_name(), "enqueued");
+        assert_eq!(enqueued.event.job_id(), &second);
+
+        // The replay continues seamlessly into live events.
+        backend.cancel(ctx, second.clone()).await.unwrap();
+        let canceled = replay.next().await.unwrap();
+        assert_eq!(canceled.event.event_name(), "canceled");
+        assert_eq!(canceled.queue, "default");
+        assert_eq!(canceled.job_type, "test_job");
+    }
+
+    #[tokio::test]
+    async fn test_ctx_metadata_is_persisted_and_echoed() {
+        use tokio_stream::StreamExt;
+
+        let backend = MemoryBackend::new();
+        let ctx = create_test_context()
+            .with_meta("request_id", "req-42")
+            .with_meta("user_id", "u-7")
+            .with_tag("not", "persisted");
+        let mut events = backend.event_stream(create_test_context());
+
+        let job_id = backend
+            .enqueue(ctx.clone(), create_test_job_message())
+            .await
+            .unwrap();
+
+        let record = backend
+            .get_record(create_test_context(), job_id.clone())
+            .await
+            .unwrap();
+        assert_eq!(record.metadata, ctx.metadata);
+        assert!(!record.metadata.contains_key("not"));
+
+        let enqueued = events.next().await.unwrap();
+        assert_eq!(enqueued.job_id(), &job_id);
+        let metadata = enqueued.metadata().unwrap();
+        assert_eq!(
+            metadata.get("request_id").map(String::as_str),
+            Some("req-42")
+        );
+        assert_eq!(metadata.get("user_id").map(String::as_str), Some("u-7"));
+    }
+
+    #[tokio::test]
+    async fn test_retried_job_events_carry_attempt_and_duration() {
+        use tokio_stream::StreamExt;
+
+        let backend = MemoryBackend::new();
+        let ctx = create_test_context();
+        let mut events = backend
+            .event_stream_filtered(ctx.clone(), EventFilter::new().queue("default"))
+            .unwrap();
+
+        let job_id = backend
+            .enqueue(ctx.clone(), create_test_job_message())
+            .await
+            .unwrap();
+        let leased = backend
+            .dequeue(ctx.clone(), &["default"])
+            .await
+            .unwrap()
+            .unwrap();
+        backend
+            .ack_fail(
+                ctx.clone(),
+                job_id.clone(),
+                leased.lease_token,
+                "transient".to_string(),
+                Some(chrono::Utc::now()),
+            )
+            .await
+            .unwrap();
+        let leased = backend
+            .dequeue(ctx.clone(), &["default"])
+            .await
+            .unwrap()
+            .unwrap();
+        backend
+            .ack_complete(ctx, job_id, leased.lease_token, None)
+            .await
+            .unwrap();
+
+        let mut seen = Vec::new();
+        for _ in 0..5 {
+            let event = events.next().await.unwrap().event;
+            assert_eq!(event.queue(), "default");
+            seen.push((event.event_name(), event.attempt()));
+            if let JobEvent::Retrying { duration_ms, .. }
+            | JobEvent::Completed { duration_ms, .. } = &event
+            {
+                assert!(duration_ms.is_some());
+            }
+        }
+        assert_eq!(
+            seen,
+            vec![
+                ("enqueued", None),
+                ("leased", Some(1)),
+                ("retrying", Some(1)),
+                ("leased", Some(2)),
+                ("completed", Some(2)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replay_from_evicted_cursor_is_rejected() {
+        let backend = MemoryBackend::new().with_event_log_capacity(2);
+        let ctx = create_test_context();
+
+        for _ in 0..3 {
+            backend
+                .enqueue(ctx.clone(), create_test_job_message())
+                .await
+                .unwrap();
+        }
+
+        // Event 1 was evicted, so events after cursor 0 are incomplete.
+        let expired = backend.replay_from(ctx.clone(), EventCursor::new("0"), EventFilter::new());
+        assert!(matches!(expired, Err(QueueError::EventCursorExpired(_))));
+        assert!(backend
+            .replay_from(ctx.clone(), EventCursor::new("1"), EventFilter::new())
+            .is_ok());
+
+        let invalid = backend.replay_from(ctx, EventCursor::new("not-a-seq"), EventFilter::new());
+        assert!(matches!(invalid, Err(QueueError::InvalidEventCursor(_))));
+    }
 }
@@ -3,12 +3,18 @@ pub mod memory;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use futures_core::Stream;
+use serde::{Deserialize, Serialize};
 use std::pin::Pin;
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::{
-    types::LeaseToken, JobEvent, JobId, JobMessage, JobRecord, JobStatus, LeasedJob,
-    QueueCapabilities, QueueCtx, QueueError, QueueResult,
+    clock::{SharedClock, SystemClock},
+    types::{
+        CursoredEvent, EventCursor, EventFilter, JobGroup, JobGroupId, JobTypeFilter, LeaseToken,
+    },
+    JobEvent, JobId, JobMessage, JobRecord, JobStatus, LeasedJob, QueueCapabilities, QueueCtx,
+    QueueError, QueueResult,
 };
 
 /// Per-job outcome from a single lease-reaper cycle.
@@ -33,6 +39,35 @@ pub struct ReapOutcome {
     pub retry_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+/// A queue and how many jobs are waiting in it, for dashboards.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueSummary {
+    /// Queue name.
+    pub queue: String,
+    /// Jobs waiting to be leased, including delayed and retrying ones.
+    pub depth: usize,
+}
+
+/// Point-in-time counts for one queue, for autoscaling and metrics.
+///
+/// Waiting jobs are split by whether they are due: `depth` counts jobs a
+/// worker could lease right now, `scheduled_count` those held back by a
+/// future `run_at` or retry backoff. Terminal jobs are not counted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueStats {
+    /// Queue name.
+    pub queue: String,
+    /// Jobs eligible to be leased now (enqueued or retrying, and due).
+    pub depth: usize,
+    /// Earliest due time among the `depth` jobs — `now - oldest_run_at` is
+    /// how long the queue's head has been waiting. `None` when `depth` is 0.
+    pub oldest_run_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Jobs currently leased by a worker.
+    pub leased_count: usize,
+    /// Jobs waiting for a future `run_at` or retry time.
+    pub scheduled_count: usize,
+}
+
 /// Type alias for boxed streams (stable Rust compatible)
 pub type BoxStream<T> = Pin<Box<dyn Stream<Item = T> + Send + 'static>>;
 
@@ -44,8 +79,33 @@ pub trait QueueBackend: Send + Sync {
 
     /// Lease-based dequeue (eligible jobs only)
     /// Returns jobs with run_at <= now and not in terminal status
+    ///
+    /// Implementations must never lease a job whose `run_at` is still in the
+    /// future — `QueueAdapter::enqueue_at`/`enqueue_in` and retry backoff rely
+    /// on this as their only scheduling mechanism.
     async fn dequeue(&self, ctx: QueueCtx, queues: &[&str]) -> QueueResult<Option<LeasedJob>>;
 
+    /// Like [`Self::dequeue`], but only leases jobs whose type `job_types`
+    /// permits. Jobs of other types are skipped without being consumed, so
+    /// they stay eligible for other workers.
+    ///
+    /// **Optional** — the default handles [`JobTypeFilter::All`] by calling
+    /// `dequeue` and returns [`QueueError::BackendUnsupported`] for anything
+    /// narrower.
+    async fn dequeue_filtered(
+        &self,
+        ctx: QueueCtx,
+        queues: &[&str],
+        job_types: &JobTypeFilter,
+    ) -> QueueResult<Option<LeasedJob>> {
+        if job_types.is_all() {
+            return self.dequeue(ctx, queues).await;
+        }
+        Err(QueueError::BackendUnsupported(
+            "dequeue_filtered: this backend cannot filter by job type".to_string(),
+        ))
+    }
+
     /// Acknowledge job completion (cancel-wins, lease token required)
     async fn ack_complete(
         &self,
@@ -66,6 +126,26 @@ pub trait QueueBackend: Send + Sync {
         retry_at: Option<DateTime<Utc>>,
     ) -> QueueResult<()>;
 
+    /// Store a running job's progress and emit [`JobEvent::Progress`].
+    ///
+    /// Like `heartbeat_extend`, only the current lease holder may report, so
+    /// a stale worker cannot overwrite a newer attempt's progress.
+    ///
+    /// **Optional** — the default returns [`QueueError::BackendUnsupported`],
+    /// which `ProgressReporter` logs and ignores.
+    async fn report_progress(
+        &self,
+        _ctx: QueueCtx,
+        _job_id: JobId,
+        _lease_token: LeaseToken,
+        _percent: u8,
+        _message: String,
+    ) -> QueueResult<()> {
+        Err(QueueError::BackendUnsupported(
+            "report_progress: this backend does not store job progress".to_string(),
+        ))
+    }
+
     /// Extend lease duration.
     ///
     /// Only required for backends that advertise `QueueCapabilities::lease_extend = true`.
@@ -84,6 +164,27 @@ pub trait QueueBackend: Send + Sync {
         ))
     }
 
+    /// Hand a leased job back to its queue without consuming a retry attempt.
+    ///
+    /// Used by [`WorkerHandle::shutdown_with`](crate::WorkerHandle::shutdown_with)
+    /// when a job is still executing at the end of the drain window: the lease is
+    /// cleared, the attempt counter is rolled back, and the job becomes
+    /// immediately eligible for another worker instead of waiting for the reaper.
+    ///
+    /// Same guards as `ack_complete`: cancel-wins, terminal jobs are rejected,
+    /// and the lease token must match. The default returns
+    /// [`QueueError::BackendUnsupported`]; callers fall back to lease expiry.
+    async fn release_lease(
+        &self,
+        _ctx: QueueCtx,
+        job_id: JobId,
+        _lease_token: LeaseToken,
+    ) -> QueueResult<()> {
+        Err(QueueError::BackendUnsupported(format!(
+            "release_lease: this backend cannot release leases early (job_id: {job_id})",
+        )))
+    }
+
     /// Cancel a job (cancel-wins semantics)
     async fn cancel(&self, ctx: QueueCtx, job_id: JobId) -> QueueResult<bool>;
 
@@ -102,9 +203,159 @@ pub trait QueueBackend: Send + Sync {
         )))
     }
 
+    /// List the tenant's queues with their current depth.
+    ///
+    /// **Optional** — used by inspection tooling such as the `ui` dashboard.
+    /// The default returns [`QueueError::BackendUnsupported`].
+    async fn list_queues(&self, _ctx: QueueCtx) -> QueueResult<Vec<QueueSummary>> {
+        Err(QueueError::BackendUnsupported(
+            "list_queues: this backend does not support queue introspection".to_string(),
+        ))
+    }
+
+    /// Every job record the tenant owns, for backend migration.
+    ///
+    /// Waiting jobs (enqueued or retrying) come first, queue by queue, in the
+    /// order this backend would lease them; leased and terminal records follow
+    /// in no particular order. Importing the waiting jobs in this order with
+    /// [`QueueBackend::import_record`] reproduces the source's lease order.
+    ///
+    /// **Optional** — used by [`crate::migrate::migrate_backend`]. The default
+    /// returns [`QueueError::BackendUnsupported`].
+    async fn export_records(&self, _ctx: QueueCtx) -> QueueResult<Vec<JobRecord>> {
+        Err(QueueError::BackendUnsupported(
+            "export_records: this backend does not support exporting jobs".to_string(),
+        ))
+    }
+
+    /// Store `record` verbatim — same job ID, status, attempt count, `run_at`,
+    /// priority, and idempotency key — and make it leasable once due.
+    ///
+    /// Returns `Ok(false)` without writing anything when the job ID already
+    /// exists, or when its idempotency key already belongs to another
+    /// non-terminal job, so re-running a migration is safe. Only waiting
+    /// (`Enqueued` / `Retrying`) records may be imported; anything else is
+    /// rejected with [`QueueError::InvalidConfig`].
+    ///
+    /// **Optional** — used by [`crate::migrate::migrate_backend`]. The default
+    /// returns [`QueueError::BackendUnsupported`].
+    async fn import_record(&self, _ctx: QueueCtx, record: JobRecord) -> QueueResult<bool> {
+        Err(QueueError::BackendUnsupported(format!(
+            "import_record: this backend does not support importing jobs (job_id: {})",
+            record.job_id
+        )))
+    }
+
+    /// Register a fan-out group expecting `total` children.
+    ///
+    /// Every job later enqueued with `JobMessage::group_id == Some(group_id)`
+    /// is appended to [`JobGroup::children`]. When the group's last child
+    /// reaches a terminal state the backend enqueues `on_complete` (with
+    /// `run_at` reset to that moment) exactly once and records its ID in
+    /// [`JobGroup::aggregate_job_id`]. A group with `total == 0` enqueues
+    /// `on_complete` immediately.
+    ///
+    /// **Optional** — required by `QueueAdapter::fan_out`. The default returns
+    /// [`QueueError::BackendUnsupported`].
+    async fn create_group(
+        &self,
+        _ctx: QueueCtx,
+        group_id: JobGroupId,
+        _total: usize,
+        _on_complete: JobMessage,
+    ) -> QueueResult<()> {
+        Err(QueueError::BackendUnsupported(format!(
+            "create_group: this backend does not support job groups (group_id: {group_id})",
+        )))
+    }
+
+    /// Current progress of a fan-out group.
+    ///
+    /// Returns [`QueueError::JobGroupNotFound`] for unknown groups and for
+    /// groups owned by another tenant.
+    ///
+    /// **Optional** — the default returns [`QueueError::BackendUnsupported`].
+    async fn get_group(&self, _ctx: QueueCtx, group_id: JobGroupId) -> QueueResult<JobGroup> {
+        Err(QueueError::BackendUnsupported(format!(
+            "get_group: this backend does not support job groups (group_id: {group_id})",
+        )))
+    }
+
+    /// Depth, age, and lease counts for one of the tenant's queues.
+    ///
+    /// A queue with no jobs reports all-zero stats rather than an error.
+    ///
+    /// **Optional** — used for autoscaling decisions and metrics export. The
+    /// default returns [`QueueError::BackendUnsupported`].
+    async fn queue_stats(&self, _ctx: QueueCtx, queue: &str) -> QueueResult<QueueStats> {
+        Err(QueueError::BackendUnsupported(format!(
+            "queue_stats: this backend does not support queue introspection (queue: {queue})",
+        )))
+    }
+
+    /// The tenant's most recently updated job records, newest first, at most
+    /// `limit` of them.
+    ///
+    /// **Optional** — used by inspection tooling such as the `ui` dashboard.
+    /// The default returns [`QueueError::BackendUnsupported`].
+    async fn recent_records(&self, _ctx: QueueCtx, _limit: usize) -> QueueResult<Vec<JobRecord>> {
+        Err(QueueError::BackendUnsupported(
+            "recent_records: this backend does not support job listing".to_string(),
+        ))
+    }
+
+    /// Put a permanently failed job back on its queue with a fresh retry budget.
+    ///
+    /// Returns `Ok(true)` when the job was requeued and `Ok(false)` when it is
+    /// not in the `Failed` state (mirrors [`QueueBackend::cancel`]).
+    ///
+    /// **Optional** — the default returns [`QueueError::BackendUnsupported`].
+    async fn requeue_failed(&self, _ctx: QueueCtx, job_id: JobId) -> QueueResult<bool> {
+        Err(QueueError::BackendUnsupported(format!(
+            "requeue_failed: this backend cannot requeue failed jobs (job_id: {job_id})",
+        )))
+    }
+
     /// Event stream for observability (boxed for stable Rust)
     fn event_stream(&self, ctx: QueueCtx) -> BoxStream<JobEvent>;
 
+    /// Live stream of the tenant's events that pass `filter`, each tagged with
+    /// its [`EventCursor`].
+    ///
+    /// **Optional** — the default returns [`QueueError::BackendUnsupported`].
+    /// Backends that keep an ordered event log (a bounded ring buffer for
+    /// `MemoryBackend`, Streams for Redis) should override this together with
+    /// [`QueueBackend::replay_from`].
+    fn event_stream_filtered(
+        &self,
+        _ctx: QueueCtx,
+        _filter: EventFilter,
+    ) -> QueueResult<BoxStream<CursoredEvent>> {
+        Err(QueueError::BackendUnsupported(
+            "event_stream_filtered: this backend does not keep an event log".to_string(),
+        ))
+    }
+
+    /// Replay the tenant's events emitted after `cursor` that pass `filter`,
+    /// in emission order, then continue with live events.
+    ///
+    /// Lets a reconnecting consumer pick up exactly where it left off: no
+    /// events are skipped or delivered twice across the replay/live boundary.
+    /// Returns [`QueueError::EventCursorExpired`] when events after `cursor`
+    /// have already been evicted from the log.
+    ///
+    /// **Optional** — the default returns [`QueueError::BackendUnsupported`].
+    fn replay_from(
+        &self,
+        _ctx: QueueCtx,
+        cursor: EventCursor,
+        _filter: EventFilter,
+    ) -> QueueResult<BoxStream<CursoredEvent>> {
+        Err(QueueError::BackendUnsupported(format!(
+            "replay_from: this backend does not keep an event log (cursor: {cursor})",
+        )))
+    }
+
     /// Reclaim expired leases by detecting timed-out jobs and re-queuing them for retry.
     ///
     /// Backends that manage lease expiry internally (e.g. [`MemoryBackend`]) should
@@ -119,6 +370,16 @@ pub trait QueueBackend: Send + Sync {
         Ok(vec![])
     }
 
+    /// The time source this backend evaluates `run_at`, lease expiry, and
+    /// timestamps against.
+    ///
+    /// The adapter reads time from here too (retry scheduling, `enqueue_in`),
+    /// so a [`TestClock`](crate::TestClock) installed on the backend controls
+    /// the whole pipeline. Defaults to [`SystemClock`].
+    fn clock(&self) -> SharedClock {
+        Arc::new(SystemClock)
+    }
+
     /// Get backend capabilities
     fn capabilities(&self) -> QueueCapabilities;
 }
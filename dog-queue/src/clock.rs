@@ -0,0 +1,116 @@
+//! Injectable time source.
+//!
+//! Queue semantics are defined in terms of "now": delayed jobs become eligible
+//! once `run_at <= now`, leases expire once `lease_until < now`, and retries
+//! are scheduled relative to now. Backends and the adapter read the time from
+//! a [`Clock`] instead of calling `Utc::now()` directly so that tests can swap
+//! in a [`TestClock`] and move time forward deterministically instead of
+//! sleeping.
+//!
+//! ```
+//! use dog_queue::backend::memory::MemoryBackend;
+//! use dog_queue::TestClock;
+//! use std::time::Duration;
+//!
+//! let clock = TestClock::starting_now();
+//! let backend = MemoryBackend::new().with_clock(clock.clone());
+//!
+//! // ... lease a job, then jump past its lease instead of waiting 5 minutes:
+//! clock.advance(Duration::from_secs(301));
+//! ```
+
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::sync::Arc;
+
+/// Source of the current time for queue operations.
+pub trait Clock: Send + Sync + fmt::Debug {
+    /// The current time.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Shared, type-erased clock handle as stored by backends.
+pub type SharedClock = Arc<dyn Clock>;
+
+/// Wall-clock time (`Utc::now()`). The default everywhere.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Manually driven clock for tests.
+///
+/// Time only moves when [`TestClock::advance`] or [`TestClock::set`] is
+/// called. Clones share the same instant, so keep one handle in the test and
+/// give another to the backend.
+#[derive(Debug, Clone)]
+pub struct TestClock {
+    // parking_lot::Mutex: never held across an .await, infallible (no poisoning).
+    now: Arc<parking_lot::Mutex<DateTime<Utc>>>,
+}
+
+impl TestClock {
+    /// A clock frozen at `start`.
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            now: Arc::new(parking_lot::Mutex::new(start)),
+        }
+    }
+
+    /// A clock frozen at the current wall-clock time.
+    pub fn starting_now() -> Self {
+        Self::new(Utc::now())
+    }
+
+    /// Move time forward by `by`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `by` does not fit in a `chrono::Duration` — a test bug, not a
+    /// runtime condition.
+    pub fn advance(&self, by: std::time::Duration) {
+        let by = chrono::Duration::from_std(by).expect("TestClock::advance: duration out of range");
+        *self.now.lock() += by;
+    }
+
+    /// Jump to `instant` (may move time backwards).
+    pub fn set(&self, instant: DateTime<Utc>) {
+        *self.now.lock() = instant;
+    }
+}
+
+impl Default for TestClock {
+    fn default() -> Self {
+        Self::starting_now()
+    }
+}
+
+impl Clock for TestClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_clock_only_moves_when_advanced() {
+        let start = Utc::now();
+        let clock = TestClock::new(start);
+        assert_eq!(clock.now(), start);
+
+        let shared = clock.clone();
+        clock.advance(Duration::from_secs(90));
+        assert_eq!(shared.now(), start + chrono::Duration::seconds(90));
+
+        shared.set(start);
+        assert_eq!(clock.now(), start);
+    }
+}
@@ -42,17 +42,20 @@ pub trait JobCodec: Send + Sync {
 /// Optional per-enqueue overrides.
 ///
 /// Both fields are `None` by default:
-/// - `queue` defaults to `J::JOB_TYPE` (each job type routes to its own queue).
-/// - `run_at` defaults to `Utc::now()` (immediate execution).
+/// - `queue` defaults to `J::QUEUE` (the job-type name unless the job
+///   overrides it).
+/// - `run_at` defaults to `Utc::now()` plus `J::DEFAULT_DELAY` (immediate
+///   execution unless the job overrides it).
 ///
 /// Use `QueueAdapter::enqueue_opts` to pass non-default values.
 #[derive(Debug, Clone, Default)]
 pub struct EnqueueOptions {
-    /// Target queue name. `None` means "use the job-type name as the queue".
+    /// Target queue name. `None` means "use the job's `QUEUE`".
     pub queue: Option<String>,
 
-    /// Earliest time the job is eligible for processing. `None` means "run
-    /// immediately". Useful for delayed or scheduled jobs.
+    /// Earliest time the job is eligible for processing. `None` means "after
+    /// the job's `DEFAULT_DELAY`", i.e. immediately for most jobs. Useful for
+    /// delayed or scheduled jobs.
     pub run_at: Option<DateTime<Utc>>,
 }
 
@@ -182,14 +185,18 @@ impl CodecRegistry {
 
     /// Encode a job into a `JobMessage`, respecting caller-supplied options.
     ///
-    /// - `opts.queue`: if `None`, defaults to `J::JOB_TYPE` (each job type gets
-    ///   its own queue). Pass a name explicitly to support multi-queue routing or
-    ///   priority lanes (e.g. `"email-high"` vs `"email-low"`).
-    /// - `opts.run_at`: if `None`, defaults to `Utc::now()` (run immediately).
+    /// - `opts.queue`: if `None`, defaults to `J::QUEUE` (the job-type name
+    ///   unless the job routes itself elsewhere). Pass a name explicitly to
+    ///   support priority lanes (e.g. `"email-high"` vs `"email-low"`).
+    /// - `opts.run_at`: if `None`, defaults to `Utc::now()` plus
+    ///   `J::DEFAULT_DELAY` (run immediately unless the job sets a delay).
+    ///   `QueueAdapter::enqueue_opts` always fills this from the backend's
+    ///   [`Clock`](crate::Clock) first, so the fallback only applies to direct
+    ///   callers.
     ///   Set this to schedule delayed jobs without constructing `JobMessage` manually.
     ///
     /// Payload size enforcement (against `QueueConfig::max_payload_size`) is
-    /// performed by the adapter in `enqueue_opts()` after this method returns,
+    /// performed by the adapter on the returned (codec-encoded) bytes,
     /// not here — this method does not have access to the adapter configuration.
     pub fn encode_job<J: Job>(&self, job: &J, opts: EnqueueOptions) -> QueueResult<JobMessage> {
         let codec = self.default_codec()?;
@@ -210,11 +217,17 @@ impl CodecRegistry {
             job_type: J::JOB_TYPE.to_string(),
             payload_bytes: payload,
             codec: codec.codec_id().to_string(),
-            queue: opts.queue.unwrap_or_else(|| J::JOB_TYPE.to_string()),
+            queue: opts.queue.unwrap_or_else(|| J::QUEUE.to_string()),
             priority: J::PRIORITY,
             max_retries: J::MAX_RETRIES,
-            run_at: opts.run_at.unwrap_or_else(Utc::now),
+            run_at: opts
+                .run_at
+                .unwrap_or_else(|| default_run_at::<J>(Utc::now())),
             idempotency_key: job.idempotency_key().map(|k| k.into_owned()),
+            idempotency_expires_at: None,
+            traceparent: None,
+            group_id: None,
+            delivery: J::DELIVERY,
         })
     }
 
@@ -225,6 +238,19 @@ impl CodecRegistry {
     }
 }
 
+/// When a job enqueued at `now` without an explicit `run_at` becomes
+/// eligible: `now` plus the job's `DEFAULT_DELAY`. A delay too large for
+/// chrono saturates to the latest representable time.
+pub(crate) fn default_run_at<J: Job>(now: DateTime<Utc>) -> DateTime<Utc> {
+    match J::DEFAULT_DELAY {
+        None => now,
+        Some(delay) => chrono::Duration::from_std(delay)
+            .ok()
+            .and_then(|delay| now.checked_add_signed(delay))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC),
+    }
+}
+
 impl Default for CodecRegistry {
     fn default() -> Self {
         Self::new()
@@ -1,4 +1,4 @@
-use crate::types::ids::JobId;
+use crate::types::ids::{JobGroupId, JobId};
 use thiserror::Error;
 
 /// Result type for queue operations
@@ -16,6 +16,10 @@ pub enum QueueError {
     #[error("Job not found: {0}")]
     JobNotFound(JobId),
 
+    /// The requested fan-out group does not exist in this tenant's namespace.
+    #[error("Job group not found: {0}")]
+    JobGroupNotFound(JobGroupId),
+
     /// Lease token mismatch — the presented token does not match the one issued
     /// when the job was leased.  Carries the `job_id` so operators can correlate
     /// the error to a specific job in logs without external tracing.
@@ -39,7 +43,15 @@ pub enum QueueError {
     #[error("Codec not found: {0}")]
     CodecNotFound(String),
 
-    #[error("Payload too large: {size} bytes (max: {max})")]
+    /// The encoded job payload exceeds `QueueConfig::max_payload_size`.
+    ///
+    /// `size` is the payload length after the codec ran; `max` is the
+    /// configured limit. Usually a sign that data was embedded in the job
+    /// instead of a reference to it.
+    #[error(
+        "Payload too large: {size} bytes (max: {max}) — store large data elsewhere \
+         and enqueue a reference to it"
+    )]
     PayloadTooLarge { size: usize, max: usize },
 
     #[error("Backend does not support feature: {0}")]
@@ -68,6 +80,18 @@ pub enum QueueError {
     #[error("Internal error: {0}")]
     Internal(String),
 
+    /// The cursor passed to `replay_from` was not issued by this backend.
+    #[error("Invalid event cursor: {0}")]
+    InvalidEventCursor(String),
+
+    /// The events following the cursor have already been evicted from the
+    /// backend's bounded event log, so a gap-free replay is impossible.
+    ///
+    /// Consumers should resynchronise from current job state (e.g. via
+    /// `get_record`) and then resume with a live `event_stream_filtered`.
+    #[error("Event cursor expired: {0}")]
+    EventCursorExpired(String),
+
     /// Execution time limit exceeded.
     ///
     /// Returned by [`QueueAdapter::execute_now`] when the configured
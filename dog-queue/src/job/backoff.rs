@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+/// Retry backoff strategy.
+///
+/// The adapter's default policy is [`RetryBackoff::Exponential`] built from
+/// `QueueConfig::base_retry_backoff`, `max_retry_backoff` and `retry_jitter`.
+/// Jobs that need a different shape (a flaky external API vs. a CPU-bound
+/// task) override [`Job::retry_backoff`](crate::Job::retry_backoff) and can
+/// use this type to compute the delay:
+///
+/// ```
+/// use dog_queue::RetryBackoff;
+/// use std::time::Duration;
+///
+/// let backoff = RetryBackoff::Linear {
+///     step: Duration::from_secs(5),
+///     max: Duration::from_secs(60),
+/// };
+/// assert_eq!(backoff.delay(3), Duration::from_secs(15));
+/// assert_eq!(backoff.delay(100), Duration::from_secs(60));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryBackoff {
+    /// The same delay before every retry.
+    Fixed(Duration),
+
+    /// `step × attempt`, capped at `max`.
+    Linear { step: Duration, max: Duration },
+
+    /// `base × 2^(attempt - 1)`, capped at `max`.
+    ///
+    /// With `jitter = true` the delay is sampled uniformly from `[0, capped]`
+    /// ("full jitter", the AWS recommendation). This desynchronises concurrent
+    /// retriers that all failed at the same instant — preventing the thundering
+    /// herd that pure exponential backoff causes on mass failures.
+    Exponential {
+        base: Duration,
+        max: Duration,
+        jitter: bool,
+    },
+}
+
+impl RetryBackoff {
+    /// Delay before the retry that follows failed attempt number `attempt`
+    /// (1-based, as stored in `JobRecord::attempt`).
+    pub fn delay(&self, attempt: u32) -> Duration {
+        match *self {
+            Self::Fixed(delay) => delay,
+            Self::Linear { step, max } => step.saturating_mul(attempt.max(1)).min(max),
+            Self::Exponential { base, max, jitter } => {
+                // saturating_pow keeps very high attempt counts at the cap
+                // instead of overflowing.
+                let ceiling = base
+                    .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+                    .min(max);
+                if jitter && !ceiling.is_zero() {
+                    // rand::random_range is the rand 0.10 top-level API, consistent
+                    // with the poll_jitter sampling in the worker loop. Inclusive
+                    // upper bound matches the documented `[0, capped]` range.
+                    Duration::from_nanos(rand::random_range(0u64..=ceiling.as_nanos() as u64))
+                } else {
+                    ceiling
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_backoff() {
+        let backoff = RetryBackoff::Fixed(Duration::from_secs(7));
+        assert_eq!(backoff.delay(1), Duration::from_secs(7));
+        assert_eq!(backoff.delay(10), Duration::from_secs(7));
+    }
+
+    #[test]
+    fn test_linear_backoff_is_capped() {
+        let backoff = RetryBackoff::Linear {
+            step: Duration::from_secs(2),
+            max: Duration::from_secs(9),
+        };
+        assert_eq!(backoff.delay(1), Duration::from_secs(2));
+        assert_eq!(backoff.delay(4), Duration::from_secs(8));
+        assert_eq!(backoff.delay(5), Duration::from_secs(9));
+    }
+
+    #[test]
+    fn test_exponential_backoff_without_jitter() {
+        let backoff = RetryBackoff::Exponential {
+            base: Duration::from_secs(1),
+            max: Duration::from_secs(30),
+            jitter: false,
+        };
+        assert_eq!(backoff.delay(1), Duration::from_secs(1));
+        assert_eq!(backoff.delay(2), Duration::from_secs(2));
+        assert_eq!(backoff.delay(4), Duration::from_secs(8));
+        assert_eq!(backoff.delay(6), Duration::from_secs(30));
+        // Huge attempt counts saturate at the cap rather than overflowing.
+        assert_eq!(backoff.delay(u32::MAX), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_exponential_backoff_jitter_stays_within_ceiling() {
+        let backoff = RetryBackoff::Exponential {
+            base: Duration::from_millis(100),
+            max: Duration::from_secs(1),
+            jitter: true,
+        };
+        for attempt in 1..=10 {
+            let ceiling = Duration::from_millis(100)
+                .saturating_mul(2u32.saturating_pow(attempt - 1))
+                .min(Duration::from_secs(1));
+            assert!(backoff.delay(attempt) <= ceiling);
+        }
+    }
+}
@@ -1,10 +1,16 @@
+pub mod backoff;
+pub mod progress;
 pub mod registry;
 
+pub use backoff::RetryBackoff;
+pub use progress::ProgressReporter;
 pub use registry::{JobHandler, JobRegistry};
 
-use crate::{JobError, JobPriority};
+use crate::{DeliveryMode, JobError, JobPriority};
 use async_trait::async_trait;
 use serde::{de::DeserializeOwned, Serialize};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
 
 /// Trait for defining jobs that can be processed by the queue
 #[async_trait]
@@ -37,8 +43,64 @@ pub trait Job: Send + Sync + Serialize + DeserializeOwned + 'static {
     /// attempt MAX_RETRIES + 1 → permanent failure.
     const MAX_RETRIES: u32 = 3;
 
-    /// Execute the job with the given context
-    async fn execute(&self, ctx: Self::Context) -> Result<Self::Result, JobError>;
+    /// Queue this job type is enqueued onto when the caller doesn't pick one
+    /// with [`EnqueueOptions::with_queue`](crate::codec::EnqueueOptions::with_queue).
+    ///
+    /// Defaults to `JOB_TYPE`, giving each job type its own queue. Override to
+    /// route by type — e.g. every media job onto `"media"`, served by a
+    /// dedicated worker pool — without each call site naming the queue.
+    const QUEUE: &'static str = Self::JOB_TYPE;
+
+    /// Delay before a freshly enqueued job becomes eligible, when the caller
+    /// doesn't supply a `run_at`.
+    ///
+    /// `None` by default (run immediately). An explicit `run_at` — including
+    /// the one `QueueAdapter::enqueue_in` computes — always wins.
+    const DEFAULT_DELAY: Option<Duration> = None;
+
+    /// Whether an attempt with an ambiguous outcome (lost lease, drain
+    /// timeout) is retried or failed.
+    ///
+    /// Defaults to [`DeliveryMode::AtLeastOnce`], which suits idempotent
+    /// jobs. Set [`DeliveryMode::AtMostOnce`] for jobs that must never
+    /// double-fire, accepting that such a job may be failed without having
+    /// run to completion — see [`DeliveryMode`] for the tradeoff.
+    const DELIVERY: DeliveryMode = DeliveryMode::AtLeastOnce;
+
+    /// Delay before retrying after failed attempt number `attempt` (1-based).
+    ///
+    /// Returns `None` by default, which applies the adapter's configured
+    /// exponential policy (`QueueConfig::base_retry_backoff`,
+    /// `max_retry_backoff`, `retry_jitter`). Override to give this job type its
+    /// own shape — [`RetryBackoff`] covers the common fixed, linear, and
+    /// capped exponential-with-jitter strategies:
+    ///
+    /// ```ignore
+    /// fn retry_backoff(attempt: u32) -> Option<Duration> {
+    ///     Some(RetryBackoff::Fixed(Duration::from_secs(30)).delay(attempt))
+    /// }
+    /// ```
+    fn retry_backoff(_attempt: u32) -> Option<Duration> {
+        None
+    }
+
+    /// Execute the job with the given context.
+    ///
+    /// `cancel` fires when the job is canceled while this execution is in
+    /// flight. Cancellation is cooperative: long-running jobs should check
+    /// `cancel.is_cancelled()` between units of work (or `select!` on
+    /// `cancel.cancelled()`) and return early. Whatever is returned after
+    /// cancellation is discarded — the worker never acks a canceled job as
+    /// completed or failed. Jobs that finish quickly can ignore the token.
+    ///
+    /// `progress` reports how far along a long-running job is (see
+    /// [`ProgressReporter`]); short jobs can ignore it too.
+    async fn execute(
+        &self,
+        ctx: Self::Context,
+        cancel: CancellationToken,
+        progress: ProgressReporter,
+    ) -> Result<Self::Result, JobError>;
 
     /// Get idempotency key (optional).
     ///
@@ -0,0 +1,138 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+use crate::{types::LeaseToken, JobId, QueueBackend, QueueCtx};
+
+/// Handle a running job uses to report how far along it is.
+///
+/// Each report is stored on the job's [`JobRecord::progress`](crate::JobRecord::progress)
+/// and emitted as a [`JobEvent::Progress`](crate::JobEvent::Progress), so a UI
+/// can render a progress bar for long jobs (e.g. a transcode):
+///
+/// ```ignore
+/// for (i, segment) in segments.iter().enumerate() {
+///     transcode(segment).await?;
+///     let percent = ((i + 1) * 100 / segments.len()) as u8;
+///     progress.report(percent, format!("segment {}/{}", i + 1, segments.len())).await;
+/// }
+/// ```
+///
+/// Reports closer together than `QueueConfig::progress_interval` are
+/// debounced to avoid event storms: the latest one is held back and sent
+/// when the interval expires or the job finishes, whichever comes first. A
+/// report of 100% always goes through at once. Reporting never fails the
+/// job — a backend error is logged and ignored.
+#[derive(Clone, Default)]
+pub struct ProgressReporter {
+    target: Option<Arc<ProgressTarget>>,
+}
+
+struct ProgressTarget {
+    backend: Arc<dyn QueueBackend + Send + Sync>,
+    ctx: QueueCtx,
+    job_id: JobId,
+    lease_token: LeaseToken,
+    min_interval: Duration,
+    /// Held across the backend call so reports are stored in order.
+    state: tokio::sync::Mutex<ProgressState>,
+}
+
+#[derive(Default)]
+struct ProgressState {
+    last_sent: Option<Instant>,
+    /// Latest debounced report, waiting for the interval to expire.
+    pending: Option<(u8, String)>,
+    /// Whether a task is already waiting to send `pending`.
+    flush_scheduled: bool,
+}
+
+impl ProgressReporter {
+    /// A reporter that discards every report, for executions with no stored
+    /// job (`QueueAdapter::execute_now`) and for tests.
+    pub fn noop() -> Self {
+        Self::default()
+    }
+
+    /// A reporter for the leased job `job_id`.
+    pub(crate) fn new(
+        backend: Arc<dyn QueueBackend + Send + Sync>,
+        ctx: QueueCtx,
+        job_id: JobId,
+        lease_token: LeaseToken,
+        min_interval: Duration,
+    ) -> Self {
+        Self {
+            target: Some(Arc::new(ProgressTarget {
+                backend,
+                ctx,
+                job_id,
+                lease_token,
+                min_interval,
+                state: tokio::sync::Mutex::default(),
+            })),
+        }
+    }
+
+    /// Report `percent` (clamped to 100) complete, with a short status
+    /// message.
+    pub async fn report(&self, percent: u8, message: impl Into<String>) {
+        let Some(target) = &self.target else {
+            return;
+        };
+        let percent = percent.min(100);
+        let mut state = target.state.lock().await;
+        let now = Instant::now();
+        let next_at = state.last_sent.map(|sent| sent + target.min_interval);
+        if let Some(next_at) = next_at.filter(|&at| now < at && percent < 100) {
+            state.pending = Some((percent, message.into()));
+            if !state.flush_scheduled {
+                state.flush_scheduled = true;
+                let target = target.clone();
+                tokio::spawn(async move {
+                    tokio::time::sleep_until(next_at.into()).await;
+                    target.flush().await;
+                });
+            }
+            return;
+        }
+        state.pending = None;
+        state.last_sent = Some(now);
+        target.send(percent, message.into()).await;
+    }
+
+    /// Send the report still held back by the debounce, once the job has
+    /// finished and before its lease is released.
+    pub(crate) async fn finish(&self) {
+        if let Some(target) = &self.target {
+            target.flush().await;
+        }
+    }
+}
+
+impl ProgressTarget {
+    async fn flush(&self) {
+        let mut state = self.state.lock().await;
+        state.flush_scheduled = false;
+        if let Some((percent, message)) = state.pending.take() {
+            state.last_sent = Some(Instant::now());
+            self.send(percent, message).await;
+        }
+    }
+
+    async fn send(&self, percent: u8, message: String) {
+        if let Err(e) = self
+            .backend
+            .report_progress(
+                self.ctx.clone(),
+                self.job_id.clone(),
+                self.lease_token.clone(),
+                percent,
+                message,
+            )
+            .await
+        {
+            debug!("Dropped progress report for job {}: {e}", self.job_id);
+        }
+    }
+}
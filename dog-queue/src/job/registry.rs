@@ -1,21 +1,30 @@
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
 
-use crate::{Job, JobError, JobMessage, QueueError, QueueResult};
+use crate::{Job, JobError, JobMessage, ProgressReporter, QueueError, QueueResult};
 
 /// Type-erased job handler for runtime dispatch
 #[async_trait]
 pub trait JobHandler: Send + Sync {
-    /// Execute a job with the given message and context
+    /// Execute a job with the given message and context.
+    ///
+    /// `cancel` and `progress` are forwarded to [`Job::execute`] unchanged.
     async fn execute(
         &self,
         message: &JobMessage,
         context: Arc<dyn std::any::Any + Send + Sync>,
+        cancel: CancellationToken,
+        progress: ProgressReporter,
     ) -> Result<Option<String>, JobError>;
 
     /// Get the job type this handler processes
     fn job_type(&self) -> &'static str;
+
+    /// The job type's retry backoff override (see [`Job::retry_backoff`]).
+    fn retry_backoff(&self, attempt: u32) -> Option<Duration>;
 }
 
 /// Concrete job handler implementation
@@ -37,6 +46,8 @@ impl<J: Job> JobHandler for ConcreteJobHandler<J> {
         &self,
         message: &JobMessage,
         context: Arc<dyn std::any::Any + Send + Sync>,
+        cancel: CancellationToken,
+        progress: ProgressReporter,
     ) -> Result<Option<String>, JobError> {
         // Deserialize the job from payload
         let job: J = serde_json::from_slice(&message.payload_bytes)
@@ -58,7 +69,7 @@ impl<J: Job> JobHandler for ConcreteJobHandler<J> {
             .clone();
 
         // Execute the job
-        let result = job.execute(typed_context).await?;
+        let result = job.execute(typed_context, cancel, progress).await?;
 
         // Serialize the result.  A serialization failure here is a programming
         // error in `J::Result`'s `Serialize` impl — `serde_json::to_string` writes
@@ -77,6 +88,10 @@ impl<J: Job> JobHandler for ConcreteJobHandler<J> {
     fn job_type(&self) -> &'static str {
         J::JOB_TYPE
     }
+
+    fn retry_backoff(&self, attempt: u32) -> Option<Duration> {
+        J::retry_backoff(attempt)
+    }
 }
 
 /// Registry for managing job types and their handlers
@@ -108,7 +123,7 @@ impl JobRegistry {
     /// Get a cloned handler for the given job type.
     ///
     /// Clone the handler under the registry lock, drop the lock, then call
-    /// `handler.execute(decoded_message, context)` outside the lock.
+    /// `handler.execute(decoded_message, context, cancel)` outside the lock.
     /// This prevents long-running jobs from blocking `register_job()` (write lock).
     ///
     /// The `decoded_message` passed to `handler.execute()` must have its
@@ -159,7 +174,12 @@ mod tests {
         const PRIORITY: JobPriority = JobPriority::Normal;
         const MAX_RETRIES: u32 = 3;
 
-        async fn execute(&self, ctx: Self::Context) -> Result<Self::Result, JobError> {
+        async fn execute(
+            &self,
+            ctx: Self::Context,
+            _cancel: CancellationToken,
+            _progress: ProgressReporter,
+        ) -> Result<Self::Result, JobError> {
             Ok(format!("Processed: {} with context: {}", self.data, ctx))
         }
     }
@@ -191,6 +211,10 @@ mod tests {
             max_retries: 3,
             run_at: chrono::Utc::now(),
             idempotency_key: None,
+            idempotency_expires_at: None,
+            traceparent: None,
+            group_id: None,
+            delivery: crate::DeliveryMode::default(),
         };
 
         // Correct pattern: clone handler under the lock, drop lock, execute outside.
@@ -198,7 +222,15 @@ mod tests {
             .get_handler("test_job")
             .expect("handler must be registered");
         let context = Arc::new("test_context".to_string()) as Arc<dyn std::any::Any + Send + Sync>;
-        let result = handler.execute(&message, context).await.unwrap();
+        let result = handler
+            .execute(
+                &message,
+                context,
+                CancellationToken::new(),
+                ProgressReporter::noop(),
+            )
+            .await
+            .unwrap();
 
         assert!(result.is_some());
         assert!(result
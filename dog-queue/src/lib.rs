@@ -43,7 +43,12 @@
 //!     const PRIORITY: JobPriority = JobPriority::Normal;
 //!     const MAX_RETRIES: u32 = 3;
 //!
-//!     async fn execute(&self, ctx: Self::Context) -> Result<Self::Result, JobError> {
+//!     async fn execute(
+//!         &self,
+//!         ctx: Self::Context,
+//!         _cancel: CancellationToken,
+//!         _progress: ProgressReporter,
+//!     ) -> Result<Self::Result, JobError> {
 //!         // use ctx.smtp_host to send self.recipient / self.subject
 //!         Ok(())
 //!     }
@@ -67,16 +72,21 @@
 //!     .start_workers(ctx, app_ctx, vec!["send_email".to_string()])
 //!     .await?;
 //!
-//! // Graceful shutdown
-//! handle.shutdown().await?;
+//! // Graceful shutdown — stop leasing, give in-flight jobs up to 30s to
+//! // finish, then release the leases of any still running
+//! let summary = handle
+//!     .shutdown_with(DrainOptions::with_timeout(Duration::from_secs(30)))
+//!     .await?;
 //! ```
 
 // Production-ready architecture modules
 pub mod adapter;
 pub mod backend;
+pub mod clock;
 pub mod codec;
 pub mod error;
 pub mod job;
+pub mod migrate;
 pub mod observability;
 pub mod types;
 
@@ -92,43 +102,46 @@ mod tests;
 
 // Core API exports - standardize on QueueAdapter for DogRS consistency
 pub use adapter::QueueAdapter;
-pub use adapter::{QueueConfig, WorkerHandle};
+pub use adapter::{DrainOptions, DrainSummary, QueueConfig, WorkerHandle};
 pub use backend::QueueBackend;
+pub use clock::{Clock, SystemClock, TestClock};
 pub use codec::json::JsonCodec;
 pub use codec::{CodecRegistry, EnqueueOptions, JobCodec};
 pub use error::{JobError, QueueError, QueueResult};
-pub use job::{Job, JobRegistry};
+pub use job::{Job, JobRegistry, ProgressReporter, RetryBackoff};
+pub use tokio_util::sync::CancellationToken;
 pub use types::{
-    JobEvent, JobId, JobMessage, JobPriority, JobRecord, JobStatus, LeaseToken, LeasedJob,
-    QueueCapabilities, QueueCtx, QueueFeature,
+    CursoredEvent, DeliveryMode, EventCursor, EventFilter, JobEvent, JobGroup, JobGroupId, JobId,
+    JobMessage, JobPriority, JobProgress, JobRecord, JobStatus, JobTypeFilter, LeaseToken,
+    LeasedJob, QueueCapabilities, QueueCtx, QueueFeature,
 };
 
 // Observability exports
 pub use observability::{LiveMetrics, ObservabilityLayer, PerformanceAnalytics};
 
 // Optional feature exports
-#[cfg(feature = "cron-scheduling")]
+// #[cfg(feature = "cron-scheduling")]
 // pub use scheduling::{Schedule, Scheduler};
 
 // Backend implementations
-#[cfg(feature = "redis")]
+// #[cfg(feature = "redis")]
 // pub use backend::redis::RedisBackend;
 
-#[cfg(feature = "postgres")]
+// #[cfg(feature = "postgres")]
 // pub use backend::postgres::PostgresBackend;
 
-#[cfg(feature = "sqlite")]
+// #[cfg(feature = "sqlite")]
 // pub use backend::sqlite::SqliteBackend;
 
 // Observability features
 #[cfg(feature = "metrics")]
 pub use observability::metrics::{MetricsCollector, PrometheusExporter};
 
-#[cfg(feature = "tracing-opentelemetry")]
+// #[cfg(feature = "tracing-opentelemetry")]
 // pub use observability::tracing::{DistributedTracing, SpanCollector};
 
 #[cfg(feature = "ui")]
-// pub use observability::ui::WebUI;
+pub use observability::ui::WebUI;
 
 /// Production-ready prelude for multi-tenant job processing
 pub mod prelude {
@@ -136,16 +149,19 @@ pub mod prelude {
     pub use crate::{Job, QueueAdapter, QueueBackend};
 
     // Essential types
-    pub use crate::{JobError, JobId, JobPriority, JobStatus, LeaseToken, QueueCtx, QueueResult};
+    pub use crate::{
+        CancellationToken, JobError, JobId, JobPriority, JobStatus, LeaseToken, ProgressReporter,
+        QueueCtx, QueueResult,
+    };
 
     // Adapter configuration and lifecycle
-    pub use crate::{EnqueueOptions, QueueConfig, WorkerHandle};
+    pub use crate::{DrainOptions, DrainSummary, EnqueueOptions, QueueConfig, WorkerHandle};
 
     // Codec system
     pub use crate::{CodecRegistry, JobCodec, JsonCodec};
 
     // Job registry
-    pub use crate::JobRegistry;
+    pub use crate::{JobRegistry, RetryBackoff};
 
     // Observability
     pub use crate::{LiveMetrics, ObservabilityLayer, PerformanceAnalytics};
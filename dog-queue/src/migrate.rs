@@ -0,0 +1,233 @@
+//! Copy waiting jobs between backends.
+//!
+//! Every backend implements the same lease semantics, so moving storage
+//! (e.g. from `MemoryBackend` in a prototype to a durable backend) only needs
+//! the queued work carried across intact. [`migrate_backend`] copies each
+//! tenant's **waiting** jobs — enqueued, delayed, and retrying — preserving
+//! job ID, status, attempt count, `run_at`/retry time, priority, lease order,
+//! and idempotency key.
+//!
+//! In-flight jobs are never copied: their lease lives in the source backend,
+//! and a copy would run a second time. They are listed in
+//! [`MigrationReport::skipped_leased`] so operators can drain workers (or
+//! wait for those jobs to finish) and run the migration again — it is
+//! idempotent, as already-copied jobs are skipped. Terminal jobs stay behind.
+//!
+//! ```rust,ignore
+//! use dog_queue::migrate::{migrate_backend, MigrateOptions};
+//!
+//! let opts = MigrateOptions::new(["tenant_a", "tenant_b"]);
+//! let preview = migrate_backend(&old, &new, opts.clone().dry_run(true)).await?;
+//! println!("would copy {} jobs", preview.migrated);
+//!
+//! let report = migrate_backend(&old, &new, opts).await?;
+//! assert!(report.skipped_leased.is_empty(), "drain workers and re-run");
+//! ```
+
+use crate::{backend::QueueBackend, JobId, JobStatus, QueueCtx, QueueError, QueueResult};
+
+/// Which tenants to migrate, and whether to write anything.
+#[derive(Debug, Clone, Default)]
+pub struct MigrateOptions {
+    /// Tenants whose jobs are copied. Backends are tenant-scoped and offer no
+    /// way to enumerate tenants, so the caller names them.
+    pub tenants: Vec<String>,
+    /// Count what would be copied without writing to the target.
+    pub dry_run: bool,
+}
+
+impl MigrateOptions {
+    /// Migrate `tenants`, writing to the target.
+    pub fn new<I, S>(tenants: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            tenants: tenants.into_iter().map(Into::into).collect(),
+            dry_run: false,
+        }
+    }
+
+    /// Only report counts; leave the target untouched.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+}
+
+/// What [`migrate_backend`] copied (or, in a dry run, would copy).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// Waiting jobs copied to the target (in a dry run: that would be copied).
+    pub migrated: usize,
+    /// Waiting jobs the target already had — from an earlier run, or sharing
+    /// an idempotency key with one of the target's live jobs.
+    pub already_present: usize,
+    /// Jobs leased by a worker at export time, left in the source.
+    pub skipped_leased: Vec<JobId>,
+    /// Completed, failed, and canceled jobs, left in the source.
+    pub skipped_terminal: usize,
+    /// Whether this was a dry run.
+    pub dry_run: bool,
+}
+
+/// Copy the waiting jobs of `opts.tenants` from `from` to `to`.
+///
+/// Both backends must implement [`QueueBackend::export_records`] /
+/// [`QueueBackend::import_record`]; otherwise this fails with
+/// [`QueueError::BackendUnsupported`] before writing anything. The source is
+/// only read — retire it once the report shows no skipped leased jobs.
+///
+/// A dry run detects jobs already present in the target by ID only; an
+/// idempotency-key clash is reported as `migrated` until the real run.
+pub async fn migrate_backend(
+    from: &dyn QueueBackend,
+    to: &dyn QueueBackend,
+    opts: MigrateOptions,
+) -> QueueResult<MigrationReport> {
+    let mut report = MigrationReport {
+        dry_run: opts.dry_run,
+        ..Default::default()
+    };
+
+    for tenant in &opts.tenants {
+        let ctx = QueueCtx::new(tenant.as_str());
+
+        for record in from.export_records(ctx.clone()).await? {
+            match record.status {
+                JobStatus::Enqueued | JobStatus::Retrying { .. } => {}
+                JobStatus::Processing { .. } => {
+                    report.skipped_leased.push(record.job_id);
+                    continue;
+                }
+                _ => {
+                    report.skipped_terminal += 1;
+                    continue;
+                }
+            }
+
+            let copied = if opts.dry_run {
+                match to.get_record(ctx.clone(), record.job_id.clone()).await {
+                    Ok(_) => false,
+                    Err(QueueError::JobNotFound(_)) => true,
+                    Err(e) => return Err(e),
+                }
+            } else {
+                to.import_record(ctx.clone(), record).await?
+            };
+
+            if copied {
+                report.migrated += 1;
+            } else {
+                report.already_present += 1;
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::memory::MemoryBackend;
+    use crate::{JobMessage, JobPriority};
+
+    fn message(priority: JobPriority, key: Option<&str>) -> JobMessage {
+        let message =
+            JobMessage::new("test_job", b"{}".to_vec(), "json", "default").with_priority(priority);
+        match key {
+            Some(key) => message.with_idempotency_key(key),
+            None => message,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migrate_preserves_identity_order_and_state() {
+        let source = MemoryBackend::new();
+        let target = MemoryBackend::new();
+        let ctx = QueueCtx::new("tenant_migrate");
+        let now = chrono::Utc::now();
+
+        let low = source
+            .enqueue(ctx.clone(), message(JobPriority::Low, None))
+            .await
+            .unwrap();
+        let keyed = source
+            .enqueue(ctx.clone(), message(JobPriority::Normal, Some("once")))
+            .await
+            .unwrap();
+        let high = source
+            .enqueue(ctx.clone(), message(JobPriority::High, None))
+            .await
+            .unwrap();
+        let later = source
+            .enqueue(
+                ctx.clone(),
+                message(JobPriority::Critical, None).with_run_at(now + chrono::Duration::hours(1)),
+            )
+            .await
+            .unwrap();
+        // One in flight, one finished.
+        let in_flight = source
+            .enqueue(ctx.clone(), message(JobPriority::Critical, None))
+            .await
+            .unwrap();
+        let leased = source
+            .dequeue(ctx.clone(), &["default"])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(leased.record.job_id, in_flight);
+        let done = source
+            .enqueue(ctx.clone(), message(JobPriority::Low, None))
+            .await
+            .unwrap();
+        source.cancel(ctx.clone(), done).await.unwrap();
+
+        let opts = MigrateOptions::new(["tenant_migrate"]);
+        let preview = migrate_backend(&source, &target, opts.clone().dry_run(true))
+            .await
+            .unwrap();
+        assert_eq!(preview.migrated, 4);
+        assert!(target.export_records(ctx.clone()).await.unwrap().is_empty());
+
+        let report = migrate_backend(&source, &target, opts.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            report,
+            MigrationReport {
+                migrated: 4,
+                already_present: 0,
+                skipped_leased: vec![in_flight],
+                skipped_terminal: 1,
+                dry_run: false,
+            }
+        );
+
+        // The delayed job keeps its run_at, so it is not leasable yet.
+        let record = target.get_record(ctx.clone(), later).await.unwrap();
+        assert!(record.message.run_at > now);
+        assert!(matches!(record.status, JobStatus::Enqueued));
+
+        // The idempotency key is live in the target.
+        let dedup = target
+            .enqueue(ctx.clone(), message(JobPriority::Normal, Some("once")))
+            .await
+            .unwrap();
+        assert_eq!(dedup, keyed);
+
+        // Lease order and job IDs carry over: High, Normal, Low.
+        let mut order = Vec::new();
+        while let Some(job) = target.dequeue(ctx.clone(), &["default"]).await.unwrap() {
+            order.push(job.record.job_id);
+        }
+        assert_eq!(order, vec![high, keyed, low]);
+
+        // Re-running is a no-op: every waiting job is already in the target.
+        let rerun = migrate_backend(&source, &target, opts).await.unwrap();
+        assert_eq!((rerun.migrated, rerun.already_present), (0, 4));
+    }
+}
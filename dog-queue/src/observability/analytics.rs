@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::debug;
 
 use crate::{JobId, QueueCtx};
@@ -87,6 +88,12 @@ impl ObservabilityLayer {
     pub fn metrics(&self) -> &super::LiveMetrics {
         &self.metrics
     }
+
+    /// Shared handle to the live metrics, for a
+    /// [`PrometheusExporter`](super::PrometheusExporter) or the web UI.
+    pub fn live_metrics(&self) -> Arc<super::LiveMetrics> {
+        Arc::clone(&self.metrics)
+    }
 }
 
 impl Default for ObservabilityLayer {
@@ -186,4 +193,58 @@ impl PerformanceAnalytics {
         let (global, _) = self.observability.metrics.snapshot_all();
         global.retry_rate()
     }
+
+    /// Execution latency of `job_type` at quantile `q` (e.g. `0.95` for p95),
+    /// within 1% of the exact value.
+    ///
+    /// Prefer this to averages for SLAs: a few slow runs barely move the
+    /// mean but show up in p95/p99. Returns `None` without timing data or for
+    /// a `q` outside `[0.0, 1.0]`.
+    pub fn percentile(&self, job_type: &str, q: f64) -> Option<Duration> {
+        self.observability
+            .metrics
+            .performance_metrics()
+            .quantile_execution_time(job_type, q)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles_expose_the_tail_that_averages_hide() {
+        let observability = Arc::new(ObservabilityLayer::new());
+        // 1..=1000 ms, recorded out of order.
+        for i in (1..=1000u64).rev() {
+            observability
+                .metrics()
+                .record_execution_time("resize", Duration::from_millis(i));
+        }
+        // 95% fast runs and a 5% spike.
+        for i in 0..1000 {
+            let ms = if i % 20 == 0 { 2000 } else { 10 };
+            observability
+                .metrics()
+                .record_execution_time("email", Duration::from_millis(ms));
+        }
+        let analytics = PerformanceAnalytics::new(observability.clone());
+
+        let p95 = analytics.percentile("resize", 0.95).unwrap();
+        assert!(p95.abs_diff(Duration::from_millis(950)) <= Duration::from_millis(10));
+        let p50 = analytics.percentile("resize", 0.5).unwrap();
+        assert!(p50.abs_diff(Duration::from_millis(500)) <= Duration::from_millis(10));
+
+        let average = observability
+            .metrics()
+            .performance_metrics()
+            .average_execution_time("email")
+            .unwrap();
+        assert!(average < Duration::from_millis(200));
+        let p99 = analytics.percentile("email", 0.99).unwrap();
+        assert!(p99.abs_diff(Duration::from_secs(2)) <= Duration::from_millis(20));
+
+        assert_eq!(analytics.percentile("unknown", 0.95), None);
+        assert_eq!(analytics.percentile("resize", 1.01), None);
+    }
 }
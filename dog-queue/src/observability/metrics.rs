@@ -1,10 +1,14 @@
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
-use std::collections::{HashMap, VecDeque};
+use hdrhistogram::Histogram;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+#[cfg(feature = "metrics")]
+use crate::backend::QueueStats;
+
 // ---------------------------------------------------------------------------
 // Per-type atomic counters (updated synchronously — no locks, no spawns)
 // ---------------------------------------------------------------------------
@@ -65,9 +69,9 @@ pub struct LiveMetrics {
     per_type: DashMap<String, PerTypeCounters>,
 
     /// Performance timing data — kept behind a `std::sync::Mutex` because
-    /// `record_execution_time` is a synchronous write (VecDeque push + optional
-    /// pop_front — nanoseconds). Using a tokio async lock would add an unnecessary
-    /// yield point on every job completion.
+    /// `record_execution_time` is a synchronous write (one histogram bucket
+    /// increment — nanoseconds). Using a tokio async lock would add an
+    /// unnecessary yield point on every job completion.
     performance: Arc<Mutex<PerformanceMetrics>>,
 }
 
@@ -279,26 +283,61 @@ impl JobTypeMetrics {
 }
 
 // ---------------------------------------------------------------------------
-// PerformanceMetrics — execution timing ring-buffer
+// PerformanceMetrics — execution timing histograms
 // ---------------------------------------------------------------------------
 
+/// Significant figures kept by each latency histogram: quantiles are within
+/// 1% of the recorded value.
+const LATENCY_SIGNIFICANT_FIGURES: u8 = 2;
+
+/// Execution timings of one job type.
+///
+/// An HDR histogram of microseconds answers any quantile in constant memory
+/// and without sorting, however many executions it has seen. The exact sum
+/// keeps [`PerformanceMetrics::average_execution_time`] free of bucket error.
+#[derive(Clone)]
+struct ExecutionTimes {
+    histogram: Histogram<u64>,
+    total: Duration,
+}
+
+impl ExecutionTimes {
+    fn new() -> Self {
+        Self {
+            histogram: Histogram::new(LATENCY_SIGNIFICANT_FIGURES)
+                .expect("significant figures are in range"),
+            total: Duration::ZERO,
+        }
+    }
+
+    fn record(&mut self, duration: Duration) {
+        let micros = u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+        // The histogram grows to fit; only values past its hard limit saturate.
+        if self.histogram.record(micros).is_err() {
+            self.histogram.saturating_record(micros);
+        }
+        self.total = self.total.saturating_add(duration);
+    }
+}
+
 /// Per-job-type execution timing data.
 ///
-/// Uses `VecDeque` so that evicting the oldest entry when the ring buffer is
-/// full is O(1) (`pop_front`) rather than O(n) (`Vec::remove(0)`).
+/// Each job type keeps a streaming histogram of every execution, so memory
+/// stays bounded and a quantile read walks the buckets instead of sorting
+/// samples.
 ///
 /// Stores `std::time::Duration` (always non-negative, no chrono dependency)
 /// rather than `chrono::Duration` (signed, allows negative values).
 ///
 /// `execution_times` is wrapped in `Arc` so that `Clone` (called by
 /// `LiveMetrics::performance_metrics()` on every snapshot) is O(1) — it
-/// only increments the Arc refcount rather than deep-copying all ring buffers.
+/// only increments the Arc refcount rather than deep-copying all histograms.
 /// Copy-on-write semantics are preserved: `Arc::make_mut` in
 /// `record_execution_time` detects when a snapshot holder still holds a
 /// reference and performs a deep clone only at that point, not on every
 /// snapshot.
 pub struct PerformanceMetrics {
-    execution_times: Arc<HashMap<String, VecDeque<Duration>>>,
+    execution_times: Arc<HashMap<String, ExecutionTimes>>,
     last_updated: DateTime<Utc>,
 }
 
@@ -336,76 +375,66 @@ impl PerformanceMetrics {
         }
     }
 
-    /// Record execution time for a job type (ring-buffer: keeps the last 1000).
+    /// Record execution time for a job type.
     ///
     /// Uses `Arc::make_mut` on `execution_times` for copy-on-write semantics:
     /// if a snapshot caller is still holding a reference, only then is the
     /// backing `HashMap` deep-cloned. The common case (no concurrent snapshot)
     /// mutates in place without any allocation.
     pub fn record_execution_time(&mut self, job_type: &str, duration: Duration) {
-        let times = Arc::make_mut(&mut self.execution_times)
+        Arc::make_mut(&mut self.execution_times)
             .entry(job_type.to_string())
-            .or_default();
-        times.push_back(duration);
-        if times.len() > 1000 {
-            times.pop_front(); // O(1) — was Vec::remove(0) which is O(n)
-        }
+            .or_insert_with(ExecutionTimes::new)
+            .record(duration);
         self.last_updated = Utc::now();
     }
 
     /// Average execution time for a job type.
     pub fn average_execution_time(&self, job_type: &str) -> Option<Duration> {
         let times = self.execution_times.get(job_type)?;
-        if times.is_empty() {
+        let count = times.histogram.len();
+        if count == 0 {
             return None;
         }
-        let total_nanos: u128 = times.iter().map(|d| d.as_nanos()).sum();
         Some(Duration::from_nanos(
-            (total_nanos / times.len() as u128) as u64,
+            (times.total.as_nanos() / count as u128) as u64,
         ))
     }
 
-    /// Percentile execution time for a job type (e.g. 50.0 for p50).
+    /// Execution time of a job type at quantile `q` (e.g. `0.95` for p95),
+    /// within 1% of the exact value.
     ///
     /// Returns `None` if there is no timing data for the job type or if
-    /// `percentile` is outside `[0.0, 100.0]`.
-    pub fn percentile_execution_time(&self, job_type: &str, percentile: f64) -> Option<Duration> {
-        self.percentiles(job_type, &[percentile])
-            .into_iter()
-            .next()
-            .flatten()
+    /// `q` is outside `[0.0, 1.0]`.
+    pub fn quantile_execution_time(&self, job_type: &str, q: f64) -> Option<Duration> {
+        self.quantiles(job_type, &[q]).into_iter().next().flatten()
     }
 
-    /// Compute multiple percentiles in a single sort pass.
+    /// [`Self::quantile_execution_time`] for several quantiles at once
+    /// (e.g. p50 + p95 + p99 for dashboards).
     ///
-    /// More efficient than calling [`percentile_execution_time`] repeatedly when
-    /// several quantiles are needed at once (e.g. p50 + p95 + p99 for dashboards).
-    ///
-    /// Returns one `Option<Duration>` per input percentile, in the same order.
-    /// Returns `None` for a given percentile if:
+    /// Returns one `Option<Duration>` per input quantile, in the same order.
+    /// Returns `None` for a given quantile if:
     /// - There is no timing data for the job type.
-    /// - The percentile value is outside `[0.0, 100.0]` (programming error — callers
+    /// - The quantile is outside `[0.0, 1.0]` (programming error — callers
     ///   should validate before calling).
-    pub fn percentiles(&self, job_type: &str, percentiles: &[f64]) -> Vec<Option<Duration>> {
+    pub fn quantiles(&self, job_type: &str, quantiles: &[f64]) -> Vec<Option<Duration>> {
         let times = match self.execution_times.get(job_type) {
-            Some(t) if !t.is_empty() => t,
-            _ => return vec![None; percentiles.len()],
+            Some(t) if !t.histogram.is_empty() => t,
+            _ => return vec![None; quantiles.len()],
         };
-        // Sort once for all requested percentiles.
-        // std::time::Duration implements Ord — sort_unstable is correct and faster.
-        let mut sorted: Vec<Duration> = times.iter().cloned().collect();
-        sorted.sort_unstable();
-        let n = sorted.len();
-        percentiles
+        quantiles
             .iter()
-            .map(|&p| {
-                // Validate range — a percentile outside [0, 100] is a programming error;
-                // return None rather than an out-of-bounds or misleading in-bounds index.
-                if !(0.0..=100.0).contains(&p) {
+            .map(|&q| {
+                // A quantile outside [0, 1] is a programming error; return None
+                // rather than silently clamping it.
+                if !(0.0..=1.0).contains(&q) {
                     return None;
                 }
-                let index = ((p / 100.0) * (n - 1) as f64).round() as usize;
-                sorted.get(index).cloned()
+                let micros = times.histogram.value_at_quantile(q);
+                Some(Duration::from_micros(
+                    times.histogram.median_equivalent(micros),
+                ))
             })
             .collect()
     }
@@ -575,28 +604,26 @@ mod tests {
         let avg = perf.average_execution_time("test_job").unwrap();
         assert_eq!(avg.as_millis(), 200);
 
-        let p50 = perf.percentile_execution_time("test_job", 50.0).unwrap();
-        assert_eq!(p50.as_millis(), 200);
+        let p50 = perf.quantile_execution_time("test_job", 0.5).unwrap();
+        assert!(p50.abs_diff(Duration::from_millis(200)) <= Duration::from_millis(2));
     }
 
     #[test]
-    fn test_ring_buffer_eviction_is_bounded() {
+    fn test_histogram_memory_is_bounded() {
         let mut perf = PerformanceMetrics::new();
-        // Insert 1100 entries — ring buffer should cap at 1000
-        for i in 0..1100u64 {
-            perf.record_execution_time("job", Duration::from_millis(i));
+        for i in 0..10_000u64 {
+            perf.record_execution_time("job", Duration::from_millis(i % 2000));
+        }
+        let buckets = perf.execution_times["job"].histogram.distinct_values();
+        for i in 0..100_000u64 {
+            perf.record_execution_time("job", Duration::from_millis(i % 2000));
         }
         assert_eq!(
-            perf.execution_times["job"].len(),
-            1000,
-            "ring buffer must cap at 1000 entries"
-        );
-        // The oldest 100 should have been evicted; first entry is ms=100
-        assert_eq!(
-            perf.execution_times["job"][0].as_millis(),
-            100,
-            "oldest entries are evicted first"
+            perf.execution_times["job"].histogram.distinct_values(),
+            buckets,
+            "more samples in the same range must not grow the histogram"
         );
+        assert_eq!(perf.execution_times["job"].histogram.len(), 110_000);
     }
 
     #[test]
@@ -634,8 +661,8 @@ mod tests {
 /// VictoriaMetrics.
 ///
 /// All counters carry a `job_type` label for per-type breakdown.  Label
-/// values are escaped per the Prometheus specification (backslash and
-/// double-quote are the only characters that require escaping).
+/// values are escaped per the Prometheus specification (backslash,
+/// double-quote and line feed).
 ///
 /// Available when the `metrics` feature is enabled.
 ///
@@ -719,9 +746,7 @@ impl PrometheusExporter {
             let mut type_entries: Vec<(&String, &JobTypeMetrics)> = per_type.iter().collect();
             type_entries.sort_unstable_by_key(|(k, _)| k.as_str());
             for (job_type, metrics) in &type_entries {
-                // Escape per Prometheus text format spec:
-                // backslash → \\, double-quote → \"
-                let escaped = job_type.replace('\\', r"\\").replace('"', "\\\"");
+                let escaped = escape_label_value(job_type);
                 let _ = writeln!(
                     out,
                     "{}{{job_type=\"{}\"}} {}",
@@ -732,10 +757,102 @@ impl PrometheusExporter {
             }
         }
 
+        // Execution latency quantiles over each job type's executions.
+        let performance = self.live_metrics.performance_metrics();
+        let name = "dog_queue_job_duration_seconds";
+        let _ = writeln!(
+            out,
+            "# HELP {name} Job execution time, partitioned by job type."
+        );
+        let _ = writeln!(out, "# TYPE {name} summary");
+        for job_type in performance.job_types() {
+            let escaped = escape_label_value(&job_type);
+            let quantiles = [0.5, 0.95, 0.99];
+            let values = performance.quantiles(&job_type, &quantiles);
+            for (q, value) in quantiles.iter().zip(values) {
+                if let Some(value) = value {
+                    let _ = writeln!(
+                        out,
+                        "{name}{{job_type=\"{escaped}\",quantile=\"{q}\"}} {}",
+                        value.as_secs_f64()
+                    );
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Render [`Self::gather`] followed by per-queue gauges from `stats`.
+    ///
+    /// Queue stats live in the backend rather than in [`LiveMetrics`], so the
+    /// caller fetches them first — typically one
+    /// [`QueueBackend::queue_stats`](crate::QueueBackend::queue_stats) call
+    /// per queue it serves — and passes them in. Each gauge carries a `queue`
+    /// label; `dog_queue_queue_oldest_run_at_seconds` is a Unix timestamp
+    /// (omitted for empty queues) so dashboards can compute head-of-line age.
+    pub fn gather_with_queue_stats(&self, stats: &[QueueStats]) -> String {
+        use std::fmt::Write as _;
+
+        let mut out = self.gather();
+
+        struct Gauge {
+            name: &'static str,
+            help: &'static str,
+            get: fn(&QueueStats) -> Option<i64>,
+        }
+
+        let gauges: &[Gauge] = &[
+            Gauge {
+                name: "dog_queue_queue_depth",
+                help: "Jobs eligible to be leased now, partitioned by queue.",
+                get: |s| Some(s.depth as i64),
+            },
+            Gauge {
+                name: "dog_queue_queue_leased",
+                help: "Jobs currently leased by a worker, partitioned by queue.",
+                get: |s| Some(s.leased_count as i64),
+            },
+            Gauge {
+                name: "dog_queue_queue_scheduled",
+                help: "Jobs waiting for a future run_at or retry time, partitioned by queue.",
+                get: |s| Some(s.scheduled_count as i64),
+            },
+            Gauge {
+                name: "dog_queue_queue_oldest_run_at_seconds",
+                help: "Unix time the oldest eligible job became due, partitioned by queue.",
+                get: |s| s.oldest_run_at.map(|t| t.timestamp()),
+            },
+        ];
+
+        let mut sorted: Vec<&QueueStats> = stats.iter().collect();
+        sorted.sort_unstable_by_key(|s| s.queue.as_str());
+
+        for gauge in gauges {
+            let _ = writeln!(out, "# HELP {} {}", gauge.name, gauge.help);
+            let _ = writeln!(out, "# TYPE {} gauge", gauge.name);
+            for queue_stats in &sorted {
+                if let Some(value) = (gauge.get)(queue_stats) {
+                    let escaped = escape_label_value(&queue_stats.queue);
+                    let _ = writeln!(out, "{}{{queue=\"{}\"}} {}", gauge.name, escaped, value);
+                }
+            }
+        }
+
         out
     }
 }
 
+/// Escape a label value per the Prometheus text format:
+/// backslash → `\\`, double-quote → `\"`, line feed → `\n`.
+#[cfg(feature = "metrics")]
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', "\\\"")
+        .replace('\n', r"\n")
+}
+
 #[cfg(all(test, feature = "metrics"))]
 mod prometheus_tests {
     use super::*;
@@ -757,16 +874,58 @@ mod prometheus_tests {
         assert!(output.contains(r#"dog_queue_jobs_failed_total{job_type="resize_image"} 1"#));
     }
 
+    #[test]
+    fn test_prometheus_exporter_renders_latency_quantiles() {
+        let metrics = Arc::new(LiveMetrics::new());
+        for ms in 1..=100 {
+            metrics.record_execution_time("send_email", Duration::from_millis(ms * 10));
+        }
+
+        let output = PrometheusExporter::new(metrics).gather();
+
+        assert!(output.contains("# TYPE dog_queue_job_duration_seconds summary"));
+        let quantile = |q: &str| -> f64 {
+            let prefix = format!(
+                r#"dog_queue_job_duration_seconds{{job_type="send_email",quantile="{q}"}} "#
+            );
+            let line = output.lines().find_map(|l| l.strip_prefix(&prefix));
+            line.unwrap().parse().unwrap()
+        };
+        assert!((quantile("0.5") - 0.5).abs() <= 0.01);
+        assert!((quantile("0.99") - 0.99).abs() <= 0.01);
+    }
+
+    #[test]
+    fn test_prometheus_exporter_renders_queue_gauges() {
+        let exporter = PrometheusExporter::new(Arc::new(LiveMetrics::new()));
+        let output = exporter.gather_with_queue_stats(&[QueueStats {
+            queue: "mail".to_string(),
+            depth: 4,
+            oldest_run_at: None,
+            leased_count: 2,
+            scheduled_count: 1,
+        }]);
+
+        assert!(output.contains("# TYPE dog_queue_queue_depth gauge"));
+        assert!(output.contains(r#"dog_queue_queue_depth{queue="mail"} 4"#));
+        assert!(output.contains(r#"dog_queue_queue_leased{queue="mail"} 2"#));
+        assert!(output.contains(r#"dog_queue_queue_scheduled{queue="mail"} 1"#));
+        // Empty queues have no head-of-line timestamp.
+        assert!(!output.contains(r#"dog_queue_queue_oldest_run_at_seconds{queue="mail"}"#));
+    }
+
     #[test]
     fn test_prometheus_exporter_escapes_label_values() {
         let metrics = Arc::new(LiveMetrics::new());
         // job_type with special characters that need escaping
         metrics.increment_jobs_enqueued(r#"my\"tricky\type"#);
+        metrics.increment_jobs_enqueued("two\nlines");
 
         let exporter = PrometheusExporter::new(metrics);
         let output = exporter.gather();
 
-        // Backslash should be doubled, double-quote should be escaped
+        // Backslash should be doubled, double-quote and line feed escaped
         assert!(output.contains(r#"job_type="my\\\"tricky\\type""#));
+        assert!(output.contains(r#"job_type="two\nlines""#));
     }
 }
@@ -2,8 +2,8 @@ pub mod analytics;
 pub mod metrics;
 pub mod tracing;
 
-// #[cfg(feature = "ui")]
-// pub mod ui;
+#[cfg(feature = "ui")]
+pub mod ui;
 
 pub use analytics::{ObservabilityLayer, PerformanceAnalytics};
 pub use metrics::{LiveMetrics, MetricsCollector, PerformanceMetrics};
@@ -14,5 +14,5 @@ pub use metrics::PrometheusExporter;
 #[cfg(feature = "tracing-opentelemetry")]
 pub use tracing::{DistributedTracing, SpanCollector};
 
-// #[cfg(feature = "ui")]
-// pub use ui::WebUI;
+#[cfg(feature = "ui")]
+pub use ui::WebUI;
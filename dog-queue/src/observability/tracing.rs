@@ -1,7 +1,138 @@
 // #[cfg(feature = "tracing-opentelemetry")]
 // use opentelemetry::trace::{SpanId, TraceId};
+use std::fmt;
 use std::sync::Arc;
 
+// ---------------------------------------------------------------------------
+// W3C trace-context propagation (enqueue → execute)
+// ---------------------------------------------------------------------------
+
+/// A parsed W3C `traceparent` header value
+/// (`00-<32 hex trace-id>-<16 hex parent-id>-<2 hex flags>`).
+///
+/// This is what `QueueAdapter::enqueue` stores in `JobMessage::traceparent`
+/// so the worker that eventually executes the job can continue the
+/// producer's trace, even when it runs in another process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceParent {
+    /// 32 lowercase hex characters.
+    pub trace_id: String,
+    /// 16 lowercase hex characters — the producer's span ID.
+    pub span_id: String,
+    /// Trace flags; bit 0 is "sampled".
+    pub flags: u8,
+}
+
+impl TraceParent {
+    /// Parse a version-00 `traceparent` value.
+    ///
+    /// Returns `None` for malformed values and for the all-zero trace/span IDs,
+    /// which the spec defines as invalid.
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.trim().split('-');
+        let (version, trace_id, span_id, flags) =
+            (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+        if parts.next().is_some() || version != "00" {
+            return None;
+        }
+        let is_hex = |s: &str, len: usize| {
+            s.len() == len && s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+        };
+        if !is_hex(trace_id, 32) || !is_hex(span_id, 16) || !is_hex(flags, 2) {
+            return None;
+        }
+        if trace_id.bytes().all(|b| b == b'0') || span_id.bytes().all(|b| b == b'0') {
+            return None;
+        }
+        Some(Self {
+            trace_id: trace_id.to_string(),
+            span_id: span_id.to_string(),
+            flags: u8::from_str_radix(flags, 16).ok()?,
+        })
+    }
+}
+
+impl fmt::Display for TraceParent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "00-{}-{}-{:02x}",
+            self.trace_id, self.span_id, self.flags
+        )
+    }
+}
+
+/// `traceparent` of the current `tracing` span's OpenTelemetry context.
+///
+/// Returns `None` when there is no valid OpenTelemetry span in scope, and
+/// always when the `tracing-opentelemetry` feature is disabled.
+pub fn current_traceparent() -> Option<String> {
+    #[cfg(feature = "tracing-opentelemetry")]
+    {
+        use opentelemetry::trace::TraceContextExt;
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        let cx = ::tracing::Span::current().context();
+        let span = cx.span();
+        let sc = span.span_context();
+        if !sc.is_valid() {
+            return None;
+        }
+        Some(
+            TraceParent {
+                trace_id: sc.trace_id().to_string(),
+                span_id: sc.span_id().to_string(),
+                flags: sc.trace_flags().to_u8(),
+            }
+            .to_string(),
+        )
+    }
+    #[cfg(not(feature = "tracing-opentelemetry"))]
+    {
+        None
+    }
+}
+
+/// Make `span` a child of the remote span described by `traceparent`.
+///
+/// Malformed values are ignored (the span stays a root span) — a corrupt
+/// header must never prevent the job from running. A no-op when the
+/// `tracing-opentelemetry` feature is disabled.
+pub fn set_remote_parent(span: &::tracing::Span, traceparent: &str) {
+    let Some(parent) = TraceParent::parse(traceparent) else {
+        ::tracing::debug!("Ignoring malformed traceparent {traceparent:?}");
+        return;
+    };
+
+    #[cfg(feature = "tracing-opentelemetry")]
+    {
+        use opentelemetry::trace::{
+            SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState,
+        };
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        let (Ok(trace_id), Ok(span_id)) = (
+            TraceId::from_hex(&parent.trace_id),
+            SpanId::from_hex(&parent.span_id),
+        ) else {
+            return;
+        };
+        let remote = SpanContext::new(
+            trace_id,
+            span_id,
+            TraceFlags::new(parent.flags),
+            true,
+            TraceState::default(),
+        );
+        let cx = opentelemetry::Context::new().with_remote_span_context(remote);
+        if let Err(e) = span.set_parent(cx) {
+            ::tracing::debug!("Could not attach remote trace parent: {e}");
+        }
+    }
+    #[cfg(not(feature = "tracing-opentelemetry"))]
+    let _ = (span, parent);
+}
+
 /// Distributed tracing integration for job processing
 pub struct DistributedTracing;
 
@@ -126,6 +257,31 @@ mod tests {
         // Test passes - basic tracing functionality works
     }
 
+    #[test]
+    fn test_traceparent_round_trip() {
+        let value = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let parsed = TraceParent::parse(value).unwrap();
+        assert_eq!(parsed.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(parsed.span_id, "00f067aa0ba902b7");
+        assert_eq!(parsed.flags, 1);
+        assert_eq!(parsed.to_string(), value);
+    }
+
+    #[test]
+    fn test_traceparent_rejects_malformed_values() {
+        for value in [
+            "",
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+        ] {
+            assert!(TraceParent::parse(value).is_none(), "{value:?}");
+        }
+    }
+
     #[test]
     fn test_span_collector() {
         let collector = SpanCollector::new();
@@ -0,0 +1,640 @@
+//! Read-only web dashboard for queue inspection (`ui` feature).
+//!
+//! [`WebUI`] is an Axum [`Router`] factory. It renders plain server-side HTML
+//! (no asset pipeline, no frontend framework) plus a few lines of JavaScript
+//! that subscribe to a Server-Sent Events feed backed by
+//! [`QueueBackend::event_stream`].
+//!
+//! # Pages
+//!
+//! | Route                       | Content                                            |
+//! |-----------------------------|----------------------------------------------------|
+//! | `GET  /?tenant=…`           | queues with depth, latency, recent jobs, live log  |
+//! | `GET  /jobs/{id}?tenant=…`  | full record from [`QueueBackend::get_record`]      |
+//! | `GET  /events?tenant=…`     | SSE feed of the tenant's [`JobEvent`]s as JSON     |
+//! | `POST /jobs/{id}/cancel`    | cancel a job (requires an action guard)            |
+//! | `POST /jobs/{id}/requeue`   | requeue a failed job (requires an action guard)    |
+//!
+//! Every page is scoped to one tenant, selected with the `tenant` query
+//! parameter (or [`WebUI::with_default_tenant`]), so the dashboard never
+//! mixes jobs across tenants.
+//!
+//! # Mounting alongside a dog-axum app
+//!
+//! ```rust,ignore
+//! use dog_queue::WebUI;
+//!
+//! let ui = WebUI::new(&adapter)
+//!     // Must match the path the router is nested under so links resolve.
+//!     .with_base_path("/admin/queue")
+//!     // Without a guard the dashboard is strictly read-only.
+//!     .with_actions(|headers| {
+//!         headers
+//!             .get("x-admin-token")
+//!             .is_some_and(|v| v == "let-me-in")
+//!     });
+//!
+//! let ax = dog_axum::axum(app).use_router("/admin/queue", ui.router());
+//! ```
+//!
+//! The dashboard itself performs no authentication; put it behind the same
+//! middleware as the rest of your admin surface.
+
+use std::convert::Infallible;
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, IntoResponse, Redirect, Response,
+    },
+    routing::{get, post},
+    Router,
+};
+use serde::Deserialize;
+use tokio_stream::StreamExt;
+
+use crate::{
+    backend::QueueBackend, JobId, JobRecord, JobStatus, LiveMetrics, QueueAdapter, QueueCtx,
+    QueueError,
+};
+
+/// Authorizes the dashboard's mutating actions from the request headers.
+pub type ActionGuard = Arc<dyn Fn(&HeaderMap) -> bool + Send + Sync>;
+
+/// Axum router factory for the queue dashboard. See the [module docs](self).
+#[derive(Clone)]
+pub struct WebUI {
+    backend: Arc<dyn QueueBackend + Send + Sync>,
+    metrics: Option<Arc<LiveMetrics>>,
+    base_path: String,
+    default_tenant: Option<String>,
+    action_guard: Option<ActionGuard>,
+    recent_limit: usize,
+}
+
+impl WebUI {
+    /// Dashboard over the adapter's backend and execution metrics.
+    pub fn new<B: QueueBackend + Send + Sync + 'static>(adapter: &QueueAdapter<B>) -> Self {
+        Self::from_backend(adapter.dyn_backend())
+            .with_metrics(adapter.observability().live_metrics())
+    }
+
+    /// Dashboard over a backend handle directly.
+    pub fn from_backend(backend: Arc<dyn QueueBackend + Send + Sync>) -> Self {
+        Self {
+            backend,
+            metrics: None,
+            base_path: String::new(),
+            default_tenant: None,
+            action_guard: None,
+            recent_limit: 50,
+        }
+    }
+
+    /// Path the router is nested under (e.g. `"/admin/queue"`), used to build
+    /// links and form targets. Defaults to the root.
+    pub fn with_base_path(mut self, base_path: impl Into<String>) -> Self {
+        self.base_path = base_path.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Show p50/p95/p99 execution latency per job type from `metrics`.
+    ///
+    /// Latency is process-wide, not per tenant: it comes from the workers
+    /// that share these metrics.
+    pub fn with_metrics(mut self, metrics: Arc<LiveMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Tenant shown when a request has no `tenant` query parameter.
+    pub fn with_default_tenant(mut self, tenant: impl Into<String>) -> Self {
+        self.default_tenant = Some(tenant.into());
+        self
+    }
+
+    /// Enable the cancel/requeue buttons, authorized per request by `guard`.
+    ///
+    /// Without a guard the action routes always answer `403 Forbidden` and
+    /// the buttons are not rendered.
+    pub fn with_actions<F>(mut self, guard: F) -> Self
+    where
+        F: Fn(&HeaderMap) -> bool + Send + Sync + 'static,
+    {
+        self.action_guard = Some(Arc::new(guard));
+        self
+    }
+
+    /// How many recent jobs the overview lists (default 50).
+    pub fn with_recent_limit(mut self, limit: usize) -> Self {
+        self.recent_limit = limit;
+        self
+    }
+
+    /// Build the router. Nest it under the configured base path.
+    pub fn router(self) -> Router {
+        Router::new()
+            .route("/", get(overview))
+            .route("/jobs/{id}", get(job_detail))
+            .route("/jobs/{id}/cancel", post(cancel_job))
+            .route("/jobs/{id}/requeue", post(requeue_job))
+            .route("/events", get(events))
+            .with_state(Arc::new(self))
+    }
+
+    fn tenant(&self, query: &TenantQuery) -> Option<String> {
+        query
+            .tenant
+            .clone()
+            .filter(|t| !t.is_empty())
+            .or_else(|| self.default_tenant.clone())
+    }
+
+    fn home_url(&self, tenant: &str) -> String {
+        let root = if self.base_path.is_empty() {
+            "/"
+        } else {
+            &self.base_path
+        };
+        format!("{root}?tenant={}", encode_query(tenant))
+    }
+
+    fn job_url(&self, job_id: &JobId, suffix: &str, tenant: &str) -> String {
+        format!(
+            "{}/jobs/{}{suffix}?tenant={}",
+            self.base_path,
+            encode_query(job_id.as_str()),
+            encode_query(tenant),
+        )
+    }
+
+    fn actions_allowed(&self, headers: &HeaderMap) -> bool {
+        self.action_guard
+            .as_ref()
+            .is_some_and(|guard| guard(headers))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TenantQuery {
+    tenant: Option<String>,
+}
+
+type UiState = State<Arc<WebUI>>;
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+async fn overview(State(ui): UiState, Query(query): Query<TenantQuery>) -> Response {
+    let Some(tenant) = ui.tenant(&query) else {
+        return page("dog-queue", TENANT_FORM.to_string()).into_response();
+    };
+    let ctx = QueueCtx::new(tenant.clone());
+
+    let queues = match ui.backend.list_queues(ctx.clone()).await {
+        Ok(queues) => queues,
+        Err(e) => return error_page(&e),
+    };
+    let records = match ui.backend.recent_records(ctx, ui.recent_limit).await {
+        Ok(records) => records,
+        Err(e) => return error_page(&e),
+    };
+
+    let mut body = format!("<p>Tenant <b>{}</b></p>{TENANT_FORM}", escape(&tenant));
+
+    body.push_str("<h2>Queues</h2><table><tr><th>Queue</th><th>Depth</th></tr>");
+    for q in &queues {
+        let _ = write!(
+            body,
+            "<tr><td>{}</td><td>{}</td></tr>",
+            escape(&q.queue),
+            q.depth
+        );
+    }
+    body.push_str("</table>");
+
+    if let Some(metrics) = &ui.metrics {
+        let performance = metrics.performance_metrics();
+        body.push_str(
+            "<h2>Latency</h2><table><tr><th>Job type</th>\
+             <th>p50</th><th>p95</th><th>p99</th></tr>",
+        );
+        for job_type in performance.job_types() {
+            let _ = write!(body, "<tr><td>{}</td>", escape(&job_type));
+            for value in performance.quantiles(&job_type, &[0.5, 0.95, 0.99]) {
+                let ms = value.map_or(0.0, |d| d.as_secs_f64() * 1000.0);
+                let _ = write!(body, "<td>{ms:.1} ms</td>");
+            }
+            body.push_str("</tr>");
+        }
+        body.push_str("</table>");
+    }
+
+    body.push_str(
+        "<h2>Recent jobs</h2><table><tr><th>Job</th><th>Type</th><th>Queue</th>\
+         <th>Status</th><th>Attempts</th><th>Updated</th><th>Last error</th></tr>",
+    );
+    for r in &records {
+        let _ = write!(
+            body,
+            "<tr><td><a href=\"{}\">{}</a></td><td>{}</td><td>{}</td>\
+             <td class=\"{status}\">{status}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(&ui.job_url(&r.job_id, "", &tenant)),
+            escape(r.job_id.as_str()),
+            escape(&r.message.job_type),
+            escape(&r.message.queue),
+            r.attempt,
+            r.updated_at.format("%Y-%m-%d %H:%M:%S"),
+            escape(r.last_error.as_deref().unwrap_or("")),
+            status = r.status.name(),
+        );
+    }
+    body.push_str("</table>");
+
+    let _ = write!(
+        body,
+        "<h2>Live events</h2><ul id=\"events\"></ul><script>\
+         const log = document.getElementById('events');\
+         const source = new EventSource('{}/events?tenant={}');\
+         source.onmessage = (msg) => {{\
+           const item = document.createElement('li');\
+           item.textContent = msg.data;\
+           log.prepend(item);\
+           while (log.children.length > 100) log.lastChild.remove();\
+         }};</script>",
+        escape(&ui.base_path),
+        escape(&encode_query(&tenant)),
+    );
+
+    page("dog-queue", body).into_response()
+}
+
+async fn job_detail(
+    State(ui): UiState,
+    Path(id): Path<String>,
+    Query(query): Query<TenantQuery>,
+    headers: HeaderMap,
+) -> Response {
+    let Some(tenant) = ui.tenant(&query) else {
+        return (StatusCode::BAD_REQUEST, "missing tenant").into_response();
+    };
+    let job_id = JobId::from(id);
+    let record = match ui
+        .backend
+        .get_record(QueueCtx::new(tenant.clone()), job_id.clone())
+        .await
+    {
+        Ok(record) => record,
+        Err(e) => return error_page(&e),
+    };
+
+    let mut body = format!(
+        "<p><a href=\"{}\">&larr; all jobs</a></p>",
+        escape(&ui.home_url(&tenant))
+    );
+    body.push_str(&record_table(&record));
+
+    if ui.actions_allowed(&headers) {
+        if !record.status.is_terminal() {
+            let _ = write!(
+                body,
+                "<form method=\"post\" action=\"{}\"><button>Cancel job</button></form>",
+                escape(&ui.job_url(&job_id, "/cancel", &tenant)),
+            );
+        }
+        if matches!(record.status, JobStatus::Failed { .. }) {
+            let _ = write!(
+                body,
+                "<form method=\"post\" action=\"{}\"><button>Requeue job</button></form>",
+                escape(&ui.job_url(&job_id, "/requeue", &tenant)),
+            );
+        }
+    }
+
+    page(&format!("Job {}", job_id), body).into_response()
+}
+
+async fn cancel_job(
+    State(ui): UiState,
+    Path(id): Path<String>,
+    Query(query): Query<TenantQuery>,
+    headers: HeaderMap,
+) -> Response {
+    run_action(
+        &ui,
+        id,
+        &query,
+        &headers,
+        |backend, ctx, job_id| async move { backend.cancel(ctx, job_id).await },
+    )
+    .await
+}
+
+async fn requeue_job(
+    State(ui): UiState,
+    Path(id): Path<String>,
+    Query(query): Query<TenantQuery>,
+    headers: HeaderMap,
+) -> Response {
+    run_action(
+        &ui,
+        id,
+        &query,
+        &headers,
+        |backend, ctx, job_id| async move { backend.requeue_failed(ctx, job_id).await },
+    )
+    .await
+}
+
+/// Shared guard → action → redirect-to-detail flow for the POST routes.
+async fn run_action<F, Fut>(
+    ui: &WebUI,
+    id: String,
+    query: &TenantQuery,
+    headers: &HeaderMap,
+    action: F,
+) -> Response
+where
+    F: FnOnce(Arc<dyn QueueBackend + Send + Sync>, QueueCtx, JobId) -> Fut,
+    Fut: std::future::Future<Output = Result<bool, QueueError>>,
+{
+    if !ui.actions_allowed(headers) {
+        return (StatusCode::FORBIDDEN, "actions are not permitted").into_response();
+    }
+    let Some(tenant) = ui.tenant(query) else {
+        return (StatusCode::BAD_REQUEST, "missing tenant").into_response();
+    };
+    let job_id = JobId::from(id);
+    match action(
+        ui.backend.clone(),
+        QueueCtx::new(tenant.clone()),
+        job_id.clone(),
+    )
+    .await
+    {
+        // Post/redirect/get: the detail page shows the outcome either way.
+        Ok(_) => Redirect::to(&ui.job_url(&job_id, "", &tenant)).into_response(),
+        Err(e) => error_page(&e),
+    }
+}
+
+async fn events(State(ui): UiState, Query(query): Query<TenantQuery>) -> Response {
+    let Some(tenant) = ui.tenant(&query) else {
+        return (StatusCode::BAD_REQUEST, "missing tenant").into_response();
+    };
+    let stream = ui.backend.event_stream(QueueCtx::new(tenant)).map(|event| {
+        let data = serde_json::to_string(&event).unwrap_or_default();
+        Ok::<_, Infallible>(Event::default().event(event.event_name()).data(data))
+    });
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+// ---------------------------------------------------------------------------
+// Rendering
+// ---------------------------------------------------------------------------
+
+const TENANT_FORM: &str = "<form method=\"get\"><label>Tenant \
+     <input name=\"tenant\" required></label> <button>Show</button></form>";
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2rem}\
+     table{border-collapse:collapse}td,th{border:1px solid #ccc;padding:.25rem .5rem;\
+     text-align:left}.failed{color:#b00}.completed{color:#070}.canceled{color:#777}";
+
+fn page(title: &str, body: String) -> Html<String> {
+    Html(format!(
+        "<!doctype html><html><head><meta charset=\"utf-8\"><title>{title}</title>\
+         <style>{STYLE}</style></head><body><h1>{title}</h1>{body}</body></html>",
+        title = escape(title),
+    ))
+}
+
+fn record_table(record: &JobRecord) -> String {
+    let mut rows = vec![
+        ("Status", record.status.name().to_string()),
+        ("Type", record.message.job_type.clone()),
+        ("Queue", record.message.queue.clone()),
+        ("Priority", format!("{:?}", record.message.priority)),
+        (
+            "Attempts",
+            format!(
+                "{} (max retries {})",
+                record.attempt, record.message.max_retries
+            ),
+        ),
+        ("Run at", record.message.run_at.to_rfc3339()),
+        ("Created", record.created_at.to_rfc3339()),
+        ("Updated", record.updated_at.to_rfc3339()),
+        ("Last error", record.last_error.clone().unwrap_or_default()),
+        ("Result", record.result.clone().unwrap_or_default()),
+    ];
+    if let Some(key) = &record.message.idempotency_key {
+        rows.push(("Idempotency key", key.clone()));
+    }
+    if let Some(lease_until) = record.lease_until() {
+        rows.push(("Lease until", lease_until.to_rfc3339()));
+    }
+    // Payloads are opaque bytes; show them when they are text (JSON codec).
+    if let Ok(payload) = std::str::from_utf8(&record.message.payload_bytes) {
+        rows.push(("Payload", payload.to_string()));
+    }
+
+    let mut table = String::from("<table>");
+    for (name, value) in rows {
+        let _ = write!(
+            table,
+            "<tr><th>{name}</th><td><code>{}</code></td></tr>",
+            escape(&value)
+        );
+    }
+    table.push_str("</table>");
+    table
+}
+
+fn error_page(error: &QueueError) -> Response {
+    let status = match error {
+        QueueError::JobNotFound(_) => StatusCode::NOT_FOUND,
+        QueueError::BackendUnsupported(_) => StatusCode::NOT_IMPLEMENTED,
+        QueueError::JobCanceled | QueueError::JobAlreadyTerminal => StatusCode::CONFLICT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (
+        status,
+        page("Error", format!("<p>{}</p>", escape(&error.to_string()))),
+    )
+        .into_response()
+}
+
+/// Minimal HTML escaping for text and attribute values.
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Percent-encode a query/path component (RFC 3986 unreserved set passes).
+fn encode_query(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for b in value.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b'~') {
+            out.push(b as char);
+        } else {
+            let _ = write!(out, "%{b:02X}");
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::memory::MemoryBackend;
+    use crate::JobMessage;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    async fn failed_job(backend: &MemoryBackend, ctx: &QueueCtx) -> JobId {
+        let message = JobMessage::new("email", b"{\"to\":\"a@b.c\"}".to_vec(), "json", "mail");
+        let job_id = backend.enqueue(ctx.clone(), message).await.unwrap();
+        let leased = backend
+            .dequeue(ctx.clone(), &["mail"])
+            .await
+            .unwrap()
+            .unwrap();
+        backend
+            .ack_fail(
+                ctx.clone(),
+                job_id.clone(),
+                leased.lease_token,
+                "smtp <down>".to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+        job_id
+    }
+
+    async fn body_text(response: Response) -> String {
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_overview_lists_queues_and_jobs_for_tenant() {
+        let backend = MemoryBackend::new();
+        let ctx = QueueCtx::new("acme");
+        let job_id = failed_job(&backend, &ctx).await;
+        let router = WebUI::from_backend(Arc::new(backend)).router();
+
+        let response = router
+            .oneshot(Request::get("/?tenant=acme").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let html = body_text(response).await;
+        assert!(html.contains(job_id.as_str()));
+        assert!(html.contains("<td>mail</td>"));
+        // Error text is escaped, not injected.
+        assert!(html.contains("smtp &lt;down&gt;"));
+    }
+
+    #[tokio::test]
+    async fn test_overview_shows_latency_percentiles() {
+        let metrics = Arc::new(LiveMetrics::new());
+        for ms in 1..=100 {
+            metrics.record_execution_time("email", std::time::Duration::from_millis(ms));
+        }
+        let router = WebUI::from_backend(Arc::new(MemoryBackend::new()))
+            .with_metrics(metrics)
+            .router();
+
+        let response = router
+            .oneshot(Request::get("/?tenant=acme").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let html = body_text(response).await;
+        assert!(html.contains("<h2>Latency</h2>"));
+        let row = html.split("<tr><td>email</td>").nth(1).unwrap();
+        let cells: Vec<f64> = row
+            .split("</tr>")
+            .next()
+            .unwrap()
+            .split("<td>")
+            .filter_map(|cell| cell.strip_suffix(" ms</td>"))
+            .map(|ms| ms.parse().unwrap())
+            .collect();
+        assert_eq!(cells.len(), 3);
+        for (ms, expected) in cells.iter().zip([50.0, 95.0, 99.0]) {
+            assert!(
+                (ms - expected).abs() <= 1.0,
+                "{ms} ms is not ~{expected} ms"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_actions_require_guard() {
+        let backend = MemoryBackend::new();
+        let ctx = QueueCtx::new("acme");
+        let job_id = failed_job(&backend, &ctx).await;
+        let uri = format!("/jobs/{job_id}/requeue?tenant=acme");
+
+        let read_only = WebUI::from_backend(Arc::new(backend.clone())).router();
+        let response = read_only
+            .oneshot(Request::post(&uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let guarded = WebUI::from_backend(Arc::new(backend.clone()))
+            .with_actions(|headers| headers.contains_key("x-admin"))
+            .router();
+        let response = guarded
+            .oneshot(
+                Request::post(&uri)
+                    .header("x-admin", "1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert!(matches!(
+            backend.get_status(ctx, job_id).await.unwrap(),
+            JobStatus::Enqueued
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_detail_of_other_tenant_is_not_found() {
+        let backend = MemoryBackend::new();
+        let job_id = failed_job(&backend, &QueueCtx::new("acme")).await;
+        let router = WebUI::from_backend(Arc::new(backend)).router();
+
+        let response = router
+            .oneshot(
+                Request::get(format!("/jobs/{job_id}?tenant=other"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -9,7 +9,8 @@ use std::sync::{
 use tokio::time::{sleep, Duration, Instant};
 
 use crate::{
-    backend::memory::MemoryBackend, Job, JobError, JobPriority, QueueAdapter, QueueCtx, QueueError,
+    backend::memory::MemoryBackend, CancellationToken, Job, JobError, JobPriority, JobStatus,
+    ProgressReporter, QueueAdapter, QueueCtx, QueueError, RetryBackoff,
 };
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -35,7 +36,12 @@ impl Job for CountingJob {
     const PRIORITY: JobPriority = JobPriority::Normal;
     const MAX_RETRIES: u32 = 3;
 
-    async fn execute(&self, ctx: Self::Context) -> Result<Self::Result, JobError> {
+    async fn execute(
+        &self,
+        ctx: Self::Context,
+        _cancel: CancellationToken,
+        _progress: ProgressReporter,
+    ) -> Result<Self::Result, JobError> {
         ctx.0.fetch_add(1, Ordering::SeqCst);
         Ok(format!("done:{}", self.label))
     }
@@ -55,7 +61,12 @@ impl Job for FailingJob {
     const PRIORITY: JobPriority = JobPriority::Normal;
     const MAX_RETRIES: u32 = 2;
 
-    async fn execute(&self, ctx: Self::Context) -> Result<Self::Result, JobError> {
+    async fn execute(
+        &self,
+        ctx: Self::Context,
+        _cancel: CancellationToken,
+        _progress: ProgressReporter,
+    ) -> Result<Self::Result, JobError> {
         ctx.0.fetch_add(1, Ordering::SeqCst);
         if self.permanent {
             Err(JobError::Permanent("always fails".to_string()))
@@ -65,6 +76,33 @@ impl Job for FailingJob {
     }
 }
 
+/// Fails retryably with a fixed one-hour backoff instead of the adapter default.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FixedBackoffJob;
+
+#[async_trait]
+impl Job for FixedBackoffJob {
+    type Context = Counter;
+    type Result = String;
+
+    const JOB_TYPE: &'static str = "fixed_backoff_job";
+    const PRIORITY: JobPriority = JobPriority::Normal;
+
+    fn retry_backoff(attempt: u32) -> Option<Duration> {
+        Some(RetryBackoff::Fixed(Duration::from_secs(3600)).delay(attempt))
+    }
+
+    async fn execute(
+        &self,
+        ctx: Self::Context,
+        _cancel: CancellationToken,
+        _progress: ProgressReporter,
+    ) -> Result<Self::Result, JobError> {
+        ctx.0.fetch_add(1, Ordering::SeqCst);
+        Err(JobError::Retryable("rate limited".to_string()))
+    }
+}
+
 fn make_adapter() -> QueueAdapter<MemoryBackend> {
     QueueAdapter::new(MemoryBackend::new())
 }
@@ -223,6 +261,10 @@ async fn test_idempotent_enqueue_executes_once() {
         max_retries: 3,
         run_at: chrono::Utc::now(),
         idempotency_key: Some("unique-op-123".to_string()),
+        idempotency_expires_at: None,
+        traceparent: None,
+        group_id: None,
+        delivery: crate::DeliveryMode::default(),
     };
 
     // Enqueue twice with the same idempotency key — should deduplicate
@@ -393,6 +435,10 @@ async fn test_lease_expiry_requeues_job() {
         max_retries: 3,
         run_at: chrono::Utc::now(),
         idempotency_key: None,
+        idempotency_expires_at: None,
+        traceparent: None,
+        group_id: None,
+        delivery: crate::DeliveryMode::default(),
     };
 
     let job_id = backend.enqueue(ctx.clone(), msg).await.unwrap();
@@ -469,3 +515,1203 @@ async fn test_fifo_within_same_priority() {
         "all 5 jobs should execute"
     );
 }
+
+// ---------------------------------------------------------------------------
+// 9. Graceful shutdown drain: in-flight jobs finish or have their lease released
+// ---------------------------------------------------------------------------
+
+#[derive(Clone, Serialize, Deserialize)]
+struct SlowJob {
+    millis: u64,
+}
+
+#[async_trait]
+impl Job for SlowJob {
+    type Context = Counter;
+    type Result = ();
+
+    const JOB_TYPE: &'static str = "slow_job";
+    const PRIORITY: JobPriority = JobPriority::Normal;
+    const MAX_RETRIES: u32 = 3;
+
+    async fn execute(
+        &self,
+        ctx: Self::Context,
+        _cancel: CancellationToken,
+        _progress: ProgressReporter,
+    ) -> Result<Self::Result, JobError> {
+        // Count when execution starts so tests can wait for the job to be in flight.
+        ctx.0.fetch_add(1, Ordering::SeqCst);
+        sleep(Duration::from_millis(self.millis)).await;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_shutdown_drains_in_flight_job_to_completion() {
+    use crate::backend::QueueBackend;
+    use crate::{DrainOptions, JobStatus};
+
+    let adapter = Arc::new(make_adapter());
+    adapter.register_job::<SlowJob>().await.unwrap();
+
+    let started = Counter(Arc::new(AtomicU32::new(0)));
+    let ctx = QueueCtx::new("tenant_drain".to_string());
+    let job_id = adapter
+        .enqueue(ctx.clone(), SlowJob { millis: 300 })
+        .await
+        .unwrap();
+
+    let handle = adapter
+        .start_workers(ctx.clone(), started.clone(), vec!["slow_job".to_string()])
+        .await
+        .unwrap();
+
+    let s = started.0.clone();
+    poll_until(
+        || s.load(Ordering::SeqCst) >= 1,
+        Duration::from_secs(5),
+        "slow job should start executing",
+    )
+    .await;
+
+    let summary = handle
+        .shutdown_with(DrainOptions::with_timeout(Duration::from_secs(5)))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        summary.completed, 1,
+        "in-flight job should finish inside the drain window"
+    );
+    assert_eq!(summary.released, 0);
+
+    let record = adapter.backend().get_record(ctx, job_id).await.unwrap();
+    assert!(matches!(record.status, JobStatus::Completed { .. }));
+}
+
+#[tokio::test]
+async fn test_shutdown_releases_lease_after_drain_timeout() {
+    use crate::backend::QueueBackend;
+    use crate::{DrainOptions, JobStatus};
+
+    let adapter = Arc::new(make_adapter());
+    adapter.register_job::<SlowJob>().await.unwrap();
+
+    let started = Counter(Arc::new(AtomicU32::new(0)));
+    let ctx = QueueCtx::new("tenant_release".to_string());
+    let job_id = adapter
+        .enqueue(ctx.clone(), SlowJob { millis: 60_000 })
+        .await
+        .unwrap();
+
+    let handle = adapter
+        .start_workers(ctx.clone(), started.clone(), vec!["slow_job".to_string()])
+        .await
+        .unwrap();
+
+    let s = started.0.clone();
+    poll_until(
+        || s.load(Ordering::SeqCst) >= 1,
+        Duration::from_secs(5),
+        "slow job should start executing",
+    )
+    .await;
+
+    let begun = Instant::now();
+    let summary = handle
+        .shutdown_with(DrainOptions::with_timeout(Duration::from_millis(100)))
+        .await
+        .unwrap();
+    assert!(
+        begun.elapsed() < Duration::from_secs(5),
+        "shutdown must not wait for the job beyond the drain timeout"
+    );
+
+    assert_eq!(summary.completed, 0);
+    assert_eq!(
+        summary.released, 1,
+        "still-running job should have its lease released"
+    );
+
+    // The lease is freed and the attempt rolled back: the job is immediately
+    // leasable by another worker without having burned a retry.
+    let record = adapter
+        .backend()
+        .get_record(ctx.clone(), job_id.clone())
+        .await
+        .unwrap();
+    assert!(matches!(record.status, JobStatus::Enqueued));
+    assert!(record.lease_token.is_none());
+    assert_eq!(record.attempt, 0);
+
+    let leased = adapter
+        .backend()
+        .dequeue(ctx, &["slow_job"])
+        .await
+        .unwrap()
+        .expect("released job should be dequeue-eligible immediately");
+    assert_eq!(leased.record.job_id, job_id);
+    assert_eq!(leased.record.attempt, 1);
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct SlowOnceJob {
+    millis: u64,
+}
+
+#[async_trait]
+impl Job for SlowOnceJob {
+    type Context = Counter;
+    type Result = ();
+
+    const JOB_TYPE: &'static str = "slow_once_job";
+    const PRIORITY: JobPriority = JobPriority::Normal;
+    const MAX_RETRIES: u32 = 3;
+    const DELIVERY: crate::DeliveryMode = crate::DeliveryMode::AtMostOnce;
+
+    async fn execute(
+        &self,
+        ctx: Self::Context,
+        _cancel: CancellationToken,
+        _progress: ProgressReporter,
+    ) -> Result<Self::Result, JobError> {
+        ctx.0.fetch_add(1, Ordering::SeqCst);
+        sleep(Duration::from_millis(self.millis)).await;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_shutdown_abandons_at_most_once_job_after_drain_timeout() {
+    use crate::backend::QueueBackend;
+    use crate::{DrainOptions, JobStatus};
+
+    let adapter = Arc::new(make_adapter());
+    adapter.register_job::<SlowOnceJob>().await.unwrap();
+
+    let started = Counter(Arc::new(AtomicU32::new(0)));
+    let ctx = QueueCtx::new("tenant_abandon".to_string());
+    let job_id = adapter
+        .enqueue(ctx.clone(), SlowOnceJob { millis: 60_000 })
+        .await
+        .unwrap();
+
+    let handle = adapter
+        .start_workers(
+            ctx.clone(),
+            started.clone(),
+            vec!["slow_once_job".to_string()],
+        )
+        .await
+        .unwrap();
+
+    let s = started.0.clone();
+    poll_until(
+        || s.load(Ordering::SeqCst) >= 1,
+        Duration::from_secs(5),
+        "slow job should start executing",
+    )
+    .await;
+
+    let summary = handle
+        .shutdown_with(DrainOptions::with_timeout(Duration::from_millis(100)))
+        .await
+        .unwrap();
+
+    assert_eq!(summary.completed, 0);
+    assert_eq!(summary.released, 0);
+    assert_eq!(
+        summary.abandoned, 1,
+        "at-most-once job still running should be abandoned, not released"
+    );
+
+    // Abandoned rather than released: the job is failed and never re-leased.
+    let record = adapter.backend().get_record(ctx, job_id).await.unwrap();
+    assert!(matches!(record.status, JobStatus::Failed { .. }));
+    assert!(record.lease_token.is_none());
+}
+
+// ---------------------------------------------------------------------------
+// 10. Per-queue concurrency: a saturated queue doesn't starve another queue
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_per_queue_concurrency_isolates_queues() {
+    use crate::{DrainOptions, QueueConfig};
+
+    let config = QueueConfig {
+        max_workers: 4,
+        per_queue_concurrency: [("slow_job".to_string(), 3), ("counting_job".to_string(), 1)]
+            .into_iter()
+            .collect(),
+        ..QueueConfig::default()
+    };
+    let adapter = Arc::new(QueueAdapter::with_config(MemoryBackend::new(), config));
+    adapter.register_job::<SlowJob>().await.unwrap();
+    adapter.register_job::<CountingJob>().await.unwrap();
+
+    let counter = Counter(Arc::new(AtomicU32::new(0)));
+    let ctx = QueueCtx::new("tenant_per_queue".to_string());
+
+    // Saturate the slow queue well beyond its 3 dedicated workers.
+    for _ in 0..6 {
+        adapter
+            .enqueue(ctx.clone(), SlowJob { millis: 60_000 })
+            .await
+            .unwrap();
+    }
+
+    let handle = adapter
+        .start_workers(
+            ctx.clone(),
+            counter.clone(),
+            vec!["slow_job".to_string(), "counting_job".to_string()],
+        )
+        .await
+        .unwrap();
+
+    let c = counter.0.clone();
+    poll_until(
+        || c.load(Ordering::SeqCst) >= 3,
+        Duration::from_secs(5),
+        "slow queue should occupy its 3 dedicated workers",
+    )
+    .await;
+
+    // The counting queue still has its own worker and runs promptly.
+    adapter
+        .enqueue(
+            ctx,
+            CountingJob {
+                label: "unblocked".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    poll_until(
+        || c.load(Ordering::SeqCst) >= 4,
+        Duration::from_secs(5),
+        "low-concurrency queue must not be blocked by the saturated queue",
+    )
+    .await;
+
+    // Extra slow jobs waited for their own queue's workers instead of
+    // spilling into the counting queue's worker.
+    sleep(Duration::from_millis(200)).await;
+    assert_eq!(counter.0.load(Ordering::SeqCst), 4);
+
+    let summary = handle
+        .shutdown_with(DrainOptions::with_timeout(Duration::from_millis(50)))
+        .await
+        .unwrap();
+    assert_eq!(summary.released, 3);
+}
+
+// ---------------------------------------------------------------------------
+// 11. Per-job retry backoff: a job type's override replaces the default policy
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_job_retry_backoff_override() {
+    use crate::backend::QueueBackend;
+
+    let adapter = Arc::new(make_adapter());
+    adapter.register_job::<FixedBackoffJob>().await.unwrap();
+
+    let counter = Counter(Arc::new(AtomicU32::new(0)));
+    let ctx = QueueCtx::new("tenant_backoff".to_string());
+
+    let job_id = adapter.enqueue(ctx.clone(), FixedBackoffJob).await.unwrap();
+
+    let handle = adapter
+        .start_workers(
+            ctx.clone(),
+            counter.clone(),
+            vec!["fixed_backoff_job".to_string()],
+        )
+        .await
+        .unwrap();
+
+    let c = counter.0.clone();
+    poll_until(
+        || c.load(Ordering::SeqCst) >= 1,
+        Duration::from_secs(5),
+        "job should run once",
+    )
+    .await;
+
+    // The default policy (base 1s, jittered) would retry within a second or
+    // two; the override pushes the retry an hour out.
+    let before = chrono::Utc::now();
+    let mut status = adapter
+        .backend()
+        .get_status(ctx.clone(), job_id.clone())
+        .await
+        .unwrap();
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !matches!(status, JobStatus::Retrying { .. }) && Instant::now() < deadline {
+        sleep(Duration::from_millis(10)).await;
+        status = adapter
+            .backend()
+            .get_status(ctx.clone(), job_id.clone())
+            .await
+            .unwrap();
+    }
+    handle.shutdown().await.unwrap();
+
+    match status {
+        JobStatus::Retrying { retry_at } => {
+            let delay = retry_at - before;
+            assert!(
+                delay > chrono::Duration::minutes(59),
+                "retry should be scheduled ~1h out, got {delay}"
+            );
+        }
+        other => panic!("expected Retrying, got {other:?}"),
+    }
+    assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+}
+
+// ---------------------------------------------------------------------------
+// 12. Trace propagation: the execution span is a child of the enqueuing span
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "tracing-opentelemetry")]
+#[tokio::test]
+async fn test_trace_context_propagates_from_enqueue_to_execute() {
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+    use tracing::Instrument;
+    use tracing_subscriber::prelude::*;
+
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("dog-queue-test")));
+    // current_thread runtime: spawned workers run on this thread and see the
+    // thread-local default subscriber.
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let adapter = Arc::new(make_adapter());
+    adapter.register_job::<CountingJob>().await.unwrap();
+    let counter = Counter(Arc::new(AtomicU32::new(0)));
+    let ctx = QueueCtx::new("tenant_tracing".to_string());
+
+    adapter
+        .enqueue(
+            ctx.clone(),
+            CountingJob {
+                label: "traced".to_string(),
+            },
+        )
+        .instrument(tracing::info_span!("http_request"))
+        .await
+        .unwrap();
+
+    let handle = adapter
+        .start_workers(ctx, counter.clone(), vec!["counting_job".to_string()])
+        .await
+        .unwrap();
+    let c = counter.0.clone();
+    poll_until(
+        || c.load(Ordering::SeqCst) >= 1,
+        Duration::from_secs(5),
+        "traced job should execute",
+    )
+    .await;
+    handle.shutdown().await.unwrap();
+
+    let spans = exporter.get_finished_spans().unwrap();
+    let span = |name: &str| {
+        spans
+            .iter()
+            .find(|s| s.name == name)
+            .unwrap_or_else(|| panic!("no exported span named {name}"))
+    };
+    let request = span("http_request");
+    let enqueue = span("enqueue_opts");
+    let execute = span("dog_queue.execute");
+
+    assert_eq!(
+        execute.span_context.trace_id(),
+        request.span_context.trace_id(),
+        "execution must join the producer's trace"
+    );
+    assert_eq!(
+        execute.parent_span_id,
+        enqueue.span_context.span_id(),
+        "execution span must be a child of the enqueue span"
+    );
+    assert!(execute.parent_span_is_remote);
+}
+
+// ---------------------------------------------------------------------------
+// 13. Delayed enqueue: a job is not leased before its run_at
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_enqueue_in_defers_lease_until_run_at() {
+    use crate::{backend::QueueBackend, TestClock};
+
+    let clock = TestClock::starting_now();
+    let adapter = Arc::new(QueueAdapter::new(
+        MemoryBackend::new().with_clock(clock.clone()),
+    ));
+    adapter.register_job::<CountingJob>().await.unwrap();
+    let ctx = QueueCtx::new("tenant_delayed".to_string());
+
+    let job_id = adapter
+        .enqueue_in(
+            ctx.clone(),
+            CountingJob {
+                label: "later".to_string(),
+            },
+            Duration::from_secs(2),
+        )
+        .await
+        .unwrap();
+
+    let counter = Counter(Arc::new(AtomicU32::new(0)));
+    let handle = adapter
+        .start_workers(
+            ctx.clone(),
+            counter.clone(),
+            vec!["counting_job".to_string()],
+        )
+        .await
+        .unwrap();
+
+    // Workers poll many times, but the clock has not reached run_at.
+    clock.advance(Duration::from_millis(1999));
+    sleep(Duration::from_millis(300)).await;
+    assert_eq!(
+        counter.0.load(Ordering::SeqCst),
+        0,
+        "a job whose run_at is in the future must not be leased"
+    );
+
+    clock.advance(Duration::from_millis(1));
+    let c = counter.0.clone();
+    poll_until(
+        || c.load(Ordering::SeqCst) >= 1,
+        Duration::from_secs(5),
+        "delayed job should run once run_at has passed",
+    )
+    .await;
+    handle.shutdown().await.unwrap();
+
+    let record = adapter.backend().get_record(ctx, job_id).await.unwrap();
+    assert_eq!(record.attempt, 1);
+}
+
+// ---------------------------------------------------------------------------
+// 14. Cancellation interrupts a running job that checks its token
+// ---------------------------------------------------------------------------
+
+/// Works in 10 ms steps for up to `steps` steps, bailing out as soon as its
+/// cancellation token fires. Adds 1 to the counter on start and 10 when it
+/// observes cancellation.
+#[derive(Clone, Serialize, Deserialize)]
+struct CooperativeJob {
+    steps: u32,
+}
+
+#[async_trait]
+impl Job for CooperativeJob {
+    type Context = Counter;
+    type Result = ();
+
+    const JOB_TYPE: &'static str = "cooperative_job";
+    const MAX_RETRIES: u32 = 3;
+
+    async fn execute(
+        &self,
+        ctx: Self::Context,
+        cancel: CancellationToken,
+        _progress: ProgressReporter,
+    ) -> Result<Self::Result, JobError> {
+        ctx.0.fetch_add(1, Ordering::SeqCst);
+        for _ in 0..self.steps {
+            if cancel.is_cancelled() {
+                ctx.0.fetch_add(10, Ordering::SeqCst);
+                // Returning Ok here must NOT complete the job.
+                return Ok(());
+            }
+            sleep(Duration::from_millis(10)).await;
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_cancel_interrupts_running_job() {
+    use crate::backend::QueueBackend;
+
+    let adapter = Arc::new(make_adapter());
+    adapter.register_job::<CooperativeJob>().await.unwrap();
+    let ctx = QueueCtx::new("tenant_cancel_running".to_string());
+
+    // 3000 steps ≈ 30s if the job were left to run to completion.
+    let job_id = adapter
+        .enqueue(ctx.clone(), CooperativeJob { steps: 3000 })
+        .await
+        .unwrap();
+
+    let counter = Counter(Arc::new(AtomicU32::new(0)));
+    let handle = adapter
+        .start_workers(
+            ctx.clone(),
+            counter.clone(),
+            vec!["cooperative_job".to_string()],
+        )
+        .await
+        .unwrap();
+
+    let c = counter.0.clone();
+    poll_until(
+        || c.load(Ordering::SeqCst) >= 1,
+        Duration::from_secs(5),
+        "job should start executing",
+    )
+    .await;
+
+    let canceled_at = Instant::now();
+    assert!(adapter.cancel(ctx.clone(), job_id.clone()).await.unwrap());
+
+    let c = counter.0.clone();
+    poll_until(
+        || c.load(Ordering::SeqCst) >= 11,
+        Duration::from_secs(2),
+        "running job should observe its cancellation token promptly",
+    )
+    .await;
+    assert!(canceled_at.elapsed() < Duration::from_secs(2));
+
+    // Give the worker time to (not) ack the result.
+    sleep(Duration::from_millis(100)).await;
+    let record = adapter.backend().get_record(ctx, job_id).await.unwrap();
+    assert!(
+        matches!(record.status, JobStatus::Canceled { .. }),
+        "a canceled job must stay Canceled, got {:?}",
+        record.status
+    );
+    assert!(
+        record.result.is_none(),
+        "canceled job must not store a result"
+    );
+
+    handle.shutdown().await.unwrap();
+}
+
+// ---------------------------------------------------------------------------
+// 15. await_result: typed results for request/response-style callers
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_await_result_waits_for_completion() {
+    let adapter = Arc::new(make_adapter());
+    adapter.register_job::<CountingJob>().await.unwrap();
+    let ctx = QueueCtx::new("tenant_await".to_string());
+
+    let job_id = adapter
+        .enqueue(
+            ctx.clone(),
+            CountingJob {
+                label: "reply".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+    // Start waiting before any worker exists, so the result arrives via the
+    // event stream rather than the initial status read.
+    let waiter = {
+        let adapter = adapter.clone();
+        let (ctx, job_id) = (ctx.clone(), job_id.clone());
+        tokio::spawn(async move {
+            adapter
+                .await_result::<CountingJob>(ctx, job_id, Duration::from_secs(5))
+                .await
+        })
+    };
+    sleep(Duration::from_millis(50)).await;
+
+    let counter = Counter(Arc::new(AtomicU32::new(0)));
+    let handle = adapter
+        .start_workers(ctx.clone(), counter, vec!["counting_job".to_string()])
+        .await
+        .unwrap();
+
+    assert_eq!(waiter.await.unwrap().unwrap(), "done:reply");
+
+    // The job is already terminal now: a second call resolves immediately.
+    let started = Instant::now();
+    let again = adapter
+        .await_result::<CountingJob>(ctx, job_id, Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(again, "done:reply");
+    assert!(started.elapsed() < Duration::from_millis(500));
+
+    handle.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_await_result_surfaces_failure_cancel_and_timeout() {
+    let adapter = Arc::new(make_adapter());
+    adapter.register_job::<FailingJob>().await.unwrap();
+    adapter.register_job::<CountingJob>().await.unwrap();
+    let ctx = QueueCtx::new("tenant_await_errors".to_string());
+
+    // No workers yet: the wait times out and leaves the job untouched.
+    let pending = adapter
+        .enqueue(
+            ctx.clone(),
+            CountingJob {
+                label: "never".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    let err = adapter
+        .await_result::<CountingJob>(ctx.clone(), pending.clone(), Duration::from_millis(100))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, QueueError::Timeout(_)), "got {err:?}");
+
+    adapter.cancel(ctx.clone(), pending.clone()).await.unwrap();
+    let err = adapter
+        .await_result::<CountingJob>(ctx.clone(), pending, Duration::from_secs(1))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, QueueError::JobCanceled), "got {err:?}");
+
+    let failing = adapter
+        .enqueue(ctx.clone(), FailingJob { permanent: true })
+        .await
+        .unwrap();
+    let counter = Counter(Arc::new(AtomicU32::new(0)));
+    let handle = adapter
+        .start_workers(ctx.clone(), counter, vec!["failing_job".to_string()])
+        .await
+        .unwrap();
+
+    let err = adapter
+        .await_result::<FailingJob>(ctx, failing, Duration::from_secs(5))
+        .await
+        .unwrap_err();
+    match err {
+        QueueError::JobFailed(JobError::Permanent(msg)) => assert!(msg.contains("always fails")),
+        other => panic!("expected JobFailed, got {other:?}"),
+    }
+
+    handle.shutdown().await.unwrap();
+}
+
+// ---------------------------------------------------------------------------
+// 16. Fan-out: the aggregation job fires once, after the last child
+// ---------------------------------------------------------------------------
+
+/// Aggregation job for test 16: adds 100 to the counter so children (+1 each)
+/// and aggregations can be told apart in a single counter.
+#[derive(Clone, Serialize, Deserialize)]
+struct SummaryJob {
+    group_id: crate::JobGroupId,
+}
+
+#[async_trait]
+impl Job for SummaryJob {
+    type Context = Counter;
+    type Result = ();
+
+    const JOB_TYPE: &'static str = "summary_job";
+
+    async fn execute(
+        &self,
+        ctx: Self::Context,
+        _cancel: CancellationToken,
+        _progress: ProgressReporter,
+    ) -> Result<Self::Result, JobError> {
+        ctx.0.fetch_add(100, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_fan_out_fires_one_aggregation_after_all_children() {
+    use crate::backend::QueueBackend;
+
+    let adapter = Arc::new(make_adapter());
+    adapter.register_job::<CountingJob>().await.unwrap();
+    adapter.register_job::<SummaryJob>().await.unwrap();
+    let ctx = QueueCtx::new("tenant_fan_out".to_string());
+
+    let children = (0..5)
+        .map(|i| CountingJob {
+            label: format!("track-{i}"),
+        })
+        .collect();
+    let group_id = adapter
+        .fan_out(ctx.clone(), children, |group_id| SummaryJob { group_id })
+        .await
+        .unwrap();
+
+    let group = adapter
+        .group_status(ctx.clone(), group_id.clone())
+        .await
+        .unwrap();
+    assert_eq!(group.total, 5);
+    assert_eq!(group.children.len(), 5);
+    assert!(group.aggregate_job_id.is_none());
+
+    let counter = Counter(Arc::new(AtomicU32::new(0)));
+    let handle = adapter
+        .start_workers(
+            ctx.clone(),
+            counter.clone(),
+            vec!["counting_job".to_string(), "summary_job".to_string()],
+        )
+        .await
+        .unwrap();
+
+    let c = counter.0.clone();
+    poll_until(
+        || c.load(Ordering::SeqCst) >= 105,
+        Duration::from_secs(5),
+        "all 5 children and the aggregation should run",
+    )
+    .await;
+    // Give a duplicate aggregation (the bug this guards against) time to run.
+    sleep(Duration::from_millis(200)).await;
+    assert_eq!(counter.0.load(Ordering::SeqCst), 105);
+
+    let group = adapter.group_status(ctx.clone(), group_id).await.unwrap();
+    assert_eq!(group.completed, 5);
+    assert!(group.is_finished() && !group.has_failures());
+    let aggregate_id = group.aggregate_job_id.expect("aggregation enqueued");
+    let record = adapter
+        .backend()
+        .get_record(ctx, aggregate_id)
+        .await
+        .unwrap();
+    assert!(matches!(record.status, JobStatus::Completed { .. }));
+
+    handle.shutdown().await.unwrap();
+}
+
+// ---------------------------------------------------------------------------
+// 17. Idempotency TTL: an expired key no longer deduplicates
+// ---------------------------------------------------------------------------
+
+#[derive(Clone, Serialize, Deserialize)]
+struct KeyedJob {
+    key: String,
+}
+
+#[async_trait]
+impl Job for KeyedJob {
+    type Context = Counter;
+    type Result = ();
+
+    const JOB_TYPE: &'static str = "keyed_job";
+
+    async fn execute(
+        &self,
+        _ctx: Self::Context,
+        _cancel: CancellationToken,
+        _progress: ProgressReporter,
+    ) -> Result<Self::Result, JobError> {
+        Ok(())
+    }
+
+    fn idempotency_key(&self) -> Option<std::borrow::Cow<'_, str>> {
+        Some(std::borrow::Cow::Borrowed(&self.key))
+    }
+}
+
+#[tokio::test]
+async fn test_idempotency_key_expires_after_ttl() {
+    use crate::{QueueConfig, TestClock};
+
+    let clock = TestClock::starting_now();
+    let config = QueueConfig {
+        idempotency_ttl: Some(Duration::from_secs(60)),
+        ..Default::default()
+    };
+    let adapter = QueueAdapter::with_config(MemoryBackend::new().with_clock(clock.clone()), config);
+    adapter.register_job::<KeyedJob>().await.unwrap();
+    let ctx = QueueCtx::new("tenant_idem_ttl".to_string());
+    let job = || KeyedJob {
+        key: "nightly-report".to_string(),
+    };
+
+    let first = adapter.enqueue(ctx.clone(), job()).await.unwrap();
+    clock.advance(Duration::from_secs(59));
+    assert_eq!(
+        adapter.enqueue(ctx.clone(), job()).await.unwrap(),
+        first,
+        "key is still live inside the TTL"
+    );
+
+    // Past the TTL a fresh job is created, even though `first` never ran.
+    clock.advance(Duration::from_secs(2));
+    let second = adapter.enqueue(ctx.clone(), job()).await.unwrap();
+    assert_ne!(second, first);
+    assert_eq!(adapter.enqueue(ctx.clone(), job()).await.unwrap(), second);
+
+    // The periodic sweep drops the expired entry.
+    clock.advance(Duration::from_secs(61));
+    assert_eq!(adapter.backend().sweep_expired_idempotency_keys().await, 1);
+    assert_ne!(adapter.enqueue(ctx, job()).await.unwrap(), second);
+}
+
+// ---------------------------------------------------------------------------
+// 18. Type-driven routing: a job's QUEUE and DEFAULT_DELAY apply without options
+// ---------------------------------------------------------------------------
+
+/// Routes itself onto the "media" queue, eligible 30 s after enqueue.
+#[derive(Clone, Serialize, Deserialize)]
+struct WaveformJob;
+
+#[async_trait]
+impl Job for WaveformJob {
+    type Context = Counter;
+    type Result = ();
+
+    const JOB_TYPE: &'static str = "generate_waveform";
+    const QUEUE: &'static str = "media";
+    const DEFAULT_DELAY: Option<Duration> = Some(Duration::from_secs(30));
+
+    async fn execute(
+        &self,
+        ctx: Self::Context,
+        _cancel: CancellationToken,
+        _progress: ProgressReporter,
+    ) -> Result<Self::Result, JobError> {
+        ctx.0.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_job_queue_and_default_delay_route_the_job() {
+    use crate::{backend::QueueBackend, codec::EnqueueOptions, Clock, TestClock};
+
+    let clock = TestClock::starting_now();
+    let adapter = Arc::new(QueueAdapter::new(
+        MemoryBackend::new().with_clock(clock.clone()),
+    ));
+    adapter.register_job::<WaveformJob>().await.unwrap();
+    let ctx = QueueCtx::new("tenant_routing".to_string());
+
+    let job_id = adapter.enqueue(ctx.clone(), WaveformJob).await.unwrap();
+    let record = adapter
+        .backend()
+        .get_record(ctx.clone(), job_id.clone())
+        .await
+        .unwrap();
+    assert_eq!(record.message.queue, "media");
+    assert_eq!(
+        record.message.run_at,
+        clock.now() + chrono::Duration::seconds(30)
+    );
+
+    // Neither the "default" queue nor the job-type name reach it.
+    clock.advance(Duration::from_secs(30));
+    let counter = Counter(Arc::new(AtomicU32::new(0)));
+    let handle = adapter
+        .start_workers(
+            ctx.clone(),
+            counter.clone(),
+            vec!["default".to_string(), "generate_waveform".to_string()],
+        )
+        .await
+        .unwrap();
+    sleep(Duration::from_millis(300)).await;
+    handle.shutdown().await.unwrap();
+    assert_eq!(counter.0.load(Ordering::SeqCst), 0);
+
+    let handle = adapter
+        .start_workers(ctx.clone(), counter.clone(), vec!["media".to_string()])
+        .await
+        .unwrap();
+    let c = counter.0.clone();
+    poll_until(
+        || c.load(Ordering::SeqCst) >= 1,
+        Duration::from_secs(5),
+        "a \"media\" worker should pick up the job",
+    )
+    .await;
+    handle.shutdown().await.unwrap();
+
+    // Explicit options still win over the job's defaults.
+    let now = clock.now();
+    let job_id = adapter
+        .enqueue_opts(
+            ctx.clone(),
+            WaveformJob,
+            EnqueueOptions::scheduled(now).with_queue("media-high"),
+        )
+        .await
+        .unwrap();
+    let record = adapter.backend().get_record(ctx, job_id).await.unwrap();
+    assert_eq!(record.message.queue, "media-high");
+    assert_eq!(record.message.run_at, now);
+}
+
+// ---------------------------------------------------------------------------
+// 19. Job-type filters: a restricted pool leaves other types on the queue
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_job_type_allowlist_leaves_other_types_queued() {
+    use crate::{backend::QueueBackend, codec::EnqueueOptions, JobTypeFilter};
+
+    let adapter = Arc::new(make_adapter());
+    adapter.register_job::<CountingJob>().await.unwrap();
+    adapter.register_job::<WaveformJob>().await.unwrap();
+    let ctx = QueueCtx::new("tenant_job_filter".to_string());
+    let shared = || EnqueueOptions::scheduled(chrono::Utc::now()).with_queue("shared");
+
+    // The waveform job is at the head of the queue, ahead of the counting job.
+    let waveform_id = adapter
+        .enqueue_opts(ctx.clone(), WaveformJob, shared())
+        .await
+        .unwrap();
+    let counting_id = adapter
+        .enqueue_opts(
+            ctx.clone(),
+            CountingJob {
+                label: "light".to_string(),
+            },
+            shared(),
+        )
+        .await
+        .unwrap();
+
+    let counter = Counter(Arc::new(AtomicU32::new(0)));
+    let handle = adapter
+        .start_workers_filtered(
+            ctx.clone(),
+            counter.clone(),
+            vec!["shared".to_string()],
+            JobTypeFilter::allow(["counting_job"]),
+        )
+        .await
+        .unwrap();
+    let c = counter.0.clone();
+    poll_until(
+        || c.load(Ordering::SeqCst) >= 1,
+        Duration::from_secs(5),
+        "the allowed job type should run",
+    )
+    .await;
+    sleep(Duration::from_millis(300)).await;
+    handle.shutdown().await.unwrap();
+
+    let backend = adapter.backend();
+    let counting = backend.get_record(ctx.clone(), counting_id).await.unwrap();
+    assert_eq!(counting.status.name(), "completed");
+    let waveform = backend
+        .get_record(ctx.clone(), waveform_id.clone())
+        .await
+        .unwrap();
+    assert_eq!(waveform.status.name(), "enqueued");
+    assert_eq!(waveform.attempt, 0, "a skipped job is not leased");
+
+    // A pool that excludes counting jobs picks the waveform job up.
+    let handle = adapter
+        .start_workers_filtered(
+            ctx.clone(),
+            counter.clone(),
+            vec!["shared".to_string()],
+            JobTypeFilter::deny(["counting_job"]),
+        )
+        .await
+        .unwrap();
+    poll_until(
+        || c.load(Ordering::SeqCst) >= 2,
+        Duration::from_secs(5),
+        "a pool that permits the remaining type should run it",
+    )
+    .await;
+    handle.shutdown().await.unwrap();
+    let waveform = backend.get_record(ctx, waveform_id).await.unwrap();
+    assert_eq!(waveform.status.name(), "completed");
+}
+
+// ---------------------------------------------------------------------------
+// 20. Progress: reports update the record and are debounced
+// ---------------------------------------------------------------------------
+
+/// Reports 25/50/75/100% in quick succession, then finishes.
+#[derive(Clone, Serialize, Deserialize)]
+struct TranscodeJob;
+
+#[async_trait]
+impl Job for TranscodeJob {
+    type Context = Counter;
+    type Result = ();
+
+    const JOB_TYPE: &'static str = "transcode_job";
+
+    async fn execute(
+        &self,
+        ctx: Self::Context,
+        _cancel: CancellationToken,
+        progress: ProgressReporter,
+    ) -> Result<Self::Result, JobError> {
+        for percent in [25, 50, 75, 100] {
+            progress
+                .report(percent, format!("{percent}% encoded"))
+                .await;
+        }
+        ctx.0.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_progress_reports_update_the_record() {
+    use crate::backend::QueueBackend;
+    use tokio_stream::StreamExt;
+
+    let adapter = Arc::new(make_adapter());
+    adapter.register_job::<TranscodeJob>().await.unwrap();
+    let ctx = QueueCtx::new("tenant_progress".to_string());
+    let mut events = adapter.backend().event_stream(ctx.clone());
+
+    let job_id = adapter.enqueue(ctx.clone(), TranscodeJob).await.unwrap();
+    let counter = Counter(Arc::new(AtomicU32::new(0)));
+    let handle = adapter
+        .start_workers(
+            ctx.clone(),
+            counter.clone(),
+            vec!["transcode_job".to_string()],
+        )
+        .await
+        .unwrap();
+    let c = counter.0.clone();
+    poll_until(
+        || c.load(Ordering::SeqCst) >= 1,
+        Duration::from_secs(5),
+        "transcode job should run",
+    )
+    .await;
+    handle.shutdown().await.unwrap();
+
+    let record = adapter.backend().get_record(ctx, job_id).await.unwrap();
+    let latest = record.progress.expect("progress should be stored");
+    assert_eq!(latest.percent, 100);
+    assert_eq!(latest.message, "100% encoded");
+
+    // Within the default 1 s progress_interval only the first report and
+    // the final 100% get through; 100% replaces the held-back 75%.
+    let mut reported = Vec::new();
+    while let Ok(Some(event)) =
+        tokio::time::timeout(Duration::from_millis(100), events.next()).await
+    {
+        if let crate::JobEvent::Progress { percent, .. } = event {
+            reported.push(percent);
+        }
+    }
+    assert_eq!(reported, vec![25, 100]);
+}
+
+/// Reports 10, 20 and 30 percent, pausing `pause_ms` after the first two.
+#[derive(Clone, Serialize, Deserialize)]
+struct StalledTranscodeJob {
+    pause_ms: u64,
+}
+
+#[async_trait]
+impl Job for StalledTranscodeJob {
+    type Context = Counter;
+    type Result = ();
+
+    const JOB_TYPE: &'static str = "stalled_transcode_job";
+
+    async fn execute(
+        &self,
+        ctx: Self::Context,
+        _cancel: CancellationToken,
+        progress: ProgressReporter,
+    ) -> Result<Self::Result, JobError> {
+        progress.report(10, "10% encoded").await;
+        progress.report(20, "20% encoded").await;
+        sleep(Duration::from_millis(self.pause_ms)).await;
+        progress.report(30, "30% encoded").await;
+        ctx.0.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+/// Progress percentages emitted for `job_pause_ms`, run with a 100 ms
+/// progress interval.
+async fn debounced_progress(job_pause_ms: u64) -> Vec<u8> {
+    use crate::backend::QueueBackend;
+    use crate::QueueConfig;
+    use tokio_stream::StreamExt;
+
+    let config = QueueConfig {
+        progress_interval: Duration::from_millis(100),
+        ..QueueConfig::default()
+    };
+    let adapter = Arc::new(QueueAdapter::with_config(MemoryBackend::new(), config));
+    adapter.register_job::<StalledTranscodeJob>().await.unwrap();
+    let ctx = QueueCtx::new("tenant_debounced_progress".to_string());
+    let mut events = adapter.backend().event_stream(ctx.clone());
+
+    adapter
+        .enqueue(
+            ctx.clone(),
+            StalledTranscodeJob {
+                pause_ms: job_pause_ms,
+            },
+        )
+        .await
+        .unwrap();
+    let counter = Counter(Arc::new(AtomicU32::new(0)));
+    let handle = adapter
+        .start_workers(
+            ctx.clone(),
+            counter.clone(),
+            vec!["stalled_transcode_job".to_string()],
+        )
+        .await
+        .unwrap();
+    let c = counter.0.clone();
+    poll_until(
+        || c.load(Ordering::SeqCst) >= 1,
+        Duration::from_secs(5),
+        "stalled transcode job should run",
+    )
+    .await;
+    handle.shutdown().await.unwrap();
+
+    let mut reported = Vec::new();
+    while let Ok(Some(event)) =
+        tokio::time::timeout(Duration::from_millis(100), events.next()).await
+    {
+        if let crate::JobEvent::Progress { percent, .. } = event {
+            reported.push(percent);
+        }
+    }
+    reported
+}
+
+#[tokio::test]
+async fn test_debounced_progress_is_sent_when_the_interval_expires() {
+    // 20% is held back, then sent once the interval is up; 30% comes more
+    // than an interval later, so it goes straight through.
+    assert_eq!(debounced_progress(300).await, vec![10, 20, 30]);
+}
+
+#[tokio::test]
+async fn test_debounced_progress_is_sent_when_the_job_finishes() {
+    // 20% is replaced by 30%, which is held back until the job finishes.
+    assert_eq!(debounced_progress(0).await, vec![10, 30]);
+}
@@ -15,6 +15,12 @@ pub struct QueueCtx {
 
     /// Additional tags for observability and filtering
     pub tags: HashMap<String, String>,
+
+    /// Key/value metadata persisted on every job enqueued with this context
+    /// (see [`JobRecord::metadata`](crate::JobRecord::metadata)), for tracing
+    /// a job back to the request or user that created it.
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
 }
 
 impl QueueCtx {
@@ -31,6 +37,7 @@ impl QueueCtx {
             trace_id: None,
             request_id: None,
             tags: HashMap::new(),
+            metadata: HashMap::new(),
         }
     }
 
@@ -61,6 +68,20 @@ impl QueueCtx {
         self
     }
 
+    /// Add a metadata entry to persist on jobs enqueued with this context.
+    ///
+    /// Unlike tags, metadata is stored on the `JobRecord` and echoed in its
+    /// `Enqueued` event, e.g. `.with_meta("user_id", user.id)`.
+    pub fn with_meta(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Get a metadata value by key
+    pub fn get_meta(&self, key: &str) -> Option<&str> {
+        self.metadata.get(key).map(|s| s.as_str())
+    }
+
     /// Get a tag value by key.
     ///
     /// Returns `Option<&str>` rather than `Option<&String>` — idiomatic Rust;
@@ -1,10 +1,16 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use super::JobId;
 
-/// Minimal stable event protocol for structured observability
+/// Minimal stable event protocol for structured observability.
+///
+/// Fields are only ever added, with `#[serde(default)]`, so consumers can
+/// still read events stored by older versions. Variants may be added too,
+/// so matches outside this crate need a wildcard arm.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum JobEvent {
     /// Job was enqueued
     Enqueued {
@@ -12,6 +18,11 @@ pub enum JobEvent {
         tenant_id: String,
         queue: String,
         job_type: String,
+        /// Metadata the job was enqueued with (`JobRecord::metadata`).
+        /// Later events carry the same `job_id`, so this is enough to
+        /// correlate the whole lifecycle.
+        #[serde(default)]
+        metadata: HashMap<String, String>,
         at: DateTime<Utc>,
     },
 
@@ -19,6 +30,13 @@ pub enum JobEvent {
     Leased {
         job_id: JobId,
         tenant_id: String,
+        /// Queue the job belongs to. Empty for events recorded before this
+        /// field was added.
+        #[serde(default)]
+        queue: String,
+        /// Attempt number, starting at 1 for the first lease.
+        #[serde(default)]
+        attempt: u32,
         lease_until: DateTime<Utc>,
         at: DateTime<Utc>,
     },
@@ -27,6 +45,17 @@ pub enum JobEvent {
     Retrying {
         job_id: JobId,
         tenant_id: String,
+        /// Queue the job belongs to. Empty for events recorded before this
+        /// field was added.
+        #[serde(default)]
+        queue: String,
+        /// Attempt number, starting at 1 for the first lease.
+        #[serde(default)]
+        attempt: u32,
+        /// Time from lease to this outcome in milliseconds; `None` when no
+        /// worker reported it (e.g. the lease expired).
+        #[serde(default)]
+        duration_ms: Option<u64>,
         retry_at: DateTime<Utc>,
         error: String,
         at: DateTime<Utc>,
@@ -36,6 +65,17 @@ pub enum JobEvent {
     Completed {
         job_id: JobId,
         tenant_id: String,
+        /// Queue the job belongs to. Empty for events recorded before this
+        /// field was added.
+        #[serde(default)]
+        queue: String,
+        /// Attempt number, starting at 1 for the first lease.
+        #[serde(default)]
+        attempt: u32,
+        /// Time from lease to this outcome in milliseconds; `None` when no
+        /// worker reported it (e.g. the lease expired).
+        #[serde(default)]
+        duration_ms: Option<u64>,
         at: DateTime<Utc>,
     },
 
@@ -43,6 +83,17 @@ pub enum JobEvent {
     Failed {
         job_id: JobId,
         tenant_id: String,
+        /// Queue the job belongs to. Empty for events recorded before this
+        /// field was added.
+        #[serde(default)]
+        queue: String,
+        /// Attempt number, starting at 1 for the first lease.
+        #[serde(default)]
+        attempt: u32,
+        /// Time from lease to this outcome in milliseconds; `None` when no
+        /// worker reported it (e.g. the lease expired).
+        #[serde(default)]
+        duration_ms: Option<u64>,
         error: String,
         at: DateTime<Utc>,
     },
@@ -51,6 +102,19 @@ pub enum JobEvent {
     Canceled {
         job_id: JobId,
         tenant_id: String,
+        /// Queue the job belongs to. Empty for events recorded before this
+        /// field was added.
+        #[serde(default)]
+        queue: String,
+        at: DateTime<Utc>,
+    },
+
+    /// Job lease was released back to the queue without completing, e.g. when
+    /// a worker pool shut down before the job finished draining.
+    Released {
+        job_id: JobId,
+        tenant_id: String,
+        queue: String,
         at: DateTime<Utc>,
     },
 
@@ -63,9 +127,24 @@ pub enum JobEvent {
     HeartbeatExtended {
         job_id: JobId,
         tenant_id: String,
+        /// Queue the job belongs to. Empty for events recorded before this
+        /// field was added.
+        #[serde(default)]
+        queue: String,
         new_lease_until: DateTime<Utc>,
         at: DateTime<Utc>,
     },
+
+    /// Running job reported progress (see `ProgressReporter`).
+    Progress {
+        job_id: JobId,
+        tenant_id: String,
+        queue: String,
+        /// Percent complete, 0–100.
+        percent: u8,
+        message: String,
+        at: DateTime<Utc>,
+    },
 }
 
 impl JobEvent {
@@ -78,7 +157,9 @@ impl JobEvent {
             Self::Completed { .. } => "completed",
             Self::Failed { .. } => "failed",
             Self::Canceled { .. } => "canceled",
+            Self::Released { .. } => "released",
             Self::HeartbeatExtended { .. } => "heartbeat_extended",
+            Self::Progress { .. } => "progress",
         }
     }
 
@@ -94,7 +175,43 @@ impl JobEvent {
             | Self::Completed { tenant_id, .. }
             | Self::Failed { tenant_id, .. }
             | Self::Canceled { tenant_id, .. }
-            | Self::HeartbeatExtended { tenant_id, .. } => tenant_id,
+            | Self::Released { tenant_id, .. }
+            | Self::HeartbeatExtended { tenant_id, .. }
+            | Self::Progress { tenant_id, .. } => tenant_id,
+        }
+    }
+
+    /// Get the queue name from any event
+    pub fn queue(&self) -> &str {
+        match self {
+            Self::Enqueued { queue, .. }
+            | Self::Leased { queue, .. }
+            | Self::Retrying { queue, .. }
+            | Self::Completed { queue, .. }
+            | Self::Failed { queue, .. }
+            | Self::Canceled { queue, .. }
+            | Self::Released { queue, .. }
+            | Self::HeartbeatExtended { queue, .. }
+            | Self::Progress { queue, .. } => queue,
+        }
+    }
+
+    /// Metadata carried by the event; only `Enqueued` events have it
+    pub fn metadata(&self) -> Option<&HashMap<String, String>> {
+        match self {
+            Self::Enqueued { metadata, .. } => Some(metadata),
+            _ => None,
+        }
+    }
+
+    /// Attempt number for events tied to one attempt (lease, retry, outcome)
+    pub fn attempt(&self) -> Option<u32> {
+        match self {
+            Self::Leased { attempt, .. }
+            | Self::Retrying { attempt, .. }
+            | Self::Completed { attempt, .. }
+            | Self::Failed { attempt, .. } => Some(*attempt),
+            _ => None,
         }
     }
 
@@ -107,7 +224,9 @@ impl JobEvent {
             | Self::Completed { job_id, .. }
             | Self::Failed { job_id, .. }
             | Self::Canceled { job_id, .. }
-            | Self::HeartbeatExtended { job_id, .. } => job_id,
+            | Self::Released { job_id, .. }
+            | Self::HeartbeatExtended { job_id, .. }
+            | Self::Progress { job_id, .. } => job_id,
         }
     }
 
@@ -120,7 +239,179 @@ impl JobEvent {
             | Self::Completed { at, .. }
             | Self::Failed { at, .. }
             | Self::Canceled { at, .. }
-            | Self::HeartbeatExtended { at, .. } => at,
+            | Self::Released { at, .. }
+            | Self::HeartbeatExtended { at, .. }
+            | Self::Progress { at, .. } => at,
+        }
+    }
+}
+
+/// Opaque position in a backend's event log.
+///
+/// Every event delivered through [`QueueBackend::event_stream_filtered`] or
+/// [`QueueBackend::replay_from`] carries the cursor that identifies it. A
+/// consumer persists the last cursor it processed and, after reconnecting,
+/// passes it to `replay_from` to receive everything emitted since.
+///
+/// The token format is backend-specific (a sequence number for
+/// `MemoryBackend`, a stream entry ID for Redis Streams) and must not be
+/// interpreted by callers. It serializes as a plain string so it can be
+/// stored or sent to a browser as-is.
+///
+/// [`QueueBackend::event_stream_filtered`]: crate::QueueBackend::event_stream_filtered
+/// [`QueueBackend::replay_from`]: crate::QueueBackend::replay_from
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EventCursor(String);
+
+impl EventCursor {
+    /// Wrap a backend-issued token. Intended for backend implementations and
+    /// for restoring a cursor previously obtained from [`EventCursor::as_str`].
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(token.into())
+    }
+
+    /// Get the token string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for EventCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// An event together with its log position and the job's routing fields.
+///
+/// [`JobEvent`] variants do not carry the job type; it is attached here,
+/// with the queue, so dashboards can group events without a record lookup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CursoredEvent {
+    /// Position of this event; pass to `replay_from` to resume after it.
+    pub cursor: EventCursor,
+    /// Queue the job belongs to.
+    pub queue: String,
+    /// Job type string (from `JobMessage::job_type`).
+    pub job_type: String,
+    /// The event itself.
+    pub event: JobEvent,
+}
+
+/// Selects which events a filtered stream delivers.
+///
+/// Every criterion that is set must match (logical AND); unset criteria match
+/// everything, so `EventFilter::default()` passes all of the tenant's events.
+///
+/// ```
+/// use dog_queue::types::EventFilter;
+///
+/// // Terminal transitions of `send_email` jobs on the `mail` queue.
+/// let filter = EventFilter::new()
+///     .queue("mail")
+///     .job_type("send_email")
+///     .events(["completed", "failed"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventFilter {
+    /// Only events for jobs on this queue.
+    pub queue: Option<String>,
+    /// Only events for jobs of this type.
+    pub job_type: Option<String>,
+    /// Only these status transitions, by [`JobEvent::event_name`]
+    /// (e.g. `"completed"`, `"retrying"`). Empty means all.
+    pub events: Vec<String>,
+    /// Only events for this job.
+    pub job_id: Option<JobId>,
+}
+
+impl EventFilter {
+    /// Filter that matches every event (same as `Default`).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restrict to jobs on `queue`.
+    pub fn queue(mut self, queue: impl Into<String>) -> Self {
+        self.queue = Some(queue.into());
+        self
+    }
+
+    /// Restrict to jobs of `job_type`.
+    pub fn job_type(mut self, job_type: impl Into<String>) -> Self {
+        self.job_type = Some(job_type.into());
+        self
+    }
+
+    /// Restrict to the given event names (see [`JobEvent::event_name`]).
+    pub fn events<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.events = names.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Restrict to a single job.
+    pub fn job_id(mut self, job_id: JobId) -> Self {
+        self.job_id = Some(job_id);
+        self
+    }
+
+    /// Whether `event` (for a job on `queue` with type `job_type`) passes.
+    pub fn matches(&self, queue: &str, job_type: &str, event: &JobEvent) -> bool {
+        self.queue.as_deref().is_none_or(|q| q == queue)
+            && self.job_type.as_deref().is_none_or(|t| t == job_type)
+            && (self.events.is_empty() || self.events.iter().any(|n| n == event.event_name()))
+            && self.job_id.as_ref().is_none_or(|id| id == event.job_id())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn completed(job_id: &JobId) -> JobEvent {
+        JobEvent::Completed {
+            job_id: job_id.clone(),
+            tenant_id: "t".to_string(),
+            queue: "q".to_string(),
+            attempt: 1,
+            duration_ms: Some(5),
+            at: Utc::now(),
         }
     }
+
+    #[test]
+    fn test_default_filter_matches_everything() {
+        let id = JobId::new();
+        assert!(EventFilter::default().matches("q", "t", &completed(&id)));
+    }
+
+    #[test]
+    fn test_filter_criteria_are_anded() {
+        let id = JobId::new();
+        let filter = EventFilter::new()
+            .queue("mail")
+            .events(["completed"])
+            .job_id(id.clone());
+
+        assert!(filter.matches("mail", "any", &completed(&id)));
+        assert!(!filter.matches("other", "any", &completed(&id)));
+        assert!(!filter.matches("mail", "any", &completed(&JobId::new())));
+
+        let failed = JobEvent::Failed {
+            job_id: id,
+            tenant_id: "t".to_string(),
+            queue: "mail".to_string(),
+            attempt: 1,
+            duration_ms: None,
+            error: "boom".to_string(),
+            at: Utc::now(),
+        };
+        assert!(!filter.matches("mail", "any", &failed));
+    }
 }
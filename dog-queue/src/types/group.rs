@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{JobGroupId, JobId};
+
+/// Progress of a fan-out created by `QueueAdapter::fan_out`.
+///
+/// The backend counts each child once, at its first terminal transition
+/// (completed, permanently failed, or canceled). When every child has settled
+/// it enqueues the group's aggregation job exactly once and records its ID in
+/// `aggregate_job_id`. Children that failed or were canceled do not hold the
+/// aggregation back — the aggregation job reads this status (via
+/// `QueueAdapter::group_status`) to see partial failures.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobGroup {
+    /// Group identifier, also stamped on every child's `JobMessage::group_id`.
+    pub group_id: JobGroupId,
+    /// Number of children the group was created with.
+    pub total: usize,
+    /// Children enqueued so far, in enqueue order.
+    pub children: Vec<JobId>,
+    /// Children that completed successfully.
+    pub completed: usize,
+    /// Children that failed permanently (retries exhausted or permanent error).
+    pub failed: usize,
+    /// Children that were canceled.
+    pub canceled: usize,
+    /// The aggregation job, once every child has settled.
+    pub aggregate_job_id: Option<JobId>,
+    /// When the group was created.
+    pub created_at: DateTime<Utc>,
+}
+
+impl JobGroup {
+    /// Create an empty group expecting `total` children.
+    pub fn new(group_id: JobGroupId, total: usize, now: DateTime<Utc>) -> Self {
+        Self {
+            group_id,
+            total,
+            children: Vec::with_capacity(total),
+            completed: 0,
+            failed: 0,
+            canceled: 0,
+            aggregate_job_id: None,
+            created_at: now,
+        }
+    }
+
+    /// Children that reached a terminal state, whatever the outcome.
+    pub fn settled(&self) -> usize {
+        self.completed + self.failed + self.canceled
+    }
+
+    /// Whether every child has settled.
+    pub fn is_finished(&self) -> bool {
+        self.settled() >= self.total
+    }
+
+    /// Whether any child failed or was canceled.
+    pub fn has_failures(&self) -> bool {
+        self.failed + self.canceled > 0
+    }
+}
@@ -63,6 +63,34 @@ impl From<&str> for JobId {
     }
 }
 
+/// Unique identifier for a fan-out group (see [`JobGroup`](super::JobGroup))
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct JobGroupId(String);
+
+impl JobGroupId {
+    /// Generate a new unique group ID
+    pub fn new() -> Self {
+        Self(Uuid::new_v4().to_string())
+    }
+
+    /// Get the inner string value
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for JobGroupId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for JobGroupId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 /// Lease token for job processing - prevents concurrent processing
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct LeaseToken(String);
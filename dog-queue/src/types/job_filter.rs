@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Restricts which job types a worker pool leases.
+///
+/// Lets a pool be dedicated to heavy job types (or kept away from them)
+/// without giving those types their own queue. Jobs a worker may not take
+/// stay queued for other workers.
+///
+/// ```
+/// use dog_queue::types::JobTypeFilter;
+///
+/// let heavy = JobTypeFilter::allow(["transcode_audio"]);
+/// assert!(heavy.permits("transcode_audio"));
+/// assert!(!heavy.permits("send_email"));
+///
+/// let light = JobTypeFilter::deny(["transcode_audio"]);
+/// assert!(light.permits("send_email"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobTypeFilter {
+    /// Every job type (the default).
+    #[default]
+    All,
+    /// Only these job types.
+    Allow(HashSet<String>),
+    /// Every job type except these.
+    Deny(HashSet<String>),
+}
+
+impl JobTypeFilter {
+    /// Permit only the given job types.
+    pub fn allow<I, S>(job_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Allow(job_types.into_iter().map(Into::into).collect())
+    }
+
+    /// Permit every job type except the given ones.
+    pub fn deny<I, S>(job_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Deny(job_types.into_iter().map(Into::into).collect())
+    }
+
+    /// Whether a worker with this filter may lease a job of `job_type`.
+    pub fn permits(&self, job_type: &str) -> bool {
+        match self {
+            Self::All => true,
+            Self::Allow(types) => types.contains(job_type),
+            Self::Deny(types) => !types.contains(job_type),
+        }
+    }
+
+    /// Whether this filter permits every job type.
+    pub fn is_all(&self) -> bool {
+        matches!(self, Self::All)
+    }
+}
@@ -1,7 +1,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use super::JobPriority;
+use super::{JobGroupId, JobPriority};
 
 /// Job message - immutable submission data
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +29,72 @@ pub struct JobMessage {
 
     /// Optional idempotency key (scoped by tenant/queue/job_type)
     pub idempotency_key: Option<String>,
+
+    /// When `idempotency_key` stops deduplicating; `None` means never.
+    ///
+    /// Set by the adapter from `QueueConfig::idempotency_ttl`. Once past, an
+    /// enqueue with the same key creates a fresh job even if the original is
+    /// still pending. `#[serde(default)]` for the same reason as `traceparent`.
+    #[serde(default)]
+    pub idempotency_expires_at: Option<DateTime<Utc>>,
+
+    /// W3C `traceparent` of the span that enqueued the job.
+    ///
+    /// Captured by `QueueAdapter::enqueue` when the `tracing-opentelemetry`
+    /// feature is enabled; the worker's execution span is parented to it so a
+    /// trace runs end-to-end from the producing request to job execution.
+    /// `#[serde(default)]` keeps messages serialized before this field existed
+    /// readable.
+    #[serde(default)]
+    pub traceparent: Option<String>,
+
+    /// Fan-out group this job belongs to, if any.
+    ///
+    /// Set by `QueueAdapter::fan_out`; the backend counts the job towards the
+    /// group when it reaches a terminal state. `#[serde(default)]` for the same
+    /// reason as `traceparent`.
+    #[serde(default)]
+    pub group_id: Option<JobGroupId>,
+
+    /// What happens when the job's outcome is ambiguous (see
+    /// [`DeliveryMode`]). Set from `Job::DELIVERY`. `#[serde(default)]` for
+    /// the same reason as `traceparent`.
+    #[serde(default)]
+    pub delivery: DeliveryMode,
+}
+
+/// How a job is treated when a worker loses track of it mid-execution.
+///
+/// An attempt's outcome is *ambiguous* when its lease is lost — the worker
+/// crashed, or stopped heartbeating — or when a shutdown drain times out
+/// with the job still running. The job may have done all, some or none of
+/// its work, and the queue can't tell which. Explicit outcomes are not
+/// ambiguous: a job that returns `JobError::Retryable` is retried under
+/// either mode.
+///
+/// | | ambiguous outcome | risk |
+/// |---|---|---|
+/// | `AtLeastOnce` | retried (within `MAX_RETRIES`) | may run twice |
+/// | `AtMostOnce` | failed permanently | may never complete |
+///
+/// `AtLeastOnce` keeps the normal retry policy rather than retrying any
+/// harder: an ambiguous attempt counts against `Job::MAX_RETRIES` like any
+/// other failed attempt. Jobs that need more attempts should raise
+/// `MAX_RETRIES`.
+///
+/// Prefer `AtLeastOnce` and idempotent jobs. Use `AtMostOnce` only when a
+/// duplicate is worse than a miss (sending an email, charging a card), and
+/// watch for its `Failed` events to follow up by hand.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryMode {
+    /// Retry ambiguous attempts under the job's normal retry policy; the
+    /// job may run more than once.
+    #[default]
+    AtLeastOnce,
+    /// Never retry ambiguous attempts; the job runs at most once past the
+    /// point where it could have had side effects.
+    AtMostOnce,
 }
 
 impl JobMessage {
@@ -48,6 +114,10 @@ impl JobMessage {
             max_retries: 3,
             run_at: Utc::now(),
             idempotency_key: None,
+            idempotency_expires_at: None,
+            traceparent: None,
+            group_id: None,
+            delivery: DeliveryMode::default(),
         }
     }
 
@@ -75,6 +145,18 @@ impl JobMessage {
         self
     }
 
+    /// Set the W3C `traceparent` to continue when the job executes
+    pub fn with_traceparent(mut self, traceparent: impl Into<String>) -> Self {
+        self.traceparent = Some(traceparent.into());
+        self
+    }
+
+    /// Set how an ambiguous outcome is handled
+    pub fn with_delivery(mut self, delivery: DeliveryMode) -> Self {
+        self.delivery = delivery;
+        self
+    }
+
     /// Check if the job is eligible to run at the given reference time.
     ///
     /// Takes an explicit `now` rather than calling `Utc::now()` internally so
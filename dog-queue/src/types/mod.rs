@@ -1,15 +1,19 @@
 pub mod capabilities;
 pub mod ctx;
 pub mod events;
+pub mod group;
 pub mod ids;
+pub mod job_filter;
 pub mod message;
 pub mod priority;
 pub mod record;
 
 pub use capabilities::{QueueCapabilities, QueueFeature};
 pub use ctx::QueueCtx;
-pub use events::JobEvent;
-pub use ids::{JobId, LeaseToken};
-pub use message::JobMessage;
+pub use events::{CursoredEvent, EventCursor, EventFilter, JobEvent};
+pub use group::JobGroup;
+pub use ids::{JobGroupId, JobId, LeaseToken};
+pub use job_filter::JobTypeFilter;
+pub use message::{DeliveryMode, JobMessage};
 pub use priority::JobPriority;
-pub use record::{JobRecord, JobStatus, LeasedJob};
+pub use record::{JobProgress, JobRecord, JobStatus, LeasedJob};
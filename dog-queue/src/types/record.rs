@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use super::{JobId, JobMessage, LeaseToken};
 
@@ -106,6 +107,10 @@ pub struct JobRecord {
     /// Last error message (if any)
     pub last_error: Option<String>,
 
+    /// When the current (or last) attempt was leased.
+    #[serde(default)]
+    pub started_at: Option<DateTime<Utc>>,
+
     /// JSON-serialized result returned by the job handler on successful completion.
     ///
     /// Populated by [`QueueBackend::ack_complete`] when the handler returns
@@ -116,6 +121,17 @@ pub struct JobRecord {
     #[serde(default)]
     pub result: Option<String>,
 
+    /// Metadata from the enqueuing [`QueueCtx`](crate::QueueCtx) (e.g.
+    /// `request_id`, `user_id`), kept so the job can be correlated with the
+    /// context that created it.
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+
+    /// Latest progress reported by the current (or last) attempt, if any.
+    /// Cleared when a new attempt is leased.
+    #[serde(default)]
+    pub progress: Option<JobProgress>,
+
     /// Current lease token (if processing).
     ///
     /// Skipped during serialization to prevent the raw proof-of-ownership token
@@ -127,10 +143,13 @@ pub struct JobRecord {
 }
 
 impl JobRecord {
-    /// Create a new job record
-    pub fn new(job_id: JobId, tenant_id: impl Into<String>, message: JobMessage) -> Self {
-        let now = Utc::now();
-
+    /// Create a new job record created at `now`
+    pub fn new(
+        job_id: JobId,
+        tenant_id: impl Into<String>,
+        message: JobMessage,
+        now: DateTime<Utc>,
+    ) -> Self {
         // Always start as Enqueued regardless of run_at.
         // Delayed-job eligibility is enforced by the (priority, run_at, job_id) tuple
         // stored in the queue entry — the dequeue scan gates on run_at <= now there.
@@ -145,11 +164,20 @@ impl JobRecord {
             created_at: now,
             updated_at: now,
             last_error: None,
+            started_at: None,
             result: None,
+            metadata: HashMap::new(),
+            progress: None,
             lease_token: None,
         }
     }
 
+    /// Attach the enqueuing context's metadata
+    pub fn with_metadata(mut self, metadata: HashMap<String, String>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
     /// The lease deadline when this job is currently being processed, or `None`.
     ///
     /// This is the single authoritative source for the lease deadline.
@@ -172,32 +200,43 @@ impl JobRecord {
     }
 
     /// Set an error and update timestamp
-    pub fn set_error(&mut self, error: String) {
+    pub fn set_error(&mut self, error: String, now: DateTime<Utc>) {
         self.last_error = Some(error);
-        self.updated_at = Utc::now();
+        self.updated_at = now;
     }
 
     /// Start processing with a lease.
     ///
     /// The `lease_until` timestamp is stored exclusively inside
     /// [`JobStatus::Processing`] — it is the single source of truth.
-    pub fn start_processing(&mut self, lease_token: LeaseToken, lease_until: DateTime<Utc>) {
+    pub fn start_processing(
+        &mut self,
+        lease_token: LeaseToken,
+        lease_until: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) {
         self.status = JobStatus::Processing { lease_until };
+        self.progress = None;
         self.lease_token = Some(lease_token);
-        self.updated_at = Utc::now();
+        self.started_at = Some(now);
+        self.updated_at = now;
+    }
+
+    /// Milliseconds since the current attempt was leased, if it was.
+    pub fn attempt_duration_ms(&self, now: DateTime<Utc>) -> Option<u64> {
+        self.started_at
+            .map(|started| (now - started).num_milliseconds().max(0) as u64)
     }
 
     /// Complete the job successfully
-    pub fn complete(&mut self) {
-        let now = Utc::now();
+    pub fn complete(&mut self, now: DateTime<Utc>) {
         self.status = JobStatus::Completed { completed_at: now };
         self.lease_token = None;
         self.updated_at = now;
     }
 
     /// Fail the job permanently
-    pub fn fail(&mut self, error: String) {
-        let now = Utc::now();
+    pub fn fail(&mut self, error: String, now: DateTime<Utc>) {
         self.status = JobStatus::Failed {
             failed_at: now,
             error: error.clone(),
@@ -212,21 +251,55 @@ impl JobRecord {
     /// Does NOT increment `attempt` — that is `dequeue`'s job when the lease is
     /// created, making `dequeue` the sole source of truth for the attempt counter.
     /// Incrementing here AND in `dequeue` would silently halve the retry budget.
-    pub fn schedule_retry(&mut self, retry_at: DateTime<Utc>) {
+    pub fn schedule_retry(&mut self, retry_at: DateTime<Utc>, now: DateTime<Utc>) {
         self.status = JobStatus::Retrying { retry_at };
         self.lease_token = None;
-        self.updated_at = Utc::now();
+        self.updated_at = now;
+    }
+
+    /// Release the lease and return the job to `Enqueued`.
+    ///
+    /// Rolls back the attempt counter incremented by `dequeue`: the job was
+    /// interrupted by a worker shutdown, not by a failure, so it must not
+    /// consume part of its retry budget.
+    pub fn release(&mut self, now: DateTime<Utc>) {
+        self.status = JobStatus::Enqueued;
+        self.attempt = self.attempt.saturating_sub(1);
+        self.lease_token = None;
+        self.updated_at = now;
+    }
+
+    /// Return a permanently failed job to `Enqueued` with a fresh retry budget.
+    ///
+    /// `last_error` is kept so operators can still see why the job failed
+    /// before it was requeued.
+    pub fn requeue(&mut self, now: DateTime<Utc>) {
+        self.status = JobStatus::Enqueued;
+        self.attempt = 0;
+        self.lease_token = None;
+        self.updated_at = now;
     }
 
     /// Cancel the job
-    pub fn cancel(&mut self) {
-        let now = Utc::now();
+    pub fn cancel(&mut self, now: DateTime<Utc>) {
         self.status = JobStatus::Canceled { canceled_at: now };
         self.lease_token = None;
         self.updated_at = now;
     }
 }
 
+/// Progress a running job reported through
+/// [`ProgressReporter::report`](crate::ProgressReporter::report)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobProgress {
+    /// Percent complete, 0–100
+    pub percent: u8,
+    /// Short status message, e.g. `"segment 3/12"`
+    pub message: String,
+    /// When the report was stored
+    pub at: DateTime<Utc>,
+}
+
 /// A job that has been leased for processing
 #[derive(Debug, Clone)]
 pub struct LeasedJob {
@@ -1,8 +1,7 @@
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::{
-    parse_macro_input, spanned::Spanned, Attribute, Expr, ExprLit, ItemMod, Lit, LitBool, LitStr,
-    Meta,
+    parse_macro_input, spanned::Spanned, Attribute, Expr, ExprLit, ItemMod, Lit, LitStr, Meta,
 };
 
 // ---------------------------------------------------------------------------
@@ -12,6 +11,7 @@ struct SchemaArgs {
     service: Option<LitStr>,
     error_message: Option<LitStr>,
     backend: Option<LitStr>,
+    messages: Option<LitStr>,
 }
 
 impl syn::parse::Parse for SchemaArgs {
@@ -19,6 +19,7 @@ impl syn::parse::Parse for SchemaArgs {
         let mut service = None;
         let mut error_message = None;
         let mut backend = Option::None;
+        let mut messages = None;
 
         let metas = syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated(input)?;
         for meta in metas {
@@ -36,6 +37,7 @@ impl syn::parse::Parse for SchemaArgs {
                         "service" => service = Some(s),
                         "error_message" => error_message = Some(s),
                         "backend" => backend = Some(s),
+                        "messages" => messages = Some(s),
                         _ => {}
                     }
                 }
@@ -45,6 +47,7 @@ impl syn::parse::Parse for SchemaArgs {
             service,
             error_message,
             backend,
+            messages,
         })
     }
 }
@@ -58,6 +61,7 @@ pub fn schema(args: TokenStream, item: TokenStream) -> TokenStream {
         service,
         error_message,
         backend,
+        messages,
     } = parse_macro_input!(args as SchemaArgs);
 
     let mut module = parse_macro_input!(item as ItemMod);
@@ -89,6 +93,7 @@ pub fn schema(args: TokenStream, item: TokenStream) -> TokenStream {
 
     let mut create_struct: Option<syn::ItemStruct> = None;
     let mut patch_struct: Option<syn::ItemStruct> = None;
+    let mut update_struct: Option<syn::ItemStruct> = None;
 
     for it in items.iter() {
         if let syn::Item::Struct(s) = it {
@@ -98,6 +103,9 @@ pub fn schema(args: TokenStream, item: TokenStream) -> TokenStream {
             if has_marker_attr(&s.attrs, "patch") {
                 patch_struct = Some(s.clone());
             }
+            if has_marker_attr(&s.attrs, "update") {
+                update_struct = Some(s.clone());
+            }
         }
     }
 
@@ -110,8 +118,75 @@ pub fn schema(args: TokenStream, item: TokenStream) -> TokenStream {
         .into();
     };
 
-    let create_rules = collect_field_rules(&create_struct);
-    let patch_rules = patch_struct.as_ref().map(collect_field_rules);
+    // Every other struct in the module can be nested in a payload.
+    let nested_structs: Vec<syn::ItemStruct> = items
+        .iter()
+        .filter_map(|it| match it {
+            syn::Item::Struct(s)
+                if !["create", "patch", "update"]
+                    .iter()
+                    .any(|m| has_marker_attr(&s.attrs, m)) =>
+            {
+                Some(s.clone())
+            }
+            _ => None,
+        })
+        .collect();
+    let nested_idents: Vec<syn::Ident> = nested_structs.iter().map(|s| s.ident.clone()).collect();
+
+    let create_rules = match collect_field_rules(&create_struct, &nested_idents) {
+        Ok(rules) => rules,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let patch_rules = match patch_struct
+        .as_ref()
+        .map(|s| collect_field_rules(s, &nested_idents))
+        .transpose()
+    {
+        Ok(rules) => rules,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let update_rules = match update_struct
+        .as_ref()
+        .map(|s| collect_field_rules(s, &nested_idents))
+        .transpose()
+    {
+        Ok(rules) => rules,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let mut nested_fns = Vec::new();
+    if backend.value() != "validator" {
+        for st in &nested_structs {
+            let rules = match collect_field_rules(st, &nested_idents) {
+                Ok(rules) => rules,
+                Err(e) => return e.to_compile_error().into(),
+            };
+            let checks = match collect_struct_checks(st) {
+                Ok(checks) => checks,
+                Err(e) => return e.to_compile_error().into(),
+            };
+            nested_fns.push(gen_validate_nested(&st.ident, &rules, &checks));
+        }
+    }
+
+    let create_checks = match collect_struct_checks(&create_struct) {
+        Ok(checks) => checks,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let patch_checks = match patch_struct.as_ref().map(collect_struct_checks).transpose() {
+        Ok(checks) => checks.unwrap_or_default(),
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let update_checks = match update_struct
+        .as_ref()
+        .map(collect_struct_checks)
+        .transpose()
+    {
+        Ok(checks) => checks.unwrap_or_default(),
+        Err(e) => return e.to_compile_error().into(),
+    };
 
     // Remove internal marker attrs so they don't reach rustc.
     strip_internal_attrs(items);
@@ -120,31 +195,83 @@ pub fn schema(args: TokenStream, item: TokenStream) -> TokenStream {
     let patch_ident = patch_struct.as_ref().map(|s| s.ident.clone());
 
     let resolve_create_fn = gen_resolve_create(&create_rules, &error_message);
-    let validate_create_fn =
-        gen_validate_create(&create_rules, &error_message, &backend, &create_ident);
+    let validate_create_fn = gen_validate_create(
+        &quote::format_ident!("validate_create"),
+        &create_rules,
+        &create_checks,
+        &error_message,
+        &backend,
+        &create_ident,
+    );
+    // Update replaces the whole record, so it is validated like a create.
+    let validate_update_fn = match (&update_struct, &update_rules) {
+        (Some(st), Some(rules)) => gen_validate_create(
+            &quote::format_ident!("validate_update"),
+            rules,
+            &update_checks,
+            &error_message,
+            &backend,
+            &st.ident,
+        ),
+        _ => quote! {},
+    };
+    let resolve_patch_fn = patch_rules
+        .as_deref()
+        .map(gen_resolve_patch)
+        .unwrap_or_else(|| quote! {});
     let validate_patch_fn = patch_rules
         .as_ref()
         .map(|rules| {
             let patch_ident = patch_ident
                 .as_ref()
                 .expect("patch rules implies patch struct");
-            gen_validate_patch(rules, &error_message, &backend, patch_ident)
+            gen_validate_patch(rules, &patch_checks, &error_message, &backend, patch_ident)
         })
         .unwrap_or_else(|| quote! {});
 
-    let register_fn = gen_register_fn(&service, patch_rules.is_some());
+    let schema_errors_fn = match gen_schema_errors_fn(messages.as_ref()) {
+        Ok(f) => f,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let json_schema_fn = gen_json_schema(
+        &create_rules,
+        patch_rules.as_deref(),
+        update_rules.as_deref(),
+    );
+    let register_fn = gen_register_fn(&service, patch_rules.is_some(), update_rules.is_some());
 
     if let Ok(it) = syn::parse2::<syn::Item>(resolve_create_fn) {
         items.push(it);
     }
+    if let Ok(it) = syn::parse2::<syn::Item>(schema_errors_fn) {
+        items.push(it);
+    }
+    for nested_fn in nested_fns {
+        if let Ok(it) = syn::parse2::<syn::Item>(nested_fn) {
+            items.push(it);
+        }
+    }
     if let Ok(it) = syn::parse2::<syn::Item>(validate_create_fn) {
         items.push(it);
     }
+    if !validate_update_fn.is_empty() {
+        if let Ok(it) = syn::parse2::<syn::Item>(validate_update_fn) {
+            items.push(it);
+        }
+    }
+    if !resolve_patch_fn.is_empty() {
+        if let Ok(it) = syn::parse2::<syn::Item>(resolve_patch_fn) {
+            items.push(it);
+        }
+    }
     if !validate_patch_fn.is_empty() {
         if let Ok(it) = syn::parse2::<syn::Item>(validate_patch_fn) {
             items.push(it);
         }
     }
+    if let Ok(it) = syn::parse2::<syn::Item>(json_schema_fn) {
+        items.push(it);
+    }
     if let Ok(it) = syn::parse2::<syn::Item>(register_fn) {
         items.push(it);
     }
@@ -164,9 +291,13 @@ fn strip_internal_attrs(items: &mut [syn::Item]) {
         if let syn::Item::Struct(s) = it {
             s.attrs.push(syn::parse_quote!(#[allow(dead_code)]));
 
-            // strip #[create]/#[patch]
-            s.attrs
-                .retain(|a| !(a.path().is_ident("create") || a.path().is_ident("patch")));
+            // strip #[create]/#[patch]/#[update]/#[dog(...)]
+            s.attrs.retain(|a| {
+                !(a.path().is_ident("create")
+                    || a.path().is_ident("patch")
+                    || a.path().is_ident("update")
+                    || a.path().is_ident("dog"))
+            });
 
             // strip #[dog(...)] on fields
             if let syn::Fields::Named(named) = &mut s.fields {
@@ -185,6 +316,14 @@ fn strip_internal_attrs(items: &mut [syn::Item]) {
 enum FieldKind {
     String,
     Bool,
+    Number,
+    /// Another struct of the schema module
+    Struct(syn::Ident),
+    /// A `Vec` or set, with the kind and JSON type of its items
+    List {
+        item: Box<FieldKind>,
+        item_type: Option<&'static str>,
+    },
     Other,
 }
 
@@ -192,18 +331,52 @@ enum FieldKind {
 struct FieldRule {
     json_key: String,
     kind: FieldKind,
+    /// JSON Schema `type`, when the Rust type maps to one
+    json_type: Option<&'static str>,
     trim: bool,
     min_len: Option<usize>,
-    default_bool: Option<bool>,
+    max_len: Option<usize>,
+    /// Exact length in chars, from `len(N)`
+    len: Option<usize>,
+    /// Inclusive bounds of a `Number` field
+    min: Option<f64>,
+    max: Option<f64>,
+    /// Regex a `String` field must match, checked to compile
+    pattern: Option<LitStr>,
+    /// Allowed values of a `String` field, from `one_of("a", "b")`
+    one_of: Option<Vec<LitStr>>,
+    /// `email` or `url`, from the marker of the same name
+    format: Option<&'static str>,
+    /// Literal inserted by `resolve_create` when the field is missing
+    default: Option<Expr>,
     optional: bool,
+    /// Rejected when present, from `immutable`
+    immutable: bool,
+    /// `message = "..."` overrides, by error code
+    messages: Vec<(&'static str, LitStr)>,
+}
+
+impl FieldRule {
+    fn message(&self, code: &str) -> Option<&LitStr> {
+        self.messages
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, m)| m)
+    }
+
+    fn set_message(&mut self, code: &'static str, message: Option<LitStr>) {
+        if let Some(message) = message {
+            self.messages.push((code, message));
+        }
+    }
 }
 
-fn collect_field_rules(st: &syn::ItemStruct) -> Vec<FieldRule> {
+fn collect_field_rules(st: &syn::ItemStruct, nested: &[syn::Ident]) -> syn::Result<Vec<FieldRule>> {
     let mut rules = Vec::new();
 
     let fields = match &st.fields {
         syn::Fields::Named(n) => &n.named,
-        _ => return rules,
+        _ => return Ok(rules),
     };
 
     for f in fields {
@@ -214,14 +387,24 @@ fn collect_field_rules(st: &syn::ItemStruct) -> Vec<FieldRule> {
 
         let mut rule = FieldRule {
             json_key,
-            kind: field_kind(&f.ty),
+            kind: structured_kind(&f.ty, nested).unwrap_or_else(|| field_kind(&f.ty)),
+            json_type: json_type(&f.ty),
             trim: false,
             min_len: None,
-            default_bool: None,
+            max_len: None,
+            len: None,
+            min: None,
+            max: None,
+            pattern: None,
+            format: None,
+            one_of: None,
+            default: None,
             optional: is_option_type(&f.ty),
+            immutable: false,
+            messages: Vec::new(),
         };
 
-        // Parse #[dog(trim, min_len(3), default = false)] on fields
+        // Parse #[dog(trim, min_len(3), max_len(50), min = 0, max = 100, default = "draft")] on fields
         for attr in &f.attrs {
             if !attr.path().is_ident("dog") {
                 continue;
@@ -240,27 +423,87 @@ fn collect_field_rules(st: &syn::ItemStruct) -> Vec<FieldRule> {
                                     rule.trim = true;
                                 } else if p.is_ident("optional") {
                                     rule.optional = true;
+                                } else if p.is_ident("immutable") {
+                                    rule.immutable = true;
+                                } else if p.is_ident("email") {
+                                    rule.format = Some(format_marker(&p, "email", &f.ty)?);
+                                } else if p.is_ident("url") {
+                                    rule.format = Some(format_marker(&p, "url", &f.ty)?);
                                 }
                             }
-                            Meta::List(ml) => {
-                                // min_len(3)
-                                if ml.path.is_ident("min_len") {
-                                    if let Ok(n) = ml.parse_args::<syn::LitInt>() {
-                                        if let Ok(v) = n.base10_parse::<usize>() {
-                                            rule.min_len = Some(v);
-                                        }
-                                    }
-                                }
+                            // min_len(3) or min_len(3, message = "too short")
+                            Meta::List(ml) if ml.path.is_ident("min_len") => {
+                                let (args, message) = rule_args(&ml)?;
+                                rule.min_len = length_arg(&ml, &args)?;
+                                rule.set_message("min_len", message);
+                            }
+                            // max_len(50)
+                            Meta::List(ml) if ml.path.is_ident("max_len") => {
+                                let (args, message) = rule_args(&ml)?;
+                                rule.max_len = length_arg(&ml, &args)?;
+                                rule.set_message("max_len", message);
+                            }
+                            // len(2)
+                            Meta::List(ml) if ml.path.is_ident("len") => {
+                                let (args, message) = rule_args(&ml)?;
+                                rule.len = length_arg(&ml, &args)?;
+                                rule.set_message("len", message);
+                            }
+                            // one_of("draft", "published")
+                            Meta::List(ml) if ml.path.is_ident("one_of") => {
+                                let (args, message) = rule_args(&ml)?;
+                                rule.one_of = Some(one_of_args(&ml, &args, &f.ty)?);
+                                rule.set_message("one_of", message);
+                            }
+                            // min(0, message = "..."), max(...), the list
+                            // forms of `min = 0` and `max = 100`
+                            Meta::List(ml) if ml.path.is_ident("min") => {
+                                let (args, message) = rule_args(&ml)?;
+                                rule.min = args.first().and_then(number_lit);
+                                rule.set_message("min", message);
+                            }
+                            Meta::List(ml) if ml.path.is_ident("max") => {
+                                let (args, message) = rule_args(&ml)?;
+                                rule.max = args.first().and_then(number_lit);
+                                rule.set_message("max", message);
+                            }
+                            // pattern("^[a-z]+$", message = "...")
+                            Meta::List(ml) if ml.path.is_ident("pattern") => {
+                                let (args, message) = rule_args(&ml)?;
+                                let Some(pattern) = args.first() else {
+                                    return Err(syn::Error::new(
+                                        ml.span(),
+                                        "`pattern(...)` needs a regex",
+                                    ));
+                                };
+                                rule.pattern = Some(pattern_lit(pattern, &f.ty)?);
+                                rule.set_message("pattern", message);
+                            }
+                            // email(message = "..."), url(message = "...")
+                            Meta::List(ml)
+                                if ml.path.is_ident("email") || ml.path.is_ident("url") =>
+                            {
+                                let (_, message) = rule_args(&ml)?;
+                                let format = if ml.path.is_ident("email") {
+                                    "email"
+                                } else {
+                                    "url"
+                                };
+                                rule.format = Some(format_marker(&ml.path, format, &f.ty)?);
+                                rule.set_message(format, message);
                             }
                             // syn 2.x: MetaNameValue.value is Expr, not Lit
                             Meta::NameValue(nv) if nv.path.is_ident("default") => {
-                                if let Expr::Lit(ExprLit {
-                                    lit: Lit::Bool(LitBool { value, .. }),
-                                    ..
-                                }) = nv.value
-                                {
-                                    rule.default_bool = Some(value);
-                                }
+                                rule.default = Some(default_lit(&nv.value, &f.ty)?);
+                            }
+                            Meta::NameValue(nv) if nv.path.is_ident("min") => {
+                                rule.min = number_lit(&nv.value);
+                            }
+                            Meta::NameValue(nv) if nv.path.is_ident("max") => {
+                                rule.max = number_lit(&nv.value);
+                            }
+                            Meta::NameValue(nv) if nv.path.is_ident("pattern") => {
+                                rule.pattern = Some(pattern_lit(&nv.value, &f.ty)?);
                             }
                             _ => {}
                         }
@@ -272,7 +515,317 @@ fn collect_field_rules(st: &syn::ItemStruct) -> Vec<FieldRule> {
         rules.push(rule);
     }
 
-    rules
+    Ok(rules)
+}
+
+/// Checks that run after a struct's field checks.
+#[derive(Default)]
+struct StructChecks {
+    /// `requires(...)` on the struct
+    requires: Vec<Requirement>,
+    /// `validate_with = "path::to::fn"` functions: those on the struct's
+    /// fields in order, then the struct's own
+    validators: Vec<syn::Path>,
+}
+
+/// `requires(if = "country", equals = "US", then = "postal_code")`: `then`
+/// must be present when `if` is present (and equal to `equals`, if given).
+struct Requirement {
+    if_field: LitStr,
+    equals: Option<Expr>,
+    then: LitStr,
+}
+
+fn collect_struct_checks(st: &syn::ItemStruct) -> syn::Result<StructChecks> {
+    let mut requires = Vec::new();
+    for attr in &st.attrs {
+        if !attr.path().is_ident("dog") {
+            continue;
+        }
+        let Meta::List(ref list) = attr.meta else {
+            continue;
+        };
+        let Ok(metas) = list
+            .parse_args_with(syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated)
+        else {
+            continue;
+        };
+        for meta in metas {
+            if let Meta::List(ml) = meta {
+                if ml.path.is_ident("requires") {
+                    requires.push(requirement_args(&ml)?);
+                }
+            }
+        }
+    }
+
+    Ok(StructChecks {
+        requires,
+        validators: collect_validators(st)?,
+    })
+}
+
+/// The `if`, `equals` and `then` of `requires(...)`.
+fn requirement_args(ml: &syn::MetaList) -> syn::Result<Requirement> {
+    // `if` is a keyword, so these aren't `MetaNameValue`s.
+    let args = ml.parse_args_with(|input: syn::parse::ParseStream| {
+        let mut args = Vec::new();
+        while !input.is_empty() {
+            let name = input.call(<syn::Ident as syn::ext::IdentExt>::parse_any)?;
+            input.parse::<syn::Token![=]>()?;
+            let value: Expr = input.parse()?;
+            args.push((name, value));
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+        Ok(args)
+    })?;
+    let mut if_field = None;
+    let mut equals = None;
+    let mut then = None;
+
+    for (name, value) in args {
+        let str_lit = match &value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(s), ..
+            }) => Some(s.clone()),
+            _ => None,
+        };
+        if name == "if" {
+            if_field = str_lit;
+        } else if name == "then" {
+            then = str_lit;
+        } else if name == "equals" {
+            let Expr::Lit(_) = value else {
+                return Err(syn::Error::new(value.span(), "`equals` must be a literal"));
+            };
+            equals = Some(value);
+        } else {
+            return Err(syn::Error::new(
+                name.span(),
+                "`requires` takes `if`, `equals` and `then`",
+            ));
+        }
+    }
+
+    match (if_field, then) {
+        (Some(if_field), Some(then)) => Ok(Requirement {
+            if_field,
+            equals,
+            then,
+        }),
+        _ => Err(syn::Error::new(
+            ml.span(),
+            "`requires` needs `if = \"field\"` and `then = \"field\"`",
+        )),
+    }
+}
+
+fn collect_validators(st: &syn::ItemStruct) -> syn::Result<Vec<syn::Path>> {
+    let field_attrs = st.fields.iter().flat_map(|f| f.attrs.iter());
+    let mut paths = Vec::new();
+
+    for attr in field_attrs.chain(st.attrs.iter()) {
+        if !attr.path().is_ident("dog") {
+            continue;
+        }
+        let Meta::List(ref list) = attr.meta else {
+            continue;
+        };
+        let Ok(metas) = list
+            .parse_args_with(syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated)
+        else {
+            continue;
+        };
+        for meta in metas {
+            let Meta::NameValue(nv) = meta else {
+                continue;
+            };
+            if !nv.path.is_ident("validate_with") {
+                continue;
+            }
+            let Expr::Lit(ExprLit {
+                lit: Lit::Str(path),
+                ..
+            }) = &nv.value
+            else {
+                return Err(syn::Error::new(
+                    nv.value.span(),
+                    "`validate_with` must be a string literal path",
+                ));
+            };
+            paths.push(path.parse::<syn::Path>()?);
+        }
+    }
+
+    Ok(paths)
+}
+
+/// The positional arguments of a rule like `min_len(3, message = "...")`,
+/// and its `message`.
+fn rule_args(ml: &syn::MetaList) -> syn::Result<(Vec<Expr>, Option<LitStr>)> {
+    let exprs =
+        ml.parse_args_with(syn::punctuated::Punctuated::<Expr, syn::Token![,]>::parse_terminated)?;
+    let mut args = Vec::new();
+    let mut message = None;
+
+    for expr in exprs {
+        match expr {
+            Expr::Assign(assign) if matches!(&*assign.left, Expr::Path(p) if p.path.is_ident("message")) =>
+            {
+                let Expr::Lit(ExprLit {
+                    lit: Lit::Str(m), ..
+                }) = *assign.right
+                else {
+                    return Err(syn::Error::new(
+                        assign.right.span(),
+                        "`message` must be a string literal",
+                    ));
+                };
+                message = Some(m);
+            }
+            expr => args.push(expr),
+        }
+    }
+
+    Ok((args, message))
+}
+
+/// The `N` of `min_len(N)`, `max_len(N)` or `len(N)`.
+fn length_arg(ml: &syn::MetaList, args: &[Expr]) -> syn::Result<Option<usize>> {
+    match args.first() {
+        Some(Expr::Lit(ExprLit {
+            lit: Lit::Int(n), ..
+        })) => n.base10_parse::<usize>().map(Some),
+        _ => Err(syn::Error::new(
+            ml.span(),
+            "expected a length, as in `min_len(3)`",
+        )),
+    }
+}
+
+/// An `email` or `url` marker, which only applies to `String` fields.
+fn format_marker(
+    path: &syn::Path,
+    format: &'static str,
+    ty: &syn::Type,
+) -> syn::Result<&'static str> {
+    if !matches!(field_kind(ty), FieldKind::String) {
+        return Err(syn::Error::new(
+            path.span(),
+            format!("`{format}` only applies to String fields"),
+        ));
+    }
+    Ok(format)
+}
+
+/// The `default = ...` of a field: a literal matching the field's kind.
+fn default_lit(expr: &Expr, ty: &syn::Type) -> syn::Result<Expr> {
+    let matches_kind = match field_kind(ty) {
+        FieldKind::String => matches!(
+            expr,
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(_),
+                ..
+            })
+        ),
+        FieldKind::Bool => matches!(
+            expr,
+            Expr::Lit(ExprLit {
+                lit: Lit::Bool(_),
+                ..
+            })
+        ),
+        FieldKind::Number => number_lit(expr).is_some(),
+        _ => {
+            return Err(syn::Error::new(
+                expr.span(),
+                "`default` only applies to String, bool and number fields",
+            ))
+        }
+    };
+    if !matches_kind {
+        return Err(syn::Error::new(
+            expr.span(),
+            "`default` must be a literal of the field's type",
+        ));
+    }
+    Ok(expr.clone())
+}
+
+/// The allowed values of `one_of(...)`, which only applies to `String` fields.
+fn one_of_args(ml: &syn::MetaList, args: &[Expr], ty: &syn::Type) -> syn::Result<Vec<LitStr>> {
+    if !matches!(field_kind(ty), FieldKind::String) {
+        return Err(syn::Error::new(
+            ml.path.span(),
+            "`one_of` only applies to String fields",
+        ));
+    }
+    let values = args
+        .iter()
+        .map(|arg| match arg {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(s), ..
+            }) => Ok(s.clone()),
+            _ => Err(syn::Error::new(
+                arg.span(),
+                "`one_of` values must be string literals",
+            )),
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    if values.is_empty() {
+        return Err(syn::Error::new(
+            ml.span(),
+            "`one_of` needs at least one value",
+        ));
+    }
+    Ok(values)
+}
+
+/// The `pattern = "..."` of a `String` field, if it compiles as a regex.
+fn pattern_lit(expr: &Expr, ty: &syn::Type) -> syn::Result<LitStr> {
+    let Expr::Lit(ExprLit {
+        lit: Lit::Str(pattern),
+        ..
+    }) = expr
+    else {
+        return Err(syn::Error::new(
+            expr.span(),
+            "`pattern` must be a string literal",
+        ));
+    };
+    if !matches!(field_kind(ty), FieldKind::String) {
+        return Err(syn::Error::new(
+            pattern.span(),
+            "`pattern` only applies to String fields",
+        ));
+    }
+    if let Err(e) = regex::Regex::new(&pattern.value()) {
+        return Err(syn::Error::new(
+            pattern.span(),
+            format!("invalid `pattern`: {e}"),
+        ));
+    }
+    Ok(pattern.clone())
+}
+
+/// `3`, `-3` or `0.5` as an `f64`.
+fn number_lit(expr: &Expr) -> Option<f64> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(i), ..
+        }) => i.base10_parse::<f64>().ok(),
+        Expr::Lit(ExprLit {
+            lit: Lit::Float(f), ..
+        }) => f.base10_parse::<f64>().ok(),
+        Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => number_lit(expr).map(|n| -n),
+        _ => None,
+    }
 }
 
 fn is_option_type(ty: &syn::Type) -> bool {
@@ -309,6 +862,9 @@ fn field_kind(ty: &syn::Type) -> FieldKind {
                 if seg.ident == "bool" {
                     return FieldKind::Bool;
                 }
+                if matches!(json_type(inner), Some("integer" | "number")) {
+                    return FieldKind::Number;
+                }
             }
             FieldKind::Other
         }
@@ -316,11 +872,69 @@ fn field_kind(ty: &syn::Type) -> FieldKind {
     }
 }
 
+/// `Struct` for a struct of the schema module, or `List` for a collection,
+/// looking through `Option`.
+fn structured_kind(ty: &syn::Type, nested: &[syn::Ident]) -> Option<FieldKind> {
+    let syn::Type::Path(p) = ty else {
+        return None;
+    };
+    let seg = p.path.segments.last()?;
+    let type_arg = match &seg.arguments {
+        syn::PathArguments::AngleBracketed(ab) => ab.args.iter().find_map(|a| match a {
+            syn::GenericArgument::Type(t) => Some(t),
+            _ => None,
+        }),
+        _ => None,
+    };
+
+    if seg.ident == "Option" {
+        return structured_kind(type_arg?, nested);
+    }
+    if seg.arguments.is_none() && nested.contains(&seg.ident) {
+        return Some(FieldKind::Struct(seg.ident.clone()));
+    }
+    if json_type(ty) == Some("array") {
+        let item = type_arg?;
+        return Some(FieldKind::List {
+            item: Box::new(structured_kind(item, nested).unwrap_or_else(|| field_kind(item))),
+            item_type: json_type(item),
+        });
+    }
+    None
+}
+
+fn json_type(ty: &syn::Type) -> Option<&'static str> {
+    let syn::Type::Path(p) = ty else {
+        return None;
+    };
+    let seg = p.path.segments.last()?;
+    if seg.ident == "Option" {
+        if let syn::PathArguments::AngleBracketed(ab) = &seg.arguments {
+            if let Some(syn::GenericArgument::Type(t)) = ab.args.first() {
+                return json_type(t);
+            }
+        }
+        return None;
+    }
+    match seg.ident.to_string().as_str() {
+        "String" | "str" => Some("string"),
+        "bool" => Some("boolean"),
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128"
+        | "usize" => Some("integer"),
+        "f32" | "f64" => Some("number"),
+        "Vec" | "VecDeque" | "HashSet" | "BTreeSet" => Some("array"),
+        "HashMap" | "BTreeMap" | "Map" => Some("object"),
+        _ => None,
+    }
+}
+
 // ---------------------------------------------------------------------------
-// Code generation — unchanged from original
+// Code generation
 // ---------------------------------------------------------------------------
-fn gen_resolve_create(rules: &[FieldRule], _error_message: &LitStr) -> proc_macro2::TokenStream {
-    let trim_stmts = rules
+/// Trims the `trim` fields of `obj` that hold strings; absent and `null`
+/// fields are left alone.
+fn gen_trim_stmts(rules: &[FieldRule]) -> Vec<proc_macro2::TokenStream> {
+    rules
         .iter()
         .filter(|r| r.trim && matches!(r.kind, FieldKind::String))
         .map(|r| {
@@ -330,16 +944,21 @@ fn gen_resolve_create(rules: &[FieldRule], _error_message: &LitStr) -> proc_macr
                     *s = s.trim().to_string();
                 }
             }
-        });
+        })
+        .collect()
+}
+
+fn gen_resolve_create(rules: &[FieldRule], _error_message: &LitStr) -> proc_macro2::TokenStream {
+    let trim_stmts = gen_trim_stmts(rules);
 
     let default_stmts = rules
         .iter()
-        .filter_map(|r| r.default_bool.map(|v| (r, v)))
+        .filter_map(|r| r.default.as_ref().map(|v| (r, v)))
         .map(|(r, v)| {
             let key = &r.json_key;
             quote! {
                 if !obj.contains_key(#key) {
-                    obj.insert(#key.to_string(), serde_json::Value::Bool(#v));
+                    obj.insert(#key.to_string(), serde_json::json!(#v));
                 }
             }
         });
@@ -361,136 +980,364 @@ fn gen_resolve_create(rules: &[FieldRule], _error_message: &LitStr) -> proc_macr
     }
 }
 
+/// `resolve_patch`: the same normalization as `resolve_create`, without
+/// defaults, since a patch only carries the fields it changes.
+fn gen_resolve_patch(rules: &[FieldRule]) -> proc_macro2::TokenStream {
+    let trim_stmts = gen_trim_stmts(rules);
+
+    quote! {
+        pub fn resolve_patch<P>(data: &mut serde_json::Value, _meta: &dog_schema::HookMeta<serde_json::Value, P>) -> anyhow::Result<()>
+        where
+            P: Send + Clone + 'static,
+        {
+            let Some(obj) = data.as_object_mut() else {
+                return Ok(());
+            };
+
+            #(#trim_stmts)*
+
+            Ok(())
+        }
+    }
+}
+
+/// `validate_create`, or `validate_update` from an `#[update]` struct.
 fn gen_validate_create(
+    fn_ident: &syn::Ident,
     rules: &[FieldRule],
+    struct_checks: &StructChecks,
     error_message: &LitStr,
     backend: &LitStr,
     create_ident: &syn::Ident,
 ) -> proc_macro2::TokenStream {
+    let custom_checks = gen_custom_checks(struct_checks, error_message);
+    let struct_checks = gen_struct_checks(struct_checks);
+
     if backend.value() == "validator" {
+        let immutable_checks = gen_immutable_guard(rules, error_message);
         return quote! {
-            pub fn validate_create<P>(
+            pub fn #fn_ident<P>(
                 data: &serde_json::Value,
                 _meta: &dog_schema::HookMeta<serde_json::Value, P>,
             ) -> anyhow::Result<()>
             where
                 P: Send + Clone + 'static,
             {
+                #immutable_checks
                 let _parsed: #create_ident = dog_schema_validator::validate::<#create_ident>(data, #error_message)?;
-                Ok(())
+                #custom_checks
             }
         };
     }
 
-    let checks = rules.iter().map(|r| {
-        let key = &r.json_key;
-        let min_len = r.min_len;
+    let checks = gen_create_checks(rules);
 
-        match r.kind {
-            FieldKind::String => {
-                let min_len_check = if let Some(n) = min_len {
-                    quote! {
-                        if v.chars().count() < #n {
-                            errs.push_field(#key, format!("must be at least {} chars", #n));
-                        }
-                    }
-                } else {
-                    quote! {}
-                };
+    quote! {
+        pub fn #fn_ident<P>(data: &serde_json::Value, _meta: &dog_schema::HookMeta<serde_json::Value, P>) -> anyhow::Result<()>
+        where
+            P: Send + Clone + 'static,
+        {
+            let Some(obj) = data.as_object() else {
+                return Err(dog_schema::schema_error(#error_message, "expected JSON object"));
+            };
 
-                if r.optional {
-                    quote! {
-                        if let Some(v) = obj.get(#key).and_then(|v| v.as_str()) {
-                            if v.trim().is_empty() {
-                                errs.push_field(#key, "must not be empty");
+            let mut errs = __schema_errors();
+
+            #(#checks)*
+            #struct_checks
+
+            if errs.is_empty() {
+                Ok(())
+            } else {
+                Err(errs.into_unprocessable_anyhow(#error_message))
+            }
+        }
+    }
+}
+
+/// `validate_create`'s checks of each field of `obj`, pushing to `errs`.
+fn gen_create_checks(rules: &[FieldRule]) -> Vec<proc_macro2::TokenStream> {
+    rules
+        .iter()
+        .map(|r| {
+            let key = &r.json_key;
+
+            if r.immutable {
+                return gen_immutable_check(r);
+            }
+
+            match &r.kind {
+                FieldKind::String => {
+                    let length_check = gen_length_check(r);
+                    let pattern_check = gen_pattern_check(r);
+                    let format_check = gen_format_check(r);
+                    let one_of_check = gen_one_of_check(r);
+
+                    if r.optional {
+                        quote! {
+                            if let Some(v) = obj.get(#key).and_then(|v| v.as_str()) {
+                                if v.trim().is_empty() {
+                                    errs.push_code(#key, "not_empty", &[]);
+                                }
+                                #length_check
+                                #pattern_check
+                                #format_check
+                                #one_of_check
+                            }
+                        }
+                    } else {
+                        let missing = gen_missing_check(r);
+                        quote! {
+                            match obj.get(#key) {
+                                #missing
+                                Some(val) => {
+                                    if let Some(v) = val.as_str() {
+                                        if v.trim().is_empty() {
+                                            errs.push_code(#key, "not_empty", &[]);
+                                        }
+                                        #length_check
+                                        #pattern_check
+                                        #format_check
+                                        #one_of_check
+                                    } else {
+                                        errs.push_code(#key, "string", &[]);
+                                    }
+                                }
                             }
-                            #min_len_check
                         }
                     }
-                } else {
-                    quote! {
-                        match obj.get(#key) {
-                            None => errs.push_schema(format!("missing field `{}`", #key)),
-                            Some(val) => {
-                                if let Some(v) = val.as_str() {
-                                    if v.trim().is_empty() {
-                                        errs.push_field(#key, "must not be empty");
+                }
+                FieldKind::Bool => {
+                    if r.optional || r.default.is_some() {
+                        quote! {
+                            if let Some(val) = obj.get(#key) {
+                                if !val.is_boolean() {
+                                    errs.push_code(#key, "boolean", &[]);
+                                }
+                            }
+                        }
+                    } else {
+                        quote! {
+                            match obj.get(#key) {
+                                None => errs.push_schema_code("required", &[("field", #key)]),
+                                Some(val) => {
+                                    if !val.is_boolean() {
+                                        errs.push_code(#key, "boolean", &[]);
                                     }
-                                    #min_len_check
-                                } else {
-                                    errs.push_field(#key, "must be a string");
                                 }
                             }
                         }
                     }
                 }
-            }
-            FieldKind::Bool => {
-                let allow_missing = r.default_bool.is_some() || r.optional;
-                if allow_missing {
-                    quote! {
-                        if let Some(val) = obj.get(#key) {
-                            if !val.is_boolean() {
-                                errs.push_field(#key, "must be a boolean");
+                FieldKind::Number => {
+                    let number_check = gen_number_check(r);
+                    if r.optional {
+                        quote! {
+                            if let Some(val) = obj.get(#key).filter(|v| !v.is_null()) {
+                                #number_check
+                            }
+                        }
+                    } else {
+                        let missing = gen_missing_check(r);
+                        quote! {
+                            match obj.get(#key) {
+                                #missing
+                                Some(val) => {
+                                    #number_check
+                                }
                             }
                         }
                     }
-                } else {
-                    quote! {
-                        match obj.get(#key) {
-                            None => errs.push_schema(format!("missing field `{}`", #key)),
-                            Some(val) => {
-                                if !val.is_boolean() {
-                                    errs.push_field(#key, "must be a boolean");
+                }
+                FieldKind::Struct(_) | FieldKind::List { .. } => {
+                    let structured_check = gen_structured_check(key, &r.kind);
+                    if r.optional {
+                        quote! {
+                            if let Some(val) = obj.get(#key).filter(|v| !v.is_null()) {
+                                #structured_check
+                            }
+                        }
+                    } else {
+                        quote! {
+                            match obj.get(#key) {
+                                None => errs.push_schema_code("required", &[("field", #key)]),
+                                Some(val) => {
+                                    #structured_check
                                 }
                             }
                         }
                     }
                 }
-            }
-            FieldKind::Other => {
-                if r.optional {
-                    quote! {}
-                } else {
-                    quote! {
-                        if obj.get(#key).is_none() {
-                            errs.push_schema(format!("missing field `{}`", #key));
+                FieldKind::Other => {
+                    if r.optional {
+                        quote! {}
+                    } else {
+                        quote! {
+                            if obj.get(#key).is_none() {
+                                errs.push_schema_code("required", &[("field", #key)]);
+                            }
                         }
                     }
                 }
             }
+        })
+        .collect()
+}
+
+/// The `None` arm of a required field's `match obj.get(key)`. Defaulted
+/// fields may be missing, as on update, where `resolve_create` doesn't run.
+/// Rejects an `immutable` field that is present and not null.
+fn gen_immutable_check(r: &FieldRule) -> proc_macro2::TokenStream {
+    let key = &r.json_key;
+    let push = gen_push(r, "immutable", &[]);
+    quote! {
+        if data.get(#key).is_some_and(|v| !v.is_null()) {
+            #push
         }
-    });
+    }
+}
 
+/// The validator backend's `immutable` checks, run before the payload is
+/// parsed so they are not masked by serde errors.
+fn gen_immutable_guard(rules: &[FieldRule], error_message: &LitStr) -> proc_macro2::TokenStream {
+    let checks: Vec<_> = rules
+        .iter()
+        .filter(|r| r.immutable)
+        .map(gen_immutable_check)
+        .collect();
+    if checks.is_empty() {
+        return quote! {};
+    }
     quote! {
-        pub fn validate_create<P>(data: &serde_json::Value, _meta: &dog_schema::HookMeta<serde_json::Value, P>) -> anyhow::Result<()>
-        where
-            P: Send + Clone + 'static,
-        {
+        let mut errs = __schema_errors();
+        #(#checks)*
+        if !errs.is_empty() {
+            return Err(errs.into_unprocessable_anyhow(#error_message));
+        }
+    }
+}
+
+fn gen_missing_check(r: &FieldRule) -> proc_macro2::TokenStream {
+    let key = &r.json_key;
+    if r.default.is_some() {
+        quote! { None => {} }
+    } else {
+        quote! { None => errs.push_schema_code("required", &[("field", #key)]), }
+    }
+}
+
+/// The validator of a nested struct. It returns the errors of `data` keyed
+/// relative to it, for the caller to merge under the field's path.
+fn gen_validate_nested(
+    ident: &syn::Ident,
+    rules: &[FieldRule],
+    struct_checks: &StructChecks,
+) -> proc_macro2::TokenStream {
+    let fn_ident = nested_fn_ident(ident);
+    let checks = gen_create_checks(rules);
+    let struct_checks = gen_struct_checks(struct_checks);
+
+    quote! {
+        #[allow(dead_code, non_snake_case, unused_variables)]
+        fn #fn_ident(data: &serde_json::Value) -> dog_schema::SchemaErrors {
+            let mut errs = __schema_errors();
             let Some(obj) = data.as_object() else {
-                return Err(dog_schema::schema_error(#error_message, "expected JSON object"));
+                errs.push_schema_code("object", &[]);
+                return errs;
             };
 
-            let mut errs = dog_schema::SchemaErrors::default();
-
             #(#checks)*
+            #struct_checks
 
-            if errs.is_empty() {
-                Ok(())
-            } else {
-                Err(errs.into_unprocessable_anyhow(#error_message))
+            errs
+        }
+    }
+}
+
+fn nested_fn_ident(ident: &syn::Ident) -> syn::Ident {
+    quote::format_ident!("__validate_{}", ident)
+}
+
+/// Checks `val` (in scope) against a `Struct` or `List` field, reporting
+/// errors at paths like `address.city` and `tags[0].email`.
+fn gen_structured_check(key: &str, kind: &FieldKind) -> proc_macro2::TokenStream {
+    match kind {
+        FieldKind::Struct(ident) => {
+            let fn_ident = nested_fn_ident(ident);
+            quote! {
+                errs.extend_nested(#key, #fn_ident(val));
             }
         }
+        FieldKind::List { item, item_type } => {
+            let item_check = gen_item_check(item, *item_type);
+            if item_check.is_empty() {
+                return quote! {
+                    if !val.is_array() {
+                        errs.push_code(#key, "array", &[]);
+                    }
+                };
+            }
+            quote! {
+                match val.as_array() {
+                    Some(items) => {
+                        for (i, item) in items.iter().enumerate() {
+                            let path = format!("{}[{}]", #key, i);
+                            #item_check
+                        }
+                    }
+                    None => errs.push_code(#key, "array", &[]),
+                }
+            }
+        }
+        _ => quote! {},
+    }
+}
+
+/// Checks `item` (in scope) of a `List` field, reporting errors at `path`.
+fn gen_item_check(item: &FieldKind, item_type: Option<&'static str>) -> proc_macro2::TokenStream {
+    match item {
+        FieldKind::Struct(ident) => {
+            let fn_ident = nested_fn_ident(ident);
+            quote! {
+                errs.extend_nested(&path, #fn_ident(item));
+            }
+        }
+        FieldKind::String => quote! {
+            if !item.is_string() {
+                errs.push_code(&path, "string", &[]);
+            }
+        },
+        FieldKind::Bool => quote! {
+            if !item.is_boolean() {
+                errs.push_code(&path, "boolean", &[]);
+            }
+        },
+        FieldKind::Number if item_type == Some("integer") => quote! {
+            if !dog_schema::__private::is_integer(item) {
+                errs.push_code(&path, "integer", &[]);
+            }
+        },
+        FieldKind::Number => quote! {
+            if !item.is_number() {
+                errs.push_code(&path, "number", &[]);
+            }
+        },
+        FieldKind::List { .. } | FieldKind::Other => quote! {},
     }
 }
 
 fn gen_validate_patch(
     rules: &[FieldRule],
+    struct_checks: &StructChecks,
     error_message: &LitStr,
     backend: &LitStr,
     patch_ident: &syn::Ident,
 ) -> proc_macro2::TokenStream {
+    let custom_checks = gen_custom_checks(struct_checks, error_message);
+    let struct_checks = gen_struct_checks(struct_checks);
+
     if backend.value() == "validator" {
+        let immutable_checks = gen_immutable_guard(rules, error_message);
         return quote! {
             pub fn validate_patch<P>(
                 data: &serde_json::Value,
@@ -499,27 +1346,26 @@ fn gen_validate_patch(
             where
                 P: Send + Clone + 'static,
             {
+                #immutable_checks
                 let _parsed: #patch_ident = dog_schema_validator::validate::<#patch_ident>(data, #error_message)?;
-                Ok(())
+                #custom_checks
             }
         };
     }
 
     let checks = rules.iter().map(|r| {
         let key = &r.json_key;
-        let min_len = r.min_len;
 
-        match r.kind {
+        if r.immutable {
+            return gen_immutable_check(r);
+        }
+
+        match &r.kind {
             FieldKind::String => {
-                let min_len_check = if let Some(n) = min_len {
-                    quote! {
-                        if v.chars().count() < #n {
-                            errs.push_field(#key, format!("must be at least {} chars", #n));
-                        }
-                    }
-                } else {
-                    quote! {}
-                };
+                let length_check = gen_length_check(r);
+                let pattern_check = gen_pattern_check(r);
+                let format_check = gen_format_check(r);
+                let one_of_check = gen_one_of_check(r);
 
                 quote! {
                     if let Some(val) = obj.get(#key) {
@@ -527,11 +1373,14 @@ fn gen_validate_patch(
                             // allow null (treat as not provided)
                         } else if let Some(v) = val.as_str() {
                             if v.trim().is_empty() {
-                                errs.push_field(#key, "must not be empty");
+                                errs.push_code(#key, "not_empty", &[]);
                             }
-                            #min_len_check
+                            #length_check
+                            #pattern_check
+                            #format_check
+                            #one_of_check
                         } else {
-                            errs.push_field(#key, "must be a string");
+                            errs.push_code(#key, "string", &[]);
                         }
                     }
                 }
@@ -542,11 +1391,31 @@ fn gen_validate_patch(
                         if val.is_null() {
                             // allow null
                         } else if !val.is_boolean() {
-                            errs.push_field(#key, "must be a boolean");
+                            errs.push_code(#key, "boolean", &[]);
                         }
                     }
                 }
             }
+            FieldKind::Number => {
+                let number_check = gen_number_check(r);
+                quote! {
+                    if let Some(val) = obj.get(#key) {
+                        if val.is_null() {
+                            // allow null
+                        } else {
+                            #number_check
+                        }
+                    }
+                }
+            }
+            FieldKind::Struct(_) | FieldKind::List { .. } => {
+                let structured_check = gen_structured_check(key, &r.kind);
+                quote! {
+                    if let Some(val) = obj.get(#key).filter(|v| !v.is_null()) {
+                        #structured_check
+                    }
+                }
+            }
             FieldKind::Other => {
                 quote! {
                     if let Some(val) = obj.get(#key) {
@@ -568,9 +1437,10 @@ fn gen_validate_patch(
                 return Err(dog_schema::schema_error(#error_message, "expected JSON object"));
             };
 
-            let mut errs = dog_schema::SchemaErrors::default();
+            let mut errs = __schema_errors();
 
             #(#checks)*
+            #struct_checks
 
             if errs.is_empty() {
                 Ok(())
@@ -581,17 +1451,352 @@ fn gen_validate_patch(
     }
 }
 
-fn gen_register_fn(service: &LitStr, has_patch: bool) -> proc_macro2::TokenStream {
+/// Runs the struct checks after the validator backend has parsed `data`.
+fn gen_custom_checks(
+    struct_checks: &StructChecks,
+    error_message: &LitStr,
+) -> proc_macro2::TokenStream {
+    if struct_checks.requires.is_empty() && struct_checks.validators.is_empty() {
+        return quote! { Ok(()) };
+    }
+    let struct_checks = gen_struct_checks(struct_checks);
+    quote! {
+        let mut errs = __schema_errors();
+        #struct_checks
+        if errs.is_empty() {
+            Ok(())
+        } else {
+            Err(errs.into_unprocessable_anyhow(#error_message))
+        }
+    }
+}
+
+/// Checks `data` (in scope) against the `requires` rules, then calls the
+/// `validate_with` functions.
+fn gen_struct_checks(struct_checks: &StructChecks) -> proc_macro2::TokenStream {
+    let requires = struct_checks.requires.iter().map(|req| {
+        let Requirement {
+            if_field,
+            equals,
+            then,
+        } = req;
+        let (condition, push) = match equals {
+            Some(value) => {
+                let shown = match value {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(s), ..
+                    }) => s.value(),
+                    _ => quote!(#value).to_string(),
+                };
+                (
+                    quote! { data.get(#if_field) == Some(&serde_json::json!(#value)) },
+                    quote! {
+                        errs.push_code(#then, "required_if", &[("field", #if_field), ("value", #shown)]);
+                    },
+                )
+            }
+            None => (
+                quote! { data.get(#if_field).is_some_and(|v| !v.is_null()) },
+                quote! { errs.push_code(#then, "required_if_set", &[("field", #if_field)]); },
+            ),
+        };
+        quote! {
+            if #condition && data.get(#then).is_none_or(serde_json::Value::is_null) {
+                #push
+            }
+        }
+    });
+    let validators = &struct_checks.validators;
+
+    quote! {
+        #(#requires)*
+        #(#validators(data, &mut errs);)*
+    }
+}
+
+/// Pushes error `code` for a field: the rule's own `message` if it has one,
+/// otherwise the text from the schema's message catalog.
+fn gen_push(r: &FieldRule, code: &str, params: &[(&str, String)]) -> proc_macro2::TokenStream {
+    let key = &r.json_key;
+    let names = params.iter().map(|(name, _)| name);
+    let values = params.iter().map(|(_, value)| value);
+    let params = quote! { &[#((#names, #values)),*] };
+    match r.message(code) {
+        Some(message) => quote! {
+            errs.push_field(#key, dog_schema::format_message(#message, #params));
+        },
+        None => quote! {
+            errs.push_code(#key, #code, #params);
+        },
+    }
+}
+
+/// `__schema_errors()`: an empty `SchemaErrors` using the schema's
+/// `messages` catalog, if it has one.
+fn gen_schema_errors_fn(messages: Option<&LitStr>) -> syn::Result<proc_macro2::TokenStream> {
+    let body = match messages {
+        Some(path) => {
+            let path = path.parse::<syn::Path>()?;
+            quote! { dog_schema::SchemaErrors::with_catalog(&#path) }
+        }
+        None => quote! { dog_schema::SchemaErrors::default() },
+    };
+    Ok(quote! {
+        fn __schema_errors() -> dog_schema::SchemaErrors {
+            #body
+        }
+    })
+}
+
+/// Checks `v` (a `&str` in scope) against a `String` field's length bounds,
+/// counted in chars.
+fn gen_length_check(r: &FieldRule) -> proc_macro2::TokenStream {
+    if r.min_len.is_none() && r.max_len.is_none() && r.len.is_none() {
+        return quote! {};
+    }
+    let min_check = r.min_len.map(|n| {
+        let push = gen_push(r, "min_len", &[("min", n.to_string())]);
+        quote! {
+            if len < #n {
+                #push
+            }
+        }
+    });
+    let max_check = r.max_len.map(|n| {
+        let push = gen_push(r, "max_len", &[("max", n.to_string())]);
+        quote! {
+            if len > #n {
+                #push
+            }
+        }
+    });
+    let exact_check = r.len.map(|n| {
+        let push = gen_push(r, "len", &[("len", n.to_string())]);
+        quote! {
+            if len != #n {
+                #push
+            }
+        }
+    });
+
+    quote! {
+        {
+            let len = v.chars().count();
+            #min_check
+            #max_check
+            #exact_check
+        }
+    }
+}
+
+/// Checks `v` (a `&str` in scope) against a `String` field's pattern. The
+/// regex is compiled on first use.
+fn gen_pattern_check(r: &FieldRule) -> proc_macro2::TokenStream {
+    let Some(pattern) = &r.pattern else {
+        return quote! {};
+    };
+    let push = gen_push(r, "pattern", &[("pattern", pattern.value())]);
+    quote! {
+        {
+            static PATTERN: std::sync::OnceLock<dog_schema::__private::Regex> =
+                std::sync::OnceLock::new();
+            let re = PATTERN.get_or_init(|| {
+                dog_schema::__private::Regex::new(#pattern)
+                    .expect("pattern checked at compile time")
+            });
+            if !re.is_match(v) {
+                #push
+            }
+        }
+    }
+}
+
+/// Checks `v` (a `&str` in scope) against a `String` field's `email` or `url`
+/// format.
+fn gen_format_check(r: &FieldRule) -> proc_macro2::TokenStream {
+    let Some(format) = r.format else {
+        return quote! {};
+    };
+    let is_valid = match format {
+        "email" => quote! { dog_schema::__private::is_email(v) },
+        _ => quote! { dog_schema::__private::is_url(v) },
+    };
+    let push = gen_push(r, format, &[]);
+    quote! {
+        if !#is_valid {
+            #push
+        }
+    }
+}
+
+/// Checks `v` (a `&str` in scope) is one of a `String` field's allowed values.
+fn gen_one_of_check(r: &FieldRule) -> proc_macro2::TokenStream {
+    let Some(values) = &r.one_of else {
+        return quote! {};
+    };
+    let allowed = values
+        .iter()
+        .map(LitStr::value)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let push = gen_push(r, "one_of", &[("values", allowed)]);
+    quote! {
+        if ![#(#values),*].contains(&v) {
+            #push
+        }
+    }
+}
+
+/// Checks `val` (in scope) against a `Number` field's type and bounds.
+fn gen_number_check(r: &FieldRule) -> proc_macro2::TokenStream {
+    let key = &r.json_key;
+    let min_check = r.min.map(|n| {
+        let push = gen_push(r, "min", &[("min", n.to_string())]);
+        quote! {
+            if v < #n {
+                #push
+            }
+        }
+    });
+    let max_check = r.max.map(|n| {
+        let push = gen_push(r, "max", &[("max", n.to_string())]);
+        quote! {
+            if v > #n {
+                #push
+            }
+        }
+    });
+
+    let (is_valid, expected) = if r.json_type == Some("integer") {
+        (quote! { dog_schema::__private::is_integer(val) }, "integer")
+    } else {
+        (quote! { val.is_number() }, "number")
+    };
+
+    quote! {
+        match val.as_f64() {
+            Some(v) if #is_valid => {
+                #min_check
+                #max_check
+            }
+            _ => errs.push_code(#key, #expected, &[]),
+        }
+    }
+}
+
+/// `json_schema()`: the create (and patch and update) payloads as JSON
+/// Schemas, for API docs.
+fn gen_json_schema(
+    create: &[FieldRule],
+    patch: Option<&[FieldRule]>,
+    update: Option<&[FieldRule]>,
+) -> proc_macro2::TokenStream {
+    let create_schema = gen_object_schema(create, true);
+    let patch_stmt = patch
+        .map(|rules| {
+            let patch_schema = gen_object_schema(rules, false);
+            quote! { schema.insert("patch".to_string(), #patch_schema); }
+        })
+        .unwrap_or_else(|| quote! {});
+    let update_stmt = update
+        .map(|rules| {
+            let update_schema = gen_object_schema(rules, true);
+            quote! { schema.insert("update".to_string(), #update_schema); }
+        })
+        .unwrap_or_else(|| quote! {});
+
+    quote! {
+        pub fn json_schema() -> serde_json::Value {
+            let mut schema = serde_json::Map::new();
+            schema.insert("create".to_string(), #create_schema);
+            #patch_stmt
+            #update_stmt
+            serde_json::Value::Object(schema)
+        }
+    }
+}
+
+fn gen_object_schema(rules: &[FieldRule], with_required: bool) -> proc_macro2::TokenStream {
+    let properties = rules.iter().map(|r| {
+        let key = &r.json_key;
+        let mut entries = Vec::new();
+        if let Some(t) = r.json_type {
+            entries.push(quote! { "type": #t });
+        }
+        if let Some(n) = r.min_len.or(r.len) {
+            entries.push(quote! { "minLength": #n });
+        }
+        if let Some(n) = r.max_len.or(r.len) {
+            entries.push(quote! { "maxLength": #n });
+        }
+        if let Some(p) = &r.pattern {
+            entries.push(quote! { "pattern": #p });
+        }
+        if let Some(values) = &r.one_of {
+            entries.push(quote! { "enum": [#(#values),*] });
+        }
+        if let Some(format) = r.format {
+            let format = if format == "url" { "uri" } else { format };
+            entries.push(quote! { "format": #format });
+        }
+        if let Some(n) = r.min {
+            entries.push(quote! { "minimum": #n });
+        }
+        if let Some(n) = r.max {
+            entries.push(quote! { "maximum": #n });
+        }
+        if let Some(v) = &r.default {
+            entries.push(quote! { "default": #v });
+        }
+        if r.immutable {
+            entries.push(quote! { "readOnly": true });
+        }
+        quote! {
+            properties.insert(#key.to_string(), serde_json::json!({ #(#entries),* }));
+        }
+    });
+
+    // Same rule as validate_create: present unless optional, defaulted or immutable.
+    let required = rules
+        .iter()
+        .filter(|r| with_required && !r.optional && r.default.is_none() && !r.immutable)
+        .map(|r| &r.json_key);
+
+    quote! {
+        {
+            let mut properties = serde_json::Map::new();
+            #(#properties)*
+            serde_json::json!({
+                "type": "object",
+                "properties": properties,
+                "required": [#(#required),*],
+            })
+        }
+    }
+}
+
+fn gen_register_fn(
+    service: &LitStr,
+    has_patch: bool,
+    has_update: bool,
+) -> proc_macro2::TokenStream {
     let svc = service.value();
     let svc_lit = LitStr::new(&svc, service.span());
 
     let patch = if has_patch {
         quote! {
+            s.on_patch().resolve(resolve_patch);
             s.on_patch().validate(validate_patch);
         }
     } else {
         quote! {}
     };
+    // Without an #[update] struct, a full replace is validated like a create.
+    let validate_update = if has_update {
+        quote! { validate_update }
+    } else {
+        quote! { validate_create }
+    };
 
     quote! {
         pub fn register<P>(builder: &mut dog_core::DogAppBuilder<serde_json::Value, P>) -> anyhow::Result<()>
@@ -600,11 +1805,14 @@ fn gen_register_fn(service: &LitStr, has_patch: bool) -> proc_macro2::TokenStrea
         {
             use dog_schema::SchemaHooksExt;
 
+            builder.set_service_schema(#svc_lit, json_schema());
             builder.service_hooks(#svc_lit, |h| {
+                // Each hook resets the builder to all writes, so scope them one by one.
                 h.schema(|s| {
-                    s.on_create().resolve(resolve_create).validate(validate_create);
+                    s.on_create().resolve(resolve_create);
+                    s.on_create().validate(validate_create);
                     #patch
-                    s.on_update().validate(validate_create);
+                    s.on_update().validate(#validate_update);
                 });
             });
 
@@ -1,5 +1,5 @@
 use serde::de::DeserializeOwned;
-use serde_json::{json, Value};
+use serde_json::Value;
 use validator::Validate;
 
 use dog_schema::SchemaErrors;
@@ -69,12 +69,102 @@ fn validator_errors_to_schema_errors(errs: &validator::ValidationErrors) -> Sche
     out
 }
 
+/// The `expected` part of a serde type error, as a field message.
+fn expected_message(expected: &str) -> Option<&'static str> {
+    const INTEGERS: [&str; 12] = [
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+    ];
+    if INTEGERS.contains(&expected) {
+        return Some("must be an integer");
+    }
+    match expected {
+        "f32" | "f64" => Some("must be a number"),
+        "a string" | "a character" => Some("must be a string"),
+        "a boolean" => Some("must be a boolean"),
+        "a sequence" => Some("must be an array"),
+        "a map" => Some("must be an object"),
+        e if e.starts_with("struct ") => Some("must be an object"),
+        _ => None,
+    }
+}
+
+/// Flattens a deserialize error into `out`, keyed by the path of the value
+/// that failed, like validation errors. Anything unrecognised is reported
+/// as serde's own message.
+fn push_deserialize_error(
+    out: &mut SchemaErrors,
+    data: &Value,
+    err: &serde_path_to_error::Error<serde_json::Error>,
+) {
+    let path = match err.path().to_string() {
+        p if p == "." => String::new(),
+        p => p,
+    };
+    let msg = err.inner().to_string();
+
+    // serde_json reports the missing field at its parent's path.
+    if let Some(field) = msg
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.strip_suffix('`'))
+    {
+        out.push_field(&join_path(&path, field), "is required");
+        return;
+    }
+
+    let friendly = if let Some(rest) = msg.strip_prefix("invalid type: ") {
+        rest.split_once(", expected ")
+            .and_then(|(_, expected)| expected_message(expected))
+    } else if let Some(rest) = msg.strip_prefix("invalid value: ") {
+        // e.g. `-1` for a `u32`
+        rest.split_once(", expected ")
+            .and_then(|(_, expected)| expected_message(expected))
+            .map(|_| "is out of range")
+    } else if msg == "invalid number" {
+        // With serde_json's `arbitrary_precision`, both `-1` and `1.5` for a
+        // `u32` fail this way; tell them apart by the value itself.
+        value_at(data, err.path()).map(|v| {
+            if is_integral(v) {
+                "is out of range"
+            } else {
+                "must be an integer"
+            }
+        })
+    } else {
+        None
+    };
+
+    match (path.is_empty(), friendly) {
+        (false, Some(friendly)) => out.push_field(&path, friendly),
+        (false, None) => out.push_field(&path, msg),
+        (true, _) => out.push_schema(msg),
+    }
+}
+
+/// The value `path` points at in `data`.
+fn value_at<'a>(data: &'a Value, path: &serde_path_to_error::Path) -> Option<&'a Value> {
+    use serde_path_to_error::Segment;
+
+    path.iter().try_fold(data, |value, segment| match segment {
+        Segment::Seq { index } => value.get(index),
+        Segment::Map { key } => value.get(key.as_str()),
+        Segment::Enum { .. } | Segment::Unknown => None,
+    })
+}
+
+/// Whether `v` is a number written without a fraction or exponent.
+fn is_integral(v: &Value) -> bool {
+    v.as_number()
+        .is_some_and(|n| !n.to_string().contains(['.', 'e', 'E']))
+}
+
 pub fn validate<T>(data: &Value, error_message: &str) -> anyhow::Result<T>
 where
     T: DeserializeOwned + Validate,
 {
-    let parsed: T = serde_json::from_value(data.clone()).map_err(|e| {
-        dog_schema::unprocessable(error_message, json!({"_schema": [e.to_string()]}))
+    let parsed: T = serde_path_to_error::deserialize(data).map_err(|e| {
+        let mut out = SchemaErrors::default();
+        push_deserialize_error(&mut out, data, &e);
+        out.into_unprocessable_anyhow(error_message)
     })?;
 
     parsed.validate().map_err(|e| {
@@ -131,4 +221,63 @@ mod tests {
         );
         assert_eq!(errors["tags[0].email"][0], "tag email must be valid");
     }
+
+    #[derive(Debug, Deserialize, Validate)]
+    #[allow(dead_code)]
+    struct Person {
+        name: String,
+        age: u32,
+        #[validate(nested)]
+        tags: Vec<Tag>,
+    }
+
+    fn deserialize_errors(data: serde_json::Value) -> serde_json::Value {
+        let err = validate::<Person>(&data, "People schema validation failed").unwrap_err();
+        let dog = DogError::from_anyhow(&err).expect("must be DogError");
+        dog.errors.clone().unwrap()
+    }
+
+    #[test]
+    fn wrong_typed_field_is_a_field_error() {
+        let errors = deserialize_errors(json!({ "name": "Ada", "age": "old", "tags": [] }));
+
+        assert_eq!(errors, json!({ "age": ["must be an integer"] }));
+    }
+
+    #[test]
+    fn deserialize_errors_keep_nested_paths() {
+        let errors = deserialize_errors(json!({
+            "name": "Ada",
+            "age": 36,
+            "tags": [{ "email": "ada@example.com" }, { "email": false }],
+        }));
+        assert_eq!(errors, json!({ "tags[1].email": ["must be a string"] }));
+
+        let errors = deserialize_errors(json!({ "name": "Ada", "age": -1, "tags": [] }));
+        assert_eq!(errors, json!({ "age": ["is out of range"] }));
+    }
+
+    #[test]
+    fn fractional_number_for_integer_field_is_not_a_range_error() {
+        let errors = deserialize_errors(json!({ "name": "Ada", "age": 1.5, "tags": [] }));
+
+        assert_eq!(errors, json!({ "age": ["must be an integer"] }));
+    }
+
+    #[test]
+    fn missing_field_is_reported_on_the_field() {
+        let errors = deserialize_errors(json!({ "name": "Ada", "tags": [] }));
+
+        assert_eq!(errors, json!({ "age": ["is required"] }));
+    }
+
+    #[test]
+    fn non_object_payload_stays_a_schema_error() {
+        let errors = deserialize_errors(json!("Ada"));
+
+        assert!(errors["_schema"][0]
+            .as_str()
+            .unwrap()
+            .contains("invalid type"));
+    }
 }
@@ -3,10 +3,77 @@ pub use dog_schema_macros::schema;
 use dog_core::errors::DogError;
 use serde_json::{json, Map, Value};
 
+/// Turns error codes into text, for localized validation errors.
+///
+/// `#[schema(messages = "path::to::CATALOG")]` makes the generated
+/// validators use a catalog. Codes it returns `None` for fall back to the
+/// English text of [`EnglishMessages`].
+pub trait MessageCatalog: Send + Sync {
+    /// The text for `code`, given its `params` (such as `("min", "3")` for
+    /// `min_len`).
+    fn message(&self, code: &str, params: &[(&str, &str)]) -> Option<String>;
+}
+
+/// The built-in English messages.
+///
+/// | code | message |
+/// |------|---------|
+/// | `required` | missing field \`{field}\` |
+/// | `not_empty` | must not be empty |
+/// | `string`, `boolean`, `integer`, `number`, `object`, `array` | must be a string (and so on) |
+/// | `min_len`, `max_len`, `len` | must be at least {min} chars, at most {max} chars, exactly {len} chars |
+/// | `min`, `max` | must be at least {min}, at most {max} |
+/// | `pattern` | must match pattern {pattern} |
+/// | `email`, `url` | must be a valid email, a valid URL |
+/// | `one_of` | must be one of {values} |
+/// | `required_if` | is required when {field} is {value} |
+/// | `required_if_set` | is required when {field} is set |
+/// | `immutable` | cannot be changed |
+pub struct EnglishMessages;
+
+impl MessageCatalog for EnglishMessages {
+    fn message(&self, code: &str, params: &[(&str, &str)]) -> Option<String> {
+        let template = match code {
+            "required" => "missing field `{field}`",
+            "not_empty" => "must not be empty",
+            "string" => "must be a string",
+            "boolean" => "must be a boolean",
+            "integer" => "must be an integer",
+            "number" => "must be a number",
+            "object" => "must be an object",
+            "array" => "must be an array",
+            "min_len" => "must be at least {min} chars",
+            "max_len" => "must be at most {max} chars",
+            "len" => "must be exactly {len} chars",
+            "min" => "must be at least {min}",
+            "max" => "must be at most {max}",
+            "pattern" => "must match pattern {pattern}",
+            "email" => "must be a valid email",
+            "url" => "must be a valid URL",
+            "one_of" => "must be one of {values}",
+            "required_if" => "is required when {field} is {value}",
+            "required_if_set" => "is required when {field} is set",
+            "immutable" => "cannot be changed",
+            _ => return None,
+        };
+        Some(format_message(template, params))
+    }
+}
+
+/// Replaces each `{name}` in `template` with its value from `params`.
+pub fn format_message(template: &str, params: &[(&str, &str)]) -> String {
+    params
+        .iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), value)
+        })
+}
+
 #[must_use = "call into_unprocessable_anyhow() to propagate errors"]
 #[derive(Default)]
 pub struct SchemaErrors {
     map: Map<String, Value>,
+    catalog: Option<&'static dyn MessageCatalog>,
 }
 
 impl SchemaErrors {
@@ -14,6 +81,33 @@ impl SchemaErrors {
         Self::default()
     }
 
+    /// Errors whose codes are turned into text by `catalog`.
+    pub fn with_catalog(catalog: &'static dyn MessageCatalog) -> Self {
+        Self {
+            map: Map::new(),
+            catalog: Some(catalog),
+        }
+    }
+
+    /// Pushes the message for error `code` on `field`.
+    pub fn push_code(&mut self, field: &str, code: &str, params: &[(&str, &str)]) {
+        let msg = self.message(code, params);
+        Self::push_to(&mut self.map, field, msg);
+    }
+
+    /// Pushes the message for error `code` on the payload as a whole.
+    pub fn push_schema_code(&mut self, code: &str, params: &[(&str, &str)]) {
+        let msg = self.message(code, params);
+        Self::push_to(&mut self.map, "_schema", msg);
+    }
+
+    fn message(&self, code: &str, params: &[(&str, &str)]) -> String {
+        self.catalog
+            .and_then(|c| c.message(code, params))
+            .or_else(|| EnglishMessages.message(code, params))
+            .unwrap_or_else(|| code.to_string())
+    }
+
     pub fn push_schema(&mut self, msg: impl Into<String>) {
         Self::push_to(&mut self.map, "_schema", msg);
     }
@@ -37,6 +131,28 @@ impl SchemaErrors {
         }
     }
 
+    /// Adds `nested`'s errors under `prefix`, as `prefix.field` or
+    /// `prefix[0]`; its schema-level errors go to `prefix` itself.
+    pub fn extend_nested(&mut self, prefix: &str, nested: SchemaErrors) {
+        for (key, msgs) in nested.map {
+            let path = if key == "_schema" {
+                prefix.to_string()
+            } else if key.starts_with('[') {
+                format!("{prefix}{key}")
+            } else {
+                format!("{prefix}.{key}")
+            };
+            let Value::Array(msgs) = msgs else {
+                continue;
+            };
+            for msg in msgs {
+                if let Value::String(msg) = msg {
+                    Self::push_to(&mut self.map, &path, msg);
+                }
+            }
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.map.is_empty()
     }
@@ -58,6 +174,36 @@ pub fn schema_error(message: &str, msg: impl Into<String>) -> anyhow::Error {
     unprocessable(message, json!({"_schema": [msg.into()]}))
 }
 
+/// Used by `#[schema]`-generated code; not a public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use regex::Regex;
+
+    use std::sync::OnceLock;
+
+    /// `#[dog(email)]`: one `@`, no whitespace, and a dot in the domain.
+    pub fn is_email(v: &str) -> bool {
+        static EMAIL: OnceLock<Regex> = OnceLock::new();
+        EMAIL
+            .get_or_init(|| Regex::new(r"^[^\s@]+@[^\s@]+\.[^\s@.]+$").expect("valid regex"))
+            .is_match(v)
+    }
+
+    /// `#[dog(url)]`: an absolute URL with a host.
+    pub fn is_url(v: &str) -> bool {
+        url::Url::parse(v).is_ok_and(|u| u.has_host())
+    }
+
+    /// `integer` fields: a number without a fraction or exponent.
+    ///
+    /// Unlike `is_i64() || is_u64()`, this also accepts integers wider than
+    /// 64 bits, which serde_json keeps exact with `arbitrary_precision`.
+    pub fn is_integer(v: &serde_json::Value) -> bool {
+        v.as_number()
+            .is_some_and(|n| !n.to_string().contains(['.', 'e', 'E']))
+    }
+}
+
 pub mod schema_hooks;
 pub use schema_hooks::{
     HookMeta, ResolveData, Rules, SchemaBuilder, SchemaHooksExt, ValidateData, WriteMethods,
@@ -0,0 +1,27 @@
+//! Helpers shared by the schema integration tests.
+#![allow(dead_code)]
+
+use dog_core::errors::DogError;
+use dog_core::{DogApp, ServiceCaller, ServiceMethodKind, TenantContext};
+use dog_schema::HookMeta;
+use serde_json::Value;
+
+/// Hook metadata for a `method` call in the `test` tenant.
+pub fn meta(method: ServiceMethodKind) -> HookMeta<Value, ()> {
+    let app: DogApp<Value, ()> = DogApp::default();
+    HookMeta {
+        tenant: TenantContext::new("test"),
+        method,
+        params: (),
+        config: app.config_snapshot(),
+        services: ServiceCaller::new(app),
+    }
+}
+
+/// The field errors of a failed validation.
+pub fn errors(result: anyhow::Result<()>) -> Value {
+    let err = result.unwrap_err();
+    DogError::from_anyhow(&err)
+        .and_then(|e| e.errors.clone())
+        .expect("validation errors")
+}
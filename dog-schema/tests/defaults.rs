@@ -0,0 +1,81 @@
+mod common;
+
+use common::meta;
+use dog_core::ServiceMethodKind;
+use serde_json::{json, Value};
+
+#[dog_schema::schema(service = "posts")]
+mod posts_schema {
+    #[create]
+    pub struct CreatePost {
+        pub title: String,
+        #[dog(default = "draft")]
+        pub status: String,
+        #[dog(default = 10, min = 1)]
+        pub limit: u32,
+        #[dog(default = -0.5)]
+        pub weight: f64,
+        #[dog(default = false)]
+        pub published: bool,
+    }
+}
+
+fn resolve_create(mut data: Value) -> Value {
+    posts_schema::resolve_create(&mut data, &meta(ServiceMethodKind::Create)).unwrap();
+    data
+}
+
+#[test]
+fn defaults_fill_missing_fields() {
+    let data = resolve_create(json!({ "title": "Hello" }));
+
+    assert_eq!(
+        data,
+        json!({
+            "title": "Hello",
+            "status": "draft",
+            "limit": 10,
+            "weight": -0.5,
+            "published": false,
+        })
+    );
+    assert!(posts_schema::validate_create(&data, &meta(ServiceMethodKind::Create)).is_ok());
+}
+
+#[test]
+fn defaults_leave_present_fields_alone() {
+    let data = resolve_create(json!({
+        "title": "Hello",
+        "status": "published",
+        "limit": 3,
+        "weight": 2.0,
+        "published": true,
+    }));
+
+    assert_eq!(data["status"], "published");
+    assert_eq!(data["limit"], 3);
+    assert_eq!(data["weight"], 2.0);
+    assert_eq!(data["published"], true);
+}
+
+#[test]
+fn defaulted_fields_are_still_validated_when_present() {
+    let meta = meta(ServiceMethodKind::Update);
+
+    assert!(posts_schema::validate_create(&json!({ "title": "Hello" }), &meta).is_ok());
+    assert!(
+        posts_schema::validate_create(&json!({ "title": "Hello", "limit": 0 }), &meta).is_err()
+    );
+    assert!(
+        posts_schema::validate_create(&json!({ "title": "Hello", "status": 1 }), &meta).is_err()
+    );
+}
+
+#[test]
+fn defaults_appear_in_json_schema() {
+    let schema = posts_schema::json_schema();
+
+    assert_eq!(schema["create"]["properties"]["status"]["default"], "draft");
+    assert_eq!(schema["create"]["properties"]["limit"]["default"], 10);
+    assert_eq!(schema["create"]["required"], json!(["title"]));
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use common::{errors, meta};
+use dog_core::ServiceMethodKind;
+use serde_json::{json, Value};
+
+#[dog_schema::schema(service = "contacts")]
+mod contacts_schema {
+    #[create]
+    pub struct CreateContact {
+        #[dog(trim, email)]
+        pub email: String,
+        #[dog(url)]
+        pub website: Option<String>,
+    }
+
+    #[patch]
+    pub struct PatchContact {
+        #[dog(email)]
+        pub email: Option<String>,
+    }
+}
+
+fn validate_create(data: Value) -> anyhow::Result<()> {
+    contacts_schema::validate_create(&data, &meta(ServiceMethodKind::Create))
+}
+
+#[test]
+fn valid_emails_pass() {
+    for email in ["ada@example.com", "first.last+tag@mail.example.co.uk"] {
+        assert!(
+            validate_create(json!({ "email": email })).is_ok(),
+            "{email}"
+        );
+    }
+}
+
+#[test]
+fn invalid_emails_are_field_errors() {
+    for email in [
+        "ada",
+        "ada@",
+        "@example.com",
+        "ada@example",
+        "a da@example.com",
+    ] {
+        let errs = errors(validate_create(json!({ "email": email })));
+        assert_eq!(errs["email"], json!(["must be a valid email"]), "{email}");
+    }
+}
+
+#[test]
+fn valid_urls_pass() {
+    for url in ["https://example.com", "http://localhost:3030/path?q=1"] {
+        assert!(
+            validate_create(json!({ "email": "ada@example.com", "website": url })).is_ok(),
+            "{url}"
+        );
+    }
+}
+
+#[test]
+fn invalid_urls_are_field_errors() {
+    for url in ["example.com", "not a url", "mailto:ada@example.com"] {
+        let errs = errors(validate_create(
+            json!({ "email": "ada@example.com", "website": url }),
+        ));
+        assert_eq!(errs.as_object().unwrap().len(), 1);
+        assert_eq!(errs["website"], json!(["must be a valid URL"]), "{url}");
+    }
+}
+
+#[test]
+fn optional_field_may_be_absent() {
+    assert!(validate_create(json!({ "email": "ada@example.com" })).is_ok());
+}
+
+#[test]
+fn email_applies_to_patches() {
+    let meta = meta(ServiceMethodKind::Patch);
+    assert!(contacts_schema::validate_patch(&json!({}), &meta).is_ok());
+
+    let errs = errors(contacts_schema::validate_patch(
+        &json!({ "email": "nope" }),
+        &meta,
+    ));
+    assert_eq!(errs["email"], json!(["must be a valid email"]));
+}
+
+#[test]
+fn json_schema_has_the_formats() {
+    let schema = contacts_schema::json_schema();
+
+    assert_eq!(
+        schema["create"]["properties"]["email"],
+        json!({ "type": "string", "format": "email" })
+    );
+    assert_eq!(
+        schema["create"]["properties"]["website"],
+        json!({ "type": "string", "format": "uri" })
+    );
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use common::{errors, meta};
+use dog_core::ServiceMethodKind;
+use dog_schema::{format_message, MessageCatalog};
+use serde_json::json;
+
+#[dog_schema::schema(service = "accounts")]
+mod accounts_schema {
+    #[create]
+    pub struct CreateAccount {
+        #[dog(
+            min_len(3, message = "too short"),
+            max_len(8, message = "{max} chars max")
+        )]
+        pub username: String,
+        #[dog(min(18, message = "adults only"))]
+        pub age: u32,
+    }
+}
+
+/// French text for a few codes; the rest fall back to English.
+struct French;
+
+impl MessageCatalog for French {
+    fn message(&self, code: &str, params: &[(&str, &str)]) -> Option<String> {
+        let template = match code {
+            "required" => "champ `{field}` manquant",
+            "min_len" => "doit contenir au moins {min} caractères",
+            "string" => "doit être une chaîne",
+            _ => return None,
+        };
+        Some(format_message(template, params))
+    }
+}
+
+static FRENCH: French = French;
+
+#[dog_schema::schema(service = "comptes", messages = "super::FRENCH")]
+mod comptes_schema {
+    #[create]
+    pub struct CreateCompte {
+        #[dog(min_len(3))]
+        pub pseudo: String,
+        #[dog(max = 120)]
+        pub age: Option<u32>,
+    }
+}
+
+#[test]
+fn rule_messages_override_the_defaults() {
+    let meta = meta(ServiceMethodKind::Create);
+
+    let errs = errors(accounts_schema::validate_create(
+        &json!({ "username": "ab", "age": 12 }),
+        &meta,
+    ));
+    assert_eq!(
+        errs,
+        json!({ "username": ["too short"], "age": ["adults only"] })
+    );
+
+    let errs = errors(accounts_schema::validate_create(
+        &json!({ "username": "abcdefghi", "age": 30 }),
+        &meta,
+    ));
+    assert_eq!(errs, json!({ "username": ["8 chars max"] }));
+}
+
+#[test]
+fn catalog_localizes_messages() {
+    let meta = meta(ServiceMethodKind::Create);
+
+    let errs = errors(comptes_schema::validate_create(&json!({}), &meta));
+    assert_eq!(errs, json!({ "_schema": ["champ `pseudo` manquant"] }));
+
+    let errs = errors(comptes_schema::validate_create(
+        &json!({ "pseudo": "ab" }),
+        &meta,
+    ));
+    assert_eq!(
+        errs,
+        json!({ "pseudo": ["doit contenir au moins 3 caractères"] })
+    );
+}
+
+#[test]
+fn catalog_falls_back_to_english() {
+    let errs = errors(comptes_schema::validate_create(
+        &json!({ "pseudo": "abc", "age": 200 }),
+        &meta(ServiceMethodKind::Create),
+    ));
+
+    assert_eq!(errs, json!({ "age": ["must be at most 120"] }));
+}
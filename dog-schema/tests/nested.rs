@@ -0,0 +1,116 @@
+mod common;
+
+use common::{errors, meta};
+use dog_core::ServiceMethodKind;
+use serde_json::{json, Value};
+
+#[dog_schema::schema(service = "users")]
+mod users_schema {
+    pub struct Address {
+        #[dog(min_len(2))]
+        pub city: String,
+        pub zip: Option<String>,
+    }
+
+    pub struct Tag {
+        #[dog(email)]
+        pub email: String,
+    }
+
+    #[create]
+    pub struct CreateUser {
+        pub name: String,
+        pub address: Address,
+        pub tags: Vec<Tag>,
+        pub scores: Option<Vec<u32>>,
+    }
+
+    #[patch]
+    pub struct PatchUser {
+        pub address: Option<Address>,
+    }
+}
+
+fn validate_create(data: Value) -> anyhow::Result<()> {
+    users_schema::validate_create(&data, &meta(ServiceMethodKind::Create))
+}
+
+#[test]
+fn valid_nested_payload_passes() {
+    assert!(validate_create(json!({
+        "name": "Ada",
+        "address": { "city": "London" },
+        "tags": [{ "email": "ada@example.com" }],
+        "scores": [1, 2, 3],
+    }))
+    .is_ok());
+}
+
+#[test]
+fn nested_object_errors_are_dotted() {
+    let errs = errors(validate_create(json!({
+        "name": "Ada",
+        "address": { "city": "L", "zip": 12 },
+        "tags": [],
+    })));
+
+    assert_eq!(errs["address.city"], json!(["must be at least 2 chars"]));
+    assert_eq!(errs.as_object().unwrap().len(), 1);
+}
+
+#[test]
+fn missing_nested_field_is_reported_on_the_object() {
+    let errs = errors(validate_create(json!({
+        "name": "Ada",
+        "address": {},
+        "tags": [],
+    })));
+
+    assert_eq!(errs["address"], json!(["missing field `city`"]));
+}
+
+#[test]
+fn array_element_errors_are_indexed() {
+    let errs = errors(validate_create(json!({
+        "name": "Ada",
+        "address": { "city": "London" },
+        "tags": [{ "email": "ada@example.com" }, { "email": "nope" }],
+        "scores": [1, 1.5, "3"],
+    })));
+
+    assert_eq!(
+        errs,
+        json!({
+            "tags[1].email": ["must be a valid email"],
+            "scores[1]": ["must be an integer"],
+            "scores[2]": ["must be an integer"],
+        })
+    );
+}
+
+#[test]
+fn wrong_container_types_are_field_errors() {
+    let errs = errors(validate_create(json!({
+        "name": "Ada",
+        "address": "London",
+        "tags": { "email": "ada@example.com" },
+    })));
+
+    assert_eq!(errs["address"], json!(["must be an object"]));
+    assert_eq!(errs["tags"], json!(["must be an array"]));
+}
+
+#[test]
+fn patch_validates_nested_objects() {
+    let meta = meta(ServiceMethodKind::Patch);
+    assert!(users_schema::validate_patch(&json!({ "address": null }), &meta).is_ok());
+
+    let errs = errors(users_schema::validate_patch(
+        &json!({ "address": { "city": "X" } }),
+        &meta,
+    ));
+    assert_eq!(
+        errs,
+        json!({ "address.city": ["must be at least 2 chars"] })
+    );
+}
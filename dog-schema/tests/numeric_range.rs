@@ -0,0 +1,116 @@
+mod common;
+
+use common::{errors, meta};
+use dog_core::ServiceMethodKind;
+use serde_json::json;
+
+#[dog_schema::schema(service = "products")]
+mod products_schema {
+    #[create]
+    pub struct CreateProduct {
+        pub name: String,
+        #[dog(min = 0, max = 100)]
+        pub discount: u32,
+        #[dog(min = -0.5, max = 99.5)]
+        pub rating: Option<f64>,
+        pub stock: i64,
+    }
+
+    #[patch]
+    pub struct PatchProduct {
+        #[dog(min = 0, max = 100)]
+        pub discount: Option<u32>,
+    }
+}
+
+#[test]
+fn values_within_range_pass() {
+    let data = json!({ "name": "Lamp", "discount": 100, "rating": -0.5, "stock": -3 });
+    assert!(products_schema::validate_create(&data, &meta(ServiceMethodKind::Create)).is_ok());
+
+    let data = json!({ "name": "Lamp", "discount": 0, "stock": 0 });
+    assert!(products_schema::validate_create(&data, &meta(ServiceMethodKind::Create)).is_ok());
+}
+
+#[test]
+fn below_min_is_a_field_error() {
+    let data = json!({ "name": "Lamp", "discount": -1, "rating": -1.5, "stock": 1 });
+
+    let errs = errors(products_schema::validate_create(
+        &data,
+        &meta(ServiceMethodKind::Create),
+    ));
+
+    assert_eq!(errs["discount"], json!(["must be at least 0"]));
+    assert_eq!(errs["rating"], json!(["must be at least -0.5"]));
+}
+
+#[test]
+fn above_max_is_a_field_error() {
+    let data = json!({ "name": "Lamp", "discount": 101, "rating": 100, "stock": 1 });
+
+    let errs = errors(products_schema::validate_create(
+        &data,
+        &meta(ServiceMethodKind::Create),
+    ));
+
+    assert_eq!(errs["discount"], json!(["must be at most 100"]));
+    assert_eq!(errs["rating"], json!(["must be at most 99.5"]));
+}
+
+#[test]
+fn non_numeric_values_are_field_errors() {
+    let data = json!({ "name": "Lamp", "discount": "50", "rating": true, "stock": 1.5 });
+
+    let errs = errors(products_schema::validate_create(
+        &data,
+        &meta(ServiceMethodKind::Create),
+    ));
+
+    assert_eq!(errs["discount"], json!(["must be an integer"]));
+    assert_eq!(errs["rating"], json!(["must be a number"]));
+    assert_eq!(errs["stock"], json!(["must be an integer"]));
+}
+
+#[test]
+fn missing_required_number_is_a_schema_error() {
+    let data = json!({ "name": "Lamp", "discount": 5 });
+
+    let errs = errors(products_schema::validate_create(
+        &data,
+        &meta(ServiceMethodKind::Create),
+    ));
+
+    assert_eq!(errs["_schema"], json!(["missing field `stock`"]));
+}
+
+#[test]
+fn patch_checks_range_of_provided_numbers_only() {
+    let meta = meta(ServiceMethodKind::Patch);
+    assert!(products_schema::validate_patch(&json!({}), &meta).is_ok());
+    assert!(products_schema::validate_patch(&json!({ "discount": null }), &meta).is_ok());
+    assert!(products_schema::validate_patch(&json!({ "discount": 40 }), &meta).is_ok());
+
+    let errs = errors(products_schema::validate_patch(
+        &json!({ "discount": 250 }),
+        &meta,
+    ));
+    assert_eq!(errs["discount"], json!(["must be at most 100"]));
+
+    let errs = errors(products_schema::validate_patch(
+        &json!({ "discount": "lots" }),
+        &meta,
+    ));
+    assert_eq!(errs["discount"], json!(["must be an integer"]));
+}
+
+#[test]
+fn json_schema_has_the_bounds() {
+    let schema = products_schema::json_schema();
+
+    let discount = &schema["create"]["properties"]["discount"];
+    assert_eq!(discount["type"], "integer");
+    assert_eq!(discount["minimum"], 0.0);
+    assert_eq!(discount["maximum"], 100.0);
+    assert_eq!(schema["patch"]["properties"]["discount"]["maximum"], 100.0);
+}
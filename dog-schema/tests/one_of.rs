@@ -0,0 +1,71 @@
+mod common;
+
+use common::{errors, meta};
+use dog_core::ServiceMethodKind;
+use serde_json::{json, Value};
+
+#[dog_schema::schema(service = "articles")]
+mod articles_schema {
+    #[create]
+    pub struct CreateArticle {
+        #[dog(one_of("draft", "published", "archived"))]
+        pub status: String,
+    }
+
+    #[patch]
+    pub struct PatchArticle {
+        #[dog(one_of("draft", "published", "archived"))]
+        pub status: Option<String>,
+    }
+}
+
+fn validate_create(data: Value) -> anyhow::Result<()> {
+    articles_schema::validate_create(&data, &meta(ServiceMethodKind::Create))
+}
+
+#[test]
+fn allowed_value_passes() {
+    assert!(validate_create(json!({ "status": "published" })).is_ok());
+}
+
+#[test]
+fn value_outside_the_set_lists_the_allowed_values() {
+    let errs = errors(validate_create(json!({ "status": "deleted" })));
+
+    assert_eq!(
+        errs["status"],
+        json!(["must be one of draft, published, archived"])
+    );
+}
+
+#[test]
+fn matching_is_case_sensitive() {
+    assert!(validate_create(json!({ "status": "Draft" })).is_err());
+}
+
+#[test]
+fn patch_allows_absence_and_null() {
+    let meta = meta(ServiceMethodKind::Patch);
+    assert!(articles_schema::validate_patch(&json!({}), &meta).is_ok());
+    assert!(articles_schema::validate_patch(&json!({ "status": null }), &meta).is_ok());
+    assert!(articles_schema::validate_patch(&json!({ "status": "archived" }), &meta).is_ok());
+
+    let errs = errors(articles_schema::validate_patch(
+        &json!({ "status": "gone" }),
+        &meta,
+    ));
+    assert_eq!(
+        errs["status"],
+        json!(["must be one of draft, published, archived"])
+    );
+}
+
+#[test]
+fn json_schema_has_the_enum() {
+    let schema = articles_schema::json_schema();
+
+    assert_eq!(
+        schema["create"]["properties"]["status"],
+        json!({ "type": "string", "enum": ["draft", "published", "archived"] })
+    );
+}
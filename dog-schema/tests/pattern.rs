@@ -0,0 +1,78 @@
+mod common;
+
+use common::{errors, meta};
+use dog_core::ServiceMethodKind;
+use serde_json::{json, Value};
+
+#[dog_schema::schema(service = "pages")]
+mod pages_schema {
+    #[create]
+    pub struct CreatePage {
+        #[dog(pattern = "^[a-z0-9-]+$")]
+        pub slug: String,
+        #[dog(pattern = r"^\+?[0-9 ]{7,15}$")]
+        pub phone: Option<String>,
+    }
+
+    #[patch]
+    pub struct PatchPage {
+        #[dog(pattern = "^[a-z0-9-]+$")]
+        pub slug: Option<String>,
+    }
+}
+
+fn validate_create(data: Value) -> anyhow::Result<()> {
+    pages_schema::validate_create(&data, &meta(ServiceMethodKind::Create))
+}
+
+#[test]
+fn matching_values_pass() {
+    assert!(
+        validate_create(json!({ "slug": "hello-world-2", "phone": "+44 20 7946 0958" })).is_ok()
+    );
+}
+
+#[test]
+fn non_matching_value_is_a_field_error() {
+    let errs = errors(validate_create(json!({ "slug": "Hello World!" })));
+
+    assert_eq!(errs["slug"], json!(["must match pattern ^[a-z0-9-]+$"]));
+}
+
+#[test]
+fn optional_field_may_be_absent_but_not_invalid() {
+    assert!(validate_create(json!({ "slug": "about" })).is_ok());
+
+    let errs = errors(validate_create(
+        json!({ "slug": "about", "phone": "call me" }),
+    ));
+
+    assert_eq!(errs.as_object().unwrap().len(), 1);
+    assert_eq!(
+        errs["phone"],
+        json!([r"must match pattern ^\+?[0-9 ]{7,15}$"])
+    );
+}
+
+#[test]
+fn pattern_applies_to_patches() {
+    let meta = meta(ServiceMethodKind::Patch);
+    assert!(pages_schema::validate_patch(&json!({}), &meta).is_ok());
+    assert!(pages_schema::validate_patch(&json!({ "slug": "new-slug" }), &meta).is_ok());
+
+    let errs = errors(pages_schema::validate_patch(
+        &json!({ "slug": "new_slug" }),
+        &meta,
+    ));
+    assert_eq!(errs["slug"], json!(["must match pattern ^[a-z0-9-]+$"]));
+}
+
+#[test]
+fn json_schema_has_the_pattern() {
+    let schema = pages_schema::json_schema();
+
+    assert_eq!(
+        schema["create"]["properties"]["slug"],
+        json!({ "type": "string", "pattern": "^[a-z0-9-]+$" })
+    );
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use common::{errors, meta};
+use dog_core::ServiceMethodKind;
+use serde_json::{json, Value};
+
+#[dog_schema::schema(service = "addresses")]
+mod addresses_schema {
+    #[create]
+    #[dog(requires(if = "country", equals = "US", then = "postal_code"))]
+    #[dog(requires(if = "phone", then = "phone_country"))]
+    pub struct CreateAddress {
+        pub country: String,
+        pub postal_code: Option<String>,
+        pub phone: Option<String>,
+        pub phone_country: Option<String>,
+    }
+}
+
+fn validate_create(data: Value) -> anyhow::Result<()> {
+    addresses_schema::validate_create(&data, &meta(ServiceMethodKind::Create))
+}
+
+#[test]
+fn field_is_required_when_the_condition_holds() {
+    assert!(validate_create(json!({ "country": "US", "postal_code": "94103" })).is_ok());
+
+    let errs = errors(validate_create(json!({ "country": "US" })));
+    assert_eq!(
+        errs,
+        json!({ "postal_code": ["is required when country is US"] })
+    );
+
+    let errs = errors(validate_create(
+        json!({ "country": "US", "postal_code": null }),
+    ));
+    assert_eq!(
+        errs,
+        json!({ "postal_code": ["is required when country is US"] })
+    );
+}
+
+#[test]
+fn field_is_optional_when_the_condition_does_not_hold() {
+    assert!(validate_create(json!({ "country": "NZ" })).is_ok());
+}
+
+#[test]
+fn condition_without_equals_checks_presence() {
+    assert!(validate_create(json!({ "country": "NZ", "phone": null })).is_ok());
+
+    let errs = errors(validate_create(
+        json!({ "country": "NZ", "phone": "021 123 4567" }),
+    ));
+    assert_eq!(
+        errs,
+        json!({ "phone_country": ["is required when phone is set"] })
+    );
+}
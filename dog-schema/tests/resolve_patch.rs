@@ -0,0 +1,86 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::meta;
+use dog_core::{DogApp, DogService, ServiceMethodKind, TenantContext};
+use serde_json::{json, Value};
+
+#[dog_schema::schema(service = "tags")]
+mod tags_schema {
+    #[create]
+    pub struct CreateTag {
+        #[dog(trim, max_len(5))]
+        pub label: String,
+    }
+
+    #[patch]
+    pub struct PatchTag {
+        #[dog(trim, max_len(5))]
+        pub label: Option<String>,
+        #[dog(trim)]
+        pub note: Option<String>,
+    }
+}
+
+/// Returns the patch it was given, as stored.
+struct Echo;
+
+#[async_trait::async_trait]
+impl DogService<Value, ()> for Echo {
+    async fn patch(
+        &self,
+        _ctx: &TenantContext,
+        _id: Option<&str>,
+        data: Value,
+        _params: (),
+    ) -> anyhow::Result<Value> {
+        Ok(data)
+    }
+}
+
+#[test]
+fn resolve_patch_trims_present_strings_only() {
+    let mut data = json!({ "label": "  rust  ", "note": null });
+
+    tags_schema::resolve_patch(&mut data, &meta(ServiceMethodKind::Patch)).unwrap();
+
+    assert_eq!(data, json!({ "label": "rust", "note": null }));
+}
+
+#[tokio::test]
+async fn patches_are_trimmed_before_validation() {
+    let mut builder = DogApp::<Value, ()>::builder();
+    tags_schema::register(&mut builder).unwrap();
+    let app = builder.build();
+    app.register_service("tags", Arc::new(Echo));
+
+    let stored = app
+        .service("tags")
+        .unwrap()
+        .patch(
+            TenantContext::new("test"),
+            Some("1"),
+            json!({ "label": " rust  " }),
+            (),
+        )
+        .await
+        .unwrap();
+
+    // "rust" fits max_len(5) only once trimmed.
+    assert_eq!(stored, json!({ "label": "rust" }));
+
+    // Create validation doesn't apply: `label` isn't required on patch.
+    let stored = app
+        .service("tags")
+        .unwrap()
+        .patch(
+            TenantContext::new("test"),
+            Some("1"),
+            json!({ "note": " hi " }),
+            (),
+        )
+        .await
+        .unwrap();
+    assert_eq!(stored, json!({ "note": "hi" }));
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::{errors, meta};
+use dog_core::ServiceMethodKind;
+use serde_json::{json, Value};
+
+#[dog_schema::schema(service = "handles")]
+mod handles_schema {
+    #[create]
+    pub struct CreateHandle {
+        #[dog(trim, min_len(3), max_len(8))]
+        pub name: String,
+        #[dog(len(2))]
+        pub country: Option<String>,
+    }
+
+    #[patch]
+    pub struct PatchHandle {
+        #[dog(max_len(8))]
+        pub name: Option<String>,
+    }
+}
+
+/// Runs `resolve_create` then `validate_create`, as the create pipeline does.
+fn create(mut data: Value) -> anyhow::Result<()> {
+    let meta = meta(ServiceMethodKind::Create);
+    handles_schema::resolve_create(&mut data, &meta)?;
+    handles_schema::validate_create(&data, &meta)
+}
+
+#[test]
+fn values_within_bounds_pass() {
+    assert!(create(json!({ "name": "abc" })).is_ok());
+    assert!(create(json!({ "name": "abcdefgh", "country": "NZ" })).is_ok());
+}
+
+#[test]
+fn too_long_value_is_a_field_error() {
+    let errs = errors(create(json!({ "name": "abcdefghi" })));
+
+    assert_eq!(errs["name"], json!(["must be at most 8 chars"]));
+}
+
+#[test]
+fn trim_runs_before_max_len() {
+    assert!(create(json!({ "name": "  abcdefgh \n" })).is_ok());
+
+    let errs = errors(create(json!({ "name": " abcdefghi " })));
+    assert_eq!(errs["name"], json!(["must be at most 8 chars"]));
+}
+
+#[test]
+fn length_counts_chars_not_bytes() {
+    // 8 chars, 16 bytes
+    assert!(create(json!({ "name": "ééééé€€€" })).is_ok());
+    assert!(create(json!({ "name": "abc", "country": "ÅÖ" })).is_ok());
+}
+
+#[test]
+fn exact_length_is_enforced() {
+    let errs = errors(create(json!({ "name": "abc", "country": "NZL" })));
+
+    assert_eq!(errs.as_object().unwrap().len(), 1);
+    assert_eq!(errs["country"], json!(["must be exactly 2 chars"]));
+}
+
+#[test]
+fn max_len_applies_to_patches() {
+    let meta = meta(ServiceMethodKind::Patch);
+    assert!(handles_schema::validate_patch(&json!({ "name": "abcdefgh" }), &meta).is_ok());
+
+    let errs = errors(handles_schema::validate_patch(
+        &json!({ "name": "abcdefghi" }),
+        &meta,
+    ));
+    assert_eq!(errs["name"], json!(["must be at most 8 chars"]));
+}
+
+#[test]
+fn json_schema_has_the_length_bounds() {
+    let schema = handles_schema::json_schema();
+
+    assert_eq!(
+        schema["create"]["properties"]["name"],
+        json!({ "type": "string", "minLength": 3, "maxLength": 8 })
+    );
+    assert_eq!(
+        schema["create"]["properties"]["country"],
+        json!({ "type": "string", "minLength": 2, "maxLength": 2 })
+    );
+}
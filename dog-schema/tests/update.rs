@@ -0,0 +1,171 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::{errors, meta};
+use dog_core::errors::DogError;
+use dog_core::{DogApp, DogService, ServiceMethodKind, TenantContext};
+use serde_json::{json, Value};
+
+#[dog_schema::schema(service = "accounts")]
+mod accounts_schema {
+    #[create]
+    pub struct CreateAccount {
+        #[dog(min_len(3))]
+        pub username: String,
+        #[dog(email)]
+        pub email: String,
+    }
+
+    #[update]
+    pub struct UpdateAccount {
+        #[dog(immutable)]
+        pub username: Option<String>,
+        #[dog(email)]
+        pub email: String,
+    }
+}
+
+#[dog_schema::schema(service = "notes")]
+mod notes_schema {
+    #[create]
+    pub struct CreateNote {
+        #[dog(min_len(1))]
+        pub body: String,
+    }
+}
+
+/// Returns the record it was given, as stored.
+struct Echo;
+
+#[async_trait::async_trait]
+impl DogService<Value, ()> for Echo {
+    async fn create(
+        &self,
+        _ctx: &TenantContext,
+        data: Value,
+        _params: (),
+    ) -> anyhow::Result<Value> {
+        Ok(data)
+    }
+
+    async fn update(
+        &self,
+        _ctx: &TenantContext,
+        _id: &str,
+        data: Value,
+        _params: (),
+    ) -> anyhow::Result<Value> {
+        Ok(data)
+    }
+}
+
+fn app() -> DogApp<Value, ()> {
+    let mut builder = DogApp::<Value, ()>::builder();
+    accounts_schema::register(&mut builder).unwrap();
+    notes_schema::register(&mut builder).unwrap();
+    let app = builder.build();
+    app.register_service("accounts", Arc::new(Echo));
+    app.register_service("notes", Arc::new(Echo));
+    app
+}
+
+#[test]
+fn immutable_field_is_rejected_on_update() {
+    let meta = meta(ServiceMethodKind::Update);
+    let errs = errors(accounts_schema::validate_update(
+        &json!({ "username": "ada", "email": "ada@example.com" }),
+        &meta,
+    ));
+
+    assert_eq!(errs.as_object().unwrap().len(), 1);
+    assert_eq!(errs["username"], json!(["cannot be changed"]));
+}
+
+#[test]
+fn update_without_the_immutable_field_passes() {
+    let meta = meta(ServiceMethodKind::Update);
+    assert!(
+        accounts_schema::validate_update(&json!({ "email": "ada@example.com" }), &meta).is_ok()
+    );
+    assert!(accounts_schema::validate_update(
+        &json!({ "username": null, "email": "ada@example.com" }),
+        &meta
+    )
+    .is_ok());
+}
+
+#[test]
+fn update_has_its_own_rules() {
+    let meta = meta(ServiceMethodKind::Update);
+    let errs = errors(accounts_schema::validate_update(&json!({}), &meta));
+
+    assert_eq!(errs["_schema"], json!(["missing field `email`"]));
+}
+
+#[test]
+fn create_may_set_the_immutable_field() {
+    let meta = meta(ServiceMethodKind::Create);
+    assert!(accounts_schema::validate_create(
+        &json!({ "username": "ada", "email": "ada@example.com" }),
+        &meta
+    )
+    .is_ok());
+}
+
+#[tokio::test]
+async fn register_validates_updates_with_the_update_rules() {
+    let app = app();
+    let accounts = app.service("accounts").unwrap();
+    let tenant = || TenantContext::new("test");
+
+    let account = json!({ "username": "ada", "email": "ada@example.com" });
+    let created = accounts
+        .create(tenant(), account.clone(), ())
+        .await
+        .unwrap();
+    assert_eq!(created, account);
+
+    let err = accounts
+        .update(tenant(), "1", account, ())
+        .await
+        .unwrap_err();
+    let errs = DogError::from_anyhow(&err)
+        .and_then(|e| e.errors.clone())
+        .unwrap();
+    assert_eq!(errs["username"], json!(["cannot be changed"]));
+
+    let stored = accounts
+        .update(tenant(), "1", json!({ "email": "ada@example.org" }), ())
+        .await
+        .unwrap();
+    assert_eq!(stored, json!({ "email": "ada@example.org" }));
+}
+
+#[tokio::test]
+async fn update_falls_back_to_create_rules() {
+    let app = app();
+    let notes = app.service("notes").unwrap();
+
+    let err = notes
+        .update(TenantContext::new("test"), "1", json!({ "body": "" }), ())
+        .await
+        .unwrap_err();
+    let errs = DogError::from_anyhow(&err)
+        .and_then(|e| e.errors.clone())
+        .unwrap();
+    assert!(errs["body"].is_array());
+
+    assert!(notes_schema::json_schema().get("update").is_none());
+}
+
+#[test]
+fn json_schema_has_the_update_payload() {
+    let schema = accounts_schema::json_schema();
+
+    assert_eq!(schema["update"]["required"], json!(["email"]));
+    assert_eq!(
+        schema["update"]["properties"]["username"],
+        json!({ "type": "string", "readOnly": true })
+    );
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::{errors, meta};
+use dog_core::ServiceMethodKind;
+use dog_schema::SchemaErrors;
+use serde_json::{json, Value};
+
+#[dog_schema::schema(service = "bookings")]
+mod bookings_schema {
+    #[create]
+    #[dog(validate_with = "super::ends_after_start")]
+    pub struct CreateBooking {
+        #[dog(min = 0)]
+        pub start: i64,
+        pub end: i64,
+        #[dog(validate_with = "super::no_reserved_rooms")]
+        pub room: String,
+    }
+
+    #[patch]
+    #[dog(validate_with = "super::ends_after_start")]
+    pub struct PatchBooking {
+        pub start: Option<i64>,
+        pub end: Option<i64>,
+    }
+}
+
+fn ends_after_start(data: &Value, errs: &mut SchemaErrors) {
+    if let (Some(start), Some(end)) = (data["start"].as_i64(), data["end"].as_i64()) {
+        if end <= start {
+            errs.push_field("end", "must be after start");
+        }
+    }
+}
+
+fn no_reserved_rooms(data: &Value, errs: &mut SchemaErrors) {
+    if data["room"] == "boardroom" {
+        errs.push_field("room", "is reserved");
+    }
+}
+
+fn validate_create(data: Value) -> anyhow::Result<()> {
+    bookings_schema::validate_create(&data, &meta(ServiceMethodKind::Create))
+}
+
+#[test]
+fn custom_checks_pass_on_valid_data() {
+    assert!(validate_create(json!({ "start": 1, "end": 2, "room": "kitchen" })).is_ok());
+}
+
+#[test]
+fn struct_validator_cross_checks_fields() {
+    let errs = errors(validate_create(
+        json!({ "start": 5, "end": 3, "room": "kitchen" }),
+    ));
+
+    assert_eq!(errs, json!({ "end": ["must be after start"] }));
+}
+
+#[test]
+fn custom_errors_join_the_generated_ones() {
+    let errs = errors(validate_create(
+        json!({ "start": -5, "end": -6, "room": "boardroom" }),
+    ));
+
+    assert_eq!(
+        errs,
+        json!({
+            "start": ["must be at least 0"],
+            "end": ["must be after start"],
+            "room": ["is reserved"],
+        })
+    );
+}
+
+#[test]
+fn patch_runs_its_own_validators() {
+    let meta = meta(ServiceMethodKind::Patch);
+    assert!(bookings_schema::validate_patch(&json!({ "end": 1 }), &meta).is_ok());
+
+    let errs = errors(bookings_schema::validate_patch(
+        &json!({ "start": 2, "end": 1 }),
+        &meta,
+    ));
+    assert_eq!(errs, json!({ "end": ["must be after start"] }));
+}
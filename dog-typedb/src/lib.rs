@@ -1,9 +1,27 @@
 pub mod adapter;
+pub mod migrations;
+pub mod pool;
+pub mod query;
+pub mod results;
 pub mod service;
+pub mod stream;
 pub mod transactions;
 
 pub use adapter::TypeDBAdapter;
-pub use service::{TypeDBDriverFactory, TypeDBService, TypeDBServiceHandlers};
+pub use migrations::{
+    load_migrations, run_migrations, run_migrations_with, Migration, MigrationReport,
+    MigrationStore, TypeDBMigrationStore,
+};
+pub use pool::{Connector, Pool, PoolOptions, PooledConnection};
+pub use query::{quote_string, Query, TypeQLValue};
+pub use results::{decode_rows, decode_rows_with, flatten_rows};
+pub use service::{
+    FindQueryBuilder, InsertQueryBuilder, TypeDBConnector, TypeDBDriverFactory, TypeDBPool,
+    TypeDBService, TypeDBServiceHandlers,
+};
+pub use stream::{stream_typedb_query, AnswerStream};
 pub use transactions::{
-    execute_read_transaction, execute_typedb_query, load_schema_from_file, TransactionType,
+    execute_read_transaction, execute_typedb_query, execute_typedb_query_with_retry,
+    execute_write_batch, is_transient_write_error, load_schema_from_file, retry_write,
+    run_write_batch, TransactionType, WriteConflict, WriteRetryPolicy, WriteTransaction,
 };
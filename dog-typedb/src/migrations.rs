@@ -0,0 +1,209 @@
+//! Versioned schema migrations.
+//!
+//! A migrations directory holds one `.tql` file per migration, named
+//! `<version>_<description>.tql` (e.g. `0001_initial.tql`,
+//! `0002_add_email.tql`). Each file is a single `define`, `undefine` or
+//! `redefine` query. Migrations are applied in version order, each in its
+//! own schema transaction together with a record of its version, so a
+//! migration is either fully applied and recorded or not at all. Versions
+//! already recorded in the database are skipped, which makes
+//! [`run_migrations`] safe to call on every start.
+//!
+//! Applied versions are stored as `dog-migration` entities, defined on the
+//! first run.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use typedb_driver::TypeDBDriver;
+
+use crate::results::flatten_rows;
+use crate::transactions::{
+    execute_read_transaction, execute_typedb_query, typedb_answer_to_http_ok,
+};
+
+const TRACKING_SCHEMA: &str = "define
+    attribute dog-migration-version, value integer;
+    attribute dog-migration-name, value string;
+    entity dog-migration, owns dog-migration-version @key, owns dog-migration-name;";
+
+/// One migration file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migration {
+    pub version: u64,
+    /// The file name without `.tql`, e.g. `0001_initial`.
+    pub name: String,
+    pub query: String,
+}
+
+/// What [`run_migrations`] did, by migration name, in version order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    pub applied: Vec<String>,
+    /// Migrations found already applied.
+    pub skipped: Vec<String>,
+}
+
+/// Where migrations are applied and their versions recorded.
+#[async_trait]
+pub trait MigrationStore: Send + Sync {
+    /// Versions recorded as applied.
+    async fn applied_versions(&self) -> Result<Vec<u64>>;
+
+    /// Run `migration` and record its version, atomically.
+    async fn apply(&self, migration: &Migration) -> Result<()>;
+}
+
+/// The `.tql` migrations of `dir`, in version order. Other files are
+/// ignored.
+pub fn load_migrations(dir: impl AsRef<Path>) -> Result<Vec<Migration>> {
+    let dir = dir.as_ref();
+    let entries = fs::read_dir(dir).map_err(|e| {
+        anyhow!(
+            "Failed to read migrations directory {}: {}",
+            dir.display(),
+            e
+        )
+    })?;
+
+    let mut migrations = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("tql") {
+            continue;
+        }
+        let name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .ok_or_else(|| anyhow!("Invalid migration file name: {}", path.display()))?
+            .to_string();
+        let version = name
+            .split('_')
+            .next()
+            .and_then(|v| v.parse::<u64>().ok())
+            .ok_or_else(|| {
+                anyhow!(
+                    "Migration {} must be named <version>_<description>.tql",
+                    path.display()
+                )
+            })?;
+        let query = fs::read_to_string(&path)
+            .map_err(|e| anyhow!("Failed to read migration {}: {}", path.display(), e))?;
+        migrations.push(Migration {
+            version,
+            name,
+            query,
+        });
+    }
+
+    migrations.sort_by_key(|m| m.version);
+    if let Some(pair) = migrations.windows(2).find(|w| w[0].version == w[1].version) {
+        return Err(anyhow!(
+            "Migrations {} and {} have the same version",
+            pair[0].name,
+            pair[1].name
+        ));
+    }
+    Ok(migrations)
+}
+
+/// Apply the migrations of `dir` that `store` has not recorded yet.
+///
+/// Stops at the first failing migration; those before it stay applied.
+pub async fn run_migrations_with(
+    store: &dyn MigrationStore,
+    dir: impl AsRef<Path>,
+) -> Result<MigrationReport> {
+    let migrations = load_migrations(dir)?;
+    let applied = store.applied_versions().await?;
+
+    let mut report = MigrationReport::default();
+    for migration in migrations {
+        if applied.contains(&migration.version) {
+            report.skipped.push(migration.name);
+            continue;
+        }
+        store
+            .apply(&migration)
+            .await
+            .map_err(|e| e.context(format!("Migration {} failed", migration.name)))?;
+        report.applied.push(migration.name);
+    }
+    Ok(report)
+}
+
+/// Apply the pending migrations of `dir` to `database`. See the
+/// [module docs](self).
+pub async fn run_migrations(
+    driver: &TypeDBDriver,
+    database: &str,
+    dir: impl AsRef<Path>,
+) -> Result<MigrationReport> {
+    run_migrations_with(&TypeDBMigrationStore::new(driver, database), dir).await
+}
+
+/// [`MigrationStore`] of a TypeDB database.
+pub struct TypeDBMigrationStore<'a> {
+    driver: &'a TypeDBDriver,
+    database: &'a str,
+}
+
+impl<'a> TypeDBMigrationStore<'a> {
+    pub fn new(driver: &'a TypeDBDriver, database: &'a str) -> Self {
+        Self { driver, database }
+    }
+}
+
+#[async_trait]
+impl MigrationStore for TypeDBMigrationStore<'_> {
+    async fn applied_versions(&self) -> Result<Vec<u64>> {
+        // Defining the tracking types again is a no-op.
+        execute_typedb_query(self.driver, self.database, TRACKING_SCHEMA).await?;
+
+        let result = execute_read_transaction(
+            self.driver,
+            self.database,
+            "match $m isa dog-migration, has dog-migration-version $v;",
+        )
+        .await?;
+        flatten_rows(&result)?
+            .iter()
+            .map(|row| {
+                row.get("v")
+                    .and_then(|v| v.as_u64())
+                    .ok_or_else(|| anyhow!("Invalid migration version record: {:?}", row))
+            })
+            .collect()
+    }
+
+    async fn apply(&self, migration: &Migration) -> Result<()> {
+        let tx = self
+            .driver
+            .transaction(self.database, typedb_driver::TransactionType::Schema)
+            .await
+            .map_err(|e| anyhow!("Failed to create schema transaction: {}", e))?;
+
+        let answer = tx
+            .query(&migration.query)
+            .await
+            .map_err(|e| anyhow!("Failed to execute migration query: {}", e))?;
+        typedb_answer_to_http_ok(answer, "schema", &migration.query, 1).await?;
+
+        let record = format!(
+            "insert $m isa dog-migration, has dog-migration-version {}, has dog-migration-name {};",
+            migration.version,
+            serde_json::to_string(&migration.name)?
+        );
+        let answer = tx
+            .query(&record)
+            .await
+            .map_err(|e| anyhow!("Failed to record migration: {}", e))?;
+        typedb_answer_to_http_ok(answer, "schema", &record, 1).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| anyhow!("Failed to commit migration: {}", e))
+    }
+}
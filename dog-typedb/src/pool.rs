@@ -0,0 +1,228 @@
+use std::collections::VecDeque;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::{timeout, Duration, Instant};
+
+/// Opens and checks the connections of a [`Pool`].
+#[async_trait]
+pub trait Connector: Send + Sync + 'static {
+    type Connection: Send + Sync + 'static;
+
+    async fn connect(&self) -> Result<Self::Connection>;
+
+    /// Whether an idle connection can still be used. Unhealthy connections
+    /// are dropped and replaced.
+    async fn is_healthy(&self, connection: &Self::Connection) -> bool;
+}
+
+/// Sizing and timeouts of a [`Pool`].
+#[derive(Debug, Clone)]
+pub struct PoolOptions {
+    /// Connections opened up front and kept even when idle.
+    pub min_size: usize,
+    /// Connections open at most; `acquire` waits for one to be returned
+    /// beyond that.
+    pub max_size: usize,
+    /// Idle connections beyond `min_size` are closed once idle this long
+    /// (checked on `acquire`). `None` keeps them.
+    pub idle_timeout: Option<Duration>,
+    /// How long `acquire` waits for a free connection before failing.
+    pub acquire_timeout: Duration,
+}
+
+impl Default for PoolOptions {
+    fn default() -> Self {
+        Self {
+            min_size: 0,
+            max_size: 10,
+            idle_timeout: Some(Duration::from_secs(300)),
+            acquire_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl PoolOptions {
+    pub fn validate(&self) -> Result<()> {
+        if self.max_size == 0 {
+            return Err(anyhow!("Pool max_size must be at least 1"));
+        }
+        if self.min_size > self.max_size {
+            return Err(anyhow!(
+                "Pool min_size ({}) must not exceed max_size ({})",
+                self.min_size,
+                self.max_size
+            ));
+        }
+        Ok(())
+    }
+}
+
+struct IdleConnection<T> {
+    connection: T,
+    idle_since: Instant,
+}
+
+struct PoolInner<C: Connector> {
+    connector: C,
+    options: PoolOptions,
+    /// One permit per connection that may be handed out.
+    permits: Arc<Semaphore>,
+    idle: Mutex<VecDeque<IdleConnection<C::Connection>>>,
+}
+
+/// A bounded pool of reusable connections.
+///
+/// `acquire` hands out an idle connection (health-checked first) or opens a
+/// new one while fewer than `max_size` are out; the returned guard puts the
+/// connection back when dropped. Cloning shares the pool.
+pub struct Pool<C: Connector> {
+    inner: Arc<PoolInner<C>>,
+}
+
+impl<C: Connector> Clone for Pool<C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<C: Connector> Pool<C> {
+    /// Create the pool and open `min_size` connections.
+    pub async fn new(connector: C, options: PoolOptions) -> Result<Self> {
+        options.validate()?;
+
+        let mut idle = VecDeque::with_capacity(options.max_size);
+        for _ in 0..options.min_size {
+            idle.push_back(IdleConnection {
+                connection: connector.connect().await?,
+                idle_since: Instant::now(),
+            });
+        }
+
+        Ok(Self {
+            inner: Arc::new(PoolInner {
+                permits: Arc::new(Semaphore::new(options.max_size)),
+                connector,
+                options,
+                idle: Mutex::new(idle),
+            }),
+        })
+    }
+
+    pub fn options(&self) -> &PoolOptions {
+        &self.inner.options
+    }
+
+    /// A connection, waiting up to `acquire_timeout` if `max_size` are in use.
+    pub async fn acquire(&self) -> Result<PooledConnection<C>> {
+        let permit = timeout(
+            self.inner.options.acquire_timeout,
+            Arc::clone(&self.inner.permits).acquire_owned(),
+        )
+        .await
+        .map_err(|_| anyhow!("Timed out waiting for a pooled connection"))?
+        .map_err(|_| anyhow!("Connection pool is closed"))?;
+
+        while let Some(connection) = self.take_idle() {
+            if self.inner.connector.is_healthy(&connection).await {
+                return Ok(self.guard(connection, permit));
+            }
+        }
+
+        let connection = self.inner.connector.connect().await?;
+        Ok(self.guard(connection, permit))
+    }
+
+    /// Open connections, idle or in use.
+    pub fn size(&self) -> usize {
+        self.in_use() + self.idle()
+    }
+
+    pub fn idle(&self) -> usize {
+        self.inner.idle.lock().unwrap().len()
+    }
+
+    pub fn in_use(&self) -> usize {
+        self.inner.options.max_size - self.inner.permits.available_permits()
+    }
+
+    /// The most recently returned idle connection, after closing those idle
+    /// for longer than `idle_timeout` beyond `min_size`.
+    fn take_idle(&self) -> Option<C::Connection> {
+        let mut idle = self.inner.idle.lock().unwrap();
+        if let Some(idle_timeout) = self.inner.options.idle_timeout {
+            // Oldest first: connections are returned to the back.
+            while idle.len() > self.inner.options.min_size
+                && idle
+                    .front()
+                    .is_some_and(|c| c.idle_since.elapsed() >= idle_timeout)
+            {
+                idle.pop_front();
+            }
+        }
+        idle.pop_back().map(|c| c.connection)
+    }
+
+    fn guard(
+        &self,
+        connection: C::Connection,
+        permit: OwnedSemaphorePermit,
+    ) -> PooledConnection<C> {
+        PooledConnection {
+            connection: Some(connection),
+            pool: Arc::clone(&self.inner),
+            _permit: permit,
+        }
+    }
+}
+
+/// A connection borrowed from a [`Pool`], returned to it on drop.
+pub struct PooledConnection<C: Connector> {
+    connection: Option<C::Connection>,
+    pool: Arc<PoolInner<C>>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl<C: Connector> PooledConnection<C> {
+    /// Close the connection instead of returning it, e.g. after an error
+    /// that left it unusable.
+    pub fn discard(mut self) {
+        self.connection = None;
+    }
+}
+
+impl<C: Connector> Deref for PooledConnection<C> {
+    type Target = C::Connection;
+
+    fn deref(&self) -> &Self::Target {
+        self.connection
+            .as_ref()
+            .expect("connection present until drop")
+    }
+}
+
+impl<C: Connector> DerefMut for PooledConnection<C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.connection
+            .as_mut()
+            .expect("connection present until drop")
+    }
+}
+
+impl<C: Connector> Drop for PooledConnection<C> {
+    fn drop(&mut self) {
+        // Returned before the permit is released (fields drop after this),
+        // so the next `acquire` finds it.
+        if let Some(connection) = self.connection.take() {
+            self.pool.idle.lock().unwrap().push_back(IdleConnection {
+                connection,
+                idle_since: Instant::now(),
+            });
+        }
+    }
+}
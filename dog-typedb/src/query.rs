@@ -0,0 +1,245 @@
+//! A small TypeQL builder for `match` and `insert` queries.
+//!
+//! Values are rendered as TypeQL literals, with strings quoted and escaped,
+//! and type labels and variable names are checked, so user input can't
+//! change the shape of the query:
+//!
+//! ```
+//! use dog_typedb::Query;
+//!
+//! let query = Query::match_entity("vehicle")
+//!     .has("vehicle-id", r#"v1"; delete $x;"#)
+//!     .has_var("max-speed", "speed")
+//!     .select(["speed"])
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(
+//!     query,
+//!     r#"match $vehicle isa vehicle, has vehicle-id "v1\"; delete $x;", has max-speed $speed; select $speed;"#
+//! );
+//! ```
+
+use anyhow::{anyhow, Result};
+
+/// A value bound into a query.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeQLValue {
+    String(String),
+    Integer(i64),
+    Double(f64),
+    Boolean(bool),
+}
+
+impl TypeQLValue {
+    /// The value as a TypeQL literal.
+    pub fn to_typeql(&self) -> Result<String> {
+        match self {
+            TypeQLValue::String(s) => Ok(quote_string(s)),
+            TypeQLValue::Integer(i) => Ok(i.to_string()),
+            TypeQLValue::Double(d) if d.is_finite() => Ok(format!("{:?}", d)),
+            TypeQLValue::Double(d) => Err(anyhow!("{} has no TypeQL literal", d)),
+            TypeQLValue::Boolean(b) => Ok(b.to_string()),
+        }
+    }
+}
+
+impl From<&str> for TypeQLValue {
+    fn from(value: &str) -> Self {
+        TypeQLValue::String(value.to_string())
+    }
+}
+
+impl From<String> for TypeQLValue {
+    fn from(value: String) -> Self {
+        TypeQLValue::String(value)
+    }
+}
+
+impl From<&String> for TypeQLValue {
+    fn from(value: &String) -> Self {
+        TypeQLValue::String(value.clone())
+    }
+}
+
+impl From<i64> for TypeQLValue {
+    fn from(value: i64) -> Self {
+        TypeQLValue::Integer(value)
+    }
+}
+
+impl From<i32> for TypeQLValue {
+    fn from(value: i32) -> Self {
+        TypeQLValue::Integer(value.into())
+    }
+}
+
+impl From<u32> for TypeQLValue {
+    fn from(value: u32) -> Self {
+        TypeQLValue::Integer(value.into())
+    }
+}
+
+impl From<f64> for TypeQLValue {
+    fn from(value: f64) -> Self {
+        TypeQLValue::Double(value)
+    }
+}
+
+impl From<bool> for TypeQLValue {
+    fn from(value: bool) -> Self {
+        TypeQLValue::Boolean(value)
+    }
+}
+
+/// `value` as a quoted TypeQL string literal.
+pub fn quote_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Match,
+    Insert,
+}
+
+#[derive(Debug, Clone)]
+enum Has {
+    Value(String, TypeQLValue),
+    Var(String, String),
+}
+
+/// A single-pattern `match` or `insert` query. See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct Query {
+    stage: Stage,
+    type_label: String,
+    var: String,
+    has: Vec<Has>,
+    select: Vec<String>,
+    limit: Option<u64>,
+}
+
+impl Query {
+    /// `match $<type_label> isa <type_label>, ...;`
+    pub fn match_entity(type_label: impl Into<String>) -> Self {
+        Self::new(Stage::Match, type_label.into())
+    }
+
+    /// `insert $<type_label> isa <type_label>, ...;`
+    pub fn insert_entity(type_label: impl Into<String>) -> Self {
+        Self::new(Stage::Insert, type_label.into())
+    }
+
+    fn new(stage: Stage, type_label: String) -> Self {
+        Self {
+            stage,
+            var: type_label.clone(),
+            type_label,
+            has: Vec::new(),
+            select: Vec::new(),
+            limit: None,
+        }
+    }
+
+    /// Name the instance variable (without `$`) instead of after its type.
+    pub fn var(mut self, var: impl Into<String>) -> Self {
+        self.var = var.into();
+        self
+    }
+
+    /// `has <attribute> <value>`, with `value` escaped.
+    pub fn has(mut self, attribute: impl Into<String>, value: impl Into<TypeQLValue>) -> Self {
+        self.has.push(Has::Value(attribute.into(), value.into()));
+        self
+    }
+
+    /// `has <attribute> $<var>`, binding the attribute to a variable.
+    pub fn has_var(mut self, attribute: impl Into<String>, var: impl Into<String>) -> Self {
+        self.has.push(Has::Var(attribute.into(), var.into()));
+        self
+    }
+
+    /// `select $a, $b;` after a match.
+    pub fn select<I, S>(mut self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.select = vars.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// `limit <n>;` after a match.
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// The TypeQL query. Fails on an invalid label or variable name, or a
+    /// value without a literal.
+    pub fn build(&self) -> Result<String> {
+        let type_label = identifier(&self.type_label, "type label")?;
+        let mut query = format!(
+            "{} ${} isa {}",
+            match self.stage {
+                Stage::Match => "match",
+                Stage::Insert => "insert",
+            },
+            identifier(&self.var, "variable")?,
+            type_label
+        );
+        for has in &self.has {
+            let (attribute, value) = match has {
+                Has::Value(attribute, value) => (attribute, value.to_typeql()?),
+                Has::Var(attribute, var) => {
+                    (attribute, format!("${}", identifier(var, "variable")?))
+                }
+            };
+            query.push_str(&format!(
+                ", has {} {}",
+                identifier(attribute, "type label")?,
+                value
+            ));
+        }
+        query.push(';');
+
+        if self.stage == Stage::Insert && (!self.select.is_empty() || self.limit.is_some()) {
+            return Err(anyhow!("select and limit only apply to match queries"));
+        }
+        if !self.select.is_empty() {
+            let vars = self
+                .select
+                .iter()
+                .map(|v| identifier(v, "variable").map(|v| format!("${}", v)))
+                .collect::<Result<Vec<_>>>()?;
+            query.push_str(&format!(" select {};", vars.join(", ")));
+        }
+        if let Some(limit) = self.limit {
+            query.push_str(&format!(" limit {};", limit));
+        }
+        Ok(query)
+    }
+}
+
+/// `name` if it is a valid TypeQL label or variable name.
+fn identifier<'a>(name: &'a str, what: &str) -> Result<&'a str> {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if valid {
+        Ok(name)
+    } else {
+        Err(anyhow!("Invalid TypeQL {}: {:?}", what, name))
+    }
+}
@@ -0,0 +1,118 @@
+use anyhow::{anyhow, Result};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+/// Decode the answers of an [`execute_typedb_query`](crate::execute_typedb_query)
+/// result into `T`, one per row.
+///
+/// Each row becomes a JSON object keyed by query variable (without `$`),
+/// with concepts flattened to plain values:
+///
+/// - attributes to their value, typed by `valueType` (`long` and `double`
+///   as numbers, `boolean` as a bool, the rest as strings). `decimal` stays
+///   a string, without TypeQL's `dec` suffix, so no digits are lost; decode
+///   it into a `String` or an exact decimal type.
+/// - entities and relations to their `iid`
+/// - types to their label
+///
+/// Fields are matched by variable name, so `#[serde(rename = "...")]` maps
+/// a variable to a differently named field. Concept documents (`fetch`
+/// queries) are already plain JSON and decode as they are.
+pub fn decode_rows<T: DeserializeOwned>(result: &Value) -> Result<Vec<T>> {
+    decode_rows_with(result, &[])
+}
+
+/// [`decode_rows`], renaming variables first: `mapping` holds
+/// `(variable, field)` pairs.
+pub fn decode_rows_with<T: DeserializeOwned>(
+    result: &Value,
+    mapping: &[(&str, &str)],
+) -> Result<Vec<T>> {
+    flatten_rows(result)?
+        .into_iter()
+        .enumerate()
+        .map(|(i, mut row)| {
+            for (variable, field) in mapping {
+                if let Some(value) = row.remove(*variable) {
+                    row.insert(field.to_string(), value);
+                }
+            }
+            serde_json::from_value(Value::Object(row))
+                .map_err(|e| anyhow!("Failed to decode TypeDB answer {}: {}", i, e))
+        })
+        .collect()
+}
+
+/// The answers of a query result as flat JSON objects (see [`decode_rows`]).
+pub fn flatten_rows(result: &Value) -> Result<Vec<Map<String, Value>>> {
+    let ok = result.get("ok").unwrap_or(result);
+    let answer_type = ok.get("answerType").and_then(Value::as_str).unwrap_or("");
+    let answers = match ok.get("answers") {
+        Some(Value::Array(answers)) => answers,
+        _ => return Err(anyhow!("Not a TypeDB query result: missing answers")),
+    };
+
+    answers
+        .iter()
+        .enumerate()
+        .map(|(i, answer)| {
+            let data = match answer.get("data") {
+                Some(Value::Object(data)) => data,
+                _ => return Err(anyhow!("TypeDB answer {} has no data object", i)),
+            };
+            if answer_type == "conceptDocuments" {
+                return Ok(data.clone());
+            }
+            Ok(data
+                .iter()
+                .map(|(variable, concept)| (variable.clone(), flatten_concept(concept)))
+                .collect())
+        })
+        .collect()
+}
+
+fn flatten_concept(concept: &Value) -> Value {
+    let kind = concept.get("kind").and_then(Value::as_str).unwrap_or("");
+    match kind {
+        "attribute" => {
+            let value_type = concept
+                .get("valueType")
+                .and_then(Value::as_str)
+                .unwrap_or("string");
+            typed_value(concept.get("value"), value_type)
+        }
+        // Values from reduce/let are rendered as text; numbers and booleans
+        // parse back as JSON.
+        "value" => match concept.get("value") {
+            Some(Value::String(s)) => serde_json::from_str::<Value>(s)
+                .ok()
+                .filter(|v| v.is_number() || v.is_boolean())
+                .unwrap_or_else(|| Value::String(s.trim_matches('"').to_string())),
+            other => other.cloned().unwrap_or(Value::Null),
+        },
+        "entity" | "relation" => concept.get("iid").cloned().unwrap_or(Value::Null),
+        "entityType" | "relationType" | "attributeType" | "roleType" => {
+            concept.get("label").cloned().unwrap_or(Value::Null)
+        }
+        _ => concept.clone(),
+    }
+}
+
+fn typed_value(value: Option<&Value>, value_type: &str) -> Value {
+    let Some(value) = value else {
+        return Value::Null;
+    };
+    let Some(text) = value.as_str() else {
+        return value.clone();
+    };
+    let parsed = match value_type {
+        "long" | "integer" => text.parse::<i64>().ok().map(Value::from),
+        "double" => text.parse::<f64>().ok().map(Value::from),
+        "decimal" => Some(Value::String(
+            text.strip_suffix("dec").unwrap_or(text).to_string(),
+        )),
+        "boolean" => text.parse::<bool>().ok().map(Value::from),
+        _ => None,
+    };
+    parsed.unwrap_or_else(|| Value::String(text.to_string()))
+}
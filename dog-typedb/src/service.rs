@@ -8,6 +8,10 @@ use dog_core::tenant::TenantContext;
 use dog_core::{DogService, ServiceCapabilities};
 use typedb_driver::{Addresses, Credentials, DriverOptions, DriverTlsConfig, TypeDBDriver};
 
+use crate::pool::{Connector, Pool, PoolOptions};
+use crate::stream::{stream_typedb_query, AnswerStream};
+use crate::transactions::execute_write_batch;
+
 pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
 pub type CreateHandler<R, P> = Arc<
@@ -72,6 +76,16 @@ pub type RemoveHandler<R, P> = Arc<
         + Sync,
 >;
 
+/// Builds the insert query of one record for
+/// [`TypeDBService::create_many`]. An error rejects the record, and with it
+/// the whole batch.
+pub type InsertQueryBuilder<R, P> =
+    Arc<dyn Fn(&TenantContext, &R, &P) -> Result<String> + Send + Sync>;
+
+/// Builds the read query of [`TypeDBService::find_stream`] from the find
+/// params.
+pub type FindQueryBuilder<P> = Arc<dyn Fn(&TenantContext, &P) -> Result<String> + Send + Sync>;
+
 #[derive(Default)]
 pub struct TypeDBServiceHandlers<R, P> {
     pub create: Option<CreateHandler<R, P>>,
@@ -80,6 +94,8 @@ pub struct TypeDBServiceHandlers<R, P> {
     pub update: Option<UpdateHandler<R, P>>,
     pub patch: Option<PatchHandler<R, P>>,
     pub remove: Option<RemoveHandler<R, P>>,
+    pub insert_query: Option<InsertQueryBuilder<R, P>>,
+    pub find_query: Option<FindQueryBuilder<P>>,
 }
 
 #[derive(Clone)]
@@ -113,6 +129,59 @@ impl TypeDBService {
             handlers: Arc::new(handlers),
         }
     }
+
+    /// Create `records` in a single write transaction.
+    ///
+    /// Every record's query is built with the `insert_query` handler first,
+    /// so an invalid record fails the batch before anything is written. The
+    /// queries then run together and commit atomically: if any fails, the
+    /// transaction is rolled back and none of the records are created.
+    /// Returns one query result per record.
+    pub async fn create_many(
+        &self,
+        ctx: &TenantContext,
+        records: Vec<serde_json::Value>,
+        params: serde_json::Value,
+    ) -> Result<Vec<serde_json::Value>> {
+        let builder = self
+            .handlers
+            .insert_query
+            .as_ref()
+            .ok_or_else(|| anyhow!("Method not implemented: create_many"))?;
+
+        let queries = records
+            .iter()
+            .enumerate()
+            .map(|(i, record)| {
+                builder(ctx, record, &params)
+                    .map_err(|e| e.context(format!("Invalid record {} in batch", i)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if queries.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        execute_write_batch(&self.driver, &self.database, &queries).await
+    }
+
+    /// A find that streams its answers instead of collecting them, for
+    /// exports and other large results. The query comes from the
+    /// `find_query` handler; see [`stream_typedb_query`] for what is
+    /// yielded and how long the transaction stays open.
+    pub async fn find_stream(
+        &self,
+        ctx: &TenantContext,
+        params: serde_json::Value,
+    ) -> Result<AnswerStream> {
+        let builder = self
+            .handlers
+            .find_query
+            .as_ref()
+            .ok_or_else(|| anyhow!("Method not implemented: find_stream"))?;
+
+        let query = builder(ctx, &params)?;
+        stream_typedb_query(&self.driver, &self.database, &query).await
+    }
 }
 
 #[async_trait]
@@ -136,6 +205,26 @@ impl DogService<serde_json::Value, serde_json::Value> for TypeDBService {
         handler(ctx, &self.driver, &self.database, data, params).await
     }
 
+    /// With an `insert_query` handler the batch is written in one
+    /// transaction (see [`TypeDBService::create_many`]); without one, each
+    /// record goes through `create`.
+    async fn create_many(
+        &self,
+        ctx: &TenantContext,
+        data: Vec<serde_json::Value>,
+        params: serde_json::Value,
+    ) -> Result<Vec<serde_json::Value>> {
+        if self.handlers.insert_query.is_some() {
+            return TypeDBService::create_many(self, ctx, data, params).await;
+        }
+
+        let mut created = Vec::with_capacity(data.len());
+        for record in data {
+            created.push(DogService::create(self, ctx, record, params.clone()).await?);
+        }
+        Ok(created)
+    }
+
     async fn find(
         &self,
         ctx: &TenantContext,
@@ -249,4 +338,45 @@ impl TypeDBDriverFactory {
     pub async fn connect_default(address: &str) -> Result<TypeDBDriver> {
         Self::connect(address, "admin", "password", false).await
     }
+
+    /// A pool of drivers connected like [`connect`](Self::connect), so that
+    /// requests reuse connections instead of opening their own.
+    pub async fn pool(
+        address: &str,
+        username: &str,
+        password: &str,
+        tls: bool,
+        options: PoolOptions,
+    ) -> Result<TypeDBPool> {
+        let connector = TypeDBConnector {
+            address: address.to_string(),
+            username: username.to_string(),
+            password: password.to_string(),
+            tls,
+        };
+        Pool::new(connector, options).await
+    }
+}
+
+pub type TypeDBPool = Pool<TypeDBConnector>;
+
+/// Opens the drivers of a [`TypeDBPool`].
+pub struct TypeDBConnector {
+    address: String,
+    username: String,
+    password: String,
+    tls: bool,
+}
+
+#[async_trait]
+impl Connector for TypeDBConnector {
+    type Connection = TypeDBDriver;
+
+    async fn connect(&self) -> Result<TypeDBDriver> {
+        TypeDBDriverFactory::connect(&self.address, &self.username, &self.password, self.tls).await
+    }
+
+    async fn is_healthy(&self, driver: &TypeDBDriver) -> bool {
+        driver.is_open()
+    }
 }
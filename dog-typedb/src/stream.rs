@@ -0,0 +1,102 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use anyhow::{anyhow, Result};
+use futures::stream::{BoxStream, Stream, StreamExt};
+use serde_json::Value;
+use typedb_driver::answer::QueryAnswer;
+use typedb_driver::{Transaction, TypeDBDriver};
+
+use crate::transactions::row_data;
+
+/// The answers of a query, yielded as the driver produces them.
+///
+/// Each item is the `data` of one answer, as in the `answers` of
+/// [`execute_typedb_query`](crate::execute_typedb_query): a row's concepts
+/// by variable, or a fetch document. The stream owns whatever keeps the
+/// answers flowing (the transaction, for [`stream_typedb_query`]) and
+/// releases it when dropped, whether or not it was read to the end.
+pub struct AnswerStream {
+    answers: BoxStream<'static, Result<Value>>,
+    _guard: Box<dyn Send>,
+}
+
+impl AnswerStream {
+    /// Stream `answers`, holding `guard` until the stream is dropped.
+    pub fn new<S, G>(answers: S, guard: G) -> Self
+    where
+        S: Stream<Item = Result<Value>> + Send + 'static,
+        G: Send + 'static,
+    {
+        Self {
+            answers: answers.boxed(),
+            _guard: Box::new(guard),
+        }
+    }
+}
+
+impl Stream for AnswerStream {
+    type Item = Result<Value>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.answers.poll_next_unpin(cx)
+    }
+}
+
+/// Closes the read transaction of a stream once the stream is dropped.
+struct TransactionGuard(Option<Transaction>);
+
+impl Drop for TransactionGuard {
+    fn drop(&mut self) {
+        let Some(tx) = self.0.take() else {
+            return;
+        };
+        // Closing is async; outside a runtime the driver's own cleanup is
+        // all that is left.
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            handle.spawn(async move {
+                let _ = tx.close().await;
+            });
+        }
+    }
+}
+
+/// Run `query` in a read transaction and stream its answers instead of
+/// collecting them, for results too large to buffer.
+///
+/// The transaction stays open for as long as the stream lives. Write and
+/// schema queries are rejected by TypeDB.
+pub async fn stream_typedb_query(
+    driver: &TypeDBDriver,
+    database: &str,
+    query: &str,
+) -> Result<AnswerStream> {
+    let tx = driver
+        .transaction(database, typedb_driver::TransactionType::Read)
+        .await
+        .map_err(|e| anyhow!("Failed to create read transaction: {}", e))?;
+
+    let answer = tx
+        .query(query)
+        .await
+        .map_err(|e| anyhow!("Failed to execute read query: {}", e))?;
+
+    let answers = match answer {
+        QueryAnswer::Ok(_) => futures::stream::empty().boxed(),
+        QueryAnswer::ConceptRowStream(_, rows) => rows
+            .map(|row| {
+                let row = row.map_err(|e| anyhow!("Failed to get concept row: {}", e))?;
+                Ok(Value::Object(row_data(&row)?))
+            })
+            .boxed(),
+        QueryAnswer::ConceptDocumentStream(_, documents) => documents
+            .map(|document| {
+                let document =
+                    document.map_err(|e| anyhow!("Failed to get concept document: {}", e))?;
+                Ok(serde_json::to_value(document.into_json())?)
+            })
+            .boxed(),
+    };
+
+    Ok(AnswerStream::new(answers, TransactionGuard(Some(tx))))
+}
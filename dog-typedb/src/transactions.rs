@@ -1,7 +1,9 @@
 use anyhow::Result;
+use async_trait::async_trait;
 use futures::StreamExt;
 use serde_json::{json, Map, Value};
 use std::fs;
+use std::future::Future;
 use tokio::time::{sleep, Duration};
 use typedb_driver::TypeDBDriver;
 
@@ -151,22 +153,221 @@ pub fn analyze_query(query: &str) -> QueryAnalysis {
     }
 }
 
+/// How write transactions are retried after a transient conflict.
+#[derive(Debug, Clone)]
+pub struct WriteRetryPolicy {
+    /// Attempts in total, including the first; 1 disables retries.
+    pub max_attempts: u32,
+    /// Wait before the first retry; doubles on each further retry.
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for WriteRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(50),
+            max_backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+impl WriteRetryPolicy {
+    /// Run once, never retry.
+    pub fn no_retry() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Wait before retry number `retry` (1-based).
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Prefix of the codes TypeDB reports isolation conflicts with.
+const ISOLATION_CONFLICT_CODE_PREFIX: &str = "ISO";
+
+/// A write transaction that lost to a concurrent commit. Re-running it may
+/// succeed, so [`retry_write`] does.
+#[derive(Debug, Clone)]
+pub struct WriteConflict {
+    pub message: String,
+}
+
+impl WriteConflict {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for WriteConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for WriteConflict {}
+
+/// `err` from `action` in a write transaction: a [`WriteConflict`] when the
+/// server reports an isolation conflict, otherwise a plain error.
+fn write_error(action: &str, err: typedb_driver::Error) -> anyhow::Error {
+    if is_isolation_conflict(&err) {
+        WriteConflict::new(format!("{action}: {err}")).into()
+    } else {
+        anyhow::anyhow!("{action}: {err}")
+    }
+}
+
+fn is_isolation_conflict(err: &typedb_driver::Error) -> bool {
+    matches!(err, typedb_driver::Error::Server(_))
+        && err.code().starts_with(ISOLATION_CONFLICT_CODE_PREFIX)
+}
+
+/// Whether `err` is a conflict with a concurrent transaction, which
+/// re-running the transaction may resolve: a [`WriteConflict`], or a driver
+/// error the server reported as an isolation conflict. Other failures, such
+/// as schema or constraint violations, would fail again.
+pub fn is_transient_write_error(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        cause.is::<WriteConflict>()
+            || cause
+                .downcast_ref::<typedb_driver::Error>()
+                .is_some_and(is_isolation_conflict)
+    })
+}
+
+/// Run `work` (one whole write transaction: open, query, commit) and re-run
+/// it with backoff while it fails with a transient conflict, up to
+/// `policy.max_attempts` times.
+pub async fn retry_write<T, F, Fut>(policy: &WriteRetryPolicy, mut work: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match work().await {
+            Err(err) if attempt < policy.max_attempts && is_transient_write_error(&err) => {
+                sleep(policy.backoff(attempt)).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
 /// Executes a TypeDB query and returns TypeDB Studio/HTTP compatible response.
 /// Response schema: answerType is ONLY ok|conceptRows|conceptDocuments.
+///
+/// Write queries are retried on conflict with the default [`WriteRetryPolicy`].
 pub async fn execute_typedb_query(
     driver: &TypeDBDriver,
     database: &str,
     query: &str,
+) -> Result<Value> {
+    execute_typedb_query_with_retry(driver, database, query, &WriteRetryPolicy::default()).await
+}
+
+/// [`execute_typedb_query`] with the given retry policy for write queries.
+pub async fn execute_typedb_query_with_retry(
+    driver: &TypeDBDriver,
+    database: &str,
+    query: &str,
+    retry: &WriteRetryPolicy,
 ) -> Result<Value> {
     let analysis = analyze_query(query);
 
     match analysis.transaction_type {
         TransactionType::Read => execute_read_query(driver, database, query).await,
-        TransactionType::Write => execute_write_query(driver, database, query).await,
+        TransactionType::Write => {
+            retry_write(retry, || execute_write_query(driver, database, query)).await
+        }
         TransactionType::Schema => execute_schema_query(driver, database, query).await,
     }
 }
 
+/// One write transaction, as used by [`run_write_batch`].
+#[async_trait]
+pub trait WriteTransaction: Send + Sized {
+    /// Run `query`, returning its answers in the Studio/HTTP format.
+    async fn query(&mut self, query: &str) -> Result<Value>;
+
+    async fn commit(self) -> Result<()>;
+
+    async fn rollback(self) -> Result<()>;
+}
+
+#[async_trait]
+impl WriteTransaction for typedb_driver::Transaction {
+    async fn query(&mut self, query: &str) -> Result<Value> {
+        let answer = typedb_driver::Transaction::query(self, query)
+            .await
+            .map_err(|e| write_error("Failed to execute write query", e))?;
+        typedb_answer_to_http_ok(answer, "write", query, 10_000).await
+    }
+
+    async fn commit(self) -> Result<()> {
+        typedb_driver::Transaction::commit(self)
+            .await
+            .map_err(|e| write_error("Failed to commit write transaction", e))
+    }
+
+    async fn rollback(self) -> Result<()> {
+        typedb_driver::Transaction::rollback(&self)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to roll back write transaction: {}", e))
+    }
+}
+
+/// Run `queries` in `tx` and commit them together. If any query fails, the
+/// transaction is rolled back, so none of them take effect, and the error
+/// names the failing query's index.
+pub async fn run_write_batch<T: WriteTransaction>(
+    mut tx: T,
+    queries: &[String],
+) -> Result<Vec<Value>> {
+    let mut results = Vec::with_capacity(queries.len());
+    for (i, query) in queries.iter().enumerate() {
+        match tx.query(query).await {
+            Ok(result) => results.push(result),
+            Err(e) => {
+                // The query error is the one worth reporting.
+                let _ = tx.rollback().await;
+                return Err(e.context(format!("Batch query {} failed; batch rolled back", i)));
+            }
+        }
+    }
+    tx.commit().await?;
+    Ok(results)
+}
+
+/// Execute `queries` atomically in one write transaction (see
+/// [`run_write_batch`]), retrying the whole batch on conflict with the
+/// default [`WriteRetryPolicy`].
+pub async fn execute_write_batch(
+    driver: &TypeDBDriver,
+    database: &str,
+    queries: &[String],
+) -> Result<Vec<Value>> {
+    retry_write(&WriteRetryPolicy::default(), || async {
+        let tx = driver
+            .transaction(database, typedb_driver::TransactionType::Write)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to create write transaction: {}", e))?;
+        run_write_batch(tx, queries).await
+    })
+    .await
+}
+
 async fn execute_read_query(driver: &TypeDBDriver, database: &str, query: &str) -> Result<Value> {
     let tx = driver
         .transaction(database, typedb_driver::TransactionType::Read)
@@ -210,14 +411,14 @@ async fn execute_write_query(driver: &TypeDBDriver, database: &str, query: &str)
     let answer = tx
         .query(query)
         .await
-        .map_err(|e| anyhow::anyhow!("Failed to execute write query: {}", e))?;
+        .map_err(|e| write_error("Failed to execute write query", e))?;
 
     // IMPORTANT: consume streams before commit (keep tx alive)
     let res = typedb_answer_to_http_ok(answer, "write", query, 10_000).await?;
 
     tx.commit()
         .await
-        .map_err(|e| anyhow::anyhow!("Failed to commit write transaction: {}", e))?;
+        .map_err(|e| write_error("Failed to commit write transaction", e))?;
 
     Ok(res)
 }
@@ -246,7 +447,7 @@ async fn execute_schema_query(driver: &TypeDBDriver, database: &str, query: &str
     Ok(res)
 }
 
-async fn typedb_answer_to_http_ok(
+pub(crate) async fn typedb_answer_to_http_ok(
     answer: typedb_driver::answer::QueryAnswer,
     query_type: &str,
     query: &str,
@@ -294,15 +495,7 @@ async fn typedb_answer_to_http_ok(
             while let Some(row_result) = stream.next().await {
                 let row =
                     row_result.map_err(|e| anyhow::anyhow!("Failed to get concept row: {}", e))?;
-                let mut data_map = Map::new();
-
-                for column_name in row.get_column_names() {
-                    if let Ok(Some(concept)) = row.get(column_name) {
-                        data_map.insert(column_name.clone(), format_concept(concept)?);
-                    }
-                }
-
-                answers.push(json!({"data": data_map, "involvedBlocks": [0]}));
+                answers.push(json!({"data": row_data(&row)?, "involvedBlocks": [0]}));
 
                 if answers.len() >= max_answers {
                     truncated = true;
@@ -323,6 +516,17 @@ async fn typedb_answer_to_http_ok(
     }
 }
 
+/// The `data` of a concept row answer: its concepts by column name.
+pub(crate) fn row_data(row: &typedb_driver::answer::ConceptRow) -> Result<Map<String, Value>> {
+    let mut data_map = Map::new();
+    for column_name in row.get_column_names() {
+        if let Ok(Some(concept)) = row.get(column_name) {
+            data_map.insert(column_name.clone(), format_concept(concept)?);
+        }
+    }
+    Ok(data_map)
+}
+
 /// Formats a TypeDB Concept into a Studio-friendly JSON object.
 fn format_concept(concept: &typedb_driver::concept::Concept) -> Result<Value> {
     use typedb_driver::concept::Concept;
@@ -0,0 +1,102 @@
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use dog_typedb::{run_write_batch, WriteTransaction};
+use serde_json::{json, Value};
+
+/// A database whose writes only become visible on commit.
+#[derive(Default)]
+struct FakeDatabase {
+    committed: Mutex<Vec<String>>,
+    executed: Mutex<Vec<String>>,
+    rolled_back: Mutex<bool>,
+}
+
+struct FakeTransaction {
+    db: Arc<FakeDatabase>,
+    staged: Vec<String>,
+}
+
+impl FakeTransaction {
+    fn open(db: &Arc<FakeDatabase>) -> Self {
+        Self {
+            db: Arc::clone(db),
+            staged: Vec::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl WriteTransaction for FakeTransaction {
+    async fn query(&mut self, query: &str) -> Result<Value> {
+        self.db.executed.lock().unwrap().push(query.to_string());
+        if query.contains("invalid") {
+            return Err(anyhow!(
+                "[INF11] Type-inference was unable to find compatible schema types"
+            ));
+        }
+        self.staged.push(query.to_string());
+        Ok(json!({ "ok": { "answerType": "ok", "query": query } }))
+    }
+
+    async fn commit(self) -> Result<()> {
+        self.db.committed.lock().unwrap().extend(self.staged);
+        Ok(())
+    }
+
+    async fn rollback(self) -> Result<()> {
+        *self.db.rolled_back.lock().unwrap() = true;
+        Ok(())
+    }
+}
+
+fn queries(names: &[&str]) -> Vec<String> {
+    names
+        .iter()
+        .map(|name| format!("insert $p isa person, has name \"{}\";", name))
+        .collect()
+}
+
+#[tokio::test]
+async fn valid_batch_commits_every_query() {
+    let db = Arc::new(FakeDatabase::default());
+    let batch = queries(&["ada", "grace", "linus"]);
+
+    let results = run_write_batch(FakeTransaction::open(&db), &batch)
+        .await
+        .unwrap();
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(*db.committed.lock().unwrap(), batch);
+    assert!(!*db.rolled_back.lock().unwrap());
+}
+
+#[tokio::test]
+async fn one_invalid_record_rolls_back_the_whole_batch() {
+    let db = Arc::new(FakeDatabase::default());
+    let batch = queries(&["ada", "invalid", "linus"]);
+
+    let err = run_write_batch(FakeTransaction::open(&db), &batch)
+        .await
+        .unwrap_err();
+
+    assert!(db.committed.lock().unwrap().is_empty());
+    assert!(*db.rolled_back.lock().unwrap());
+    // Nothing runs after the failing query.
+    assert_eq!(db.executed.lock().unwrap().len(), 2);
+    assert!(err.to_string().contains("Batch query 1 failed"));
+    assert!(format!("{:#}", err).contains("INF11"));
+}
+
+#[tokio::test]
+async fn empty_batch_commits_nothing() {
+    let db = Arc::new(FakeDatabase::default());
+
+    let results = run_write_batch(FakeTransaction::open(&db), &[])
+        .await
+        .unwrap();
+
+    assert!(results.is_empty());
+    assert!(db.committed.lock().unwrap().is_empty());
+}
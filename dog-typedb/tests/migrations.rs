@@ -0,0 +1,141 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use dog_typedb::{load_migrations, run_migrations_with, Migration, MigrationStore};
+
+/// Applied versions and every query run, as a database would keep them.
+#[derive(Default)]
+struct MemoryStore {
+    applied: Mutex<Vec<u64>>,
+    executed: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl MigrationStore for MemoryStore {
+    async fn applied_versions(&self) -> Result<Vec<u64>> {
+        Ok(self.applied.lock().unwrap().clone())
+    }
+
+    async fn apply(&self, migration: &Migration) -> Result<()> {
+        if migration.query.contains("invalid") {
+            return Err(anyhow!("[TYR03] Invalid define"));
+        }
+        self.executed.lock().unwrap().push(migration.query.clone());
+        self.applied.lock().unwrap().push(migration.version);
+        Ok(())
+    }
+}
+
+fn migrations_dir(test: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "dog-typedb-migrations-{}-{test}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    for (name, contents) in files {
+        std::fs::write(dir.join(name), contents).unwrap();
+    }
+    dir
+}
+
+#[tokio::test]
+async fn running_twice_applies_each_migration_once() {
+    let dir = migrations_dir(
+        "twice",
+        &[
+            ("0001_initial.tql", "define entity person;"),
+            ("0002_add_name.tql", "define attribute name, value string;"),
+        ],
+    );
+    let store = MemoryStore::default();
+
+    let first = run_migrations_with(&store, &dir).await.unwrap();
+    assert_eq!(first.applied, ["0001_initial", "0002_add_name"]);
+    assert!(first.skipped.is_empty());
+
+    let second = run_migrations_with(&store, &dir).await.unwrap();
+    assert!(second.applied.is_empty());
+    assert_eq!(second.skipped, ["0001_initial", "0002_add_name"]);
+
+    assert_eq!(
+        *store.executed.lock().unwrap(),
+        [
+            "define entity person;",
+            "define attribute name, value string;"
+        ]
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn new_migrations_run_after_those_already_applied() {
+    let dir = migrations_dir("pending", &[("0001_initial.tql", "define entity person;")]);
+    let store = MemoryStore::default();
+    run_migrations_with(&store, &dir).await.unwrap();
+
+    std::fs::write(dir.join("0002_company.tql"), "define entity company;").unwrap();
+    let report = run_migrations_with(&store, &dir).await.unwrap();
+
+    assert_eq!(report.applied, ["0002_company"]);
+    assert_eq!(report.skipped, ["0001_initial"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn failing_migration_stops_the_run() {
+    let dir = migrations_dir(
+        "failing",
+        &[
+            ("1_initial.tql", "define entity person;"),
+            ("2_broken.tql", "define invalid"),
+            ("3_company.tql", "define entity company;"),
+        ],
+    );
+    let store = MemoryStore::default();
+
+    let err = run_migrations_with(&store, &dir).await.unwrap_err();
+
+    assert!(err.to_string().contains("Migration 2_broken failed"));
+    assert_eq!(*store.applied.lock().unwrap(), [1]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn migrations_are_ordered_by_numeric_version() {
+    let dir = migrations_dir(
+        "order",
+        &[
+            ("10_later.tql", "define entity b;"),
+            ("2_earlier.tql", "define entity a;"),
+            ("README.md", "not a migration"),
+        ],
+    );
+
+    let migrations = load_migrations(&dir).unwrap();
+
+    let versions: Vec<u64> = migrations.iter().map(|m| m.version).collect();
+    assert_eq!(versions, [2, 10]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn duplicate_and_unnumbered_migrations_are_rejected() {
+    let dir = migrations_dir(
+        "duplicate",
+        &[
+            ("1_a.tql", "define entity a;"),
+            ("01_b.tql", "define entity b;"),
+        ],
+    );
+    let err = load_migrations(&dir).unwrap_err();
+    assert!(err.to_string().contains("same version"));
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let dir = migrations_dir("unnumbered", &[("initial.tql", "define entity a;")]);
+    let err = load_migrations(&dir).unwrap_err();
+    assert!(err.to_string().contains("<version>_<description>.tql"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
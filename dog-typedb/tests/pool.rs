@@ -0,0 +1,129 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use dog_typedb::{Connector, Pool, PoolOptions};
+use tokio::time::{sleep, Duration};
+
+/// Stands in for a TypeDB driver: counts connections and lets tests kill them.
+#[derive(Clone, Default)]
+struct FakeConnector {
+    opened: Arc<AtomicUsize>,
+}
+
+struct FakeConnection {
+    id: usize,
+    alive: AtomicBool,
+}
+
+#[async_trait]
+impl Connector for FakeConnector {
+    type Connection = FakeConnection;
+
+    async fn connect(&self) -> anyhow::Result<FakeConnection> {
+        let id = self.opened.fetch_add(1, Ordering::SeqCst);
+        Ok(FakeConnection {
+            id,
+            alive: AtomicBool::new(true),
+        })
+    }
+
+    async fn is_healthy(&self, connection: &FakeConnection) -> bool {
+        connection.alive.load(Ordering::SeqCst)
+    }
+}
+
+fn options(max_size: usize) -> PoolOptions {
+    PoolOptions {
+        max_size,
+        ..PoolOptions::default()
+    }
+}
+
+#[tokio::test]
+async fn concurrent_queries_reuse_a_bounded_number_of_connections() {
+    let connector = FakeConnector::default();
+    let pool = Pool::new(connector.clone(), options(4)).await.unwrap();
+    let peak = Arc::new(AtomicUsize::new(0));
+
+    let queries = (0..50).map(|_| {
+        let pool = pool.clone();
+        let peak = peak.clone();
+        tokio::spawn(async move {
+            let _connection = pool.acquire().await.unwrap();
+            peak.fetch_max(pool.in_use(), Ordering::SeqCst);
+            sleep(Duration::from_millis(2)).await;
+        })
+    });
+    for query in queries.collect::<Vec<_>>() {
+        query.await.unwrap();
+    }
+
+    assert!(peak.load(Ordering::SeqCst) <= 4);
+    assert!(connector.opened.load(Ordering::SeqCst) <= 4);
+    assert_eq!(pool.in_use(), 0);
+    assert_eq!(pool.size(), connector.opened.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn dead_connections_are_replaced() {
+    let connector = FakeConnector::default();
+    let pool = Pool::new(
+        connector.clone(),
+        PoolOptions {
+            min_size: 1,
+            ..options(2)
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(pool.idle(), 1);
+
+    let connection = pool.acquire().await.unwrap();
+    assert_eq!(connection.id, 0);
+    connection.alive.store(false, Ordering::SeqCst);
+    drop(connection);
+
+    let connection = pool.acquire().await.unwrap();
+    assert_eq!(connection.id, 1);
+    assert_eq!(pool.size(), 1);
+}
+
+#[tokio::test]
+async fn acquire_times_out_when_the_pool_is_exhausted() {
+    let pool = Pool::new(
+        FakeConnector::default(),
+        PoolOptions {
+            acquire_timeout: Duration::from_millis(20),
+            ..options(1)
+        },
+    )
+    .await
+    .unwrap();
+
+    let held = pool.acquire().await.unwrap();
+    assert!(pool.acquire().await.is_err());
+
+    drop(held);
+    assert_eq!(pool.acquire().await.unwrap().id, 0);
+}
+
+#[tokio::test]
+async fn idle_connections_beyond_min_size_expire() {
+    let connector = FakeConnector::default();
+    let pool = Pool::new(
+        connector.clone(),
+        PoolOptions {
+            idle_timeout: Some(Duration::from_millis(20)),
+            ..options(2)
+        },
+    )
+    .await
+    .unwrap();
+
+    drop(pool.acquire().await.unwrap());
+    sleep(Duration::from_millis(40)).await;
+
+    assert_eq!(pool.acquire().await.unwrap().id, 1);
+    assert_eq!(connector.opened.load(Ordering::SeqCst), 2);
+}
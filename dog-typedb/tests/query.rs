@@ -0,0 +1,124 @@
+use dog_typedb::{quote_string, Query};
+
+#[test]
+fn match_with_plain_values() {
+    let query = Query::match_entity("vehicle")
+        .var("v")
+        .has("vehicle-id", "truck-7")
+        .has("max-speed", 90)
+        .has("is-commercial", true)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        query,
+        r#"match $v isa vehicle, has vehicle-id "truck-7", has max-speed 90, has is-commercial true;"#
+    );
+}
+
+#[test]
+fn quotes_cannot_close_the_string_literal() {
+    let id = r#"x"; match $all isa vehicle; delete $all; #"#;
+
+    let query = Query::match_entity("vehicle")
+        .has("vehicle-id", id)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        query,
+        r#"match $vehicle isa vehicle, has vehicle-id "x\"; match $all isa vehicle; delete $all; #";"#
+    );
+}
+
+#[test]
+fn backslashes_cannot_escape_the_closing_quote() {
+    // A trailing backslash would otherwise turn the closing quote into `\"`.
+    let query = Query::insert_entity("person")
+        .has("name", r#"evil\"#)
+        .build()
+        .unwrap();
+    assert_eq!(query, r#"insert $person isa person, has name "evil\\";"#);
+
+    assert_eq!(quote_string(r#"a\"b"#), r#""a\\\"b""#);
+}
+
+#[test]
+fn other_special_characters_are_kept_inside_the_literal() {
+    let value = "line one\nline two\t$x; {} , 'single' \u{e9}\u{1f697}";
+
+    let literal = quote_string(value);
+
+    assert_eq!(literal, format!("\"{}\"", value));
+}
+
+#[test]
+fn doubles_keep_a_decimal_point_and_reject_non_finite_values() {
+    let query = Query::match_entity("vehicle")
+        .has("vehicle-weight", 1200.0)
+        .build()
+        .unwrap();
+    assert_eq!(
+        query,
+        "match $vehicle isa vehicle, has vehicle-weight 1200.0;"
+    );
+
+    assert!(Query::match_entity("vehicle")
+        .has("vehicle-weight", f64::NAN)
+        .build()
+        .is_err());
+}
+
+#[test]
+fn select_and_limit() {
+    let query = Query::match_entity("vehicle")
+        .var("v")
+        .has("vehicle-id", "truck-7")
+        .has_var("engine-type", "engine")
+        .has_var("max-speed", "speed")
+        .select(["engine", "speed"])
+        .limit(1)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        query,
+        r#"match $v isa vehicle, has vehicle-id "truck-7", has engine-type $engine, has max-speed $speed; select $engine, $speed; limit 1;"#
+    );
+}
+
+#[test]
+fn labels_and_variables_are_validated() {
+    let injected_label = Query::match_entity("vehicle; delete $x")
+        .build()
+        .unwrap_err();
+    assert!(injected_label
+        .to_string()
+        .contains("Invalid TypeQL type label"));
+
+    let injected_attribute = Query::match_entity("vehicle")
+        .has("vehicle-id \"x\", has name", "y")
+        .build()
+        .unwrap_err();
+    assert!(injected_attribute
+        .to_string()
+        .contains("Invalid TypeQL type label"));
+
+    let injected_var = Query::match_entity("vehicle")
+        .select(["v; delete $v"])
+        .build()
+        .unwrap_err();
+    assert!(injected_var.to_string().contains("Invalid TypeQL variable"));
+
+    assert!(Query::match_entity("").build().is_err());
+    assert!(Query::match_entity("1vehicle").build().is_err());
+}
+
+#[test]
+fn select_and_limit_are_rejected_on_inserts() {
+    assert!(Query::insert_entity("vehicle")
+        .has("vehicle-id", "truck-7")
+        .limit(1)
+        .build()
+        .is_err());
+}
@@ -0,0 +1,146 @@
+use dog_typedb::{decode_rows, decode_rows_with};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+fn attribute(label: &str, value: &str, value_type: &str) -> Value {
+    json!({
+        "kind": "attribute",
+        "value": value,
+        "valueType": value_type,
+        "type": { "kind": "attributeType", "label": label, "valueType": value_type }
+    })
+}
+
+/// A result as returned by `execute_typedb_query` for
+/// `match $v isa vehicle, has vehicle-id $id, has max-speed $speed, ...;`.
+fn vehicles() -> Value {
+    json!({
+        "ok": {
+            "queryType": "read",
+            "answerType": "conceptRows",
+            "answers": [
+                {
+                    "data": {
+                        "v": { "kind": "entity", "iid": "0x1e00000000000000000000", "type": { "kind": "entityType", "label": "vehicle" } },
+                        "vehicle-id": attribute("vehicle-id", "TRK-1", "string"),
+                        "speed": attribute("max-speed", "90", "long"),
+                        "weight": attribute("weight", "7.5", "double"),
+                        "commercial": attribute("is-commercial", "true", "boolean")
+                    },
+                    "involvedBlocks": [0]
+                },
+                {
+                    "data": {
+                        "v": { "kind": "entity", "iid": "0x1e00000000000000000001", "type": { "kind": "entityType", "label": "vehicle" } },
+                        "vehicle-id": attribute("vehicle-id", "VAN-2", "string"),
+                        "speed": attribute("max-speed", "120", "long"),
+                        "weight": attribute("weight", "2", "double"),
+                        "commercial": attribute("is-commercial", "false", "boolean")
+                    },
+                    "involvedBlocks": [0]
+                }
+            ],
+            "query": "match ...",
+            "warning": null
+        }
+    })
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Vehicle {
+    #[serde(rename = "v")]
+    iid: String,
+    #[serde(rename = "vehicle-id")]
+    id: String,
+    max_speed: i64,
+    weight: f64,
+    commercial: bool,
+}
+
+#[test]
+fn decodes_rows_into_structs() {
+    let vehicles: Vec<Vehicle> = decode_rows_with(&vehicles(), &[("speed", "max_speed")]).unwrap();
+
+    assert_eq!(
+        vehicles,
+        vec![
+            Vehicle {
+                iid: "0x1e00000000000000000000".to_string(),
+                id: "TRK-1".to_string(),
+                max_speed: 90,
+                weight: 7.5,
+                commercial: true,
+            },
+            Vehicle {
+                iid: "0x1e00000000000000000001".to_string(),
+                id: "VAN-2".to_string(),
+                max_speed: 120,
+                weight: 2.0,
+                commercial: false,
+            },
+        ]
+    );
+}
+
+#[test]
+fn decodes_fetch_documents_and_reduce_values() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Doc {
+        name: String,
+    }
+    let docs = json!({ "ok": {
+        "answerType": "conceptDocuments",
+        "answers": [{ "data": { "name": "Alice" }, "involvedBlocks": [0] }]
+    }});
+    assert_eq!(
+        decode_rows::<Doc>(&docs).unwrap(),
+        vec![Doc {
+            name: "Alice".to_string()
+        }]
+    );
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Count {
+        count: i64,
+    }
+    let counts = json!({ "ok": {
+        "answerType": "conceptRows",
+        "answers": [{ "data": { "count": { "kind": "value", "value": "42" } }, "involvedBlocks": [0] }]
+    }});
+    assert_eq!(
+        decode_rows::<Count>(&counts).unwrap(),
+        vec![Count { count: 42 }]
+    );
+}
+
+#[test]
+fn reports_rows_that_do_not_fit() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Missing {
+        colour: String,
+    }
+    let err = decode_rows::<Missing>(&vehicles()).unwrap_err();
+    assert!(err.to_string().contains("answer 0"), "{err}");
+}
+
+#[test]
+fn decimals_decode_as_exact_strings() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Price {
+        amount: String,
+    }
+    let prices = json!({ "ok": {
+        "answerType": "conceptRows",
+        "answers": [{
+            "data": { "amount": attribute("amount", "12345678901234567.89dec", "decimal") },
+            "involvedBlocks": [0]
+        }]
+    }});
+    assert_eq!(
+        decode_rows::<Price>(&prices).unwrap(),
+        vec![Price {
+            amount: "12345678901234567.89".to_string()
+        }]
+    );
+}
@@ -0,0 +1,65 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use dog_typedb::AnswerStream;
+use futures::channel::mpsc::unbounded;
+use futures::StreamExt;
+use serde_json::json;
+
+/// Stands in for the transaction a stream keeps open.
+struct FakeTransaction {
+    closed: Arc<AtomicBool>,
+}
+
+impl Drop for FakeTransaction {
+    fn drop(&mut self) {
+        self.closed.store(true, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test]
+async fn answers_are_consumed_as_they_are_produced() {
+    let closed = Arc::new(AtomicBool::new(false));
+    let (tx, rx) = unbounded();
+    let mut answers = AnswerStream::new(
+        rx,
+        FakeTransaction {
+            closed: Arc::clone(&closed),
+        },
+    );
+
+    // Each answer is read before the next one exists.
+    for n in 0..3 {
+        tx.unbounded_send(Ok(json!({ "n": n }))).unwrap();
+        let answer = answers.next().await.unwrap().unwrap();
+        assert_eq!(answer, json!({ "n": n }));
+        assert!(!closed.load(Ordering::SeqCst));
+    }
+
+    drop(tx);
+    assert!(answers.next().await.is_none());
+    // Exhausted but still alive: the transaction is only released on drop.
+    assert!(!closed.load(Ordering::SeqCst));
+    drop(answers);
+    assert!(closed.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn dropping_a_partly_read_stream_releases_the_transaction() {
+    let closed = Arc::new(AtomicBool::new(false));
+    let (tx, rx) = unbounded();
+    for n in 0..100 {
+        tx.unbounded_send(Ok(json!({ "n": n }))).unwrap();
+    }
+    let mut answers = AnswerStream::new(
+        rx,
+        FakeTransaction {
+            closed: Arc::clone(&closed),
+        },
+    );
+
+    assert_eq!(answers.next().await.unwrap().unwrap(), json!({ "n": 0 }));
+    drop(answers);
+
+    assert!(closed.load(Ordering::SeqCst));
+}
@@ -0,0 +1,93 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use anyhow::anyhow;
+use dog_typedb::{is_transient_write_error, retry_write, WriteConflict, WriteRetryPolicy};
+use tokio::time::Duration;
+
+fn policy() -> WriteRetryPolicy {
+    WriteRetryPolicy {
+        max_attempts: 3,
+        initial_backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(5),
+    }
+}
+
+fn conflict() -> anyhow::Error {
+    WriteConflict::new("Failed to commit write transaction: [ISO1] Isolation conflict").into()
+}
+
+#[tokio::test]
+async fn conflict_on_first_attempt_succeeds_on_retry() {
+    let attempts = AtomicU32::new(0);
+
+    let result = retry_write(&policy(), || async {
+        match attempts.fetch_add(1, Ordering::SeqCst) {
+            0 => Err(conflict()),
+            _ => Ok("committed"),
+        }
+    })
+    .await;
+
+    assert_eq!(result.unwrap(), "committed");
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn schema_violations_are_not_retried() {
+    let attempts = AtomicU32::new(0);
+
+    let result: anyhow::Result<()> = retry_write(&policy(), || async {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        Err(anyhow!(
+            "Failed to execute write query: attribute type 'age' is not owned by 'person'"
+        ))
+    })
+    .await;
+
+    assert!(result.is_err());
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn conflict_wording_in_other_errors_is_not_retried() {
+    let attempts = AtomicU32::new(0);
+
+    let result: anyhow::Result<()> = retry_write(&policy(), || async {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        Err(anyhow!(
+            "Failed to execute write query: key constraint conflict on concurrent-user"
+        ))
+    })
+    .await;
+
+    assert!(result.is_err());
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn retries_stop_after_max_attempts() {
+    let attempts = AtomicU32::new(0);
+
+    let result: anyhow::Result<()> = retry_write(&policy(), || async {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        Err(conflict())
+    })
+    .await;
+
+    let err = result.unwrap_err().context("Saving the post failed");
+    assert!(is_transient_write_error(&err));
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn backoff_doubles_up_to_the_cap() {
+    let policy = WriteRetryPolicy {
+        max_attempts: 10,
+        initial_backoff: Duration::from_millis(50),
+        max_backoff: Duration::from_millis(300),
+    };
+    assert_eq!(policy.backoff(1), Duration::from_millis(50));
+    assert_eq!(policy.backoff(2), Duration::from_millis(100));
+    assert_eq!(policy.backoff(3), Duration::from_millis(200));
+    assert_eq!(policy.backoff(4), Duration::from_millis(300));
+}